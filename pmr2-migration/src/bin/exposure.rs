@@ -443,7 +443,7 @@ async fn process_wizard_export(
             .into_iter()
             .filter_map(|(id, prompt, answer)| {
                 if let Some(answer) = answer {
-                    Some((id, answer))
+                    Some((id, answer.into()))
                 } else {
                     eprintln!("{prompt} failed to be answered");
                     None
@@ -0,0 +1,14 @@
+use pmrcore::ac::agent::Agent;
+
+/// The agent to enforce the policy for, along with the scopes that
+/// further limit the actions that the agent may be permitted, such as
+/// when the agent is acting through a personal access token.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScopedAgent {
+    agent: Agent,
+    // the actions that may be permitted, where `None` leaves the agent
+    // unrestricted.
+    scopes: Option<Vec<String>>,
+}
+
+mod impls;
@@ -0,0 +1,66 @@
+use pmrcore::ac::agent::Agent;
+
+use crate::{
+    session::Session,
+    user::User,
+};
+use super::ScopedAgent;
+
+impl ScopedAgent {
+    pub fn new(agent: impl Into<Agent>, scopes: Option<Vec<String>>) -> Self {
+        Self {
+            agent: agent.into(),
+            scopes,
+        }
+    }
+
+    pub fn agent(&self) -> &Agent {
+        &self.agent
+    }
+
+    pub fn scopes(&self) -> Option<&[String]> {
+        self.scopes.as_deref()
+    }
+
+    /// Whether the action is within the scopes of this agent; this does
+    /// not consider the policy in any way.
+    pub fn in_scope(&self, action: &str) -> bool {
+        self.scopes
+            .as_ref()
+            .map(|scopes| scopes.iter().any(|scope| scope == action))
+            .unwrap_or(true)
+    }
+}
+
+impl From<Agent> for ScopedAgent {
+    fn from(agent: Agent) -> Self {
+        Self::new(agent, None)
+    }
+}
+
+impl From<&User> for ScopedAgent {
+    fn from(user: &User) -> Self {
+        Self::new(user, None)
+    }
+}
+
+impl From<User> for ScopedAgent {
+    fn from(user: User) -> Self {
+        Self::new(user, None)
+    }
+}
+
+impl From<&Session> for ScopedAgent {
+    fn from(session: &Session) -> Self {
+        Self::new(
+            session.user(),
+            session.user_token().map(|token| token.scopes.clone()),
+        )
+    }
+}
+
+impl From<Session> for ScopedAgent {
+    fn from(session: Session) -> Self {
+        (&session).into()
+    }
+}
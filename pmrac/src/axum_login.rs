@@ -7,6 +7,8 @@ pub struct Credentials {
 pub enum Authorization {
     LoginPassword(String, String),
     Token(String),
+    #[cfg(feature="oidc")]
+    Identity(crate::oidc::Identity),
 }
 
 mod impls;
@@ -28,7 +28,21 @@ impl AuthnBackend for Platform {
                 ).await?;
                 Ok(Some(session))
             }
-            _ => unimplemented!(),
+            Authorization::Token(token) => {
+                let session = self.authenticate_user_token_login(
+                    &token,
+                    credentials.origin,
+                ).await?;
+                Ok(Some(session))
+            }
+            #[cfg(feature="oidc")]
+            Authorization::Identity(identity) => {
+                let session = self.authenticate_user_identity_login(
+                    &identity,
+                    credentials.origin,
+                ).await?;
+                Ok(Some(session))
+            }
         }
     }
 
@@ -41,6 +41,11 @@ enum Commands {
         cmd: RoleCmd,
     },
     #[command(arg_required_else_help = true)]
+    Group {
+        #[command(subcommand)]
+        cmd: GroupCmd,
+    },
+    #[command(arg_required_else_help = true)]
     Resource {
         resource: String,
         #[command(subcommand)]
@@ -102,6 +107,47 @@ enum RoleCmd {
     },
 }
 
+#[derive(Debug, Subcommand)]
+enum GroupCmd {
+    #[command(arg_required_else_help = true)]
+    Create {
+        name: String,
+    },
+    /// Add the user to the group
+    #[command(arg_required_else_help = true)]
+    Add {
+        name: String,
+        login: String,
+    },
+    /// Remove the user from the group
+    #[command(arg_required_else_help = true)]
+    Remove {
+        name: String,
+        login: String,
+    },
+    #[command(arg_required_else_help = true)]
+    Status {
+        name: String,
+    },
+    List,
+}
+
+#[derive(Debug, Subcommand)]
+enum GroupRoleCmd {
+    #[command(arg_required_else_help = true)]
+    Grant {
+        group: String,
+        #[arg(value_enum)]
+        role: Role,
+    },
+    #[command(arg_required_else_help = true)]
+    Revoke {
+        group: String,
+        #[arg(value_enum)]
+        role: Role,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 enum ResourceCmd {
     #[command(arg_required_else_help = true)]
@@ -109,6 +155,11 @@ enum ResourceCmd {
         #[command(subcommand)]
         cmd: RoleCmd,
     },
+    #[command(arg_required_else_help = true)]
+    GroupRole {
+        #[command(subcommand)]
+        cmd: GroupRoleCmd,
+    },
     // use workflow transition instead whenever that gets implemented.
     // for now just provide a way to set the state directly
     #[command(arg_required_else_help = true)]
@@ -179,6 +230,9 @@ async fn main() -> anyhow::Result<()> {
         Commands::Role { cmd } => {
             parse_role(&platform, cmd).await?;
         },
+        Commands::Group { cmd } => {
+            parse_group(&platform, cmd).await?;
+        },
         Commands::Resource { resource, cmd } => {
             parse_resource(&platform, resource, cmd).await?;
         },
@@ -217,8 +271,60 @@ async fn parse_user<'p>(
             println!("status: {password_status}");
 
             // could have not destructured it but getting it done this way for now...
-            let agent = Agent::User(User { id, name, created_ts });
-            let res_grants = platform.get_res_grants_for_agent(&agent).await?;
+            let user = User { id, name, created_ts };
+            let res_grants = platform.get_res_grants_for_agent(&user.clone().into()).await?;
+            for (res, roles) in res_grants.into_iter() {
+                let role = roles.into_iter()
+                    .map(<&'static str>::from)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("role(s) granted for '{res}': [{role}]");
+            }
+            let groups = platform.get_groups_for_user(user).await?;
+            for group in groups.into_iter() {
+                println!("member of group '{}'", group.name);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn parse_group<'p>(
+    platform: &'p Platform,
+    arg: GroupCmd,
+) -> anyhow::Result<()> {
+    match arg {
+        GroupCmd::Create { name } => {
+            let group = platform.create_group(&name).await?;
+            let id = group.id();
+            println!("group {name:?} created with id {id}");
+        }
+        GroupCmd::Add { name, login } => {
+            let group = get_group(platform, &name).await?;
+            let (user, _) = platform.login_status(&login).await?;
+            if group.add_member(user).await? {
+                println!("{login} added to group {name}");
+            } else {
+                println!("{login} is already a member of group {name}");
+            }
+        }
+        GroupCmd::Remove { name, login } => {
+            let group = get_group(platform, &name).await?;
+            let (user, _) = platform.login_status(&login).await?;
+            if group.remove_member(user).await? {
+                println!("{login} removed from group {name}");
+            } else {
+                println!("{login} is not a member of group {name}");
+            }
+        }
+        GroupCmd::Status { name } => {
+            let group = get_group(platform, &name).await?;
+            println!("id: {}", group.id());
+            println!("name: {}", group.name());
+            for user in group.members().await?.into_iter() {
+                println!("member: {}", user.name);
+            }
+            let res_grants = platform.get_res_grants_for_agent(&group.into()).await?;
             for (res, roles) in res_grants.into_iter() {
                 let role = roles.into_iter()
                     .map(<&'static str>::from)
@@ -227,10 +333,23 @@ async fn parse_user<'p>(
                 println!("role(s) granted for '{res}': [{role}]");
             }
         }
+        GroupCmd::List => {
+            for group in platform.get_groups().await?.into_iter() {
+                println!("{}", group.name);
+            }
+        }
     }
     Ok(())
 }
 
+async fn get_group<'p>(
+    platform: &'p Platform,
+    name: &str,
+) -> anyhow::Result<pmrac::group::Group> {
+    platform.get_group_by_name(name).await?
+        .ok_or_else(|| anyhow::anyhow!("group {name:?} does not exist"))
+}
+
 async fn parse_password<'p>(
     platform: &'p Platform,
     login: String,
@@ -294,6 +413,9 @@ async fn parse_resource<'p>(
         ResourceCmd::Role { cmd } => {
             parse_resource_role(&platform, resource, cmd).await?
         }
+        ResourceCmd::GroupRole { cmd } => {
+            parse_resource_group_role(&platform, resource, cmd).await?
+        }
         ResourceCmd::State { state } => {
             platform.set_wf_state_for_res(&resource, state).await?;
             println!("workflow state for resource {resource} set to {state}");
@@ -340,6 +462,32 @@ async fn parse_resource_role<'p>(
     Ok(())
 }
 
+async fn parse_resource_group_role<'p>(
+    platform: &'p Platform,
+    resource: String,
+    arg: GroupRoleCmd,
+) -> anyhow::Result<()> {
+    match arg {
+        GroupRoleCmd::Grant { group, role } => {
+            let agent = get_group(platform, &group).await?;
+            if platform.res_grant_role_to_agent(&resource, agent, role).await? {
+                println!("role {role} granted to group {group} for resource {resource}");
+            } else {
+                println!("role {role} was already granted to group {group} for resource {resource}");
+            }
+        }
+        GroupRoleCmd::Revoke { group, role } => {
+            let agent = get_group(platform, &group).await?;
+            if platform.res_revoke_role_from_agent(&resource, agent, role).await? {
+                println!("role {role} revoked from group {group} for resource {resource}");
+            } else {
+                println!("group {group} has no role {role} for resource {resource}");
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn parse_policy<'p>(
     platform: &'p Platform,
     arg: PolicyCmd,
@@ -13,6 +13,16 @@ pub enum Error {
     Misconfiguration,
     #[error(transparent)]
     Rbac(#[from] pmrrbac::error::Error),
+    #[error(transparent)]
+    Registration(#[from] RegistrationError),
+    #[error(transparent)]
+    Mail(#[from] MailError),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+    #[error(transparent)]
+    Token(#[from] TokenError),
+    #[error(transparent)]
+    Group(#[from] GroupError),
 }
 
 #[non_exhaustive]
@@ -39,4 +49,80 @@ pub enum AuthenticationError {
     Restricted,
     #[error("UnknownUser")]
     UnknownUser,
+    #[error("Invalid Token")]
+    InvalidToken,
+    #[error("Expired Token")]
+    ExpiredToken,
+}
+
+/// The errors from the flows driven by the tokens sent by mail, i.e. the
+/// registration, the binding of email addresses and the password reset.
+#[non_exhaustive]
+#[derive(Debug, Error, PartialEq)]
+pub enum RegistrationError {
+    #[error("Invalid Email")]
+    InvalidEmail,
+    #[error("Invalid Name")]
+    InvalidName,
+    #[error("Invalid Token")]
+    InvalidToken,
+    #[error("Email Unavailable")]
+    EmailUnavailable,
+    #[error("Name Unavailable")]
+    NameUnavailable,
+    #[error("Rate Limited")]
+    RateLimited,
+    #[error("Registration Unavailable")]
+    Unavailable,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum MailError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Unsupported Mail Transport: {0}")]
+    Unsupported(String),
+}
+
+/// The errors from authenticating with and linking the identities from
+/// external identity providers.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum IdentityError {
+    #[error("Unknown Identity Provider: {0}")]
+    UnknownProvider(String),
+    #[error("Invalid State")]
+    InvalidState,
+    #[error("Invalid Provider Response: {0}")]
+    InvalidResponse(String),
+    #[error("Unlinked Identity")]
+    Unlinked,
+    #[error("Identity Unavailable")]
+    Unavailable,
+    #[cfg(feature="oidc")]
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
+
+/// The errors from creating the personal access tokens.
+#[non_exhaustive]
+#[derive(Debug, Error, PartialEq)]
+pub enum TokenError {
+    #[error("Invalid Name")]
+    InvalidName,
+    #[error("Invalid Scopes")]
+    InvalidScopes,
+    #[error("Invalid Lifetime")]
+    InvalidLifetime,
+}
+
+/// The errors from managing the groups.
+#[non_exhaustive]
+#[derive(Debug, Error, PartialEq)]
+pub enum GroupError {
+    #[error("Invalid Name")]
+    InvalidName,
+    #[error("Name Unavailable")]
+    NameUnavailable,
 }
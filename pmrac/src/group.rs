@@ -0,0 +1,11 @@
+use pmrcore::ac::group;
+
+use crate::Platform;
+
+#[derive(Clone)]
+pub struct Group {
+    platform: Platform,
+    group: group::Group,
+}
+
+mod impls;
@@ -0,0 +1,85 @@
+use pmrcore::ac::{
+    agent::Agent,
+    group,
+    user,
+};
+
+use crate::{
+    error::Error,
+    Platform,
+};
+use super::Group;
+
+impl Group {
+    pub(crate) fn new(
+        platform: Platform,
+        group: group::Group,
+    ) -> Self {
+        Self {
+            platform,
+            group,
+        }
+    }
+
+    pub fn id(&self) -> i64 {
+        self.group.id
+    }
+
+    pub fn name(&self) -> &str {
+        self.group.name.as_ref()
+    }
+
+    pub fn clone_inner(&self) -> group::Group {
+        self.group.clone()
+    }
+
+    pub fn into_inner(self) -> group::Group {
+        self.group
+    }
+
+    pub async fn members(&self) -> Result<Vec<user::User>, Error> {
+        self.platform.get_group_members(self).await
+    }
+
+    /// Add the user to the group.  Returns false if the user is already
+    /// a member of the group.
+    pub async fn add_member(
+        &self,
+        user: impl Into<user::User>,
+    ) -> Result<bool, Error> {
+        self.platform.add_user_to_group(self, user).await
+    }
+
+    /// Remove the user from the group.  Returns false if the user is not
+    /// a member of the group.
+    pub async fn remove_member(
+        &self,
+        user: impl Into<user::User>,
+    ) -> Result<bool, Error> {
+        self.platform.remove_user_from_group(self, user).await
+    }
+}
+
+impl From<&Group> for Agent {
+    fn from(group: &Group) -> Self {
+        group.clone_inner().into()
+    }
+}
+
+impl From<Group> for Agent {
+    fn from(group: Group) -> Self {
+        group.into_inner().into()
+    }
+}
+
+impl From<&Group> for group::Group {
+    fn from(group: &Group) -> Self {
+        group.clone_inner()
+    }
+}
+
+impl From<Group> for group::Group {
+    fn from(group: Group) -> Self {
+        group.into_inner()
+    }
+}
@@ -1,6 +1,11 @@
+pub mod agent;
 #[cfg(feature="axum-login")]
 pub mod axum_login;
 pub mod error;
+pub mod group;
+pub mod mail;
+#[cfg(feature="oidc")]
+pub mod oidc;
 pub mod password;
 pub mod platform;
 pub mod session;
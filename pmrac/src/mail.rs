@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use crate::error::MailError;
+
+/// A plain text message to be sent to a single email address.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// The transport through which the platform sends out mail, such as the
+/// tokens for the verification of email addresses.
+#[async_trait]
+pub trait MailTransport: Send + Sync {
+    async fn send(&self, mail: &Mail) -> Result<(), MailError>;
+}
+
+/// Appends every mail to the file at the path, which is created if it
+/// doesn't already exist.
+pub struct FileTransport {
+    path: PathBuf,
+}
+
+/// Writes every mail to stdout.
+pub struct StdoutTransport;
+
+mod impls;
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::error::MailError;
+use super::*;
+
+impl fmt::Display for Mail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "To: {}\nSubject: {}\n\n{}\n",
+            self.to,
+            self.subject,
+            self.body,
+        )
+    }
+}
+
+impl FileTransport {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl MailTransport for FileTransport {
+    async fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        // this is meant for testing and development, so the write is
+        // simply done without regard to the runtime.
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(format!("{mail}\n").as_bytes())?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MailTransport for StdoutTransport {
+    async fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        println!("{mail}");
+        Ok(())
+    }
+}
+
+/// The transport may be specified as `stdout` or `file:<path>`.
+impl FromStr for Box<dyn MailTransport> {
+    type Err = MailError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "stdout" => Ok(Box::new(StdoutTransport)),
+            Some(("file", path)) if !path.is_empty() =>
+                Ok(Box::new(FileTransport::new(path))),
+            _ => Err(MailError::Unsupported(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+
+/// The configuration of an OpenID Connect provider, where the `name` is
+/// the identifier for the provider that is recorded with the identities
+/// linked to the users.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProviderConfig {
+    pub name: String,
+    /// The name of the provider as presented to the users.
+    pub title: String,
+    /// The issuer identifier, from which the provider metadata is
+    /// discovered.
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    /// The URL that the provider redirects back to with the authorization
+    /// code.
+    pub redirect_url: String,
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+    /// Whether a new user may be registered for an identity from this
+    /// provider that isn't linked to any existing user.
+    #[serde(default)]
+    pub register: bool,
+}
+
+/// The subset of the provider metadata used for the authorization code
+/// flow.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct Metadata {
+    pub(crate) issuer: String,
+    pub(crate) authorization_endpoint: String,
+    pub(crate) token_endpoint: String,
+}
+
+pub(crate) struct Provider {
+    pub(crate) config: ProviderConfig,
+    pub(crate) metadata: OnceCell<Metadata>,
+    pub(crate) client: reqwest::Client,
+}
+
+/// An authorization that was started with the provider, which must be
+/// kept by the client (e.g. in its session) until the provider redirects
+/// back with the authorization code.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PendingAuthorization {
+    pub provider: String,
+    pub state: String,
+    pub nonce: String,
+    pub pkce_verifier: String,
+}
+
+/// The identity of an end-user as asserted by the provider.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Identity {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub preferred_username: Option<String>,
+}
+
+fn default_scopes() -> Vec<String> {
+    ["openid", "email", "profile"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+mod impls;
@@ -0,0 +1,189 @@
+use base64::{
+    Engine as _,
+    engine::general_purpose::URL_SAFE_NO_PAD,
+};
+use chrono::Utc;
+use reqwest::Url;
+use serde::Deserialize;
+use sha2::{
+    Digest,
+    Sha256,
+};
+
+use crate::error::IdentityError;
+use super::*;
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    iss: String,
+    sub: String,
+    aud: Audience,
+    exp: i64,
+    azp: Option<String>,
+    nonce: Option<String>,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    preferred_username: Option<String>,
+}
+
+impl Audience {
+    fn contains(&self, value: &str) -> bool {
+        match self {
+            Self::One(aud) => aud == value,
+            Self::Many(auds) => auds.iter().any(|aud| aud == value),
+        }
+    }
+
+    fn is_multiple(&self) -> bool {
+        matches!(self, Self::Many(auds) if auds.len() > 1)
+    }
+}
+
+impl Provider {
+    pub(crate) fn new(config: ProviderConfig) -> Self {
+        Self {
+            config,
+            metadata: OnceCell::new(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// The metadata is discovered on first use, such that the platform
+    /// may be built while the provider is unreachable.
+    async fn metadata(&self) -> Result<&Metadata, IdentityError> {
+        self.metadata.get_or_try_init(|| async {
+            let url = format!(
+                "{}/.well-known/openid-configuration",
+                self.config.issuer.trim_end_matches('/'),
+            );
+            let metadata: Metadata = self.client.get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            if metadata.issuer != self.config.issuer {
+                Err(IdentityError::InvalidResponse(format!(
+                    "issuer {} does not match the configured issuer",
+                    metadata.issuer,
+                )))?;
+            }
+            Ok(metadata)
+        }).await
+    }
+
+    /// Returns the URL to the authorization endpoint for the end-user to
+    /// be sent to, along with the pending authorization to be kept.
+    pub(crate) async fn authorize(
+        &self,
+        state: String,
+        nonce: String,
+        pkce_verifier: String,
+    ) -> Result<(String, PendingAuthorization), IdentityError> {
+        let metadata = self.metadata().await?;
+        let url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", &self.config.client_id),
+                ("redirect_uri", &self.config.redirect_url),
+                ("scope", &self.config.scopes.join(" ")),
+                ("state", &state),
+                ("nonce", &nonce),
+                ("code_challenge", &pkce_challenge(&pkce_verifier)),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+            .map_err(|e| IdentityError::InvalidResponse(e.to_string()))?;
+        Ok((url.into(), PendingAuthorization {
+            provider: self.config.name.clone(),
+            state,
+            nonce,
+            pkce_verifier,
+        }))
+    }
+
+    /// Exchange the authorization code for the ID token at the token
+    /// endpoint, and returns the identity within once validated.
+    ///
+    /// As the ID token is received directly from the token endpoint, the
+    /// TLS server validation is used in lieu of checking its signature as
+    /// permitted by OpenID Connect Core 1.0 section 3.1.3.7.
+    pub(crate) async fn identify(
+        &self,
+        pending: &PendingAuthorization,
+        code: &str,
+    ) -> Result<Identity, IdentityError> {
+        let metadata = self.metadata().await?;
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.config.redirect_url),
+            ("client_id", &self.config.client_id),
+            ("code_verifier", &pending.pkce_verifier),
+        ];
+        if let Some(client_secret) = self.config.client_secret.as_deref() {
+            form.push(("client_secret", client_secret));
+        }
+        let response: TokenResponse = self.client.post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let claims = decode_id_token(&response.id_token)?;
+
+        if claims.iss != metadata.issuer {
+            Err(IdentityError::InvalidResponse("issuer mismatched".to_string()))?;
+        }
+        if !claims.aud.contains(&self.config.client_id) {
+            Err(IdentityError::InvalidResponse("audience mismatched".to_string()))?;
+        }
+        if claims.aud.is_multiple() && claims.azp.as_deref() != Some(&self.config.client_id) {
+            Err(IdentityError::InvalidResponse("authorized party mismatched".to_string()))?;
+        }
+        if claims.exp <= Utc::now().timestamp() {
+            Err(IdentityError::InvalidResponse("token expired".to_string()))?;
+        }
+        if claims.nonce.as_deref() != Some(&pending.nonce) {
+            Err(IdentityError::InvalidResponse("nonce mismatched".to_string()))?;
+        }
+
+        Ok(Identity {
+            provider: self.config.name.clone(),
+            subject: claims.sub,
+            email: claims.email,
+            email_verified: claims.email_verified,
+            preferred_username: claims.preferred_username,
+        })
+    }
+}
+
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn decode_id_token(id_token: &str) -> Result<Claims, IdentityError> {
+    let payload = id_token.split('.')
+        .nth(1)
+        .ok_or(IdentityError::InvalidResponse("malformed id_token".to_string()))?;
+    let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('='))
+        .map_err(|e| IdentityError::InvalidResponse(e.to_string()))?;
+    serde_json::from_slice(&payload)
+        .map_err(|e| IdentityError::InvalidResponse(e.to_string()))
+}
+
@@ -1,10 +1,17 @@
 use pmrcore::{
-    ac::session::SessionFactory,
+    ac::session::{
+        SessionFactory,
+        SessionTokenFactory,
+    },
     platform::ACPlatform,
 };
 use pmrrbac::Builder as PmrRbacBuilder;
 use std::sync::Arc;
 
+use crate::mail::MailTransport;
+#[cfg(feature="oidc")]
+use crate::oidc;
+
 #[derive(Default)]
 pub struct Builder {
     // platform
@@ -13,6 +20,27 @@ pub struct Builder {
     password_autopurge: bool,
     pmrrbac_builder: PmrRbacBuilder,
     session_factory: SessionFactory,
+    // email binding is unavailable without a mail transport
+    mail_transport: Option<Box<dyn MailTransport>>,
+    // the token of the bind request is appended to this to form the link
+    email_bindreq_url: Option<String>,
+    // the number of seconds a bind request remains valid
+    email_bindreq_lifetime: i64,
+    // the token of the password reset request is appended to this to
+    // form the link
+    password_resetreq_url: Option<String>,
+    // the number of seconds a password reset request remains valid
+    password_resetreq_lifetime: i64,
+    // the maximum number of bind requests, and separately the password
+    // reset requests, an origin may make within the window, in seconds
+    mail_rate_limit: i64,
+    mail_rate_window: i64,
+    token_factory: SessionTokenFactory,
+    // the maximum number of seconds a personal access token may remain
+    // valid for
+    user_token_max_lifetime: i64,
+    #[cfg(feature="oidc")]
+    oidc_providers: Vec<oidc::ProviderConfig>,
 }
 
 struct PlatformInner {
@@ -20,6 +48,17 @@ struct PlatformInner {
     password_autopurge: bool,
     pmrrbac_builder: PmrRbacBuilder,
     session_factory: SessionFactory,
+    mail_transport: Option<Box<dyn MailTransport>>,
+    email_bindreq_url: Option<String>,
+    email_bindreq_lifetime: i64,
+    password_resetreq_url: Option<String>,
+    password_resetreq_lifetime: i64,
+    mail_rate_limit: i64,
+    mail_rate_window: i64,
+    token_factory: SessionTokenFactory,
+    user_token_max_lifetime: i64,
+    #[cfg(feature="oidc")]
+    oidc_providers: Vec<oidc::Provider>,
 }
 
 #[derive(Clone)]
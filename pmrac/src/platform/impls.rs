@@ -3,13 +3,22 @@ use pmrcore::{
     ac::{
         agent::Agent,
         genpolicy::Policy,
+        group,
         role::Role,
         session::{
             self,
             SessionFactory,
             SessionToken,
+            SessionTokenFactory,
+        },
+        user::{
+            self,
+            UserEmail,
+            UserEmailBindReq,
+            UserIdentity,
+            UserPasswordResetReq,
+            UserToken,
         },
-        user,
         workflow::State,
     },
     platform::ACPlatform
@@ -18,11 +27,20 @@ use pmrrbac::Builder as PmrRbacBuilder;
 use std::sync::Arc;
 
 use crate::{
+    agent::ScopedAgent,
     error::{
         AuthenticationError,
         Error,
+        GroupError,
         PasswordError,
+        RegistrationError,
+        TokenError,
+    },
+    mail::{
+        Mail,
+        MailTransport,
     },
+    group::Group,
     user::User,
     password::{
         Password,
@@ -30,13 +48,41 @@ use crate::{
     },
     session::Session,
 };
+#[cfg(feature="oidc")]
+use crate::{
+    error::IdentityError,
+    oidc::{
+        Identity,
+        PendingAuthorization,
+        Provider,
+        ProviderConfig,
+    },
+};
 
 use super::*;
 
+/// The number of seconds a bind request remains valid by default.
+pub const EMAIL_BINDREQ_LIFETIME: i64 = 86400;
+/// The number of seconds a password reset request remains valid by
+/// default.
+pub const PASSWORD_RESETREQ_LIFETIME: i64 = 3600;
+/// The number of bind requests, and separately the password reset
+/// requests, an origin may make within the window by default.
+pub const MAIL_RATE_LIMIT: i64 = 5;
+pub const MAIL_RATE_WINDOW: i64 = 3600;
+/// The maximum number of seconds a personal access token may remain
+/// valid for by default.
+pub const USER_TOKEN_MAX_LIFETIME: i64 = 31536000;
+
 impl Builder {
     pub fn new() -> Self {
         Self {
             pmrrbac_builder: PmrRbacBuilder::new(),
+            email_bindreq_lifetime: EMAIL_BINDREQ_LIFETIME,
+            password_resetreq_lifetime: PASSWORD_RESETREQ_LIFETIME,
+            mail_rate_limit: MAIL_RATE_LIMIT,
+            mail_rate_window: MAIL_RATE_WINDOW,
+            user_token_max_lifetime: USER_TOKEN_MAX_LIFETIME,
             .. Default::default()
         }
     }
@@ -66,6 +112,61 @@ impl Builder {
         self
     }
 
+    pub fn mail_transport(mut self, val: impl MailTransport + 'static) -> Self {
+        self.mail_transport = Some(Box::new(val));
+        self
+    }
+
+    pub fn boxed_mail_transport(mut self, val: Box<dyn MailTransport>) -> Self {
+        self.mail_transport = Some(val);
+        self
+    }
+
+    pub fn email_bindreq_url(mut self, val: String) -> Self {
+        self.email_bindreq_url = Some(val);
+        self
+    }
+
+    pub fn email_bindreq_lifetime(mut self, val: i64) -> Self {
+        self.email_bindreq_lifetime = val;
+        self
+    }
+
+    pub fn password_resetreq_url(mut self, val: String) -> Self {
+        self.password_resetreq_url = Some(val);
+        self
+    }
+
+    pub fn password_resetreq_lifetime(mut self, val: i64) -> Self {
+        self.password_resetreq_lifetime = val;
+        self
+    }
+
+    /// Limit the number of bind requests, and separately the password
+    /// reset requests, that any origin may make to `limit` within the
+    /// `window` in seconds.
+    pub fn mail_rate_limit(mut self, limit: i64, window: i64) -> Self {
+        self.mail_rate_limit = limit;
+        self.mail_rate_window = window;
+        self
+    }
+
+    pub fn token_factory(mut self, val: SessionTokenFactory) -> Self {
+        self.token_factory = val;
+        self
+    }
+
+    pub fn user_token_max_lifetime(mut self, val: i64) -> Self {
+        self.user_token_max_lifetime = val;
+        self
+    }
+
+    #[cfg(feature="oidc")]
+    pub fn oidc_provider(mut self, val: ProviderConfig) -> Self {
+        self.oidc_providers.push(val);
+        self
+    }
+
     pub fn build(self) -> Platform {
         Platform(Arc::new(PlatformInner {
             ac_platform: self.ac_platform
@@ -73,6 +174,20 @@ impl Builder {
             password_autopurge: self.password_autopurge,
             pmrrbac_builder: self.pmrrbac_builder,
             session_factory: self.session_factory,
+            mail_transport: self.mail_transport,
+            email_bindreq_url: self.email_bindreq_url,
+            email_bindreq_lifetime: self.email_bindreq_lifetime,
+            password_resetreq_url: self.password_resetreq_url,
+            password_resetreq_lifetime: self.password_resetreq_lifetime,
+            mail_rate_limit: self.mail_rate_limit,
+            mail_rate_window: self.mail_rate_window,
+            token_factory: self.token_factory,
+            user_token_max_lifetime: self.user_token_max_lifetime,
+            #[cfg(feature="oidc")]
+            oidc_providers: self.oidc_providers
+                .into_iter()
+                .map(Provider::new)
+                .collect(),
         }))
     }
 }
@@ -196,6 +311,659 @@ impl Platform {
     }
 }
 
+// Email binding and registration
+
+impl Platform {
+    /// Request the registration of a new user for the email address on
+    /// behalf of the origin.  The token required to complete the
+    /// registration is sent to the email address; to avoid disclosing
+    /// whether the address is already bound to some user, this succeeds
+    /// regardless and the owner of the address is notified instead.
+    pub async fn request_registration(
+        &self,
+        email: &str,
+        origin: &str,
+    ) -> Result<(), Error> {
+        let email = email.trim();
+        let (token, mail_transport) = self.new_email_bindreq(email, None, origin).await?;
+        let body = if self.0.ac_platform.get_user_by_email(email).await?.is_some() {
+            format!(
+                "A request was made from {origin} to register a new account with \
+                this email address, but an account is already registered with it.\n\n\
+                If this request was not made by you, this message may be ignored."
+            )
+        } else {
+            format!(
+                "A request was made from {origin} to register a new account with \
+                this email address.  To complete the registration, visit:\n\n\
+                {}\n\n\
+                If this request was not made by you, the link may be visited to \
+                decline it.",
+                self.email_bindreq_link(&token),
+            )
+        };
+        mail_transport.send(&Mail {
+            to: email.to_string(),
+            subject: "Account registration".to_string(),
+            body,
+        }).await?;
+        Ok(())
+    }
+
+    /// Request the email address be bound to the user on behalf of the
+    /// origin.  The token required to complete the binding is sent to
+    /// the email address.
+    pub async fn request_user_email_bind(
+        &self,
+        user_id: i64,
+        email: &str,
+        origin: &str,
+    ) -> Result<(), Error> {
+        let email = email.trim();
+        let user = self.0.ac_platform.get_user_by_id(user_id).await?
+            .ok_or(AuthenticationError::UnknownUser)?;
+        let (token, mail_transport) = self.new_email_bindreq(email, Some(user_id), origin).await?;
+        mail_transport.send(&Mail {
+            to: email.to_string(),
+            subject: "Email address verification".to_string(),
+            body: format!(
+                "A request was made from {origin} to add this email address to the \
+                account {}.  To verify this address, visit:\n\n\
+                {}\n\n\
+                If this request was not made by you, the link may be visited to \
+                decline it.",
+                user.name,
+                self.email_bindreq_link(&token),
+            ),
+        }).await?;
+        Ok(())
+    }
+
+    async fn new_email_bindreq(
+        &self,
+        email: &str,
+        origin_user_id: Option<i64>,
+        origin: &str,
+    ) -> Result<(String, &dyn MailTransport), Error> {
+        let mail_transport = self.0.mail_transport
+            .as_deref()
+            .ok_or(RegistrationError::Unavailable)?;
+        validate_email(email)?;
+        let since_ts = Utc::now().timestamp() - self.0.mail_rate_window;
+        if self.0.ac_platform.count_user_email_bindreqs_by_origin(
+            origin,
+            since_ts,
+        ).await? >= self.0.mail_rate_limit {
+            log::warn!("rate limited email bind requests from origin {origin}");
+            Err(RegistrationError::RateLimited)?;
+        }
+        let token = self.0.token_factory.create().to_string();
+        self.0.ac_platform.add_user_email_bindreq(
+            email,
+            origin_user_id,
+            origin,
+            &token,
+        ).await?;
+        Ok((token, mail_transport))
+    }
+
+    fn email_bindreq_link(&self, token: &str) -> String {
+        format!("{}{token}", self.0.email_bindreq_url.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the bind request with the token, provided that it may
+    /// still be used.
+    pub async fn get_email_bindreq(
+        &self,
+        token: &str,
+    ) -> Result<UserEmailBindReq, Error> {
+        let since_ts = Utc::now().timestamp() - self.0.email_bindreq_lifetime;
+        Ok(self.0.ac_platform.get_user_email_bindreq(token).await?
+            .filter(|bindreq| !bindreq.rejected && bindreq.created_ts >= since_ts)
+            .ok_or(RegistrationError::InvalidToken)?)
+    }
+
+    /// Complete the registration for the bind request with the token, by
+    /// creating the user with the name and password and then binding the
+    /// email address to it.
+    pub async fn complete_registration(
+        &self,
+        token: &str,
+        name: &str,
+        password: &str,
+        password_confirm: &str,
+    ) -> Result<User, Error> {
+        let bindreq = self.get_email_bindreq(token).await?;
+        if bindreq.origin_user_id.is_some() {
+            Err(RegistrationError::InvalidToken)?;
+        }
+        (password == password_confirm)
+            .then_some(())
+            .ok_or(PasswordError::Mismatched)?;
+        let name = name.trim();
+        validate_name(name)?;
+        if self.0.ac_platform.get_user_by_name(name).await?.is_some() {
+            Err(RegistrationError::NameUnavailable)?;
+        }
+        if self.0.ac_platform.get_user_by_email(&bindreq.email).await?.is_some() {
+            Err(RegistrationError::EmailUnavailable)?;
+        }
+        if !self.0.ac_platform.consume_user_email_bindreq(bindreq.id).await? {
+            Err(RegistrationError::InvalidToken)?;
+        }
+        let user = self.create_user(name).await?;
+        self.new_user_id_password(user.id(), password).await?;
+        self.0.ac_platform.add_user_email(user.id(), &bindreq.email).await?;
+        Ok(user)
+    }
+
+    /// Bind the email address of the bind request with the token to the
+    /// user that made the request.
+    pub async fn verify_user_email_bind(
+        &self,
+        token: &str,
+    ) -> Result<User, Error> {
+        let bindreq = self.get_email_bindreq(token).await?;
+        let user = self.get_user(
+            bindreq.origin_user_id.ok_or(RegistrationError::InvalidToken)?
+        ).await?
+            .ok_or(AuthenticationError::UnknownUser)?;
+        if self.0.ac_platform.get_user_by_email(&bindreq.email).await?.is_some() {
+            Err(RegistrationError::EmailUnavailable)?;
+        }
+        if !self.0.ac_platform.consume_user_email_bindreq(bindreq.id).await? {
+            Err(RegistrationError::InvalidToken)?;
+        }
+        self.0.ac_platform.add_user_email(user.id(), &bindreq.email).await?;
+        Ok(user)
+    }
+
+    /// Reject the bind request with the token, for use by the owner of
+    /// the email address that did not make the request.
+    pub async fn reject_email_bindreq(
+        &self,
+        token: &str,
+    ) -> Result<(), Error> {
+        let bindreq = self.get_email_bindreq(token).await?;
+        log::info!(
+            "email bind request {} from origin {:?} rejected",
+            bindreq.id,
+            bindreq.origin,
+        );
+        Ok(self.0.ac_platform.reject_user_email_bindreq(bindreq.id).await?)
+    }
+
+    pub async fn get_user_emails(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserEmail>, Error> {
+        Ok(self.0.ac_platform.get_user_emails(user_id).await?)
+    }
+}
+
+// Only the basic structure of the address is checked here, as it may only
+// be truly verified by sending mail to it.
+fn validate_email(email: &str) -> Result<(), RegistrationError> {
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty()
+            && !domain.is_empty()
+            && !domain.contains('@')
+            && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+        => Ok(()),
+        _ => Err(RegistrationError::InvalidEmail),
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), RegistrationError> {
+    (!name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c.is_control()))
+        .then_some(())
+        .ok_or(RegistrationError::InvalidName)
+}
+
+// External identities
+
+impl Platform {
+    pub async fn get_user_identities(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserIdentity>, Error> {
+        Ok(self.0.ac_platform.get_user_identities(user_id).await?)
+    }
+
+    /// Unlink the identity from the user.  Returns false if the identity
+    /// was not linked to the user.
+    pub async fn unlink_user_identity(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<bool, Error> {
+        Ok(self.0.ac_platform.remove_user_identity(user_id, id).await?)
+    }
+}
+
+#[cfg(feature="oidc")]
+impl Platform {
+    pub fn oidc_providers(&self) -> impl Iterator<Item = &ProviderConfig> {
+        self.0.oidc_providers
+            .iter()
+            .map(|provider| &provider.config)
+    }
+
+    fn oidc_provider(&self, name: &str) -> Result<&Provider, IdentityError> {
+        self.0.oidc_providers
+            .iter()
+            .find(|provider| provider.config.name == name)
+            .ok_or_else(|| IdentityError::UnknownProvider(name.to_string()))
+    }
+
+    /// Start the authorization with the provider.  Returns the URL that
+    /// the end-user should be sent to, along with the pending
+    /// authorization that must be presented back with the response from
+    /// the provider.
+    pub async fn oidc_authorization(
+        &self,
+        provider: &str,
+    ) -> Result<(String, PendingAuthorization), Error> {
+        let provider = self.oidc_provider(provider)?;
+        Ok(provider.authorize(
+            self.0.token_factory.create().to_string(),
+            self.0.token_factory.create().to_string(),
+            format!(
+                "{}{}",
+                self.0.token_factory.create(),
+                self.0.token_factory.create(),
+            ),
+        ).await?)
+    }
+
+    /// Complete the pending authorization with the state and the
+    /// authorization code returned by the provider, returning the
+    /// identity asserted by the provider.
+    pub async fn oidc_identity(
+        &self,
+        pending: &PendingAuthorization,
+        state: &str,
+        code: &str,
+    ) -> Result<Identity, Error> {
+        if pending.state != state {
+            Err(IdentityError::InvalidState)?;
+        }
+        let provider = self.oidc_provider(&pending.provider)?;
+        Ok(provider.identify(pending, code).await?)
+    }
+
+    /// Link the identity to the user, such that the user may authenticate
+    /// with it.
+    pub async fn link_user_identity(
+        &self,
+        user_id: i64,
+        identity: &Identity,
+    ) -> Result<(), Error> {
+        self.0.ac_platform.get_user_by_id(user_id).await?
+            .ok_or(AuthenticationError::UnknownUser)?;
+        match self.0.ac_platform.get_user_by_identity(
+            &identity.provider,
+            &identity.subject,
+        ).await? {
+            Some(user) if user.id == user_id => (),
+            Some(_) => Err(IdentityError::Unavailable)?,
+            None => {
+                self.0.ac_platform.add_user_identity(
+                    user_id,
+                    &identity.provider,
+                    &identity.subject,
+                ).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the user linked to the identity.  If the identity is not
+    /// linked and the provider permits registration, a new user is
+    /// registered with the identity linked, provided that the verified
+    /// email address of the identity, if any, isn't bound to some other
+    /// user as that user should link the identity instead.
+    pub async fn authenticate_user_identity(
+        &self,
+        identity: &Identity,
+    ) -> Result<User, Error> {
+        let provider = self.oidc_provider(&identity.provider)?;
+        if let Some(user) = self.0.ac_platform.get_user_by_identity(
+            &identity.provider,
+            &identity.subject,
+        ).await? {
+            let password = self.0.ac_platform.get_user_password(user.id).await;
+            if let Ok(Password::Restricted) = password.as_deref().map(Password::from_database) {
+                Err(AuthenticationError::Restricted)?;
+            }
+            return Ok(User::new(self.clone(), user));
+        }
+        if !provider.config.register {
+            Err(IdentityError::Unlinked)?;
+        }
+        let email = identity.email
+            .as_deref()
+            .filter(|email| identity.email_verified && validate_email(email).is_ok());
+        if let Some(email) = email
+            && self.0.ac_platform.get_user_by_email(email).await?.is_some()
+        {
+            Err(RegistrationError::EmailUnavailable)?;
+        }
+        let name = self.identity_user_name(identity).await?;
+        let user = self.create_user(&name).await?;
+        self.0.ac_platform.add_user_identity(
+            user.id(),
+            &identity.provider,
+            &identity.subject,
+        ).await?;
+        if let Some(email) = email {
+            self.0.ac_platform.add_user_email(user.id(), email).await?;
+        }
+        log::info!(
+            "registered user {} for identity {:?} from provider {}",
+            user.name(),
+            identity.subject,
+            identity.provider,
+        );
+        Ok(user)
+    }
+
+    // Derive an available name for a new user from the identity, with a
+    // numeric suffix appended should the name be taken.
+    async fn identity_user_name(
+        &self,
+        identity: &Identity,
+    ) -> Result<String, Error> {
+        let base: String = identity.preferred_username
+            .as_deref()
+            .or(identity.email
+                .as_deref()
+                .and_then(|email| email.split_once('@'))
+                .map(|(local, _)| local)
+            )
+            .unwrap_or(&identity.provider)
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .collect();
+        let base = if base.is_empty() { identity.provider.clone() } else { base };
+        for name in std::iter::once(base.clone())
+            .chain((2..100).map(|n| format!("{base}-{n}")))
+        {
+            validate_name(&name)?;
+            if self.0.ac_platform.get_user_by_name(&name).await?.is_none() {
+                return Ok(name);
+            }
+        }
+        Err(RegistrationError::NameUnavailable)?
+    }
+}
+
+// Personal access tokens
+
+impl Platform {
+    /// Create a personal access token for the user that remains valid
+    /// for `lifetime` seconds, which may only be used for the actions
+    /// listed in `scopes`.  Returns the token along with its record, as
+    /// the token may not be acquired again afterwards.
+    pub async fn create_user_token(
+        &self,
+        user_id: i64,
+        name: &str,
+        scopes: &[String],
+        lifetime: i64,
+    ) -> Result<(String, UserToken), Error> {
+        if name.trim().is_empty() {
+            Err(TokenError::InvalidName)?;
+        }
+        if scopes.is_empty() {
+            Err(TokenError::InvalidScopes)?;
+        }
+        if lifetime <= 0 || lifetime > self.0.user_token_max_lifetime {
+            Err(TokenError::InvalidLifetime)?;
+        }
+        let token = self.0.token_factory.create().to_string();
+        let expires_ts = Utc::now().timestamp() + lifetime;
+        let id = self.0.ac_platform.add_user_token(
+            user_id,
+            name.trim(),
+            &token,
+            scopes,
+            expires_ts,
+        ).await?;
+        let user_token = self.0.ac_platform.get_user_token_by_id(id).await?
+            .expect("the token should have been created");
+        Ok((token, user_token))
+    }
+
+    pub async fn get_user_tokens(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserToken>, Error> {
+        Ok(self.0.ac_platform.get_user_tokens(user_id).await?)
+    }
+
+    /// Revoke the personal access token from the user, which also logs
+    /// out all sessions created from it.  Returns false if the token does
+    /// not belong to the user.
+    pub async fn revoke_user_token(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<bool, Error> {
+        Ok(self.0.ac_platform.remove_user_token(user_id, id).await?)
+    }
+
+    /// Returns the user of the personal access token along with the
+    /// record of the token, provided that the token remains valid.
+    pub async fn authenticate_user_token(
+        &self,
+        token: &str,
+    ) -> Result<(User, UserToken), Error> {
+        let user_token = self.0.ac_platform.get_user_token(token).await?
+            .ok_or(AuthenticationError::InvalidToken)?;
+        verify_user_token(&user_token)?;
+        let user = self.get_user(user_token.user_id).await?
+            .ok_or(AuthenticationError::UnknownUser)?;
+        let password = self.0.ac_platform.get_user_password(user.id()).await;
+        if let Ok(Password::Restricted) = password.as_deref().map(Password::from_database) {
+            Err(AuthenticationError::Restricted)?;
+        }
+        Ok((user, user_token))
+    }
+}
+
+fn verify_user_token(user_token: &UserToken) -> Result<(), Error> {
+    if user_token.expires_ts <= Utc::now().timestamp() {
+        Err(AuthenticationError::ExpiredToken)?;
+    }
+    Ok(())
+}
+
+// Password reset
+
+impl Platform {
+    /// Request the password of the user bound to the email address be
+    /// reset on behalf of the origin.  The token required to reset the
+    /// password is sent to the email address; to avoid disclosing whether
+    /// the address is bound to some user, this succeeds regardless.
+    pub async fn request_password_reset(
+        &self,
+        email: &str,
+        origin: &str,
+    ) -> Result<(), Error> {
+        let email = email.trim();
+        let mail_transport = self.0.mail_transport
+            .as_deref()
+            .ok_or(RegistrationError::Unavailable)?;
+        validate_email(email)?;
+        let since_ts = Utc::now().timestamp() - self.0.mail_rate_window;
+        if self.0.ac_platform.count_user_password_resetreqs_by_origin(
+            origin,
+            since_ts,
+        ).await? >= self.0.mail_rate_limit {
+            log::warn!("rate limited password reset requests from origin {origin}");
+            Err(RegistrationError::RateLimited)?;
+        }
+        let Some(user) = self.0.ac_platform.get_user_by_email(email).await? else {
+            log::info!("password reset requested from origin {origin} for an unbound email");
+            return Ok(());
+        };
+        let user_email = self.0.ac_platform.get_user_emails(user.id).await?
+            .into_iter()
+            .find(|user_email| user_email.email == email)
+            .ok_or(Error::Misconfiguration)?;
+        let token = self.0.token_factory.create().to_string();
+        self.0.ac_platform.add_user_password_resetreq(
+            &user_email,
+            origin,
+            &token,
+        ).await?;
+        mail_transport.send(&Mail {
+            to: email.to_string(),
+            subject: "Password reset".to_string(),
+            body: format!(
+                "A request was made from {origin} to reset the password of the \
+                account {}.  To set a new password, visit:\n\n\
+                {}{token}\n\n\
+                If this request was not made by you, this message may be ignored.",
+                user.name,
+                self.0.password_resetreq_url.as_deref().unwrap_or_default(),
+            ),
+        }).await?;
+        Ok(())
+    }
+
+    /// Returns the password reset request with the token, provided that
+    /// it may still be used, i.e. it has not expired and the email address
+    /// it was sent to remains bound to the user.
+    pub async fn get_password_resetreq(
+        &self,
+        token: &str,
+    ) -> Result<UserPasswordResetReq, Error> {
+        let since_ts = Utc::now().timestamp() - self.0.password_resetreq_lifetime;
+        let resetreq = self.0.ac_platform.get_user_password_resetreq(token).await?
+            .filter(|resetreq| resetreq.created_ts >= since_ts)
+            .ok_or(RegistrationError::InvalidToken)?;
+        self.0.ac_platform.get_user_emails(resetreq.user_id).await?
+            .iter()
+            .any(|user_email| user_email.id == resetreq.user_email_id)
+            .then_some(resetreq)
+            .ok_or(RegistrationError::InvalidToken.into())
+    }
+
+    /// Reset the password of the user with the password reset request
+    /// with the token, which also logs out all sessions of the user and
+    /// consumes every other reset request still outstanding for the user.
+    pub async fn complete_password_reset(
+        &self,
+        token: &str,
+        password: &str,
+        password_confirm: &str,
+    ) -> Result<User, Error> {
+        let resetreq = self.get_password_resetreq(token).await?;
+        (password == password_confirm)
+            .then_some(())
+            .ok_or(PasswordError::Mismatched)?;
+        let user = self.get_user(resetreq.user_id).await?
+            .ok_or(AuthenticationError::UnknownUser)?;
+        let (_, status) = self.login_status(user.name()).await?;
+        if matches!(status, PasswordStatus::Restricted) {
+            Err(AuthenticationError::Restricted)?;
+        }
+        if !self.0.ac_platform.consume_user_password_resetreq(resetreq.id).await? {
+            Err(RegistrationError::InvalidToken)?;
+        }
+        // any other reset requests still outstanding for the user may no
+        // longer be used once the password has been reset.
+        self.0.ac_platform.consume_user_password_resetreqs_for_user(user.id()).await?;
+        self.force_user_id_password(user.id(), Password::new(password)).await?;
+        self.logout_user(user.id()).await?;
+        Ok(user)
+    }
+}
+
+// Group management
+
+impl Platform {
+    pub async fn create_group(
+        &self,
+        name: &str,
+    ) -> Result<Group, Error> {
+        validate_name(name)
+            .map_err(|_| GroupError::InvalidName)?;
+        if self.0.ac_platform.get_group_by_name(name).await?.is_some() {
+            Err(GroupError::NameUnavailable)?;
+        }
+        let id = self.0.ac_platform.add_group(name).await?;
+        Ok(self.get_group(id).await?
+            .expect("the group should have been created"))
+    }
+
+    pub async fn get_group(
+        &self,
+        id: i64,
+    ) -> Result<Option<Group>, Error> {
+        let group = self.0.ac_platform.get_group_by_id(id).await?
+            .map(|group| Group::new(self.clone(), group));
+        Ok(group)
+    }
+
+    pub async fn get_group_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<Group>, Error> {
+        let group = self.0.ac_platform.get_group_by_name(name).await?
+            .map(|group| Group::new(self.clone(), group));
+        Ok(group)
+    }
+
+    pub async fn get_groups(
+        &self,
+    ) -> Result<Vec<group::Group>, Error> {
+        Ok(self.0.ac_platform.get_groups().await?)
+    }
+
+    pub async fn add_user_to_group(
+        &self,
+        group: impl Into<group::Group>,
+        user: impl Into<user::User>,
+    ) -> Result<bool, Error> {
+        Ok(self.0.ac_platform.add_user_to_group(
+            &group.into(),
+            &user.into(),
+        ).await?)
+    }
+
+    pub async fn remove_user_from_group(
+        &self,
+        group: impl Into<group::Group>,
+        user: impl Into<user::User>,
+    ) -> Result<bool, Error> {
+        Ok(self.0.ac_platform.remove_user_from_group(
+            &group.into(),
+            &user.into(),
+        ).await?)
+    }
+
+    pub async fn get_groups_for_user(
+        &self,
+        user: impl Into<user::User>,
+    ) -> Result<Vec<group::Group>, Error> {
+        Ok(self.0.ac_platform.get_groups_for_user(
+            &user.into(),
+        ).await?)
+    }
+
+    pub async fn get_group_members(
+        &self,
+        group: impl Into<group::Group>,
+    ) -> Result<Vec<user::User>, Error> {
+        Ok(self.0.ac_platform.get_group_members(
+            &group.into(),
+        ).await?)
+    }
+}
+
 // Agent Policy management
 
 impl Platform {
@@ -323,6 +1091,17 @@ impl Platform {
         Ok(ts)
     }
 
+    pub async fn list_res_with_wf_state(
+        &self,
+        wf_state: State,
+        prefix: &str,
+    ) -> Result<Vec<String>, Error> {
+        Ok(self.0.ac_platform.list_res_with_wf_state(
+            wf_state,
+            prefix,
+        ).await?)
+    }
+
     pub async fn generate_policy_for_agent_res(
         &self,
         agent: &Agent,
@@ -359,7 +1138,24 @@ impl Platform {
         // least until this is fully stablized.
         let session = self.0.session_factory.create(user.id(), origin);
         self.0.ac_platform.save_session(&session).await?;
-        let session = Session::new(self.clone(), session, user);
+        let session = Session::new(self.clone(), session, user, None);
+        Ok(session)
+    }
+
+    /// Create a new session for the user that is limited by the personal
+    /// access token.
+    pub async fn new_user_token_session(
+        &self,
+        user: User,
+        user_token: UserToken,
+        origin: String,
+    ) -> Result<Session, Error> {
+        let session = session::Session {
+            user_token_id: Some(user_token.id),
+            .. self.0.session_factory.create(user.id(), origin)
+        };
+        self.0.ac_platform.save_session(&session).await?;
+        let session = Session::new(self.clone(), session, user, Some(user_token));
         Ok(session)
     }
 
@@ -371,10 +1167,25 @@ impl Platform {
         let session = self.0.ac_platform.load_session(token).await?;
         let user = self.get_user(session.user_id).await?
             .ok_or(AuthenticationError::UnknownUser)?;
+        let user_token = match session.user_token_id {
+            Some(id) => {
+                // the token is checked on every load such that its expiry
+                // applies to the sessions created from it.
+                let user_token = self.0.ac_platform.get_user_token_by_id(id).await?
+                    .ok_or(AuthenticationError::InvalidToken)?;
+                if let Err(e) = verify_user_token(&user_token) {
+                    self.0.ac_platform.purge_session(token).await?;
+                    return Err(e);
+                }
+                Some(user_token)
+            }
+            None => None,
+        };
         Ok(Session::new(
             self.clone(),
             session,
             user,
+            user_token,
         ))
     }
 
@@ -409,6 +1220,27 @@ impl Platform {
         Ok(session)
     }
 
+    #[cfg(feature="oidc")]
+    pub async fn authenticate_user_identity_login(
+        &self,
+        identity: &Identity,
+        origin: String,
+    ) -> Result<Session, Error> {
+        let user = self.authenticate_user_identity(identity).await?;
+        let session = self.new_user_session(user, origin).await?;
+        Ok(session)
+    }
+
+    pub async fn authenticate_user_token_login(
+        &self,
+        token: &str,
+        origin: String,
+    ) -> Result<Session, Error> {
+        let (user, user_token) = self.authenticate_user_token(token).await?;
+        let session = self.new_user_token_session(user, user_token, origin).await?;
+        Ok(session)
+    }
+
     // TODO login via some generated token (e.g. account password reset
     // token generated via new user or password reset).
     // pub async fn authenticate_user_token(
@@ -427,17 +1259,23 @@ impl Platform {
 impl Platform {
     /// Simply provide a result with whether or not the agent is
     /// permitted to access the resource with the specified action.
+    ///
+    /// Where the agent is limited by scopes, the action must also be
+    /// within those scopes on top of being permitted by the policy.
     pub async fn enforce(
         &self,
-        agent: impl Into<Agent>,
+        agent: impl Into<ScopedAgent>,
         res: impl AsRef<str> + ToString,
         action: impl AsRef<str>,
     ) -> Result<bool, Error> {
         let agent = agent.into();
+        if !agent.in_scope(action.as_ref()) {
+            return Ok(false);
+        }
         Ok(self.0.pmrrbac_builder
             .build_with_policy(
                 self.generate_policy_for_agent_res(
-                    &agent,
+                    agent.agent(),
                     res.to_string(),
                 ).await?,
             )
@@ -449,7 +1287,7 @@ impl Platform {
     /// with the Policy that went into the enforcer.
     pub async fn get_policy_and_enforce(
         &self,
-        agent: impl Into<Agent>,
+        agent: impl Into<ScopedAgent>,
         res: impl AsRef<str> + ToString,
         action: impl AsRef<str>,
     ) -> Result<(Policy, bool), Error> {
@@ -458,7 +1296,7 @@ impl Platform {
         let instant = Instant::now();
         let agent = agent.into();
         let policy = self.generate_policy_for_agent_res(
-            &agent,
+            agent.agent(),
             res.to_string(),
         ).await?;
         let elapsed = instant.elapsed();
@@ -472,7 +1310,8 @@ impl Platform {
         log::trace!("enforcer generated from policy in {elapsed:?}");
 
         let instant = Instant::now();
-        let result = enforcer.enforce(action.as_ref())?;
+        let result = agent.in_scope(action.as_ref())
+            && enforcer.enforce(action.as_ref())?;
         let elapsed = instant.elapsed();
         log::trace!("enforcement completed in {elapsed:?}");
 
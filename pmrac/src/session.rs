@@ -1,4 +1,7 @@
-use pmrcore::ac::session;
+use pmrcore::ac::{
+    session,
+    user::UserToken,
+};
 
 use crate::{
     Platform,
@@ -10,6 +13,8 @@ pub struct Session {
     platform: Platform,
     session: session::Session,
     user: User,
+    // the personal access token that the session was created from.
+    user_token: Option<UserToken>,
 }
 
 mod impls;
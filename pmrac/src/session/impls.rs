@@ -1,4 +1,7 @@
-use pmrcore::ac::session;
+use pmrcore::ac::{
+    session,
+    user::UserToken,
+};
 
 use crate::{
     error::Error,
@@ -12,11 +15,13 @@ impl Session {
         platform: Platform,
         session: session::Session,
         user: User,
+        user_token: Option<UserToken>,
     ) -> Self {
         Self {
             platform,
             session,
             user,
+            user_token,
         }
     }
 
@@ -24,6 +29,12 @@ impl Session {
         &self.user
     }
 
+    /// The personal access token this session was created from, which
+    /// limits this session to the actions within its scopes.
+    pub fn user_token(&self) -> Option<&UserToken> {
+        self.user_token.as_ref()
+    }
+
     // access to every field, which may or may not be what we want.
     pub fn session(&self) -> &session::Session {
         &self.session
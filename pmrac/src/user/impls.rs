@@ -1,6 +1,12 @@
 use pmrcore::ac::{
     agent::Agent,
-    user,
+    group,
+    user::{
+        self,
+        UserEmail,
+        UserIdentity,
+        UserToken,
+    },
 };
 
 use crate::{
@@ -80,6 +86,36 @@ impl User {
             .await?
         )
     }
+
+    pub async fn emails(&self) -> Result<Vec<UserEmail>, Error> {
+        self.platform.get_user_emails(self.user.id).await
+    }
+
+    pub async fn identities(&self) -> Result<Vec<UserIdentity>, Error> {
+        self.platform.get_user_identities(self.user.id).await
+    }
+
+    pub async fn tokens(&self) -> Result<Vec<UserToken>, Error> {
+        self.platform.get_user_tokens(self.user.id).await
+    }
+
+    pub async fn groups(&self) -> Result<Vec<group::Group>, Error> {
+        self.platform.get_groups_for_user(self).await
+    }
+
+    pub async fn request_email_bind(
+        &self,
+        email: &str,
+        origin: &str,
+    ) -> Result<(), Error> {
+        self.platform
+            .request_user_email_bind(
+                self.user.id,
+                email,
+                origin,
+            )
+            .await
+    }
 }
 
 impl From<&User> for Agent {
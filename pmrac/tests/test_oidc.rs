@@ -0,0 +1,336 @@
+use axum::{
+    Form,
+    Json,
+    Router,
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+};
+use base64::{
+    Engine as _,
+    engine::general_purpose::URL_SAFE_NO_PAD,
+};
+use chrono::Utc;
+use pmrac::{
+    Platform,
+    error::{
+        AuthenticationError,
+        Error,
+        IdentityError,
+        RegistrationError,
+    },
+    oidc::ProviderConfig,
+    password::Password,
+    platform::Builder,
+};
+use serde_json::{
+    Value,
+    json,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+use test_pmr::ac::create_sqlite_backend;
+
+const CLIENT_ID: &str = "pmrapp";
+const CLIENT_SECRET: &str = "secret";
+const REDIRECT_URL: &str = "http://localhost/auth/oidc/mock/callback";
+
+// A minimal OpenID Connect provider that issues the claims registered for
+// the authorization codes, as the end-user interaction at the
+// authorization endpoint is out of scope.
+#[derive(Clone)]
+struct MockProvider {
+    issuer: String,
+    // the claims for the authorization codes, along with the PKCE
+    // challenge that was presented with the authorization request.
+    codes: Arc<Mutex<HashMap<String, (Value, String)>>>,
+}
+
+impl MockProvider {
+    async fn start() -> anyhow::Result<Self> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let provider = Self {
+            issuer: format!("http://{}", listener.local_addr()?),
+            codes: Default::default(),
+        };
+        let router = Router::new()
+            .route("/.well-known/openid-configuration", get(discovery))
+            .route("/token", post(token))
+            .with_state(provider.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await
+        });
+        Ok(provider)
+    }
+
+    fn config(&self, register: bool) -> ProviderConfig {
+        ProviderConfig {
+            name: "mock".to_string(),
+            title: "Mock Provider".to_string(),
+            issuer: self.issuer.clone(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: Some(CLIENT_SECRET.to_string()),
+            redirect_url: REDIRECT_URL.to_string(),
+            scopes: vec!["openid".to_string(), "email".to_string()],
+            register,
+        }
+    }
+
+    // Act as the end-user consenting at the authorization endpoint with
+    // the url, with the claims of the ID token overridden by the provided
+    // claims.  Returns the state and the authorization code that would be
+    // redirected back to the client.
+    fn authorize(&self, url: &str, claims: Value) -> anyhow::Result<(String, String)> {
+        let url = reqwest::Url::parse(url)?;
+        assert_eq!(url.path(), "/authorize");
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(query["response_type"], "code");
+        assert_eq!(query["client_id"], CLIENT_ID);
+        assert_eq!(query["redirect_uri"], REDIRECT_URL);
+        assert_eq!(query["scope"], "openid email");
+        assert_eq!(query["code_challenge_method"], "S256");
+        let mut id_claims = json!({
+            "iss": self.issuer,
+            "aud": CLIENT_ID,
+            "exp": Utc::now().timestamp() + 300,
+            "nonce": query["nonce"],
+        });
+        for (key, value) in claims.as_object().expect("claims must be an object") {
+            id_claims[key] = value.clone();
+        }
+        let mut codes = self.codes.lock().expect("not poisoned");
+        let code = format!("code{}", codes.len());
+        codes.insert(code.clone(), (id_claims, query["code_challenge"].clone()));
+        Ok((query["state"].clone(), code))
+    }
+}
+
+async fn discovery(State(provider): State<MockProvider>) -> Json<Value> {
+    Json(json!({
+        "issuer": provider.issuer,
+        "authorization_endpoint": format!("{}/authorize", provider.issuer),
+        "token_endpoint": format!("{}/token", provider.issuer),
+    }))
+}
+
+async fn token(
+    State(provider): State<MockProvider>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<Json<Value>, StatusCode> {
+    // the code may only be used once.
+    let (claims, challenge) = provider.codes.lock().expect("not poisoned")
+        .remove(&form["code"])
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let verifier_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(&form["code_verifier"]));
+    if form["grant_type"] != "authorization_code"
+        || form["client_id"] != CLIENT_ID
+        || form["client_secret"] != CLIENT_SECRET
+        || form["redirect_uri"] != REDIRECT_URL
+        || verifier_challenge != challenge
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let id_token = format!(
+        "{}.{}.",
+        URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#),
+        URL_SAFE_NO_PAD.encode(claims.to_string()),
+    );
+    Ok(Json(json!({
+        "access_token": "access",
+        "token_type": "Bearer",
+        "id_token": id_token,
+    })))
+}
+
+async fn create_platform(provider: &MockProvider, register: bool) -> anyhow::Result<Platform> {
+    Ok(Builder::new()
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .oidc_provider(provider.config(register))
+        .build())
+}
+
+// Go through the authorization flow with the claims, returning the
+// result from authenticating the identity.
+async fn login(
+    platform: &Platform,
+    provider: &MockProvider,
+    claims: Value,
+) -> Result<pmrac::user::User, Error> {
+    let (url, pending) = platform.oidc_authorization("mock").await?;
+    let (state, code) = provider.authorize(&url, claims)
+        .expect("authorization url is valid");
+    let identity = platform.oidc_identity(&pending, &state, &code).await?;
+    platform.authenticate_user_identity(&identity).await
+}
+
+#[tokio::test]
+async fn oidc_registration() -> anyhow::Result<()> {
+    let provider = MockProvider::start().await?;
+    let platform = create_platform(&provider, true).await?;
+    assert_eq!(
+        platform.oidc_providers()
+            .map(|config| config.name.as_str())
+            .collect::<Vec<_>>(),
+        ["mock"],
+    );
+
+    let claims = json!({
+        "sub": "alice-sub",
+        "email": "alice@example.com",
+        "email_verified": true,
+        "preferred_username": "alice",
+    });
+    let user = login(&platform, &provider, claims.clone()).await?;
+    assert_eq!(user.name(), "alice");
+    let identities = user.identities().await?;
+    assert_eq!(identities.len(), 1);
+    assert_eq!(identities[0].provider, "mock");
+    assert_eq!(identities[0].subject, "alice-sub");
+    assert_eq!(user.emails().await?[0].email, "alice@example.com");
+    // the registered user has no usable password.
+    assert!(platform.authenticate_user("alice", "").await.is_err());
+
+    // subsequent logins return the same user.
+    assert_eq!(login(&platform, &provider, claims).await?.id(), user.id());
+
+    // the name is made available for a different identity.
+    let user = login(&platform, &provider, json!({
+        "sub": "other-sub",
+        "email": "other@example.com",
+        "preferred_username": "alice",
+    })).await?;
+    assert_eq!(user.name(), "alice-2");
+    // the unverified email address is not bound.
+    assert!(user.emails().await?.is_empty());
+
+    // the verified email address that is already bound to some user will
+    // require that user to link the identity.
+    assert!(matches!(
+        login(&platform, &provider, json!({
+            "sub": "third-sub",
+            "email": "alice@example.com",
+            "email_verified": true,
+        })).await,
+        Err(Error::Registration(RegistrationError::EmailUnavailable)),
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn oidc_link() -> anyhow::Result<()> {
+    let provider = MockProvider::start().await?;
+    let platform = create_platform(&provider, false).await?;
+    let user = platform.create_user("admin").await?;
+    let other = platform.create_user("other").await?;
+    let claims = json!({"sub": "admin-sub"});
+
+    assert!(matches!(
+        login(&platform, &provider, claims.clone()).await,
+        Err(Error::Identity(IdentityError::Unlinked)),
+    ));
+
+    let (url, pending) = platform.oidc_authorization("mock").await?;
+    let (state, code) = provider.authorize(&url, claims.clone())?;
+    let identity = platform.oidc_identity(&pending, &state, &code).await?;
+    platform.link_user_identity(user.id(), &identity).await?;
+    // linking again is fine, but not to a different user.
+    platform.link_user_identity(user.id(), &identity).await?;
+    assert!(matches!(
+        platform.link_user_identity(other.id(), &identity).await,
+        Err(Error::Identity(IdentityError::Unavailable)),
+    ));
+
+    assert_eq!(login(&platform, &provider, claims.clone()).await?.id(), user.id());
+    let session = platform.authenticate_user_identity_login(
+        &identity,
+        "127.0.0.1".to_string(),
+    ).await?;
+    assert_eq!(session.user().id(), user.id());
+
+    // restricted users may not log in with their identities.
+    platform.force_user_id_password(user.id(), Password::Restricted).await?;
+    assert!(matches!(
+        login(&platform, &provider, claims.clone()).await,
+        Err(Error::Authentication(AuthenticationError::Restricted)),
+    ));
+
+    let identities = user.identities().await?;
+    assert!(!platform.unlink_user_identity(other.id(), identities[0].id).await?);
+    assert!(platform.unlink_user_identity(user.id(), identities[0].id).await?);
+    assert!(matches!(
+        login(&platform, &provider, claims).await,
+        Err(Error::Identity(IdentityError::Unlinked)),
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn oidc_invalid() -> anyhow::Result<()> {
+    let provider = MockProvider::start().await?;
+    let platform = create_platform(&provider, true).await?;
+
+    assert!(matches!(
+        platform.oidc_authorization("unknown").await,
+        Err(Error::Identity(IdentityError::UnknownProvider(_))),
+    ));
+
+    // the state must match the pending authorization.
+    let (url, pending) = platform.oidc_authorization("mock").await?;
+    let (_, code) = provider.authorize(&url, json!({"sub": "user"}))?;
+    assert!(matches!(
+        platform.oidc_identity(&pending, "wrong", &code).await,
+        Err(Error::Identity(IdentityError::InvalidState)),
+    ));
+
+    // the authorization code may only be exchanged once.
+    let (url, pending) = platform.oidc_authorization("mock").await?;
+    let (state, code) = provider.authorize(&url, json!({"sub": "user"}))?;
+    platform.oidc_identity(&pending, &state, &code).await?;
+    assert!(matches!(
+        platform.oidc_identity(&pending, &state, &code).await,
+        Err(Error::Identity(IdentityError::Http(_))),
+    ));
+
+    // the code is bound to the pending authorization it was issued for.
+    let (_, other_pending) = platform.oidc_authorization("mock").await?;
+    let (url, _) = platform.oidc_authorization("mock").await?;
+    let (state, code) = provider.authorize(&url, json!({"sub": "user"}))?;
+    let other_pending = pmrac::oidc::PendingAuthorization {
+        state: state.clone(),
+        .. other_pending
+    };
+    assert!(matches!(
+        platform.oidc_identity(&other_pending, &state, &code).await,
+        Err(Error::Identity(IdentityError::Http(_))),
+    ));
+
+    for claims in [
+        json!({"sub": "user", "iss": "http://evil.example.com"}),
+        json!({"sub": "user", "aud": "other"}),
+        json!({"sub": "user", "aud": [CLIENT_ID, "other"]}),
+        json!({"sub": "user", "exp": Utc::now().timestamp() - 1}),
+        json!({"sub": "user", "nonce": "wrong"}),
+    ] {
+        assert!(matches!(
+            login(&platform, &provider, claims).await,
+            Err(Error::Identity(IdentityError::InvalidResponse(_))),
+        ));
+    }
+    // multiple audiences are accepted with the client as the authorized
+    // party.
+    login(&platform, &provider, json!({
+        "sub": "user",
+        "aud": [CLIENT_ID, "other"],
+        "azp": CLIENT_ID,
+    })).await?;
+    Ok(())
+}
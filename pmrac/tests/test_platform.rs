@@ -9,8 +9,12 @@ use pmrac::{
     error::{
         AuthenticationError,
         Error,
+        GroupError,
         PasswordError,
+        RegistrationError,
+        TokenError,
     },
+    mail::FileTransport,
     password::{
         Password,
         PasswordStatus,
@@ -445,6 +449,94 @@ async fn policy_enforcement_with_roles() -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn group_policy_enforcement(platform: pmrac::Platform) -> anyhow::Result<()> {
+    platform.assign_policy_to_wf_state(State::Private, Role::Editor, "").await?;
+    platform.assign_policy_to_wf_state(State::Private, Role::Editor, "editor_view").await?;
+    platform.assign_policy_to_wf_state(State::Private, Role::Editor, "editor_edit").await?;
+    platform.assign_policy_to_wf_state(State::Private, Role::Owner, "grant_edit").await?;
+
+    assert!(matches!(
+        platform.create_group("").await,
+        Err(Error::Group(GroupError::InvalidName)),
+    ));
+    let lab = platform.create_group("lab").await?;
+    assert!(matches!(
+        platform.create_group("lab").await,
+        Err(Error::Group(GroupError::NameUnavailable)),
+    ));
+    let admin = platform.create_group("admin").await?;
+    assert_eq!(
+        platform.get_group_by_name("lab").await?
+            .map(|group| group.id()),
+        Some(lab.id()),
+    );
+
+    let alice = platform.create_user("alice").await?;
+    let bob = platform.create_user("bob").await?;
+    assert!(lab.add_member(&alice).await?);
+    assert!(!lab.add_member(&alice).await?);
+    assert_eq!(alice.groups().await?, [lab.clone_inner()]);
+    assert!(bob.groups().await?.is_empty());
+
+    // a single grant to the group covers all the resources it was made for
+    for res in ["/item/1", "/item/2"] {
+        platform.set_wf_state_for_res(res, State::Private).await?;
+        platform.res_grant_role_to_agent(res, &lab, Role::Editor).await?;
+    }
+    platform.set_wf_state_for_res("/item/3", State::Private).await?;
+    platform.res_grant_role_to_agent("/item/1", &admin, Role::Owner).await?;
+
+    assert!(platform.enforce(&alice, "/item/1", "editor_edit").await?);
+    assert!(platform.enforce(&alice, "/item/2", "editor_edit").await?);
+    assert!(!platform.enforce(&alice, "/item/3", "editor_edit").await?);
+    // alice is not a member of the admin group
+    assert!(!platform.enforce(&alice, "/item/1", "grant_edit").await?);
+    assert!(!platform.enforce(&bob, "/item/1", "editor_edit").await?);
+    assert!(!platform.enforce(Agent::Anonymous, "/item/1", "").await?);
+
+    let (policy, enforcement) = platform.get_policy_and_enforce(&alice, "/item/1", "").await?;
+    assert!(enforcement);
+    assert_eq!(policy.agent_groups, ["lab"]);
+    assert_eq!(
+        &policy.to_roles().into_iter().collect::<Vec<_>>(),
+        &[Role::Editor],
+    );
+
+    // new members gain the access granted to the group
+    assert!(lab.add_member(&bob).await?);
+    assert!(platform.enforce(&bob, "/item/2", "editor_edit").await?);
+    assert_eq!(lab.members().await?.len(), 2);
+
+    // which is lost when they are removed from the group
+    assert!(lab.remove_member(&alice).await?);
+    assert!(!lab.remove_member(&alice).await?);
+    assert!(!platform.enforce(&alice, "/item/1", "editor_edit").await?);
+    assert!(platform.enforce(&bob, "/item/1", "editor_edit").await?);
+
+    assert!(platform.res_revoke_role_from_agent("/item/1", &lab, Role::Editor).await?);
+    assert!(!platform.enforce(&bob, "/item/1", "editor_edit").await?);
+    assert!(platform.enforce(&bob, "/item/2", "editor_edit").await?);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn group_policy_enforcement_default() -> anyhow::Result<()> {
+    group_policy_enforcement(create_sqlite_platform(true).await?).await
+}
+
+#[cfg(feature = "casbin")]
+#[async_std::test]
+async fn group_policy_enforcement_casbin() -> anyhow::Result<()> {
+    use pmrrbac::casbin::CasbinBuilder;
+
+    group_policy_enforcement(Builder::new()
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .pmrrbac_builder(CasbinBuilder::new().into())
+        .build()
+    ).await
+}
+
 #[async_std::test]
 async fn sessions() -> anyhow::Result<()> {
     let platform = Builder::new()
@@ -565,6 +657,404 @@ async fn authenticate_credentials_into_session() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[async_std::test]
+async fn user_tokens() -> anyhow::Result<()> {
+    let platform = Builder::new()
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .user_token_max_lifetime(86400)
+        .build();
+    platform.assign_policy_to_wf_state(State::Private, Role::Owner, "").await?;
+    platform.assign_policy_to_wf_state(State::Private, Role::Owner, "protocol_view").await?;
+    platform.assign_policy_to_wf_state(State::Private, Role::Owner, "protocol_write").await?;
+    platform.set_wf_state_for_res("/workspace/1/", State::Private).await?;
+
+    let user = platform.create_user("user").await?;
+    let other = platform.create_user("other").await?;
+    platform.res_grant_role_to_agent("/workspace/1/", &user, Role::Owner).await?;
+
+    let scopes = ["protocol_view".to_string()];
+    for (name, scopes, lifetime, error) in [
+        (" ", &scopes[..], 3600, TokenError::InvalidName),
+        ("script", &[], 3600, TokenError::InvalidScopes),
+        ("script", &scopes[..], 0, TokenError::InvalidLifetime),
+        ("script", &scopes[..], 86401, TokenError::InvalidLifetime),
+    ] {
+        assert!(matches!(
+            platform.create_user_token(user.id(), name, scopes, lifetime).await,
+            Err(Error::Token(e)) if e == error,
+        ));
+    }
+
+    let (token, user_token) = platform.create_user_token(
+        user.id(),
+        "script",
+        &scopes,
+        3600,
+    ).await?;
+    assert_eq!(user_token.name, "script");
+    assert_eq!(user_token.scopes, scopes);
+    assert_eq!(user_token.expires_ts, user_token.created_ts + 3600);
+    assert_eq!(user.tokens().await?, [user_token.clone()]);
+    assert!(matches!(
+        platform.authenticate_user_token("unknown").await,
+        Err(Error::Authentication(AuthenticationError::InvalidToken)),
+    ));
+
+    let session = platform.authenticate_user_token_login(
+        &token,
+        "localhost".to_string(),
+    ).await?;
+    assert_eq!(session.user().id(), user.id());
+    assert_eq!(session.user_token(), Some(&user_token));
+    let session = platform.load_session(session.session().token).await?;
+    assert_eq!(session.user_token(), Some(&user_token));
+
+    // the session is limited to the scopes of the token on top of the
+    // policy, while the user remains unrestricted.
+    assert!(platform.enforce(&user, "/workspace/1/", "").await?);
+    assert!(platform.enforce(&user, "/workspace/1/", "protocol_write").await?);
+    assert!(platform.enforce(&session, "/workspace/1/", "protocol_view").await?);
+    assert!(!platform.enforce(&session, "/workspace/1/", "").await?);
+    assert!(!platform.enforce(&session, "/workspace/1/", "protocol_write").await?);
+    assert!(!platform.enforce(&session, "/workspace/2/", "protocol_view").await?);
+    let (_, enforcement) = platform.get_policy_and_enforce(
+        &session,
+        "/workspace/1/",
+        "protocol_write",
+    ).await?;
+    assert!(!enforcement);
+
+    // only the user of the token may revoke it, which also logs out the
+    // sessions created from it.
+    assert!(!platform.revoke_user_token(other.id(), user_token.id).await?);
+    assert!(platform.revoke_user_token(user.id(), user_token.id).await?);
+    assert!(platform.load_session(session.session().token).await.is_err());
+    assert!(matches!(
+        platform.authenticate_user_token(&token).await,
+        Err(Error::Authentication(AuthenticationError::InvalidToken)),
+    ));
+    assert!(user.tokens().await?.is_empty());
+
+    // expired tokens may not be used, and also expire their sessions.
+    platform.backend().add_user_token(user.id(), "expired", "expired", &scopes, 0).await?;
+    assert!(matches!(
+        platform.authenticate_user_token("expired").await,
+        Err(Error::Authentication(AuthenticationError::ExpiredToken)),
+    ));
+    let user_token = platform.backend().get_user_token("expired").await?
+        .expect("token was created");
+    let session = platform.new_user_token_session(
+        user.clone(),
+        user_token,
+        "localhost".to_string(),
+    ).await?;
+    assert!(matches!(
+        platform.load_session(session.session().token).await,
+        Err(Error::Authentication(AuthenticationError::ExpiredToken)),
+    ));
+    assert!(platform.get_user_sessions(user.id()).await?.is_empty());
+
+    // restricted users may not use their tokens.
+    let (token, _) = platform.create_user_token(user.id(), "script", &scopes, 3600).await?;
+    platform.force_user_id_password(user.id(), Password::Restricted).await?;
+    assert!(matches!(
+        platform.authenticate_user_token(&token).await,
+        Err(Error::Authentication(AuthenticationError::Restricted)),
+    ));
+
+    Ok(())
+}
+
+const BINDREQ_URL: &str = "https://example.com/auth/email_bindreq?token=";
+const RESETREQ_URL: &str = "https://example.com/auth/password_resetreq?token=";
+
+fn create_mail_platform_builder(path: &std::path::Path) -> Builder {
+    Builder::new()
+        .mail_transport(FileTransport::new(path))
+        .email_bindreq_url(BINDREQ_URL.to_string())
+        .password_resetreq_url(RESETREQ_URL.to_string())
+}
+
+// Returns the tokens from all the links with the prefix sent so far.
+fn sent_link_tokens(path: &std::path::Path, prefix: &str) -> anyhow::Result<Vec<String>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| line.strip_prefix(prefix))
+        .map(str::to_string)
+        .collect())
+}
+
+// Returns the tokens from all the links to bind requests sent so far.
+fn sent_tokens(path: &std::path::Path) -> anyhow::Result<Vec<String>> {
+    sent_link_tokens(path, BINDREQ_URL)
+}
+
+#[async_std::test]
+async fn registration() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("mail");
+    let platform = create_mail_platform_builder(&path)
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .build();
+
+    assert!(matches!(
+        platform.request_registration("not an email", "127.0.0.1").await,
+        Err(Error::Registration(RegistrationError::InvalidEmail)),
+    ));
+    assert!(matches!(
+        platform.request_registration("@example.com", "127.0.0.1").await,
+        Err(Error::Registration(RegistrationError::InvalidEmail)),
+    ));
+
+    platform.request_registration("user@example.com", "127.0.0.1").await?;
+    let mail = std::fs::read_to_string(&path)?;
+    assert!(mail.starts_with("To: user@example.com\nSubject: Account registration\n"));
+    let tokens = sent_tokens(&path)?;
+    assert_eq!(tokens.len(), 1);
+    let token = &tokens[0];
+
+    assert!(matches!(
+        platform.complete_registration("no_such_token", "user", "hunter2", "hunter2").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+    assert!(matches!(
+        platform.complete_registration(token, "user", "hunter2", "hunter").await,
+        Err(Error::Password(PasswordError::Mismatched)),
+    ));
+    assert!(matches!(
+        platform.complete_registration(token, "some user", "hunter2", "hunter2").await,
+        Err(Error::Registration(RegistrationError::InvalidName)),
+    ));
+    platform.create_user("admin").await?;
+    assert!(matches!(
+        platform.complete_registration(token, "admin", "hunter2", "hunter2").await,
+        Err(Error::Registration(RegistrationError::NameUnavailable)),
+    ));
+
+    let user = platform.complete_registration(token, "user", "hunter2", "hunter2").await?;
+    assert_eq!(user.name(), "user");
+    let emails = user.emails().await?;
+    assert_eq!(emails.len(), 1);
+    assert_eq!(emails[0].email, "user@example.com");
+    assert_eq!(platform.authenticate_user("user", "hunter2").await?.id(), user.id());
+
+    // the token may only be used once.
+    assert!(matches!(
+        platform.complete_registration(token, "other", "hunter2", "hunter2").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+
+    // registering with an address already bound succeeds, but the mail
+    // sent has no link to complete the registration.
+    platform.request_registration("user@example.com", "127.0.0.1").await?;
+    assert_eq!(sent_tokens(&path)?.len(), 1);
+    assert!(std::fs::read_to_string(&path)?.contains("already registered"));
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn registration_unavailable() -> anyhow::Result<()> {
+    let platform = create_sqlite_platform(true).await?;
+    assert!(matches!(
+        platform.request_registration("user@example.com", "127.0.0.1").await,
+        Err(Error::Registration(RegistrationError::Unavailable)),
+    ));
+    Ok(())
+}
+
+#[async_std::test]
+async fn registration_rate_limit() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("mail");
+    let platform = create_mail_platform_builder(&path)
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .mail_rate_limit(2, 3600)
+        .build();
+
+    platform.request_registration("user1@example.com", "127.0.0.1").await?;
+    platform.request_registration("user2@example.com", "127.0.0.1").await?;
+    assert!(matches!(
+        platform.request_registration("user3@example.com", "127.0.0.1").await,
+        Err(Error::Registration(RegistrationError::RateLimited)),
+    ));
+    platform.request_registration("user3@example.com", "127.0.0.2").await?;
+    assert_eq!(sent_tokens(&path)?.len(), 3);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn registration_expired() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("mail");
+    let platform = create_mail_platform_builder(&path)
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .email_bindreq_lifetime(-60)
+        .build();
+
+    platform.request_registration("user@example.com", "127.0.0.1").await?;
+    let token = &sent_tokens(&path)?[0];
+    assert!(matches!(
+        platform.complete_registration(token, "user", "hunter2", "hunter2").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn user_email_bind() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("mail");
+    let platform = create_mail_platform_builder(&path)
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .build();
+
+    let admin = platform.create_user("admin").await?;
+    let user = platform.create_user("user").await?;
+    admin.request_email_bind("admin@example.com", "127.0.0.1").await?;
+    user.request_email_bind("admin@example.com", "127.0.0.1").await?;
+    user.request_email_bind("user@example.com", "127.0.0.1").await?;
+    let tokens = sent_tokens(&path)?;
+    assert_eq!(tokens.len(), 3);
+
+    // tokens for binding to existing users cannot be used for registration.
+    assert!(matches!(
+        platform.complete_registration(&tokens[0], "other", "hunter2", "hunter2").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+    assert_eq!(platform.verify_user_email_bind(&tokens[0]).await?.id(), admin.id());
+    assert!(matches!(
+        platform.verify_user_email_bind(&tokens[0]).await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+    // the address may only be bound to one user.
+    assert!(matches!(
+        platform.verify_user_email_bind(&tokens[1]).await,
+        Err(Error::Registration(RegistrationError::EmailUnavailable)),
+    ));
+
+    platform.reject_email_bindreq(&tokens[2]).await?;
+    assert!(matches!(
+        platform.verify_user_email_bind(&tokens[2]).await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+
+    let emails = admin.emails().await?;
+    assert_eq!(emails.len(), 1);
+    assert_eq!(emails[0].email, "admin@example.com");
+    assert!(user.emails().await?.is_empty());
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn password_reset() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("mail");
+    let platform = create_mail_platform_builder(&path)
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .mail_rate_limit(2, 3600)
+        .build();
+
+    let user = platform.create_user("user").await?;
+    user.reset_password("hunter2", "hunter2").await?;
+    user.request_email_bind("user@example.com", "127.0.0.1").await?;
+    platform.verify_user_email_bind(&sent_tokens(&path)?[0]).await?;
+    platform.new_user_session(user.clone(), "127.0.0.1".to_string()).await?;
+    assert_eq!(platform.get_user_sessions(user.id()).await?.len(), 1);
+
+    // nothing is sent for addresses not bound to any user.
+    platform.request_password_reset("nobody@example.com", "127.0.0.1").await?;
+    assert!(sent_link_tokens(&path, RESETREQ_URL)?.is_empty());
+
+    platform.request_password_reset("user@example.com", "127.0.0.1").await?;
+    let tokens = sent_link_tokens(&path, RESETREQ_URL)?;
+    assert_eq!(tokens.len(), 1);
+    let token = &tokens[0];
+
+    assert!(matches!(
+        platform.complete_password_reset("no_such_token", "hunter3", "hunter3").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+    assert!(matches!(
+        platform.complete_password_reset(token, "hunter3", "hunter").await,
+        Err(Error::Password(PasswordError::Mismatched)),
+    ));
+    assert_eq!(
+        platform.complete_password_reset(token, "hunter3", "hunter3").await?.id(),
+        user.id(),
+    );
+    assert!(platform.authenticate_user("user", "hunter2").await.is_err());
+    assert!(platform.authenticate_user("user", "hunter3").await.is_ok());
+    assert!(platform.get_user_sessions(user.id()).await?.is_empty());
+
+    // the token may only be used once.
+    assert!(matches!(
+        platform.complete_password_reset(token, "hunter4", "hunter4").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+
+    // the requests are limited by origin.
+    platform.request_password_reset("user@example.com", "127.0.0.1").await?;
+    assert!(matches!(
+        platform.request_password_reset("user@example.com", "127.0.0.1").await,
+        Err(Error::Registration(RegistrationError::RateLimited)),
+    ));
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn password_reset_consumes_all_tokens() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("mail");
+    let platform = create_mail_platform_builder(&path)
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .build();
+
+    let user = platform.create_user("user").await?;
+    user.request_email_bind("user@example.com", "127.0.0.1").await?;
+    platform.verify_user_email_bind(&sent_tokens(&path)?[0]).await?;
+    platform.request_password_reset("user@example.com", "127.0.0.1").await?;
+    platform.request_password_reset("user@example.com", "127.0.0.1").await?;
+    let tokens = sent_link_tokens(&path, RESETREQ_URL)?;
+    assert_eq!(tokens.len(), 2);
+
+    platform.complete_password_reset(&tokens[1], "hunter2", "hunter2").await?;
+    // the older link may no longer be used once the password was reset.
+    assert!(matches!(
+        platform.complete_password_reset(&tokens[0], "hunter3", "hunter3").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+    assert!(platform.authenticate_user("user", "hunter2").await.is_ok());
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn password_reset_expired() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("mail");
+    let platform = create_mail_platform_builder(&path)
+        .boxed_ac_platform(create_sqlite_backend().await?)
+        .password_resetreq_lifetime(-60)
+        .build();
+
+    let user = platform.create_user("user").await?;
+    user.request_email_bind("user@example.com", "127.0.0.1").await?;
+    platform.verify_user_email_bind(&sent_tokens(&path)?[0]).await?;
+    platform.request_password_reset("user@example.com", "127.0.0.1").await?;
+    let token = &sent_link_tokens(&path, RESETREQ_URL)?[0];
+    assert!(matches!(
+        platform.complete_password_reset(token, "hunter2", "hunter2").await,
+        Err(Error::Registration(RegistrationError::InvalidToken)),
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn test_send_sync_ctrl() {
     is_send_sync::<pmrac::Platform>();
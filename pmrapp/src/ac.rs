@@ -1,7 +1,13 @@
+use chrono::{
+    TimeZone,
+    Utc,
+};
 use leptos::prelude::*;
 use leptos_router::{
     components::{A, ParentRoute, Route},
+    hooks::use_query,
     nested_router::Outlet,
+    params::Params,
     SsrMode,
     StaticSegment,
 };
@@ -19,10 +25,26 @@ use crate::{
 
 pub mod api;
 use api::{
+    CompletePasswordReset,
+    CompleteRegistration,
+    CreateToken,
+    RejectEmailBindreq,
+    RequestEmailBind,
+    RequestPasswordReset,
+    RequestRegistration,
+    RevokeToken,
     SignInWithLoginPassword,
     SignOut,
+    TOKEN_SCOPES,
+    UnlinkIdentity,
+    VerifyEmailBindreq,
     WorkflowTransition,
     current_user,
+    current_user_emails,
+    current_user_identities,
+    current_user_tokens,
+    email_bindreq,
+    oidc_providers,
 };
 
 #[derive(Clone)]
@@ -60,6 +82,13 @@ pub fn ACRoutes() -> impl leptos_router::MatchNestedRoutes + Clone {
             <Route path=StaticSegment("/") view=LoginPage/>
             <Route path=StaticSegment("login") view=LoginPage/>
             <Route path=StaticSegment("logged_out") view=LogoutPage/>
+            <Route path=StaticSegment("register") view=RegisterPage/>
+            <Route path=StaticSegment("email_bindreq") view=EmailBindReqPage/>
+            <Route path=StaticSegment("email") view=EmailPage/>
+            <Route path=StaticSegment("identities") view=IdentitiesPage/>
+            <Route path=StaticSegment("password_reset") view=PasswordResetPage/>
+            <Route path=StaticSegment("password_resetreq") view=PasswordResetReqPage/>
+            <Route path=StaticSegment("tokens") view=TokensPage/>
         </ParentRoute>
     }
     .into_inner()
@@ -192,6 +221,30 @@ fn AuthTop() -> impl IntoView {
 fn LoginPage() -> impl IntoView {
     let account_ctx = expect_context::<AccountCtx>();
     let action = ServerAction::<SignInWithLoginPassword>::new();
+    let providers = Resource::new_blocking(
+        move || (),
+        move |_| async move { oidc_providers().await },
+    );
+
+    // the providers are simply omitted should they fail to load.
+    let providers_view = move || Suspend::new(async move {
+        providers.await
+            .ok()
+            .filter(|providers| !providers.is_empty())
+            .map(|providers| view! {
+                <ul id="oidc-providers">
+                    {providers.into_iter()
+                        .map(|provider| view! {
+                            <li>
+                                <a href=format!("/auth/oidc/{}/login", provider.name) rel="external">
+                                    "Sign in with "{provider.title}
+                                </a>
+                            </li>
+                        })
+                        .collect_view()}
+                </ul>
+            })
+    });
 
     view! {
         <h1>"Login Form"</h1>
@@ -224,6 +277,9 @@ fn LoginPage() -> impl IntoView {
                 <input type="submit" value="Login"/>
             </div>
         </ActionForm>
+        <Transition>{providers_view}</Transition>
+        <p>"Don't have an account? "<A href="/auth/register">"Register"</A></p>
+        <p>"Forgot your password? "<A href="/auth/password_reset">"Reset it"</A></p>
     }
 }
 
@@ -233,3 +289,379 @@ fn LogoutPage() -> impl IntoView {
         <h1>"You are now logged out."</h1>
     }
 }
+
+// Renders the outcome of an action that results in a message.
+fn action_result_view<E: std::fmt::Display>(
+    value: Option<Result<String, E>>,
+) -> Option<AnyView> {
+    match value {
+        Some(Ok(s)) => Some(view! {
+            <p class="standard ok">{s}</p>
+        }.into_any()),
+        Some(Err(e)) => Some(view! {
+            <p class="standard error">{format!("Error: {e}")}</p>
+        }.into_any()),
+        None => None,
+    }
+}
+
+#[component]
+fn RegisterPage() -> impl IntoView {
+    let action = ServerAction::<RequestRegistration>::new();
+
+    view! {
+        <h1>"Register"</h1>
+        <p>
+            "A link to complete the registration will be sent to the email address."
+        </p>
+        <ActionForm attr:id="register" action=action>
+            <div>{move || action_result_view(action.value().get())}</div>
+            <div>
+                <label for="email">"Email"</label>
+                <input type="email" name="email" required/>
+            </div>
+            <div>
+                <input type="submit" value="Register"/>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[derive(Params, PartialEq, Clone, Debug)]
+pub struct TokenQuery {
+    token: Option<String>,
+}
+
+#[component]
+fn EmailBindReqPage() -> impl IntoView {
+    let query = use_query::<TokenQuery>();
+    let resource = Resource::new_blocking(
+        move || query.get().ok().and_then(|q| q.token),
+        move |token| async move {
+            match token {
+                Some(token) => email_bindreq(token.clone()).await
+                    .map(|bindreq| (token, bindreq)),
+                None => Err(crate::error::AuthError::InvalidToken),
+            }
+        },
+    );
+    let complete_action = ServerAction::<CompleteRegistration>::new();
+    let verify_action = ServerAction::<VerifyEmailBindreq>::new();
+    let reject_action = ServerAction::<RejectEmailBindreq>::new();
+
+    let view = move || Suspend::new(async move {
+        resource.await.map(|(token, bindreq)| {
+            let form_token = token.clone();
+            let form = if bindreq.registration {
+                view! {
+                    <p>"Complete the registration for "{bindreq.email}"."</p>
+                    <ActionForm attr:id="complete-registration" action=complete_action>
+                        <div>{move || action_result_view(complete_action.value().get())}</div>
+                        <input type="hidden" name="token" value=form_token/>
+                        <div>
+                            <label for="login">"Login"</label>
+                            <input type="text" name="login" required/>
+                        </div>
+                        <div>
+                            <label for="password">"Password"</label>
+                            <input type="password" name="password" required/>
+                        </div>
+                        <div>
+                            <label for="password_confirm">"Confirm Password"</label>
+                            <input type="password" name="password_confirm" required/>
+                        </div>
+                        <div>
+                            <input type="submit" value="Register"/>
+                        </div>
+                    </ActionForm>
+                }.into_any()
+            } else {
+                view! {
+                    <p>"Verify the email address "{bindreq.email}" for your account."</p>
+                    <ActionForm attr:id="verify-email" action=verify_action>
+                        <div>{move || action_result_view(verify_action.value().get())}</div>
+                        <input type="hidden" name="token" value=form_token/>
+                        <div>
+                            <input type="submit" value="Verify"/>
+                        </div>
+                    </ActionForm>
+                }.into_any()
+            };
+            view! {
+                {form}
+                <p>"If this request was not made by you, it may be declined instead."</p>
+                <ActionForm attr:id="reject-email-bindreq" action=reject_action>
+                    <div>{move || action_result_view(reject_action.value().get())}</div>
+                    <input type="hidden" name="token" value=token/>
+                    <div>
+                        <input type="submit" value="Decline"/>
+                    </div>
+                </ActionForm>
+            }.into_any()
+        })
+        .unwrap_or_else(|e| view! {
+            <p class="standard error">{format!("Error: {e}")}</p>
+        }.into_any())
+    });
+
+    view! {
+        <h1>"Email Verification"</h1>
+        <Transition fallback=move || view! { <p>"Loading..."</p> }>
+            {view}
+        </Transition>
+    }
+}
+
+#[component]
+fn EmailPage() -> impl IntoView {
+    let action = ServerAction::<RequestEmailBind>::new();
+    let resource = Resource::new_blocking(
+        move || (),
+        move |_| async move { current_user_emails().await },
+    );
+
+    let view = move || Suspend::new(async move {
+        resource.await
+            .map(|emails| view! {
+                <ul>
+                    {emails.into_iter()
+                        .map(|email| view! { <li>{email.email}</li> })
+                        .collect_view()}
+                </ul>
+            }.into_any())
+            .unwrap_or_else(|e| view! {
+                <p class="standard error">{format!("Error: {e}")}</p>
+            }.into_any())
+    });
+
+    view! {
+        <h1>"Email Addresses"</h1>
+        <Transition fallback=move || view! { <p>"Loading..."</p> }>
+            {view}
+        </Transition>
+        <ActionForm attr:id="request-email-bind" action=action>
+            <div>{move || action_result_view(action.value().get())}</div>
+            <div>
+                <label for="email">"Add Email"</label>
+                <input type="email" name="email" required/>
+            </div>
+            <div>
+                <input type="submit" value="Add"/>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn IdentitiesPage() -> impl IntoView {
+    let action = ServerAction::<UnlinkIdentity>::new();
+    let resource = Resource::new_blocking(
+        move || action.version().get(),
+        move |_| async move { current_user_identities().await },
+    );
+    let providers = Resource::new_blocking(
+        move || (),
+        move |_| async move { oidc_providers().await },
+    );
+
+    let view = move || Suspend::new(async move {
+        resource.await
+            .map(|identities| view! {
+                <ul>
+                    {identities.into_iter()
+                        .map(|identity| view! {
+                            <li>
+                                {format!("{} ({})", identity.provider, identity.subject)}
+                                <ActionForm action=action>
+                                    <input type="hidden" name="id" value=identity.id/>
+                                    <input type="submit" value="Unlink"/>
+                                </ActionForm>
+                            </li>
+                        })
+                        .collect_view()}
+                </ul>
+            }.into_any())
+            .unwrap_or_else(|e| view! {
+                <p class="standard error">{format!("Error: {e}")}</p>
+            }.into_any())
+    });
+
+    let providers_view = move || Suspend::new(async move {
+        providers.await
+            .map(|providers| view! {
+                <ul>
+                    {providers.into_iter()
+                        .map(|provider| view! {
+                            <li>
+                                <a href=format!("/auth/oidc/{}/link", provider.name) rel="external">
+                                    "Link with "{provider.title}
+                                </a>
+                            </li>
+                        })
+                        .collect_view()}
+                </ul>
+            }.into_any())
+            .unwrap_or_else(|e| view! {
+                <p class="standard error">{format!("Error: {e}")}</p>
+            }.into_any())
+    });
+
+    view! {
+        <h1>"Linked Identities"</h1>
+        <div>{move || action_result_view(action.value().get())}</div>
+        <Transition fallback=move || view! { <p>"Loading..."</p> }>
+            {view}
+        </Transition>
+        <Transition>{providers_view}</Transition>
+    }
+}
+
+#[component]
+fn TokensPage() -> impl IntoView {
+    let create_action = ServerAction::<CreateToken>::new();
+    let revoke_action = ServerAction::<RevokeToken>::new();
+    let resource = Resource::new_blocking(
+        move || (create_action.version().get(), revoke_action.version().get()),
+        move |_| async move { current_user_tokens().await },
+    );
+
+    let date = |ts: i64| Utc.timestamp_opt(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .single()
+        .unwrap_or_else(|| "????-??-??".to_string());
+
+    let view = move || Suspend::new(async move {
+        resource.await
+            .map(|tokens| view! {
+                <ul>
+                    {tokens.into_iter()
+                        .map(|token| {
+                            let scopes = token.scopes.iter()
+                                .map(|action| if action.is_empty() { "view" } else { action })
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            view! {
+                                <li>
+                                    {format!(
+                                        "{} ({scopes}); expires {}",
+                                        token.name,
+                                        date(token.expires_ts),
+                                    )}
+                                    <ActionForm action=revoke_action>
+                                        <input type="hidden" name="id" value=token.id/>
+                                        <input type="submit" value="Revoke"/>
+                                    </ActionForm>
+                                </li>
+                            }
+                        })
+                        .collect_view()}
+                </ul>
+            }.into_any())
+            .unwrap_or_else(|e| view! {
+                <p class="standard error">{format!("Error: {e}")}</p>
+            }.into_any())
+    });
+
+    let created_view = move || match create_action.value().get() {
+        Some(Ok(new_token)) => Some(view! {
+            <p class="standard ok">
+                "The token "{new_token.user_token.name}" has been created; copy it now as \
+                it will not be shown again: "<code>{new_token.token}</code>
+            </p>
+        }.into_any()),
+        Some(Err(e)) => Some(view! {
+            <p class="standard error">{format!("Error: {e}")}</p>
+        }.into_any()),
+        None => None,
+    };
+
+    view! {
+        <h1>"Personal Access Tokens"</h1>
+        <p>
+            "A personal access token may be exchanged for a bearer token through \
+            /api/bearer/from_token, which may then only be used for the actions \
+            within the scopes of the token."
+        </p>
+        <div>{move || action_result_view(revoke_action.value().get())}</div>
+        <Transition fallback=move || view! { <p>"Loading..."</p> }>
+            {view}
+        </Transition>
+        <ActionForm attr:id="create-token" action=create_action>
+            <div>{created_view}</div>
+            <div>
+                <label for="name">"Name"</label>
+                <input type="text" name="name" required/>
+            </div>
+            <fieldset>
+                <legend>"Scopes"</legend>
+                {TOKEN_SCOPES.iter()
+                    .enumerate()
+                    .map(|(i, scope)| view! {
+                        <div>
+                            <label>
+                                <input type="checkbox" name=format!("scopes[{i}]") value=scope.name/>
+                                {scope.description}
+                            </label>
+                        </div>
+                    })
+                    .collect_view()}
+            </fieldset>
+            <div>
+                <label for="days">"Expires in (days)"</label>
+                <input type="number" name="days" min="1" value="30" required/>
+            </div>
+            <div>
+                <input type="submit" value="Create"/>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn PasswordResetPage() -> impl IntoView {
+    let action = ServerAction::<RequestPasswordReset>::new();
+
+    view! {
+        <h1>"Reset Password"</h1>
+        <p>
+            "A link to reset the password will be sent to the email address of the account."
+        </p>
+        <ActionForm attr:id="password-reset" action=action>
+            <div>{move || action_result_view(action.value().get())}</div>
+            <div>
+                <label for="email">"Email"</label>
+                <input type="email" name="email" required/>
+            </div>
+            <div>
+                <input type="submit" value="Reset Password"/>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn PasswordResetReqPage() -> impl IntoView {
+    let query = use_query::<TokenQuery>();
+    let action = ServerAction::<CompletePasswordReset>::new();
+    let token = move || query.get().ok().and_then(|q| q.token).unwrap_or_default();
+
+    view! {
+        <h1>"Reset Password"</h1>
+        <p>"Setting a new password will also sign out all sessions of the account."</p>
+        <ActionForm attr:id="complete-password-reset" action=action>
+            <div>{move || action_result_view(action.value().get())}</div>
+            <input type="hidden" name="token" value=token/>
+            <div>
+                <label for="password">"New Password"</label>
+                <input type="password" name="password" required/>
+            </div>
+            <div>
+                <label for="password_confirm">"Confirm Password"</label>
+                <input type="password" name="password_confirm" required/>
+            </div>
+            <div>
+                <input type="submit" value="Set Password"/>
+            </div>
+        </ActionForm>
+    }
+}
@@ -3,7 +3,16 @@ use leptos::{
     server,
     server_fn,
 };
-use pmrcore::ac::user::User;
+use pmrcore::ac::user::{
+    User,
+    UserEmail,
+    UserIdentity,
+    UserToken,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
 #[cfg(feature = "ssr")]
 mod ssr {
@@ -11,7 +20,10 @@ mod ssr {
     pub use std::str::FromStr;
     pub use crate::{
         server::platform,
-        server::ac::session,
+        server::ac::{
+            origin,
+            session,
+        },
         workflow::state::TRANSITIONS,
     };
 }
@@ -71,6 +83,41 @@ pub async fn bearer_from_login_password(
         .ok_or(AuthError::InternalServerError)?)
 }
 
+/// Acquire a bearer token from a personal access token, where the bearer
+/// token is limited to the scopes of the personal access token.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/bearer/from_token",
+    request_body(
+        description = r#"
+Acquire a bearer token from a personal access token.
+        "#,
+        content((
+            String = "application/json",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("abcdefghijkKJIHGFEDCBA"),
+        )),
+        description = "The bearer token.",
+    ), AppError),
+))]
+#[server(
+    endpoint = "bearer/from_token",
+    input = server_fn::codec::Json,
+)]
+pub async fn bearer_from_token(
+    token: String,
+) -> Result<String, AuthError> {
+    let mut session = session().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    session.sign_in_with_token(token).await?
+        .ok_or(AuthError::InternalServerError)
+}
+
 /// Sets a `SameSite=Strict` session cookie on success.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
@@ -193,16 +240,18 @@ pub async fn workflow_transition(
     resource: String,
     target: String,
 ) -> Result<PolicyState, AppError> {
-    if let Some(user) = current_user().await
+    let session = session().await
         // TODO figure out how to actually get 404 status code working here.
-        .map_err(|_| AppError::Forbidden)?
-    {
+        .map_err(|_| AppError::Forbidden)?;
+    if session.current_user().is_some() {
         let target_state = State::from_str(&target)
             .expect("State::from_str shouldn't have failed!");
         let platform = platform().await
             .map_err(|_| AppError::InternalServerError)?;
 
-        platform.workflow_transition(&user, resource, target_state)
+        // the agent carries the scopes of the token the session may be
+        // derived from.
+        platform.workflow_transition(session.agent(), resource, target_state)
             .await
             .map_err(|_| AppError::InternalServerError)?
             .map(|policy| PolicyState::new(Some(policy), target_state))
@@ -211,3 +260,666 @@ pub async fn workflow_transition(
         Err(AppError::Forbidden)?
     }
 }
+
+/// The email bind request, as presented to the holder of its token.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EmailBindReq {
+    pub email: String,
+    /// Whether the request is for the registration of a new account,
+    /// rather than for an address to be added to an existing account.
+    pub registration: bool,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/request_registration",
+    request_body(
+        description = r#"
+Request the registration of a new account for the email address, where the
+link to complete the registration will be sent to the address.
+        "#,
+        content((
+            String = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("A message has been sent to the email address."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+))]
+#[server(
+    endpoint = "request_registration",
+)]
+pub async fn request_registration(
+    email: String,
+) -> Result<String, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    platform.ac_platform
+        .request_registration(&email, &origin().await?)
+        .await?;
+    Ok(format!(
+        "A message has been sent to {email} with the instructions to complete \
+        the registration."
+    ))
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/email_bindreq",
+    request_body(
+        description = r#"
+Look up the email bind request with the token.
+        "#,
+        content((
+            String = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        description = "The email bind request.",
+        body = EmailBindReq,
+    ), AppError),
+))]
+#[server(
+    endpoint = "email_bindreq",
+)]
+pub async fn email_bindreq(
+    token: String,
+) -> Result<EmailBindReq, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    let bindreq = platform.ac_platform
+        .get_email_bindreq(&token)
+        .await?;
+    Ok(EmailBindReq {
+        email: bindreq.email,
+        registration: bindreq.origin_user_id.is_none(),
+    })
+}
+
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct CompleteRegistrationArgs {
+    token: String,
+    login: String,
+    password: String,
+    password_confirm: String,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/complete_registration",
+    request_body(
+        description = r#"
+Complete the registration of a new account with the token sent to the email
+address.
+        "#,
+        content((
+            CompleteRegistrationArgs = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("The account has been registered."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+))]
+#[server(
+    endpoint = "complete_registration",
+)]
+pub async fn complete_registration(
+    token: String,
+    login: String,
+    password: String,
+    password_confirm: String,
+) -> Result<String, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    let user = platform.ac_platform
+        .complete_registration(&token, &login, &password, &password_confirm)
+        .await?;
+    Ok(format!(
+        "The account {} has been registered; you may now sign in.",
+        user.name(),
+    ))
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/verify_email_bindreq",
+    request_body(
+        description = r#"
+Add the email address to the account that requested it with the token sent
+to the address.
+        "#,
+        content((
+            String = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("The email address has been verified."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+))]
+#[server(
+    endpoint = "verify_email_bindreq",
+)]
+pub async fn verify_email_bindreq(
+    token: String,
+) -> Result<String, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    platform.ac_platform
+        .verify_user_email_bind(&token)
+        .await?;
+    Ok("The email address has been verified.".to_string())
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/reject_email_bindreq",
+    request_body(
+        description = r#"
+Decline the email bind request with the token, for use by the owner of the
+email address who did not make the request.
+        "#,
+        content((
+            String = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("The request has been declined."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+))]
+#[server(
+    endpoint = "reject_email_bindreq",
+)]
+pub async fn reject_email_bindreq(
+    token: String,
+) -> Result<String, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    platform.ac_platform
+        .reject_email_bindreq(&token)
+        .await?;
+    Ok("The request has been declined.".to_string())
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/current_user_emails",
+    responses((
+        status = 200,
+        description = "The email addresses of the current user.",
+        body = Vec<UserEmail>,
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "current_user_emails",
+)]
+pub async fn current_user_emails() -> Result<Vec<UserEmail>, AuthError> {
+    let user = session().await
+        .map_err(|_| AuthError::InternalServerError)?
+        .current_account_user()?;
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    Ok(platform.ac_platform
+        .get_user_emails(user.id)
+        .await?)
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/request_email_bind",
+    request_body(
+        description = r#"
+Request the email address be added to the current user, where the link to
+verify the address will be sent to the address.
+        "#,
+        content((
+            String = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("A message has been sent to the email address."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "request_email_bind",
+)]
+pub async fn request_email_bind(
+    email: String,
+) -> Result<String, AuthError> {
+    let user = session().await
+        .map_err(|_| AuthError::InternalServerError)?
+        .current_account_user()?;
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    platform.ac_platform
+        .request_user_email_bind(user.id, &email, &origin().await?)
+        .await?;
+    Ok(format!(
+        "A message has been sent to {email} with the instructions to verify \
+        the address."
+    ))
+}
+
+/// An OpenID Connect provider that users may sign in with.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct OidcProvider {
+    pub name: String,
+    pub title: String,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/oidc_providers",
+    responses((
+        status = 200,
+        description = "The OpenID Connect providers that users may sign in with, \
+            through `/auth/oidc/{name}/login`.",
+        body = Vec<OidcProvider>,
+    ), AppError),
+))]
+#[server(
+    endpoint = "oidc_providers",
+)]
+pub async fn oidc_providers() -> Result<Vec<OidcProvider>, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    Ok(platform.ac_platform
+        .oidc_providers()
+        .map(|config| OidcProvider {
+            name: config.name.clone(),
+            title: config.title.clone(),
+        })
+        .collect())
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/current_user_identities",
+    responses((
+        status = 200,
+        description = "The identities from the OpenID Connect providers linked to the \
+            current user.",
+        body = Vec<UserIdentity>,
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "current_user_identities",
+)]
+pub async fn current_user_identities() -> Result<Vec<UserIdentity>, AuthError> {
+    let user = session().await
+        .map_err(|_| AuthError::InternalServerError)?
+        .current_account_user()?;
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    Ok(platform.ac_platform
+        .get_user_identities(user.id)
+        .await?)
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/unlink_identity",
+    request_body(
+        description = r#"
+Unlink the identity with the id from the current user.
+        "#,
+        content((
+            i64 = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("The identity has been unlinked."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "unlink_identity",
+)]
+pub async fn unlink_identity(
+    id: i64,
+) -> Result<String, AuthError> {
+    let user = session().await
+        .map_err(|_| AuthError::InternalServerError)?
+        .current_account_user()?;
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    if platform.ac_platform
+        .unlink_user_identity(user.id, id)
+        .await?
+    {
+        Ok("The identity has been unlinked.".to_string())
+    } else {
+        Err(AuthError::InvalidCredentials)
+    }
+}
+
+/// A group of actions that a personal access token may be limited to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TokenScope {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub actions: &'static [&'static str],
+}
+
+/// The groups of actions available for the personal access tokens.
+pub const TOKEN_SCOPES: &[TokenScope] = &[
+    TokenScope {
+        name: "view",
+        description: "View workspaces and exposures, and clone workspaces through git",
+        actions: &[""],
+    },
+    TokenScope {
+        name: "create",
+        description: "Create workspaces and exposures",
+        actions: &["", "create"],
+    },
+    TokenScope {
+        name: "edit",
+        description: "Edit exposures, and push to workspaces through git",
+        actions: &["", "edit"],
+    },
+    TokenScope {
+        name: "synchronize",
+        description: "Synchronize workspaces with their remotes",
+        actions: &["", "protocol_write"],
+    },
+    TokenScope {
+        name: "workflow",
+        description: "Publish and otherwise transition the workflow state of workspaces and exposures",
+        actions: &["", "workflow"],
+    },
+    TokenScope {
+        name: "exposure_wizard",
+        description: "Create exposures and build them through the exposure wizard",
+        actions: &["", "create", "edit"],
+    },
+];
+
+/// A newly created personal access token, where the token is only
+/// provided this once.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct NewUserToken {
+    pub token: String,
+    pub user_token: UserToken,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/current_user_tokens",
+    responses((
+        status = 200,
+        description = "The personal access tokens of the current user.",
+        body = Vec<UserToken>,
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "current_user_tokens",
+)]
+pub async fn current_user_tokens() -> Result<Vec<UserToken>, AuthError> {
+    let user = session().await
+        .map_err(|_| AuthError::InternalServerError)?
+        .current_account_user()?;
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    Ok(platform.ac_platform
+        .get_user_tokens(user.id)
+        .await?)
+}
+
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct CreateTokenArgs {
+    name: String,
+    /// The names of the groups of actions the token is limited to.
+    scopes: Vec<String>,
+    /// The number of days until the token expires.
+    days: i64,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/create_token",
+    request_body(
+        description = r#"
+Create a personal access token for the current user, limited to the groups
+of actions named by the scopes, i.e. `view`, `create`, `edit`,
+`synchronize`, `workflow` or `exposure_wizard`.
+        "#,
+        content((
+            CreateTokenArgs = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        description = "The new token, which will not be provided again.",
+        body = NewUserToken,
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "create_token",
+)]
+pub async fn create_token(
+    name: String,
+    #[server(default)]
+    scopes: Vec<String>,
+    days: i64,
+) -> Result<NewUserToken, AuthError> {
+    let user = session().await
+        .map_err(|_| AuthError::InternalServerError)?
+        .current_account_user()?;
+    let mut actions = Vec::new();
+    for scope in scopes.iter() {
+        let scope = TOKEN_SCOPES.iter()
+            .find(|token_scope| token_scope.name == scope)
+            .ok_or(AuthError::InvalidTokenScopes)?;
+        actions.extend(scope.actions.iter().map(|action| action.to_string()));
+    }
+    actions.sort_unstable();
+    actions.dedup();
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    let (token, user_token) = platform.ac_platform
+        .create_user_token(
+            user.id,
+            &name,
+            &actions,
+            days.checked_mul(86400).ok_or(AuthError::InvalidTokenLifetime)?,
+        )
+        .await?;
+    Ok(NewUserToken { token, user_token })
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/revoke_token",
+    request_body(
+        description = r#"
+Revoke the personal access token with the id from the current user, which
+also signs out the sessions acquired with it.
+        "#,
+        content((
+            i64 = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("The token has been revoked."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "revoke_token",
+)]
+pub async fn revoke_token(
+    id: i64,
+) -> Result<String, AuthError> {
+    let user = session().await
+        .map_err(|_| AuthError::InternalServerError)?
+        .current_account_user()?;
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    if platform.ac_platform
+        .revoke_user_token(user.id, id)
+        .await?
+    {
+        Ok("The token has been revoked.".to_string())
+    } else {
+        Err(AuthError::InvalidCredentials)
+    }
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/request_password_reset",
+    request_body(
+        description = r#"
+Request the password of the account bound to the email address be reset,
+where the link to set the new password will be sent to the address.
+        "#,
+        content((
+            String = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("A message has been sent to the email address."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+))]
+#[server(
+    endpoint = "request_password_reset",
+)]
+pub async fn request_password_reset(
+    email: String,
+) -> Result<String, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    platform.ac_platform
+        .request_password_reset(&email, &origin().await?)
+        .await?;
+    Ok(format!(
+        "If an account is registered with {email}, a message has been sent to \
+        it with the instructions to reset the password."
+    ))
+}
+
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct CompletePasswordResetArgs {
+    token: String,
+    password: String,
+    password_confirm: String,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/complete_password_reset",
+    request_body(
+        description = r#"
+Set the new password with the token sent to the email address, which also
+signs out all sessions of the account.
+        "#,
+        content((
+            CompletePasswordResetArgs = "application/x-www-form-urlencoded",
+        )),
+    ),
+    responses((
+        status = 200,
+        content((
+            String = "application/json",
+            example = json!("The password has been reset."),
+        )),
+        description = "Message describing the outcome.",
+    ), AppError),
+))]
+#[server(
+    endpoint = "complete_password_reset",
+)]
+pub async fn complete_password_reset(
+    token: String,
+    password: String,
+    password_confirm: String,
+) -> Result<String, AuthError> {
+    let platform = platform().await
+        .map_err(|_| AuthError::InternalServerError)?;
+    let user = platform.ac_platform
+        .complete_password_reset(&token, &password, &password_confirm)
+        .await?;
+    Ok(format!(
+        "The password for {} has been reset; you may now sign in.",
+        user.name(),
+    ))
+}
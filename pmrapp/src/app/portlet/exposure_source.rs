@@ -31,7 +31,9 @@ impl IntoRender for ExposureSourceItem {
                     <a href=format!("{prefix}file/{commit_id}/")>
                         {commit_id.get(..12).unwrap_or(&commit_id).to_string()}
                     </a>
-                    ".
+                    " ("
+                    <a href=format!("{prefix}diff/HEAD/?from={commit_id}")>"compare with latest"</a>
+                    ").
                 "</div>
             </section>
         }
@@ -1,6 +1,21 @@
 use leptos::{IntoView, component, logging, view};
 use leptos::prelude::*;
-use leptos_router::hooks::use_location;
+use leptos_router::{
+    hooks::{
+        use_location,
+        use_query_map,
+    },
+    params::Params,
+};
+use pmrcore::{
+    ac::workflow::State,
+    listing::{
+        Cursor,
+        Listing,
+        SortKey,
+        SortOrder,
+    },
+};
 
 #[component]
 pub fn Redirect(
@@ -116,6 +131,26 @@ pub fn SelectMap(
     }
 }
 
+#[component]
+pub fn SelectMultiple(
+    name: String,
+    options: Vec<String>,
+    #[prop(default = vec![])] values: Vec<String>,
+) -> impl IntoView {
+    let options_view = options.into_iter()
+        .map(|option| {
+            let selected = values.contains(&option).then_some("selected");
+            let label = option.clone();
+            view! { <option value=option selected=selected>{label}</option> }
+        })
+        .collect_view();
+    view! {
+        <select id=name.clone() name=name multiple="multiple">
+            {options_view}
+        </select>
+    }
+}
+
 #[component]
 pub fn Spinner() -> impl IntoView {
     view! {
@@ -126,3 +161,53 @@ pub fn Spinner() -> impl IntoView {
         </div>
     }
 }
+
+/// The query parameters accepted by the paginated listings.
+#[derive(Params, PartialEq, Clone, Debug)]
+pub struct ListingParams {
+    sort: Option<SortKey>,
+    order: Option<SortOrder>,
+    after: Option<Cursor>,
+    limit: Option<u32>,
+    state: Option<State>,
+    owner: Option<String>,
+    has_exposure: Option<bool>,
+}
+
+impl From<ListingParams> for Listing {
+    fn from(params: ListingParams) -> Self {
+        Listing {
+            sort: params.sort.unwrap_or_default(),
+            order: params.order.unwrap_or_default(),
+            after: params.after,
+            limit: params.limit,
+            state: params.state,
+            owner: params.owner,
+            has_exposure: params.has_exposure,
+        }
+    }
+}
+
+/// Link to the following page of a listing, retaining the current query
+/// with only the cursor (provided through the `key` parameter) replaced.
+#[component]
+pub fn NextPage<T>(
+    next: Option<T>,
+    #[prop(default = "after")]
+    key: &'static str,
+) -> impl IntoView
+where
+    T: std::fmt::Display + Send + Sync + 'static,
+{
+    let query = use_query_map();
+    next.map(|next| {
+        let href = query.with_untracked(|query| {
+            let mut query = query.clone();
+            query.replace(key, next.to_string());
+            query.to_query_string()
+        });
+        view! {
+            <div class="next-page"><a href=href>"Next page"</a></div>
+        }
+    })
+}
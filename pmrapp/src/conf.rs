@@ -1,5 +1,9 @@
 use clap::Parser;
-use pmrctrl::platform::Builder;
+use pmrctrl::{
+    platform::Builder,
+    scheduler::SyncPolicy,
+};
+use pmrtqs::executor::OrphanPolicy;
 
 #[derive(Debug, Parser)]
 pub struct Cli {
@@ -7,8 +11,29 @@ pub struct Cli {
     pub cors_allow_origins: Vec<String>,
     #[clap(flatten)]
     pub platform_builder: Builder,
+    #[clap(flatten)]
+    pub sync_policy: SyncPolicy,
     #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
     pub verbose: u8,
     #[clap(long, default_value = "0")]
     pub with_runners: usize,
+    /// How the runners recover the tasks orphaned by a previous instance.
+    #[clap(long, value_enum, default_value_t = OrphanPolicy::Fail)]
+    pub orphan_policy: OrphanPolicy,
+    /// Run the scheduler that synchronizes the workspaces with their remote
+    /// repositories in this process.
+    #[clap(long)]
+    pub with_sync_scheduler: bool,
+    /// The secret for verifying the events received by the webhook, which
+    /// is only available with the secret and the sync scheduler.
+    #[clap(long, value_name = "PMR_WEBHOOK_SECRET", env = "PMR_WEBHOOK_SECRET")]
+    pub webhook_secret: Option<String>,
+    /// The token the remote runners must provide to claim the tasks from
+    /// this server, which are only accepted with the token.
+    #[clap(long, value_name = "PMR_RUNNER_TOKEN", env = "PMR_RUNNER_TOKEN")]
+    pub runner_token: Option<String>,
+    /// The duration in seconds of the leases on the tasks claimed by the
+    /// remote runners.
+    #[clap(long, default_value = "60")]
+    pub runner_lease: i64,
 }
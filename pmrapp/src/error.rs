@@ -130,7 +130,60 @@ impl FromServerFnError for AppError {
 #[cfg(feature = "ssr")]
 mod ssr {
     use axum::response::{IntoResponse, Response};
-    use crate::error::AppError;
+    use pmrac::error::{
+        Error as ACError,
+        IdentityError,
+        PasswordError,
+        RegistrationError,
+        TokenError,
+    };
+    use crate::error::{
+        AppError,
+        AuthError,
+    };
+
+    impl From<ACError> for AuthError {
+        fn from(e: ACError) -> Self {
+            match e {
+                ACError::Authentication(_) => AuthError::InvalidCredentials,
+                ACError::Password(PasswordError::Mismatched) => AuthError::PasswordMismatched,
+                ACError::Registration(e) => match e {
+                    RegistrationError::EmailUnavailable => AuthError::EmailUnavailable,
+                    RegistrationError::InvalidEmail => AuthError::InvalidEmail,
+                    RegistrationError::InvalidName => AuthError::InvalidName,
+                    RegistrationError::InvalidToken => AuthError::InvalidToken,
+                    RegistrationError::NameUnavailable => AuthError::NameUnavailable,
+                    RegistrationError::RateLimited => AuthError::RateLimited,
+                    RegistrationError::Unavailable => AuthError::RegistrationUnavailable,
+                    e => {
+                        log::error!("{e}");
+                        AuthError::InternalServerError
+                    }
+                },
+                ACError::Token(e) => match e {
+                    TokenError::InvalidName => AuthError::InvalidTokenName,
+                    TokenError::InvalidScopes => AuthError::InvalidTokenScopes,
+                    TokenError::InvalidLifetime => AuthError::InvalidTokenLifetime,
+                    e => {
+                        log::error!("{e}");
+                        AuthError::InternalServerError
+                    }
+                },
+                ACError::Identity(e) => match e {
+                    IdentityError::Unavailable => AuthError::IdentityUnavailable,
+                    IdentityError::Unlinked => AuthError::IdentityUnlinked,
+                    e => {
+                        log::warn!("{e}");
+                        AuthError::InvalidCredentials
+                    }
+                },
+                e => {
+                    log::error!("{e}");
+                    AuthError::InternalServerError
+                }
+            }
+        }
+    }
 
     impl IntoResponse for AppError {
         fn into_response(self) -> Response {
@@ -147,6 +200,20 @@ pub enum AuthError {
     InvalidCredentials,
     NetworkError,
     SerdeError,
+    EmailUnavailable,
+    IdentityUnavailable,
+    IdentityUnlinked,
+    InvalidEmail,
+    InvalidName,
+    InvalidToken,
+    InvalidTokenName,
+    InvalidTokenScopes,
+    InvalidTokenLifetime,
+    NameUnavailable,
+    PasswordMismatched,
+    RateLimited,
+    RegistrationUnavailable,
+    TokenSession,
 }
 
 impl From<AuthError> for &'static str {
@@ -156,6 +223,22 @@ impl From<AuthError> for &'static str {
             AuthError::InvalidCredentials => "Invalid credentials provided",
             AuthError::NetworkError => "Network error",
             AuthError::SerdeError => "Encoding error (is the application out of date?)",
+            AuthError::EmailUnavailable => "The email address is not available",
+            AuthError::IdentityUnavailable => "The identity is linked to another account",
+            AuthError::IdentityUnlinked => "The identity is not linked to any account; \
+                sign in and link it to the account first",
+            AuthError::InvalidEmail => "Invalid email address",
+            AuthError::InvalidName => "Invalid login; it must not be empty or contain spaces",
+            AuthError::InvalidToken => "Invalid or expired token",
+            AuthError::InvalidTokenName => "The token must have a name",
+            AuthError::InvalidTokenScopes => "The token must permit at least one action",
+            AuthError::InvalidTokenLifetime => "The token must expire within the permitted time",
+            AuthError::NameUnavailable => "The login is not available",
+            AuthError::PasswordMismatched => "The passwords do not match",
+            AuthError::RateLimited => "Too many requests, please try again later",
+            AuthError::RegistrationUnavailable => "Registration is unavailable",
+            AuthError::TokenSession => "The account may not be managed through a personal \
+                access token",
         }
     }
 }
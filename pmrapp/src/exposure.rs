@@ -8,7 +8,10 @@ use leptos_router::{
         ParentRoute,
         Route,
     },
-    hooks::use_params,
+    hooks::{
+        use_params,
+        use_query,
+    },
     nested_router::Outlet,
     params::Params,
     ParamSegment,
@@ -22,6 +25,10 @@ use pmrcore::{
         self,
         profile::ExposureFileProfile,
     },
+    listing::{
+        Listing,
+        Page,
+    },
     profile::UserPromptGroup,
     task_template::{
         UserArg,
@@ -45,10 +52,13 @@ pub mod api;
 use crate::{
     ac::AccountCtx,
     component::{
+        ListingParams,
+        NextPage,
         Redirect,
         RedirectTS,
         SelectList,
         SelectMap,
+        SelectMultiple,
         Spinner,
     },
     error::AppError,
@@ -64,11 +74,13 @@ use crate::{
         resolve_exposure_path,
         update_wizard_field,
         wizard,
-        Exposures,
+        wizard_build_status,
         ExposureInfo,
+        ExposurePage,
         ResolvedExposurePath,
         WizardAddFile,
         WizardBuild,
+        WizardViewStatus,
         WIZARD_FIELD_ROUTE,
     },
     view::{
@@ -147,7 +159,8 @@ pub fn ExposureRoot() -> impl IntoView {
                 // this ensures if not taken, this will take it and effect the
                 // drop to release the lock.
                 let _ = take_context::<SsrWriteSignal<Option<PolicyState>>>();
-                result
+                // the aliased exposures are not paginated.
+                result.map(|entries| Page { entries, next: None })
             }
         },
     );
@@ -165,15 +178,17 @@ pub fn ExposureRoot() -> impl IntoView {
 #[component]
 pub fn ExposureIdRoot() -> impl IntoView {
     let account_ctx = expect_context::<AccountCtx>();
+    let query = use_query::<ListingParams>();
     let exposures = Resource::new_blocking(
-        move || (),
-        move |_| {
+        move || query.get().ok().map(Listing::from),
+        move |listing| {
             let set_ps = account_ctx.policy_state.write_only();
             async move {
+                let listing = listing.ok_or(AppError::BadRequest)?;
                 // required by notify_into_inner which will take it.
                 provide_context(set_ps);
                 // id root uses id.
-                let result = list_exposures()
+                let result = list_exposures(listing)
                     .await
                     .map(EnforcedOk::notify_into_inner);
                 // this ensures if not taken, this will take it and effect the
@@ -210,21 +225,26 @@ pub fn ExposureListing() -> impl IntoView {
     //     let policy_state = account_ctx.policy_state.inner_write_signal();
     //     move || policy_state.set(PolicyState::default())
     // });
-    let exposures = expect_context::<Resource<Result<Exposures, AppError>>>();
+    let exposures = expect_context::<Resource<Result<ExposurePage, AppError>>>();
 
     let exposure_listing = move || Suspend::new(async move {
-        exposures.await.map(|exposures| exposures
-            .into_iter()
-            .map(move |exposure| view! {
-                <div>
-                    <div><a href=format!("{root}{}/", exposure.alias)>
-                        "Exposure "{exposure.entity.id}
-                    </a></div>
-                    <div>{exposure.entity.description}</div>
-                </div>
-            })
-            .collect_view()
-        )
+        exposures.await.map(|exposures| {
+            let entries = exposures.entries
+                .into_iter()
+                .map(move |exposure| view! {
+                    <div>
+                        <div><a href=format!("{root}{}/", exposure.alias)>
+                            "Exposure "{exposure.entity.id}
+                        </a></div>
+                        <div>{exposure.entity.description}</div>
+                    </div>
+                })
+                .collect_view();
+            view! {
+                {entries}
+                <NextPage next=exposures.next/>
+            }
+        })
     });
 
     view! {
@@ -412,7 +432,13 @@ pub fn ExposureMain() -> impl IntoView {
         let base_href: Arc<str> = entity_root.get().to_string().into();
         exposure_info.await.map(|info| view! {
             <h1>"Viewing exposure "{info.exposure.id}</h1>
-            <ExposureFileListing base_href=base_href files=info.files/>
+            <ExposureFileListing base_href=base_href.clone() files=info.files/>
+            <div class="archive-download">
+                "Download all files: "
+                <a href=format!("{base_href}/:/archive/zip") target="_self">"zip"</a>
+                " "
+                <a href=format!("{base_href}/:/archive/tar.gz") target="_self">"tar.gz"</a>
+            </div>
         })
     });
 
@@ -560,7 +586,14 @@ pub fn WizardField(
     user_arg: UserArg,
 )-> impl IntoView {
     let ef_profile_ref = ef_profile.as_ref();
-    let field_input = ef_profile_ref.user_input.get(&user_arg.id).map(|s| s.to_string());
+    let field_values = ef_profile_ref.user_input.get(&user_arg.id)
+        .map(|input| input.values()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+        )
+        .unwrap_or_default();
+    let field_input = field_values.first().cloned();
     let name = format!("{}-{}", ef_profile_ref.exposure_file_id, user_arg.id);
     let (v, _) = arc_signal(field_input.clone());
 
@@ -572,16 +605,19 @@ pub fn WizardField(
     // this is for the fadeout transition for the okay status
     let (status_okay_class, set_status_okay_class) = signal("okay".to_string());
 
-    let action = ArcAction::new(move |(name, value): &(String, String)| {
+    // the values are sent as repeated fields, such that arguments that
+    // accept multiple values may be updated.
+    let action = ArcAction::new(move |(name, values): &(String, Vec<String>)| {
         let name = name.to_owned();
-        let value = value.to_owned();
+        let values = values.to_owned();
         let status_clear = status_clear_clone.clone();
         async move {
-            logging::log!("sending update to field {name:?} with {value:?}");
-            let result = update_wizard_field(vec![
+            logging::log!("sending update to field {name:?} with {values:?}");
+            let mut fields = vec![
                 ("exposure_id".to_string(), exposure_id.to_string()),
-                (name, value),
-            ]).await;
+            ];
+            fields.extend(values.into_iter().map(|value| (name.clone(), value)));
+            let result = update_wizard_field(fields).await;
             // TODO only clear status if result is Ok?
             status_clear.get()
                 .map(|a| a.dispatch(()));
@@ -635,64 +671,117 @@ pub fn WizardField(
             send_wrapper::SendWrapper::new(async move {
                 gloo_timers::future::TimeoutFuture::new(_delay).await
             }).await;
-            action.dispatch((name, value))
+            action.dispatch((name, vec![value]))
         }
     });
 
-    let field_element = if let Some(choices) = user_arg.choices {
-        let name = name.clone();
-        let options = <Vec<UserChoice>>::from(choices)
-            .into_iter()
-            .map(|UserChoice(choice, _)| choice)
-            .collect::<Vec<_>>();
-        // this is used for making sure clients with active scripting (i.e. with immediate
-        // update capabilities), the expected option is selected, rather than relying on
-        // the browser leaving it at a possible stale value.
-        view! {
-            <SelectList name options value=field_input
-                on:change=move |ev| {
-                    let element = ev
-                        .unchecked_ref::<web_sys::Event>()
-                        .target()
-                        .unwrap_throw()
-                        .unchecked_into::<web_sys::HtmlSelectElement>();
-                    let name = element.name();
-                    let value = element.value();
-                    action.dispatch((name, value));
-                }
-                prop:value=move || v.get().unwrap_or("".to_string())
-            />
-        }.into_any()
-    } else {
-        let name = name.clone();
-        view! {
-            <input type="text" id=name.clone() name=name value=field_input
-                prop:value=move || v.get().unwrap_or("".to_string())
-                on:keyup=move |ev| {
-                    let element = ev
-                        .unchecked_ref::<web_sys::Event>()
-                        .target()
-                        .unwrap_throw()
-                        .unchecked_into::<web_sys::HtmlInputElement>();
-                    let value = element.value();
-                    // the keyup can be triggered by navigating within the
-                    // field, so validate the content has in fact changed.
-                    if Some(&value) != current.as_ref() {
+    let field_element = match (user_arg.choices, user_arg.multiple) {
+        (Some(choices), true) => {
+            let name = name.clone();
+            let options = <Vec<UserChoice>>::from(choices)
+                .into_iter()
+                .map(|UserChoice(choice, _)| choice)
+                .collect::<Vec<_>>();
+            view! {
+                <SelectMultiple name options values=field_values
+                    on:change=move |ev| {
+                        let element = ev
+                            .unchecked_ref::<web_sys::Event>()
+                            .target()
+                            .unwrap_throw()
+                            .unchecked_into::<web_sys::HtmlSelectElement>();
                         let name = element.name();
-                        // abort the existing abort handle, if any
-                        abort_handle
-                            .take()
-                            .map(ActionAbortHandle::abort);
-                        // record the update here while also dispatch the
-                        // action with a small delay for the newly set
-                        // abort handle to repeat the cycle, effectively
-                        // function as a debouncer.
-                        current = Some(value.clone());
-                        abort_handle = Some(delayed_action.dispatch((name, value, 500)));
+                        let selected = element.selected_options();
+                        let values = (0..selected.length())
+                            .filter_map(|idx| selected.item(idx))
+                            .map(|option| option
+                                .unchecked_into::<web_sys::HtmlOptionElement>()
+                                .value()
+                            )
+                            .collect::<Vec<_>>();
+                        action.dispatch((name, values));
                     }
-                }
-            />
-        }.into_any()
+                />
+            }.into_any()
+        }
+        (Some(choices), false) => {
+            let name = name.clone();
+            let options = <Vec<UserChoice>>::from(choices)
+                .into_iter()
+                .map(|UserChoice(choice, _)| choice)
+                .collect::<Vec<_>>();
+            // this is used for making sure clients with active scripting (i.e. with immediate
+            // update capabilities), the expected option is selected, rather than relying on
+            // the browser leaving it at a possible stale value.
+            view! {
+                <SelectList name options value=field_input
+                    on:change=move |ev| {
+                        let element = ev
+                            .unchecked_ref::<web_sys::Event>()
+                            .target()
+                            .unwrap_throw()
+                            .unchecked_into::<web_sys::HtmlSelectElement>();
+                        let name = element.name();
+                        let value = element.value();
+                        action.dispatch((name, vec![value]));
+                    }
+                    prop:value=move || v.get().unwrap_or("".to_string())
+                />
+            }.into_any()
+        }
+        (None, true) => {
+            // without choices, each line is taken as a separate value.
+            let name = name.clone();
+            view! {
+                <textarea id=name.clone() name=name
+                    on:change=move |ev| {
+                        let element = ev
+                            .unchecked_ref::<web_sys::Event>()
+                            .target()
+                            .unwrap_throw()
+                            .unchecked_into::<web_sys::HtmlTextAreaElement>();
+                        let name = element.name();
+                        let values = element.value()
+                            .lines()
+                            .filter(|line| !line.is_empty())
+                            .map(str::to_string)
+                            .collect::<Vec<_>>();
+                        action.dispatch((name, values));
+                    }
+                >{field_values.join("\n")}</textarea>
+            }.into_any()
+        }
+        (None, false) => {
+            let name = name.clone();
+            view! {
+                <input type="text" id=name.clone() name=name value=field_input
+                    prop:value=move || v.get().unwrap_or("".to_string())
+                    on:keyup=move |ev| {
+                        let element = ev
+                            .unchecked_ref::<web_sys::Event>()
+                            .target()
+                            .unwrap_throw()
+                            .unchecked_into::<web_sys::HtmlInputElement>();
+                        let value = element.value();
+                        // the keyup can be triggered by navigating within the
+                        // field, so validate the content has in fact changed.
+                        if Some(&value) != current.as_ref() {
+                            let name = element.name();
+                            // abort the existing abort handle, if any
+                            if let Some(handle) = abort_handle.take() {
+                                handle.abort();
+                            }
+                            // record the update here while also dispatch the
+                            // action with a small delay for the newly set
+                            // abort handle to repeat the cycle, effectively
+                            // function as a debouncer.
+                            current = Some(value.clone());
+                            abort_handle = Some(delayed_action.dispatch((name, value, 500)));
+                        }
+                    }
+                />
+            }.into_any()
+        }
     };
     view! {
         <label for=name>
@@ -726,6 +815,77 @@ pub fn WizardField(
     }
 }
 
+#[component]
+pub fn WizardBuildStatus(
+    exposure_id: i64,
+    build_action: ServerAction<WizardBuild>,
+) -> impl IntoView {
+    let (refresh, set_refresh) = signal(0usize);
+    let status_res = Resource::new(
+        move || (build_action.version().get(), refresh.get()),
+        move |_| wizard_build_status(exposure_id),
+    );
+
+    // keep refreshing the status while any builds remain pending
+    let delayed_refresh = Action::new(move |()| async move {
+        #[cfg(not(feature = "ssr"))]
+        send_wrapper::SendWrapper::new(async move {
+            gloo_timers::future::TimeoutFuture::new(2000).await
+        }).await;
+        set_refresh.update(|n| *n += 1);
+    });
+    Effect::new(move |_| {
+        if let Some(Ok(statuses)) = status_res.get()
+            && statuses.iter().any(|WizardViewStatus { status, .. }| status.task
+                .as_ref()
+                .is_some_and(|task| task.state.is_pending())
+            )
+        {
+            delayed_refresh.dispatch(());
+        }
+    });
+
+    let status_view = move || Suspend::new(async move {
+        status_res.await.map(|statuses| {
+            if statuses.is_empty() {
+                return view! { <p>"No views have been configured for building."</p> }.into_any();
+            }
+            let items = statuses.into_iter()
+                .map(|WizardViewStatus { status, output }| {
+                    let (state, failure) = match status.task {
+                        Some(task) => (
+                            task.state.to_string(),
+                            task.failure.map(|failure| format!(" ({failure})")),
+                        ),
+                        None => ("unknown".to_string(), None),
+                    };
+                    let output = output
+                        .filter(|output| !output.is_empty())
+                        .map(|output| view! { <pre>{output}</pre> });
+                    view! {
+                        <li>
+                            {status.workspace_file_path}" \u{2014} "{status.view_key}
+                            <span class=format!("state {state}")>{state.clone()}</span>
+                            {failure}
+                            {output}
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! { <ul class="build-status">{items}</ul> }.into_any()
+        })
+    });
+
+    view! {
+        <fieldset>
+            <legend>"Build Status"</legend>
+            <Transition>
+                {status_view}
+            </Transition>
+        </fieldset>
+    }
+}
+
 #[component]
 pub fn Wizard() -> impl IntoView {
     let wizard_add_file = ServerAction::<WizardAddFile>::new();
@@ -871,6 +1031,7 @@ pub fn Wizard() -> impl IntoView {
                     </fieldset>
                 </Form>
                 {build_form}
+                <WizardBuildStatus exposure_id build_action=wizard_build/>
             }
         })
     });
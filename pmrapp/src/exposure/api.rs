@@ -10,6 +10,11 @@ use pmrcore::{
         ExposureFile,
         ExposureFileView,
         profile::ExposureFileProfile,
+        task::ExposureFileViewTaskStatus,
+    },
+    listing::{
+        Listing,
+        Page,
     },
     profile::{
         Profile,
@@ -45,10 +50,15 @@ mod ssr {
             ExposureFileView as _,
         },
     };
+    pub use pmrcore::task::{
+        TaskLog,
+        TaskState,
+    };
     pub use pmrctrl::error::CtrlError;
     pub use std::borrow::Cow;
     pub use crate::{
         server::{
+            listing_error,
             log_error,
             platform,
             ac::session,
@@ -60,14 +70,51 @@ mod ssr {
 use self::ssr::*;
 
 pub type Exposures = Vec<AliasEntry<Exposure>>;
+pub type ExposurePage = Page<AliasEntry<Exposure>>;
+
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct ListExposuresArgs {
+    listing: Option<Listing>,
+}
 
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
     path = "/api/list_exposures",
+    request_body(
+        description = r#"
+List a page of exposures.  Default values will be used where the listing, or any of its fields,
+are omitted; note that `has_exposure` has no effect for this listing.
+
+The `next` cursor from a page may be provided as `after` to retrieve the following page, along with
+the same `sort` that produced it.
+        "#,
+        content((
+            ListExposuresArgs = "application/json",
+            examples(
+                ("Example 1" = (
+                    summary = "The first page of exposures using the default listing.",
+                    value = json!({})
+                )),
+                ("Example 2" = (
+                    summary = "The page of exposures owned by the user `admin` following the provided cursor, sorted by description.",
+                    value = json!({
+                        "listing": {
+                            "sort": "description",
+                            "after": "d42.Example model",
+                            "owner": "admin"
+                        }
+                    })
+                )),
+            )
+        )),
+    ),
     responses((
         status = 200,
-        description = "List of exposures within an EnforcedOk; wrapped in EnforcedOk due to typical usage as a top level page listing.",
-        body = EnforcedOk<Exposures>,
+        description = "Page of exposures within an EnforcedOk; wrapped in EnforcedOk due to typical usage as a top level page listing.",
+        body = EnforcedOk<ExposurePage>,
     ), AppError),
     security(
         (),
@@ -75,21 +122,24 @@ pub type Exposures = Vec<AliasEntry<Exposure>>;
         ("bearer" = []),
     ),
 ))]
-#[server(endpoint = "list_exposures")]
-pub async fn list_exposures() -> Result<EnforcedOk<Exposures>, AppError> {
+#[server(
+    endpoint = "list_exposures",
+    input = server_fn::codec::Json,
+)]
+pub async fn list_exposures(
+    #[server(default)]
+    listing: Listing,
+) -> Result<EnforcedOk<ExposurePage>, AppError> {
     let policy_state = session().await?
         .enforcer_and_policy_state("/exposure/", "").await?;
     let platform = platform().await?;
     Ok(policy_state.to_enforced_ok(
-        ExposureBackend::list(platform.mc_platform.as_ref())
-            .await
-            .map_err(|_| AppError::InternalServerError)?
-            .into_iter()
+        platform.list_exposures_page(&listing).await
+            .map_err(listing_error)?
             .map(|exposure| AliasEntry {
                 alias: exposure.id.to_string(),
                 entity: exposure,
             })
-            .collect()
     ))
 }
 
@@ -901,3 +951,88 @@ pub async fn wizard_build(
         .map_err(|_| AppError::InternalServerError)?;
     Ok(result)
 }
+
+/// The status of the build of an exposure file view.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WizardViewStatus {
+    pub status: ExposureFileViewTaskStatus,
+    /// The final lines of output from the task; this is from stderr
+    /// for a failed task, or from stdout for a running task.
+    pub output: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+const WIZARD_OUTPUT_LIMIT: u64 = 4096;
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/exposure_wizard_build_status",
+    request_body(
+        description = r#"
+Acquire the status of the builds of the views for the exposure.
+        "#,
+        content((
+            Id = "application/json",
+            examples(
+                ("Example 1" = (
+                    summary = "Acquire the build status by the exposure's alias.",
+                    value = json!({
+                        "id": {
+                            "Aliased": "c1",
+                        },
+                    }),
+                )),
+            )
+        )),
+    ),
+    responses((
+        status = 200,
+        description = "The build status of every view of every exposure file.",
+        body = Vec<WizardViewStatus>,
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "exposure_wizard_build_status",
+    input = server_fn::codec::Json,
+)]
+pub async fn wizard_build_status_openapi(
+    id: Id,
+) -> Result<Vec<WizardViewStatus>, AppError> {
+    let exposure_id = resolve_id(id).await?;
+    wizard_build_status(exposure_id).await
+}
+
+#[server]
+pub async fn wizard_build_status(
+    exposure_id: i64,
+) -> Result<Vec<WizardViewStatus>, AppError> {
+    session().await?
+        .enforcer(format!("/exposure/{exposure_id}/"), "edit").await?;
+    let platform = platform().await?;
+    let ec = platform.get_exposure(exposure_id).await
+        .map_err(|_| AppError::InternalServerError)?;
+    let mut result = Vec::new();
+    for status in ec.view_task_statuses().await
+        .map_err(log_error)?
+    {
+        let log = match status.task.as_ref().map(|task| task.state) {
+            Some(TaskState::Failed) => Some(TaskLog::Stderr),
+            Some(TaskState::Running) => Some(TaskLog::Stdout),
+            _ => None,
+        };
+        let output = match (status.task_id, log) {
+            (Some(task_id), Some(log)) => Some(
+                ec.read_task_log_tail(task_id, log, WIZARD_OUTPUT_LIMIT).await
+                    .map_err(log_error)?
+            ),
+            _ => None,
+        };
+        result.push(WizardViewStatus { status, output });
+    }
+    Ok(result)
+}
@@ -28,18 +28,30 @@ async fn main() -> anyhow::Result<()> {
     use pmrapp::exposure::api::WIZARD_FIELD_ROUTE;
     use pmrapp::server::{
         exposure::{
+            archive_aliased_exposure_download,
+            archive_exposure_download,
             exposure_file_data,
             exposure_file_safe_html,
+            exposure_task_log,
             wizard_field_update,
         },
+        git,
         index,
+        oidc,
+        runner::{self, RemoteRunners},
+        webhook::{self, Webhook},
         workspace::{
+            archive_aliased_workspace_download,
+            archive_workspace_download,
             collection_json_workspace,
             raw_aliased_workspace_download,
             raw_workspace_download,
         },
     };
-    use pmrctrl::executor::Executor;
+    use pmrctrl::{
+        executor::Executor,
+        scheduler::SyncScheduler,
+    };
     use pmrtqs::runtime::Builder as RuntimeBuilder;
     use time::Duration;
     use tower::{
@@ -85,6 +97,18 @@ async fn main() -> anyhow::Result<()> {
     let platform = args.platform_builder.build().await
         .map_err(anyhow::Error::from_boxed)?;
 
+    let push_webhook = if args.with_sync_scheduler {
+        let (scheduler, queue) = SyncScheduler::new(platform.clone(), args.sync_policy.clone());
+        tokio::spawn(scheduler.run());
+        args.webhook_secret.clone()
+            .map(|secret| Webhook { queue, secret })
+    } else {
+        if args.webhook_secret.is_some() {
+            log::warn!("the webhook requires the sync scheduler; it will not be available");
+        }
+        None
+    };
+
     let session_store = MemoryStore::default();
     // let session_layer = SessionManagerLayer::new(session_store.clone())
     let session_layer = SessionManagerLayer::new(MemoryStore::default())
@@ -98,7 +122,8 @@ async fn main() -> anyhow::Result<()> {
                 platform.ac_platform.clone(),
             )
             .with_session_manager_layer(session_layer)
-            .with_bearer_token_endpoint("/api/bearer/from_login_password"),
+            .with_bearer_token_endpoint("/api/bearer/from_login_password")
+            .with_bearer_token_endpoint("/api/bearer/from_token"),
         );
 
     let cors = CorsLayer::new()
@@ -127,6 +152,23 @@ async fn main() -> anyhow::Result<()> {
         .route("/workspace/:/id/{workspace_id}/rawfile/{commit_id}/{*path}", get(raw_workspace_download))
         .route("/api/workspace/{workspace_alias}/rawfile/{commit_id}/{*path}", get(raw_aliased_workspace_download))
         .route("/api/workspace/:/id/{workspace_id}/rawfile/{commit_id}/{*path}", get(raw_workspace_download))
+        .route("/workspace/{workspace_alias}/archive/{commit_id}/{format}", get(archive_aliased_workspace_download))
+        .route("/workspace/:/id/{workspace_id}/archive/{commit_id}/{format}", get(archive_workspace_download))
+        .route("/api/workspace/{workspace_alias}/archive/{commit_id}/{format}", get(archive_aliased_workspace_download))
+        .route("/api/workspace/:/id/{workspace_id}/archive/{commit_id}/{format}", get(archive_workspace_download))
+        .route("/exposure/{exposure_alias}/:/archive/{format}", get(archive_aliased_exposure_download))
+        .route("/exposure/:/id/{exposure_id}/:/archive/{format}", get(archive_exposure_download))
+        .route("/api/exposure/{e_id}/:/archive/{format}", get(archive_exposure_download))
+        .route("/api/exposure/{e_id}/:/task/{task_id}/{log}", get(exposure_task_log))
+
+        // The git smart HTTP protocol, such that the workspace URLs may be cloned.
+        .route("/workspace/{workspace_alias}/info/refs", get(git::aliased_info_refs))
+        .route("/workspace/{workspace_alias}/git-upload-pack", post(git::aliased_upload_pack))
+        .route("/workspace/:/id/{workspace_id}/info/refs", get(git::info_refs))
+        .route("/workspace/:/id/{workspace_id}/git-upload-pack", post(git::upload_pack))
+        // The body of a push is streamed to git, with the size limited there.
+        .route("/workspace/{workspace_alias}/git-receive-pack", post(git::aliased_receive_pack))
+        .route("/workspace/:/id/{workspace_id}/git-receive-pack", post(git::receive_pack))
 
         // Index routes
         .route("/api/citations", get(index::citations))
@@ -139,6 +181,11 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/index/{keyword}/{term}/", get(index::resources))
         .route("/api/search", post(index::resource_briefs))
 
+        // Login with the OpenID Connect providers.
+        .route("/auth/oidc/{provider}/login", get(oidc::login))
+        .route("/auth/oidc/{provider}/link", get(oidc::link))
+        .route("/auth/oidc/{provider}/callback", get(oidc::callback))
+
         .route(WIZARD_FIELD_ROUTE, post(wizard_field_update))
         .leptos_routes(
             &leptos_options,
@@ -150,6 +197,24 @@ async fn main() -> anyhow::Result<()> {
         )
         .fallback(leptos_axum::file_and_error_handler(shell));
 
+    // Push events from the hosting services of the remote repositories.
+    let app = match push_webhook {
+        Some(push_webhook) => app.route(
+            "/api/webhook/push",
+            post(webhook::push).layer(Extension(push_webhook)),
+        ),
+        None => app,
+    };
+
+    // The work-claiming protocol for the remote runners.
+    let app = match args.runner_token.clone() {
+        Some(token) => app.merge(runner::router(RemoteRunners {
+            token,
+            lease: args.runner_lease,
+        })),
+        None => app,
+    };
+
     #[cfg(feature = "utoipa")]
     let app = app.merge(
         utoipa_swagger_ui::SwaggerUi::new("/swagger-ui")
@@ -184,6 +249,7 @@ async fn main() -> anyhow::Result<()> {
         let executor = Executor::new(platform.clone());
         let mut runtime = RuntimeBuilder::from(executor)
             .permits(args.with_runners)
+            .orphan_policy(args.orphan_policy)
             .build_with_handle(tokio::runtime::Handle::current());
         runtime.start();
         runtime
@@ -191,7 +257,8 @@ async fn main() -> anyhow::Result<()> {
 
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     log::info!("listening on http://{}", &addr);
-    axum::serve(listener, app.into_make_service())
+    // The address of the client is the origin for the email bind requests.
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
         .with_graceful_shutdown((move || {
             async {
                 if let Some(runtime) = runtime{
@@ -1,6 +1,16 @@
 use pmrcore::{
+    listing::{
+        Listing,
+        SortKey,
+        SortOrder,
+    },
     profile::UserPromptGroup,
-    task_template::UserInputMap,
+    repo::ArchiveFormat,
+    task::TaskLog,
+    task_template::{
+        UserInput,
+        UserInputMap,
+    },
     workspace::Workspace,
 };
 use utoipa::{
@@ -20,18 +30,36 @@ use utoipa::{
 
 use crate::{
     ac::api::{
+        __path_complete_password_reset,
+        __path_complete_registration,
+        __path_create_token,
         __path_current_user,
+        __path_current_user_emails,
+        __path_current_user_identities,
+        __path_current_user_tokens,
         __path_bearer_from_login_password,
+        __path_bearer_from_token,
+        __path_email_bindreq,
+        __path_oidc_providers,
+        __path_reject_email_bindreq,
+        __path_request_email_bind,
+        __path_request_password_reset,
+        __path_request_registration,
+        __path_revoke_token,
         __path_sign_in_with_login_password,
         __path_sign_out,
+        __path_unlink_identity,
+        __path_verify_email_bindreq,
         __path_workflow_transition,
     },
     app::id::Id,
     enforcement::EnforcedOk,
     exposure::api::{
+        ExposurePage,
         Exposures,
         ExposureInfo,
         WizardInfo,
+        WizardViewStatus,
         __path_create_exposure_openapi,
         __path_list_exposures,
         __path_list_aliased_exposures,
@@ -41,11 +69,14 @@ use crate::{
         __path_wizard,
         __path_wizard_add_file_openapi,
         __path_wizard_build_openapi,
+        __path_wizard_build_status_openapi,
     },
     server::{
         exposure::{
+            __path_archive_exposure_download,
             __path_exposure_file_data,
             __path_exposure_file_safe_html,
+            __path_exposure_task_log,
             __path_wizard_field_update,
         },
         index::{
@@ -55,16 +86,22 @@ use crate::{
             __path_resources,
             __path_resource_briefs,
         },
-        workspace::__path_raw_aliased_workspace_download,
+        workspace::{
+            __path_archive_aliased_workspace_download,
+            __path_raw_aliased_workspace_download,
+        },
     },
     workspace::api::{
         __path_create_workspace_core,
         __path_list_workspaces,
         __path_list_aliased_workspaces,
+        __path_get_blame_info,
+        __path_get_diff_info,
         __path_get_log_info,
         __path_get_workspace_info,
         __path_workspace_root_policy_state,
         __path_synchronize_openapi,
+        WorkspacePage,
         Workspaces,
     },
 };
@@ -101,15 +138,33 @@ impl Modify for Security {
     paths(
         // Auth
         bearer_from_login_password,
+        bearer_from_token,
+        complete_password_reset,
+        complete_registration,
+        create_token,
         current_user,
+        current_user_emails,
+        current_user_identities,
+        current_user_tokens,
+        email_bindreq,
+        oidc_providers,
+        reject_email_bindreq,
+        request_email_bind,
+        request_password_reset,
+        request_registration,
+        revoke_token,
         sign_in_with_login_password,
         sign_out,
+        unlink_identity,
+        verify_email_bindreq,
         workflow_transition,
 
         // Exposures
+        archive_exposure_download,
         create_exposure_openapi,
         exposure_file_data,
         exposure_file_safe_html,
+        exposure_task_log,
         list_exposures,
         list_aliased_exposures,
         list_aliased_exposures_for_workspace,
@@ -118,6 +173,7 @@ impl Modify for Security {
         wizard,
         wizard_add_file_openapi,
         wizard_build_openapi,
+        wizard_build_status_openapi,
         wizard_field_update,
 
         // Index
@@ -131,21 +187,35 @@ impl Modify for Security {
         create_workspace_core,
         list_workspaces,
         list_aliased_workspaces,
+        get_blame_info,
+        get_diff_info,
         get_log_info,
         get_workspace_info,
         synchronize_openapi,
+        archive_aliased_workspace_download,
         raw_aliased_workspace_download,
         workspace_root_policy_state,
     ),
     components(schemas(
+        ArchiveFormat,
         EnforcedOk<Workspaces>,
+        EnforcedOk<WorkspacePage>,
+        EnforcedOk<ExposurePage>,
+        ExposurePage,
         Exposures,
         ExposureInfo,
         Id,
+        Listing,
+        SortKey,
+        SortOrder,
+        TaskLog,
+        UserInput,
         UserInputMap,
         UserPromptGroup,
         WizardInfo,
+        WizardViewStatus,
         Workspace,
+        WorkspacePage,
         Workspaces,
     )),
 )]
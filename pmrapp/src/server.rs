@@ -1,4 +1,8 @@
-use pmrctrl::platform::Platform;
+use pmrcore::error::BackendError;
+use pmrctrl::{
+    error::PlatformError,
+    platform::Platform,
+};
 use crate::{
     app::id::Id,
     error::AppError
@@ -17,6 +21,16 @@ pub fn log_error(error: impl std::fmt::Display) -> AppError {
     AppError::InternalServerError
 }
 
+/// Map the error from listing a page of entries, where a cursor that
+/// does not apply to the requested sort is the fault of the client.
+pub fn listing_error(error: PlatformError) -> AppError {
+    match error {
+        PlatformError::BackendError(BackendError::AppInvariantViolation(_)) =>
+            AppError::BadRequest,
+        error => log_error(error),
+    }
+}
+
 pub async fn resolve_id(kind: &'static str, id: Id) -> Result<i64, AppError> {
     let platform = platform().await?;
     id.resolve(&platform, kind).await
@@ -24,5 +38,9 @@ pub async fn resolve_id(kind: &'static str, id: Id) -> Result<i64, AppError> {
 
 pub mod ac;
 pub mod exposure;
+pub mod git;
 pub mod index;
+pub mod oidc;
+pub mod runner;
+pub mod webhook;
 pub mod workspace;
@@ -1,10 +1,14 @@
-use axum::Extension;
+use axum::{
+    Extension,
+    extract::ConnectInfo,
+};
 use axum_login::{
     AuthSession,
     Error as AxumLoginError,
 };
 use axum_login_bearer::BearerTokenSession;
 use pmrac::{
+    agent::ScopedAgent,
     error::Error as ACError,
     axum_login::{
         Authorization,
@@ -12,10 +16,16 @@ use pmrac::{
     },
     Platform,
 };
+use futures::future::BoxFuture;
 use pmrcore::ac::{
     agent::Agent,
-    user::User,
+    user::{
+        User,
+        UserToken,
+    },
 };
+use pmrrepo::handle::SubmoduleAccess;
+use std::net::SocketAddr;
 use crate::{
     enforcement::PolicyState,
     error::{
@@ -45,19 +55,29 @@ impl From<Session> for AuthSession<Platform> {
 }
 
 impl Session {
+    /// The agent of the session, limited by the scopes of the personal
+    /// access token that the session was created from, if any.
+    pub fn agent(&self) -> ScopedAgent {
+        self.0.user
+            .as_ref()
+            .map(ScopedAgent::from)
+            .unwrap_or(Agent::Anonymous.into())
+    }
+
     pub async fn enforcer(
         &self,
         resource: impl Into<String>,
         action: impl Into<String>,
     ) -> Result<(), AppError> {
         let backend = &self.0.backend;
-        let agent: Agent = self.0.user
-            .as_ref()
-            .map(|auth| auth.user().into())
-            .unwrap_or(Agent::Anonymous);
+        let agent = self.agent();
         let resource = resource.into();
         let action = action.into();
-        log::trace!("enforce on: agent={agent} resource={resource:?} action={action:?}");
+        log::trace!(
+            "enforce on: agent={} scopes={:?} resource={resource:?} action={action:?}",
+            agent.agent(),
+            agent.scopes(),
+        );
         if backend
             .enforce(agent.clone(), &resource, action)
             .await
@@ -78,13 +98,14 @@ impl Session {
         action: impl Into<String>,
     ) -> Result<PolicyState, AppError> {
         let backend = &self.0.backend;
-        let agent: Agent = self.0.user
-            .as_ref()
-            .map(|auth| auth.user().into())
-            .unwrap_or(Agent::Anonymous);
+        let agent = self.agent();
         let resource = resource.into();
         let action = action.into();
-        log::trace!("enforce on: agent={agent} resource={resource:?} action={action:?}");
+        log::trace!(
+            "enforce on: agent={} scopes={:?} resource={resource:?} action={action:?}",
+            agent.agent(),
+            agent.scopes(),
+        );
         let (policy, result) = backend
             .get_policy_and_enforce(agent.clone(), &resource, action)
             .await
@@ -110,6 +131,24 @@ impl Session {
             authorization: Authorization::LoginPassword(login, password),
             origin: "localhost".to_string(),  // TODO plug in remote host
         };
+        self.sign_in(creds).await
+    }
+
+    pub async fn sign_in_with_token(
+        &mut self,
+        token: String,
+    ) -> Result<Option<String>, AuthError> {
+        let creds = Credentials {
+            authorization: Authorization::Token(token),
+            origin: origin().await?,
+        };
+        self.sign_in(creds).await
+    }
+
+    async fn sign_in(
+        &mut self,
+        creds: Credentials,
+    ) -> Result<Option<String>, AuthError> {
         match self.0.authenticate(creds).await {
             Ok(Some(auth)) => {
                 self.0.login(&auth).await
@@ -153,6 +192,32 @@ impl Session {
             .as_ref()
             .map(|auth| auth.user().clone_inner())
     }
+
+    /// The personal access token the current session was created from.
+    pub fn current_user_token(&self) -> Option<UserToken> {
+        self.0.user
+            .as_ref()
+            .and_then(|auth| auth.user_token().cloned())
+    }
+
+    /// The current user, provided that the session isn't limited by a
+    /// personal access token, for the management of the account.
+    pub fn current_account_user(&self) -> Result<User, AuthError> {
+        if self.current_user_token().is_some() {
+            return Err(AuthError::TokenSession);
+        }
+        self.current_user()
+            .ok_or(AuthError::InvalidCredentials)
+    }
+}
+
+// The workspaces of submodules are only included for those who may read them.
+impl SubmoduleAccess for Session {
+    fn permits(&self, workspace_id: i64) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            self.enforcer(format!("/workspace/{workspace_id}/"), "").await.is_ok()
+        })
+    }
 }
 
 pub async fn session() -> Result<Session, AppError> {
@@ -163,3 +228,15 @@ pub async fn session() -> Result<Session, AppError> {
         .into()
     )
 }
+
+/// The address of the client, for use as the origin of requests that are
+/// limited by their origin.
+pub async fn origin() -> Result<String, AuthError> {
+    Ok(leptos_axum::extract::<ConnectInfo<SocketAddr>>()
+        .await
+        .map_err(|_| AuthError::InternalServerError)?
+        .0
+        .ip()
+        .to_string()
+    )
+}
@@ -1,24 +1,58 @@
 use ammonia::UrlRelative;
 use axum::{
     Extension,
-    extract::Path,
+    extract::{
+        Path,
+        Query,
+    },
     http::{
         header::{CONTENT_TYPE, HeaderMap},
         HeaderValue,
     },
-    response::IntoResponse,
+    response::{
+        IntoResponse,
+        Response,
+        sse::{
+            Event,
+            KeepAlive,
+            Sse,
+        },
+    },
 };
 use axum_login::AuthSession;
+use futures::stream::{
+    self,
+    Stream,
+};
 use itertools::Itertools;
 use regex::Regex;
 use pmrac::Platform as ACPlatform;
-use pmrcore::task_template::UserInputMap;
+use pmrcore::{
+    exposure::traits::Exposure as _,
+    repo::ArchiveFormat,
+    task::{
+        TaskLog,
+        TaskStatus,
+    },
+    task_template::{
+        UserInput,
+        UserInputMap,
+    },
+};
 use pmrctrl::platform::Platform;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
     collections::HashMap,
+    convert::Infallible,
+    io::SeekFrom,
+    path::PathBuf,
     sync::LazyLock,
+    time::Duration,
+};
+use tokio::io::{
+    AsyncReadExt,
+    AsyncSeekExt,
 };
 use crate::{
     app::id::Id,
@@ -27,12 +61,19 @@ use crate::{
     server::{
         self,
         ac::Session,
+        workspace::{
+            ArchiveQuery,
+            archive_download,
+        },
     },
 };
 
 static RE: LazyLock<Regex> = LazyLock::new(||
     Regex::new(r"^(\d+)-(\d+)$").unwrap());
 
+/// An exposure_file_id paired with its user input keyed by arg_id.
+type ExposureFileUserInput = (i64, Vec<(i64, UserInput)>);
+
 pub async fn resolve_id(id: Id) -> Result<i64, AppError> {
     server::resolve_id("exposure", id).await
 }
@@ -42,7 +83,8 @@ pub async fn resolve_id(id: Id) -> Result<i64, AppError> {
     path = WIZARD_FIELD_ROUTE,
     request_body(
         description = r#"
-Update the user input mapping for the provided `ExposureFile`s.
+Update the user input mapping for the provided `ExposureFile`s.  A list
+of values may be provided for arguments that accept multiple values.
         "#,
         content((
             WizardFieldUpdateArgs = "application/json",
@@ -66,6 +108,12 @@ Update the user input mapping for the provided `ExposureFile`s.
                                 "18": "CellML RDF Metadata",
                                 "19": "Creative Commons - Attributions 3.0 Unported",
                             },
+                            "3": {
+                                "5": [
+                                    "baylor_hollingworth_chandler_2002_a.cellml",
+                                    "baylor_hollingworth_chandler_2002_b.cellml",
+                                ],
+                            },
                         }
                     }),
                 )),
@@ -113,7 +161,7 @@ pub async fn wizard_field_update(
 
 fn parse_wizard_field_update_www_form_urlencode(
     body: &str,
-) -> Result<(i64, impl Iterator<Item = (i64, Vec<(i64, String)>)>), AppError> {
+) -> Result<(i64, impl Iterator<Item = ExposureFileUserInput>), AppError> {
     // 1. parse the incoming body and split the key-value pairs for keys
     //    that have two numbers separated by a `-` which denotes that
     //    these are to be treated as exposure_file_id + arg_id pairs for
@@ -149,10 +197,19 @@ fn parse_wizard_field_update_www_form_urlencode(
 
     // 5. process the fields by grouping them by exposure_file_id, while
     //    ensuring that the exposure_file_id are under the exposure to
-    //    maintain the security invariant.
+    //    maintain the security invariant.  The repeated keys from fields
+    //    that accept multiple values are grouped into a list of values.
     let ef_user_input = fields.into_iter()
         .into_group_map()
-        .into_iter();
+        .into_iter()
+        .map(|(exposure_file_id, values)| (
+            exposure_file_id,
+            values.into_iter()
+                .into_group_map()
+                .into_iter()
+                .map(|(arg_id, values)| (arg_id, values.into_iter().collect()))
+                .collect(),
+        ));
 
     Ok((exposure_id, ef_user_input))
 }
@@ -161,13 +218,13 @@ fn parse_wizard_field_update_www_form_urlencode(
 #[derive(Clone, Serialize, Deserialize)]
 pub struct WizardFieldUpdateArgs {
     pub id: Id,
-    pub ef_user_input: HashMap<i64, HashMap<i64, String>>,
+    pub ef_user_input: HashMap<i64, HashMap<i64, UserInput>>,
 }
 
 async fn parse_wizard_field_update_json(
     platform: &Platform,
     body: &str,
-) -> Result<(i64, impl Iterator<Item = (i64, HashMap<i64, String>)>), AppError> {
+) -> Result<(i64, impl Iterator<Item = (i64, HashMap<i64, UserInput>)>), AppError> {
     // 1. parse the incoming body and split the key-value pairs for keys
     //    that have two numbers separated by a `-` which denotes that
     //    these are to be treated as exposure_file_id + arg_id pairs for
@@ -185,7 +242,7 @@ async fn process_ef_user_input(
     platform: Extension<Platform>,
     session: Extension<AuthSession<ACPlatform>>,
     exposure_id: i64,
-    ef_user_input: impl Iterator<Item = (i64, impl IntoIterator<Item = (i64, String)>)>,
+    ef_user_input: impl Iterator<Item = (i64, impl IntoIterator<Item = (i64, UserInput)>)>,
 ) -> Result<(), AppError> {
     // 0. validate permission
     Session::from(session)
@@ -206,7 +263,7 @@ async fn process_ef_user_input(
             .await
             .map_err(|_| AppError::InternalServerError)?;
 
-        // Convert `Vec<(i64, std::string::String)>` into `HashMap<i64, std::string::String>`
+        // Convert `Vec<(i64, UserInput)>` into `HashMap<i64, UserInput>`
         // TODO this somehow couples, it's desirable to allow HashMap (i.e. JavaScript's object)
         // to be passed directly here, so this logic should be decoupled.
         let user_input = values.into_iter()
@@ -306,3 +363,223 @@ pub async fn exposure_file_safe_html(
         .to_string()
     )
 }
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/exposure/{exposure_id}/:/archive/{format}",
+    summary = "This endpoint is also bound to `/exposure/{exposure_alias}/:/archive/{format}`.",
+    params(
+        ("exposure_id" = i64, Path, description = "Exposure id."),
+        ("format" = ArchiveFormat, Path, description = "The format of the archive."),
+        ArchiveQuery,
+    ),
+    responses((
+        status = 200,
+        description = "The archive of the workspace at the commit of the exposure.",
+        body = Vec<u8>,
+    ), AppError),
+    security(
+        (),
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+pub async fn archive_exposure_download(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path((exposure_id, format)): Path<(i64, String)>,
+    query: Query<ArchiveQuery>,
+) -> Result<Response, AppError> {
+    archive_exposure(platform, session, exposure_id, format, query, exposure_id.to_string()).await
+}
+
+pub async fn archive_aliased_exposure_download(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path((exposure_alias, format)): Path<(String, String)>,
+    query: Query<ArchiveQuery>,
+) -> Result<Response, AppError> {
+    let exposure_id = platform
+        .mc_platform
+        .resolve_alias("exposure", &exposure_alias)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or(AppError::NotFound)?;
+    archive_exposure(platform, session, exposure_id, format, query, exposure_alias).await
+}
+
+async fn archive_exposure(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    exposure_id: i64,
+    format: String,
+    Query(query): Query<ArchiveQuery>,
+    name: String,
+) -> Result<Response, AppError> {
+    let session = Session::from(session);
+    session.enforcer(format!("/exposure/{exposure_id}/"), "").await?;
+    let ec = platform.get_exposure(exposure_id).await
+        .map_err(|_| AppError::NotFound)?;
+    let exposure = ec.exposure();
+    archive_download(
+        &platform,
+        &session,
+        exposure.workspace_id(),
+        exposure.commit_id(),
+        &format,
+        query,
+        &format!("exposure-{name}"),
+    ).await
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/exposure/{exposure_id}/:/task/{task_id}/{log}",
+    description = r#"
+Stream the log of a task queued to generate an exposure file view as
+server-sent events.  Each `log` event carries the complete lines written
+to the log since the previous event, or up to 64 KiB of output that has
+no newline; once the task is no longer pending a final `status` event
+carrying the `TaskStatus` is sent.
+    "#,
+    params(
+        ("exposure_id" = i64, Path, description = "Exposure id."),
+        ("task_id" = i64, Path, description = "Task id."),
+        ("log" = TaskLog, Path, description = "The log to stream."),
+    ),
+    responses((
+        status = 200,
+        description = "The stream of events for the log.",
+        content_type = "text/event-stream",
+        body = String,
+    ), AppError),
+    security(
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+pub async fn exposure_task_log(
+    Extension(platform): Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path((exposure_id, task_id, log)): Path<(i64, i64, String)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    Session::from(session)
+        .enforcer(format!("/exposure/{exposure_id}/"), "edit").await?;
+    let log = log.parse::<TaskLog>()
+        .map_err(|_| AppError::NotFound)?;
+    let ec = platform.get_exposure(exposure_id).await
+        .map_err(|_| AppError::NotFound)?;
+    let task = ec.get_view_task(task_id).await
+        .map_err(|_| AppError::NotFound)?;
+    let path = log.path(&task.basedir);
+    drop(ec);
+
+    let tail = TaskLogTail {
+        platform,
+        task_id,
+        path,
+        offset: 0,
+        partial: Vec::new(),
+        complete: None,
+        done: false,
+    };
+    Ok(Sse::new(stream::unfold(tail, TaskLogTail::next_event))
+        .keep_alive(KeepAlive::default()))
+}
+
+const TASK_LOG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const TASK_LOG_CHUNK_SIZE: usize = 64 * 1024;
+
+struct TaskLogTail {
+    platform: Platform,
+    task_id: i64,
+    path: PathBuf,
+    offset: u64,
+    // bytes read past the final complete line
+    partial: Vec<u8>,
+    complete: Option<TaskStatus>,
+    done: bool,
+}
+
+impl TaskLogTail {
+    fn log_event(lines: &[u8]) -> Event {
+        // carriage returns are not permitted in the data of an event
+        Event::default()
+            .event("log")
+            .data(String::from_utf8_lossy(lines).replace('\r', ""))
+    }
+
+    // Read the complete lines written to the log since the last read,
+    // without the final newline.  At most `TASK_LOG_CHUNK_SIZE` bytes
+    // are held at a time, so a full buffer without a newline is
+    // returned as is.
+    async fn read_lines(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        let mut file = match tokio::fs::File::open(&self.path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if file.metadata().await?.len() < self.offset {
+            // the log was restarted by another attempt of the task
+            self.offset = 0;
+            self.partial.clear();
+        }
+        file.seek(SeekFrom::Start(self.offset)).await?;
+        let limit = TASK_LOG_CHUNK_SIZE.saturating_sub(self.partial.len());
+        self.offset += file.take(limit as u64)
+            .read_to_end(&mut self.partial).await? as u64;
+        if self.partial.len() >= TASK_LOG_CHUNK_SIZE {
+            return Ok(Some(std::mem::take(&mut self.partial)));
+        }
+        Ok(self.partial.iter()
+            .rposition(|b| *b == b'\n')
+            .map(|pos| {
+                let mut lines = self.partial.drain(..=pos).collect::<Vec<_>>();
+                lines.pop();
+                lines
+            }))
+    }
+
+    async fn next_event(mut self) -> Option<(Result<Event, Infallible>, Self)> {
+        loop {
+            if self.done {
+                return None;
+            }
+            match self.read_lines().await {
+                Ok(Some(lines)) => return Some((Ok(Self::log_event(&lines)), self)),
+                Ok(None) => (),
+                Err(e) => {
+                    log::warn!("failed to read log for task {}: {e}", self.task_id);
+                    self.done = true;
+                    return Some((Ok(Event::default().event("error").data("failed to read log")), self));
+                }
+            }
+            // the log has been read in full after the task was found to
+            // be complete, so send out any remaining output and then the
+            // final status.
+            if let Some(status) = self.complete.as_ref() {
+                if !self.partial.is_empty() {
+                    let rest = std::mem::take(&mut self.partial);
+                    return Some((Ok(Self::log_event(&rest)), self));
+                }
+                let event = Event::default()
+                    .event("status")
+                    .json_data(status)
+                    .unwrap_or_default();
+                self.done = true;
+                return Some((Ok(event), self));
+            }
+            match self.platform.get_task(self.task_id).await {
+                Ok(task) if !task.state().is_pending() => {
+                    self.complete = Some(TaskStatus::from(&task));
+                }
+                Ok(_) => tokio::time::sleep(TASK_LOG_POLL_INTERVAL).await,
+                Err(e) => {
+                    log::warn!("failed to acquire task {}: {e}", self.task_id);
+                    self.done = true;
+                    return Some((Ok(Event::default().event("error").data("failed to acquire task")), self));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,905 @@
+//! The git smart HTTP protocol for the repositories of the workspaces.
+//!
+//! This is implemented by delegating to `git upload-pack` and `git
+//! receive-pack` running in the stateless RPC mode against the repository
+//! managed by the repo backend.  Access to the workspace is enforced in the
+//! same way as the rawfile endpoints, with pushes requiring the `edit`
+//! action; clients may authenticate through the bearer token, e.g. with
+//! `git -c http.extraHeader="Authorization: Bearer ..." push`.
+//!
+//! Pushes may provide the `rebuild-exposures` push option (i.e. `git push
+//! -o rebuild-exposures`) to have all exposures of the workspace rebuilt
+//! once the push is received.
+
+use axum::{
+    Extension,
+    body::{
+        Body,
+        Bytes,
+    },
+    extract::{
+        Path,
+        Query,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use axum_login::AuthSession;
+use flate2::read::GzDecoder;
+use futures::TryStreamExt;
+use http::{
+    HeaderMap,
+    header,
+};
+use pmrac::Platform as ACPlatform;
+use pmrctrl::platform::Platform;
+use serde::Deserialize;
+use std::{
+    io::{
+        ErrorKind,
+        Read,
+        Write,
+    },
+    path::PathBuf,
+    process::Stdio,
+};
+use tokio::{
+    io::AsyncWriteExt,
+    process::Command,
+};
+use tokio_util::io::{
+    ReaderStream,
+    StreamReader,
+    SyncIoBridge,
+};
+
+use crate::{
+    error::AppError,
+    server::{
+        ac::Session,
+        log_error,
+    },
+};
+
+/// The limit on the size of the request body for a push, which also
+/// applies to the body once decoded.
+pub const RECEIVE_PACK_LIMIT: usize = 256 * 1024 * 1024;
+
+/// The limit on the size of the request body for a fetch once decoded;
+/// the size of the body as sent is limited by the default body limit.
+pub const UPLOAD_PACK_LIMIT: usize = 64 * 1024 * 1024;
+
+const REBUILD_EXPOSURES: &str = "rebuild-exposures";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Service {
+    UploadPack,
+    ReceivePack,
+}
+
+impl Service {
+    fn from_query(service: Option<&str>) -> Option<Self> {
+        match service {
+            Some("git-upload-pack") => Some(Service::UploadPack),
+            Some("git-receive-pack") => Some(Service::ReceivePack),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Service::UploadPack => "git-upload-pack",
+            Service::ReceivePack => "git-receive-pack",
+        }
+    }
+
+    /// The action on the workspace required by the service.
+    fn action(&self) -> &'static str {
+        match self {
+            Service::UploadPack => "",
+            Service::ReceivePack => "edit",
+        }
+    }
+
+    fn command(
+        &self,
+        repo_dir: &std::path::Path,
+        headers: &HeaderMap,
+        advertise_refs: bool,
+    ) -> Command {
+        let mut cmd = Command::new("git");
+        match self {
+            Service::UploadPack => cmd.arg("upload-pack"),
+            Service::ReceivePack => cmd.args([
+                "-c", "receive.advertisePushOptions=true",
+                "receive-pack",
+            ]),
+        };
+        cmd.arg("--stateless-rpc");
+        if advertise_refs {
+            cmd.arg("--advertise-refs");
+        }
+        // pass through the negotiation of the protocol version.
+        if let Some(protocol) = git_protocol(headers) {
+            cmd.env("GIT_PROTOCOL", protocol);
+        }
+        cmd.arg(repo_dir);
+        cmd
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InfoRefsQuery {
+    service: Option<String>,
+}
+
+/// Encode the line as a pkt-line.
+fn pkt_line(line: &str) -> String {
+    format!("{:04x}{line}", line.len() + 4)
+}
+
+/// Read the next pkt-line from the reader, with `Some(None)` denoting a
+/// flush-pkt and `None` denoting the end of (or invalid) input.  All the
+/// bytes read are appended to `consumed`.
+fn read_pkt(reader: &mut impl Read, consumed: &mut Vec<u8>) -> Option<Option<Vec<u8>>> {
+    // read exactly `len` bytes, returning where they start in `consumed`.
+    fn fill(reader: &mut impl Read, consumed: &mut Vec<u8>, len: usize) -> Option<usize> {
+        let start = consumed.len();
+        let read = reader.take(len as u64)
+            .read_to_end(consumed)
+            .ok()?;
+        (read == len).then_some(start)
+    }
+    let start = fill(reader, consumed, 4)?;
+    let len = usize::from_str_radix(std::str::from_utf8(&consumed[start..]).ok()?, 16).ok()?;
+    match len {
+        0 => Some(None),
+        1..=3 => None,
+        len => {
+            let start = fill(reader, consumed, len - 4)?;
+            Some(Some(consumed[start..].to_vec()))
+        }
+    }
+}
+
+/// Read the push options from the request to receive-pack, which are
+/// sent after the commands where the client makes use of the capability.
+/// All the bytes read are appended to `consumed`.
+fn read_push_options(reader: &mut impl Read, consumed: &mut Vec<u8>) -> Vec<String> {
+    let mut enabled = false;
+    let mut first = true;
+    loop {
+        match read_pkt(reader, consumed) {
+            Some(Some(line)) => {
+                // the capabilities follow the NUL of the first command.
+                if first {
+                    enabled = line.split(|c| *c == 0)
+                        .nth(1)
+                        .is_some_and(|caps| caps
+                            .split(|c| c.is_ascii_whitespace())
+                            .any(|cap| cap == b"push-options"));
+                    first = false;
+                }
+            }
+            Some(None) => break,
+            None => return Vec::new(),
+        }
+    }
+    let mut result = Vec::new();
+    if enabled {
+        while let Some(Some(line)) = read_pkt(reader, consumed) {
+            result.push(String::from_utf8_lossy(&line).trim_end_matches('\n').to_string());
+        }
+    }
+    result
+}
+
+/// A reader that fails with `ErrorKind::FileTooLarge` once the inner
+/// reader provides more than the limit.
+pub(crate) struct Limit<R> {
+    inner: R,
+    remaining: usize,
+}
+
+impl<R> Limit<R> {
+    pub(crate) fn new(inner: R, limit: usize) -> Self {
+        Self { inner, remaining: limit }
+    }
+}
+
+impl<R: Read> Read for Limit<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 {
+            // a single byte is sufficient to tell whether there is more.
+            return match self.inner.read(&mut [0u8])? {
+                0 => Ok(0),
+                _ => Err(std::io::Error::new(
+                    ErrorKind::FileTooLarge,
+                    "request body exceeds the limit",
+                )),
+            };
+        }
+        let len = buf.len().min(self.remaining);
+        let read = self.inner.read(&mut buf[..len])?;
+        self.remaining -= read;
+        Ok(read)
+    }
+}
+
+fn git_protocol(headers: &HeaderMap) -> Option<&str> {
+    headers.get("git-protocol")
+        .and_then(|value| value.to_str().ok())
+}
+
+/// Provide the reader that decodes the body of the request, as clients
+/// will compress the larger requests.  The reader fails once either the
+/// body or the decoded body exceeds the limit.
+fn decoder<'a>(
+    headers: &HeaderMap,
+    body: impl Read + Send + 'a,
+    limit: usize,
+) -> Result<Box<dyn Read + Send + 'a>, AppError> {
+    let body = Limit::new(body, limit);
+    match headers.get(header::CONTENT_ENCODING).map(|v| v.as_bytes()) {
+        None | Some(b"identity") => Ok(Box::new(body)),
+        Some(b"gzip") | Some(b"x-gzip") => Ok(Box::new(Limit::new(GzDecoder::new(body), limit))),
+        Some(_) => Err(AppError::BadRequest),
+    }
+}
+
+/// Decode the body of the request in full.
+fn decode_body(headers: &HeaderMap, body: Bytes, limit: usize) -> Result<Vec<u8>, AppError> {
+    let mut input = Vec::new();
+    decoder(headers, body.as_ref(), limit)?
+        .read_to_end(&mut input)
+        .map_err(|_| AppError::BadRequest)?;
+    Ok(input)
+}
+
+/// Feed the request to receive-pack, returning the push options sent by
+/// the client.
+fn feed_receive_pack(
+    mut reader: impl Read,
+    mut writer: impl Write,
+) -> std::io::Result<Vec<String>> {
+    let mut consumed = Vec::new();
+    let options = read_push_options(&mut reader, &mut consumed);
+    writer.write_all(&consumed)?;
+    std::io::copy(&mut reader, &mut writer)?;
+    writer.flush()?;
+    Ok(options)
+}
+
+/// Ensure the agent of the session may access the workspace through the
+/// service and return the directory to its repository, which will be
+/// absent for workspaces that were never synchronized or have since been
+/// archived.
+async fn enforced_repo_dir(
+    platform: &Platform,
+    session: &Session,
+    workspace_id: i64,
+    service: Service,
+) -> Result<PathBuf, AppError> {
+    session.enforcer(format!("/workspace/{workspace_id}/"), service.action()).await?;
+    Ok(platform.repo_backend().repo_dir(workspace_id))
+}
+
+/// The advertisement of receive-pack for a repository without any refs,
+/// for workspaces that have yet to be synchronized, such that the
+/// repository is only created once the push is received.
+fn empty_receive_pack_advertisement() -> Vec<u8> {
+    format!(
+        "{}0000",
+        pkt_line(&format!(
+            "{} capabilities^{{}}\0report-status delete-refs side-band-64k \
+                quiet atomic ofs-delta push-options object-format=sha1\n",
+            "0".repeat(40),
+        )),
+    ).into_bytes()
+}
+
+async fn resolve_alias(platform: &Platform, workspace_alias: &str) -> Result<i64, AppError> {
+    platform
+        .mc_platform
+        .resolve_alias("workspace", workspace_alias)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or(AppError::NotFound)
+}
+
+pub async fn aliased_info_refs(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path(workspace_alias): Path<String>,
+    query: Query<InfoRefsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let workspace_id = resolve_alias(&platform, &workspace_alias).await?;
+    info_refs(platform, session, Path(workspace_id), query, headers).await
+}
+
+/// The ref advertisement that begins the smart HTTP protocol.  The dumb
+/// protocol is not supported.
+pub async fn info_refs(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path(workspace_id): Path<i64>,
+    Query(query): Query<InfoRefsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let service = Service::from_query(query.service.as_deref())
+        .ok_or(AppError::BadRequest)?;
+    let session = Session::from(session.0);
+    let repo_dir = enforced_repo_dir(&platform, &session, workspace_id, service).await?;
+
+    let advertisement = if repo_dir.is_dir() {
+        let output = service.command(&repo_dir, &headers, true)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output().await
+            .map_err(log_error)?;
+        if !output.status.success() {
+            Err(log_error(format!(
+                "{} --advertise-refs failed for workspace {workspace_id}: {}",
+                service.name(),
+                String::from_utf8_lossy(&output.stderr),
+            )))?
+        }
+        output.stdout
+    } else {
+        // this request only reads, so the repository is left for the
+        // push itself to create.
+        let workspace = platform.mc_platform.get_workspace_by_id(workspace_id).await
+            .map_err(|_| AppError::NotFound)?;
+        match service {
+            Service::ReceivePack if workspace.archived_ts.is_none() =>
+                empty_receive_pack_advertisement(),
+            _ => Err(AppError::NotFound)?,
+        }
+    };
+
+    // version 2 of the protocol omits the service announcement.
+    let mut body = if git_protocol(&headers)
+        .is_some_and(|protocol| protocol.contains("version=2"))
+    {
+        Vec::new()
+    } else {
+        format!("{}0000", pkt_line(&format!("# service={}\n", service.name()))).into_bytes()
+    };
+    body.extend(advertisement);
+    Ok((
+        [
+            (header::CONTENT_TYPE, format!("application/x-{}-advertisement", service.name())),
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+        ],
+        body,
+    ).into_response())
+}
+
+pub async fn aliased_upload_pack(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path(workspace_alias): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    let workspace_id = resolve_alias(&platform, &workspace_alias).await?;
+    upload_pack(platform, session, Path(workspace_id), headers, body).await
+}
+
+/// The negotiation and the transfer of the pack to the client, with the
+/// pack streamed from git as it is being generated.
+pub async fn upload_pack(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path(workspace_id): Path<i64>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    let service = Service::UploadPack;
+    let session = Session::from(session.0);
+    let repo_dir = enforced_repo_dir(&platform, &session, workspace_id, service).await?;
+    if !repo_dir.is_dir() {
+        Err(AppError::NotFound)?
+    }
+    let input = decode_body(&headers, body, UPLOAD_PACK_LIMIT)?;
+
+    let mut child = service.command(&repo_dir, &headers, false)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(log_error)?;
+    let mut stdin = child.stdin.take()
+        .ok_or(AppError::InternalServerError)?;
+    let stdout = child.stdout.take()
+        .ok_or(AppError::InternalServerError)?;
+
+    // the request is fed to git independently of the response such that
+    // neither side of the pipes can stall the other.
+    tokio::spawn(async move {
+        if let Err(e) = stdin.write_all(&input).await {
+            log::warn!("failed to write request to git upload-pack: {e}");
+        }
+        drop(stdin);
+        match child.wait().await {
+            Ok(status) if !status.success() => log::warn!(
+                "git upload-pack for workspace {workspace_id} exited with {status}"
+            ),
+            Err(e) => log::warn!("failed to wait on git upload-pack: {e}"),
+            _ => (),
+        }
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-git-upload-pack-result"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        Body::from_stream(ReaderStream::new(stdout)),
+    ).into_response())
+}
+
+pub async fn aliased_receive_pack(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path(workspace_alias): Path<String>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, AppError> {
+    let workspace_id = resolve_alias(&platform, &workspace_alias).await?;
+    receive_pack(platform, session, Path(workspace_id), headers, body).await
+}
+
+/// The update of the refs and the transfer of the pack from the client,
+/// followed by the same steps taken after a synchronization.  The request
+/// is streamed to git as it is being received.
+pub async fn receive_pack(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path(workspace_id): Path<i64>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, AppError> {
+    let service = Service::ReceivePack;
+    let session = Session::from(session.0);
+    enforced_repo_dir(&platform, &session, workspace_id, service).await?;
+    // an empty repository is initialized for pushes to workspaces that
+    // have yet to be synchronized.
+    let repo_dir = platform.repo_backend().init_workspace_repo(workspace_id).await
+        .map_err(|_| AppError::NotFound)?;
+    let reader = decoder(
+        &headers,
+        SyncIoBridge::new(StreamReader::new(
+            body.into_data_stream().map_err(std::io::Error::other)
+        )),
+        RECEIVE_PACK_LIMIT,
+    )?;
+
+    let mut child = service.command(&repo_dir, &headers, false)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(log_error)?;
+    let writer = SyncIoBridge::new(child.stdin.take()
+        .ok_or(AppError::InternalServerError)?);
+    let feeder = tokio::task::spawn_blocking(move || feed_receive_pack(reader, writer));
+    // the report of the status is small enough to be fully collected, as
+    // the refs must be updated before the post-receive steps may apply.
+    let output = child.wait_with_output().await
+        .map_err(log_error)?;
+    let rebuild_exposures = match feeder.await.map_err(log_error)? {
+        Ok(options) => options.iter()
+            .any(|option| option == REBUILD_EXPOSURES),
+        // git will have failed on the truncated input.
+        Err(e) if e.kind() == ErrorKind::FileTooLarge => Err(AppError::BadRequest)?,
+        Err(e) => {
+            log::warn!("failed to write request to git receive-pack: {e}");
+            false
+        }
+    };
+    if output.status.success() {
+        // the push had been received at this point, so any failures that
+        // follow are only logged as they cannot be reported to the client.
+        match platform.receive_workspace(
+            session.agent(),
+            workspace_id,
+            rebuild_exposures,
+        ).await {
+            Ok(tasks) => log::info!(
+                "received push for workspace {workspace_id}; {tasks} task(s) queued"
+            ),
+            Err(e) => log::error!(
+                "failed post-receive for workspace {workspace_id}: {e}"
+            ),
+        }
+    } else {
+        log::warn!(
+            "git receive-pack for workspace {workspace_id} exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr),
+        );
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-git-receive-pack-result"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        output.stdout,
+    ).into_response())
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{
+        Router,
+        http::{
+            Request,
+            StatusCode,
+        },
+        routing::{
+            get,
+            post,
+        },
+    };
+    use axum_login_bearer::{
+        BearerTokenAuthManagerLayer,
+        BearerTokenSession,
+    };
+    use pmrac::{
+        axum_login::{
+            Authorization,
+            Credentials,
+        },
+        platform::Builder as ACPlatformBuilder,
+    };
+    use pmrcore::ac::{
+        role::Role,
+        workflow::State,
+    };
+    use test_pmr::{
+        ac::create_sqlite_backend,
+        ctrl::create_sqlite_platform,
+    };
+    use tower::ServiceExt;
+    use tower_sessions::{
+        MemoryStore,
+        SessionManagerLayer,
+    };
+
+    use super::*;
+
+    fn pkt(line: &str) -> String {
+        format!("{:04x}{line}", line.len() + 4)
+    }
+
+    const COMMAND: &str = "0000000000000000000000000000000000000000 \
+        1111111111111111111111111111111111111111 refs/heads/main";
+
+    #[test]
+    fn test_read_pkt() {
+        let input = format!("{}0000", pkt("hello\n"));
+        let mut reader = input.as_bytes();
+        let mut consumed = Vec::new();
+        assert_eq!(read_pkt(&mut reader, &mut consumed), Some(Some(b"hello\n".to_vec())));
+        assert_eq!(read_pkt(&mut reader, &mut consumed), Some(None));
+        assert_eq!(read_pkt(&mut reader, &mut consumed), None);
+        assert_eq!(consumed, input.as_bytes());
+    }
+
+    #[test]
+    fn test_read_pkt_truncated() {
+        // truncated within the header
+        let mut consumed = Vec::new();
+        assert_eq!(read_pkt(&mut &b"00"[..], &mut consumed), None);
+        assert_eq!(consumed, b"00");
+
+        // truncated within the payload
+        let mut consumed = Vec::new();
+        assert_eq!(read_pkt(&mut &b"000ahel"[..], &mut consumed), None);
+        assert_eq!(consumed, b"000ahel");
+
+        // invalid length
+        assert_eq!(read_pkt(&mut &b"0002"[..], &mut Vec::new()), None);
+        assert_eq!(read_pkt(&mut &b"zzzz"[..], &mut Vec::new()), None);
+    }
+
+    #[test]
+    fn test_read_push_options() {
+        let input = format!(
+            "{}0000{}{}0000PACK",
+            pkt(&format!("{COMMAND}\0report-status push-options\n")),
+            pkt("rebuild-exposures\n"),
+            pkt("other"),
+        );
+        let mut reader = input.as_bytes();
+        let mut consumed = Vec::new();
+        assert_eq!(
+            read_push_options(&mut reader, &mut consumed),
+            ["rebuild-exposures", "other"],
+        );
+        // the remainder is left for the pack.
+        assert_eq!(reader, b"PACK");
+        assert_eq!([consumed.as_slice(), reader].concat(), input.as_bytes());
+    }
+
+    #[test]
+    fn test_read_push_options_without_capability() {
+        let input = format!(
+            "{}{}0000PACK",
+            pkt(&format!("{COMMAND}\0report-status\n")),
+            pkt(&format!("{COMMAND}\n")),
+        );
+        let mut reader = input.as_bytes();
+        let mut consumed = Vec::new();
+        assert!(read_push_options(&mut reader, &mut consumed).is_empty());
+        assert_eq!(reader, b"PACK");
+    }
+
+    #[test]
+    fn test_read_push_options_truncated() {
+        let input = format!(
+            "{}0000{}",
+            pkt(&format!("{COMMAND}\0push-options\n")),
+            &pkt("rebuild-exposures\n")[..8],
+        );
+        let mut consumed = Vec::new();
+        assert!(read_push_options(&mut input.as_bytes(), &mut consumed).is_empty());
+        assert_eq!(consumed, input.as_bytes());
+
+        let input = &pkt(&format!("{COMMAND}\0push-options\n"))[..20];
+        assert!(read_push_options(&mut input.as_bytes(), &mut Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_feed_receive_pack() {
+        let input = format!(
+            "{}0000{}0000PACK",
+            pkt(&format!("{COMMAND}\0push-options\n")),
+            pkt("rebuild-exposures\n"),
+        );
+        let mut output = Vec::new();
+        assert_eq!(
+            feed_receive_pack(input.as_bytes(), &mut output).unwrap(),
+            ["rebuild-exposures"],
+        );
+        assert_eq!(output, input.as_bytes());
+    }
+
+    #[test]
+    fn test_decoder_limit() {
+        use flate2::{
+            Compression,
+            write::GzEncoder,
+        };
+
+        let headers = HeaderMap::new();
+        let mut output = Vec::new();
+        decoder(&headers, &[0u8; 16][..], 16).unwrap()
+            .read_to_end(&mut output)
+            .unwrap();
+        assert_eq!(output.len(), 16);
+        let err = decoder(&headers, &[0u8; 17][..], 16).unwrap()
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&[0u8; 65536]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_ENCODING, "gzip".parse().unwrap());
+        let mut output = Vec::new();
+        decoder(&headers, compressed.as_slice(), 65536).unwrap()
+            .read_to_end(&mut output)
+            .unwrap();
+        assert_eq!(output.len(), 65536);
+        assert!(compressed.len() < 1024);
+        let err = decoder(&headers, compressed.as_slice(), 1024).unwrap()
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+
+        headers.insert(header::CONTENT_ENCODING, "br".parse().unwrap());
+        assert!(decoder(&headers, &[0u8; 16][..], 16).is_err());
+    }
+
+    // Sign in as the user with the login provided as the body, which is
+    // also the password, returning the bearer token for the session.
+    async fn sign_in(
+        Extension(mut session): Extension<AuthSession<ACPlatform>>,
+        bearer: BearerTokenSession,
+        login: String,
+    ) -> Result<String, AppError> {
+        let creds = Credentials {
+            authorization: Authorization::LoginPassword(login.clone(), login),
+            origin: "localhost".to_string(),
+        };
+        let auth = session.authenticate(creds).await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or(AppError::Forbidden)?;
+        session.login(&auth).await
+            .map_err(|_| AppError::InternalServerError)?;
+        bearer.save().await
+            .map_err(|_| AppError::InternalServerError)?;
+        bearer.encode_token()
+            .ok_or(AppError::InternalServerError)
+    }
+
+    // Run git within the directory, isolated from the configuration of the
+    // host, returning whether it succeeded.
+    async fn git(
+        dir: &std::path::Path,
+        token: Option<&str>,
+        args: &[&str],
+    ) -> anyhow::Result<bool> {
+        let mut cmd = Command::new("git");
+        cmd.current_dir(dir)
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .args(["-c", "user.name=Tester", "-c", "user.email=tester@example.com"]);
+        if let Some(token) = token {
+            cmd.arg("-c").arg(format!("http.extraHeader=Authorization: Bearer {token}"));
+        }
+        Ok(cmd.args(args)
+            .stdin(Stdio::null())
+            .output().await?
+            .status
+            .success())
+    }
+
+    // The status of the response to the request made with the token.
+    async fn status(
+        app: &Router,
+        method: &str,
+        uri: &str,
+        token: Option<&str>,
+    ) -> anyhow::Result<StatusCode> {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        Ok(app.clone().oneshot(request.body(Body::empty())?).await?.status())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_smart_http() -> anyhow::Result<()> {
+        let (root, mut platform) = create_sqlite_platform().await?;
+        // anonymous agents may read the published workspaces, as they do
+        // on the deployments.
+        platform.ac_platform = ACPlatformBuilder::new()
+            .boxed_ac_platform(create_sqlite_backend().await?)
+            .pmrrbac_builder(pmrrbac::Builder::new().anonymous_reader(true))
+            .build();
+        let ac_platform = &platform.ac_platform;
+        ac_platform.assign_policy_to_wf_state(State::Published, Role::Reader, "").await?;
+        ac_platform.assign_policy_to_wf_state(State::Published, Role::Owner, "").await?;
+        ac_platform.assign_policy_to_wf_state(State::Published, Role::Owner, "edit").await?;
+        ac_platform.assign_policy_to_wf_state(State::Private, Role::Owner, "").await?;
+        ac_platform.set_wf_state_for_res("/workspace/1/", State::Published).await?;
+        ac_platform.set_wf_state_for_res("/workspace/2/", State::Private).await?;
+        let editor = ac_platform.create_user("editor").await?;
+        editor.reset_password("editor", "editor").await?;
+        ac_platform.res_grant_role_to_agent("/workspace/1/", &editor, Role::Owner).await?;
+        // a workspace that has yet to be synchronized.
+        let unsynced = platform.mc_platform.add_workspace(
+            "https://models.example.com/unsynced/".into(),
+            "unsynced".into(),
+            "".into(),
+        ).await?;
+        let res = format!("/workspace/{unsynced}/");
+        ac_platform.set_wf_state_for_res(&res, State::Published).await?;
+        ac_platform.res_grant_role_to_agent(&res, &editor, Role::Owner).await?;
+        let reader = ac_platform.create_user("reader").await?;
+        reader.reset_password("reader", "reader").await?;
+        ac_platform.grant_role_to_user(&reader, Role::Reader).await?;
+
+        let app = Router::new()
+            .without_v07_checks()
+            .route("/login", post(sign_in))
+            .route("/workspace/:/id/{workspace_id}/info/refs", get(info_refs))
+            .route("/workspace/:/id/{workspace_id}/git-upload-pack", post(upload_pack))
+            .route("/workspace/:/id/{workspace_id}/git-receive-pack", post(receive_pack))
+            .layer(Extension(platform.clone()))
+            .layer(
+                BearerTokenAuthManagerLayer::new(
+                    MemoryStore::default(),
+                    platform.ac_platform.clone(),
+                )
+                .with_session_manager_layer(SessionManagerLayer::new(MemoryStore::default()))
+                .with_bearer_token_endpoint("/login"),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let server = format!("http://{}", listener.local_addr()?);
+        tokio::spawn({
+            let app = app.clone();
+            async move { axum::serve(listener, app).await }
+        });
+        let sign_in = |login: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(Request::post("/login").body(Body::from(login))?).await?;
+                assert_eq!(response.status(), StatusCode::OK);
+                let token = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+                Ok::<_, anyhow::Error>(String::from_utf8(token.to_vec())?)
+            }
+        };
+        let editor = sign_in("editor").await?;
+        let reader = sign_in("reader").await?;
+
+        // the public workspace may be cloned by anyone.
+        let public = format!("{server}/workspace/:/id/1");
+        assert!(git(root.path(), None, &["clone", &public, "anonymous"]).await?);
+        assert!(root.path().join("anonymous/README").exists());
+
+        // the private workspace is not available to anonymous agents.
+        assert_eq!(
+            status(&app, "GET", "/workspace/:/id/2/info/refs?service=git-upload-pack", None).await?,
+            StatusCode::FORBIDDEN,
+        );
+        assert_eq!(
+            status(&app, "POST", "/workspace/:/id/2/git-upload-pack", None).await?,
+            StatusCode::FORBIDDEN,
+        );
+        let private = format!("{server}/workspace/:/id/2");
+        assert!(!git(root.path(), None, &["clone", &private, "private"]).await?);
+        assert!(!root.path().join("private").exists());
+
+        // the editor may push into the workspace...
+        let clone = root.path().join("anonymous");
+        std::fs::write(clone.join("pushed"), "pushed by the editor\n")?;
+        assert!(git(&clone, None, &["add", "pushed"]).await?);
+        assert!(git(&clone, None, &["commit", "-m", "pushed by the editor"]).await?);
+        assert!(git(&clone, Some(&editor), &["push", "origin", "HEAD"]).await?);
+        assert!(git(root.path(), None, &["clone", &public, "pushed"]).await?);
+        assert!(root.path().join("pushed/pushed").exists());
+
+        // ... but the reader may not.
+        assert_eq!(
+            status(&app, "GET", "/workspace/:/id/1/info/refs?service=git-receive-pack", Some(&reader)).await?,
+            StatusCode::FORBIDDEN,
+        );
+        assert_eq!(
+            status(&app, "POST", "/workspace/:/id/1/git-receive-pack", Some(&reader)).await?,
+            StatusCode::FORBIDDEN,
+        );
+        assert!(git(&clone, None, &["commit", "--amend", "-m", "amended by the reader"]).await?);
+        assert!(!git(&clone, Some(&reader), &["push", "--force", "origin", "HEAD"]).await?);
+        // the reader may still clone the workspace.
+        assert!(git(root.path(), Some(&reader), &["clone", &public, "reader"]).await?);
+
+        // the repository of the unsynchronized workspace is only created by
+        // the push, not by the advertisements that may precede it.
+        let repo_dir = platform.repo_root().join(unsynced.to_string());
+        let info_refs = |service: &str| format!(
+            "/workspace/:/id/{unsynced}/info/refs?service={service}"
+        );
+        assert_eq!(
+            status(&app, "GET", &info_refs("git-receive-pack"), Some(&editor)).await?,
+            StatusCode::OK,
+        );
+        assert_eq!(
+            status(&app, "GET", &info_refs("git-upload-pack"), None).await?,
+            StatusCode::NOT_FOUND,
+        );
+        assert!(!repo_dir.exists());
+        let url = format!("{server}/workspace/:/id/{unsynced}");
+        assert!(git(&clone, Some(&editor), &["push", &url, "HEAD:refs/heads/main"]).await?);
+        assert!(repo_dir.is_dir());
+        assert!(git(root.path(), None, &["clone", "-b", "main", &url, "unsynced"]).await?);
+        assert!(root.path().join("unsynced/pushed").exists());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! The endpoints for logging in with, and linking the identities from, the
+//! OpenID Connect providers configured for the platform.
+//!
+//! The end-user is sent from either the login or the link endpoint for a
+//! provider to its authorization endpoint, with the pending authorization
+//! kept in the session until the provider redirects the end-user back to
+//! the callback endpoint, which is the `redirect_url` configured for the
+//! provider.
+
+use axum::{
+    Extension,
+    extract::{
+        ConnectInfo,
+        Path,
+        Query,
+    },
+    response::{
+        IntoResponse,
+        Redirect,
+        Response,
+    },
+};
+use axum_login::AuthSession;
+use http::StatusCode;
+use pmrac::{
+    Platform as ACPlatform,
+    axum_login::{
+        Authorization,
+        Credentials,
+    },
+    oidc::PendingAuthorization,
+};
+use pmrctrl::platform::Platform;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::net::SocketAddr;
+
+use crate::error::AuthError;
+
+const PENDING_KEY: &str = "pmrapp.oidc.pending";
+
+#[derive(Deserialize, Serialize)]
+struct Pending {
+    authorization: PendingAuthorization,
+    // the user to link the identity to, otherwise the identity is used to
+    // log in.
+    link_user_id: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct CallbackQuery {
+    state: Option<String>,
+    code: Option<String>,
+    error: Option<String>,
+}
+
+fn failure(error: AuthError) -> Response {
+    (StatusCode::FORBIDDEN, <&'static str>::from(error)).into_response()
+}
+
+async fn authorize(
+    platform: &Platform,
+    auth_session: &AuthSession<ACPlatform>,
+    provider: &str,
+    link_user_id: Option<i64>,
+) -> Result<Redirect, AuthError> {
+    let (url, authorization) = platform.ac_platform
+        .oidc_authorization(provider)
+        .await?;
+    auth_session.session
+        .insert(PENDING_KEY, Pending { authorization, link_user_id })
+        .await
+        .map_err(|_| AuthError::InternalServerError)?;
+    Ok(Redirect::to(&url))
+}
+
+pub async fn login(
+    Path(provider): Path<String>,
+    Extension(platform): Extension<Platform>,
+    auth_session: AuthSession<ACPlatform>,
+) -> Response {
+    authorize(&platform, &auth_session, &provider, None)
+        .await
+        .map(IntoResponse::into_response)
+        .unwrap_or_else(failure)
+}
+
+pub async fn link(
+    Path(provider): Path<String>,
+    Extension(platform): Extension<Platform>,
+    auth_session: AuthSession<ACPlatform>,
+) -> Response {
+    let Some(user_id) = auth_session.user.as_ref().map(|auth| auth.user().id()) else {
+        return Redirect::to("/auth/login").into_response();
+    };
+    // identities may not be linked to the account through a session
+    // derived from a personal access token.
+    if auth_session.user.as_ref().is_some_and(|auth| auth.user_token().is_some()) {
+        return failure(AuthError::TokenSession);
+    }
+    authorize(&platform, &auth_session, &provider, Some(user_id))
+        .await
+        .map(IntoResponse::into_response)
+        .unwrap_or_else(failure)
+}
+
+pub async fn callback(
+    Path(provider): Path<String>,
+    Query(query): Query<CallbackQuery>,
+    Extension(platform): Extension<Platform>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    mut auth_session: AuthSession<ACPlatform>,
+) -> Response {
+    // the pending authorization may only be used once.
+    let pending = match auth_session.session.remove::<Pending>(PENDING_KEY).await {
+        Ok(Some(pending)) if pending.authorization.provider == provider => pending,
+        Ok(_) => return failure(AuthError::InvalidCredentials),
+        Err(_) => return failure(AuthError::InternalServerError),
+    };
+    let (Some(state), Some(code)) = (query.state, query.code) else {
+        log::info!("authorization with provider {provider} failed: {:?}", query.error);
+        return failure(AuthError::InvalidCredentials);
+    };
+    let identity = match platform.ac_platform
+        .oidc_identity(&pending.authorization, &state, &code)
+        .await
+    {
+        Ok(identity) => identity,
+        Err(e) => return failure(e.into()),
+    };
+
+    match pending.link_user_id {
+        Some(user_id) => {
+            // the user must remain logged in for the identity to be linked.
+            if auth_session.user.as_ref().map(|auth| auth.user().id()) != Some(user_id) {
+                return failure(AuthError::InvalidCredentials);
+            }
+            if auth_session.user.as_ref().is_some_and(|auth| auth.user_token().is_some()) {
+                return failure(AuthError::TokenSession);
+            }
+            match platform.ac_platform.link_user_identity(user_id, &identity).await {
+                Ok(()) => Redirect::to("/auth/identities").into_response(),
+                Err(e) => failure(e.into()),
+            }
+        }
+        None => {
+            let credentials = Credentials {
+                authorization: Authorization::Identity(identity),
+                origin: addr.ip().to_string(),
+            };
+            let session = match auth_session.authenticate(credentials).await {
+                Ok(Some(session)) => session,
+                Ok(None) => return failure(AuthError::InvalidCredentials),
+                Err(axum_login::Error::Backend(e)) => return failure(e.into()),
+                Err(_) => return failure(AuthError::InternalServerError),
+            };
+            match auth_session.login(&session).await {
+                Ok(()) => Redirect::to("/").into_response(),
+                Err(_) => failure(AuthError::InternalServerError),
+            }
+        }
+    }
+}
@@ -0,0 +1,389 @@
+//! The work-claiming protocol for the remote runners, such that tasks may
+//! be executed on hosts that do not share the database and filesystem
+//! with this server, e.g. through `pmrctrl-remote-runner`.
+//!
+//! A runner claims a task, which leases the task to the runner for the
+//! configured duration; the runner must renew the lease before it expires
+//! for as long as the task is being executed, otherwise the task returns
+//! to the queue to be claimed again.  The runner fetches the files the
+//! task processes as a tar archive, uploads the basedir of the task as a
+//! tar archive once the task exits, and then completes the task with its
+//! exit status.  The runner identifies itself through the `host` query
+//! parameter, with a task no longer leased to the runner reported as a
+//! conflict.  These endpoints are only available when a runner token is
+//! configured, which the runners must provide as the `X-Runner-Token`
+//! header.
+
+use axum::{
+    Extension,
+    Json,
+    Router,
+    body::Body,
+    extract::{
+        Path,
+        Query,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+    routing::{
+        get,
+        post,
+        put,
+    },
+};
+use futures::TryStreamExt;
+use http::{
+    HeaderMap,
+    StatusCode,
+    header,
+};
+use pmrctrl::{
+    error::{
+        CtrlError,
+        PlatformError,
+    },
+    executor::pack_dir,
+    platform::Platform,
+};
+use serde::Deserialize;
+use std::io::ErrorKind;
+use tokio_util::io::{
+    ReaderStream,
+    StreamReader,
+    SyncIoBridge,
+};
+
+use crate::{
+    error::AppError,
+    server::{
+        git::Limit,
+        log_error,
+        webhook::verify_token,
+    },
+};
+
+/// The header that provides the runner token.
+pub const RUNNER_TOKEN_HEADER: &str = "x-runner-token";
+
+/// The limit on the size of the output uploaded for a task.
+pub const TASK_OUTPUT_LIMIT: usize = 1024 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct RemoteRunners {
+    pub token: String,
+    /// The duration of the leases in seconds.
+    pub lease: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunnerQuery {
+    host: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompleteQuery {
+    host: String,
+    exit_status: i64,
+}
+
+/// The routes for the remote runners, which require the platform to be
+/// provided as an extension.
+pub fn router<S>(runners: RemoteRunners) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/api/runner/claim", post(claim))
+        .route("/api/runner/task/{task_id}/lease", post(lease))
+        .route("/api/runner/task/{task_id}/input", get(input))
+        .route("/api/runner/task/{task_id}/output", put(output))
+        .route("/api/runner/task/{task_id}/complete", post(complete))
+        .layer(Extension(runners))
+}
+
+fn authorize(runners: &RemoteRunners, headers: &HeaderMap) -> Result<(), AppError> {
+    headers.get(RUNNER_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|token| verify_token(&runners.token, token))
+        .then_some(())
+        .ok_or(AppError::Forbidden)
+}
+
+// The runner is told when the task is no longer leased to it, so that it
+// stops working on the task.
+fn lease_error(error: PlatformError) -> Result<Response, AppError> {
+    match error {
+        PlatformError::CtrlError(CtrlError::TaskNotLeased(_)) =>
+            Ok(StatusCode::CONFLICT.into_response()),
+        error => Err(log_error(error)),
+    }
+}
+
+pub async fn claim(
+    platform: Extension<Platform>,
+    runners: Extension<RemoteRunners>,
+    headers: HeaderMap,
+    Query(query): Query<RunnerQuery>,
+) -> Result<Response, AppError> {
+    authorize(&runners, &headers)?;
+    match platform.claim_task(&query.host, runners.lease).await
+        .map_err(log_error)?
+    {
+        Some(claim) => {
+            log::info!("task id {} claimed by {}", claim.task.id, query.host);
+            Ok(Json(claim).into_response())
+        }
+        None => Ok(StatusCode::NO_CONTENT.into_response()),
+    }
+}
+
+pub async fn lease(
+    platform: Extension<Platform>,
+    runners: Extension<RemoteRunners>,
+    headers: HeaderMap,
+    Path(task_id): Path<i64>,
+    Query(query): Query<RunnerQuery>,
+) -> Result<Response, AppError> {
+    authorize(&runners, &headers)?;
+    platform.renew_task_lease(task_id, &query.host, runners.lease).await
+        .map(|lease_ts| Json(lease_ts).into_response())
+        .or_else(lease_error)
+}
+
+/// The files processed by the task, streamed as a tar archive as it is
+/// being written.
+pub async fn input(
+    platform: Extension<Platform>,
+    runners: Extension<RemoteRunners>,
+    headers: HeaderMap,
+    Path(task_id): Path<i64>,
+    Query(query): Query<RunnerQuery>,
+) -> Result<Response, AppError> {
+    authorize(&runners, &headers)?;
+    let checkout = match platform.leased_task_checkout(task_id, &query.host).await {
+        Ok(Some(checkout)) => checkout,
+        Ok(None) => Err(AppError::NotFound)?,
+        Err(e) => return lease_error(e),
+    };
+    let (writer, reader) = tokio::io::duplex(64 * 1024);
+    let writer = SyncIoBridge::new(writer);
+    // a failure truncates the archive, which the runner will fail on.
+    tokio::task::spawn_blocking(move || if let Err(e) = pack_dir(&checkout, writer) {
+        log::warn!("failed to write the input of task id {task_id}: {e}");
+    });
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-tar")],
+        Body::from_stream(ReaderStream::new(reader)),
+    ).into_response())
+}
+
+/// Store the basedir of the task as uploaded by the runner, which is
+/// streamed into place as it is being received.
+pub async fn output(
+    platform: Extension<Platform>,
+    runners: Extension<RemoteRunners>,
+    headers: HeaderMap,
+    Path(task_id): Path<i64>,
+    Query(query): Query<RunnerQuery>,
+    body: Body,
+) -> Result<Response, AppError> {
+    authorize(&runners, &headers)?;
+    let reader = Limit::new(
+        SyncIoBridge::new(StreamReader::new(
+            body.into_data_stream().map_err(std::io::Error::other)
+        )),
+        TASK_OUTPUT_LIMIT,
+    );
+    match platform.store_leased_task_output(task_id, &query.host, reader).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT.into_response()),
+        Err(PlatformError::IOError(e)) if e.kind() == ErrorKind::FileTooLarge =>
+            Err(AppError::BadRequest),
+        Err(e) => lease_error(e),
+    }
+}
+
+/// Complete the task with the exit status, returning whether the view
+/// was produced.
+pub async fn complete(
+    platform: Extension<Platform>,
+    runners: Extension<RemoteRunners>,
+    headers: HeaderMap,
+    Path(task_id): Path<i64>,
+    Query(query): Query<CompleteQuery>,
+) -> Result<Response, AppError> {
+    authorize(&runners, &headers)?;
+    platform.complete_leased_task(task_id, &query.host, query.exit_status).await
+        .map(|produced| Json(produced).into_response())
+        .or_else(lease_error)
+}
+
+#[cfg(test)]
+mod test {
+    use pmrcore::exposure::{
+        task::traits::ExposureTaskTemplateBackend,
+        traits::{
+            Exposure as _,
+            ExposureFile as _,
+            ExposureFileView as _,
+        },
+    };
+    use pmrcore::task_template::UserInputMap;
+    use pmrctrl::executor::RemoteExecutor;
+    use pmrtqs::executor::traits::Executor as _;
+    use test_binary::build_test_binary_once;
+    use test_pmr::ctrl::create_sqlite_platform;
+    use tokio::sync::broadcast;
+
+    use super::*;
+
+    // Queue the task for the view of the file in the exposure, returning
+    // the ids of the exposure file, the view and the task.
+    async fn queue_view(
+        platform: &Platform,
+        exposure_id: i64,
+        path: &str,
+        vtt: i64,
+    ) -> anyhow::Result<Vec<(i64, i64, i64)>> {
+        let exposure = platform.get_exposure(exposure_id).await?;
+        let efc = exposure.create_file(path).await?;
+        let file_id = efc.exposure_file().id();
+        ExposureTaskTemplateBackend::set_file_templates(
+            platform.mc_platform.as_ref(),
+            file_id,
+            &[vtt],
+        ).await?;
+        let efvttsc = efc.build_vttc().await?;
+        let user_input = UserInputMap::from([]);
+        let tasks = efvttsc.create_tasks_from_input(&user_input)?;
+        Ok(efc.process_vttc_tasks(tasks).await?
+            .into_iter()
+            .map(|(view_id, task_id)| (file_id, view_id, task_id))
+            .collect())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_remote_runners() -> anyhow::Result<()> {
+        build_test_binary_once!(iorw, "../testing");
+        let iorw = path_to_iorw().into_string()
+            .expect("failed to build testing/iorw");
+        let (root, platform) = create_sqlite_platform().await?;
+        let vtt = platform.adds_view_task_template(
+            serde_json::from_str(&format!(r#"{{
+                "view_key": "iorw",
+                "description": "Input/Output Read/Write",
+                "task_template": {{
+                    "bin_path": "{iorw}",
+                    "version_id": "1.0.0",
+                    "args": [
+                        {{
+                            "flag": null,
+                            "flag_joined": false,
+                            "flag_omit_when_null": false,
+                            "prompt": "",
+                            "default": "workspace_file_path",
+                            "choice_fixed": true,
+                            "choice_source": "workspace_file_path",
+                            "choices": []
+                        }},
+                        {{
+                            "flag": null,
+                            "flag_joined": false,
+                            "flag_omit_when_null": false,
+                            "prompt": "",
+                            "default": "working_dir",
+                            "choice_fixed": true,
+                            "choice_source": "working_dir",
+                            "choices": []
+                        }}
+                    ]
+                }}
+            }}"#))?
+        ).await?;
+        let exposure = platform.create_exposure(
+            3,
+            "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+        ).await?;
+        let mut views = Vec::new();
+        for path in [
+            "dir1/nested/file_a",
+            "dir1/nested/file_b",
+            "dir1/nested/file_c",
+        ] {
+            views.extend(queue_view(&platform, exposure.exposure().id(), path, vtt).await?);
+        }
+
+        let app = router(RemoteRunners {
+            token: "secret".into(),
+            lease: 60,
+        }).layer(Extension(platform.clone()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let server = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        // nothing may be claimed without the token.
+        let intruder = RemoteExecutor::new(
+            &server,
+            "wrong",
+            "intruder",
+            root.path().join("intruder"),
+        );
+        assert!(intruder.start_task().await.is_err());
+
+        // drive the runners until the queue is drained, so the outcome
+        // does not depend on how quickly the tasks complete.
+        let hosts = ["runner1", "runner2"];
+        let executors = hosts.map(|host| RemoteExecutor::new(
+            &server,
+            "secret",
+            host,
+            root.path().join(host),
+        ));
+        let (abort_sender, _) = broadcast::channel(1);
+        let completed = futures::future::try_join_all(
+            executors.iter().map(|executor| {
+                let abort_sender = &abort_sender;
+                async move {
+                    let mut completed = 0;
+                    while let Some(task) = executor.start_task().await? {
+                        executor.execute(task, abort_sender.subscribe()).await?;
+                        completed += 1;
+                    }
+                    Ok::<_, PlatformError>(completed)
+                }
+            })
+        ).await?;
+        assert_eq!(completed.iter().sum::<usize>(), views.len());
+
+        let mc_platform = platform.mc_platform.as_ref();
+        for (file_id, view_id, task_id) in views.iter() {
+            // the output uploaded by the runner is served as the view.
+            let size = exposure.read_blob(*file_id, "iorw", "size").await?;
+            assert!(!size.is_empty());
+            assert!(mc_platform.get_exposure_file_view(*view_id).await?
+                .view_key()
+                .is_some());
+            let task = platform.get_task(*task_id).await?;
+            assert_eq!(task.exit_status, Some(0));
+            assert!(hosts.contains(&task.host.as_deref().expect("host recorded")));
+            // the lease ends with the task.
+            assert!(matches!(
+                platform.renew_task_lease(*task_id, task.host.as_deref().unwrap(), 60).await,
+                Err(PlatformError::CtrlError(CtrlError::TaskNotLeased(_))),
+            ));
+        }
+        assert_eq!(
+            exposure.read_blob(views[2].0, "iorw", "size").await?,
+            b"13",
+        );
+        // the runners clean up after the tasks.
+        for host in hosts {
+            if let Ok(mut entries) = std::fs::read_dir(root.path().join(host)) {
+                assert!(entries.next().is_none());
+            }
+        }
+
+        Ok(())
+    }
+}
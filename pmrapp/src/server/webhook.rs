@@ -0,0 +1,185 @@
+//! The webhook for the push events from the services hosting the remote
+//! repositories of the workspaces, i.e. GitHub, GitLab and Gitea.
+//!
+//! A push to a repository requests the synchronization of all workspaces
+//! with the url of that repository through the sync scheduler, and the
+//! ids of those workspaces are returned.  The webhook is only available
+//! when a secret is configured, as the events must be signed with it, i.e.
+//! through the `X-Hub-Signature-256` header for GitHub, the
+//! `X-Gitea-Signature` header for Gitea, or by providing the secret as the
+//! `X-Gitlab-Token` header for GitLab.
+
+use axum::{
+    Extension,
+    Json,
+    body::Bytes,
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use hmac::{
+    Hmac,
+    Mac,
+};
+use http::{
+    HeaderMap,
+    StatusCode,
+};
+use pmrctrl::{
+    platform::Platform,
+    scheduler::SyncQueue,
+};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::{
+    error::AppError,
+    server::log_error,
+};
+
+#[derive(Clone)]
+pub struct Webhook {
+    pub queue: SyncQueue,
+    pub secret: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Provider {
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+impl Provider {
+    /// Identify the provider along with the event from the headers.
+    fn from_headers(headers: &HeaderMap) -> Option<(Self, &str)> {
+        // Gitea also provides the GitHub header for compatibility, so it
+        // must be checked first.
+        [
+            ("x-gitea-event", Provider::Gitea),
+            ("x-gitlab-event", Provider::GitLab),
+            ("x-github-event", Provider::GitHub),
+        ]
+            .into_iter()
+            .find_map(|(name, provider)| headers.get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|event| (provider, event))
+            )
+    }
+
+    fn is_push(&self, event: &str) -> bool {
+        match self {
+            Provider::GitHub | Provider::Gitea => event == "push",
+            Provider::GitLab => matches!(event, "Push Hook" | "Tag Push Hook"),
+        }
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8], secret: &str) -> bool {
+        let header = |name| headers.get(name)
+            .and_then(|value| value.to_str().ok());
+        match self {
+            Provider::GitHub => header("x-hub-signature-256")
+                .and_then(|value| value.strip_prefix("sha256="))
+                .is_some_and(|signature| verify_signature(secret, body, signature)),
+            Provider::Gitea => header("x-gitea-signature")
+                .is_some_and(|signature| verify_signature(secret, body, signature)),
+            Provider::GitLab => header("x-gitlab-token")
+                .is_some_and(|token| verify_token(secret, token)),
+        }
+    }
+}
+
+/// Verify the hex encoded HMAC-SHA256 signature of the body.
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Verify the token against the secret in constant time, by comparing the
+/// HMAC-SHA256 of both under the same key.
+pub(crate) fn verify_token(secret: &str, token: &str) -> bool {
+    let mac = |value: &str| {
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"x-gitlab-token")
+            .expect("HMAC accepts keys of any length");
+        mac.update(value.as_bytes());
+        mac
+    };
+    mac(secret).verify_slice(&mac(token).finalize().into_bytes()).is_ok()
+}
+
+/// The fields that provide the urls of the repository across the payloads
+/// of the providers; GitLab provides these under `project`.
+#[derive(Debug, Default, Deserialize)]
+struct Repository {
+    clone_url: Option<String>,
+    git_url: Option<String>,
+    html_url: Option<String>,
+    ssh_url: Option<String>,
+    git_http_url: Option<String>,
+    git_ssh_url: Option<String>,
+    web_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushEvent {
+    repository: Option<Repository>,
+    project: Option<Repository>,
+}
+
+impl PushEvent {
+    fn urls(&self) -> Vec<&str> {
+        [&self.repository, &self.project]
+            .into_iter()
+            .flatten()
+            .flat_map(|repository| [
+                &repository.clone_url,
+                &repository.git_url,
+                &repository.html_url,
+                &repository.ssh_url,
+                &repository.git_http_url,
+                &repository.git_ssh_url,
+                &repository.web_url,
+            ])
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+pub async fn push(
+    platform: Extension<Platform>,
+    webhook: Extension<Webhook>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    let (provider, event) = Provider::from_headers(&headers)
+        .ok_or(AppError::BadRequest)?;
+    if !provider.verify(&headers, &body, &webhook.secret) {
+        Err(AppError::Forbidden)?
+    }
+    if !provider.is_push(event) {
+        // e.g. the ping sent when the webhook is created.
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+    let payload: PushEvent = serde_json::from_slice(&body)
+        .map_err(|_| AppError::BadRequest)?;
+    let urls = payload.urls();
+    let ids = platform.list_workspaces_by_remote(&urls)
+        .await
+        .map_err(log_error)?;
+    for id in ids.iter() {
+        if !webhook.queue.enqueue(*id) {
+            Err(log_error("sync queue is full or the scheduler is not running"))?
+        }
+    }
+    log::info!("{provider:?} push to {urls:?} queued sync for workspaces {ids:?}");
+    Ok((StatusCode::ACCEPTED, Json(ids)).into_response())
+}
+
@@ -1,7 +1,14 @@
 use axum::{
     Extension,
     Json,
-    extract::Path,
+    body::{
+        Body,
+        Bytes,
+    },
+    extract::{
+        Path,
+        Query,
+    },
     response::{
         IntoResponse,
         Redirect,
@@ -17,12 +24,23 @@ use collection_json::{
 use http::header;
 use pmrac::Platform as ACPlatform;
 use pmrcore::repo::{
+    ArchiveFormat,
     PathObjectInfo,
     RemoteInfo,
 };
 use pmrctrl::platform::Platform;
-use pmrrepo::handle::GitResultTarget;
-use std::io::Write;
+use futures::stream;
+use pmrrepo::handle::{
+    Archive,
+    GitResultTarget,
+};
+use serde::Deserialize;
+use std::io::{
+    self,
+    BufWriter,
+    Write,
+};
+use tokio::sync::mpsc;
 
 use crate::{
     app::id::Id,
@@ -31,6 +49,7 @@ use crate::{
         self,
         ac::Session,
     },
+    workspace::api::repo_error,
 };
 
 pub async fn resolve_id(id: Id) -> Result<i64, AppError> {
@@ -167,3 +186,135 @@ pub async fn raw_workspace_download(
     Ok(result.unwrap_or_else(|e| AppError::from(e).into_response()))
 }
 
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+pub struct ArchiveQuery {
+    /// Restrict the archive to the contents under this path.
+    path: Option<String>,
+    /// Include the contents of submodules from the workspaces registered
+    /// for them, rather than leaving them as empty directories.
+    #[serde(default)]
+    submodules: bool,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/workspace/{workspace_alias}/archive/{commit_id}/{format}",
+    summary = "This endpoint is also bound to `/workspace/{workspace_alias}/archive/{commit_id}/{format}`.",
+    params(
+        ("workspace_alias" = String, Path, description = "Workspace's alias."),
+        ("commit_id" = String, Path, description = "The commit id."),
+        ("format" = ArchiveFormat, Path, description = "The format of the archive."),
+        ArchiveQuery,
+    ),
+    responses((
+        status = 200,
+        description = "The archive of the contents at the commit.",
+        body = Vec<u8>,
+    ), AppError),
+    security(
+        (),
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+pub async fn archive_aliased_workspace_download(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path((workspace_alias, commit_id, format)): Path<(String, String, String)>,
+    Query(query): Query<ArchiveQuery>,
+) -> Result<Response, AppError> {
+    let workspace_id = platform
+        .mc_platform
+        .resolve_alias("workspace", &workspace_alias)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or(AppError::NotFound)?;
+    let session = Session::from(session);
+    session.enforcer(format!("/workspace/{workspace_id}/"), "").await?;
+    archive_download(&platform, &session, workspace_id, &commit_id, &format, query, &workspace_alias).await
+}
+
+pub async fn archive_workspace_download(
+    platform: Extension<Platform>,
+    session: Extension<AuthSession<ACPlatform>>,
+    Path((workspace_id, commit_id, format)): Path<(i64, String, String)>,
+    Query(query): Query<ArchiveQuery>,
+) -> Result<Response, AppError> {
+    let session = Session::from(session);
+    session.enforcer(format!("/workspace/{workspace_id}/"), "").await?;
+    archive_download(&platform, &session, workspace_id, &commit_id, &format, query, &format!("workspace-{workspace_id}")).await
+}
+
+/// Respond with the archive of the workspace at the commit, named after
+/// the provided name and the commit.  Access to the resource that leads
+/// to the workspace must have been enforced by the caller, while access
+/// to the workspaces of the submodules is enforced here via the session.
+pub(crate) async fn archive_download(
+    platform: &Platform,
+    session: &Session,
+    workspace_id: i64,
+    commit_id: &str,
+    format: &str,
+    query: ArchiveQuery,
+    name: &str,
+) -> Result<Response, AppError> {
+    let format = format.parse::<ArchiveFormat>()
+        .map_err(|_| AppError::NotFound)?;
+    let handle = platform.repo_backend().git_handle(workspace_id).await
+        .map_err(|_| AppError::NotFound)?;
+    let archive = handle.archive(
+        Some(commit_id),
+        query.path.as_deref(),
+        query.submodules.then_some(session as _),
+    ).await
+        .map_err(repo_error)?;
+    let name = format!("{name}-{}", &archive.commit_id()[..12]);
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{name}.{}\"", format.extension()),
+            ),
+        ],
+        archive_body(archive, format, format!("{name}/")),
+    ).into_response())
+}
+
+/// Stream the archive as it is being written out by a blocking task.  A
+/// failure part way through ends the body with the error, which aborts
+/// the response rather than letting it pass as a complete archive.
+fn archive_body(archive: Archive, format: ArchiveFormat, prefix: String) -> Body {
+    let (sender, mut receiver) = mpsc::channel(4);
+    tokio::task::spawn_blocking(move || {
+        let mut writer = BufWriter::with_capacity(64 * 1024, ChunkSender(sender));
+        let result = archive.write(format, &prefix, &mut writer)
+            .map_err(io::Error::other)
+            .and_then(|_| writer.flush());
+        // anything left buffered is discarded so the error is the last.
+        let (chunks, _) = writer.into_parts();
+        if let Err(e) = result {
+            // the client may have simply gone away.
+            log::warn!("failed to write archive of {}: {e}", archive.commit_id());
+            let _ = chunks.0.blocking_send(Err(e));
+        }
+    });
+    Body::from_stream(stream::poll_fn(move |cx| receiver.poll_recv(cx)))
+}
+
+// Sends everything written as the chunks of a body.
+struct ChunkSender(mpsc::Sender<io::Result<Bytes>>);
+
+impl Write for ChunkSender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
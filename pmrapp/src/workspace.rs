@@ -15,7 +15,10 @@ use leptos_router::{
         ParentRoute,
         Route,
     },
-    hooks::use_params,
+    hooks::{
+        use_params,
+        use_query,
+    },
     nested_router::Outlet,
     params::{
         Params,
@@ -27,18 +30,32 @@ use leptos_router::{
     WildcardSegment,
 };
 use leptos_sync_ssr::signal::SsrWriteSignal;
-use pmrcore::repo::{
-    LogEntryInfo,
-    PathObjectInfo,
-    RepoResult,
-    TreeInfo,
+use pmrcore::{
+    listing::{
+        Listing,
+        Page,
+    },
+    repo::{
+        BlameHunk,
+        ChangeStatus,
+        FileDiff,
+        LogEntryInfo,
+        PathObjectInfo,
+        RepoResult,
+        TreeInfo,
+    },
 };
 
 pub(crate) mod api;
 
 use crate::{
     ac::AccountCtx,
-    component::RedirectTS,
+    component::{
+        ListingParams,
+        NextPage,
+        Redirect,
+        RedirectTS,
+    },
     enforcement::{
         EnforcedOk,
         PolicyState,
@@ -52,12 +69,14 @@ use crate::{
     workspace::api::{
         list_workspaces,
         list_aliased_workspaces,
+        get_blame_info,
+        get_diff_info,
         get_log_info,
         get_workspace_info,
         workspace_root_policy_state,
         CreateWorkspace,
         Synchronize,
-        Workspaces,
+        WorkspacePage,
     },
     app::{
         portlet::{
@@ -106,6 +125,14 @@ fn WorkspaceViewRoutes() -> impl leptos_router::MatchNestedRoutes + Clone {
                 path=(StaticSegment("create_exposure"), ParamSegment("commit"),)
                 view=WorkspaceCreateExposure
                 />
+            <Route
+                path=(StaticSegment("diff"), ParamSegment("commit"), WildcardSegment("path"),)
+                view=WorkspaceDiff
+                />
+            <Route
+                path=(StaticSegment("blame"), ParamSegment("commit"), WildcardSegment("path"),)
+                view=WorkspaceBlame
+                />
             <Route path=StaticSegment("log") view=WorkspaceLog/>
         </ParentRoute>
     }
@@ -121,7 +148,7 @@ pub fn WorkspaceRoot() -> impl IntoView {
         on_cleanup(move || account_ctx.cleanup_policy_state());
     }
 
-    let workspaces: Resource<Result<Workspaces, AppError>> = Resource::new(
+    let workspaces: Resource<Result<WorkspacePage, AppError>> = Resource::new(
         move || (),
         move |_| {
             let set_ps = account_ctx.policy_state.write_only();
@@ -140,7 +167,8 @@ pub fn WorkspaceRoot() -> impl IntoView {
                     .as_deref()
                     .map(str::to_lowercase)
                     .cmp(&b.entity.description.as_deref().map(str::to_lowercase)));
-                Ok(result)
+                // the aliased workspaces are not paginated.
+                Ok(Page { entries: result, next: None })
             }
         },
     );
@@ -161,13 +189,15 @@ pub fn WorkspaceIdRoot() -> impl IntoView {
     // standard to potentially save on an additional call, but this isn't
     // typically used so the small bit of bandwidth inefficiency will have
     // to be tolerated for now.
-    let workspaces: Resource<Result<Workspaces, AppError>> = Resource::new(
-        move || (),
-        move |_| {
+    let query = use_query::<ListingParams>();
+    let workspaces: Resource<Result<WorkspacePage, AppError>> = Resource::new(
+        move || query.get().ok().map(Listing::from),
+        move |listing| {
             async move {
-                // The id workspace root will provide the standard list of
-                // workspaces as a resource, without the notify version done.
-                list_workspaces()
+                // The id workspace root will provide the requested page of
+                // the standard listing of workspaces as a resource, without
+                // the notify version done.
+                list_workspaces(listing.ok_or(AppError::BadRequest)?)
                     .await
                     .map(EnforcedOk::into_inner)
             }
@@ -229,27 +259,32 @@ fn workspace_root_page_ctx() -> impl IntoView {
 #[component]
 pub fn WorkspaceListing() -> impl IntoView {
     let root = expect_context::<Root>();
-    let workspaces = expect_context::<Resource<Result<Workspaces, AppError>>>();
+    let workspaces = expect_context::<Resource<Result<WorkspacePage, AppError>>>();
 
     let workspace_listing = move || {
         Suspend::new(async move {
-            workspaces.await.map(|workspaces| workspaces
-                .into_iter()
-                .map(move |workspace| {
-                    view! {
-                        <div>
-                            // TODO the link should point to the primary alias
-                            <div><a href=format!("{root}{}/", workspace.alias)>
-                                {workspace.entity.description.unwrap_or_else(
-                                    || format!("Workspace {}", workspace.entity.id))}
-                            </a></div>
-                            <div>{workspace.entity.url}</div>
-                            <div>{workspace.entity.long_description.unwrap_or("".to_string())}</div>
-                        </div>
-                    }
-                })
-                .collect_view()
-            )
+            workspaces.await.map(|workspaces| {
+                let entries = workspaces.entries
+                    .into_iter()
+                    .map(move |workspace| {
+                        view! {
+                            <div>
+                                // TODO the link should point to the primary alias
+                                <div><a href=format!("{root}{}/", workspace.alias)>
+                                    {workspace.entity.description.unwrap_or_else(
+                                        || format!("Workspace {}", workspace.entity.id))}
+                                </a></div>
+                                <div>{workspace.entity.url}</div>
+                                <div>{workspace.entity.long_description.unwrap_or("".to_string())}</div>
+                            </div>
+                        }
+                    })
+                    .collect_view();
+                view! {
+                    {entries}
+                    <NextPage next=workspaces.next/>
+                }
+            })
         })
     };
 
@@ -375,7 +410,15 @@ pub fn Workspace() -> impl IntoView {
     provide_context(resource);
     provide_context(params);
 
+    // superceded workspaces are redirected to their successor.
+    let successor = move || Suspend::new(async move {
+        resource.await.ok()
+            .and_then(|info| info.workspace.superceded_by_id)
+            .map(|id| view! { <Redirect path=format!("/workspace/:/id/{id}/")/> })
+    });
+
     view! {
+        <Transition>{successor}</Transition>
         {content_action_ctx.update_with(
             move || {
                 async move {
@@ -508,6 +551,22 @@ fn WorkspaceListingView(
     };
 
     let path = repo_result.path.clone().unwrap_or_else(|| String::new());
+    let archive = repo_result.commit.as_ref().map(|commit| {
+        let href = format!("{base_href}/archive/{}", commit.commit_id);
+        let query = if path.is_empty() {
+            String::new()
+        } else {
+            format!("?path={path}")
+        };
+        view! {
+            <div class="archive-download">
+                "Download: "
+                <a href=format!("{href}/zip{query}") target="_self">"zip"</a>
+                " "
+                <a href=format!("{href}/tar.gz{query}") target="_self">"tar.gz"</a>
+            </div>
+        }
+    });
     let commit_id = repo_result.commit
         .clone()
         .map(|commit| commit.commit_id)
@@ -539,6 +598,7 @@ fn WorkspaceListingView(
             }
             </tbody>
         </table>
+        {archive}
     }
 }
 
@@ -562,6 +622,10 @@ fn WorkspaceFileInfoView(
                 <div>{info}</div>
                 <div>
                     <a href=href.clone() target="_self">"download"</a>
+                    " "
+                    <a href=format!("{base_href}/log?path={path}")>"history"</a>
+                    " "
+                    <a href=format!("{base_href}/blame/{commit_id}/{path}")>"blame"</a>
                 </div>
                 {
                     (file_info.mime_type[..5] == *"image").then(||
@@ -721,22 +785,157 @@ pub fn WorkspaceCommitPath() -> impl IntoView {
     }
 }
 
+#[derive(Params, PartialEq, Clone, Debug)]
+pub struct WorkspaceDiffQuery {
+    from: Option<String>,
+}
+
+#[component]
+pub fn WorkspaceDiff() -> impl IntoView {
+    let root = expect_context::<Root>();
+    let entity_root = expect_context::<Memo<EntityRoot>>();
+    let workspace_params = expect_context::<Memo<Result<WorkspaceParams, ParamsError>>>();
+    let params = use_params::<WorkspaceCommitPathParams>();
+    let query = use_query::<WorkspaceDiffQuery>();
+
+    let resource = Resource::new_blocking(
+        move || (
+            workspace_params.get().map(|p| p.id),
+            params.get().map(|p| (p.commit, p.path)),
+            query.get().map(|q| q.from),
+        ),
+        move |p| async move {
+            match p {
+                (Ok(Some(id)), Ok((commit, path)), Ok(from)) => {
+                    let id = root.build_id(id)?;
+                    get_diff_info(id, from, commit, path).await
+                        .map(EnforcedOk::notify_into_inner)
+                }
+                _ => Err(AppError::InternalServerError),
+            }
+        }
+    );
+
+    let view = move || Suspend::new(async move {
+        resource.await.map(|info| {
+            let base_href = entity_root.get().to_string();
+            let desc = info.workspace.description
+                .clone()
+                .unwrap_or_else(
+                    || format!("Workspace {}", &info.workspace.id)
+                );
+            let new_commit_id = info.new_commit.commit_id;
+            let commit_link = |commit_id: &str| view! {
+                <a href=format!("{base_href}/file/{commit_id}/")>
+                    {commit_id.get(..12).unwrap_or(commit_id).to_string()}
+                </a>
+            };
+            let summary = match info.old_commit {
+                Some(old_commit) => view! {
+                    "Changes from "{commit_link(&old_commit.commit_id)}
+                    " to "{commit_link(&new_commit_id)}
+                }.into_any(),
+                None => view! {
+                    "Changes introduced by "{commit_link(&new_commit_id)}
+                }.into_any(),
+            };
+            let files = if info.files.is_empty() {
+                view! { <p>"No files were changed."</p> }.into_any()
+            } else {
+                info.files.into_iter()
+                    .map(|file| view! {
+                        <WorkspaceFileDiffView
+                            file
+                            href=format!("{base_href}/file/{new_commit_id}/")/>
+                    })
+                    .collect_view()
+                    .into_any()
+            };
+            view! {
+                <h1><a href=base_href.clone()>{desc}</a></h1>
+                <div class="workspace-diff">
+                    <p>
+                        {summary}
+                        {info.path.map(|path| view! { " under "<code>{path}</code> })}
+                    </p>
+                    {files}
+                </div>
+            }
+        })
+    });
+
+    view! {
+        <Transition fallback=move || view! { <p>"Loading info..."</p> }>
+            <ErrorBoundary fallback=|errors| view!{ <ErrorTemplate errors/>}>
+                {view}
+            </ErrorBoundary>
+        </Transition>
+    }
+}
+
+#[component]
+fn WorkspaceFileDiffView(
+    file: FileDiff,
+    href: String,
+) -> impl IntoView {
+    let FileDiff { status, path, old_path, binary, diff, .. } = file;
+    // deleted files are no longer available at the new commit.
+    let name = match status {
+        ChangeStatus::Deleted => view! { {path} }.into_any(),
+        _ => view! { <a href=format!("{href}{path}")>{path.clone()}</a> }.into_any(),
+    };
+    let status = match status {
+        ChangeStatus::Added => "added",
+        ChangeStatus::Deleted => "deleted",
+        ChangeStatus::Modified => "modified",
+        ChangeStatus::Renamed => "renamed",
+        ChangeStatus::Copied => "copied",
+    };
+    view! {
+        <section class="file-diff">
+            <h4>
+                <span class=format!("change {status}")>{status}</span>" "
+                {old_path.map(|old_path| view! { {old_path}" → " })}
+                {name}
+            </h4>
+            {match diff {
+                Some(diff) if !diff.is_empty() => view! { <pre>{diff}</pre> }.into_any(),
+                Some(_) => view! { <p>"No changes to the content."</p> }.into_any(),
+                None if binary => view! { <p>"Binary file not shown."</p> }.into_any(),
+                None => view! { <p>"Submodule not shown."</p> }.into_any(),
+            }}
+        </section>
+    }
+}
+
+#[derive(Params, PartialEq, Clone, Debug)]
+pub struct WorkspaceLogQuery {
+    path: Option<String>,
+    after: Option<String>,
+}
+
 #[component]
 pub fn WorkspaceLog() -> impl IntoView {
     let workspace_params = expect_context::<Memo<Result<WorkspaceParams, ParamsError>>>();
     let root = expect_context::<Root>();
     let entity_root = expect_context::<Memo<EntityRoot>>();
+    let query = use_query::<WorkspaceLogQuery>();
 
     let repo_result = expect_context::<Resource<Result<RepoResult, AppError>>>();
     let log_info = Resource::new_blocking(
-        move || workspace_params.get().map(|p| p.id),
-        move |id| async move {
-            match id {
-                Err(_) => Err(AppError::InternalServerError),
-                Ok(None) => Err(AppError::NotFound),
-                Ok(Some(id)) => {
+        move || (
+            workspace_params.get().map(|p| p.id),
+            query.get().map(|q| (q.path, q.after)),
+        ),
+        move |p| async move {
+            match p {
+                (Err(_), _) | (_, Err(_)) => Err(AppError::InternalServerError),
+                (Ok(None), _) => Err(AppError::NotFound),
+                (Ok(Some(id)), Ok((path, after))) => {
                     let id = root.build_id(id)?;
-                    get_log_info(id)
+                    // the history of a file includes its prior names.
+                    let follow = path.is_some();
+                    get_log_info(id, None, path, follow, after, None)
                         .await
                         .map(EnforcedOk::notify_into_inner)
                         .map_err(AppError::from)
@@ -771,9 +970,11 @@ pub fn WorkspaceLog() -> impl IntoView {
     let view = move || Suspend::new(async move {
         let log_info = log_info.await?;
         let mut exposure_map = exposure_map.await?;
+        let path = query.get_untracked().ok().and_then(|q| q.path);
         repo_result.await.map(|_| {
             let href = entity_root.read();
             view! {
+                {path.map(|path| view! { <p>"History of "<code>{path}</code></p> })}
                 <table class="log-listing">
                     <thead>
                         <tr>
@@ -792,6 +993,7 @@ pub fn WorkspaceLog() -> impl IntoView {
                                 author,
                                 message,
                                 commit_id,
+                                path,
                                 ..
                             }| view! {
                                 <tr>
@@ -804,7 +1006,11 @@ pub fn WorkspaceLog() -> impl IntoView {
                                     <td>{author.clone()}</td>
                                     <td>{message.clone()}</td>
                                     <td>
-                                        <a href=format!("{href}/file/{commit_id}/")>"[files]"</a>
+                                        <a href=format!(
+                                            "{href}/file/{commit_id}/{}",
+                                            path.as_deref().unwrap_or(""),
+                                        )>"[files]"</a>
+                                        <a href=format!("{href}/diff/{commit_id}/")>"[diff]"</a>
                                         <a href=format!("{href}/create_exposure/{commit_id}/")>"[create_exposure]"</a>
                                     </td>
                                     <td>{
@@ -822,8 +1028,84 @@ pub fn WorkspaceLog() -> impl IntoView {
                             .collect_view()
                     }</tbody>
                 </table>
+                <NextPage next=log_info.next/>
+            }
+
+        })
+    });
+
+    view! {
+        <Transition fallback=move || view! { <p>"Loading info..."</p> }>
+            <ErrorBoundary fallback=|errors| view!{ <ErrorTemplate errors/>}>
+                {view}
+            </ErrorBoundary>
+        </Transition>
+    }
+}
+
+#[derive(Params, PartialEq, Clone, Debug)]
+pub struct WorkspaceBlameQuery {
+    start: Option<u32>,
+}
+
+#[component]
+pub fn WorkspaceBlame() -> impl IntoView {
+    let root = expect_context::<Root>();
+    let entity_root = expect_context::<Memo<EntityRoot>>();
+    let workspace_params = expect_context::<Memo<Result<WorkspaceParams, ParamsError>>>();
+    let params = use_params::<WorkspaceCommitPathParams>();
+    let query = use_query::<WorkspaceBlameQuery>();
+
+    let resource = Resource::new_blocking(
+        move || (
+            workspace_params.get().map(|p| p.id),
+            params.get().map(|p| (p.commit, p.path)),
+            query.get().map(|q| q.start),
+        ),
+        move |p| async move {
+            match p {
+                (Ok(Some(id)), Ok((commit, Some(path))), Ok(start)) => {
+                    let id = root.build_id(id)?;
+                    get_blame_info(id, commit, path, start, None).await
+                        .map(EnforcedOk::notify_into_inner)
+                }
+                (_, Ok((_, None)), _) => Err(AppError::NotFound),
+                _ => Err(AppError::InternalServerError),
             }
+        }
+    );
 
+    let view = move || Suspend::new(async move {
+        resource.await.map(|info| {
+            let base_href = entity_root.get().to_string();
+            let desc = info.workspace.description
+                .clone()
+                .unwrap_or_else(
+                    || format!("Workspace {}", &info.workspace.id)
+                );
+            let commit_id = info.commit.commit_id;
+            view! {
+                <h1><a href=base_href.clone()>{desc}</a></h1>
+                <div class="workspace-blame">
+                    <p>
+                        "Blame of "
+                        <a href=format!("{base_href}/file/{commit_id}/{}", info.path)>
+                            <code>{info.path.clone()}</code>
+                        </a>
+                        " at "{commit_id.get(..12).unwrap_or(&commit_id).to_string()}
+                    </p>
+                    <table class="blame-listing">
+                        <tbody>{
+                            info.hunks.into_iter()
+                                .map(|hunk| view! {
+                                    <WorkspaceBlameHunkView hunk base_href=base_href.clone()/>
+                                })
+                                .collect_view()
+                        }</tbody>
+                    </table>
+                    <NextPage next=info.next key="start"/>
+                </div>
+            }
         })
     });
 
@@ -836,6 +1118,42 @@ pub fn WorkspaceLog() -> impl IntoView {
     }
 }
 
+#[component]
+fn WorkspaceBlameHunkView(
+    hunk: BlameHunk,
+    base_href: String,
+) -> impl IntoView {
+    let BlameHunk { start_line, commit, lines, .. } = hunk;
+    let end_line = start_line as usize + lines.len();
+    view! {
+        <tr>
+            <td class="commit">
+                <a href=format!("{base_href}/diff/{}/", commit.commit_id)>
+                    {commit.commit_id.get(..12).unwrap_or(&commit.commit_id).to_string()}
+                </a>
+                <div>{commit.author}</div>
+                <div>{
+                    Utc.timestamp_opt(commit.commit_timestamp, 0)
+                        .map(|dt| dt.format("%Y-%m-%d").to_string())
+                        .single()
+                        .unwrap_or_else(|| "????-??-??".to_string())
+                }</div>
+            </td>
+            <td class="line-no">
+                <pre>{
+                    (start_line as usize..end_line)
+                        .map(|no| no.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }</pre>
+            </td>
+            <td class="lines">
+                <pre>{lines.join("\n")}</pre>
+            </td>
+        </tr>
+    }
+}
+
 #[component]
 fn WorkspaceCreateExposure() -> impl IntoView {
     let workspace_params = expect_context::<Memo<Result<WorkspaceParams, ParamsError>>>();
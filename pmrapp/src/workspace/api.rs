@@ -1,7 +1,13 @@
 use leptos::{server, server_fn};
 use pmrcore::{
     alias::AliasEntry,
+    listing::{
+        Listing,
+        Page,
+    },
     repo::{
+        BlameInfo,
+        DiffResult,
         LogInfo,
         RepoResult,
     },
@@ -26,9 +32,19 @@ mod ssr {
             WorkspaceBackend,
         },
     };
+    pub use pmrcore::listing::{
+        DEFAULT_LIMIT,
+        MAX_LIMIT,
+    };
+    pub use pmrrepo::error::{
+        ContentError,
+        GixError,
+        PmrRepoError,
+    };
     pub use crate::{
         ac::api::session,
         server::{
+            listing_error,
             platform,
             workspace::resolve_id,
         }
@@ -37,7 +53,21 @@ mod ssr {
 #[cfg(feature = "ssr")]
 use self::ssr::*;
 
+/// Map the errors from the repo that are due to the request, e.g. an
+/// unknown commit or path, to the appropriate client error.
+#[cfg(feature = "ssr")]
+pub(crate) fn repo_error(e: PmrRepoError) -> AppError {
+    match e {
+        PmrRepoError::PathError(_)
+        | PmrRepoError::GixError(GixError::RevisionSpecParseSingle(_)) => AppError::NotFound,
+        PmrRepoError::ContentError(ContentError::Invalid { .. }) => AppError::BadRequest,
+        PmrRepoError::ContentError(ContentError::SubmoduleDenied { .. }) => AppError::Forbidden,
+        _ => AppError::InternalServerError,
+    }
+}
+
 pub type Workspaces = Vec<AliasEntry<Workspace>>;
+pub type WorkspacePage = Page<AliasEntry<Workspace>>;
 
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
@@ -59,13 +89,52 @@ pub async fn workspace_root_policy_state() -> Result<PolicyState, AppError> {
         .enforcer_and_policy_state("/workspace/", "").await?)
 }
 
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct ListWorkspacesArgs {
+    listing: Option<Listing>,
+}
+
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
     path = "/api/list_workspaces",
+    request_body(
+        description = r#"
+List a page of workspaces.  Default values will be used where the listing, or any of its fields,
+are omitted.
+
+The `next` cursor from a page may be provided as `after` to retrieve the following page, along with
+the same `sort` that produced it.
+        "#,
+        content((
+            ListWorkspacesArgs = "application/json",
+            examples(
+                ("Example 1" = (
+                    summary = "The first page of workspaces using the default listing.",
+                    value = json!({})
+                )),
+                ("Example 2" = (
+                    summary = "The page of published workspaces with exposures following the provided cursor, newest first.",
+                    value = json!({
+                        "listing": {
+                            "sort": "created_ts",
+                            "order": "desc",
+                            "after": "c1700000000.42",
+                            "limit": 20,
+                            "state": "published",
+                            "has_exposure": true
+                        }
+                    })
+                )),
+            )
+        )),
+    ),
     responses((
         status = 200,
-        description = "List of workspaces within an EnforcedOk; wrapped in EnforcedOk due to typical usage as a top level page listing.",
-        body = EnforcedOk<Workspaces>,
+        description = "Page of workspaces within an EnforcedOk; wrapped in EnforcedOk due to typical usage as a top level page listing.",
+        body = EnforcedOk<WorkspacePage>,
     ), AppError),
     security(
         (),
@@ -73,20 +142,24 @@ pub async fn workspace_root_policy_state() -> Result<PolicyState, AppError> {
         ("bearer" = []),
     ),
 ))]
-#[server(endpoint = "list_workspaces")]
-pub async fn list_workspaces() -> Result<EnforcedOk<Workspaces>, AppError> {
+#[server(
+    endpoint = "list_workspaces",
+    input = server_fn::codec::Json,
+)]
+pub async fn list_workspaces(
+    #[server(default)]
+    listing: Listing,
+) -> Result<EnforcedOk<WorkspacePage>, AppError> {
     let policy_state = session().await?
         .enforcer_and_policy_state("/workspace/", "").await?;
     let platform = platform().await?;
     Ok(policy_state.to_enforced_ok(
-        WorkspaceBackend::list_workspaces(platform.mc_platform.as_ref()).await
-            .map_err(|_| AppError::InternalServerError)?
-            .into_iter()
+        platform.list_workspaces_page(&listing).await
+            .map_err(listing_error)?
             .map(|workspace| AliasEntry {
                 alias: workspace.id.to_string(),
                 entity: workspace,
             })
-            .collect()
     ))
 }
 
@@ -193,20 +266,46 @@ pub async fn get_workspace_info(
     let handle = platform.repo_backend()
         .git_handle(id).await
         .map_err(|_| AppError::InternalServerError)?;
-    match (commit.as_ref(), path.as_ref(), handle.repo()) {
-        (None, None, Err(_)) => Ok(policy_state.to_enforced_ok(RepoResult {
+    // archived workspaces are only kept around for their successors.
+    if handle.workspace().archived_ts().is_some()
+        && handle.workspace().superceded_by_id().is_none()
+    {
+        Err(AppError::NotFound)?
+    }
+    let mut result: RepoResult = match (commit.as_ref(), path.as_ref(), handle.repo()) {
+        (None, None, Err(_)) => RepoResult {
             workspace: handle.workspace().clone_inner(),
             commit: None,
             path: None,
             target: None,
-        })),
+        },
         (_, _, Err(_)) => Err(AppError::InternalServerError)?,
-        (_, _, Ok(_)) => Ok(policy_state.to_enforced_ok(handle
+        (_, _, Ok(_)) => handle
             .pathinfo(commit, path)
             .map_err(|_| AppError::InternalServerError)?
-            .into()
-        )),
+            .into(),
+    };
+    // provide the final successor in the chain such that the client is
+    // only ever redirected once, and never around a cycle.
+    if result.workspace.superceded_by_id.is_some() {
+        result.workspace.superceded_by_id = platform
+            .resolve_workspace_successor(id).await
+            .map_err(|_| AppError::InternalServerError)?;
     }
+    Ok(policy_state.to_enforced_ok(result))
+}
+
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct LogInfoArgs {
+    id: Id,
+    commit: Option<String>,
+    path: Option<String>,
+    follow: Option<bool>,
+    after: Option<String>,
+    limit: Option<u32>,
 }
 
 #[cfg_attr(feature = "utoipa", utoipa::path(
@@ -214,10 +313,20 @@ pub async fn get_workspace_info(
     path = "/api/get_log_info",
     request_body(
         description = r#"
-Get log entries associated with a workspace.
+Get log entries associated with a workspace.  Default values will be used
+where null is provided.
+
+Default commit is the top level commit.
+
+Default path includes all commits, otherwise only the commits that changed
+the path will be included; with `follow`, the path will be followed through
+the commits that renamed it.
+
+The `next` value of the result may be provided as `after` to get the
+following page of entries.
         "#,
         content((
-            Id = "application/json",
+            LogInfoArgs = "application/json",
             examples(
                 ("Example 1" = (
                     summary = "Get log entries associated a specific workspace by alias.",
@@ -227,13 +336,25 @@ Get log entries associated with a workspace.
                         },
                     })
                 )),
+                ("Example 2" = (
+                    summary = "Get the following page of the history of a file, including its history before it was renamed.",
+                    value = json!({
+                        "id": {
+                          "Aliased": "beeler_reuter_1977"
+                        },
+                        "path": "beeler_reuter_1977.cellml",
+                        "follow": true,
+                        "after": "cb090c96a2ce627457b14def4910ac39219b8340",
+                        "limit": 20
+                    })
+                )),
             )
         )),
     ),
     responses((
         status = 200,
         description = "Log entries from the workspace",
-        body = EnforcedOk<RepoResult>,
+        body = EnforcedOk<LogInfo>,
     ), AppError),
     security(
         (),
@@ -247,6 +368,12 @@ Get log entries associated with a workspace.
 )]
 pub async fn get_log_info(
     id: Id,
+    commit: Option<String>,
+    path: Option<String>,
+    #[server(default)]
+    follow: bool,
+    after: Option<String>,
+    limit: Option<u32>,
 ) -> Result<EnforcedOk<LogInfo>, AppError> {
     let id = resolve_id(id).await?;
     let policy_state = session().await?
@@ -255,9 +382,200 @@ pub async fn get_log_info(
     let handle = platform.repo_backend()
         .git_handle(id).await
         .map_err(|_| AppError::InternalServerError)?;
-    // FIXME only returning up to the first 30 log entries.
-    Ok(policy_state.to_enforced_ok(handle.loginfo(None, None, Some(30))
-        .map_err(|_| AppError::InternalServerError)?))
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    Ok(policy_state.to_enforced_ok(handle
+        .history(
+            commit.as_deref(),
+            path.as_deref(),
+            follow,
+            after.as_deref(),
+            Some(limit as usize),
+        )
+        .map_err(repo_error)?
+    ))
+}
+
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct BlameInfoArgs {
+    id: Id,
+    commit: Option<String>,
+    path: String,
+    start: Option<u32>,
+    limit: Option<u32>,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/get_blame_info",
+    request_body(
+        description = r#"
+Get the commits that last changed the lines of a file within a workspace.
+Default values will be used where null is provided.
+
+Default commit is the top level commit.
+
+Default start is the first line of the file, and the `next` value of the
+result may be provided as `start` to get the following lines.
+        "#,
+        content((
+            BlameInfoArgs = "application/json",
+            examples(
+                ("Example 1" = (
+                    summary = "Blame the first lines of a file at the top level commit.",
+                    value = json!({
+                        "id": {
+                          "Aliased": "beeler_reuter_1977"
+                        },
+                        "path": "beeler_reuter_1977.cellml"
+                    })
+                )),
+                ("Example 2" = (
+                    summary = "Blame the following lines of a file at a specific commit.",
+                    value = json!({
+                        "id": {
+                          "Number": "684"
+                        },
+                        "commit": "cb090c96a2ce627457b14def4910ac39219b8340",
+                        "path": "beeler_reuter_1977.cellml",
+                        "start": 501,
+                        "limit": 500
+                    })
+                )),
+            )
+        )),
+    ),
+    responses((
+        status = 200,
+        description = "The hunks of lines of the file along with the commits that last changed them",
+        body = EnforcedOk<BlameInfo>,
+    ), AppError),
+    security(
+        (),
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "get_blame_info",
+    input = server_fn::codec::Json,
+)]
+pub async fn get_blame_info(
+    id: Id,
+    commit: Option<String>,
+    path: String,
+    start: Option<u32>,
+    limit: Option<u32>,
+) -> Result<EnforcedOk<BlameInfo>, AppError> {
+    let id = resolve_id(id).await?;
+    let policy_state = session().await?
+        .enforcer_and_policy_state(format!("/workspace/{id}/"), "").await?;
+    let platform = platform().await?;
+    let handle = platform.repo_backend()
+        .git_handle(id).await
+        .map_err(|_| AppError::InternalServerError)?;
+    // lines are more numerous than the entries of other listings.
+    let limit = limit.unwrap_or(MAX_LIMIT).clamp(1, MAX_LIMIT);
+    Ok(policy_state.to_enforced_ok(handle
+        .blame(commit.as_deref(), &path, start, Some(limit))
+        .map_err(repo_error)?
+    ))
+}
+
+// this struct is a placeholder to help utoipa
+#[cfg(feature = "utoipa")]
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+struct DiffInfoArgs {
+    id: Id,
+    old_commit: Option<String>,
+    new_commit: Option<String>,
+    path: Option<String>,
+}
+
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/get_diff_info",
+    request_body(
+        description = r#"
+Compare two commits of a workspace.  Default values will be used where null
+is provided.
+
+Default new commit is the top level commit.
+
+Default old commit is the first parent of the new commit; if the new commit
+has no parent, every file within will be listed as added.
+
+Default path includes all the files in the repo, otherwise only the changes
+to the files at or under the path will be included.
+        "#,
+        content((
+            DiffInfoArgs = "application/json",
+            examples(
+                ("Example 1" = (
+                    summary = "The changes made by the top level commit of a workspace identified by its alias.",
+                    value = json!({
+                        "id": {
+                          "Aliased": "beeler_reuter_1977"
+                        },
+                        "old_commit": null,
+                        "new_commit": null,
+                        "path": null
+                    })
+                )),
+                ("Example 2" = (
+                    summary = "The changes to a file between an exposed commit and the top level commit.",
+                    value = json!({
+                        "id": {
+                          "Number": "684"
+                        },
+                        "old_commit": "cb090c96a2ce627457b14def4910ac39219b8340",
+                        "new_commit": "HEAD",
+                        "path": "beeler_reuter_1977.cellml"
+                    })
+                )),
+            )
+        )),
+    ),
+    responses((
+        status = 200,
+        description = "The files changed between the two commits",
+        body = EnforcedOk<DiffResult>,
+    ), AppError),
+    security(
+        (),
+        ("cookie" = []),
+        ("bearer" = []),
+    ),
+))]
+#[server(
+    endpoint = "get_diff_info",
+    input = server_fn::codec::Json,
+)]
+pub async fn get_diff_info(
+    id: Id,
+    old_commit: Option<String>,
+    new_commit: Option<String>,
+    path: Option<String>,
+) -> Result<EnforcedOk<DiffResult>, AppError> {
+    let id = resolve_id(id).await?;
+    let policy_state = session().await?
+        .enforcer_and_policy_state(format!("/workspace/{id}/"), "").await?;
+    let platform = platform().await?;
+    let handle = platform.repo_backend()
+        .git_handle(id).await
+        .map_err(|_| AppError::InternalServerError)?;
+    if handle.workspace().archived_ts().is_some()
+        && handle.workspace().superceded_by_id().is_none()
+    {
+        Err(AppError::NotFound)?
+    }
+    Ok(policy_state.to_enforced_ok(handle
+        .diff(old_commit.as_deref(), new_commit.as_deref(), path.as_deref())
+        .map_err(repo_error)?
+    ))
 }
 
 // this struct is a placeholder to help utoipa
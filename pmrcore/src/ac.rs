@@ -1,5 +1,6 @@
 pub mod agent;
 pub mod genpolicy;
+pub mod group;
 pub mod permit;
 pub mod role;
 pub mod session;
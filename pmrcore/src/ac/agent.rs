@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
-use super::user::User;
+use super::{
+    group::Group,
+    user::User,
+};
 
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
@@ -7,6 +10,7 @@ pub enum Agent {
     #[default]
     Anonymous,
     User(User),
+    Group(Group),
 }
 
 #[cfg(feature = "display")]
@@ -4,7 +4,10 @@ use std::fmt::{
     Result,
 };
 
-use crate::ac::user::User;
+use crate::ac::{
+    group::Group,
+    user::User,
+};
 use super::Agent;
 
 impl Display for Agent {
@@ -15,6 +18,7 @@ impl Display for Agent {
             match self {
                 Agent::Anonymous => format!("<Agent:Anonymous>"),
                 Agent::User(User { name, .. }) => format!("<User:{name}>"),
+                Agent::Group(Group { name, .. }) => format!("<Group:{name}>"),
             }
         )
     }
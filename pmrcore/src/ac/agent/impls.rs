@@ -1,4 +1,7 @@
-use crate::ac::user::User;
+use crate::ac::{
+    group::Group,
+    user::User,
+};
 use super::Agent;
 
 impl From<User> for Agent {
@@ -7,11 +10,18 @@ impl From<User> for Agent {
     }
 }
 
+impl From<Group> for Agent {
+    fn from(group: Group) -> Agent {
+        Agent::Group(group)
+    }
+}
+
 impl From<&Agent> for Option<i64> {
     fn from(agent: &Agent) -> Self {
         match agent {
             Agent::Anonymous => None,
             Agent::User(User { id, .. }) => Some(*id),
+            Agent::Group(_) => None,
         }
     }
 }
@@ -21,6 +31,7 @@ impl From<Agent> for Option<String> {
         match agent {
             Agent::Anonymous => None,
             Agent::User(User { name, .. }) => Some(name),
+            Agent::Group(Group { name, .. }) => Some(name),
         }
     }
 }
@@ -15,13 +15,17 @@ use crate::ac::role::Role;
 pub struct Policy {
     pub agent: Agent,
     pub resource: String,
+    /// The names of the groups the agent is a member of.
+    #[serde(default)]
+    pub agent_groups: Vec<String>,
     pub agent_roles: Vec<AgentRole>,
     pub res_grants: Vec<ResGrant>,
     pub role_permits: Vec<RolePermit>,
 }
 
 /// A resource grant - the agent will have the stated role at the given
-/// resource.
+/// resource.  Where the grant is made to a group, `group` is the name of
+/// the group and the role applies to the members of the group.
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub struct ResGrant {
@@ -29,6 +33,8 @@ pub struct ResGrant {
     // res this was granted for, which may be at a higher level.
     pub res: String,
     pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     pub role: Role,
 }
 
@@ -3,6 +3,8 @@ use crate::ac::role::Roles;
 use super::*;
 
 impl Policy {
+    /// The roles of the agent, which includes the roles granted to the
+    /// groups the agent is a member of.
     pub fn to_roles(&self) -> Roles {
         let mut results = Roles(
             self.agent_roles
@@ -12,6 +14,11 @@ impl Policy {
         );
         results.0 |= self.res_grants
             .iter()
+            .filter(|v| v.group
+                .as_ref()
+                .map(|group| self.agent_groups.contains(group))
+                .unwrap_or(true)
+            )
             .map(|v| v.role)
             .collect::<EnumSet<_>>();
         results
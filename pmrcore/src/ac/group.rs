@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// A named group of users.  Roles granted to the group at a resource
+/// apply to every member of the group.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct Group {
+    pub id: i64,
+    pub name: String,
+    pub created_ts: i64,
+}
@@ -7,6 +7,9 @@ pub struct Session {
     pub origin: String,
     pub created_ts: i64,
     pub last_active_ts: i64,
+    /// The personal access token the session was created from, which
+    /// limits the session to the scopes of the token.
+    pub user_token_id: Option<i64>,
 }
 
 #[cfg(feature = "server")]
@@ -34,6 +34,7 @@ impl SessionFactory {
             origin,
             created_ts,
             last_active_ts,
+            user_token_id: None,
         }
     }
 }
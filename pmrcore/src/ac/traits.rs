@@ -3,12 +3,20 @@ use crate::error::BackendError;
 use super::{
     agent::Agent,
     genpolicy::Policy,
+    group::Group,
     role::Role,
     session::{
         Session,
         SessionToken,
     },
-    user::User,
+    user::{
+        User,
+        UserEmail,
+        UserEmailBindReq,
+        UserIdentity,
+        UserPasswordResetReq,
+        UserToken,
+    },
     workflow::State,
 };
 
@@ -39,6 +47,154 @@ pub trait UserBackend {
         &self,
         user_id: i64,
     ) -> Result<(), BackendError>;
+
+    async fn add_user_email(
+        &self,
+        user_id: i64,
+        email: &str,
+    ) -> Result<i64, BackendError>;
+    async fn get_user_emails(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserEmail>, BackendError>;
+    async fn get_user_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Option<User>, BackendError>;
+    async fn add_user_email_bindreq(
+        &self,
+        email: &str,
+        origin_user_id: Option<i64>,
+        origin: &str,
+        token: &str,
+    ) -> Result<i64, BackendError>;
+    /// Returns the bind request with the token, provided that it has
+    /// not been consumed.
+    async fn get_user_email_bindreq(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserEmailBindReq>, BackendError>;
+    /// Count the bind requests made from the origin since the timestamp,
+    /// including the ones that have been consumed or rejected.
+    async fn count_user_email_bindreqs_by_origin(
+        &self,
+        origin: &str,
+        since_ts: i64,
+    ) -> Result<i64, BackendError>;
+    /// Consume the bind request by clearing its token, such that it may
+    /// no longer be used.  Returns false if it was already consumed.
+    async fn consume_user_email_bindreq(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError>;
+    /// Mark the bind request as rejected by the owner of the address,
+    /// which also disables its token.
+    async fn reject_user_email_bindreq(
+        &self,
+        id: i64,
+    ) -> Result<(), BackendError>;
+
+    async fn add_user_password_resetreq(
+        &self,
+        user_email: &UserEmail,
+        origin: &str,
+        token: &str,
+    ) -> Result<i64, BackendError>;
+    /// Returns the password reset request with the token, provided that
+    /// it has not been consumed.
+    async fn get_user_password_resetreq(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserPasswordResetReq>, BackendError>;
+    /// Count the password reset requests made from the origin since the
+    /// timestamp, including the ones that have been consumed.
+    async fn count_user_password_resetreqs_by_origin(
+        &self,
+        origin: &str,
+        since_ts: i64,
+    ) -> Result<i64, BackendError>;
+    /// Consume the password reset request by clearing its token.  Returns
+    /// false if it was already consumed.
+    async fn consume_user_password_resetreq(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError>;
+    /// Consume all outstanding password reset requests of the user by
+    /// clearing their tokens, returning the number of requests consumed.
+    async fn consume_user_password_resetreqs_for_user(
+        &self,
+        user_id: i64,
+    ) -> Result<u64, BackendError>;
+
+    async fn add_user_identity(
+        &self,
+        user_id: i64,
+        provider: &str,
+        subject: &str,
+    ) -> Result<i64, BackendError>;
+    async fn get_user_by_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<User>, BackendError>;
+    async fn get_user_identities(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserIdentity>, BackendError>;
+    /// Remove the identity from the user.  Returns false if the identity
+    /// was not linked to the user.
+    async fn remove_user_identity(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<bool, BackendError>;
+
+    async fn add_user_token(
+        &self,
+        user_id: i64,
+        name: &str,
+        token: &str,
+        scopes: &[String],
+        expires_ts: i64,
+    ) -> Result<i64, BackendError>;
+    /// Returns the personal access token with the token, regardless of
+    /// whether it has expired.
+    async fn get_user_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserToken>, BackendError>;
+    async fn get_user_token_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<UserToken>, BackendError>;
+    async fn get_user_tokens(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserToken>, BackendError>;
+    /// Remove the personal access token from the user, along with the
+    /// sessions that were created from it.  Returns false if the token
+    /// does not belong to the user.
+    async fn remove_user_token(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<bool, BackendError>;
+
+    async fn add_group(
+        &self,
+        name: &str,
+    ) -> Result<i64, BackendError>;
+    async fn get_group_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<Group>, BackendError>;
+    async fn get_group_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<Group>, BackendError>;
+    async fn get_groups(
+        &self,
+    ) -> Result<Vec<Group>, BackendError>;
 }
 
 #[async_trait]
@@ -58,6 +214,25 @@ pub trait PolicyBackend {
         user: &User,
     ) -> Result<Vec<Role>, BackendError>;
 
+    async fn add_user_to_group(
+        &self,
+        group: &Group,
+        user: &User,
+    ) -> Result<bool, BackendError>;
+    async fn remove_user_from_group(
+        &self,
+        group: &Group,
+        user: &User,
+    ) -> Result<bool, BackendError>;
+    async fn get_groups_for_user(
+        &self,
+        user: &User,
+    ) -> Result<Vec<Group>, BackendError>;
+    async fn get_group_members(
+        &self,
+        group: &Group,
+    ) -> Result<Vec<User>, BackendError>;
+
     async fn res_grant_role_to_agent(
         &self,
         res: &str,
@@ -110,6 +285,13 @@ pub trait ResourceBackend {
         wf_state: State,
         ts: i64,
     ) -> Result<(), BackendError>;
+    /// Returns the resources with the workflow state, limited to those
+    /// that start with the `prefix`.
+    async fn list_res_with_wf_state(
+        &self,
+        wf_state: State,
+        prefix: &str,
+    ) -> Result<Vec<String>, BackendError>;
     async fn generate_policy_for_agent_res(
         &self,
         agent: &Agent,
@@ -8,6 +8,7 @@ pub struct User {
     pub created_ts: i64,
 }
 
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct UserEmail {
     pub id: i64,
@@ -15,6 +16,21 @@ pub struct UserEmail {
     pub email: String,
 }
 
+/// A request to bind an email address to a user, which is only granted
+/// once the token sent to the email address is presented back.  Where
+/// `origin_user_id` is absent, the request is for a new user.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UserEmailBindReq {
+    pub id: i64,
+    pub email: String,
+    pub origin_user_id: Option<i64>,
+    pub origin: Option<String>,
+    /// This is cleared once the request is consumed.
+    pub token: Option<String>,
+    pub created_ts: i64,
+    pub rejected: bool,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct UserPassword {
     pub id: i64,
@@ -22,3 +38,42 @@ pub struct UserPassword {
     pub password: String,
     pub created_ts: i64,
 }
+
+/// A request to reset the password of a user, bound to the email address
+/// of the user that the token was sent to.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UserPasswordResetReq {
+    pub id: i64,
+    pub user_id: i64,
+    pub user_email_id: i64,
+    pub origin: String,
+    /// This is cleared once the request is consumed.
+    pub token: Option<String>,
+    pub created_ts: i64,
+}
+
+/// An identity from an external identity provider linked to a user, where
+/// `subject` is the identifier of the user assigned by the provider.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UserIdentity {
+    pub id: i64,
+    pub user_id: i64,
+    pub provider: String,
+    pub subject: String,
+    pub created_ts: i64,
+}
+
+/// A personal access token of a user, which only permits the actions
+/// listed in its scopes until it expires.  The token itself is not
+/// included as it is only provided to the user once upon creation.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UserToken {
+    pub id: i64,
+    pub user_id: i64,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_ts: i64,
+    pub expires_ts: i64,
+}
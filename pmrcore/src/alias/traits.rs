@@ -36,6 +36,21 @@ pub trait AliasBackend {
         kind: &str,
         ids: &[i64],
     ) -> Result<Vec<(String, i64)>, BackendError>;
+    /// Reassign the aliases of the kind from `kind_id` to `new_kind_id`,
+    /// returning the number of aliases reassigned.
+    async fn reassign_aliases(
+        &self,
+        kind: &str,
+        kind_id: i64,
+        new_kind_id: i64,
+    ) -> Result<u64, BackendError>;
+    /// Remove the aliases of the kind for `kind_id` such that they may be
+    /// used again, returning the number of aliases removed.
+    async fn remove_aliases(
+        &self,
+        kind: &str,
+        kind_id: i64,
+    ) -> Result<u64, BackendError>;
 }
 
 #[async_trait]
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::task::TaskStatus;
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct ExposureFileViewTaskTemplate {
     #[serde(default)]
@@ -22,4 +24,47 @@ pub struct ExposureFileViewTask {
     pub ready: bool,
 }
 
+/// The task most recently queued to generate an exposure file view,
+/// identified by the path of the exposure file and the view_key of the
+/// view task template for the view.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ExposureFileViewTaskStatus {
+    pub exposure_file_id: i64,
+    pub workspace_file_path: String,
+    pub exposure_file_view_id: i64,
+    pub view_key: String,
+    pub task_id: Option<i64>,
+    // the status of the task is from the other backend
+    #[serde(default)]
+    pub task: Option<TaskStatus>,
+}
+
+/// An exposure file view that has been built, along with the task
+/// template currently linked to its view task template, such that the
+/// views built by tasks from a superseded task template may be found.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ExposureFileViewBuild {
+    pub exposure_id: i64,
+    pub exposure_file_id: i64,
+    pub workspace_file_path: String,
+    pub exposure_file_view_id: i64,
+    pub view_key: String,
+    pub task_template_id: i64,
+    pub task_id: Option<i64>,
+    // the task template of the task is from the other backend; this is
+    // unknown for views restored from the cache or built by tasks that
+    // have since been removed.
+    #[serde(default)]
+    pub built_task_template_id: Option<i64>,
+}
+
+impl ExposureFileViewBuild {
+    /// Whether the view was not known to have been built by a task from
+    /// the task template currently linked to its view task template.
+    pub fn is_outdated(&self) -> bool {
+        self.built_task_template_id != Some(self.task_template_id)
+    }
+}
+
 pub mod traits;
@@ -4,7 +4,11 @@ use crate::{
         BackendError,
         Error,
     },
-    exposure::task::ExposureFileViewTask,
+    exposure::task::{
+        ExposureFileViewBuild,
+        ExposureFileViewTask,
+        ExposureFileViewTaskStatus,
+    },
     profile::ViewTaskTemplate,
 };
 
@@ -38,4 +42,19 @@ pub trait ExposureTaskBackend {
         &self,
         task_id: i64,
     ) -> Result<Option<(i64, Option<String>)>, Error>;
+    /// List the task most recently queued for every exposure file view
+    /// of the exposure, ordered by the path of the exposure file then
+    /// the view_key; the status of the tasks are not provided.
+    async fn list_view_tasks_for_exposure(
+        &self,
+        exposure_id: i64,
+    ) -> Result<Vec<ExposureFileViewTaskStatus>, BackendError>;
+    /// List the exposure file views that have been built, optionally
+    /// only those with a view task template linked to the task template,
+    /// ordered by the exposure then the path of the exposure file then
+    /// the view_key; the task templates of the tasks are not provided.
+    async fn list_view_builds(
+        &self,
+        task_template_id: Option<i64>,
+    ) -> Result<Vec<ExposureFileViewBuild>, BackendError>;
 }
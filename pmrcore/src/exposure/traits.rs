@@ -5,6 +5,10 @@ use crate::{
         Error,
     },
     exposure,
+    listing::{
+        ListingQuery,
+        Page,
+    },
 };
 
 #[async_trait]
@@ -61,6 +65,15 @@ pub trait ExposureBackend {
         &self,
     ) -> Result<exposure::Exposures, BackendError>;
 
+    /// Returns a page of `Exposures`.
+    ///
+    /// An error is returned if the cursor does not apply to the sort key
+    /// of the query.
+    async fn list_page(
+        &self,
+        query: &ListingQuery,
+    ) -> Result<Page<exposure::Exposure>, BackendError>;
+
     /// Returns all `Exposures` by ids
     async fn list_by_ids(
         &self,
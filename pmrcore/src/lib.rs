@@ -7,6 +7,7 @@ pub mod exposure;
 pub mod git;
 pub mod idgen;
 pub mod index;
+pub mod listing;
 pub mod platform;
 pub mod profile;
 pub mod repo;
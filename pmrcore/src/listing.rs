@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use crate::ac::workflow::State;
+
+/// The number of entries returned for a page when no limit is provided.
+pub const DEFAULT_LIMIT: u32 = 50;
+/// The maximum number of entries that may be returned for a page.
+pub const MAX_LIMIT: u32 = 500;
+
+/// The field a listing is sorted by.  The id of the entries will be
+/// used to break any ties.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    #[default]
+    Id,
+    CreatedTs,
+    Description,
+}
+
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// The position of the last entry of a page within a listing, such that
+/// the following page may be requested.
+///
+/// The cursor is bound to the [`SortKey`] of the listing that produced
+/// it, and its string form should be treated as opaque.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Cursor {
+    Id(i64),
+    CreatedTs(i64, i64),
+    Description(String, i64),
+}
+
+/// The request for a page of a listing.
+///
+/// The `state` and `owner` filters are resolved by the platform through
+/// access control, as the underlying backends have no knowledge of them.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Listing {
+    pub sort: SortKey,
+    pub order: SortOrder,
+    #[cfg_attr(feature="utoipa", schema(value_type = Option<String>))]
+    pub after: Option<Cursor>,
+    pub limit: Option<u32>,
+    /// Only include entries with this workflow state.
+    pub state: Option<State>,
+    /// Only include entries owned by the user with this name.
+    pub owner: Option<String>,
+    /// Only include workspaces with (or without) exposures; this has no
+    /// effect on listings of other kinds of entries.
+    pub has_exposure: Option<bool>,
+}
+
+/// The query for a page of a listing as provided to the backends.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListingQuery {
+    pub sort: SortKey,
+    pub order: SortOrder,
+    pub after: Option<Cursor>,
+    pub limit: Option<u32>,
+    /// Restrict the listing to entries with these ids.
+    pub ids: Option<Vec<i64>>,
+    /// See [`Listing::has_exposure`].
+    pub has_exposure: Option<bool>,
+}
+
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Page<T> {
+    pub entries: Vec<T>,
+    /// The cursor for the following page, if there are more entries.
+    #[cfg_attr(feature="utoipa", schema(value_type = Option<String>))]
+    pub next: Option<Cursor>,
+}
+
+mod impls;
@@ -0,0 +1,266 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+use crate::error::{
+    BackendError,
+    ValueError,
+};
+use super::*;
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SortKey::Id => "id",
+            SortKey::CreatedTs => "created_ts",
+            SortKey::Description => "description",
+        })
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = ValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "id" => Ok(SortKey::Id),
+            "created_ts" => Ok(SortKey::CreatedTs),
+            "description" => Ok(SortKey::Description),
+            s => Err(ValueError::Unsupported(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        })
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = ValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            s => Err(ValueError::Unsupported(s.to_string())),
+        }
+    }
+}
+
+impl Cursor {
+    /// Create the cursor for an entry within a listing sorted by `sort`.
+    pub fn new(
+        sort: SortKey,
+        id: i64,
+        created_ts: i64,
+        description: Option<&str>,
+    ) -> Self {
+        match sort {
+            SortKey::Id => Cursor::Id(id),
+            SortKey::CreatedTs => Cursor::CreatedTs(created_ts, id),
+            SortKey::Description => Cursor::Description(
+                description.unwrap_or_default().to_string(),
+                id,
+            ),
+        }
+    }
+
+    pub fn sort_key(&self) -> SortKey {
+        match self {
+            Cursor::Id(_) => SortKey::Id,
+            Cursor::CreatedTs(..) => SortKey::CreatedTs,
+            Cursor::Description(..) => SortKey::Description,
+        }
+    }
+
+    pub fn id(&self) -> i64 {
+        match self {
+            Cursor::Id(id)
+            | Cursor::CreatedTs(_, id)
+            | Cursor::Description(_, id) => *id,
+        }
+    }
+}
+
+// The description is placed last as it may contain the separator.
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cursor::Id(id) => write!(f, "i{id}"),
+            Cursor::CreatedTs(ts, id) => write!(f, "c{ts}.{id}"),
+            Cursor::Description(description, id) => write!(f, "d{id}.{description}"),
+        }
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = ValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ValueError::Unsupported(format!("invalid cursor: {s}"));
+        let int = |value: &str| value.parse::<i64>().map_err(|_| invalid());
+        let (prefix, rest) = s.split_at_checked(1).ok_or_else(invalid)?;
+        match prefix {
+            "i" => Ok(Cursor::Id(int(rest)?)),
+            "c" => {
+                let (ts, id) = rest.split_once('.').ok_or_else(invalid)?;
+                Ok(Cursor::CreatedTs(int(ts)?, int(id)?))
+            }
+            "d" => {
+                let (id, description) = rest.split_once('.').ok_or_else(invalid)?;
+                Ok(Cursor::Description(description.to_string(), int(id)?))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(cursor: Cursor) -> String {
+        cursor.to_string()
+    }
+}
+
+impl TryFrom<String> for Cursor {
+    type Error = ValueError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Listing {
+    /// Convert this into the query for the backends, restricting the
+    /// listing to the provided ids which should be resolved from the
+    /// `state` and `owner` filters.
+    pub fn to_query(&self, ids: Option<Vec<i64>>) -> ListingQuery {
+        ListingQuery {
+            sort: self.sort,
+            order: self.order,
+            after: self.after.clone(),
+            limit: self.limit,
+            ids,
+            has_exposure: self.has_exposure,
+        }
+    }
+}
+
+impl ListingQuery {
+    /// The number of entries to return for the page, with the limit
+    /// provided clamped to within `1..=MAX_LIMIT`.
+    pub fn page_size(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    /// Returns the cursor, ensuring it is applicable to the sort key.
+    pub fn cursor(&self) -> Result<Option<&Cursor>, BackendError> {
+        match &self.after {
+            Some(cursor) if cursor.sort_key() != self.sort => Err(
+                BackendError::AppInvariantViolation(format!(
+                    "cursor `{cursor}` cannot be used when sorting by `{}`",
+                    self.sort,
+                ))
+            ),
+            cursor => Ok(cursor.as_ref()),
+        }
+    }
+}
+
+impl<T> Page<T> {
+    /// Create a page from entries fetched with a limit of one beyond
+    /// `page_size`, where the additional entry denotes the existence of
+    /// a following page.
+    pub fn from_fetched(
+        mut entries: Vec<T>,
+        page_size: u32,
+        cursor: impl Fn(&T) -> Cursor,
+    ) -> Self {
+        let next = if entries.len() > page_size as usize {
+            entries.truncate(page_size as usize);
+            entries.last().map(cursor)
+        } else {
+            None
+        };
+        Self { entries, next }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            entries: self.entries.into_iter().map(f).collect(),
+            next: self.next,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cursor_roundtrip() {
+        for cursor in [
+            Cursor::Id(1),
+            Cursor::CreatedTs(1234567890, 2),
+            Cursor::Description("".to_string(), 3),
+            Cursor::Description("A model. With dots.".to_string(), 4),
+        ] {
+            assert_eq!(Cursor::from_str(&cursor.to_string()).unwrap(), cursor);
+        }
+        assert_eq!(Cursor::Description("d.e".to_string(), 4).to_string(), "d4.d.e");
+    }
+
+    #[test]
+    fn cursor_invalid() {
+        for s in ["", "i", "ix", "x1", "c1", "c1.", "d.desc", "é1"] {
+            assert!(Cursor::from_str(s).is_err(), "{s:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn cursor_serde() -> anyhow::Result<()> {
+        let cursor = Cursor::CreatedTs(1234567890, 2);
+        assert_eq!(serde_json::to_string(&cursor)?, r#""c1234567890.2""#);
+        assert_eq!(serde_json::from_str::<Cursor>(r#""c1234567890.2""#)?, cursor);
+        assert!(serde_json::from_str::<Cursor>(r#""z""#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn query_cursor() {
+        let query = ListingQuery {
+            sort: SortKey::CreatedTs,
+            after: Some(Cursor::Id(1)),
+            .. Default::default()
+        };
+        assert!(query.cursor().is_err());
+        let query = ListingQuery {
+            after: Some(Cursor::Id(1)),
+            .. Default::default()
+        };
+        assert_eq!(query.cursor().unwrap(), Some(&Cursor::Id(1)));
+    }
+
+    #[test]
+    fn page_size() {
+        assert_eq!(ListingQuery::default().page_size(), DEFAULT_LIMIT);
+        let query = |limit| ListingQuery { limit: Some(limit), .. Default::default() };
+        assert_eq!(query(0).page_size(), 1);
+        assert_eq!(query(10).page_size(), 10);
+        assert_eq!(query(MAX_LIMIT + 1).page_size(), MAX_LIMIT);
+    }
+
+    #[test]
+    fn page_from_fetched() {
+        let page = Page::from_fetched(vec![1, 2, 3], 2, |i| Cursor::Id(*i));
+        assert_eq!(page.entries, [1, 2]);
+        assert_eq!(page.next, Some(Cursor::Id(2)));
+        let page = Page::from_fetched(vec![1, 2], 2, |i| Cursor::Id(*i));
+        assert_eq!(page.entries, [1, 2]);
+        assert_eq!(page.next, None);
+    }
+}
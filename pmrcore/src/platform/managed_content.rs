@@ -238,6 +238,9 @@ pub trait MCPlatform: WorkspaceBackend
             kind: "workspace".to_string(),
             entries: self.list_workspace_by_ids(&ids).await?
                 .into_iter()
+                // archived workspaces release their aliases, but hide any
+                // that might remain from the listing regardless.
+                .filter(|workspace| workspace.archived_ts().is_none())
                 .map(|workspace| AliasEntry {
                     alias: id_map.remove(&workspace.id())
                         .expect("unexpected id queried without an alias queried"),
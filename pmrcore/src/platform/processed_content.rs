@@ -2,7 +2,10 @@ use async_trait::async_trait;
 use crate::{
     citation::traits::CitationBackend,
     // error::BackendError,
-    index::traits::IndexDBBackend,
+    index::traits::{
+        IndexCoreDBBackend,
+        IndexDBBackend,
+    },
     platform::{
         PlatformCore,
         RawPlatform,
@@ -36,7 +39,7 @@ pub trait PCPlatform: PlatformCore
 }
 
 pub trait DefaultPCPlatform: PCPlatform {}
-pub trait RawPCPlatform: RawPlatform {}
+pub trait RawPCPlatform: PCPlatform + RawPlatform + IndexCoreDBBackend {}
 
 impl<P: PlatformCore
     + CitationBackend
@@ -52,4 +55,4 @@ impl<P: PlatformCore
     }
 }
 
-impl <P: PCPlatform + RawPlatform> RawPCPlatform for P {}
+impl <P: PCPlatform + RawPlatform + IndexCoreDBBackend> RawPCPlatform for P {}
@@ -37,6 +37,10 @@ pub struct ViewTaskTemplate {
     pub task_template_id: i64,
     #[serde(default)]
     pub updated_ts: i64,
+    // the view_key of another view that must be generated before this
+    // one, e.g. where the task for this view consumes its output.
+    #[serde(default)]
+    pub depends_on: Option<String>,
     pub task_template: Option<TaskTemplate>,
 }
 
@@ -51,6 +51,22 @@ pub trait ViewTaskTemplateBackend {
         &self,
         id: i64,
     ) -> Result<ViewTaskTemplate, BackendError>;
+    /// Set the view_key of the view that must be generated before the
+    /// view produced by this view task template; `None` clears it.
+    async fn set_view_task_template_depends_on(
+        &self,
+        id: i64,
+        depends_on: Option<&str>,
+    ) -> Result<bool, BackendError>;
+    /// Point every view task template using the task template at the
+    /// one that supersedes it, such that the profiles and the exposure
+    /// files linked with these will use the new version.  Returns the
+    /// ids of the view task templates updated.
+    async fn migrate_view_task_templates(
+        &self,
+        task_template_id: i64,
+        superceded_by_id: i64,
+    ) -> Result<Vec<i64>, BackendError>;
 }
 
 #[async_trait]
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use crate::workspace::Workspace;
 
+mod impls;
+
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TreeEntryInfo {
@@ -18,6 +20,11 @@ pub struct LogEntryInfo {
     pub committer: String,
     pub commit_timestamp: i64,
     pub message: String,
+    /// The path that was followed as of this commit, if the log was
+    /// restricted to a path; this may differ from the requested path if
+    /// it was renamed since.
+    #[serde(default)]
+    pub path: Option<String>,
 }
 
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
@@ -46,8 +53,11 @@ pub struct CommitInfo {
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct LogInfo {
-    // TODO fields about start, next, pagination?
     pub entries: Vec<LogEntryInfo>,
+    /// The id of the last commit of the entries if there are more, which
+    /// may be provided as `after` to get the following entries.
+    #[serde(default)]
+    pub next: Option<String>,
 }
 
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
@@ -95,6 +105,97 @@ pub struct RepoResult {
     pub target: Option<PathObjectInfo>,
 }
 
+/// The kind of change made to a file between two commits.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeStatus {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+    Copied,
+}
+
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileDiff {
+    pub status: ChangeStatus,
+    /// The path of the file, which is the path in the old commit for a
+    /// deleted file.
+    pub path: String,
+    /// The original path of a file that was renamed or copied.
+    pub old_path: Option<String>,
+    /// The id of the object in the old commit.
+    pub old_id: Option<String>,
+    /// The id of the object in the new commit.
+    pub new_id: Option<String>,
+    /// Whether either version of the file is binary.
+    pub binary: bool,
+    /// The changes in the unified diff format, which is omitted for
+    /// binary files and submodules.
+    pub diff: Option<String>,
+}
+
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiffResult {
+    /// The workspace that this result was derived from.
+    pub workspace: Workspace,
+    /// The commit the changes are relative to; this is `None` when the
+    /// new commit has no parent, such that every file is added.
+    pub old_commit: Option<CommitInfo>,
+    /// The commit with the changes.
+    pub new_commit: CommitInfo,
+    /// The path that the changes were restricted to.
+    pub path: Option<String>,
+    /// The files that were changed, ordered by their paths.
+    pub files: Vec<FileDiff>,
+}
+
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlameHunk {
+    /// The line number where the hunk starts in the file, starting at 1.
+    pub start_line: u32,
+    /// The line number where the hunk starts in the file as of the commit
+    /// that last changed the lines.
+    pub orig_start_line: u32,
+    /// The path of the file as of the commit that last changed the lines,
+    /// if it differs due to a rename.
+    pub orig_path: Option<String>,
+    /// The commit that last changed the lines.
+    pub commit: LogEntryInfo,
+    pub lines: Vec<String>,
+}
+
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlameInfo {
+    /// The workspace that this result was derived from.
+    pub workspace: Workspace,
+    /// The commit the file was blamed at.
+    pub commit: CommitInfo,
+    pub path: String,
+    /// The total number of lines in the file.
+    pub line_count: u32,
+    /// The hunks covering the requested lines, in the order of the lines.
+    pub hunks: Vec<BlameHunk>,
+    /// The line to start from to get the following hunks, if there are
+    /// more lines.
+    pub next: Option<u32>,
+}
+
+/// The formats an archive of a tree may be produced in.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar.gz")]
+    TarGz,
+}
+
 /*
 // This was the original result, but it lacked workspace and description
 // so it wasn't too useful, but given that a repo is ultimately from a
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::task_template::TaskTemplate;
 
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct Task {
@@ -6,14 +9,169 @@ pub struct Task {
     pub task_template_id: i64,
     pub bin_path: String,
     pub pid: Option<i64>,
+    // the host where the process with the pid was started, as recorded
+    // by the runner alongside the pid.
+    #[serde(default)]
+    pub host: Option<String>,
     pub created_ts: i64,
     pub start_ts: Option<i64>,
     pub stop_ts: Option<i64>,
     pub exit_status: Option<i64>,
     pub basedir: String,
+    // tasks with a higher priority are started first.
+    #[serde(default)]
+    pub priority: i64,
+    // the number of times the task was started.
+    #[serde(default)]
+    pub attempts: i64,
+    // the number of times the task may be started again should it fail.
+    #[serde(default)]
+    pub max_retries: i64,
+    // seconds to wait before the first retry, doubling for every retry.
+    #[serde(default)]
+    pub retry_delay: i64,
+    // a task pending a retry will not be started before this timestamp.
+    #[serde(default)]
+    pub retry_ts: Option<i64>,
+    // the timestamp a remote runner holding the task must renew its
+    // lease by, for the task to not be returned to the queue.
+    #[serde(default)]
+    pub lease_ts: Option<i64>,
+    // ids of the tasks that must complete successfully before this task
+    // may be started.
+    #[serde(default)]
+    pub depends_on: Vec<i64>,
     pub args: Option<TaskArgs>,
 }
 
+/// Priority for tasks queued in bulk, e.g. when rebuilding all the
+/// exposures of a workspace, such that tasks queued at the default
+/// priority of 0 are started before these.
+pub const PRIORITY_BULK: i64 = -10;
+
+/// Exit status recorded for a task process that terminated without an
+/// exit code, e.g. when killed by a signal from outside the executor.
+pub const EXIT_SIGNALED: i32 = -1;
+/// Exit status recorded for a task process killed by the executor in
+/// response to an abort.
+pub const EXIT_ABORTED: i32 = -2;
+/// Exit status recorded for a task process killed by the executor for
+/// running past the timeout of its task template.
+pub const EXIT_TIMED_OUT: i32 = -3;
+/// Exit status recorded for a task found with a process that is no
+/// longer alive, i.e. left behind by a runner that did not complete it.
+pub const EXIT_ORPHANED: i32 = -4;
+/// Exit status recorded for a task process terminated for exceeding the
+/// CPU time limit of its sandbox.
+pub const EXIT_CPU_LIMIT: i32 = -5;
+/// Exit status recorded for a task process terminated for exceeding the
+/// output size limit of its sandbox.
+pub const EXIT_OUTPUT_LIMIT: i32 = -6;
+/// Exit status recorded for a task process terminated for making a
+/// system call denied by its sandbox.
+pub const EXIT_SYSCALL_DENIED: i32 = -7;
+/// Exit status recorded for a task process that could not be started
+/// within its sandbox.
+pub const EXIT_SANDBOX_FAILED: i32 = -8;
+/// Exit status recorded for a task that will never be started, as a task
+/// it depends on has failed.
+pub const EXIT_DEPENDENCY_FAILED: i32 = -9;
+
+/// The reason for the failure of a task, as derived from its recorded
+/// exit status.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, Error)]
+#[serde(tag = "kind", content = "code", rename_all = "snake_case")]
+pub enum TaskFailure {
+    #[error("exited with code {0}")]
+    Exit(i64),
+    #[error("terminated by a signal")]
+    Signaled,
+    #[error("aborted")]
+    Aborted,
+    #[error("timed out")]
+    TimedOut,
+    #[error("orphaned by its runner")]
+    Orphaned,
+    #[error("exceeded the CPU time limit")]
+    CpuLimit,
+    #[error("exceeded the output size limit")]
+    OutputLimit,
+    #[error("made a denied system call")]
+    SyscallDenied,
+    #[error("could not be started within the sandbox")]
+    SandboxFailed,
+    #[error("a task it depends on has failed")]
+    DependencyFailed,
+}
+
+/// The scheduling to apply to tasks as they are being queued.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TaskSchedule {
+    #[serde(default)]
+    pub priority: i64,
+    #[serde(default)]
+    pub max_retries: i64,
+    // seconds
+    #[serde(default)]
+    pub retry_delay: i64,
+}
+
+/// The state of a task, as derived from its recorded timestamps and
+/// exit status.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// Waiting to be started, which includes tasks pending a retry.
+    Queued,
+    /// Started but yet to complete.
+    Running,
+    /// Completed with a zero exit status.
+    Succeeded,
+    /// Completed with a non-zero exit status.
+    Failed,
+}
+
+/// The status of a task as reported to users, which omits the details
+/// on how the task is executed.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TaskStatus {
+    pub id: i64,
+    pub state: TaskState,
+    pub failure: Option<TaskFailure>,
+    pub priority: i64,
+    pub attempts: i64,
+    pub max_retries: i64,
+    pub retry_ts: Option<i64>,
+    pub created_ts: i64,
+    pub start_ts: Option<i64>,
+    pub stop_ts: Option<i64>,
+}
+
+/// A task claimed by a remote runner, along with what the runner needs
+/// to execute it, for as long as the lease is renewed.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TaskClaim {
+    pub task: Task,
+    pub task_template: TaskTemplate,
+    /// The timestamp the lease expires at.
+    pub lease_ts: i64,
+    /// The location of the files the task processes on the server, for
+    /// the runner to substitute with the location it fetched them to.
+    pub checkout: Option<String>,
+}
+
+/// The logs captured from the output streams of the task process.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskLog {
+    Stdout,
+    Stderr,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct TaskArg {
     pub id: i64,
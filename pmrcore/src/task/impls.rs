@@ -1,13 +1,217 @@
 use std::{
+    fmt,
     ops::Deref,
     path::PathBuf,
     process,
+    str::FromStr,
 };
 use crate::{
     error::ValueError,
     task::*,
 };
 
+impl Task {
+    /// The failure of this task, if it has completed unsuccessfully.
+    pub fn failure(&self) -> Option<TaskFailure> {
+        self.exit_status.and_then(TaskFailure::from_exit_status)
+    }
+
+    /// The state of this task.  Note that a task pending a retry is
+    /// queued, even though it has an exit status recorded.
+    pub fn state(&self) -> TaskState {
+        match (self.start_ts, self.stop_ts, self.exit_status) {
+            (_, Some(_), Some(0)) => TaskState::Succeeded,
+            (_, Some(_), _) => TaskState::Failed,
+            (Some(_), None, _) => TaskState::Running,
+            (None, None, _) => TaskState::Queued,
+        }
+    }
+
+    /// Substitute the locations referenced by the arguments of this task
+    /// with the ones paired with them, in the order provided.
+    pub fn relocate_args(&mut self, locations: &[(&str, &str)]) {
+        if let Some(args) = self.args.as_mut() {
+            for task_arg in args.0.iter_mut() {
+                if let Some(arg) = locations.iter()
+                    .find_map(|(from, to)| relocate(&task_arg.arg, from, to))
+                {
+                    task_arg.arg = arg;
+                }
+            }
+        }
+    }
+
+    /// The argv that the process for this task will be started with,
+    /// being the bin_path followed by the arguments.
+    pub fn argv(&self) -> Vec<&str> {
+        std::iter::once(self.bin_path.as_str())
+            .chain(self.args.iter()
+                .flat_map(|args| args.iter())
+                .map(|task_arg| task_arg.arg.as_str()))
+            .collect()
+    }
+}
+
+impl From<&Task> for TaskStatus {
+    fn from(task: &Task) -> Self {
+        let state = task.state();
+        Self {
+            id: task.id,
+            state,
+            failure: (state == TaskState::Failed)
+                .then(|| task.failure())
+                .flatten(),
+            priority: task.priority,
+            attempts: task.attempts,
+            max_retries: task.max_retries,
+            retry_ts: task.retry_ts,
+            created_ts: task.created_ts,
+            start_ts: task.start_ts,
+            stop_ts: task.stop_ts,
+        }
+    }
+}
+
+impl TaskState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskState::Queued => "queued",
+            TaskState::Running => "running",
+            TaskState::Succeeded => "succeeded",
+            TaskState::Failed => "failed",
+        }
+    }
+
+    /// Whether a task in this state may yet change state.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, TaskState::Queued | TaskState::Running)
+    }
+}
+
+impl fmt::Display for TaskState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TaskState {
+    type Err = ValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(TaskState::Queued),
+            "running" => Ok(TaskState::Running),
+            "succeeded" => Ok(TaskState::Succeeded),
+            "failed" => Ok(TaskState::Failed),
+            s => Err(ValueError::Unsupported(s.to_string())),
+        }
+    }
+}
+
+impl TaskClaim {
+    /// Relocate the task to be run from the provided basedir, with the
+    /// files it processes at the provided checkout, by substituting the
+    /// locations on the server with these where they are referenced by
+    /// its arguments.
+    pub fn relocate(&mut self, basedir: &str, checkout: Option<&str>) {
+        let from = std::mem::take(&mut self.task.basedir);
+        let mut locations = vec![(from.as_str(), basedir)];
+        if let (Some(from), Some(to)) = (self.checkout.as_deref(), checkout) {
+            locations.push((from, to));
+        }
+        self.task.relocate_args(&locations);
+        self.task.basedir = basedir.to_string();
+        self.checkout = checkout.map(str::to_string);
+    }
+}
+
+// Substitute the first reference to the `from` location in the argument,
+// which may be joined to a flag, with the `to` location.
+fn relocate(arg: &str, from: &str, to: &str) -> Option<String> {
+    let (idx, _) = arg.match_indices(from)
+        .find(|(idx, _)| {
+            let rest = &arg[idx + from.len()..];
+            (*idx == 0 || arg[..*idx].ends_with('='))
+                && (rest.is_empty() || rest.starts_with('/'))
+        })?;
+    Some(format!("{}{to}{}", &arg[..idx], &arg[idx + from.len()..]))
+}
+
+impl fmt::Display for TaskClaim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TaskClaim {{ id: {} }}", self.task.id)
+    }
+}
+
+impl TaskLog {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskLog::Stdout => "stdout",
+            TaskLog::Stderr => "stderr",
+        }
+    }
+
+    /// The path to this log for a task with the provided basedir.
+    pub fn path(&self, basedir: &str) -> PathBuf {
+        [basedir, "temp", self.as_str()].iter().collect()
+    }
+}
+
+impl fmt::Display for TaskLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TaskLog {
+    type Err = ValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stdout" => Ok(TaskLog::Stdout),
+            "stderr" => Ok(TaskLog::Stderr),
+            s => Err(ValueError::Unsupported(s.to_string())),
+        }
+    }
+}
+
+impl TaskFailure {
+    /// Derive the failure from an exit status; a zero exit status is
+    /// not a failure.
+    pub fn from_exit_status(exit_status: i64) -> Option<Self> {
+        match i32::try_from(exit_status) {
+            Ok(0) => None,
+            Ok(EXIT_SIGNALED) => Some(Self::Signaled),
+            Ok(EXIT_ABORTED) => Some(Self::Aborted),
+            Ok(EXIT_TIMED_OUT) => Some(Self::TimedOut),
+            Ok(EXIT_ORPHANED) => Some(Self::Orphaned),
+            Ok(EXIT_CPU_LIMIT) => Some(Self::CpuLimit),
+            Ok(EXIT_OUTPUT_LIMIT) => Some(Self::OutputLimit),
+            Ok(EXIT_SYSCALL_DENIED) => Some(Self::SyscallDenied),
+            Ok(EXIT_SANDBOX_FAILED) => Some(Self::SandboxFailed),
+            Ok(EXIT_DEPENDENCY_FAILED) => Some(Self::DependencyFailed),
+            _ => Some(Self::Exit(exit_status)),
+        }
+    }
+}
+
+impl TaskSchedule {
+    /// The schedule for tasks queued in bulk.
+    pub fn bulk() -> Self {
+        Self {
+            priority: PRIORITY_BULK,
+            ..Default::default()
+        }
+    }
+
+    /// Apply this schedule to the task that is to be queued.
+    pub fn apply(&self, task: &mut Task) {
+        task.priority = self.priority;
+        task.max_retries = self.max_retries;
+        task.retry_delay = self.retry_delay;
+    }
+}
+
 impl From<[&str; 2]> for TaskArg {
     fn from(s: [&str; 2]) -> Self {
         Self { arg: s[0].to_owned() + s[1], .. Default::default() }
@@ -69,9 +273,12 @@ impl TryFrom<&TaskRef<'_>> for process::Command {
 
 #[cfg(test)]
 pub(crate) mod test {
-    use std::process::Command;
+    use std::{
+        path::PathBuf,
+        process::Command,
+    };
     use tempfile::TempDir;
-    use crate::task::Task;
+    use crate::task::*;
     use test_binary::build_test_binary_once;
 
     #[derive(serde::Deserialize)]
@@ -80,6 +287,123 @@ pub(crate) mod test {
         pub(crate) cwd: String,
     }
 
+    #[test]
+    fn test_failure() {
+        let task = |exit_status| Task {
+            exit_status,
+            .. Default::default()
+        };
+        assert_eq!(task(None).failure(), None);
+        assert_eq!(task(Some(0)).failure(), None);
+        assert_eq!(task(Some(2)).failure(), Some(TaskFailure::Exit(2)));
+        assert_eq!(task(Some(EXIT_TIMED_OUT.into())).failure(), Some(TaskFailure::TimedOut));
+        assert_eq!(task(Some(EXIT_CPU_LIMIT.into())).failure(), Some(TaskFailure::CpuLimit));
+        assert_eq!(task(Some(EXIT_OUTPUT_LIMIT.into())).failure(), Some(TaskFailure::OutputLimit));
+        assert_eq!(task(Some(EXIT_SANDBOX_FAILED.into())).failure(), Some(TaskFailure::SandboxFailed));
+        assert_eq!(task(Some(EXIT_DEPENDENCY_FAILED.into())).failure(), Some(TaskFailure::DependencyFailed));
+        assert_eq!(task(Some(i64::MIN)).failure(), Some(TaskFailure::Exit(i64::MIN)));
+        assert_eq!(
+            serde_json::to_string(&TaskFailure::Exit(2)).unwrap(),
+            r#"{"kind":"exit","code":2}"#,
+        );
+        assert_eq!(
+            serde_json::to_string(&TaskFailure::SyscallDenied).unwrap(),
+            r#"{"kind":"syscall_denied"}"#,
+        );
+    }
+
+    #[test]
+    fn test_state() {
+        let task = |start_ts, stop_ts, exit_status| Task {
+            start_ts,
+            stop_ts,
+            exit_status,
+            .. Default::default()
+        };
+        assert_eq!(task(None, None, None).state(), TaskState::Queued);
+        // pending a retry
+        assert_eq!(task(None, None, Some(1)).state(), TaskState::Queued);
+        assert_eq!(task(Some(1), None, None).state(), TaskState::Running);
+        assert_eq!(task(Some(1), Some(2), Some(0)).state(), TaskState::Succeeded);
+        assert_eq!(task(Some(1), Some(2), Some(1)).state(), TaskState::Failed);
+
+        let status = TaskStatus::from(&task(None, None, Some(1)));
+        assert_eq!(status.state, TaskState::Queued);
+        assert_eq!(status.failure, None);
+        let status = TaskStatus::from(&task(Some(1), Some(2), Some(EXIT_TIMED_OUT.into())));
+        assert_eq!(status.failure, Some(TaskFailure::TimedOut));
+
+        for state in [
+            TaskState::Queued,
+            TaskState::Running,
+            TaskState::Succeeded,
+            TaskState::Failed,
+        ] {
+            assert_eq!(state.to_string().parse::<TaskState>().unwrap(), state);
+            assert_eq!(serde_json::to_string(&state).unwrap(), format!("\"{state}\""));
+        }
+        assert!("done".parse::<TaskState>().is_err());
+        assert_eq!(
+            TaskLog::Stderr.path("/tmp/task"),
+            PathBuf::from("/tmp/task/temp/stderr"),
+        );
+        assert_eq!("stdout".parse::<TaskLog>().unwrap(), TaskLog::Stdout);
+    }
+
+    #[test]
+    fn test_argv() {
+        let mut task = Task {
+            bin_path: "/bin/echo".into(),
+            .. Default::default()
+        };
+        assert_eq!(task.argv(), ["/bin/echo"]);
+        task.args = Some(vec![
+            TaskArg::from(["--flag=", "value"]),
+            TaskArg::from("literal"),
+        ].into());
+        assert_eq!(task.argv(), ["/bin/echo", "--flag=value", "literal"]);
+    }
+
+    #[test]
+    fn test_relocate() {
+        let mut claim = TaskClaim {
+            task: Task {
+                id: 1,
+                basedir: "/data/view/1".into(),
+                args: Some(vec![
+                    TaskArg::from("/data/exposure/2/files/model.cellml"),
+                    TaskArg::from("--out=/data/view/1/work"),
+                    TaskArg::from("/data/view/10"),
+                    TaskArg::from("/data/exposure/2/files"),
+                    TaskArg::from("literal"),
+                ].into()),
+                .. Default::default()
+            },
+            task_template: serde_json::from_str(r#"{
+                "bin_path": "/bin/true",
+                "version_id": "1",
+                "final_task_template_arg_id": null,
+                "superceded_by_id": null,
+                "args": null
+            }"#).unwrap(),
+            lease_ts: 0,
+            checkout: Some("/data/exposure/2/files".into()),
+        };
+        claim.relocate("/runner/1/basedir", Some("/runner/1/files"));
+        assert_eq!(claim.task.basedir, "/runner/1/basedir");
+        assert_eq!(claim.checkout.as_deref(), Some("/runner/1/files"));
+        assert_eq!(
+            Vec::<&str>::from(claim.task.args.as_ref().unwrap()),
+            [
+                "/runner/1/files/model.cellml",
+                "--out=/runner/1/basedir/work",
+                "/data/view/10",
+                "/runner/1/files",
+                "literal",
+            ],
+        );
+    }
+
     #[test]
     fn test_command() -> anyhow::Result<()> {
         // FIXME platform specific pathsep
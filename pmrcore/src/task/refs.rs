@@ -7,6 +7,10 @@ use crate::{
             TaskBackend,
         },
     },
+    task_template::{
+        TaskTemplate,
+        traits::TaskTemplateBackend,
+    },
 };
 
 pub struct TaskRef<'a> {
@@ -30,14 +34,17 @@ impl TaskRef<'_> {
     pub async fn run(
         &mut self,
         pid: i64,
+        host: &str,
     ) -> Result<bool, BackendError> {
-        // self.platform.run(self.inner.id, pid).await
+        // self.platform.run(self.inner.id, pid, host).await
         let result = TaskBackend::run(
             self.platform,
             self.inner.id,
             pid,
+            host,
         ).await?;
         self.inner.pid = Some(pid);
+        self.inner.host = Some(host.to_string());
         Ok(result)
     }
 
@@ -55,6 +62,17 @@ impl TaskRef<'_> {
         Ok(result)
     }
 
+    /// Resolve the task template for this task, which specifies the
+    /// limits the task is to be run under.  Arguments are not included.
+    pub async fn task_template(
+        &self,
+    ) -> Result<TaskTemplate, BackendError> {
+        TaskTemplateBackend::get_task_template_by_id(
+            self.platform,
+            self.inner.task_template_id,
+        ).await
+    }
+
     // TODO maybe move this to a common trait like what was done with
     // Exposure types.
 
@@ -66,6 +84,10 @@ impl TaskRef<'_> {
         self.inner.pid
     }
 
+    pub fn host(&self) -> Option<&str> {
+        self.inner.host.as_deref()
+    }
+
     pub fn basedir(&self) -> &str {
         &self.inner.basedir
     }
@@ -76,6 +98,10 @@ impl TaskRef<'_> {
 }
 
 impl TaskRef<'_> {
+    pub fn inner(&self) -> &Task {
+        &self.inner
+    }
+
     pub fn into_inner(self) -> Task {
         self.inner
     }
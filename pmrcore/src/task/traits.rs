@@ -4,7 +4,10 @@ use crate::{
         BackendError,
         task::TaskError,
     },
-    task::Task,
+    task::{
+        Task,
+        TaskState,
+    },
 };
 
 #[async_trait]
@@ -19,25 +22,78 @@ pub trait TaskBackend {
         &self,
         id: i64,
     ) -> Result<Task, BackendError>;
-    /// Start a task. This should pick the oldest task that was added,
-    /// and atomically update its start_ts and return the complete task
-    /// instance.
+    /// Start a task. This should pick the task with the highest
+    /// priority, then the oldest, out of the tasks that are not waiting
+    /// on a retry or on the tasks they depend on, and atomically update
+    /// its start_ts and attempts and return the complete task instance.
     ///
     /// Returns some complete task instance, or none if no such task is
     /// found.
     async fn start(
         &self,
     ) -> Result<Option<Task>, BackendError>;
-    /// Mark the task by id with the running pid.
+    /// Mark the task by id with the running pid, along with the host
+    /// that the pid refers to.
     async fn run(
         &self,
         id: i64,
         pid: i64,
+        host: &str,
     ) -> Result<bool, BackendError>;
     /// Exit a task by id, with an exit status for the related process.
+    ///
+    /// A started task with a non-zero exit status that may still be
+    /// retried is returned to the queue with its retry_ts set, instead
+    /// of being completed.  Otherwise the failure is also recorded for
+    /// the pending tasks that depend on it, directly or otherwise.
     async fn complete(
         &self,
         id: i64,
         exit_status: i64,
     ) -> Result<bool, BackendError>;
+    /// List the tasks that have a running pid but have yet to complete,
+    /// ordered by id.  The args and dependencies are not provided with
+    /// the tasks.
+    async fn list_running(
+        &self,
+    ) -> Result<Vec<Task>, BackendError>;
+    /// List the tasks that are in the provided state, ordered by id.
+    /// The args and dependencies are not provided with the tasks.
+    async fn list_by_state(
+        &self,
+        state: TaskState,
+    ) -> Result<Vec<Task>, BackendError>;
+    /// List the tasks with the provided ids, ordered by id; ids without
+    /// a task are skipped.  The args and dependencies are not provided
+    /// with the tasks.
+    async fn list_by_ids(
+        &self,
+        ids: &[i64],
+    ) -> Result<Vec<Task>, BackendError>;
+    /// Lease a started task by id that has yet to complete to the
+    /// remote runner identified by host, for the duration in seconds
+    /// from now.  A task already leased to the same host has its lease
+    /// renewed, while a task leased to another host or with a running
+    /// pid is not leased.
+    ///
+    /// Returns the timestamp the lease expires at, if leased.
+    async fn lease(
+        &self,
+        id: i64,
+        host: &str,
+        duration: i64,
+    ) -> Result<Option<i64>, BackendError>;
+    /// Return the incomplete tasks with an expired lease to the queue,
+    /// as the remote runners holding them have stopped renewing them.
+    ///
+    /// Returns the ids of the tasks returned to the queue.
+    async fn requeue_expired_leases(
+        &self,
+    ) -> Result<Vec<i64>, BackendError>;
+    /// Return an incomplete task by id to the queue, by clearing its
+    /// start_ts and pid such that it will be started again.
+    async fn requeue(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError>;
 }
@@ -19,9 +19,39 @@ pub struct TaskTemplate {
     pub created_ts: i64,
     pub final_task_template_arg_id: Option<i64>,
     pub superceded_by_id: Option<i64>,
+    // wall-clock limit in seconds for tasks created from this template,
+    // tasks still running past this will be killed by the executor.
+    #[serde(default)]
+    pub timeout: Option<i64>,
+    // resource limits and isolation for the processes of tasks created
+    // from this template, these run unrestricted if absent.
+    #[serde(default)]
+    pub sandbox: Option<TaskSandbox>,
     pub args: Option<TaskTemplateArgs>,
 }
 
+/// The restrictions applied to the process of a task.  The limits are
+/// applied as resource limits, so the memory limit causes allocations
+/// beyond it to fail rather than the process to be terminated.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TaskSandbox {
+    /// CPU time limit in seconds.
+    pub cpu_time: Option<u64>,
+    /// Address space limit in bytes.
+    pub memory: Option<u64>,
+    /// Size limit in bytes for any file written, which includes the
+    /// captured stdout and stderr.
+    pub output_size: Option<u64>,
+    /// Run in separate namespaces without network access, with the
+    /// filesystem made read-only except for the basedir of the task.
+    pub isolate: bool,
+    /// Terminate the process should it make any of the system calls
+    /// that are not needed by tools, e.g. mount or ptrace.
+    pub seccomp: bool,
+}
+
 #[cfg(feature = "sqlx")]
 impl<'c> FromRow<'c, SqliteRow> for TaskTemplate {
     fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
@@ -32,6 +62,11 @@ impl<'c> FromRow<'c, SqliteRow> for TaskTemplate {
             created_ts: row.get(3),
             final_task_template_arg_id: row.get(4),
             superceded_by_id: row.get(5),
+            timeout: row.get(6),
+            sandbox: row.get::<Option<String>, _>(7)
+                .map(|sandbox| serde_json::from_str(&sandbox))
+                .transpose()
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
             args: None,
         })
     }
@@ -59,8 +94,14 @@ pub struct TaskTemplateArg {
     // TODO may need an enum instead that disambiguates the DB one and
     // the generated ones provided by alternative sources
     pub choices: Option<TaskTemplateArgChoices>,
-    // TODO multiple choices; MapToArgRef technically can support that
-    // now but it has been nerfed for now.
+    // multiple values may be provided by the user, each resolved through
+    // the choices; these are repeated with the flag for each value unless
+    // a separator is provided, in which case they are joined as a single
+    // value.
+    #[serde(default)]
+    pub multiple: bool,
+    #[serde(default)]
+    pub multiple_separator: Option<String>,
 }
 
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
@@ -100,6 +141,8 @@ pub struct UserArg {
     pub default: Option<String>,
     pub choice_fixed: bool,
     pub choices: Option<UserChoices>,
+    #[serde(default)]
+    pub multiple: bool,
 }
 
 #[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
@@ -140,9 +183,20 @@ impl AsRef<UserArg> for UserArg {
     }
 }
 
+/// The input value specified by the user for an argument, which may be
+/// a list of values for arguments that accept multiple values.  Input
+/// stored as a single string remains a valid `UserInput`.
+#[cfg_attr(feature="utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum UserInput {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
 /// `UserInputMap` maps from TaskTemplateArg.id to the user specified
 /// input value.  Typically this is specific to some exposure file.
-pub type UserInputMap = HashMap<i64, String>;
+pub type UserInputMap = HashMap<i64, UserInput>;
 
 mod map_to_arg;
 #[cfg(feature = "display")]
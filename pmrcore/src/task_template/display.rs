@@ -65,7 +65,11 @@ impl Display for TaskTemplateArg {
                 (Some(prompt), Some(default), true, Some(source)) =>
                     Some(format!("<<{}>;default={:?};choices={{source:'{}'}}>",
                         &prompt, &default, &source)),
-            }
+            }.map(|arg| match (self.multiple, self.multiple_separator.as_deref()) {
+                (false, _) => arg,
+                (true, None) => format!("{arg}..."),
+                (true, Some(separator)) => format!("{arg}{separator:?}..."),
+            }),
         ) {
             (None, _, None) => write!(f, ""),
             (Some(flag), _, None) => write!(f, "{}", flag),
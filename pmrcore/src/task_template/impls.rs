@@ -1,6 +1,18 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
 use crate::task_template::*;
 
+impl TaskTemplate {
+    /// The wall-clock limit for tasks created from this template.
+    pub fn timeout_duration(&self) -> Option<Duration> {
+        self.timeout
+            .and_then(|timeout| u64::try_from(timeout).ok())
+            .map(Duration::from_secs)
+    }
+}
+
 impl From<Vec<TaskTemplateArg>> for TaskTemplateArgs {
     fn from(args: Vec<TaskTemplateArg>) -> Self {
         Self(args)
@@ -141,3 +153,77 @@ impl From<Vec<String>> for UserChoices {
         )
     }
 }
+
+impl UserInput {
+    /// The values provided, with a single value being a list of one.
+    pub fn values(&self) -> Vec<&str> {
+        match self {
+            Self::Single(value) => vec![value.as_str()],
+            Self::Multiple(values) => values.iter()
+                .map(String::as_str)
+                .collect(),
+        }
+    }
+}
+
+impl From<String> for UserInput {
+    fn from(value: String) -> Self {
+        Self::Single(value)
+    }
+}
+
+impl From<&str> for UserInput {
+    fn from(value: &str) -> Self {
+        Self::Single(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for UserInput {
+    fn from(values: Vec<String>) -> Self {
+        Self::Multiple(values)
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for UserInput {
+    fn from(values: [&str; N]) -> Self {
+        Self::Multiple(values.into_iter().map(str::to_string).collect())
+    }
+}
+
+/// Collects the values provided for an argument, where a lone value is
+/// kept as a single value.
+impl FromIterator<String> for UserInput {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut values = iter.into_iter().collect::<Vec<_>>();
+        if values.len() == 1 {
+            Self::Single(values.remove(0))
+        } else {
+            Self::Multiple(values)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_user_input_serde() -> anyhow::Result<()> {
+        let user_input: UserInputMap = serde_json::from_str(r#"{
+            "1": "single",
+            "2": ["first", "second"]
+        }"#)?;
+        assert_eq!(user_input[&1], UserInput::from("single"));
+        assert_eq!(user_input[&2], UserInput::from(["first", "second"]));
+        assert_eq!(user_input[&1].values(), ["single"]);
+        assert_eq!(user_input[&2].values(), ["first", "second"]);
+        assert_eq!(serde_json::to_string(&user_input[&1])?, r#""single""#);
+        assert_eq!(serde_json::to_string(&user_input[&2])?, r#"["first","second"]"#);
+
+        let collected: UserInput = ["single".to_string()].into_iter().collect();
+        assert_eq!(collected, UserInput::from("single"));
+        let collected: UserInput = Vec::<String>::new().into_iter().collect();
+        assert_eq!(collected, UserInput::Multiple(vec![]));
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use crate::{
     error::BackendError,
     task_template::{
+        TaskSandbox,
         TaskTemplate,
         TaskTemplateArg,
         TaskTemplateArgChoice,
@@ -31,6 +32,8 @@ pub trait TaskTemplateBackend {
         default: Option<&str>,
         choice_fixed: bool,
         choice_source: Option<&str>,
+        multiple: bool,
+        multiple_separator: Option<&str>,
     ) -> Result<i64, BackendError>;
     async fn delete_task_template_arg_by_id(
         &self,
@@ -58,6 +61,30 @@ pub trait TaskTemplateBackend {
         &self,
         id: i64,
     ) -> Result<TaskTemplate, BackendError>;
+    /// Set the wall-clock limit in seconds for tasks created from the
+    /// task template; `None` removes the limit.  Unlike the arguments,
+    /// this may be changed after the task template is finalized.
+    async fn set_task_template_timeout(
+        &self,
+        id: i64,
+        timeout: Option<i64>,
+    ) -> Result<bool, BackendError>;
+    /// Set the sandbox for tasks created from the task template; `None`
+    /// removes the sandbox such that the tasks run unrestricted.
+    async fn set_task_template_sandbox(
+        &self,
+        id: i64,
+        sandbox: Option<&TaskSandbox>,
+    ) -> Result<bool, BackendError>;
+    /// Mark the task template as superseded by another, which must be a
+    /// finalized task template that is not itself superseded.  Returns
+    /// whether the task template was marked, which is not the case where
+    /// it has already been superseded.
+    async fn supersede_task_template(
+        &self,
+        id: i64,
+        superceded_by_id: i64,
+    ) -> Result<bool, BackendError>;
 
     /// This adds a task template item by the Template struct.
     ///
@@ -82,6 +109,12 @@ pub trait TaskTemplateBackend {
             args.push(task.await?);
         }
         let final_arg_id = self.finalize_new_task_template(id).await?;
+        if item.timeout.is_some() {
+            self.set_task_template_timeout(id, item.timeout).await?;
+        }
+        if item.sandbox.is_some() {
+            self.set_task_template_sandbox(id, item.sandbox.as_ref()).await?;
+        }
         Ok(TaskTemplate {
             id,
             bin_path: item.bin_path,
@@ -89,6 +122,8 @@ pub trait TaskTemplateBackend {
             created_ts: created_ts,
             final_task_template_arg_id: final_arg_id,
             superceded_by_id: None,
+            timeout: item.timeout,
+            sandbox: item.sandbox,
             args: Some(args.into()),
         })
     }
@@ -112,6 +147,8 @@ pub trait TaskTemplateBackend {
             item.default.as_deref(),
             item.choice_fixed,
             item.choice_source.as_deref(),
+            item.multiple,
+            item.multiple_separator.as_deref(),
         ).await?;
         let mut tasks = item.choices
             .map(|choices| choices.0)
@@ -133,6 +170,8 @@ pub trait TaskTemplateBackend {
             choice_fixed: item.choice_fixed,
             choice_source: item.choice_source,
             choices: Some(choices.into()),
+            multiple: item.multiple,
+            multiple_separator: item.multiple_separator,
         })
     }
 
@@ -11,6 +11,7 @@ pub struct Workspace {
     pub description: Option<String>,
     pub long_description: Option<String>,
     pub created_ts: i64,
+    pub archived_ts: Option<i64>,
 
     pub exposures: Option<Exposures>,
 }
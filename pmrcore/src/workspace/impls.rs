@@ -85,6 +85,9 @@ impl<'a> traits::Workspace<'a, Exposures> for Workspace {
     fn created_ts(&self) -> i64 {
         self.created_ts
     }
+    fn archived_ts(&self) -> Option<i64> {
+        self.archived_ts
+    }
     async fn exposures(&'a self) -> Result<&Exposures, Error> {
         Ok(self.exposures.as_ref().ok_or(ValueError::Uninitialized)?)
     }
@@ -110,6 +113,9 @@ impl<'a> traits::Workspace<'a, ExposureRefs<'a>> for WorkspaceRef<'a> {
     fn created_ts(&self) -> i64 {
         self.inner.created_ts
     }
+    fn archived_ts(&self) -> Option<i64> {
+        self.inner.archived_ts
+    }
     async fn exposures(&'a self) -> Result<&'a ExposureRefs<'a>, Error> {
         match self.exposures.get() {
             Some(exposures) => Ok(exposures),
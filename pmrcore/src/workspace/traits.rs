@@ -10,6 +10,7 @@ mod data {
         fn description(&self) -> Option<&str>;
         fn long_description(&self) -> Option<&str>;
         fn created_ts(&self) -> i64;
+        fn archived_ts(&self) -> Option<i64>;
         async fn exposures(&'a self) -> Result<&'a S, Error>;
     }
 }
@@ -18,6 +19,10 @@ mod backend {
     use async_trait::async_trait;
     use crate::{
         error::BackendError,
+        listing::{
+            ListingQuery,
+            Page,
+        },
         workspace::{
             Workspace,
             Workspaces,
@@ -48,6 +53,14 @@ mod backend {
         async fn list_workspaces(
             &self,
         ) -> Result<Workspaces, BackendError>;
+        /// Returns a page of the workspaces that are not archived.
+        ///
+        /// An error is returned if the cursor does not apply to the sort
+        /// key of the query.
+        async fn list_workspaces_page(
+            &self,
+            query: &ListingQuery,
+        ) -> Result<Page<Workspace>, BackendError>;
         async fn list_workspace_by_url(
             &self,
             url: &str,
@@ -56,6 +69,30 @@ mod backend {
             &self,
             ids: &[i64],
         ) -> Result<Workspaces, BackendError>;
+        /// Set the workspace that supercedes the workspace identified by
+        /// `id`, or clear it with `None`.  Returns `false` if there is no
+        /// workspace with `id`.
+        async fn set_workspace_superceded_by(
+            &self,
+            id: i64,
+            superceded_by_id: Option<i64>,
+        ) -> Result<bool, BackendError>;
+        /// Archive the workspace, which hides it from `list_workspaces`.
+        /// Returns `false` if there is no such workspace or it is already
+        /// archived.
+        async fn archive_workspace(
+            &self,
+            id: i64,
+        ) -> Result<bool, BackendError>;
+        /// Restore an archived workspace.  Returns `false` if there is no
+        /// such workspace or it is not archived.
+        async fn restore_workspace(
+            &self,
+            id: i64,
+        ) -> Result<bool, BackendError>;
+        async fn list_archived_workspaces(
+            &self,
+        ) -> Result<Workspaces, BackendError>;
     }
 
     #[async_trait]
@@ -73,6 +110,10 @@ mod backend {
             &self,
             workspace_id: i64,
         ) -> Result<Vec<WorkspaceSync>, BackendError>;
+        async fn list_latest_sync_records(
+            &self,
+            limit: i64,
+        ) -> Result<Vec<WorkspaceSync>, BackendError>;
     }
 
     #[async_trait]
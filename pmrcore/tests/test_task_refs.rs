@@ -24,8 +24,8 @@ async fn test_ref_impls() -> anyhow::Result<()> {
         )));
     platform.expect_run()
         .times(1)
-        .with(eq(task_id), eq(task_pid))
-        .returning(|_, _| Ok(true));
+        .with(eq(task_id), eq(task_pid), eq("localhost"))
+        .returning(|_, _, _| Ok(true));
     platform.expect_complete()
         .times(1)
         .with(eq(task_id), eq(task_exit))
@@ -35,8 +35,9 @@ async fn test_ref_impls() -> anyhow::Result<()> {
         .await?
         .expect("task started");
     assert_eq!(task_ref.pid(), None);
-    task_ref.run(task_pid).await?;
+    task_ref.run(task_pid, "localhost").await?;
     assert_eq!(task_ref.pid(), Some(task_pid));
+    assert_eq!(task_ref.host(), Some("localhost"));
 
     assert_eq!(task_ref.exit_status(), None);
     task_ref.complete(task_exit).await?;
@@ -45,6 +46,7 @@ async fn test_ref_impls() -> anyhow::Result<()> {
     assert_eq!(task_ref.into_inner(), Task {
         id: 1,
         pid: Some(123),
+        host: Some("localhost".into()),
         exit_status: Some(0),
         .. Default::default()
     });
@@ -0,0 +1,60 @@
+use clap::Parser;
+use pmrctrl::executor::RemoteExecutor;
+use pmrtqs::{
+    executor::host_id,
+    runtime::Builder as RuntimeBuilder,
+};
+use std::{
+    error::Error,
+    path::PathBuf,
+};
+
+/// The runner for the tasks claimed from a pmrapp server, for running on
+/// hosts that do not share the database and filesystem with the server.
+#[derive(Debug, Parser)]
+struct Cli {
+    #[clap(short = 'r', long = "runners", default_value = "8")]
+    runners: usize,
+    /// The base url of the pmrapp server to claim the tasks from.
+    #[clap(long, value_name = "PMR_RUNNER_SERVER", env = "PMR_RUNNER_SERVER")]
+    server: String,
+    /// The token for the runner endpoints of the server.
+    #[clap(long, value_name = "PMR_RUNNER_TOKEN", env = "PMR_RUNNER_TOKEN")]
+    token: String,
+    /// The directory where the claimed tasks are executed from.
+    #[clap(long, value_name = "PMR_RUNNER_ROOT", env = "PMR_RUNNER_ROOT")]
+    root: PathBuf,
+    /// Identifies this runner to the server, which defaults to the host
+    /// along with the pid of this process.
+    #[clap(long)]
+    host: Option<String>,
+    #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+
+fn main() -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+    dotenvy::dotenv().ok();
+    let args = Cli::parse();
+    stderrlog::new()
+        .module(module_path!())
+        .module("pmrctrl")
+        .module("pmrtqs")
+        .verbosity((args.verbose as usize) + 1)
+        .timestamp(stderrlog::Timestamp::Second)
+        .init()
+        .unwrap();
+
+    let host = args.host
+        .unwrap_or_else(|| format!("{}/{}", host_id(), std::process::id()));
+    log::info!("claiming tasks from {} as {host}", args.server);
+    let executor = RemoteExecutor::new(args.server, args.token, host, args.root);
+    let mut runtime = RuntimeBuilder::from(executor)
+        .permits(args.runners)
+        .build();
+    runtime.start();
+    log::info!("runner runtime starting");
+    runtime.wait();
+    log::info!("runner runtime stopped");
+    Ok(())
+}
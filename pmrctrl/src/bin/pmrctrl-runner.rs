@@ -3,7 +3,10 @@ use pmrctrl::{
     executor::Executor,
     platform::Builder,
 };
-use pmrtqs::runtime::Runtime;
+use pmrtqs::{
+    executor::OrphanPolicy,
+    runtime::Builder as RuntimeBuilder,
+};
 use std::error::Error;
 use tokio;
 
@@ -11,6 +14,9 @@ use tokio;
 struct Cli {
     #[clap(short = 'r', long = "runners", default_value = "8")]
     runners: usize,
+    /// How to recover the tasks orphaned by a previous runner.
+    #[clap(long, value_enum, default_value_t = OrphanPolicy::Fail)]
+    orphan_policy: OrphanPolicy,
     #[clap(flatten)]
     platform_builder: Builder,
     #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
@@ -37,7 +43,10 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
         Ok::<_, Box<dyn Error + Send + Sync + 'static>>(platform)
     })?;
     let executor = Executor::new(platform);
-    let mut runtime = Runtime::new(executor, args.runners);
+    let mut runtime = RuntimeBuilder::from(executor)
+        .permits(args.runners)
+        .orphan_policy(args.orphan_policy)
+        .build();
     runtime.start();
     log::info!("runner runtime starting");
     runtime.wait();
@@ -12,25 +12,36 @@ use pmrcore::{
         },
         profile::traits::ExposureFileProfileBackend,
     },
-    profile::traits::{
-        ProfileBackend,
-        ProfileViewsBackend,
-        ViewTaskTemplateBackend,
-        ViewTaskTemplateProfileBackend,
+    profile::{
+        ViewTaskTemplate,
+        ViewTaskTemplateProfile,
+        traits::{
+            ProfileBackend,
+            ProfileViewsBackend,
+            ViewTaskTemplateBackend,
+            ViewTaskTemplateProfileBackend,
+        },
     },
+    task::TaskSchedule,
     task_template::{
+        TaskTemplate,
         UserArgs,
+        UserInput,
         UserInputMap,
+        traits::TaskTemplateBackend,
     },
 };
-use pmrctrl::platform::{
-    Builder as PlatformBuilder,
-    Platform,
+use pmrctrl::{
+    platform::{
+        Builder as PlatformBuilder,
+        Platform,
+    },
+    registry::VIEW_CHOICE_SOURCES,
 };
 use pmrmodel::{
     model::{
         profile::UserViewProfileRef,
-        task_template::TaskArgBuilder,
+        task_template::TaskArgBuilders,
     },
     registry::{
         ChoiceRegistry,
@@ -39,6 +50,7 @@ use pmrmodel::{
     },
 };
 use std::{
+    collections::HashMap,
     fs,
     io::{
         stdin,
@@ -143,6 +155,11 @@ enum Commands {
         #[command(subcommand)]
         cmd: VttCmd,
     },
+    #[command(arg_required_else_help = true)]
+    Workspace {
+        #[command(subcommand)]
+        cmd: WorkspaceCmd,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -186,6 +203,15 @@ enum ExposureCmd {
     #[command(arg_required_else_help = true)]
     SubmitTasks {
         exposure_id: i64,
+        /// Tasks with a higher priority are started first.
+        #[clap(long, default_value_t = 0, allow_negative_numbers = true)]
+        priority: i64,
+        /// The number of times a failed task may be retried.
+        #[clap(long, default_value_t = 0)]
+        max_retries: i64,
+        /// Seconds to wait before the first retry, doubling for every retry.
+        #[clap(long, default_value_t = 0)]
+        retry_delay: i64,
     },
 }
 
@@ -250,6 +276,13 @@ enum VttCmd {
     Import {
         input: Option<std::path::PathBuf>,
     },
+    /// Validate a view task template without importing it.
+    Validate {
+        input: Option<std::path::PathBuf>,
+    },
+    /// List the registries that may be referenced by the `choice_source`
+    /// of the task template arguments.
+    Sources,
     #[command(arg_required_else_help = true)]
     Link {
         task_template_id: i64,
@@ -259,7 +292,68 @@ enum VttCmd {
     #[command(arg_required_else_help = true)]
     Export {
         id: i64,
-    }
+    },
+    #[command(arg_required_else_help = true)]
+    DependsOn {
+        id: i64,
+        /// The view_key of the view to be generated first; omit to clear.
+        view_key: Option<String>,
+    },
+    /// Import a complete task template as the new version of the task
+    /// template, then migrate the views over to it.
+    #[command(arg_required_else_help = true)]
+    Publish {
+        /// The task template being superseded.
+        task_template_id: i64,
+        input: Option<std::path::PathBuf>,
+    },
+    /// Supersede the task template with a newer finalized version, then
+    /// migrate the views over to it.
+    #[command(arg_required_else_help = true)]
+    Supersede {
+        task_template_id: i64,
+        superceded_by_id: i64,
+    },
+    /// List the exposure file views built with an outdated task template.
+    Outdated {
+        /// Only list the views now linked to this task template.
+        #[clap(long)]
+        task_template_id: Option<i64>,
+    },
+    /// Queue the tasks for the exposure files with outdated views.
+    Rebuild {
+        /// Only rebuild the views now linked to this task template.
+        #[clap(long)]
+        task_template_id: Option<i64>,
+        /// Tasks with a higher priority are started first.
+        #[clap(long, default_value_t = 0, allow_negative_numbers = true)]
+        priority: i64,
+        /// The number of times a failed task may be retried.
+        #[clap(long, default_value_t = 0)]
+        max_retries: i64,
+        /// Seconds to wait before the first retry, doubling for every retry.
+        #[clap(long, default_value_t = 0)]
+        retry_delay: i64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum WorkspaceCmd {
+    #[command(arg_required_else_help = true)]
+    Supersede {
+        workspace_id: i64,
+        /// The superceding workspace; omit to clear the supersession.
+        superceded_by_id: Option<i64>,
+    },
+    #[command(arg_required_else_help = true)]
+    Archive {
+        workspace_id: i64,
+    },
+    #[command(arg_required_else_help = true)]
+    Restore {
+        workspace_id: i64,
+    },
+    ListArchived,
 }
 
 #[derive(Debug, Subcommand)]
@@ -277,7 +371,9 @@ enum ExposurePathCmd {
     #[command(arg_required_else_help = true)]
     Answer {
         arg_id: i64,
-        answer: String,
+        /// Multiple values may be provided for arguments accepting them.
+        #[clap(required = true)]
+        answer: Vec<String>,
     },
     Answers,
     #[command(arg_required_else_help = true)]
@@ -289,9 +385,35 @@ enum ExposurePathCmd {
         #[clap(long, short='p', action)]
         submit: bool,
     },
+    /// Render the argv of the tasks for the views without queuing them,
+    /// using the answers provided over the ones stored for the file.
+    DryRun {
+        /// An answer in the form ARG_ID=VALUE; may be repeated, including
+        /// for the same ARG_ID to provide multiple values.
+        #[clap(long = "answer", value_name = "ARG_ID=VALUE", value_parser = parse_answer)]
+        answers: Vec<(i64, String)>,
+    },
     Views,
 }
 
+fn parse_answer(s: &str) -> Result<(i64, String), String> {
+    let (arg_id, value) = s.split_once('=')
+        .ok_or_else(|| format!("invalid answer `{s}`; expected ARG_ID=VALUE"))?;
+    let arg_id = arg_id.parse::<i64>()
+        .map_err(|e| format!("invalid ARG_ID `{arg_id}`: {e}"))?;
+    Ok((arg_id, value.to_string()))
+}
+
+fn collect_answers(answers: Vec<(i64, String)>) -> UserInputMap {
+    let mut values = HashMap::<i64, Vec<String>>::new();
+    for (arg_id, value) in answers {
+        values.entry(arg_id).or_default().push(value);
+    }
+    values.into_iter()
+        .map(|(arg_id, values)| (arg_id, values.into_iter().collect()))
+        .collect()
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
@@ -331,6 +453,9 @@ async fn main() -> anyhow::Result<()> {
         Commands::Vtt { cmd } => {
             parse_vtt(&platform, cmd).await?;
         },
+        Commands::Workspace { cmd } => {
+            parse_workspace(&platform, cmd).await?;
+        },
     }
 
     Ok(())
@@ -384,8 +509,12 @@ async fn parse_exposure<'p>(
         ExposureCmd::Path { exposure_id, path, cmd } => {
             parse_exposure_path(&platform, exposure_id, path.as_ref(), cmd).await?;
         }
-        ExposureCmd::SubmitTasks { exposure_id } => {
-            let count = platform.process_vttc_tasks_for_exposure(exposure_id).await?;
+        ExposureCmd::SubmitTasks { exposure_id, priority, max_retries, retry_delay } => {
+            let schedule = TaskSchedule { priority, max_retries, retry_delay };
+            let count = platform.process_vttc_tasks_for_exposure_with(
+                exposure_id,
+                &schedule,
+            ).await?;
             println!("Queued {count} tasks.");
         }
     }
@@ -447,12 +576,14 @@ async fn parse_profile<'p>(
             println!("updated profile {id}");
         },
         ProfileCmd::Import { input } => {
-            let id = platform.add_view_task_template_profile(
-                match input {
-                    Some(path) => conf.serde_kind.from_reader(BufReader::new(fs::File::open(path)?))?,
-                    None => conf.serde_kind.from_reader(BufReader::new(stdin()))?,
-                }
-            ).await?;
+            let vttp: ViewTaskTemplateProfile = match input {
+                Some(path) => conf.serde_kind.from_reader(BufReader::new(fs::File::open(path)?))?,
+                None => conf.serde_kind.from_reader(BufReader::new(stdin()))?,
+            };
+            for view_task_template in vttp.view_task_templates.iter() {
+                validate_view_task_template(platform, view_task_template)?;
+            }
+            let id = platform.add_view_task_template_profile(vttp).await?;
             println!("imported ViewTaskTemplateProfile {id}");
         },
         ProfileCmd::Export { as_prompts, profile_id } => {
@@ -518,14 +649,36 @@ async fn parse_vtt<'p>(
     let conf = CONF.get().expect("config is set by main");
     match arg {
         VttCmd::Import { input } => {
-            let id = platform.adds_view_task_template(
-                match input {
-                    Some(path) => conf.serde_kind.from_reader(BufReader::new(fs::File::open(path)?))?,
-                    None => conf.serde_kind.from_reader(BufReader::new(stdin()))?,
-                }
-            ).await?;
+            let view_task_template: ViewTaskTemplate = match input {
+                Some(path) => conf.serde_kind.from_reader(BufReader::new(fs::File::open(path)?))?,
+                None => conf.serde_kind.from_reader(BufReader::new(stdin()))?,
+            };
+            validate_view_task_template(platform, &view_task_template)?;
+            let id = platform.adds_view_task_template(view_task_template).await?;
             println!("imported ViewTaskTemplate {id}");
         }
+        VttCmd::Validate { input } => {
+            let view_task_template: ViewTaskTemplate = match input {
+                Some(path) => conf.serde_kind.from_reader(BufReader::new(fs::File::open(path)?))?,
+                None => conf.serde_kind.from_reader(BufReader::new(stdin()))?,
+            };
+            validate_view_task_template(platform, &view_task_template)?;
+            println!("ViewTaskTemplate {} is valid", view_task_template.view_key);
+        }
+        VttCmd::Sources => {
+            println!("choice_source - description");
+            for provider in platform.choice_registry_providers().iter() {
+                let name = provider.name();
+                let description = provider.description();
+                match provider.parameter() {
+                    Some(parameter) => println!("{name}:<{parameter}> - {description}"),
+                    None => println!("{name} - {description}"),
+                }
+            }
+            for name in VIEW_CHOICE_SOURCES.iter() {
+                println!("{name} - provided for each view");
+            }
+        }
         VttCmd::Link { task_template_id, view_key, description } => {
             let id = ViewTaskTemplateBackend::insert_view_task_template(
                 platform.mc_platform.as_ref(),
@@ -543,10 +696,93 @@ async fn parse_vtt<'p>(
             let output = conf.serde_kind.to_string(&result)?;
             println!("{output}");
         }
+        VttCmd::DependsOn { id, view_key } => {
+            if ViewTaskTemplateBackend::set_view_task_template_depends_on(
+                platform.mc_platform.as_ref(),
+                id,
+                view_key.as_deref(),
+            ).await? {
+                match view_key {
+                    Some(view_key) => println!("ViewTaskTemplate {id} now depends on view_key {view_key}"),
+                    None => println!("cleared dependency for ViewTaskTemplate {id}"),
+                }
+            } else {
+                println!("no ViewTaskTemplate {id}");
+            }
+        }
+        VttCmd::Publish { task_template_id, input } => {
+            // ensure the task template being superseded exists.
+            TaskTemplateBackend::get_task_template_by_id(
+                platform.tm_platform.as_ref(),
+                task_template_id,
+            ).await?;
+            let task_template: TaskTemplate = match input {
+                Some(path) => conf.serde_kind.from_reader(BufReader::new(fs::File::open(path)?))?,
+                None => conf.serde_kind.from_reader(BufReader::new(stdin()))?,
+            };
+            platform.validate_task_template(&task_template)?;
+            let task_template = TaskTemplateBackend::adds_task_template(
+                platform.tm_platform.as_ref(),
+                task_template,
+            ).await?;
+            let id = task_template.id;
+            println!("imported TaskTemplate {id}");
+            let vtt_ids = platform.supersede_task_template(task_template_id, id).await?;
+            println!(
+                "TaskTemplate {task_template_id} superceded by TaskTemplate {id}; \
+                migrated ViewTaskTemplate(s) {vtt_ids:?}"
+            );
+        }
+        VttCmd::Supersede { task_template_id, superceded_by_id } => {
+            let vtt_ids = platform.supersede_task_template(
+                task_template_id,
+                superceded_by_id,
+            ).await?;
+            println!(
+                "TaskTemplate {task_template_id} superceded by TaskTemplate {superceded_by_id}; \
+                migrated ViewTaskTemplate(s) {vtt_ids:?}"
+            );
+        }
+        VttCmd::Outdated { task_template_id } => {
+            let builds = platform.list_outdated_views(task_template_id).await?;
+            println!("exposure_id - path - view_key - built with - current");
+            for build in builds.iter() {
+                let built = build.built_task_template_id
+                    .map(|id| id.to_string())
+                    .unwrap_or("unknown".to_string());
+                println!(
+                    "{} - {} - {} - {built} - {}",
+                    build.exposure_id,
+                    build.workspace_file_path,
+                    build.view_key,
+                    build.task_template_id,
+                );
+            }
+        }
+        VttCmd::Rebuild { task_template_id, priority, max_retries, retry_delay } => {
+            let schedule = TaskSchedule { priority, max_retries, retry_delay };
+            let count = platform.rebuild_outdated_views_with(
+                task_template_id,
+                &schedule,
+            ).await?;
+            println!("Queued {count} tasks.");
+        }
     }
     Ok(())
 }
 
+fn validate_view_task_template(
+    platform: &Platform,
+    view_task_template: &ViewTaskTemplate,
+) -> anyhow::Result<()> {
+    let view_key = &view_task_template.view_key;
+    match view_task_template.task_template.as_ref() {
+        Some(task_template) => platform.validate_task_template(task_template)
+            .map_err(|e| anyhow::anyhow!("ViewTaskTemplate {view_key}: {e}")),
+        None => anyhow::bail!("ViewTaskTemplate {view_key}: task_template must be provided"),
+    }
+}
+
 async fn parse_file_profile<'p>(
     platform: &'p Platform,
     exposure_file_id: i64,
@@ -599,6 +835,7 @@ async fn parse_exposure_path<'p>(
             let efvttsc = efc.build_vttc().await?;
 
             // store the answer anyway.
+            let answer = answer.into_iter().collect::<UserInput>();
             let user_input = UserInputMap::from([
                 (arg_id, answer.clone()),
             ]);
@@ -612,8 +849,8 @@ async fn parse_exposure_path<'p>(
             // validation
             let arg = efvttsc.get_arg(&arg_id)
                 .expect("provided arg_id is not part of the selected profile");
-            match TaskArgBuilder::try_from((
-                Some(answer.as_ref()),
+            match TaskArgBuilders::try_from((
+                Some(&answer),
                 arg,
                 efvttsc.get_registry_cache()?,
             )) {
@@ -638,8 +875,8 @@ async fn parse_exposure_path<'p>(
                         .expect("the arg that formed this user_arg be present");
                     let prompt = &user_arg.prompt;
                     let answer = profile.user_input.get(&user_arg.id);
-                    let builder = TaskArgBuilder::try_from((
-                        answer.map(|s| s.as_ref()),
+                    let builder = TaskArgBuilders::try_from((
+                        answer,
                         arg,
                         cache.clone(),
                     ));
@@ -682,6 +919,22 @@ async fn parse_exposure_path<'p>(
                 println!("no profile assigned for exposure {exposure_id} file {path}");
             }
         },
+        ExposurePathCmd::DryRun { answers } => {
+            let id = efc.exposure_file().id();
+            let mut user_input = ExposureFileProfileBackend::get_ef_profile(
+                platform.mc_platform.as_ref(),
+                id,
+            ).await?
+                .map(|profile| profile.user_input)
+                .unwrap_or_default();
+            user_input.extend(collect_answers(answers));
+            let efvttsc = efc.build_vttc().await?;
+            let vttc_tasks = efvttsc.create_tasks_from_input(&user_input)?;
+            for vttc_task in vttc_tasks.iter() {
+                let argv = serde_json::to_string(&vttc_task.task().argv())?;
+                println!("{}: {argv}", vttc_task.view_key());
+            }
+        },
         ExposurePathCmd::Views => {
             let ef = efc.exposure_file();
             let views = ef.views().await?;
@@ -700,3 +953,41 @@ async fn parse_exposure_path<'p>(
 
     Ok(())
 }
+
+async fn parse_workspace(
+    platform: &Platform,
+    arg: WorkspaceCmd,
+) -> anyhow::Result<()> {
+    match arg {
+        WorkspaceCmd::Supersede { workspace_id, superceded_by_id } => {
+            platform.supersede_workspace(workspace_id, superceded_by_id).await?;
+            match superceded_by_id {
+                Some(id) => println!("workspace {workspace_id} superceded by workspace {id}"),
+                None => println!("workspace {workspace_id} no longer superceded"),
+            }
+        }
+        WorkspaceCmd::Archive { workspace_id } => {
+            if platform.archive_workspace(workspace_id).await? {
+                println!("archived workspace {workspace_id}");
+            } else {
+                println!("workspace {workspace_id} already archived");
+            }
+        }
+        WorkspaceCmd::Restore { workspace_id } => {
+            if platform.restore_workspace(workspace_id).await? {
+                println!("restored workspace {workspace_id}");
+            } else {
+                println!("workspace {workspace_id} was not archived");
+            }
+        }
+        WorkspaceCmd::ListArchived => {
+            let workspaces = platform.mc_platform.list_archived_workspaces().await?;
+            println!("id - archived_ts - url");
+            for workspace in workspaces.iter() {
+                let archived_ts = workspace.archived_ts.unwrap_or_default();
+                println!("{} - {archived_ts} - {}", workspace.id, workspace.url);
+            }
+        }
+    }
+    Ok(())
+}
@@ -28,6 +28,8 @@ pub enum PlatformError {
     #[error(transparent)]
     PmrRepoError(#[from] PmrRepoError),
     #[error(transparent)]
+    RemoteError(#[from] reqwest::Error),
+    #[error(transparent)]
     RunnerError(#[from] RunnerError),
     #[error(transparent)]
     TaskError(#[from] TaskError),
@@ -69,4 +71,24 @@ pub enum CtrlError {
     // FIXME need to figure out the appropriate level of detail to capture
     #[error("arg_id {0} not in profile")]
     ArgIdNotInProfile(i64),
+
+    /// The workspace cannot be superceded by the provided workspace as
+    /// that would form a cycle.
+    #[error("workspace {0} cannot be superceded by workspace {1}")]
+    SupersessionCycle(i64, i64),
+    /// The workspace has been archived.
+    #[error("workspace archived: {0}")]
+    WorkspaceArchived(i64),
+    /// The task template cannot be superceded by the provided task
+    /// template, which must be a different finalized task template that
+    /// is not itself superceded.
+    #[error("task template {0} cannot be superceded by task template {1}")]
+    TaskTemplateSupersession(i64, i64),
+    /// The task is not bound to a view of the associated resource.
+    #[error("unknown task: {0}")]
+    UnknownTask(i64),
+    /// The task is not leased to the remote runner, such as when the
+    /// lease had expired and the task was returned to the queue.
+    #[error("task not leased: {0}")]
+    TaskNotLeased(i64),
 }
@@ -1,4 +1,11 @@
 mod impls;
 mod types;
 
-pub use types::Executor;
+pub use impls::{
+    pack_dir,
+    unpack_dir,
+};
+pub use types::{
+    Executor,
+    RemoteExecutor,
+};
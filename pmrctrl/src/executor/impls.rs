@@ -1,6 +1,17 @@
 use async_trait::async_trait;
 use pmrcore::task::TaskDetached;
-use pmrtqs::executor::traits;
+use pmrtqs::executor::{
+    OrphanPolicy,
+    recover_orphans,
+    traits,
+};
+use std::{
+    io::{
+        Read,
+        Write,
+    },
+    path::Path,
+};
 use tokio::sync::broadcast;
 
 use crate::{
@@ -10,6 +21,8 @@ use crate::{
     platform::Platform,
 };
 
+mod remote;
+
 impl Executor {
     pub fn new(platform: Platform) -> Self {
         Self { platform }
@@ -19,6 +32,7 @@ impl Executor {
 #[async_trait]
 impl traits::Executor for Executor {
     type Error = PlatformError;
+    type Task = TaskDetached;
 
     async fn start_task(
         &self,
@@ -35,13 +49,42 @@ impl traits::Executor for Executor {
     async fn execute(
         &self,
         task: TaskDetached,
-        // TODO deal with aborts.
-        _abort_receiver: broadcast::Receiver<()>,
+        abort_receiver: broadcast::Receiver<()>,
     ) -> Result<(i32, bool), Self::Error> {
         let tec = TaskExecutorCtrl::new(
             &self.platform,
             task.bind(self.platform.tm_platform.as_ref())?,
         );
-        tec.execute().await
+        tec.execute_abortable(abort_receiver).await
+    }
+
+    async fn recover(
+        &self,
+        policy: OrphanPolicy,
+    ) -> Result<usize, Self::Error> {
+        Ok(recover_orphans(
+            self.platform.tm_platform.as_ref(),
+            policy,
+        ).await?.len())
     }
 }
+
+/// Write the contents of the directory as a tar archive, as the files of
+/// tasks are transferred between the server and the remote runners.  The
+/// symlinks are archived as is, rather than what they point to.
+pub fn pack_dir(dir: &Path, writer: impl Write) -> std::io::Result<()> {
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+    builder.append_dir_all(".", dir)?;
+    builder.into_inner()?.flush()
+}
+
+/// Unpack the tar archive from the reader into the directory; entries
+/// that would be placed outside of the directory are not unpacked.
+pub fn unpack_dir(reader: impl Read, dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut archive = tar::Archive::new(reader);
+    archive.set_preserve_permissions(false);
+    archive.set_overwrite(true);
+    archive.unpack(dir)
+}
@@ -0,0 +1,267 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use pmrcore::task::{
+    EXIT_SANDBOX_FAILED,
+    TaskClaim,
+};
+use pmrtqs::executor::{
+    OrphanPolicy,
+    TaskProcess,
+    aborted,
+    traits,
+};
+use reqwest::{
+    Method,
+    RequestBuilder,
+    Response,
+    StatusCode,
+};
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::broadcast,
+    time,
+};
+
+use crate::{
+    error::{
+        CtrlError,
+        PlatformError,
+    },
+    executor::{
+        RemoteExecutor,
+        pack_dir,
+        unpack_dir,
+    },
+};
+
+impl RemoteExecutor {
+    /// The executor for the pmrapp server at the base url, authenticating
+    /// with the runner token configured there.  The host identifies this runner to the
+    /// server for the leases of the tasks it claims, and the root is where
+    /// the claimed tasks are executed from.
+    pub fn new(
+        server: impl Into<String>,
+        token: impl Into<String>,
+        host: impl Into<String>,
+        root: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            server: server.into(),
+            token: token.into(),
+            host: host.into(),
+            root: root.into(),
+        }
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(
+                method,
+                format!("{}/api/runner/{path}", self.server.trim_end_matches('/')),
+            )
+            .header("x-runner-token", &self.token)
+            .query(&[("host", &self.host)])
+    }
+
+    async fn send(
+        &self,
+        task_id: i64,
+        request: RequestBuilder,
+    ) -> Result<Response, PlatformError> {
+        let response = request.send().await?;
+        if response.status() == StatusCode::CONFLICT {
+            Err(CtrlError::TaskNotLeased(task_id))?
+        }
+        Ok(response.error_for_status()?)
+    }
+
+    async fn claim(&self) -> Result<Option<TaskClaim>, PlatformError> {
+        let response = self.request(Method::POST, "claim")
+            .send()
+            .await?
+            .error_for_status()?;
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        Ok(Some(response.json().await?))
+    }
+
+    async fn renew(&self, task_id: i64) -> Result<i64, PlatformError> {
+        Ok(self.send(
+            task_id,
+            self.request(Method::POST, &format!("task/{task_id}/lease")),
+        ).await?.json().await?)
+    }
+
+    // Renew the lease on the task until it can no longer be renewed, i.e.
+    // when the server reports that it is no longer leased to this runner
+    // or if the lease has expired before it could be renewed.
+    async fn keep_lease(&self, task_id: i64, mut lease_ts: i64) {
+        loop {
+            // renew once a third of the remaining duration has passed.
+            let remaining = lease_ts - Utc::now().timestamp();
+            time::sleep(Duration::from_secs((remaining / 3).max(1) as u64)).await;
+            match self.renew(task_id).await {
+                Ok(value) => lease_ts = value,
+                Err(PlatformError::CtrlError(CtrlError::TaskNotLeased(_))) => break,
+                Err(e) if Utc::now().timestamp() >= lease_ts => {
+                    log::error!("failed to renew lease on task id {task_id}: {e}");
+                    break;
+                }
+                Err(e) => log::warn!("failed to renew lease on task id {task_id}: {e}"),
+            }
+        }
+    }
+
+    async fn fetch_input(
+        &self,
+        task_id: i64,
+        dir: &Path,
+    ) -> Result<(), PlatformError> {
+        let input = self.send(
+            task_id,
+            self.request(Method::GET, &format!("task/{task_id}/input")),
+        ).await?.bytes().await?;
+        let dir = dir.to_path_buf();
+        tokio::task::spawn_blocking(move || unpack_dir(input.as_ref(), &dir))
+            .await
+            .map_err(std::io::Error::other)??;
+        Ok(())
+    }
+
+    async fn upload_output(
+        &self,
+        task_id: i64,
+        dir: &Path,
+    ) -> Result<(), PlatformError> {
+        let dir = dir.to_path_buf();
+        let output = tokio::task::spawn_blocking(move || {
+            let mut output = Vec::new();
+            pack_dir(&dir, &mut output).map(|_| output)
+        })
+            .await
+            .map_err(std::io::Error::other)??;
+        self.send(
+            task_id,
+            self.request(Method::PUT, &format!("task/{task_id}/output"))
+                .body(output),
+        ).await?;
+        Ok(())
+    }
+
+    async fn complete(
+        &self,
+        task_id: i64,
+        exit_status: i32,
+    ) -> Result<bool, PlatformError> {
+        Ok(self.send(
+            task_id,
+            self.request(Method::POST, &format!("task/{task_id}/complete"))
+                .query(&[("exit_status", exit_status)]),
+        ).await?.json().await?)
+    }
+
+    async fn run(
+        &self,
+        mut claim: TaskClaim,
+        dir: &Path,
+        mut abort_receiver: broadcast::Receiver<()>,
+    ) -> Result<(i32, bool), PlatformError> {
+        let task_id = claim.task.id;
+        // the task may have waited for a permit since it was claimed.
+        claim.lease_ts = self.renew(task_id).await?;
+        let basedir = dir.join("basedir");
+        let checkout = match claim.checkout {
+            Some(_) => {
+                let checkout = dir.join("files");
+                self.fetch_input(task_id, &checkout).await?;
+                Some(checkout)
+            }
+            None => None,
+        };
+        claim.relocate(
+            &basedir.display().to_string(),
+            checkout.as_ref().map(|checkout| checkout.display().to_string()).as_deref(),
+        );
+
+        let code = match TaskProcess::spawn(
+            &claim.task,
+            &claim.task_template,
+            checkout.as_slice(),
+        )? {
+            Some(process) => {
+                // the process is aborted should the lease be lost, as the
+                // task may have been claimed by another runner by then.
+                let (abort_sender, process_abort_receiver) = broadcast::channel(1);
+                let executor = self.clone();
+                let lease_ts = claim.lease_ts;
+                let lease = tokio::spawn(async move {
+                    let lost = tokio::select! {
+                        _ = aborted(&mut abort_receiver) => false,
+                        _ = executor.keep_lease(task_id, lease_ts) => true,
+                    };
+                    let _ = abort_sender.send(());
+                    lost
+                });
+                let code = process.wait(process_abort_receiver).await;
+                lease.abort();
+                if let Ok(true) = lease.await {
+                    Err(CtrlError::TaskNotLeased(task_id))?
+                }
+                code?
+            }
+            None => EXIT_SANDBOX_FAILED,
+        };
+        self.upload_output(task_id, &basedir).await?;
+        Ok((code, self.complete(task_id, code).await?))
+    }
+}
+
+#[async_trait]
+impl traits::Executor for RemoteExecutor {
+    type Error = PlatformError;
+    type Task = TaskClaim;
+
+    async fn start_task(
+        &self,
+    ) -> Result<Option<TaskClaim>, Self::Error> {
+        self.claim().await
+    }
+
+    async fn execute(
+        &self,
+        task: TaskClaim,
+        abort_receiver: broadcast::Receiver<()>,
+    ) -> Result<(i32, bool), Self::Error> {
+        let dir = self.root.join(task.task.id.to_string());
+        // a previous attempt at the task may have left this behind.
+        if tokio::fs::try_exists(&dir).await? {
+            tokio::fs::remove_dir_all(&dir).await?;
+        }
+        let result = self.run(task, &dir, abort_receiver).await;
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+            log::warn!("failed to remove {}: {e}", dir.display());
+        }
+        result
+    }
+
+    async fn recover(
+        &self,
+        _policy: OrphanPolicy,
+    ) -> Result<usize, Self::Error> {
+        // the server returns the tasks with expired leases to the queue,
+        // so there is nothing left behind locally to recover.
+        Ok(0)
+    }
+}
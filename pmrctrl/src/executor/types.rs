@@ -1,6 +1,20 @@
+use std::path::PathBuf;
+
 use crate::platform::Platform;
 
 #[derive(Clone)]
 pub struct Executor {
     pub(crate) platform: Platform,
 }
+
+/// An executor that claims the tasks from a pmrapp server, such that they
+/// are executed on the local host without access to the platform, with
+/// the output of the tasks uploaded back to the server.
+#[derive(Clone)]
+pub struct RemoteExecutor {
+    pub(crate) client: reqwest::Client,
+    pub(crate) server: String,
+    pub(crate) token: String,
+    pub(crate) host: String,
+    pub(crate) root: PathBuf,
+}
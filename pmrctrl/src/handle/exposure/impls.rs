@@ -5,6 +5,10 @@ use parking_lot::{
 use pmrcore::{
     exposure::{
         profile::ExposureFileProfile,
+        task::{
+            ExposureFileViewTaskStatus,
+            traits::ExposureTaskBackend,
+        },
         traits::{
             Exposure,
             ExposureFile,
@@ -14,11 +18,18 @@ use pmrcore::{
         ExposureFileRef,
     },
     idgen::traits::GenAliasBackend,
+    task::{
+        Task,
+        TaskLog,
+        TaskStatus,
+        traits::TaskBackend,
+    },
 };
 use pmrmodel::model::profile::UserPromptGroupRefs;
 use pmrrepo::handle::GitHandle;
 use std::{
     collections::HashMap,
+    io::SeekFrom,
     ops::Deref,
     path::{
         Component,
@@ -30,6 +41,10 @@ use std::{
         OnceLock,
     },
 };
+use tokio::io::{
+    AsyncReadExt,
+    AsyncSeekExt,
+};
 
 use crate::{
     handle::{
@@ -502,6 +517,77 @@ impl<'p> ExposureCtrl<'p> {
         Ok(result)
     }
 
+    /// Lists the task most recently queued to generate every exposure
+    /// file view of this exposure, along with the status of the task.
+    pub async fn view_task_statuses(
+        &self,
+    ) -> Result<Vec<ExposureFileViewTaskStatus>, PlatformError> {
+        let mut statuses = ExposureTaskBackend::list_view_tasks_for_exposure(
+            self.0.platform.mc_platform.as_ref(),
+            self.0.exposure.id(),
+        ).await?;
+        let task_ids = statuses.iter()
+            .filter_map(|status| status.task_id)
+            .collect::<Vec<_>>();
+        let tasks = TaskBackend::list_by_ids(
+            self.0.platform.tm_platform.as_ref(),
+            &task_ids,
+        ).await?
+            .iter()
+            .map(|task| (task.id, TaskStatus::from(task)))
+            .collect::<HashMap<_, _>>();
+        statuses.iter_mut()
+            .for_each(|status| status.task = status.task_id
+                .and_then(|task_id| tasks.get(&task_id).cloned()));
+        Ok(statuses)
+    }
+
+    /// Returns the task only if it is the task most recently queued for
+    /// an exposure file view of this exposure.
+    pub async fn get_view_task(
+        &self,
+        task_id: i64,
+    ) -> Result<Task, PlatformError> {
+        ExposureTaskBackend::list_view_tasks_for_exposure(
+            self.0.platform.mc_platform.as_ref(),
+            self.0.exposure.id(),
+        ).await?
+            .iter()
+            .find(|status| status.task_id == Some(task_id))
+            .ok_or(CtrlError::UnknownTask(task_id))?;
+        self.0.platform.get_task(task_id).await
+    }
+
+    /// Reads up to the final `limit` bytes of the log for a task of an
+    /// exposure file view of this exposure, starting from the first
+    /// complete line.  A log that has yet to be written is empty.
+    pub async fn read_task_log_tail(
+        &self,
+        task_id: i64,
+        log: TaskLog,
+        limit: u64,
+    ) -> Result<String, PlatformError> {
+        let task = self.get_view_task(task_id).await?;
+        let mut file = match tokio::fs::File::open(log.path(&task.basedir)).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(String::new()),
+            Err(e) => Err(e)?,
+        };
+        let len = file.metadata().await?.len();
+        let offset = len.saturating_sub(limit);
+        file.seek(SeekFrom::Start(offset)).await?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).await?;
+        let start = match offset {
+            0 => 0,
+            _ => buf.iter()
+                .position(|b| *b == b'\n')
+                .map(|pos| pos + 1)
+                .unwrap_or(0),
+        };
+        Ok(String::from_utf8_lossy(&buf[start..]).into_owned())
+    }
+
     pub fn exposure(&self) -> &ExposureRef<'p> {
         &self.0.exposure
     }
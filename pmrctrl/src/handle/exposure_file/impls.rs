@@ -14,10 +14,25 @@ use pmrcore::{
         ExposureFileRef,
     },
     profile::ViewTaskTemplateProfile,
+    task::{
+        Task,
+        TaskSchedule,
+    },
     task_template::traits::TaskTemplateBackend,
 };
-use pmrrepo::handle::GitHandleResult;
+use pmrrepo::handle::{
+    GitHandleResult,
+    GitResultTarget,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
 use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
     fmt,
     path::PathBuf,
     sync::Arc,
@@ -229,19 +244,138 @@ impl<'p> ExposureFileCtrl<'p> {
         &self,
         vttc_tasks: Vec<VTTCTask>,
     ) -> Result<Vec<(i64, i64)>, PlatformError> {
-        let mut iter = vttc_tasks.into_iter();
+        self.process_vttc_tasks_with(vttc_tasks, &TaskSchedule::default()).await
+    }
+
+    /// Queue the tasks with the provided schedule applied.
+    ///
+    /// Where the view of a task depends on the view of another task in
+    /// the same batch, the latter will be queued first and the former
+    /// will only be started once the latter has completed successfully.
+    ///
+    /// Where the output of an identical task had been produced before, be
+    /// it for this or any other exposure, the view is restored from that
+    /// cached output instead of having its task queued.
+    ///
+    /// Returns the ExposureFileView.id and Task.id pairs in the order the
+    /// tasks were queued; views restored from the cache are not included.
+    pub async fn process_vttc_tasks_with(
+        &self,
+        vttc_tasks: Vec<VTTCTask>,
+        schedule: &TaskSchedule,
+    ) -> Result<Vec<(i64, i64)>, PlatformError> {
+        let batch = vttc_tasks.iter()
+            .map(|vttc_task| vttc_task.view_key.clone())
+            .collect::<HashSet<_>>();
+        let mut pending = vttc_tasks;
+        // the task queued and the cache key for each view processed.
+        let mut processed: HashMap<String, (Option<i64>, Option<String>)> = HashMap::new();
         let mut results: Vec<(i64, i64)> = Vec::new();
         // TODO determine if benefits of sequential insertion is
         // actually required here.
-        while let Some(vttc_task) = iter.next() {
+        while !pending.is_empty() {
+            let idx = pending.iter()
+                .position(|vttc_task| vttc_task.depends_on
+                    .as_ref()
+                    .is_none_or(|key| !batch.contains(key) || processed.contains_key(key))
+                )
+                .unwrap_or_else(|| {
+                    log::warn!(
+                        "circular view dependency for exposure file {}; \
+                        queuing view {} without it",
+                        self.0.exposure_file.id(),
+                        pending[0].view_key,
+                    );
+                    0
+                });
+            let mut vttc_task = pending.remove(idx);
+            schedule.apply(&mut vttc_task.task);
+            vttc_task.task.depends_on = vttc_task.depends_on
+                .as_ref()
+                .and_then(|key| processed.get(key))
+                .and_then(|(task_id, _)| *task_id)
+                .into_iter()
+                .collect();
+            // the output of a view may only be cached where the output
+            // of the view it depends on was also cached.
+            let cache_key = match vttc_task.depends_on.as_ref() {
+                None => self.view_cache_key(&vttc_task.task, None).await?,
+                Some(key) => match processed.get(key) {
+                    Some((_, Some(dependency_key))) => self.view_cache_key(
+                        &vttc_task.task,
+                        Some(dependency_key),
+                    ).await?,
+                    _ => None,
+                },
+            };
+            let view_key = vttc_task.view_key.clone();
             let mut efv_ctrl = self.ensure_view_from_template(
                 vttc_task.view_task_template_id
             ).await?;
-            results.push(efv_ctrl.queue_task(vttc_task).await?);
+            let task_id = match cache_key.as_deref() {
+                Some(cache_key) if self.0.platform.restore_view_output(
+                    cache_key,
+                    &vttc_task.task.basedir,
+                ).await? => {
+                    let efv_id = efv_ctrl.use_cached_output(vttc_task).await?;
+                    log::debug!(
+                        "ExposureFileView:{efv_id} restored from view cache key {cache_key}"
+                    );
+                    None
+                }
+                _ => {
+                    let result = efv_ctrl.queue_task(vttc_task).await?;
+                    if let Some(cache_key) = cache_key.as_deref() {
+                        self.0.platform.track_view_output(result.1, cache_key).await?;
+                    }
+                    results.push(result);
+                    Some(result.1)
+                }
+            };
+            processed.insert(view_key, (task_id, cache_key));
         }
         Ok(results)
     }
 
+    // The key for caching the output of the task for a view of this file,
+    // derived from the version of its task template, its arguments with
+    // the locations specific to this exposure substituted, the blob of
+    // this file, the tree of the exposure commit and the cache key of the
+    // view it depends on.  Returns None where these cannot be determined.
+    async fn view_cache_key(
+        &self,
+        task: &Task,
+        depends_on: Option<&str>,
+    ) -> Result<Option<String>, PlatformError> {
+        let Some(GitResultTarget::Object(blob)) = self.0.pathinfo.target() else {
+            return Ok(None);
+        };
+        let Some(tree) = self.0.pathinfo.commit_tree_id() else {
+            return Ok(None);
+        };
+        let task_template = TaskTemplateBackend::get_task_template_by_id(
+            self.0.platform.tm_platform.as_ref(),
+            task.task_template_id,
+        ).await?;
+        let mut task = task.clone();
+        let basedir = std::mem::take(&mut task.basedir);
+        let checkout = self.0.exposure.data_root().join("files");
+        task.relocate_args(&[
+            (&basedir, "$basedir"),
+            (&checkout.display().to_string(), "$checkout"),
+        ]);
+        let material = serde_json::json!({
+            "task_template_id": task_template.id,
+            "version_id": task_template.version_id,
+            "bin_path": task.bin_path,
+            "args": task.args.as_ref().map(Vec::<&str>::from),
+            "tree": tree,
+            "blob": blob.object.id.to_string(),
+            "depends_on": depends_on,
+        });
+        Ok(Some(hex::encode(Sha256::digest(material.to_string()))))
+    }
+
     pub async fn profile(&self) -> Result<Option<ExposureFileProfile>, PlatformError> {
         Ok(ExposureFileProfileBackend::get_ef_profile(
             self.0.platform.mc_platform.as_ref(),
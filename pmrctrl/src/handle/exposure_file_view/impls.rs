@@ -5,6 +5,7 @@ use pmrcore::{
         traits::{
             ExposureFile as _,
             ExposureFileView as _,
+            ExposureFileViewBackend,
         },
     },
     task::{
@@ -90,6 +91,32 @@ impl<'p> ExposureFileViewCtrl<'p> {
         Ok((efv_id, task.id))
     }
 
+    /// Use the output already restored into the basedir of a Task created
+    /// by ViewTaskTemplateCtrl for this view, in place of queuing it.
+    ///
+    /// Returns the newly created ExposureFileView.id
+    pub(crate) async fn use_cached_output(
+        &mut self,
+        vttc_task: VTTCTask,
+    ) -> Result<i64, PlatformError> {
+        let mcp = self.platform.mc_platform.as_ref();
+        let efv_id = ExposureTaskBackend::create_task_for_view(
+            mcp,
+            self.exposure_file_view.id(),
+            vttc_task.view_task_template_id,
+            None,
+        ).await?;
+        self.exposure_file_view
+            .update_exposure_file_view_task_id(Some(efv_id))
+            .await?;
+        ExposureFileViewBackend::update_view_key(
+            mcp,
+            self.exposure_file_view.id(),
+            Some(&vttc_task.view_key),
+        ).await?;
+        Ok(efv_id)
+    }
+
     pub async fn read_blob(&self, path: &str) -> Result<Vec<u8>, CtrlError> {
         let view_key = self.exposure_file_view
             .view_key()
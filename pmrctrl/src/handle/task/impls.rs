@@ -1,6 +1,7 @@
 use pmrcore::{
     task::TaskRef,
 };
+use tokio::sync::broadcast;
 
 use crate::{
     error::PlatformError,
@@ -19,10 +20,26 @@ impl<'p> TaskExecutorCtrl<'p> {
         }
     }
 
-    pub async fn execute(mut self) -> Result<(i32, bool), PlatformError> {
-        let (exit_status, _) = self.executor.execute().await?;
-        Ok((exit_status, self.platform.complete_task(
-            self.executor.into(),
+    pub async fn execute(self) -> Result<(i32, bool), PlatformError> {
+        // the sender is dropped immediately, so this will never abort.
+        let (_, abort_receiver) = broadcast::channel(1);
+        self.execute_abortable(abort_receiver).await
+    }
+
+    pub async fn execute_abortable(
+        mut self,
+        abort_receiver: broadcast::Receiver<()>,
+    ) -> Result<(i32, bool), PlatformError> {
+        if let Some(checkout) = self.platform.task_checkout(
+            self.executor.task().id(),
+        ).await? {
+            self.executor.add_read_only(checkout);
+        }
+        // the executor has completed the task, which may have returned
+        // it to the queue for a retry, so only the view is finalized.
+        let (exit_status, _) = self.executor.execute_abortable(abort_receiver).await?;
+        Ok((exit_status, self.platform.finalize_task(
+            self.executor.task().id(),
             exit_status.into(),
         ).await?))
     }
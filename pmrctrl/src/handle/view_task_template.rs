@@ -47,6 +47,10 @@ pub(crate) struct EFViewTaskTemplateCtrl<'p> {
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct VTTCTask {
     pub(crate) view_task_template_id: i64,
+    #[serde(default)]
+    pub(crate) view_key: String,
+    #[serde(default)]
+    pub(crate) depends_on: Option<String>,
     pub(crate) task: Task,
 }
 
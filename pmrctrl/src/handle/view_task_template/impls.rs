@@ -16,7 +16,7 @@ use pmrmodel::{
     model::{
         profile::UserPromptGroupRefs,
         task_template::{
-            TaskArgBuilder,
+            TaskArgBuilders,
             TaskBuilder,
             UserArgBuilder,
             UserArgRefs,
@@ -46,6 +46,7 @@ use crate::{
         EFViewTaskTemplatesCtrl,
         view_task_template::EFViewTaskTemplateCtrl,
     },
+    registry::ExposureFileChoiceContext,
 };
 use super::VTTCTask;
 
@@ -70,9 +71,25 @@ impl<'p> EFViewTaskTemplatesCtrl<'p> {
         Ok(match self.choice_registry.get() {
             Some(registry) => Ok::<_, PlatformError>(registry),
             None => {
-                self.choice_registry.set(Arc::new(
-                    (&self.exposure_file_ctrl).try_into()?
-                )).unwrap_or_else(|_| log::warn!(
+                // the registries for the sources referenced by the task
+                // templates are prepared alongside the defaults, as only
+                // these would provide the parameters for the providers.
+                let context = ExposureFileChoiceContext::try_from(
+                    &self.exposure_file_ctrl
+                )?;
+                let providers = self.exposure_file_ctrl.0.platform
+                    .choice_registry_providers();
+                let registry = providers.prepare(
+                    &context,
+                    providers.default_sources().chain(self.view_task_templates
+                        .iter()
+                        .filter_map(|vtt| vtt.task_template.as_ref())
+                        .filter_map(|task_template| task_template.args.as_ref())
+                        .flat_map(|args| args.iter())
+                        .filter_map(|arg| arg.choice_source.as_deref())
+                    ),
+                )?;
+                self.choice_registry.set(Arc::new(registry)).unwrap_or_else(|_| log::warn!(
                     "concurrent call to the same \
                     ViewTaskTemplateCtrls.registry_cache()"
                 ));
@@ -222,12 +239,12 @@ impl<'p> EFViewTaskTemplatesCtrl<'p> {
             let arg = self.get_arg(&arg_id)
                 .ok_or(CtrlError::ArgIdNotInProfile(*arg_id))?;
 
-            match TaskArgBuilder::try_from((
-                Some(answer.as_ref()),
+            match TaskArgBuilders::try_from((
+                Some(answer),
                 arg,
                 self.get_registry_cache()?,
             )) {
-                Ok(_) => checked_user_input.insert(*arg_id, answer.to_string()),
+                Ok(_) => checked_user_input.insert(*arg_id, answer.clone()),
                 Err(_) => None,
             };
         }
@@ -343,6 +360,8 @@ impl<'p> EFViewTaskTemplateCtrl<'p> {
           //     .to_string();
           Ok(VTTCTask {
               view_task_template_id: self.efvtt.id,
+              view_key: self.efvtt.view_key.clone(),
+              depends_on: self.efvtt.depends_on.clone(),
               task: task,
           })
     }
@@ -357,6 +376,16 @@ for
     }
 }
 
+impl VTTCTask {
+    pub fn view_key(&self) -> &str {
+        &self.view_key
+    }
+
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+}
+
 impl From<VTTCTask> for (i64, Task) {
     fn from(item: VTTCTask) -> Self {
         (
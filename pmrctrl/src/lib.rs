@@ -3,3 +3,4 @@ pub mod executor;
 pub mod handle;
 pub mod platform;
 pub mod registry;
+pub mod scheduler;
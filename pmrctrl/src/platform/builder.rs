@@ -4,7 +4,10 @@ use std::{
     fs,
 };
 use clap::{ArgAction, Parser};
-use pmrac::platform::Builder as ACPlatformBuilder;
+use pmrac::{
+    mail::MailTransport,
+    platform::Builder as ACPlatformBuilder,
+};
 use pmrcore::{
     index::{
         traits::{IndexBackend, IndexDBBackend},
@@ -23,6 +26,13 @@ use pmrrbac::Builder as PmrRbacBuilder;
 
 use super::Platform;
 
+#[cfg(feature = "oidc")]
+#[derive(serde::Deserialize)]
+struct OidcProviders {
+    #[serde(default)]
+    provider: Vec<pmrac::oidc::ProviderConfig>,
+}
+
 #[derive(Clone, Debug, Default, Parser)]
 pub struct Builder {
     #[clap(
@@ -49,6 +59,26 @@ pub struct Builder {
     pub pmr_repo_root: String,
     #[clap(long, value_name = "PMRAC_DB_URL", env = "PMRAC_DB_URL")]
     pub pmrac_db_url: String,
+    /// The transport for the mail sent for the verification of email
+    /// addresses, either `stdout` or `file:<path>`; self-service account
+    /// registration is unavailable without it.
+    #[clap(long, value_name = "PMRAC_MAIL_TRANSPORT", env = "PMRAC_MAIL_TRANSPORT")]
+    pub pmrac_mail_transport: Option<String>,
+    /// The URL to the page for the email bind requests, to which their
+    /// tokens are appended to form the links sent in the mail, e.g.
+    /// `https://example.com/auth/email_bindreq?token=`.
+    #[clap(long, value_name = "PMRAC_EMAIL_BINDREQ_URL", env = "PMRAC_EMAIL_BINDREQ_URL")]
+    pub pmrac_email_bindreq_url: Option<String>,
+    /// The URL to the page for the password reset requests, to which their
+    /// tokens are appended to form the links sent in the mail, e.g.
+    /// `https://example.com/auth/password_resetreq?token=`.
+    #[clap(long, value_name = "PMRAC_PASSWORD_RESETREQ_URL", env = "PMRAC_PASSWORD_RESETREQ_URL")]
+    pub pmrac_password_resetreq_url: Option<String>,
+    /// The path to the TOML file with the OpenID Connect providers that
+    /// users may log in with, each specified as a `[[provider]]` table.
+    #[cfg(feature = "oidc")]
+    #[clap(long, value_name = "PMRAC_OIDC_PROVIDERS", env = "PMRAC_OIDC_PROVIDERS")]
+    pub pmrac_oidc_providers: Option<String>,
     #[clap(long, value_name = "PMRAPP_DB_URL", env = "PMRAPP_DB_URL")]
     pub pmrapp_db_url: String,
     #[clap(long, value_name = "PMRPC_DB_URL", env = "PMRPC_DB_URL")]
@@ -89,6 +119,27 @@ impl Builder {
         self
     }
 
+    pub fn pmrac_mail_transport(mut self, value: String) -> Self {
+        self.pmrac_mail_transport = Some(value);
+        self
+    }
+
+    pub fn pmrac_email_bindreq_url(mut self, value: String) -> Self {
+        self.pmrac_email_bindreq_url = Some(value);
+        self
+    }
+
+    pub fn pmrac_password_resetreq_url(mut self, value: String) -> Self {
+        self.pmrac_password_resetreq_url = Some(value);
+        self
+    }
+
+    #[cfg(feature = "oidc")]
+    pub fn pmrac_oidc_providers(mut self, value: String) -> Self {
+        self.pmrac_oidc_providers = Some(value);
+        self
+    }
+
     pub fn pmrapp_db_url(mut self, value: String) -> Self {
         self.pmrapp_db_url = value;
         self
@@ -105,7 +156,28 @@ impl Builder {
     }
 
     pub async fn build(self) -> Result<Platform, Box<dyn Error + Send + Sync>> {
-        let ac_platform = ACPlatformBuilder::new()
+        let mut ac_platform_builder = ACPlatformBuilder::new();
+        if let Some(mail_transport) = self.pmrac_mail_transport.as_deref() {
+            ac_platform_builder = ac_platform_builder
+                .boxed_mail_transport(mail_transport.parse::<Box<dyn MailTransport>>()?);
+        }
+        if let Some(email_bindreq_url) = self.pmrac_email_bindreq_url {
+            ac_platform_builder = ac_platform_builder
+                .email_bindreq_url(email_bindreq_url);
+        }
+        if let Some(password_resetreq_url) = self.pmrac_password_resetreq_url {
+            ac_platform_builder = ac_platform_builder
+                .password_resetreq_url(password_resetreq_url);
+        }
+        #[cfg(feature = "oidc")]
+        if let Some(path) = self.pmrac_oidc_providers.as_deref() {
+            let config: OidcProviders = toml::from_str(&fs::read_to_string(path)?)?;
+            for provider in config.provider {
+                ac_platform_builder = ac_platform_builder
+                    .oidc_provider(provider);
+            }
+        }
+        let ac_platform = ac_platform_builder
             .boxed_ac_platform(
                 Backend::ac(
                     ConnectorOption::from(&self.pmrac_db_url)
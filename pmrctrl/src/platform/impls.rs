@@ -6,6 +6,7 @@ use pmrcore::{
         TMPlatform,
     },
 };
+use parking_lot::RwLock;
 use pmrrepo::backend::Backend;
 use std::{
     fmt,
@@ -16,7 +17,10 @@ use std::{
     sync::Arc,
 };
 
-use crate::platform::Platform;
+use crate::{
+    platform::Platform,
+    registry::default_providers,
+};
 
 impl Platform {
     pub(crate) fn new(
@@ -38,6 +42,7 @@ impl Platform {
             data_root,
             repo_root,
             repo_backend,
+            choice_registry_providers: Arc::new(RwLock::new(default_providers())),
         }
     }
 
@@ -65,7 +70,9 @@ impl fmt::Debug for Platform {
 
 mod ac;
 mod alias;
+mod cache;
 mod exposure;
 mod profile;
+mod registry;
 mod task;
 mod workspace;
@@ -1,29 +1,38 @@
+use pmrac::agent::ScopedAgent;
 use pmrcore::{
     ac::{
+        agent::Agent,
         genpolicy::Policy,
-        user::User,
+        role::Role,
         workflow::{
             State,
             state::transition::StateTransitions,
         },
     },
+    listing::Listing,
 };
+use std::collections::HashSet;
 use crate::{
     error::PlatformError,
     platform::Platform,
 };
 
 impl Platform {
-    /// Attempt to make a workflow transition using `User` on a `resource` to some target `State`.
+    /// Attempt to make a workflow transition using the agent on a `resource` to some target `State`.
     ///
-    /// Returns the new `Policy` based on what the `User` may do on the `resource`, if the user has the
-    /// permission to effect the transiation, otherwise `None` is returned.
+    /// Returns the new `Policy` based on what the agent may do on the `resource`, if the agent has the
+    /// permission to effect the transiation, otherwise `None` is returned.  Where the agent is limited
+    /// by scopes, the `"workflow"` action must be within those scopes.
     pub async fn workflow_transition(
         &self,
-        user: &User,
+        agent: impl Into<ScopedAgent>,
         resource: String,
         target_state: State,
     ) -> Result<Option<Policy>, PlatformError> {
+        let agent = agent.into();
+        if !agent.in_scope("workflow") {
+            return Ok(None);
+        }
         let transitions = StateTransitions::default();
 
         let state = self
@@ -32,7 +41,7 @@ impl Platform {
             .await?;
         let roles = self
             .ac_platform
-            .generate_policy_for_agent_res(&(user.clone().into()), resource.clone())
+            .generate_policy_for_agent_res(agent.agent(), resource.clone())
             .await?
             .to_roles();
         if transitions.validate(roles, state, target_state) {
@@ -57,7 +66,7 @@ impl Platform {
 
             let policy = self
                 .ac_platform
-                .generate_policy_for_agent_res(&(user.clone()).into(), resource)
+                .generate_policy_for_agent_res(agent.agent(), resource)
                 .await?;
             Ok(Some(policy))
         } else {
@@ -65,3 +74,69 @@ impl Platform {
         }
     }
 }
+
+// Parse the id out of a resource path in the form of `{prefix}{id}/`.
+fn res_to_id(prefix: &str, res: &str) -> Option<i64> {
+    res.strip_prefix(prefix)?
+        .strip_suffix('/')?
+        .parse()
+        .ok()
+}
+
+impl Platform {
+    /// Resolve the `state` and `owner` filters of the `listing` into the
+    /// ids of the resources under `prefix` (e.g. `/workspace/`) that
+    /// satisfy every one of them.
+    ///
+    /// Returns `None` if neither filter is present, and an unknown owner
+    /// will result in no ids.
+    pub(crate) async fn resolve_listing_ids(
+        &self,
+        prefix: &str,
+        listing: &Listing,
+    ) -> Result<Option<Vec<i64>>, PlatformError> {
+        let mut result: Option<Vec<i64>> = None;
+        if let Some(state) = listing.state {
+            result = Some(self.ac_platform
+                .list_res_with_wf_state(state, prefix)
+                .await?
+                .iter()
+                .filter_map(|res| res_to_id(prefix, res))
+                .collect());
+        }
+        if let Some(owner) = listing.owner.as_deref() {
+            let ids = match self.ac_platform.backend().get_user_by_name(owner).await? {
+                Some(user) => self.ac_platform
+                    .get_res_grants_for_agent(&Agent::User(user))
+                    .await?
+                    .into_iter()
+                    .filter(|(_, roles)| roles.contains(&Role::Owner))
+                    .filter_map(|(res, _)| res_to_id(prefix, &res))
+                    .collect::<HashSet<_>>(),
+                None => HashSet::new(),
+            };
+            result = Some(match result {
+                Some(state_ids) => state_ids.into_iter()
+                    .filter(|id| ids.contains(id))
+                    .collect(),
+                None => ids.into_iter().collect(),
+            });
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::res_to_id;
+
+    #[test]
+    fn test_res_to_id() {
+        assert_eq!(res_to_id("/workspace/", "/workspace/1/"), Some(1));
+        assert_eq!(res_to_id("/workspace/", "/workspace/12/"), Some(12));
+        assert_eq!(res_to_id("/workspace/", "/workspace/"), None);
+        assert_eq!(res_to_id("/workspace/", "/workspace/1"), None);
+        assert_eq!(res_to_id("/workspace/", "/workspace/1/file"), None);
+        assert_eq!(res_to_id("/workspace/", "/exposure/1/"), None);
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use crate::{
+    error::PlatformError,
+    platform::Platform,
+};
+
+// The outputs of the view tasks are stored by their cache keys under the
+// `cache/view` directory of the data root, while the keys for the tasks
+// that have yet to complete are stored by the task id under `task`.
+impl Platform {
+    fn view_cache_root(&self) -> PathBuf {
+        let mut result = self.data_root.join("cache");
+        result.push("view");
+        result
+    }
+
+    /// Returns the directory where the view output for the cache key is
+    /// stored, whether or not it exists.
+    pub fn view_cache_dir(&self, key: &str) -> PathBuf {
+        let mut result = self.view_cache_root();
+        result.push(&key[..2]);
+        result.push(key);
+        result
+    }
+
+    /// Restore the view output stored under the cache key into the
+    /// basedir, replacing its existing content.  Returns whether there
+    /// was an output to restore.
+    pub(crate) async fn restore_view_output(
+        &self,
+        key: &str,
+        basedir: &str,
+    ) -> Result<bool, PlatformError> {
+        let source = self.view_cache_dir(key);
+        let target = PathBuf::from(basedir);
+        Ok(tokio::task::spawn_blocking(move || {
+            if !source.is_dir() {
+                return Ok(false);
+            }
+            if target.exists() {
+                fs::remove_dir_all(&target)?;
+            }
+            copy_dir(&source, &target)?;
+            Ok::<_, io::Error>(true)
+        })
+            .await
+            .map_err(io::Error::other)??
+        )
+    }
+
+    /// Track the cache key for the queued task, such that its output will
+    /// be stored under that key once the task completes successfully.
+    pub(crate) async fn track_view_output(
+        &self,
+        task_id: i64,
+        key: &str,
+    ) -> Result<(), PlatformError> {
+        let mut path = self.view_cache_root();
+        path.push("task");
+        tokio::fs::create_dir_all(&path).await?;
+        path.push(task_id.to_string());
+        tokio::fs::write(&path, key).await?;
+        Ok(())
+    }
+
+    /// Store the output found at the basedir of the completed task under
+    /// the cache key tracked for it, if any.
+    pub(crate) async fn store_view_output(
+        &self,
+        task_id: i64,
+        basedir: &str,
+    ) -> Result<(), PlatformError> {
+        let root = self.view_cache_root();
+        let tracked = root.join("task").join(task_id.to_string());
+        let key = match tokio::fs::read_to_string(&tracked).await {
+            Ok(key) => key,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => Err(e)?,
+        };
+        let target = self.view_cache_dir(&key);
+        let staging = root.join("staging").join(task_id.to_string());
+        let source = PathBuf::from(basedir);
+        tokio::task::spawn_blocking(move || {
+            if target.exists() {
+                return Ok(());
+            }
+            if staging.exists() {
+                fs::remove_dir_all(&staging)?;
+            }
+            copy_dir(&source, &staging)?;
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // the output is only made available once it has been copied
+            // in full; if another task with the same key got there first
+            // that output is kept.
+            if let Err(e) = fs::rename(&staging, &target) {
+                fs::remove_dir_all(&staging)?;
+                if !target.exists() {
+                    return Err(e);
+                }
+            }
+            Ok::<_, io::Error>(())
+        })
+            .await
+            .map_err(io::Error::other)??;
+        tokio::fs::remove_file(&tracked).await?;
+        log::debug!("Task:{task_id} output stored under view cache key {key}");
+        Ok(())
+    }
+
+    /// Stop tracking the cache key for the task, as done for the tasks
+    /// that did not complete successfully such that the key is not left
+    /// behind.
+    pub(crate) async fn discard_view_output(
+        &self,
+        task_id: i64,
+    ) -> Result<(), PlatformError> {
+        let tracked = self.view_cache_root().join("task").join(task_id.to_string());
+        match tokio::fs::remove_file(&tracked).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e)?,
+            _ => Ok(()),
+        }
+    }
+}
+
+// Copy the directory recursively; symlinks are not copied as these may
+// reference locations outside of the directory.
+fn copy_dir(source: &Path, target: &Path) -> io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target = target.join(entry.file_name());
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
@@ -1,11 +1,31 @@
 use pmrcore::{
-    exposure::traits::{
-        Exposure,
-        ExposureBackend,
+    exposure::{
+        self,
+        task::{
+            ExposureFileViewBuild,
+            traits::ExposureTaskBackend,
+        },
+        traits::{
+            Exposure,
+            ExposureBackend,
+        },
+    },
+    listing::{
+        Listing,
+        Page,
+    },
+    task::{
+        TaskSchedule,
+        traits::TaskBackend,
     },
     workspace::traits::Workspace as _,
 };
 
+use std::collections::{
+    BTreeSet,
+    HashMap,
+};
+
 use crate::{
     error::PlatformError,
     handle::ExposureCtrl,
@@ -13,6 +33,19 @@ use crate::{
 };
 
 impl<'p> Platform {
+    /// List a page of exposures, with the access control filters of the
+    /// `listing` resolved.
+    pub async fn list_exposures_page(
+        &self,
+        listing: &Listing,
+    ) -> Result<Page<exposure::Exposure>, PlatformError> {
+        let ids = self.resolve_listing_ids("/exposure/", listing).await?;
+        Ok(ExposureBackend::list_page(
+            self.mc_platform.as_ref(),
+            &listing.to_query(ids),
+        ).await?)
+    }
+
     /// Creates an exposure with all the relevant data validated.
     ///
     /// Returns a `ExposureCtrl` handle.
@@ -82,6 +115,16 @@ impl Platform {
     ///
     /// Returns the number of tasks queued.
     pub async fn process_vttc_tasks_for_exposure(&self, id: i64) -> Result<usize, PlatformError> {
+        self.process_vttc_tasks_for_exposure_with(id, &TaskSchedule::default()).await
+    }
+
+    /// As process_vttc_tasks_for_exposure, but with the provided schedule
+    /// applied to all the tasks being queued.
+    pub async fn process_vttc_tasks_for_exposure_with(
+        &self,
+        id: i64,
+        schedule: &TaskSchedule,
+    ) -> Result<usize, PlatformError> {
         let exposure = self.get_exposure(id).await?;
         let mut args = Vec::new();
         for efvttc in exposure.list_files_efvttcs().await? {
@@ -98,7 +141,67 @@ impl Platform {
                 let vttc_tasks = efvttc.create_tasks_from_input(&profile.user_input)?;
                 result += efvttc
                     .exposure_file_ctrl()
-                    .process_vttc_tasks(vttc_tasks).await?
+                    .process_vttc_tasks_with(vttc_tasks, schedule).await?
+                    .len();
+            }
+        }
+        Ok(result)
+    }
+
+    /// List the exposure file views that were built by tasks from a task
+    /// template other than the one currently linked to their view task
+    /// template, such as after the task template has been superseded,
+    /// optionally only for the view task templates linked to the task
+    /// template.  Views with no known task template, such as those that
+    /// were restored from the cache, are also listed.
+    pub async fn list_outdated_views(
+        &self,
+        task_template_id: Option<i64>,
+    ) -> Result<Vec<ExposureFileViewBuild>, PlatformError> {
+        let mut builds = ExposureTaskBackend::list_view_builds(
+            self.mc_platform.as_ref(),
+            task_template_id,
+        ).await?;
+        let task_ids = builds.iter()
+            .filter_map(|build| build.task_id)
+            .collect::<Vec<_>>();
+        let tasks = TaskBackend::list_by_ids(
+            self.tm_platform.as_ref(),
+            &task_ids,
+        ).await?
+            .iter()
+            .map(|task| (task.id, task.task_template_id))
+            .collect::<HashMap<_, _>>();
+        builds.iter_mut()
+            .for_each(|build| build.built_task_template_id = build.task_id
+                .and_then(|task_id| tasks.get(&task_id).copied()));
+        builds.retain(ExposureFileViewBuild::is_outdated);
+        Ok(builds)
+    }
+
+    /// Queue the tasks for the exposure files with outdated views as
+    /// listed by list_outdated_views, with the user input from their
+    /// profiles and the provided schedule applied.  The views of these
+    /// files that are not outdated may be restored from the cache.
+    ///
+    /// Returns the number of tasks queued.
+    pub async fn rebuild_outdated_views_with(
+        &self,
+        task_template_id: Option<i64>,
+        schedule: &TaskSchedule,
+    ) -> Result<usize, PlatformError> {
+        let files = self.list_outdated_views(task_template_id).await?
+            .into_iter()
+            .map(|build| (build.exposure_id, build.workspace_file_path))
+            .collect::<BTreeSet<_>>();
+        let mut result = 0;
+        for (exposure_id, path) in files.iter() {
+            let exposure = self.get_exposure(*exposure_id).await?;
+            let efc = exposure.ctrl_path(path).await?;
+            if let Some(profile) = efc.profile().await? {
+                let efvttsc = efc.build_vttc().await?;
+                let vttc_tasks = efvttsc.create_tasks_from_input(&profile.user_input)?;
+                result += efc.process_vttc_tasks_with(vttc_tasks, schedule).await?
                     .len();
             }
         }
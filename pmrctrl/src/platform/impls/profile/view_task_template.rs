@@ -6,7 +6,10 @@ use pmrcore::{
     task_template::traits::TaskTemplateBackend
 };
 use crate::{
-    error::PlatformError,
+    error::{
+        CtrlError,
+        PlatformError,
+    },
     platform::Platform,
 };
 
@@ -30,6 +33,13 @@ impl Platform {
             &view_task_template.description,
             task_template_id,
         ).await?;
+        if let Some(depends_on) = view_task_template.depends_on.as_deref() {
+            ViewTaskTemplateBackend::set_view_task_template_depends_on(
+                self.mc_platform.as_ref(),
+                result,
+                Some(depends_on),
+            ).await?;
+        }
         Ok(result)
     }
 
@@ -50,6 +60,39 @@ impl Platform {
         result.task_template = Some(task_template);
         Ok(result)
     }
+
+    /// Supersede the task template with the newer version identified by
+    /// `superceded_by_id`, then migrate the view task templates linked to
+    /// the task template over to the newer version, such that the
+    /// profiles and exposure files referencing them will use the newer
+    /// version for the tasks that follow.  A task template that is
+    /// already superceded by the same version is migrated again.
+    ///
+    /// Returns the ids of the view task templates migrated.
+    pub async fn supersede_task_template(
+        &self,
+        id: i64,
+        superceded_by_id: i64,
+    ) -> Result<Vec<i64>, PlatformError> {
+        let task_template = TaskTemplateBackend::get_task_template_by_id(
+            self.tm_platform.as_ref(),
+            id,
+        ).await?;
+        if task_template.superceded_by_id != Some(superceded_by_id)
+            && !TaskTemplateBackend::supersede_task_template(
+                self.tm_platform.as_ref(),
+                id,
+                superceded_by_id,
+            ).await?
+        {
+            Err(CtrlError::TaskTemplateSupersession(id, superceded_by_id))?;
+        }
+        Ok(ViewTaskTemplateBackend::migrate_view_task_templates(
+            self.mc_platform.as_ref(),
+            id,
+            superceded_by_id,
+        ).await?)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,63 @@
+use parking_lot::RwLockReadGuard;
+use pmrcore::task_template::TaskTemplate;
+use pmrmodel::{
+    error::TaskTemplateErrors,
+    model::task_template,
+    registry::ChoiceRegistryProvider,
+};
+
+use crate::{
+    error::PlatformError,
+    platform::Platform,
+    registry::{
+        ExposureFileChoiceContext,
+        ExposureFileChoiceProviders,
+        VIEW_CHOICE_SOURCES,
+    },
+};
+
+impl Platform {
+    /// Register the choice registry provider under its name, such that
+    /// the `choice_source` of task template arguments may reference it.
+    /// The providers are shared by all clones of this platform.
+    pub fn register_choice_registry_provider(
+        &self,
+        provider: impl ChoiceRegistryProvider<
+            ExposureFileChoiceContext,
+            PlatformError,
+        > + 'static,
+    ) {
+        self.choice_registry_providers.write().register(provider);
+    }
+
+    pub fn choice_registry_providers(
+        &self,
+    ) -> RwLockReadGuard<'_, ExposureFileChoiceProviders> {
+        self.choice_registry_providers.read()
+    }
+
+    /// The names of all the registries that may be referenced by the
+    /// `choice_source` of task template arguments.
+    pub fn choice_sources(&self) -> Vec<String> {
+        self.choice_registry_providers()
+            .names()
+            .into_iter()
+            .chain(VIEW_CHOICE_SOURCES.iter().copied())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Validate the task template for use with the platform, which includes
+    /// checking that the `choice_source` of the arguments reference one of
+    /// the `choice_sources`.
+    pub fn validate_task_template(
+        &self,
+        task_template: &TaskTemplate,
+    ) -> Result<(), TaskTemplateErrors> {
+        let choice_sources = self.choice_sources();
+        task_template::validate_task_template(
+            task_template,
+            Some(&choice_sources.iter().map(String::as_str).collect::<Vec<_>>()),
+        )
+    }
+}
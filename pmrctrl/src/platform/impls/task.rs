@@ -1,18 +1,41 @@
 use pmrcore::{
     task::{
         Task,
+        TaskClaim,
         TaskRef,
         traits::TaskBackend,
     },
-    exposure::task::traits::ExposureTaskBackend,
+    task_template::traits::TaskTemplateBackend,
+    exposure::{
+        task::traits::ExposureTaskBackend,
+        traits::{
+            ExposureFileBackend,
+            ExposureFileViewBackend,
+        },
+    },
+};
+use std::{
+    io::Read,
+    path::PathBuf,
 };
 use crate::{
-    error::PlatformError,
+    error::{
+        CtrlError,
+        PlatformError,
+    },
+    executor::unpack_dir,
     handle::TaskExecutorCtrl,
     platform::Platform,
 };
 
 impl<'p> Platform {
+    pub async fn get_task(
+        &self,
+        task_id: i64,
+    ) -> Result<Task, PlatformError> {
+        Ok(TaskBackend::gets_task(self.tm_platform.as_ref(), task_id).await?)
+    }
+
     pub async fn adds_task(
         &self,
         task: Task,
@@ -36,16 +59,162 @@ impl<'p> Platform {
         exit_status: i64,
     ) -> Result<bool, PlatformError> {
         task.complete(exit_status).await?;
+        self.finalize_task(task.id(), exit_status).await
+    }
+
+    /// Claim the next task for the remote runner identified by host,
+    /// leasing it to the runner for the duration in seconds.  The tasks
+    /// with leases that have expired are returned to the queue first, so
+    /// that these may be claimed again.
+    pub async fn claim_task(
+        &self,
+        host: &str,
+        duration: i64,
+    ) -> Result<Option<TaskClaim>, PlatformError> {
+        let tm_platform = self.tm_platform.as_ref();
+        for id in TaskBackend::requeue_expired_leases(tm_platform).await? {
+            log::warn!("Task:{id} returned to the queue as its lease expired");
+        }
+        let Some(task) = TaskBackend::start(tm_platform).await? else {
+            return Ok(None);
+        };
+        let Some(lease_ts) = TaskBackend::lease(tm_platform, task.id, host, duration).await? else {
+            // not expected for a task that was just started, but it must
+            // not be left behind without a lease.
+            TaskBackend::requeue(tm_platform, task.id).await?;
+            return Ok(None);
+        };
+        let task_template = TaskTemplateBackend::get_task_template_by_id(
+            tm_platform,
+            task.task_template_id,
+        ).await?;
+        let checkout = self.task_checkout(task.id).await?
+            .map(|checkout| checkout.display().to_string());
+        log::debug!("Task:{} leased to {host} until {lease_ts}", task.id);
+        Ok(Some(TaskClaim {
+            task,
+            task_template,
+            lease_ts,
+            checkout,
+        }))
+    }
+
+    /// Renew the lease on the task held by the remote runner identified
+    /// by host, returning the timestamp the lease now expires at.
+    pub async fn renew_task_lease(
+        &self,
+        task_id: i64,
+        host: &str,
+        duration: i64,
+    ) -> Result<i64, PlatformError> {
+        Ok(TaskBackend::lease(self.tm_platform.as_ref(), task_id, host, duration)
+            .await?
+            .ok_or(CtrlError::TaskNotLeased(task_id))?
+        )
+    }
+
+    /// Returns the checkout of the files processed by the task leased to
+    /// the remote runner identified by host.
+    pub async fn leased_task_checkout(
+        &self,
+        task_id: i64,
+        host: &str,
+    ) -> Result<Option<PathBuf>, PlatformError> {
+        self.leased_task(task_id, host).await?;
+        self.task_checkout(task_id).await
+    }
+
+    /// Store the output of the task leased to the remote runner identified
+    /// by host into the basedir of the task, as read from the tar archive
+    /// provided by the reader.
+    pub async fn store_leased_task_output(
+        &self,
+        task_id: i64,
+        host: &str,
+        reader: impl Read + Send + 'static,
+    ) -> Result<(), PlatformError> {
+        let task = self.leased_task(task_id, host).await?;
+        let basedir = PathBuf::from(task.basedir);
+        tokio::task::spawn_blocking(move || unpack_dir(reader, &basedir))
+            .await
+            .map_err(std::io::Error::other)??;
+        Ok(())
+    }
+
+    /// Complete the task leased to the remote runner identified by host
+    /// with the exit status, returning whether the view was produced.
+    pub async fn complete_leased_task(
+        &self,
+        task_id: i64,
+        host: &str,
+        exit_status: i64,
+    ) -> Result<bool, PlatformError> {
+        self.leased_task(task_id, host).await?;
+        TaskBackend::complete(self.tm_platform.as_ref(), task_id, exit_status).await?;
+        self.finalize_task(task_id, exit_status).await
+    }
+
+    // Get the task by id, provided that it is leased to the host and has
+    // yet to complete.
+    async fn leased_task(
+        &self,
+        task_id: i64,
+        host: &str,
+    ) -> Result<Task, PlatformError> {
+        let task = TaskBackend::gets_task(self.tm_platform.as_ref(), task_id).await?;
+        if task.stop_ts.is_none()
+            && task.lease_ts.is_some()
+            && task.host.as_deref() == Some(host)
+        {
+            Ok(task)
+        } else {
+            Err(CtrlError::TaskNotLeased(task_id))?
+        }
+    }
+
+    /// Returns the checkout of the exposure for the exposure file view
+    /// that the task is for, as that holds the files the task processes.
+    pub(crate) async fn task_checkout(
+        &self,
+        task_id: i64,
+    ) -> Result<Option<PathBuf>, PlatformError> {
+        let mc_platform = self.mc_platform.as_ref();
+        // not every task is for an exposure file view.
+        let Ok(id) = ExposureFileViewBackend::select_id_by_task_id(
+            mc_platform,
+            task_id,
+        ).await else {
+            return Ok(None);
+        };
+        let view = ExposureFileViewBackend::get_id(mc_platform, id).await?;
+        let file = ExposureFileBackend::get_id(mc_platform, view.exposure_file_id).await?;
+        let mut result = self.data_root.join("exposure");
+        result.push(file.exposure_id.to_string());
+        result.push("files");
+        Ok(Some(result))
+    }
+
+    /// Finalize the exposure file view for a task already completed with
+    /// the exit status, returning whether the view was produced.
+    pub(crate) async fn finalize_task(
+        &self,
+        task_id: i64,
+        exit_status: i64,
+    ) -> Result<bool, PlatformError> {
+        let task = TaskBackend::gets_task(self.tm_platform.as_ref(), task_id).await?;
         // TODO figure out if we need to record task run failure for the
         // exposure task log
-        if exit_status == 0 {
-            let task_id = task.id();
-            Ok(match ExposureTaskBackend::finalize_task_id(
+        let produced = if exit_status == 0 {
+            match ExposureTaskBackend::finalize_task_id(
                 self.mc_platform.as_ref(),
                 task_id,
             ).await? {
                 Some((id, Some(view_key))) => {
                     log::debug!("Task:{task_id} ran for ExposureFileView:{id}, produced view {view_key}");
+                    // failing to cache the output does not invalidate the view.
+                    if let Err(e) = self.store_view_output(task_id, &task.basedir).await {
+                        log::warn!("Task:{task_id} output could not be cached: {e}");
+                    }
                     true
                 }
                 Some((id, None)) => {
@@ -57,9 +226,17 @@ impl<'p> Platform {
                     log::warn!("Task:{task_id} ran but it failed to produce results?");
                     false
                 }
-            })
+            }
         } else {
-            Ok(false)
+            false
+        };
+        // a task returned to the queue for a retry may still produce the
+        // output to be cached, otherwise nothing more will be stored.
+        if task.stop_ts.is_some() {
+            if let Err(e) = self.discard_view_output(task_id).await {
+                log::warn!("Task:{task_id} view cache key could not be discarded: {e}");
+            }
         }
+        Ok(produced)
     }
 }
@@ -1,7 +1,37 @@
+use pmrac::agent::ScopedAgent;
+use pmrcore::{
+    ac::workflow::State,
+    listing::{
+        Listing,
+        Page,
+    },
+    task::TaskSchedule,
+    workspace::{
+        Workspace,
+        WorkspaceSync,
+        traits::{
+            WorkspaceBackend,
+            WorkspaceSyncBackend,
+        },
+    },
+};
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
 use crate::{
-    error::PlatformError,
+    error::{
+        CtrlError,
+        PlatformError,
+    },
     handle::WorkspaceCtrl,
     platform::Platform,
+    scheduler::{
+        SyncPolicy,
+        is_remote_url,
+        remote_url_variants,
+    },
 };
 
 impl<'p> Platform {
@@ -30,4 +60,223 @@ impl<'p> Platform {
             workspace
         ))
     }
+
+    /// List a page of workspaces, with the access control filters of the
+    /// `listing` resolved.
+    pub async fn list_workspaces_page(
+        &self,
+        listing: &Listing,
+    ) -> Result<Page<Workspace>, PlatformError> {
+        let ids = self.resolve_listing_ids("/workspace/", listing).await?;
+        Ok(self.mc_platform.list_workspaces_page(&listing.to_query(ids)).await?)
+    }
+
+    /// Mark the workspace identified by `id` as superceded by the workspace
+    /// identified by `superceded_by_id`, or clear the supersession with
+    /// `None`.
+    ///
+    /// The successor must be an active workspace and the supersession may
+    /// not form a cycle, i.e. following the chain of successors from the
+    /// successor must not lead back to `id`.
+    ///
+    /// The aliases of the workspace are moved to the successor, such that
+    /// they continue to lead to the current version of the workspace; they
+    /// remain with the successor should the supersession be cleared.
+    pub async fn supersede_workspace(
+        &self,
+        id: i64,
+        superceded_by_id: Option<i64>,
+    ) -> Result<(), PlatformError> {
+        // ensure the workspace exists.
+        self.mc_platform.get_workspace_by_id(id).await?;
+        if let Some(successor_id) = superceded_by_id {
+            let mut visited = HashSet::new();
+            let mut next = Some(successor_id);
+            while let Some(current) = next {
+                if current == id {
+                    Err(CtrlError::SupersessionCycle(id, successor_id))?;
+                }
+                // a cycle further along the chain cannot lead back to `id`.
+                if !visited.insert(current) {
+                    break;
+                }
+                let workspace = self.mc_platform.get_workspace_by_id(current).await?;
+                if current == successor_id && workspace.archived_ts.is_some() {
+                    Err(CtrlError::WorkspaceArchived(successor_id))?;
+                }
+                next = workspace.superceded_by_id;
+            }
+        }
+        self.mc_platform.set_workspace_superceded_by(id, superceded_by_id).await?;
+        if let Some(successor_id) = superceded_by_id {
+            let aliases = self.mc_platform.get_aliases("workspace", id).await?;
+            self.mc_platform.reassign_aliases("workspace", id, successor_id).await?;
+            self.pc_platform.forget_resource_path(
+                Some("aliased_uri"),
+                &format!("/workspace/{id}/"),
+            ).await?;
+            for alias in aliases.iter() {
+                self.pc_platform.resource_link_kind_with_term(
+                    &format!("/workspace/{successor_id}/"),
+                    "aliased_uri",
+                    &format!("/workspace/{}/", alias.alias),
+                ).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the final successor of the workspace identified by `id` by
+    /// following its chain of successors.
+    ///
+    /// Returns `None` if the workspace is not superceded, or if the chain
+    /// forms a cycle and so has no final successor.
+    pub async fn resolve_workspace_successor(
+        &self,
+        id: i64,
+    ) -> Result<Option<i64>, PlatformError> {
+        let mut visited = HashSet::from([id]);
+        let mut current = id;
+        while let Some(next) = self.mc_platform.get_workspace_by_id(current).await?
+            .superceded_by_id
+        {
+            if !visited.insert(next) {
+                return Ok(None);
+            }
+            current = next;
+        }
+        Ok((current != id).then_some(current))
+    }
+
+    /// Archive the workspace identified by `id`, moving its repository into
+    /// the archive under the repo root and hiding it from the listings.
+    ///
+    /// The workspace and all its exposures that were published will also
+    /// be expired, and the aliases of the workspace are released such that
+    /// they may be used by other workspaces.  Returns `false` if the
+    /// workspace was already archived.
+    pub async fn archive_workspace(
+        &self,
+        id: i64,
+    ) -> Result<bool, PlatformError> {
+        if !self.repo_backend.archive_workspace(id).await? {
+            return Ok(false);
+        }
+        let mut resources = vec![format!("/workspace/{id}/")];
+        resources.extend(
+            self.mc_platform.list_for_workspace(id).await?
+                .iter()
+                .map(|exposure| format!("/exposure/{}/", exposure.id))
+        );
+        for resource in resources.iter() {
+            if self.ac_platform.get_wf_state_for_res(resource).await? == State::Published {
+                self.ac_platform.set_wf_state_for_res(resource, State::Expired).await?;
+            }
+        }
+        self.mc_platform.remove_aliases("workspace", id).await?;
+        self.pc_platform.forget_resource_path(
+            Some("aliased_uri"),
+            &format!("/workspace/{id}/"),
+        ).await?;
+        Ok(true)
+    }
+
+    /// Restore the archived workspace identified by `id`, moving its
+    /// repository back under the repo root.
+    ///
+    /// Any expiry applied through the archival is left as is, as the
+    /// decision to publish again should be made explicitly through the
+    /// workflow; likewise, the released aliases are not restored.  Returns
+    /// `false` if the workspace was not archived.
+    pub async fn restore_workspace(
+        &self,
+        id: i64,
+    ) -> Result<bool, PlatformError> {
+        Ok(self.repo_backend.restore_workspace(id).await?)
+    }
+
+    /// Apply the steps that follow a push by the agent into the repository
+    /// of the workspace identified by `id`, i.e. record the update and
+    /// index its tags as done for a synchronization, and optionally rebuild
+    /// the exposures of the workspace that the agent may edit.
+    ///
+    /// Returns the number of tasks queued for the rebuilt exposures.
+    pub async fn receive_workspace(
+        &self,
+        agent: impl Into<ScopedAgent>,
+        id: i64,
+        rebuild_exposures: bool,
+    ) -> Result<usize, PlatformError> {
+        self.repo_backend.receive_workspace(id).await?;
+        let agent = agent.into();
+        let mut result = 0;
+        if rebuild_exposures {
+            for exposure in self.mc_platform.list_for_workspace(id).await?.iter() {
+                // the exposures may be owned by others than the editors of
+                // the workspace.
+                if !self.ac_platform.enforce(
+                    agent.clone(),
+                    format!("/exposure/{}/", exposure.id),
+                    "edit",
+                ).await? {
+                    continue;
+                }
+                result += self.process_vttc_tasks_for_exposure_with(
+                    exposure.id,
+                    &TaskSchedule::bulk(),
+                ).await?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// List the ids of the active workspaces with a remote url that are
+    /// due for synchronization at `now` according to the `policy`.
+    pub async fn list_due_workspace_syncs(
+        &self,
+        policy: &SyncPolicy,
+        now: i64,
+    ) -> Result<Vec<i64>, PlatformError> {
+        let mut records = HashMap::<i64, Vec<WorkspaceSync>>::new();
+        for record in WorkspaceSyncBackend::list_latest_sync_records(
+            self.mc_platform.as_ref(),
+            policy.history_limit(),
+        ).await? {
+            records.entry(record.workspace_id)
+                .or_default()
+                .push(record);
+        }
+        let workspaces = WorkspaceBackend::list_workspaces(self.mc_platform.as_ref()).await?;
+        Ok(workspaces.iter()
+            .filter(|workspace| is_remote_url(&workspace.url))
+            .filter(|workspace| policy
+                .next_sync(
+                    records.get(&workspace.id).map(Vec::as_slice).unwrap_or_default(),
+                    now,
+                )
+                .is_some_and(|ts| ts <= now)
+            )
+            .map(|workspace| workspace.id)
+            .collect())
+    }
+
+    /// List the ids of the active workspaces with a url that refers to the
+    /// same remote as any of the provided urls, where the trailing slash
+    /// and the `.git` suffix are disregarded.
+    pub async fn list_workspaces_by_remote(
+        &self,
+        urls: &[&str],
+    ) -> Result<Vec<i64>, PlatformError> {
+        let mut result = Vec::new();
+        for url in urls.iter().flat_map(|url| remote_url_variants(url)) {
+            for workspace in self.mc_platform.list_workspace_by_url(&url).await?.iter() {
+                if workspace.archived_ts.is_none() {
+                    result.push(workspace.id);
+                }
+            }
+        }
+        result.sort();
+        result.dedup();
+        Ok(result)
+    }
 }
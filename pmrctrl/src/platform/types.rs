@@ -6,12 +6,15 @@ use pmrcore::{
         TMPlatform,
     },
 };
+use parking_lot::RwLock;
 use pmrrepo::backend::Backend;
 use std::{
     path::PathBuf,
     sync::Arc,
 };
 
+use crate::registry::ExposureFileChoiceProviders;
+
 #[derive(Clone)]
 pub struct Platform {
     pub ac_platform: pmrac::Platform,
@@ -22,4 +25,5 @@ pub struct Platform {
     pub(crate) data_root: PathBuf,
     pub(crate) repo_root: PathBuf,
     pub(crate) repo_backend: Backend,
+    pub(crate) choice_registry_providers: Arc<RwLock<ExposureFileChoiceProviders>>,
 }
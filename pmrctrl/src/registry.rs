@@ -1 +1,41 @@
+use pmrmodel::registry::ChoiceRegistryProviders;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+};
+
+use crate::error::PlatformError;
+
+/// The context for the choice registries provided for an exposure file.
+#[derive(Clone, Debug)]
+pub struct ExposureFileChoiceContext {
+    pub exposure_id: i64,
+    pub workspace_file_path: String,
+    /// The commit of the workspace the exposure is of.
+    pub commit_id: String,
+    /// The tags of the workspace, by their full reference names along
+    /// with the ids of the objects they point to.
+    pub tags: Vec<(String, String)>,
+    /// The root of the checked out files of the exposure.
+    pub files_root: PathBuf,
+    /// The files of the exposure, mapped to their paths under `files_root`.
+    pub files: HashMap<String, String>,
+    /// The data root of the exposure.
+    pub data_root: PathBuf,
+}
+
+pub type ExposureFileChoiceProviders = ChoiceRegistryProviders<
+    ExposureFileChoiceContext,
+    PlatformError,
+>;
+
+/// The names of the registries that are built for each view by the
+/// platform, rather than being provided by the registered providers.
+pub const VIEW_CHOICE_SOURCES: &[&str] = &[
+    "working_dir",
+];
+
 mod impls;
+mod provider;
+
+pub use provider::default_providers;
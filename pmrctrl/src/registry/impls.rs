@@ -1,9 +1,11 @@
-use pmrcore::exposure::traits::ExposureFile;
+use pmrcore::exposure::traits::{
+    Exposure,
+    ExposureFile,
+};
 use pmrmodel::registry::{
     ChoiceRegistry,
     PreparedChoiceRegistry,
 };
-use std::collections::HashMap;
 
 use crate::{
     error::PlatformError,
@@ -11,15 +13,9 @@ use crate::{
         ExposureCtrl,
         ExposureFileCtrl,
     },
+    registry::ExposureFileChoiceContext,
 };
 
-// TODO figure out how to define a registry for registration, i.e. there
-// needs to be a mapping of registry names to the relevant items, done
-// in a more definition driven manner (registry of registries)
-//
-// An idea for this might be a default method and the impl TryFrom for
-// each of them would flag the relevant ones on or off.
-
 impl<'p> TryFrom<&ExposureCtrl<'p>> for PreparedChoiceRegistry {
     type Error = PlatformError;
 
@@ -35,49 +31,37 @@ impl<'p> TryFrom<&ExposureCtrl<'p>> for PreparedChoiceRegistry {
     }
 }
 
-impl<'p> TryFrom<&ExposureFileCtrl<'p>> for PreparedChoiceRegistry {
+impl<'p> TryFrom<&ExposureFileCtrl<'p>> for ExposureFileChoiceContext {
     type Error = PlatformError;
 
     fn try_from(
         handle: &ExposureFileCtrl<'p>,
     ) -> Result<Self, Self::Error> {
-        let mut registry = PreparedChoiceRegistry::new();
-        // `files` is the registry for full listing of files
-        registry.register("files", handle.0.exposure.map_files_fs()?.into());
-
-        // `workspace_file_path` provides a single default entry that points to the
-        // `workspace_file_path` of the given `exposure_file` on the filesystem.
-        let workspace_file_path = handle.0.exposure.ensure_fs()?.join(handle
-            .exposure_file()
-            .workspace_file_path()
-        ).display().to_string();
-        registry.register("workspace_file_path", HashMap::from([
-            ("workspace_file_path".to_string(), workspace_file_path),
-        ]).into());
-        // TODO `workspace_file_path` should include `fs` to denote it being on the filesystem.
-        // Perhaps `_path` is the path related to things on filesystem but it should really
-        // have a dedicated prefix or suffix.
-
-        // `files_default` is the registry for full listing of files with the current
-        // file being selected as the default.
-        registry.register("files_default", handle.0.exposure.map_files_fs()?.into());
-        registry.select_keys("files_default", vec![
-            handle.exposure_file()
+        Ok(Self {
+            exposure_id: handle.exposure_file().exposure_id(),
+            workspace_file_path: handle.exposure_file()
                 .workspace_file_path()
-                .to_string()
-        ]);
-
-        // `exposure_id` is the id of the underlying exposure
-        registry.register("exposure_id", HashMap::from([
-            ("exposure_id".to_string(), handle.exposure_file().exposure_id().to_string())
-        ]).into());
+                .to_string(),
+            commit_id: handle.0.exposure.exposure().commit_id().to_string(),
+            tags: handle.0.exposure.0.git_handle.repo_tags()?,
+            files_root: handle.0.exposure.ensure_fs()?,
+            files: handle.0.exposure.map_files_fs()?,
+            data_root: handle.0.exposure.data_root(),
+        })
+    }
+}
 
-        // `exposure_file` is the current exposure file; equivalent to the workspace_file_path that
-        // underlies the `exposure_file`.
-        registry.register("exposure_file", HashMap::from([
-            ("exposure_file".to_string(), handle.exposure_file().workspace_file_path().to_string()),
-        ]).into());
+/// Prepares the registries of all providers registered with the platform
+/// that do not require a parameter.
+impl<'p> TryFrom<&ExposureFileCtrl<'p>> for PreparedChoiceRegistry {
+    type Error = PlatformError;
 
-        Ok(registry)
+    fn try_from(
+        handle: &ExposureFileCtrl<'p>,
+    ) -> Result<Self, Self::Error> {
+        let context = ExposureFileChoiceContext::try_from(handle)?;
+        handle.0.platform
+            .choice_registry_providers()
+            .prepare_defaults(&context)
     }
 }
@@ -0,0 +1,275 @@
+use pmrmodel::registry::{
+    ChoiceRegistryProvider,
+    ProvidedChoices,
+};
+use std::collections::HashMap;
+
+use crate::{
+    error::PlatformError,
+    registry::{
+        ExposureFileChoiceContext,
+        ExposureFileChoiceProviders,
+    },
+};
+
+/// The providers for the registries that the platform provides for every
+/// exposure file.
+pub fn default_providers() -> ExposureFileChoiceProviders {
+    let mut providers = ExposureFileChoiceProviders::new();
+    providers.register(Files);
+    providers.register(FilesDefault);
+    providers.register(FilesGlob);
+    providers.register(WorkspaceFilePath);
+    providers.register(ExposureId);
+    providers.register(ExposureFile);
+    providers.register(WorkspaceTags);
+    providers
+}
+
+/// `files` is the registry for full listing of files
+struct Files;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for Files {
+    fn name(&self) -> &str {
+        "files"
+    }
+
+    fn description(&self) -> &str {
+        "all files in the exposure"
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        _: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        Ok(ProvidedChoices::new(context.files.clone()))
+    }
+}
+
+/// `files_default` is the registry for full listing of files with the
+/// current file being selected as the default.
+struct FilesDefault;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for FilesDefault {
+    fn name(&self) -> &str {
+        "files_default"
+    }
+
+    fn description(&self) -> &str {
+        "all files in the exposure, with the exposure file selected"
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        _: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        Ok(ProvidedChoices::new(context.files.clone())
+            .select_keys(vec![context.workspace_file_path.clone()]))
+    }
+}
+
+/// `files_glob:<pattern>` is the registry for the listing of files with
+/// their paths matching the glob pattern.
+struct FilesGlob;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for FilesGlob {
+    fn name(&self) -> &str {
+        "files_glob"
+    }
+
+    fn description(&self) -> &str {
+        "files in the exposure with paths matching the pattern"
+    }
+
+    fn parameter(&self) -> Option<&str> {
+        Some("glob pattern, where `*` does not match across `/` but `**` does")
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        parameter: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        let pattern = parameter
+            .unwrap_or_default()
+            .chars()
+            .collect::<Vec<_>>();
+        Ok(ProvidedChoices::new(context.files
+            .iter()
+            .filter(|(path, _)| glob_match(
+                &pattern,
+                &path.chars().collect::<Vec<_>>(),
+            ))
+            .map(|(path, fs_path)| (path.clone(), fs_path.clone()))
+            .collect::<HashMap<_, _>>()
+        ))
+    }
+}
+
+/// `workspace_file_path` provides a single default entry that points to
+/// the `workspace_file_path` of the given `exposure_file` on the
+/// filesystem.
+// TODO `workspace_file_path` should include `fs` to denote it being on the filesystem.
+// Perhaps `_path` is the path related to things on filesystem but it should really
+// have a dedicated prefix or suffix.
+struct WorkspaceFilePath;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for WorkspaceFilePath {
+    fn name(&self) -> &str {
+        "workspace_file_path"
+    }
+
+    fn description(&self) -> &str {
+        "the exposure file on the filesystem"
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        _: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        Ok(ProvidedChoices::new(HashMap::from([
+            (
+                "workspace_file_path".to_string(),
+                context.files_root
+                    .join(&context.workspace_file_path)
+                    .display()
+                    .to_string(),
+            ),
+        ])))
+    }
+}
+
+/// `exposure_id` is the id of the underlying exposure
+struct ExposureId;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for ExposureId {
+    fn name(&self) -> &str {
+        "exposure_id"
+    }
+
+    fn description(&self) -> &str {
+        "the id of the exposure"
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        _: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        Ok(ProvidedChoices::new(HashMap::from([
+            ("exposure_id".to_string(), context.exposure_id.to_string()),
+        ])))
+    }
+}
+
+/// `exposure_file` is the current exposure file; equivalent to the
+/// workspace_file_path that underlies the `exposure_file`.
+struct ExposureFile;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for ExposureFile {
+    fn name(&self) -> &str {
+        "exposure_file"
+    }
+
+    fn description(&self) -> &str {
+        "the path of the exposure file within the exposure"
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        _: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        Ok(ProvidedChoices::new(HashMap::from([
+            ("exposure_file".to_string(), context.workspace_file_path.clone()),
+        ])))
+    }
+}
+
+/// `workspace_tags` is the registry for the tags of the workspace, with
+/// each tag pointing to the commit of the exposure selected.
+struct WorkspaceTags;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for WorkspaceTags {
+    fn name(&self) -> &str {
+        "workspace_tags"
+    }
+
+    fn description(&self) -> &str {
+        "the tags of the workspace, with the tags of the exposure commit selected"
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        _: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        let tags = context.tags
+            .iter()
+            .map(|(name, target)| (
+                name.strip_prefix("refs/tags/").unwrap_or(name).to_string(),
+                target.clone(),
+            ))
+            .collect::<HashMap<_, _>>();
+        let selected = tags
+            .iter()
+            .filter(|(_, target)| **target == context.commit_id)
+            .map(|(name, _)| name.clone())
+            .collect();
+        Ok(ProvidedChoices::new(tags).select_keys(selected))
+    }
+}
+
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        // `**/` also matches no directories at all.
+        ['*', '*', '/', rest @ ..] => (0..=path.len())
+            .filter(|&i| i == 0 || path[i - 1] == '/')
+            .any(|i| glob_match(rest, &path[i..])),
+        ['*', '*', rest @ ..] => (0..=path.len())
+            .any(|i| glob_match(rest, &path[i..])),
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != '/')
+            .any(|i| glob_match(rest, &path[i..])),
+        ['?', rest @ ..] => matches!(
+            path,
+            [c, tail @ ..] if *c != '/' && glob_match(rest, tail)
+        ),
+        [p, rest @ ..] => matches!(
+            path,
+            [c, tail @ ..] if c == p && glob_match(rest, tail)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::glob_match;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        glob_match(
+            &pattern.chars().collect::<Vec<_>>(),
+            &path.chars().collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(matches("*.cellml", "model.cellml"));
+        assert!(!matches("*.cellml", "dir/model.cellml"));
+        assert!(!matches("*.cellml", "model.cellml.bak"));
+        assert!(matches("**/*.cellml", "model.cellml"));
+        assert!(matches("**/*.cellml", "dir/model.cellml"));
+        assert!(matches("**/*.cellml", "dir/sub/model.cellml"));
+        assert!(matches("dir/**", "dir/sub/model.cellml"));
+        assert!(matches("?.txt", "a.txt"));
+        assert!(!matches("?.txt", "ab.txt"));
+        assert!(!matches("?", "/"));
+        assert!(matches("README", "README"));
+        assert!(!matches("", "README"));
+    }
+}
@@ -0,0 +1,21 @@
+//! The synchronization of workspaces with their remote repositories.
+//!
+//! The [`SyncScheduler`] periodically synchronizes the workspaces with a
+//! remote url, with the history of the synchronization of each workspace
+//! used to back off from remotes that repeatedly fail.  Synchronization
+//! may also be requested for specific workspaces through the associated
+//! [`SyncQueue`], e.g. as a result of a push to the remote.
+
+mod impls;
+mod types;
+
+pub use impls::{
+    SYNC_QUEUE_CAPACITY,
+    is_remote_url,
+};
+pub(crate) use impls::remote_url_variants;
+pub use types::{
+    SyncPolicy,
+    SyncQueue,
+    SyncScheduler,
+};
@@ -0,0 +1,292 @@
+use chrono::Utc;
+use pmrcore::workspace::{
+    WorkspaceSync,
+    WorkspaceSyncStatus,
+};
+use parking_lot::Mutex;
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc,
+    time::MissedTickBehavior,
+};
+
+use crate::{
+    error::PlatformError,
+    platform::Platform,
+};
+
+use super::*;
+
+/// Returns whether the url refers to a remote repository, i.e. one that
+/// is not on the local filesystem.  This includes the scp-like syntax
+/// understood by git, e.g. `git@example.com:repo.git`.
+pub fn is_remote_url(url: &str) -> bool {
+    match url.split_once("://") {
+        Some((scheme, _)) => !scheme.eq_ignore_ascii_case("file"),
+        // the single letter check avoids treating Windows drives as hosts
+        None => url.split_once(':')
+            .is_some_and(|(host, _)| host.len() > 1 && !host.contains('/')),
+    }
+}
+
+/// The variations of the url that refer to the same remote repository,
+/// i.e. with or without the trailing slash or the `.git` suffix.
+pub(crate) fn remote_url_variants(url: &str) -> Vec<String> {
+    let base = url.trim_end_matches('/');
+    let base = base.strip_suffix(".git").unwrap_or(base);
+    let mut result = vec![
+        url.to_string(),
+        base.to_string(),
+        format!("{base}/"),
+        format!("{base}.git"),
+    ];
+    result.sort();
+    result.dedup();
+    result
+}
+
+impl Default for SyncPolicy {
+    fn default() -> Self {
+        Self {
+            sync_interval: 0,
+            sync_backoff: 300,
+            sync_backoff_max: 86400,
+            sync_poll: 60,
+        }
+    }
+}
+
+impl SyncPolicy {
+    /// The delay after the provided number of consecutive failures.
+    pub fn backoff(&self, failures: u32) -> i64 {
+        self.sync_backoff
+            .saturating_mul(2i64.saturating_pow(failures.saturating_sub(1)))
+            .min(self.sync_backoff_max)
+    }
+
+    /// The number of the most recent sync records of a workspace needed
+    /// by [`Self::next_sync`], as the backoff stops growing once the
+    /// consecutive failures reach this number.
+    pub fn history_limit(&self) -> i64 {
+        (1..u32::BITS)
+            .find(|failures| self.backoff(*failures) >= self.sync_backoff_max)
+            .unwrap_or(u32::BITS)
+            .into()
+    }
+
+    /// Returns the timestamp when the workspace with the provided sync
+    /// records will be due for synchronization, or `None` if it is being
+    /// synchronized at `now`.
+    pub fn next_sync(&self, records: &[WorkspaceSync], now: i64) -> Option<i64> {
+        let mut records = records.iter().collect::<Vec<_>>();
+        records.sort_by_key(|record| Reverse(record.id));
+        let stale = |record: &WorkspaceSync| now - record.start > self.sync_backoff_max;
+        let Some(latest) = records.first() else {
+            return Some(now);
+        };
+        let failures = records.iter()
+            .take_while(|record| match WorkspaceSyncStatus::from(record.status) {
+                WorkspaceSyncStatus::Error => true,
+                WorkspaceSyncStatus::Running => stale(record),
+                _ => false,
+            })
+            .count();
+        if failures == 0 && WorkspaceSyncStatus::from(latest.status) == WorkspaceSyncStatus::Running {
+            return None;
+        }
+        let last = latest.end.unwrap_or(latest.start);
+        Some(if failures > 0 {
+            last + self.backoff(failures.try_into().unwrap_or(u32::MAX))
+        } else {
+            last + self.sync_interval
+        })
+    }
+}
+
+/// The number of workspaces that may be pending in a [`SyncQueue`].
+pub const SYNC_QUEUE_CAPACITY: usize = 1024;
+
+impl SyncScheduler {
+    pub fn new(platform: Platform, policy: SyncPolicy) -> (Self, SyncQueue) {
+        let (sender, receiver) = mpsc::channel(SYNC_QUEUE_CAPACITY);
+        let pending = Arc::new(Mutex::new(HashSet::new()));
+        (
+            Self { platform, policy, receiver, pending: pending.clone() },
+            SyncQueue { sender, pending },
+        )
+    }
+
+    /// Run the scheduler, which will synchronize the workspaces that are
+    /// due at every poll if periodic synchronization is enabled, along
+    /// with the workspaces requested through the queues, until all the
+    /// queues are dropped.
+    pub async fn run(mut self) {
+        let mut interval = tokio::time::interval(
+            Duration::from_secs(self.policy.sync_poll.max(1))
+        );
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let periodic = self.policy.sync_interval > 0;
+        loop {
+            tokio::select! {
+                workspace_id = self.receiver.recv() => match workspace_id {
+                    Some(workspace_id) => {
+                        // a request made during the sync will be queued
+                        // again as it may be for newer changes.
+                        self.pending.lock().remove(&workspace_id);
+                        self.sync(workspace_id).await
+                    }
+                    None => break,
+                },
+                _ = interval.tick(), if periodic => {
+                    if let Err(e) = self.sync_due().await {
+                        log::error!("failed to synchronize due workspaces: {e}");
+                    }
+                }
+            }
+        }
+        log::info!("sync scheduler stopped");
+    }
+
+    /// Synchronize all the workspaces that are currently due, returning
+    /// the number of workspaces that were synchronized.
+    pub async fn sync_due(&self) -> Result<usize, PlatformError> {
+        let ids = self.platform
+            .list_due_workspace_syncs(&self.policy, Utc::now().timestamp())
+            .await?;
+        for id in ids.iter() {
+            self.sync(*id).await;
+        }
+        Ok(ids.len())
+    }
+
+    async fn sync(&self, workspace_id: i64) {
+        match self.platform.repo_backend.sync_workspace(workspace_id).await {
+            Ok(()) => log::info!("workspace {workspace_id} synchronized"),
+            Err(e) => log::warn!("workspace {workspace_id} failed to synchronize: {e}"),
+        }
+    }
+}
+
+impl SyncQueue {
+    /// Request the synchronization of the workspace, returning `false` if
+    /// the queue is full or the scheduler is no longer running.  Requests
+    /// for a workspace that is already pending are merged into that one.
+    pub fn enqueue(&self, workspace_id: i64) -> bool {
+        let mut pending = self.pending.lock();
+        if pending.contains(&workspace_id) {
+            return !self.sender.is_closed();
+        }
+        if self.sender.try_send(workspace_id).is_err() {
+            return false;
+        }
+        pending.insert(workspace_id);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(id: i64, start: i64, end: Option<i64>, status: WorkspaceSyncStatus) -> WorkspaceSync {
+        WorkspaceSync {
+            id,
+            workspace_id: 1,
+            start,
+            end,
+            status: status as i64,
+        }
+    }
+
+    fn policy() -> SyncPolicy {
+        SyncPolicy {
+            sync_interval: 3600,
+            sync_backoff: 60,
+            sync_backoff_max: 1000,
+            sync_poll: 1,
+        }
+    }
+
+    #[test]
+    fn remote_url() {
+        for url in [
+            "https://models.example.com/workspace/1",
+            "git://example.com/repo.git",
+            "ssh://git@example.com/repo.git",
+            "git@example.com:repo.git",
+        ] {
+            assert!(is_remote_url(url), "{url} should be remote");
+        }
+        for url in [
+            "file:///srv/repo",
+            "/srv/repo",
+            "relative/repo",
+            "./dir:with/colon",
+            "C:\\repo",
+        ] {
+            assert!(!is_remote_url(url), "{url} should not be remote");
+        }
+    }
+
+    #[test]
+    fn url_variants() {
+        assert_eq!(remote_url_variants("https://example.com/repo.git"), [
+            "https://example.com/repo",
+            "https://example.com/repo.git",
+            "https://example.com/repo/",
+        ]);
+        assert_eq!(remote_url_variants("https://example.com/repo/"), [
+            "https://example.com/repo",
+            "https://example.com/repo.git",
+            "https://example.com/repo/",
+        ]);
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = policy();
+        assert_eq!(policy.backoff(1), 60);
+        assert_eq!(policy.backoff(2), 120);
+        assert_eq!(policy.backoff(4), 480);
+        assert_eq!(policy.backoff(5), 960);
+        assert_eq!(policy.backoff(6), 1000);
+        assert_eq!(policy.backoff(u32::MAX), 1000);
+        assert_eq!(policy.history_limit(), 6);
+        assert_eq!(SyncPolicy::default().history_limit(), 10);
+    }
+
+    #[test]
+    fn next_sync() {
+        let policy = policy();
+        assert_eq!(policy.next_sync(&[], 5000), Some(5000));
+        assert_eq!(policy.next_sync(&[
+            record(1, 100, Some(110), WorkspaceSyncStatus::Completed),
+        ], 5000), Some(3710));
+        // records are considered by their order of creation.
+        assert_eq!(policy.next_sync(&[
+            record(3, 400, Some(410), WorkspaceSyncStatus::Error),
+            record(1, 100, Some(110), WorkspaceSyncStatus::Error),
+            record(2, 200, Some(210), WorkspaceSyncStatus::Error),
+        ], 5000), Some(650));
+        // only the consecutive failures count towards the backoff.
+        assert_eq!(policy.next_sync(&[
+            record(1, 100, Some(110), WorkspaceSyncStatus::Error),
+            record(2, 200, Some(210), WorkspaceSyncStatus::Completed),
+            record(3, 400, Some(410), WorkspaceSyncStatus::Error),
+        ], 5000), Some(470));
+        assert_eq!(policy.next_sync(&[
+            record(1, 100, Some(110), WorkspaceSyncStatus::Completed),
+            record(2, 4500, None, WorkspaceSyncStatus::Running),
+        ], 5000), None);
+        // a sync that has been running for too long is a failure.
+        assert_eq!(policy.next_sync(&[
+            record(1, 100, Some(110), WorkspaceSyncStatus::Error),
+            record(2, 200, None, WorkspaceSyncStatus::Running),
+        ], 5000), Some(320));
+    }
+}
@@ -0,0 +1,47 @@
+use clap::Parser;
+use parking_lot::Mutex;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+};
+use tokio::sync::mpsc;
+
+use crate::platform::Platform;
+
+/// The timing of the synchronization of the workspaces, with all values
+/// in seconds.
+#[derive(Clone, Debug, Parser)]
+pub struct SyncPolicy {
+    /// The interval between the synchronization of a workspace; periodic
+    /// synchronization is disabled if this is 0.
+    #[clap(long, value_name = "PMR_SYNC_INTERVAL", env = "PMR_SYNC_INTERVAL", default_value_t = 0)]
+    pub sync_interval: i64,
+    /// The delay after a failed synchronization before it is attempted
+    /// again, which is doubled for every consecutive failure.
+    #[clap(long, value_name = "PMR_SYNC_BACKOFF", env = "PMR_SYNC_BACKOFF", default_value_t = 300)]
+    pub sync_backoff: i64,
+    /// The upper limit of the delay after failures; a synchronization
+    /// that is still running after this long is considered to have failed.
+    #[clap(long, value_name = "PMR_SYNC_BACKOFF_MAX", env = "PMR_SYNC_BACKOFF_MAX", default_value_t = 86400)]
+    pub sync_backoff_max: i64,
+    /// How often the workspaces are checked for whether they are due for
+    /// synchronization.
+    #[clap(long, value_name = "PMR_SYNC_POLL", env = "PMR_SYNC_POLL", default_value_t = 60)]
+    pub sync_poll: u64,
+}
+
+pub struct SyncScheduler {
+    pub(super) platform: Platform,
+    pub(super) policy: SyncPolicy,
+    pub(super) receiver: mpsc::Receiver<i64>,
+    pub(super) pending: Arc<Mutex<HashSet<i64>>>,
+}
+
+/// The queue for requesting the synchronization of specific workspaces
+/// from the [`SyncScheduler`] it was created with.  The queue is bounded,
+/// and a workspace that is already pending is not queued again.
+#[derive(Clone)]
+pub struct SyncQueue {
+    pub(super) sender: mpsc::Sender<i64>,
+    pub(super) pending: Arc<Mutex<HashSet<i64>>>,
+}
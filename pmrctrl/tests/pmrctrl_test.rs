@@ -1,4 +1,10 @@
+use pmrac::agent::ScopedAgent;
 use pmrcore::{
+    ac::{
+        agent::Agent,
+        role::Role,
+        workflow::State,
+    },
     exposure::{
         task::traits::{
             ExposureTaskTemplateBackend,
@@ -11,11 +17,25 @@ use pmrcore::{
             ExposureFileViewBackend,
         },
     },
+    listing::{
+        Listing,
+        Page,
+        SortOrder,
+    },
     task::{
+        EXIT_ABORTED,
+        EXIT_ORPHANED,
+        EXIT_TIMED_OUT,
         Task,
+        TaskArg,
+        TaskFailure,
+        TaskLog,
+        TaskSchedule,
+        TaskState,
         traits::TaskBackend,
     },
     task_template::{
+        traits::TaskTemplateBackend,
         UserChoiceRef,
         UserChoiceRefs,
         UserArg,
@@ -28,8 +48,13 @@ use pmrcore::{
         traits::{
             ProfileBackend,
             ProfileViewsBackend,
+            ViewTaskTemplateBackend,
         },
     },
+    workspace::{
+        WorkspaceSyncStatus,
+        traits::WorkspaceBackend,
+    },
 };
 use pmrmodel::{
     model::{
@@ -42,17 +67,34 @@ use pmrmodel::{
     registry::{
         ChoiceRegistry,
         ChoiceRegistryCache,
+        ChoiceRegistryProvider,
         PreparedChoiceRegistry,
+        ProvidedChoices,
     },
 };
 use pmrctrl::{
-    error::CtrlError,
+    error::{
+        CtrlError,
+        PlatformError,
+    },
     platform::Platform,
+    registry::ExposureFileChoiceContext,
+    scheduler::{
+        SyncPolicy,
+        SyncScheduler,
+    },
+};
+use pmrtqs::executor::{
+    OrphanPolicy,
+    TMPlatformExecutorInstance,
+    host_id,
+    recover_orphans,
 };
 use std::{
     path::PathBuf,
     fs::read_to_string,
 };
+use tokio::sync::broadcast;
 
 use test_binary::build_test_binary_once;
 use test_pmr::{
@@ -275,7 +317,7 @@ async fn test_platform_exposure_ctrl_resolve_view() -> anyhow::Result<()> {
         .start_task()
         .await?
         .expect("task was queued");
-    task.run(12345).await?;
+    task.run(12345, host_id()).await?;
     platform.complete_task(task, 0).await?;
 
     let efv = platform.mc_platform.as_ref()
@@ -417,6 +459,7 @@ async fn test_platform_create_exposure_file_view_task() -> anyhow::Result<()> {
             default: None,
             choice_fixed: false,
             choices: None,
+            multiple: false,
         },
         UserArg {
             id: 2,
@@ -427,12 +470,13 @@ async fn test_platform_create_exposure_file_view_task() -> anyhow::Result<()> {
                 "README".into(),
                 "if1".into(),
             ].into()),
+            multiple: false,
         },
     ]);
 
     let user_input = UserInputMap::from([
-        (1, "Example answer".to_string()),
-        (2, "README".to_string()),
+        (1, "Example answer".into()),
+        (2, "README".into()),
     ]);
 
     let new_task = platform.adds_task(
@@ -590,12 +634,15 @@ async fn make_example_runnable_task_templates<'p>(
     build_test_binary_once!(sentinel, "../testing");
     build_test_binary_once!(exit_code, "../testing");
     build_test_binary_once!(iorw, "../testing");
+    build_test_binary_once!(sleeper, "../testing");
     let sentinel = path_to_sentinel().into_string()
         .expect("failed to build testing/sentinel");
     let exit_code = path_to_exit_code().into_string()
         .expect("failed to build testing/exit_code");
     let iorw = path_to_iorw().into_string()
         .expect("failed to build testing/iorw");
+    let sleeper = path_to_sleeper().into_string()
+        .expect("failed to build testing/sleeper");
 
     let mut result: Vec<i64> = Vec::new();
     result.push(platform.adds_view_task_template(
@@ -663,6 +710,29 @@ async fn make_example_runnable_task_templates<'p>(
             }}
         }}"#))?
     ).await?);
+    result.push(platform.adds_view_task_template(
+        serde_json::from_str(&format!(r#"{{
+            "view_key": "sleeper",
+            "description": "Sleeper",
+            "task_template": {{
+                "bin_path": "{sleeper}",
+                "version_id": "1.0.0",
+                "timeout": 1,
+                "args": [
+                    {{
+                        "flag": null,
+                        "flag_joined": false,
+                        "flag_omit_when_null": false,
+                        "prompt": "Seconds",
+                        "default": null,
+                        "choice_fixed": false,
+                        "choice_source": null,
+                        "choices": []
+                    }}
+                ]
+            }}
+        }}"#))?
+    ).await?);
     Ok(result)
 }
 
@@ -801,6 +871,7 @@ async fn test_platform_file_templates_user_args_usage() -> anyhow::Result<()> {
                     default: None,
                     choice_fixed: false,
                     choices: None,
+                    multiple: false,
                 }
             ].into(),
         },
@@ -814,6 +885,7 @@ async fn test_platform_file_templates_user_args_usage() -> anyhow::Result<()> {
                     default: None,
                     choice_fixed: true,
                     choices: Some(["README".into(), "if1".into()].into()),
+                    multiple: false,
                 }
             ].into(),
         },
@@ -823,8 +895,8 @@ async fn test_platform_file_templates_user_args_usage() -> anyhow::Result<()> {
     // underlying linkage between ViewTaskTemplate and TaskTemplate
 
     let user_input = UserInputMap::from([
-        (1, "Example answer".to_string()),
-        (3, "README".to_string()),
+        (1, "Example answer".into()),
+        (3, "README".into()),
     ]);
 
     let tasks = efvttsc.create_tasks_from_input(&user_input)?
@@ -1209,7 +1281,7 @@ async fn test_exposure_file_view_task_sync() -> anyhow::Result<()> {
     ).await?;
     let efvttsc = efc.build_vttc().await?;
     let user_input = UserInputMap::from([
-        (1, "Example answer".to_string()),
+        (1, "Example answer".into()),
     ]);
 
     let tasks = efvttsc.create_tasks_from_input(&user_input)?;
@@ -1254,7 +1326,7 @@ async fn test_exposure_file_view_task_run_view_key_success() -> anyhow::Result<(
     ).await?;
     let efvttsc = efc.build_vttc().await?;
     let user_input = UserInputMap::from([
-        (1, "Example answer".to_string()),
+        (1, "Example answer".into()),
     ]);
 
     let tasks = efvttsc.create_tasks_from_input(&user_input)?;
@@ -1272,7 +1344,7 @@ async fn test_exposure_file_view_task_run_view_key_success() -> anyhow::Result<(
         .await?
         .expect("task was queued");
     assert_eq!(task.id(), task_id);
-    task.run(12345).await?;
+    task.run(12345, host_id()).await?;
 
     let result = platform.complete_task(task, 0).await?;
     assert!(result);
@@ -1304,7 +1376,7 @@ async fn test_exposure_file_view_task_run_task_fail() -> anyhow::Result<()> {
     ).await?;
     let efvttsc = efc.build_vttc().await?;
     let user_input = UserInputMap::from([
-        (1, "Example answer".to_string()),
+        (1, "Example answer".into()),
     ]);
 
     let tasks = efvttsc.create_tasks_from_input(&user_input)?;
@@ -1323,7 +1395,7 @@ async fn test_exposure_file_view_task_run_task_fail() -> anyhow::Result<()> {
         .expect("task was queued");
     assert_eq!(task.id(), task_id);
     // pretend we ran it and complete it
-    task.run(12345).await?;
+    task.run(12345, host_id()).await?;
 
     let result = platform.complete_task(task, 1).await?;
     assert!(!result);
@@ -1353,7 +1425,7 @@ async fn test_exposure_file_view_task_run_task_stale() -> anyhow::Result<()> {
     ).await?;
     let efvttsc = efc.build_vttc().await?;
     let user_input = UserInputMap::from([
-        (1, "Example answer".to_string()),
+        (1, "Example answer".into()),
     ]);
 
     let tasks = efvttsc.create_tasks_from_input(&user_input)?;
@@ -1378,7 +1450,7 @@ async fn test_exposure_file_view_task_run_task_stale() -> anyhow::Result<()> {
     ).await?;
 
     // now run the task, that is now stale...
-    task.run(12345).await?;
+    task.run(12345, host_id()).await?;
     // ... even if it was started later.
     let later_task = platform.tm_platform.as_ref()
         .start_task()
@@ -1597,7 +1669,7 @@ async fn test_task_executor_ctrl_task_failure_then_success() -> anyhow::Result<(
     ).await?;
     let efvttsc = efc.build_vttc().await?;
     let user_input = UserInputMap::from([
-        (1, "42".to_string()),
+        (1, "42".into()),
     ]);
     let tasks = efvttsc.create_tasks_from_input(&user_input)?;
     let result = efc.process_vttc_tasks(tasks).await?;
@@ -1621,7 +1693,7 @@ async fn test_task_executor_ctrl_task_failure_then_success() -> anyhow::Result<(
 
     // now simulate a correction to a successful run
     let user_input = UserInputMap::from([
-        (1, "0".to_string()),
+        (1, "0".into()),
     ]);
     let tasks = efvttsc.create_tasks_from_input(&user_input)?;
     let result = efc.process_vttc_tasks(tasks).await?;
@@ -1639,6 +1711,505 @@ async fn test_task_executor_ctrl_task_failure_then_success() -> anyhow::Result<(
     Ok(())
 }
 
+#[tokio::test]
+async fn test_task_executor_ctrl_view_dependency() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    let tm_platform = platform.tm_platform.as_ref();
+    // the sentinel view requires the exit code view to be generated first
+    assert!(ViewTaskTemplateBackend::set_view_task_template_depends_on(
+        platform.mc_platform.as_ref(),
+        vtts[0],
+        Some("exit_code_0"),
+    ).await?);
+
+    let exposure = platform.create_exposure(
+        1,
+        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
+    ).await?;
+    let efc = exposure.create_file("if1").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtts[0], vtts[1]],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+
+    // the dependency is queued first, despite the order of the templates
+    let user_input = UserInputMap::from([
+        (1, "0".into()),
+    ]);
+    let result = efc.process_vttc_tasks(
+        efvttsc.create_tasks_from_input(&user_input)?
+    ).await?;
+    let (_, exit_code_task_id) = result[0];
+    let (_, sentinel_task_id) = result[1];
+    assert_eq!(
+        TaskBackend::gets_task(tm_platform, sentinel_task_id).await?.depends_on,
+        [exit_code_task_id],
+    );
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    // the dependent task cannot be started while the dependency runs
+    assert!(platform.start_task().await?.is_none());
+    assert_eq!(task_executor_ctrl.execute().await?, (0, true));
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("the dependency has completed");
+    assert_eq!(task_executor_ctrl.execute().await?, (0, true));
+
+    // the failure of the dependency is final without retries, which
+    // fails the dependent task
+    let user_input = UserInputMap::from([
+        (1, "3".into()),
+    ]);
+    let result = efc.process_vttc_tasks(
+        efvttsc.create_tasks_from_input(&user_input)?
+    ).await?;
+    let (_, sentinel_task_id) = result[1];
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    assert_eq!(task_executor_ctrl.execute().await?, (3, false));
+    assert_eq!(
+        TaskBackend::gets_task(tm_platform, sentinel_task_id).await?.failure(),
+        Some(TaskFailure::DependencyFailed),
+    );
+    assert!(platform.start_task().await?.is_none());
+
+    // with a retry the failed dependency is queued once more
+    let schedule = TaskSchedule {
+        max_retries: 1,
+        ..Default::default()
+    };
+    let result = efc.process_vttc_tasks_with(
+        efvttsc.create_tasks_from_input(&user_input)?,
+        &schedule,
+    ).await?;
+    let (_, exit_code_task_id) = result[0];
+    let (_, sentinel_task_id) = result[1];
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    assert_eq!(task_executor_ctrl.execute().await?, (3, false));
+    let task = TaskBackend::gets_task(tm_platform, exit_code_task_id).await?;
+    assert_eq!(task.stop_ts, None);
+    assert_eq!(task.attempts, 1);
+    assert_eq!(TaskBackend::gets_task(tm_platform, sentinel_task_id).await?.stop_ts, None);
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("the failed task is retried");
+    assert_eq!(task_executor_ctrl.execute().await?, (3, false));
+    let task = TaskBackend::gets_task(tm_platform, exit_code_task_id).await?;
+    assert_eq!(task.attempts, 2);
+    assert_eq!(task.failure(), Some(TaskFailure::Exit(3)));
+    assert_eq!(
+        TaskBackend::gets_task(tm_platform, sentinel_task_id).await?.failure(),
+        Some(TaskFailure::DependencyFailed),
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_exposure_ctrl_view_task_statuses() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+
+    let exposure = platform.create_exposure(
+        1,
+        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
+    ).await?;
+    let efc = exposure.create_file("if1").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtts[0], vtts[1]],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let user_input = UserInputMap::from([
+        (1, "3".into()),
+    ]);
+    efc.process_vttc_tasks(efvttsc.create_tasks_from_input(&user_input)?).await?;
+
+    let statuses = exposure.view_task_statuses().await?;
+    assert_eq!(
+        statuses.iter()
+            .map(|status| (
+                status.workspace_file_path.as_str(),
+                status.view_key.as_str(),
+                status.task.as_ref().map(|task| task.state),
+            ))
+            .collect::<Vec<_>>(),
+        [
+            ("if1", "exit_code_0", Some(TaskState::Queued)),
+            ("if1", "sentinel_0", Some(TaskState::Queued)),
+        ],
+    );
+    let exit_code_task_id = statuses[0].task_id.expect("task queued");
+
+    while let Some(task_executor_ctrl) = platform.start_task().await? {
+        task_executor_ctrl.execute().await?;
+    }
+    let statuses = exposure.view_task_statuses().await?;
+    let task = statuses[0].task.as_ref().expect("task queued");
+    assert_eq!(task.state, TaskState::Failed);
+    assert_eq!(task.failure, Some(TaskFailure::Exit(3)));
+    assert_eq!(
+        statuses[1].task.as_ref().map(|task| task.state),
+        Some(TaskState::Succeeded),
+    );
+
+    // only the tail of the log starting from a complete line is read
+    let task = exposure.get_view_task(exit_code_task_id).await?;
+    std::fs::write(
+        TaskLog::Stderr.path(&task.basedir),
+        "first line\nsecond line\nfinal line\n",
+    )?;
+    assert_eq!(
+        exposure.read_task_log_tail(exit_code_task_id, TaskLog::Stderr, 16).await?,
+        "final line\n",
+    );
+    assert_eq!(
+        exposure.read_task_log_tail(exit_code_task_id, TaskLog::Stderr, 1024).await?,
+        "first line\nsecond line\nfinal line\n",
+    );
+
+    // tasks not of the views for this exposure are unknown
+    let other = platform.create_exposure(
+        1,
+        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
+    ).await?;
+    assert!(other.view_task_statuses().await?.is_empty());
+    let err = other.read_task_log_tail(exit_code_task_id, TaskLog::Stderr, 1024)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        PlatformError::CtrlError(CtrlError::UnknownTask(id)) if id == exit_code_task_id
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_task_executor_ctrl_timeout() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+
+    let exposure = platform.create_exposure(
+        1,
+        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
+    ).await?;
+    let efc = exposure.create_file("if1").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtts[3]],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let user_input = UserInputMap::from([
+        (4, "60".into()),
+    ]);
+    efc.process_vttc_tasks(efvttsc.create_tasks_from_input(&user_input)?).await?;
+
+    // the sleeper task template has a 1 second timeout
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    let (code, result) = task_executor_ctrl.execute().await?;
+    assert_eq!(code, EXIT_TIMED_OUT);
+    assert!(!result);
+    let task = TaskBackend::gets_task(platform.tm_platform.as_ref(), 1).await?;
+    assert_eq!(task.exit_status, Some(EXIT_TIMED_OUT.into()));
+    assert!(task.stop_ts.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_task_executor_ctrl_abort() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+
+    let exposure = platform.create_exposure(
+        1,
+        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
+    ).await?;
+    let efc = exposure.create_file("if1").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtts[3]],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let user_input = UserInputMap::from([
+        (4, "60".into()),
+    ]);
+    efc.process_vttc_tasks(efvttsc.create_tasks_from_input(&user_input)?).await?;
+
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    let (abort_sender, abort_receiver) = broadcast::channel(1);
+    abort_sender.send(())?;
+    let (code, result) = task_executor_ctrl.execute_abortable(abort_receiver).await?;
+    assert_eq!(code, EXIT_ABORTED);
+    assert!(!result);
+    let task = TaskBackend::gets_task(platform.tm_platform.as_ref(), 1).await?;
+    assert_eq!(task.exit_status, Some(EXIT_ABORTED.into()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_recover_orphans() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let tm_platform = platform.tm_platform.as_ref();
+    let task_template = TaskTemplateBackend::adds_task_template(
+        tm_platform,
+        serde_json::from_str(r#"{
+            "bin_path": "/bin/true",
+            "version_id": "1.0.0",
+            "args": []
+        }"#)?,
+    ).await?;
+    let new_task = || Task {
+        task_template_id: task_template.id,
+        bin_path: "/bin/true".into(),
+        basedir: "/tmp".into(),
+        args: Some(vec![].into()),
+        .. Default::default()
+    };
+
+    // a pid that is no longer alive
+    let mut child = std::process::Command::new("/bin/true").spawn()?;
+    let dead_pid = child.id();
+    child.wait()?;
+    let live_pid = std::process::id();
+
+    let mut ids = Vec::new();
+    for pid in [dead_pid, live_pid, dead_pid] {
+        let task = platform.adds_task(new_task()).await?;
+        TaskBackend::start(tm_platform).await?
+            .expect("a task is queued");
+        TaskBackend::run(tm_platform, task.id, pid.into(), host_id()).await?;
+        ids.push(task.id);
+    }
+
+    assert!(recover_orphans(tm_platform, OrphanPolicy::Ignore).await?.is_empty());
+    assert_eq!(TaskBackend::list_running(tm_platform).await?.len(), 3);
+
+    // only the tasks with dead pids are recovered
+    TaskBackend::complete(tm_platform, ids[2], 0).await?;
+    assert_eq!(recover_orphans(tm_platform, OrphanPolicy::Fail).await?, [ids[0]]);
+    let task = TaskBackend::gets_task(tm_platform, ids[0]).await?;
+    assert_eq!(task.exit_status, Some(EXIT_ORPHANED.into()));
+    assert_eq!(
+        TaskBackend::list_running(tm_platform).await?
+            .iter()
+            .map(|task| task.id)
+            .collect::<Vec<_>>(),
+        [ids[1]],
+    );
+
+    let task = platform.adds_task(new_task()).await?;
+    TaskBackend::start(tm_platform).await?
+        .expect("a task is queued");
+    TaskBackend::run(tm_platform, task.id, dead_pid.into(), host_id()).await?;
+    assert_eq!(recover_orphans(tm_platform, OrphanPolicy::Requeue).await?, [task.id]);
+    let restarted = TaskBackend::start(tm_platform).await?
+        .expect("the orphaned task was requeued");
+    assert_eq!(restarted.id, task.id);
+    assert_eq!(restarted.pid, None);
+    TaskBackend::complete(tm_platform, task.id, 0).await?;
+
+    // only the tasks started on this host are recovered, where the ones
+    // from a previous boot are orphaned even if their pid is now in use
+    let (hostname, _) = host_id().rsplit_once('/').expect("host id has a boot id");
+    let mut ids = Vec::new();
+    for host in ["elsewhere/boot".to_string(), format!("{hostname}/previous-boot")] {
+        let task = platform.adds_task(new_task()).await?;
+        TaskBackend::start(tm_platform).await?
+            .expect("a task is queued");
+        TaskBackend::run(tm_platform, task.id, live_pid.into(), &host).await?;
+        ids.push(task.id);
+    }
+    assert_eq!(recover_orphans(tm_platform, OrphanPolicy::Fail).await?, [ids[1]]);
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_task_executor_abort_kills_process_group() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let basedir = tempfile::tempdir()?;
+    let pid_file = basedir.path().join("pid");
+    let task_template = TaskTemplateBackend::adds_task_template(
+        platform.tm_platform.as_ref(),
+        serde_json::from_str(r#"{
+            "bin_path": "/bin/sh",
+            "version_id": "1.0.0",
+            "args": []
+        }"#)?,
+    ).await?;
+    platform.adds_task(Task {
+        task_template_id: task_template.id,
+        bin_path: "/bin/sh".into(),
+        basedir: basedir.path().display().to_string(),
+        args: Some(vec![
+            TaskArg { arg: "-c".into(), .. Default::default() },
+            TaskArg {
+                arg: format!("sleep 60 & echo $! > {}; wait", pid_file.display()),
+                .. Default::default()
+            },
+        ].into()),
+        .. Default::default()
+    }).await?;
+
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    let (abort_sender, abort_receiver) = broadcast::channel(1);
+    let pid_path = pid_file.clone();
+    tokio::spawn(async move {
+        while !std::fs::read_to_string(&pid_path).is_ok_and(|pid| pid.ends_with('\n')) {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        abort_sender.send(())
+    });
+    let (code, _) = task_executor_ctrl.execute_abortable(abort_receiver).await?;
+    assert_eq!(code, EXIT_ABORTED);
+
+    // the process spawned by the task went down with it, though it may
+    // linger as a zombie until reaped by init.
+    let pid = read_to_string(&pid_file)?;
+    let stat = format!("/proc/{}/stat", pid.trim());
+    for _ in 0..100 {
+        match read_to_string(&stat) {
+            Ok(stat) if !stat.contains(") Z ") => {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await
+            }
+            _ => return Ok(()),
+        }
+    }
+    panic!("process {} spawned by the task is still running", pid.trim());
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_task_executor_sandbox() -> anyhow::Result<()> {
+    build_test_binary_once!(hog, "../testing");
+    let hog = path_to_hog().into_string()
+        .expect("failed to build testing/hog");
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let tm_platform = platform.tm_platform.as_ref();
+    let basedir = tempfile::TempDir::new()?;
+    let outside = tempfile::TempDir::new()?;
+
+    let run = |sandbox: &str, args: Vec<String>| {
+        let platform = &platform;
+        let template = format!(r#"{{
+            "bin_path": "{hog}",
+            "version_id": "1.0.0",
+            "sandbox": {sandbox},
+            "args": []
+        }}"#);
+        let basedir = basedir.path().display().to_string();
+        async move {
+            let task_template = TaskTemplateBackend::adds_task_template(
+                tm_platform,
+                serde_json::from_str(&template)?,
+            ).await?;
+            let task = platform.adds_task(Task {
+                task_template_id: task_template.id,
+                bin_path: task_template.bin_path,
+                basedir,
+                args: Some(args.into_iter()
+                    .map(|arg| arg.as_str().into())
+                    .collect::<Vec<_>>()
+                    .into()),
+                .. Default::default()
+            }).await?;
+            // executed directly as these tasks are not for any views.
+            let (code, _) = tm_platform.start_task().await?
+                .map(TMPlatformExecutorInstance::from)
+                .expect("a task is queued")
+                .execute()
+                .await?;
+            let task = TaskBackend::gets_task(tm_platform, task.id).await?;
+            assert_eq!(task.exit_status, Some(code.into()));
+            anyhow::Ok(task.failure())
+        }
+    };
+
+    assert_eq!(
+        run(r#"{"cpu_time": 1}"#, vec!["cpu".into()]).await?,
+        Some(TaskFailure::CpuLimit),
+    );
+    assert_eq!(
+        run(r#"{"output_size": 4096}"#, vec!["output".into(), "65536".into()]).await?,
+        Some(TaskFailure::OutputLimit),
+    );
+    assert_eq!(
+        run(r#"{"seccomp": true}"#, vec!["chroot".into()]).await?,
+        Some(TaskFailure::SyscallDenied),
+    );
+
+    // processes may still be spawned, as clone3 falls back to clone
+    assert_eq!(
+        run(r#"{"seccomp": true, "isolate": true}"#, vec!["spawn".into()]).await?,
+        None,
+    );
+
+    // without the sandbox, writes anywhere may happen
+    let unrestricted = outside.path().join("unrestricted");
+    assert_eq!(
+        run("null", vec!["write".into(), unrestricted.display().to_string()]).await?,
+        None,
+    );
+    assert!(unrestricted.exists());
+
+    // isolation leaves out everything else from the root
+    let isolated = outside.path().join("isolated");
+    assert_eq!(
+        run(r#"{"isolate": true}"#, vec!["write".into(), isolated.display().to_string()]).await?,
+        Some(TaskFailure::Exit(1)),
+    );
+    assert!(!isolated.exists());
+    assert_eq!(
+        std::fs::read_to_string(basedir.path().join("temp").join("stderr"))?.trim(),
+        "No such file or directory (os error 2)",
+    );
+    // the program is available but only for reading
+    let beside = std::path::Path::new(&hog).with_file_name("beside");
+    assert_eq!(
+        run(r#"{"isolate": true}"#, vec!["write".into(), beside.display().to_string()]).await?,
+        Some(TaskFailure::Exit(1)),
+    );
+    assert!(!beside.exists());
+    assert_eq!(
+        std::fs::read_to_string(basedir.path().join("temp").join("stderr"))?.trim(),
+        "Read-only file system (os error 30)",
+    );
+    // with its own pid namespace, where the task follows its init
+    assert_eq!(
+        run(r#"{"isolate": true}"#, vec!["pid".into()]).await?,
+        None,
+    );
+    assert_eq!(
+        std::fs::read_to_string(basedir.path().join("temp").join("stdout"))?.trim(),
+        "2",
+    );
+    // the restrictions are relayed through the init
+    assert_eq!(
+        run(r#"{"isolate": true, "cpu_time": 1}"#, vec!["cpu".into()]).await?,
+        Some(TaskFailure::CpuLimit),
+    );
+    let within = basedir.path().join("within");
+    assert_eq!(
+        run(r#"{"isolate": true}"#, vec!["write".into(), within.display().to_string()]).await?,
+        None,
+    );
+    assert!(within.exists());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_resolve_exposure_file_view_read_blob() -> anyhow::Result<()> {
     let (root, platform) = create_sqlite_platform().await?;
@@ -1720,29 +2291,540 @@ async fn test_resolve_exposure_file_view_read_blob() -> anyhow::Result<()> {
 }
 
 #[tokio::test]
-async fn test_multiple_exposure_files() -> anyhow::Result<()> {
+async fn test_view_task_cached_output() -> anyhow::Result<()> {
     let (_reporoot, platform) = create_sqlite_platform().await?;
-    let exposure = platform.create_exposure(
-        1,
-        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
-    ).await?;
-    let efc1 = exposure.create_file("if1").await?;
-    let efc2 = exposure.create_file("README").await?;
-
-    assert_eq!(efc1.exposure_file().id(), 1);
-    assert_eq!(efc2.exposure_file().id(), 2);
-
-    Ok(())
-}
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    let user_input = UserInputMap::from([]);
 
-#[tokio::test]
-async fn test_exposure_file_registry() -> anyhow::Result<()> {
-    let (_reporoot, platform) = create_sqlite_platform().await?;
     let exposure = platform.create_exposure(
-        1,
-        "42845247d1a2af1bf5a0f09c85e254ba78992c2f",
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
     ).await?;
-    let efc = exposure.create_file("if1").await?;
+    let efc = exposure.create_file("dir1/nested/file_c").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtts[2]],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let result = efc.process_vttc_tasks(
+        efvttsc.create_tasks_from_input(&user_input)?
+    ).await?;
+    assert_eq!(result.len(), 1);
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    assert_eq!(task_executor_ctrl.execute().await?, (0, true));
+
+    // another exposure of the same commit reuses the output of the view
+    // without having its task queued.
+    let other = platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    let other_efc = other.create_file("dir1/nested/file_c").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        other_efc.exposure_file().id(),
+        &[vtts[2]],
+    ).await?;
+    let other_efvttsc = other_efc.build_vttc().await?;
+    let result = other_efc.process_vttc_tasks(
+        other_efvttsc.create_tasks_from_input(&user_input)?
+    ).await?;
+    assert_eq!(result, []);
+    assert!(platform.start_task().await?.is_none());
+    let (_, efvc) = other
+        .resolve_file_view("dir1/nested/file_c/iorw")
+        .await;
+    let efvc = efvc?;
+    assert_eq!(efvc.view_key(), Some("iorw"));
+    assert_eq!(efvc.read_blob("size").await?, "13".as_bytes().to_vec());
+
+    // the output for a different file is not reused.
+    let other_efc = other.create_file("dir1/nested/file_a").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        other_efc.exposure_file().id(),
+        &[vtts[2]],
+    ).await?;
+    let other_efvttsc = other_efc.build_vttc().await?;
+    let result = other_efc.process_vttc_tasks(
+        other_efvttsc.create_tasks_from_input(&user_input)?
+    ).await?;
+    assert_eq!(result.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_view_task_cached_output_failed() -> anyhow::Result<()> {
+    let (reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    let tracked = |task_id: i64| reporoot.path()
+        .join("data")
+        .join("cache")
+        .join("view")
+        .join("task")
+        .join(task_id.to_string());
+
+    let exposure = platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    let efc = exposure.create_file("dir1/nested/file_c").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtts[1]],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let user_input = UserInputMap::from([
+        (1, "3".into()),
+    ]);
+    let schedule = TaskSchedule {
+        max_retries: 1,
+        ..Default::default()
+    };
+    let result = efc.process_vttc_tasks_with(
+        efvttsc.create_tasks_from_input(&user_input)?,
+        &schedule,
+    ).await?;
+    let (_, task_id) = result[0];
+    assert!(tracked(task_id).exists());
+
+    // the key is kept for the task returned to the queue for a retry...
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    assert_eq!(task_executor_ctrl.execute().await?, (3, false));
+    assert!(tracked(task_id).exists());
+
+    // ... but not once the task has failed for good.
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("the failed task is retried");
+    assert_eq!(task_executor_ctrl.execute().await?, (3, false));
+    assert!(!tracked(task_id).exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_supersede_task_template() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    let mcp = platform.mc_platform.as_ref();
+    let tmp = platform.tm_platform.as_ref();
+    let profile_id = ProfileBackend::insert_profile(mcp, "Profile", "").await?;
+    ProfileViewsBackend::insert_profile_views(mcp, profile_id, vtts[2]).await?;
+
+    let exposure = platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    let efc = exposure.create_file("dir1/nested/file_c").await?;
+    efc.set_vttprofile(platform.get_view_task_template_profile(profile_id).await?).await?;
+    let efvttsc = efc.build_vttc().await?;
+    efc.process_vttc_tasks(
+        efvttsc.create_tasks_from_input(&UserInputMap::from([]))?
+    ).await?;
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    assert_eq!(task_executor_ctrl.execute().await?, (0, true));
+    assert_eq!(platform.list_outdated_views(None).await?, []);
+
+    // publish a new version of the task template for the iorw view.
+    let old_id = platform.get_view_task_template(vtts[2]).await?.task_template_id;
+    let mut task_template = TaskTemplateBackend::get_task_template_by_id(tmp, old_id).await?;
+    task_template.version_id = "1.0.1".into();
+    let new_id = TaskTemplateBackend::adds_task_template(tmp, task_template).await?.id;
+    assert_eq!(platform.supersede_task_template(old_id, new_id).await?, [vtts[2]]);
+    assert_eq!(
+        TaskTemplateBackend::get_task_template_by_id(tmp, old_id).await?.superceded_by_id,
+        Some(new_id),
+    );
+    let vttp = platform.get_view_task_template_profile(profile_id).await?;
+    assert_eq!(vttp.view_task_templates[0].task_template_id, new_id);
+
+    // superseding again with the same version is permitted, but not with
+    // another version.
+    assert_eq!(platform.supersede_task_template(old_id, new_id).await?.len(), 0);
+    let err = platform.supersede_task_template(old_id, vtts[0]).await.unwrap_err();
+    assert!(matches!(
+        err,
+        PlatformError::CtrlError(CtrlError::TaskTemplateSupersession(id, _)) if id == old_id
+    ));
+
+    let outdated = platform.list_outdated_views(None).await?;
+    assert_eq!(outdated.len(), 1);
+    assert_eq!(outdated[0].workspace_file_path, "dir1/nested/file_c");
+    assert_eq!(outdated[0].view_key, "iorw");
+    assert_eq!(outdated[0].task_template_id, new_id);
+    assert_eq!(outdated[0].built_task_template_id, Some(old_id));
+    assert_eq!(platform.list_outdated_views(Some(old_id)).await?, []);
+    assert_eq!(platform.list_outdated_views(Some(new_id)).await?, outdated);
+
+    assert_eq!(platform.rebuild_outdated_views_with(
+        None,
+        &TaskSchedule::default(),
+    ).await?, 1);
+    let task_executor_ctrl = platform.start_task().await?
+        .expect("a task is queued");
+    assert_eq!(task_executor_ctrl.execute().await?, (0, true));
+    assert_eq!(platform.list_outdated_views(None).await?, []);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_task_template_dry_run() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+
+    // only registries provided by the platform are referenced.
+    let iorw = platform.get_view_task_template(vtts[2]).await?
+        .task_template
+        .expect("task_template provided");
+    platform.validate_task_template(&iorw)?;
+    let mut task_template = iorw.clone();
+    task_template.args.as_mut().expect("args provided")[1].choice_source =
+        Some("no_such_registry".into());
+    assert_eq!(
+        platform.validate_task_template(&task_template).unwrap_err().to_string(),
+        "TaskTemplateErrors: [args[1] has choice_source `no_such_registry` that is \
+        not a known registry, ]",
+    );
+    // the user input is passed to the program as is.
+    let exit_code = platform.get_view_task_template(vtts[1]).await?
+        .task_template
+        .expect("task_template provided");
+    assert_eq!(
+        platform.validate_task_template(&exit_code).unwrap_err().to_string(),
+        "TaskTemplateErrors: [args[0] accepts user input that is neither a fixed \
+        choice nor joined with a flag, ]",
+    );
+
+    // the argv is rendered without anything being queued.
+    let exposure = platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    let efc = exposure.create_file("dir1/nested/file_c").await?;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtts[1], vtts[2]],
+    ).await?;
+    let user_input = UserInputMap::from([
+        (exit_code.args.as_ref().expect("args provided")[0].id, "3".into()),
+    ]);
+    let efvttsc = efc.build_vttc().await?;
+    let vttc_tasks = efvttsc.create_tasks_from_input(&user_input)?;
+    assert_eq!(vttc_tasks[0].view_key(), "exit_code_0");
+    assert_eq!(vttc_tasks[0].task().argv(), [exit_code.bin_path.as_str(), "3"]);
+    assert_eq!(vttc_tasks[1].view_key(), "iorw");
+    let argv = vttc_tasks[1].task().argv();
+    assert_eq!(argv.len(), 3);
+    assert_eq!(argv[0], iorw.bin_path);
+    assert!(argv[1].ends_with("files/dir1/nested/file_c"));
+    assert!(argv[2].ends_with("iorw/work"));
+    assert!(platform.start_task().await?.is_none());
+
+    Ok(())
+}
+
+struct Citations;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for Citations {
+    fn name(&self) -> &str {
+        "citation"
+    }
+
+    fn description(&self) -> &str {
+        "the citations for the exposure"
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        _: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        Ok(ProvidedChoices::new(vec![format!("citation_{}", context.exposure_id)]))
+    }
+}
+
+#[tokio::test]
+async fn test_choice_registry_providers() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    assert_eq!(platform.choice_sources(), [
+        "exposure_file",
+        "exposure_id",
+        "files",
+        "files_default",
+        "files_glob",
+        "workspace_file_path",
+        "workspace_tags",
+        "working_dir",
+    ]);
+
+    let mut task_template = platform.get_view_task_template(vtts[2]).await?
+        .task_template
+        .expect("task_template provided");
+    task_template.args.as_mut().expect("args provided")[0].choice_source =
+        Some("citation".into());
+    assert!(platform.validate_task_template(&task_template).is_err());
+    platform.register_choice_registry_provider(Citations);
+    assert!(platform.choice_sources().contains(&"citation".to_string()));
+    platform.validate_task_template(&task_template)?;
+
+    let exposure = platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    let efc = exposure.create_file("dir1/nested/file_c").await?;
+    let context = ExposureFileChoiceContext::try_from(&efc)?;
+    let registry = platform.choice_registry_providers().prepare(&context, [
+        "citation",
+        "files_glob:dir1/**",
+        "files_glob",
+    ])?;
+    let citations: UserChoiceRefs = registry.lookup("citation")
+        .expect("has citation registry")
+        .into();
+    assert_eq!(citations.as_slice(), &[UserChoiceRef("citation_1", false)]);
+    let files: UserChoiceRefs = registry.lookup("files_glob:dir1/**")
+        .expect("has files_glob:dir1/** registry")
+        .into();
+    assert!(files.contains(&UserChoiceRef("dir1/nested/file_c", false)));
+    assert!(files.iter().all(|choice| choice.0.starts_with("dir1/")));
+    // the parameter is required by the provider.
+    assert!(registry.lookup("files_glob").is_none());
+
+    // a view task template referencing a parameterized source will have
+    // the registry prepared for the views of the exposure file.
+    let mut vtt = platform.get_view_task_template(vtts[2]).await?;
+    vtt.view_key = "iorw_glob".to_string();
+    let args = vtt.task_template.as_mut()
+        .expect("task_template provided")
+        .args.as_mut()
+        .expect("args provided");
+    args[0].prompt = Some("File".into());
+    args[0].default = None;
+    args[0].choice_source = Some("files_glob:dir1/**".into());
+    platform.validate_task_template(vtt.task_template.as_ref().expect("task_template provided"))?;
+    let vtt_id = platform.adds_view_task_template(vtt).await?;
+    let arg_id = platform.get_view_task_template(vtt_id).await?
+        .task_template
+        .expect("task_template provided")
+        .args
+        .expect("args provided")[0]
+        .id;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtt_id],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let user_input = UserInputMap::from([
+        (arg_id, "dir1/nested/file_c".into()),
+    ]);
+    let vttc_tasks = efvttsc.create_tasks_from_input(&user_input)?;
+    let argv = vttc_tasks[0].task().argv();
+    assert!(argv[1].ends_with("files/dir1/nested/file_c"));
+    assert!(argv[2].ends_with("iorw_glob/work"));
+    let user_input = UserInputMap::from([
+        (arg_id, "README".into()),
+    ]);
+    assert!(efvttsc.create_tasks_from_input(&user_input).is_err());
+
+    Ok(())
+}
+
+/// `release:<prefix>` lists the tags of the workspace with the prefix by
+/// their names, building on the tags provided for every exposure file.
+struct Releases;
+
+impl ChoiceRegistryProvider<ExposureFileChoiceContext, PlatformError> for Releases {
+    fn name(&self) -> &str {
+        "release"
+    }
+
+    fn description(&self) -> &str {
+        "the tags of the workspace with the prefix"
+    }
+
+    fn parameter(&self) -> Option<&str> {
+        Some("the prefix of the tags")
+    }
+
+    fn provide(
+        &self,
+        context: &ExposureFileChoiceContext,
+        parameter: Option<&str>,
+    ) -> Result<ProvidedChoices, PlatformError> {
+        let prefix = parameter.unwrap_or_default();
+        Ok(ProvidedChoices::new(context.tags
+            .iter()
+            .filter_map(|(name, _)| name.strip_prefix("refs/tags/"))
+            .filter(|name| name.starts_with(prefix))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_choice_registry_workspace_tags() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    let commit_id = "083b775d81ec9b66796edbbdce4d714bb2ddc355";
+    let repo = gix::open(platform.repo_root().join("1"))?;
+    for (name, target) in [
+        ("v0", "8dd710b6b5cf607711bc44f5ca0204565bf7cc35"),
+        ("v1", commit_id),
+        ("draft", commit_id),
+    ] {
+        repo.tag_reference(
+            name,
+            gix::ObjectId::from_hex(target.as_bytes())?,
+            gix::refs::transaction::PreviousValue::MustNotExist,
+        )?;
+    }
+    let exposure = platform.create_exposure(1, commit_id).await?;
+    let efc = exposure.create_file("if1").await?;
+    let context = ExposureFileChoiceContext::try_from(&efc)?;
+
+    // the tags pointing to the commit of the exposure are selected.
+    let registry = platform.choice_registry_providers().prepare(&context, [
+        "workspace_tags",
+    ])?;
+    let tags: UserChoiceRefs = registry.lookup("workspace_tags")
+        .expect("has workspace_tags registry")
+        .into();
+    let mut tags = tags.iter().cloned().collect::<Vec<_>>();
+    tags.sort();
+    assert_eq!(tags, [
+        UserChoiceRef("draft", true),
+        UserChoiceRef("v0", false),
+        UserChoiceRef("v1", true),
+    ]);
+
+    // a provider registered from outside of the platform may build on the
+    // tags, with the task templates referencing it validated against it.
+    let mut vtt = platform.get_view_task_template(vtts[1]).await?;
+    vtt.view_key = "exit_code_release".to_string();
+    let args = vtt.task_template.as_mut()
+        .expect("task_template provided")
+        .args.as_mut()
+        .expect("args provided");
+    args[0].choice_fixed = true;
+    args[0].choice_source = Some("release:v".into());
+    let task_template = vtt.task_template.as_ref().expect("task_template provided");
+    assert!(platform.validate_task_template(task_template).is_err());
+    platform.register_choice_registry_provider(Releases);
+    platform.validate_task_template(task_template)?;
+
+    let vtt_id = platform.adds_view_task_template(vtt).await?;
+    let arg_id = platform.get_view_task_template(vtt_id).await?
+        .task_template
+        .expect("task_template provided")
+        .args
+        .expect("args provided")[0]
+        .id;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtt_id],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let user_input = UserInputMap::from([
+        (arg_id, "v1".into()),
+    ]);
+    let vttc_tasks = efvttsc.create_tasks_from_input(&user_input)?;
+    assert_eq!(vttc_tasks[0].task().argv()[1], "v1");
+    let user_input = UserInputMap::from([
+        (arg_id, "draft".into()),
+    ]);
+    assert!(efvttsc.create_tasks_from_input(&user_input).is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multiple_values_arg() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    let exposure = platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    let efc = exposure.create_file("dir1/nested/file_c").await?;
+
+    let mut vtt = platform.get_view_task_template(vtts[2]).await?;
+    vtt.view_key = "iorw_multiple".to_string();
+    let args = vtt.task_template.as_mut()
+        .expect("task_template provided")
+        .args.as_mut()
+        .expect("args provided");
+    args[0].prompt = Some("Files".into());
+    args[0].default = None;
+    args[0].choice_source = Some("files".into());
+    args[0].multiple = true;
+    platform.validate_task_template(vtt.task_template.as_ref().expect("task_template provided"))?;
+    let vtt_id = platform.adds_view_task_template(vtt).await?;
+    let arg_id = platform.get_view_task_template(vtt_id).await?
+        .task_template
+        .expect("task_template provided")
+        .args
+        .expect("args provided")[0]
+        .id;
+    ExposureTaskTemplateBackend::set_file_templates(
+        platform.mc_platform.as_ref(),
+        efc.exposure_file().id(),
+        &[vtt_id],
+    ).await?;
+    let efvttsc = efc.build_vttc().await?;
+    let user_input = UserInputMap::from([
+        (arg_id, ["README", "dir1/nested/file_c"].into()),
+    ]);
+    let vttc_tasks = efvttsc.create_tasks_from_input(&user_input)?;
+    let argv = vttc_tasks[0].task().argv();
+    assert_eq!(argv.len(), 4);
+    assert!(argv[1].ends_with("files/README"));
+    assert!(argv[2].ends_with("files/dir1/nested/file_c"));
+    assert!(argv[3].ends_with("iorw_multiple/work"));
+
+    let user_input = UserInputMap::from([
+        (arg_id, ["README", "no_such_file"].into()),
+    ]);
+    assert!(efvttsc.create_tasks_from_input(&user_input).is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multiple_exposure_files() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let exposure = platform.create_exposure(
+        1,
+        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
+    ).await?;
+    let efc1 = exposure.create_file("if1").await?;
+    let efc2 = exposure.create_file("README").await?;
+
+    assert_eq!(efc1.exposure_file().id(), 1);
+    assert_eq!(efc2.exposure_file().id(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_exposure_file_registry() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let exposure = platform.create_exposure(
+        1,
+        "42845247d1a2af1bf5a0f09c85e254ba78992c2f",
+    ).await?;
+    let efc = exposure.create_file("if1").await?;
     let registry: PreparedChoiceRegistry = (&efc).try_into()?;
     let files: UserChoiceRefs = registry.lookup("files")
         .expect("has files registry")
@@ -1894,6 +2976,380 @@ async fn test_exposure_alias_before_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_platform_supersede_workspace() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    platform.add_workspace_alias(1, "first").await?;
+    platform.add_workspace_alias(2, "second").await?;
+    platform.supersede_workspace(1, Some(2)).await?;
+    platform.supersede_workspace(2, Some(3)).await?;
+    assert_eq!(platform.mc_platform.get_workspace_by_id(1).await?.superceded_by_id, Some(2));
+    assert_eq!(platform.resolve_workspace_successor(1).await?, Some(3));
+    assert_eq!(platform.resolve_workspace_successor(2).await?, Some(3));
+    assert_eq!(platform.resolve_workspace_successor(3).await?, None);
+    // the aliases follow the supersessions to the current version
+    assert_eq!(platform.mc_platform.resolve_alias("workspace", "first").await?, Some(3));
+    assert_eq!(platform.mc_platform.resolve_alias("workspace", "second").await?, Some(3));
+
+    let err = platform.supersede_workspace(3, Some(1)).await.unwrap_err();
+    assert!(matches!(
+        err,
+        PlatformError::CtrlError(CtrlError::SupersessionCycle(3, 1)),
+    ));
+    let err = platform.supersede_workspace(3, Some(3)).await.unwrap_err();
+    assert!(matches!(
+        err,
+        PlatformError::CtrlError(CtrlError::SupersessionCycle(3, 3)),
+    ));
+    assert!(platform.supersede_workspace(4, Some(1)).await.is_err());
+    assert!(platform.supersede_workspace(1, Some(4)).await.is_err());
+
+    platform.supersede_workspace(2, None).await?;
+    platform.supersede_workspace(3, Some(1)).await?;
+    assert_eq!(platform.mc_platform.resolve_alias("workspace", "first").await?, Some(1));
+
+    // a cycle formed without the platform (e.g. directly through the
+    // backend) neither stalls the checks nor resolves to a successor.
+    platform.mc_platform.set_workspace_superceded_by(1, Some(3)).await?;
+    assert_eq!(platform.resolve_workspace_successor(3).await?, None);
+    platform.supersede_workspace(2, Some(3)).await?;
+    assert_eq!(platform.resolve_workspace_successor(2).await?, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_archive_workspace() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let exposure = platform.create_exposure(
+        1,
+        "083b775d81ec9b66796edbbdce4d714bb2ddc355",
+    ).await?;
+    let exposure_id = exposure.exposure().id();
+    platform.ac_platform.set_wf_state_for_res("/workspace/1/", State::Published).await?;
+    platform.ac_platform.set_wf_state_for_res(
+        &format!("/exposure/{exposure_id}/"), State::Published).await?;
+    platform.ac_platform.set_wf_state_for_res("/workspace/2/", State::Private).await?;
+    platform.add_workspace_alias(1, "archived").await?;
+
+    assert!(platform.archive_workspace(1).await?);
+    assert!(!platform.archive_workspace(1).await?);
+    assert!(!platform.repo_root().join("1").exists());
+    assert!(platform.repo_root().join(".archive/1").exists());
+    assert_eq!(
+        WorkspaceBackend::list_workspaces(platform.mc_platform.as_ref()).await?
+            .iter()
+            .map(|workspace| workspace.id)
+            .collect::<Vec<_>>(),
+        [2, 3],
+    );
+    assert_eq!(
+        platform.ac_platform.get_wf_state_for_res("/workspace/1/").await?,
+        State::Expired,
+    );
+    assert_eq!(
+        platform.ac_platform.get_wf_state_for_res(
+            &format!("/exposure/{exposure_id}/")).await?,
+        State::Expired,
+    );
+
+    // archived workspaces may not succeed others
+    let err = platform.supersede_workspace(2, Some(1)).await.unwrap_err();
+    assert!(matches!(
+        err,
+        PlatformError::CtrlError(CtrlError::WorkspaceArchived(1)),
+    ));
+    // the aliases of the archived workspace are released
+    assert_eq!(platform.mc_platform.resolve_alias("workspace", "archived").await?, None);
+    platform.add_workspace_alias(3, "archived").await?;
+
+    // a workspace without a repository may also be archived
+    assert!(platform.archive_workspace(2).await?);
+    assert_eq!(
+        platform.ac_platform.get_wf_state_for_res("/workspace/2/").await?,
+        State::Private,
+    );
+
+    assert!(platform.restore_workspace(1).await?);
+    assert!(!platform.restore_workspace(1).await?);
+    assert!(platform.repo_root().join("1").exists());
+    assert!(!platform.repo_root().join(".archive/1").exists());
+    assert!(platform.mc_platform.get_workspace_by_id(1).await?.archived_ts.is_none());
+    // expiry is not undone by the restoration
+    assert_eq!(
+        platform.ac_platform.get_wf_state_for_res("/workspace/1/").await?,
+        State::Expired,
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_receive_workspace() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let commit_id = "8ae6e9af37c8bd78614545d0ab807348fc46dcab";
+    // simulate a push of a new tag into the repository
+    let repo = gix::open(platform.repo_root().join("3"))?;
+    repo.tag_reference(
+        "pushed",
+        gix::ObjectId::from_hex(commit_id.as_bytes())?,
+        gix::refs::transaction::PreviousValue::MustNotExist,
+    )?;
+    assert_eq!(platform.receive_workspace(Agent::Anonymous, 3, false).await?, 0);
+    let tags = platform.mc_platform.get_workspace_tags(3).await?;
+    assert!(tags.iter()
+        .any(|tag| tag.name == "refs/tags/pushed" && tag.commit_id == commit_id));
+
+    // exposures without any views have nothing to rebuild
+    platform.create_exposure(3, commit_id).await?;
+    assert_eq!(platform.receive_workspace(Agent::Anonymous, 3, true).await?, 0);
+
+    // only a missing repository will be initialized
+    assert_eq!(
+        platform.repo_backend().init_workspace_repo(3).await?,
+        platform.repo_root().join("3"),
+    );
+    assert_eq!(platform.mc_platform.get_workspace_tags(3).await?.len(), tags.len());
+    let id = platform.mc_platform.add_workspace(
+        "https://models.example.com/new/",
+        "new".into(),
+        "".into(),
+    ).await?;
+    assert!(!platform.repo_root().join(id.to_string()).exists());
+    platform.repo_backend().init_workspace_repo(id).await?;
+    assert!(platform.repo_root().join(format!("{id}/HEAD")).exists());
+    platform.receive_workspace(Agent::Anonymous, id, false).await?;
+    assert!(platform.mc_platform.get_workspace_tags(id).await?.is_empty());
+
+    // archived workspaces cannot receive
+    platform.archive_workspace(id).await?;
+    assert!(platform.repo_backend().init_workspace_repo(id).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_receive_workspace_rebuild_exposures() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let vtts = make_example_runnable_task_templates(&platform).await?;
+    let mcp = platform.mc_platform.as_ref();
+    let profile_id = ProfileBackend::insert_profile(mcp, "Profile", "").await?;
+    ProfileViewsBackend::insert_profile_views(mcp, profile_id, vtts[2]).await?;
+    let mut exposure_ids = Vec::new();
+    for _ in 0..2 {
+        let exposure = platform.create_exposure(
+            3,
+            "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+        ).await?;
+        let efc = exposure.create_file("dir1/nested/file_c").await?;
+        efc.set_vttprofile(platform.get_view_task_template_profile(profile_id).await?).await?;
+        exposure_ids.push(exposure.exposure().id());
+    }
+
+    // the editor of the workspace only owns the first exposure
+    let editor = platform.ac_platform.create_user("editor").await?;
+    platform.ac_platform.assign_policy_to_wf_state(State::Private, Role::Owner, "edit").await?;
+    platform.ac_platform.res_grant_role_to_agent("/workspace/3/", &editor, Role::Owner).await?;
+    platform.ac_platform.res_grant_role_to_agent(
+        &format!("/exposure/{}/", exposure_ids[0]), &editor, Role::Owner).await?;
+    for exposure_id in exposure_ids.iter() {
+        platform.ac_platform.set_wf_state_for_res(
+            &format!("/exposure/{exposure_id}/"), State::Private).await?;
+    }
+
+    assert_eq!(platform.receive_workspace(&editor, 3, true).await?, 1);
+    assert_eq!(platform.receive_workspace(Agent::Anonymous, 3, true).await?, 0);
+    // nor may the scopes of the agent exclude the edit action
+    let scoped = ScopedAgent::new(&editor, Some(vec!["".to_string()]));
+    assert_eq!(platform.receive_workspace(scoped, 3, true).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_list_due_workspace_syncs() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let policy = SyncPolicy {
+        sync_interval: 3600,
+        sync_backoff: 300,
+        .. Default::default()
+    };
+    let now = chrono::Utc::now().timestamp();
+    // local workspaces are never scheduled
+    platform.mc_platform.add_workspace("/srv/local", "local".into(), "".into()).await?;
+    assert_eq!(platform.list_due_workspace_syncs(&policy, now).await?, [1, 2, 3]);
+
+    let ticket = platform.mc_platform.begin_sync(1).await?;
+    platform.mc_platform.complete_sync(ticket, WorkspaceSyncStatus::Error).await?;
+    let ticket = platform.mc_platform.begin_sync(2).await?;
+    platform.mc_platform.complete_sync(ticket, WorkspaceSyncStatus::Completed).await?;
+    platform.mc_platform.begin_sync(3).await?;
+    assert!(platform.list_due_workspace_syncs(&policy, now).await?.is_empty());
+    assert_eq!(platform.list_due_workspace_syncs(&policy, now + 400).await?, [1]);
+
+    // consecutive failures extend the backoff
+    let ticket = platform.mc_platform.begin_sync(1).await?;
+    platform.mc_platform.complete_sync(ticket, WorkspaceSyncStatus::Error).await?;
+    assert!(platform.list_due_workspace_syncs(&policy, now + 400).await?.is_empty());
+    assert_eq!(platform.list_due_workspace_syncs(&policy, now + 700).await?, [1]);
+    assert_eq!(platform.list_due_workspace_syncs(&policy, now + 4000).await?, [1, 2]);
+
+    // archived workspaces are not scheduled
+    platform.archive_workspace(1).await?;
+    assert_eq!(platform.list_due_workspace_syncs(&policy, now + 4000).await?, [2]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_list_workspaces_by_remote() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    assert_eq!(platform.list_workspaces_by_remote(&[
+        "https://models.example.com/import1.git",
+        "https://models.example.com/repodata",
+        "https://models.example.com/unknown",
+    ]).await?, [1, 3]);
+    platform.archive_workspace(1).await?;
+    assert_eq!(platform.list_workspaces_by_remote(&[
+        "https://models.example.com/import1",
+    ]).await?, Vec::<i64>::new());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_scheduler_queue() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let url = platform.repo_root().join("3");
+    let id = platform.mc_platform.add_workspace(
+        url.to_str().expect("valid path"),
+        "local".into(),
+        "".into(),
+    ).await?;
+    let (scheduler, queue) = SyncScheduler::new(platform.clone(), SyncPolicy::default());
+    assert!(queue.enqueue(id));
+    // a workspace that is already pending is not queued again.
+    assert!(queue.enqueue(id));
+    let handle = tokio::spawn(scheduler.run());
+    drop(queue);
+    handle.await?;
+
+    let records = platform.mc_platform.get_workspaces_sync_records(id).await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].status, WorkspaceSyncStatus::Completed as i64);
+    assert!(platform.repo_root().join(format!("{id}/HEAD")).exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_list_workspaces_page() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let owner = platform.ac_platform.create_user("owner").await?;
+    platform.ac_platform.create_user("other").await?;
+    platform.ac_platform.res_grant_role_to_agent("/workspace/1/", &owner, Role::Owner).await?;
+    platform.ac_platform.res_grant_role_to_agent("/workspace/3/", &owner, Role::Owner).await?;
+    platform.ac_platform.res_grant_role_to_agent("/workspace/2/", &owner, Role::Reader).await?;
+    platform.ac_platform.set_wf_state_for_res("/workspace/2/", State::Published).await?;
+    platform.ac_platform.set_wf_state_for_res("/workspace/3/", State::Published).await?;
+
+    let ids = |page: Page<pmrcore::workspace::Workspace>| page.entries
+        .iter()
+        .map(|workspace| workspace.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids(platform.list_workspaces_page(&Listing::default()).await?), [1, 2, 3]);
+    assert_eq!(ids(platform.list_workspaces_page(&Listing {
+        state: Some(State::Published),
+        .. Default::default()
+    }).await?), [2, 3]);
+    assert_eq!(ids(platform.list_workspaces_page(&Listing {
+        owner: Some("owner".to_string()),
+        .. Default::default()
+    }).await?), [1, 3]);
+    assert_eq!(ids(platform.list_workspaces_page(&Listing {
+        owner: Some("owner".to_string()),
+        state: Some(State::Published),
+        order: SortOrder::Desc,
+        .. Default::default()
+    }).await?), [3]);
+    assert!(platform.list_workspaces_page(&Listing {
+        owner: Some("other".to_string()),
+        .. Default::default()
+    }).await?.entries.is_empty());
+    assert!(platform.list_workspaces_page(&Listing {
+        owner: Some("nobody".to_string()),
+        .. Default::default()
+    }).await?.entries.is_empty());
+
+    let exposure = platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    let exposure_id = exposure.exposure().id();
+    platform.create_exposure(
+        3,
+        "8ae6e9af37c8bd78614545d0ab807348fc46dcab",
+    ).await?;
+    platform.ac_platform.set_wf_state_for_res(
+        &format!("/exposure/{exposure_id}/"), State::Published).await?;
+    assert_eq!(
+        platform.list_exposures_page(&Listing {
+            state: Some(State::Published),
+            .. Default::default()
+        }).await?
+            .entries
+            .iter()
+            .map(|exposure| exposure.id)
+            .collect::<Vec<_>>(),
+        [exposure_id],
+    );
+    assert_eq!(ids(platform.list_workspaces_page(&Listing {
+        has_exposure: Some(true),
+        .. Default::default()
+    }).await?), [3]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_workflow_transition_scoped() -> anyhow::Result<()> {
+    let (_reporoot, platform) = create_sqlite_platform().await?;
+    let owner = platform.ac_platform.create_user("owner").await?;
+    platform.ac_platform.res_grant_role_to_agent("/workspace/1/", &owner, Role::Owner).await?;
+    platform.ac_platform.set_wf_state_for_res("/workspace/1/", State::Private).await?;
+
+    // a scoped agent without the workflow action may not transition
+    let scoped = ScopedAgent::new(&owner, Some(vec!["".to_string(), "edit".to_string()]));
+    assert!(platform.workflow_transition(
+        scoped,
+        "/workspace/1/".to_string(),
+        State::Pending,
+    ).await?.is_none());
+    assert_eq!(
+        platform.ac_platform.get_wf_state_for_res("/workspace/1/").await?,
+        State::Private,
+    );
+
+    let scoped = ScopedAgent::new(&owner, Some(vec!["".to_string(), "workflow".to_string()]));
+    assert!(platform.workflow_transition(
+        scoped,
+        "/workspace/1/".to_string(),
+        State::Pending,
+    ).await?.is_some());
+    assert_eq!(
+        platform.ac_platform.get_wf_state_for_res("/workspace/1/").await?,
+        State::Pending,
+    );
+
+    // the unscoped agent remains limited by the policy
+    assert!(platform.workflow_transition(
+        &owner,
+        "/workspace/1/".to_string(),
+        State::Published,
+    ).await?.is_none());
+    assert!(platform.workflow_transition(
+        &owner,
+        "/workspace/1/".to_string(),
+        State::Private,
+    ).await?.is_some());
+
+    Ok(())
+}
+
 #[test]
 fn test_send_sync_ctrl() {
     is_send_sync::<pmrctrl::handle::ExposureCtrl>();
@@ -1,5 +1,9 @@
-use pmrcore::platform::{ACPlatform, MCPlatform, PCPlatform, TMPlatform, PlatformConnector};
+use pmrcore::platform::{ACPlatform, MCPlatform, PCPlatform, TMPlatform};
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use pmrcore::platform::PlatformConnector;
 pub use pmrcore::platform::ConnectorOption;
+#[cfg(feature = "postgres")]
+use pmrdb_postgres::PostgresBackend;
 #[cfg(feature = "sqlite")]
 use pmrdb_sqlite::SqliteBackend;
 
@@ -10,16 +14,25 @@ pub struct Error(String);
 
 #[derive(Debug)]
 enum BackendKind {
+    #[cfg(feature = "postgres")]
+    Postgres,
+    #[cfg(feature = "sqlite")]
     Sqlite,
 }
 
 mod display {
-    use super::{BackendKind, Error};
+    #[cfg(any(feature = "postgres", feature = "sqlite"))]
+    use super::BackendKind;
+    use super::Error;
     use std::fmt::{Display, Formatter, Result};
 
+    #[cfg(any(feature = "postgres", feature = "sqlite"))]
     impl Display for BackendKind {
         fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-            match self {
+            match *self {
+                #[cfg(feature = "postgres")]
+                Self::Postgres => "postgres".fmt(f),
+                #[cfg(feature = "sqlite")]
                 Self::Sqlite => "sqlite".fmt(f),
             }
         }
@@ -39,6 +52,12 @@ impl TryFrom<&str> for BackendKind {
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s.split(':').next() {
+            #[cfg(feature = "postgres")]
+            Some("postgres") | Some("postgresql") => Ok(BackendKind::Postgres),
+            #[cfg(not(feature = "postgres"))]
+            Some("postgres") | Some("postgresql") => Err(Error(format!(
+                r#"The feature "postgres" must be enabled for pmrdb in order to connect to {s:?}"#,
+            ))),
             #[cfg(feature = "sqlite")]
             Some("sqlite") => Ok(BackendKind::Sqlite),
             #[cfg(not(feature = "sqlite"))]
@@ -55,12 +74,11 @@ impl Backend {
         opts: ConnectorOption
     ) -> Result<Box<dyn ACPlatform>, Box<dyn std::error::Error + Send + Sync + 'static>> {
         match BackendKind::try_from(opts.url.as_str()) {
+            #[cfg(feature = "postgres")]
+            Ok(BackendKind::Postgres) => Ok(Box::new(PostgresBackend::ac(opts).await?)),
             #[cfg(feature = "sqlite")]
             Ok(BackendKind::Sqlite) => Ok(Box::new(SqliteBackend::ac(opts).await?)),
             Err(e) => Err(Box::new(e)),
-            // or the not features here, as `BackendKind::try_from` should have caught this
-            #[cfg(not(feature = "sqlite"))]
-            _ => unreachable!(),
         }
     }
 
@@ -68,12 +86,10 @@ impl Backend {
         opts: ConnectorOption
     ) -> Result<Box<dyn MCPlatform>, Box<dyn std::error::Error + Send + Sync + 'static>> {
         match BackendKind::try_from(opts.url.as_str()) {
+            #[cfg(feature = "postgres")]
+            Ok(BackendKind::Postgres) => Ok(Box::new(PostgresBackend::mc(opts).await?)),
             #[cfg(feature = "sqlite")]
             Ok(BackendKind::Sqlite) => Ok(Box::new(SqliteBackend::mc(opts).await?)),
-            #[cfg(not(feature = "sqlite"))]
-            Ok(s) => Err(Box::new(Error(format!(
-                "The feature {s:?} must be enabled for pmrdb in order to connect to {:?}", opts.url
-            )))),
             Err(e) => Err(Box::new(e)),
         }
     }
@@ -82,12 +98,10 @@ impl Backend {
         opts: ConnectorOption
     ) -> Result<Box<dyn PCPlatform>, Box<dyn std::error::Error + Send + Sync + 'static>> {
         match BackendKind::try_from(opts.url.as_str()) {
+            #[cfg(feature = "postgres")]
+            Ok(BackendKind::Postgres) => Ok(Box::new(PostgresBackend::pc(opts).await?)),
             #[cfg(feature = "sqlite")]
             Ok(BackendKind::Sqlite) => Ok(Box::new(SqliteBackend::pc(opts).await?)),
-            #[cfg(not(feature = "sqlite"))]
-            Ok(s) => Err(Box::new(Error(format!(
-                "The feature {s:?} must be enabled for pmrdb in order to connect to {:?}", opts.url
-            )))),
             Err(e) => Err(Box::new(e)),
         }
     }
@@ -96,12 +110,10 @@ impl Backend {
         opts: ConnectorOption
     ) -> Result<Box<dyn TMPlatform>, Box<dyn std::error::Error + Send + Sync + 'static>> {
         match BackendKind::try_from(opts.url.as_str()) {
+            #[cfg(feature = "postgres")]
+            Ok(BackendKind::Postgres) => Ok(Box::new(PostgresBackend::tm(opts).await?)),
             #[cfg(feature = "sqlite")]
             Ok(BackendKind::Sqlite) => Ok(Box::new(SqliteBackend::tm(opts).await?)),
-            #[cfg(not(feature = "sqlite"))]
-            Ok(s) => Err(Box::new(Error(format!(
-                "The feature {s:?} must be enabled for pmrdb in order to connect to {:?}", opts.url
-            )))),
             Err(e) => Err(Box::new(e)),
         }
     }
@@ -126,4 +138,14 @@ mod testing {
         assert_eq!(mcp.get_workspace(workspace_id).await?.into_inner().id, workspace_id);
         Ok(())
     }
+
+    #[cfg(feature = "postgres")]
+    #[async_std::test]
+    async fn smoke_postgres() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // simple round-trip testing
+        let mcp = Backend::mc(test_pmr::postgres::create_database().into()).await?;
+        let workspace_id = mcp.add_workspace("title", Some("description"), None).await?;
+        assert_eq!(mcp.get_workspace(workspace_id).await?.into_inner().id, workspace_id);
+        Ok(())
+    }
 }
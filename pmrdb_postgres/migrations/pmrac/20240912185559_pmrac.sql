@@ -0,0 +1,88 @@
+-- Refer to the sqlite migration for the commentary on the design of the
+-- following schema, this is largely the PostgreSQL translation of that.
+
+CREATE TABLE IF NOT EXISTS "user" (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_ts BIGINT NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS user__name ON "user"(name);
+
+CREATE TABLE IF NOT EXISTS user_email (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    email TEXT NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES "user"(id)
+);
+CREATE INDEX IF NOT EXISTS user_email__user_id_email ON user_email(user_id, email);
+CREATE UNIQUE INDEX IF NOT EXISTS user_email__email ON user_email(email);
+
+CREATE TABLE IF NOT EXISTS user_email_bindreq (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    email TEXT NOT NULL,
+    origin_user_id BIGINT,
+    origin TEXT,
+    token TEXT,
+    created_ts BIGINT NOT NULL,
+    rejected BOOLEAN
+);
+CREATE INDEX IF NOT EXISTS user_email_bindreq__token ON user_email_bindreq(token);
+
+CREATE TABLE IF NOT EXISTS user_password (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    password TEXT NOT NULL,
+    created_ts BIGINT NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES "user"(id)
+);
+CREATE INDEX IF NOT EXISTS user_password__user_id ON user_password(user_id);
+
+CREATE TABLE IF NOT EXISTS user_role (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id BIGINT,
+    role TEXT NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES "user"(id)
+);
+CREATE INDEX IF NOT EXISTS user_role__user_id ON user_role(user_id);
+CREATE UNIQUE INDEX IF NOT EXISTS user_role__user_id_role ON user_role(user_id, role);
+
+CREATE TABLE IF NOT EXISTS res_grant (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    res TEXT NOT NULL,
+    user_id BIGINT,
+    role TEXT NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES "user"(id)
+);
+CREATE INDEX IF NOT EXISTS res_grant__res ON res_grant(res);
+CREATE UNIQUE INDEX IF NOT EXISTS res_grant__res_user_id_role ON res_grant(res, user_id, role);
+
+CREATE TABLE IF NOT EXISTS wf_policy (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    state TEXT NOT NULL,
+    role TEXT NOT NULL,
+    action TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS wf_policy__state ON wf_policy(state);
+
+CREATE TABLE IF NOT EXISTS res_wf_state (
+    res TEXT PRIMARY KEY NOT NULL,
+    state TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS res_wf_state_log (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    res TEXT NOT NULL,
+    ts BIGINT NOT NULL,
+    state TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS res_wf_state_log__res_ts ON res_wf_state_log(res, ts);
+
+CREATE TABLE IF NOT EXISTS user_session (
+    token TEXT PRIMARY KEY NOT NULL,
+    user_id BIGINT NOT NULL,
+    origin TEXT NOT NULL,
+    created_ts BIGINT NOT NULL,
+    last_active_ts BIGINT NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES "user"(id)
+);
+CREATE INDEX IF NOT EXISTS user_session__user_id ON user_session(user_id);
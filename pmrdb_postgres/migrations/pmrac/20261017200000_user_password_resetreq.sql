@@ -0,0 +1,15 @@
+-- A request to reset the password of a user, which is only granted once
+-- the token sent to the bound email address is presented back.  The
+-- token is cleared once the request is consumed.
+CREATE TABLE IF NOT EXISTS user_password_resetreq (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    user_email_id BIGINT NOT NULL,
+    origin TEXT NOT NULL,
+    token TEXT,
+    created_ts BIGINT NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES "user"(id),
+    FOREIGN KEY(user_email_id) REFERENCES user_email(id)
+);
+CREATE INDEX IF NOT EXISTS user_password_resetreq__token ON user_password_resetreq(token);
+CREATE INDEX IF NOT EXISTS user_password_resetreq__origin_created_ts ON user_password_resetreq(origin, created_ts);
@@ -0,0 +1,13 @@
+-- An identity asserted by an external identity provider (e.g. through
+-- OpenID Connect) that is linked to a user, such that the user may log
+-- in through the provider.  The subject is only unique per provider.
+CREATE TABLE IF NOT EXISTS user_identity (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    provider TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    created_ts BIGINT NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES "user"(id)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS user_identity__provider_subject ON user_identity(provider, subject);
+CREATE INDEX IF NOT EXISTS user_identity__user_id ON user_identity(user_id);
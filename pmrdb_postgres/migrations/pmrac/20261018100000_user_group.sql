@@ -0,0 +1,22 @@
+-- A named group of users; the roles granted to the group at a resource
+-- apply to all of its members.
+CREATE TABLE IF NOT EXISTS user_group (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_ts BIGINT NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS user_group__name ON user_group(name);
+
+CREATE TABLE IF NOT EXISTS user_group_member (
+    user_group_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    PRIMARY KEY(user_group_id, user_id),
+    FOREIGN KEY(user_group_id) REFERENCES user_group(id),
+    FOREIGN KEY(user_id) REFERENCES "user"(id)
+);
+CREATE INDEX IF NOT EXISTS user_group_member__user_id ON user_group_member(user_id);
+
+-- The group the resource grant is made to, if any.  A grant with neither
+-- the user nor the group applies to everyone.
+ALTER TABLE res_grant ADD COLUMN IF NOT EXISTS user_group_id BIGINT REFERENCES user_group(id);
+CREATE UNIQUE INDEX IF NOT EXISTS res_grant__res_user_group_id_role ON res_grant(res, user_group_id, role);
@@ -0,0 +1,29 @@
+CREATE TABLE IF NOT EXISTS workspace (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    url TEXT NOT NULL,  -- should be immutable
+    superceded_by_id BIGINT,  -- if superceded?
+    description TEXT,
+    long_description TEXT,
+    created_ts BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS workspace_sync (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    workspace_id BIGINT NOT NULL,
+    start BIGINT NOT NULL,
+    "end" BIGINT,
+    status BIGINT NOT NULL,
+    FOREIGN KEY(workspace_id) REFERENCES workspace(id)
+);
+CREATE INDEX IF NOT EXISTS workspace_sync_idx_workspace_id ON workspace_sync(workspace_id);
+
+CREATE TABLE IF NOT EXISTS workspace_tag (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    workspace_id BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    commit_id TEXT NOT NULL,
+    FOREIGN KEY(workspace_id) REFERENCES workspace(id)
+);
+CREATE INDEX IF NOT EXISTS workspace_tag_idx_workspace_id ON workspace_tag(workspace_id);
+CREATE UNIQUE INDEX IF NOT EXISTS workspace_tag_idx_workspace_id_name ON workspace_tag(workspace_id, name);
+CREATE UNIQUE INDEX IF NOT EXISTS workspace_tag_idx_workspace_id_name_commit_id ON workspace_tag(workspace_id, name, commit_id);
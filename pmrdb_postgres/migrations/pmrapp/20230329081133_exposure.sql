@@ -0,0 +1,122 @@
+-- Refer to the sqlite migration for the commentary on the design of the
+-- following schema, this is largely the PostgreSQL translation of that.
+--
+-- As the tables reference each other in a circular manner, the foreign
+-- keys that reference tables that have yet to be created are added at
+-- the end.
+
+CREATE TABLE IF NOT EXISTS exposure (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    description TEXT,
+    workspace_id BIGINT NOT NULL,
+    workspace_tag_id BIGINT,
+    commit_id TEXT NOT NULL,  -- this is actually duplicate with tag
+    created_ts BIGINT NOT NULL,
+    default_file_id BIGINT,
+    FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+    FOREIGN KEY(workspace_tag_id) REFERENCES workspace_tag(id)
+);
+
+CREATE INDEX IF NOT EXISTS exposure__workspace_id ON exposure(workspace_id);
+CREATE INDEX IF NOT EXISTS exposure__workspace_id_commit_id ON exposure(workspace_id, commit_id);
+
+CREATE TABLE IF NOT EXISTS exposure_file (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    exposure_id BIGINT NOT NULL,
+    workspace_file_path TEXT NOT NULL,
+    default_view_id BIGINT,
+    FOREIGN KEY(exposure_id) REFERENCES exposure(id)
+);
+
+CREATE INDEX IF NOT EXISTS exposure_file__exposure_id ON exposure_file(exposure_id);
+CREATE UNIQUE INDEX IF NOT EXISTS exposure_file__exposure_id_workspace_file_path ON exposure_file(exposure_id, workspace_file_path);
+
+CREATE TABLE IF NOT EXISTS view_task_template (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    view_key TEXT NOT NULL,
+    description TEXT NOT NULL,
+    -- This references the task_template that resides on the pmrtqs
+    -- platform.
+    task_template_id BIGINT NOT NULL,
+    updated_ts BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS exposure_file_view (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    exposure_file_id BIGINT NOT NULL,
+    view_task_template_id BIGINT NOT NULL,
+    exposure_file_view_task_id BIGINT,
+    view_key TEXT,
+    updated_ts BIGINT NOT NULL,
+    FOREIGN KEY(exposure_file_id) REFERENCES exposure_file(id),
+    FOREIGN KEY(view_task_template_id) REFERENCES view_task_template(id)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS exposure_file_view__exposure_file_id_view_task_template_id ON exposure_file_view(exposure_file_id, view_task_template_id);
+CREATE INDEX IF NOT EXISTS exposure_file_view__id_exposure_file_view_task_id ON exposure_file_view(id, exposure_file_view_task_id);
+CREATE UNIQUE INDEX IF NOT EXISTS exposure_file_view__exposure_file_id_task_view_task_id ON exposure_file_view(exposure_file_id, exposure_file_view_task_id);
+
+CREATE TABLE IF NOT EXISTS exposure_file_view_task_template (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    exposure_file_id BIGINT NOT NULL,
+    view_task_template_id BIGINT NOT NULL,
+    FOREIGN KEY(exposure_file_id) REFERENCES exposure_file(id),
+    FOREIGN KEY(view_task_template_id) REFERENCES view_task_template(id)
+);
+
+CREATE TABLE IF NOT EXISTS exposure_file_view_task (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    -- No foreign key, as this is a record of some view; the reverse
+    -- direction has the foreign key declared.
+    exposure_file_view_id BIGINT NOT NULL,
+    view_task_template_id BIGINT NOT NULL,
+    -- This references the task that resides on the pmrtqs platform.
+    task_id BIGINT,
+    created_ts BIGINT,
+    ready BOOLEAN NOT NULL,
+    FOREIGN KEY(view_task_template_id) REFERENCES view_task_template(id)
+);
+CREATE INDEX IF NOT EXISTS exposure_file_view_task__task_id ON exposure_file_view_task(task_id);
+
+CREATE TABLE IF NOT EXISTS profile (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS profile__profile_title ON profile(title);
+
+CREATE TABLE IF NOT EXISTS profile_views (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    profile_id BIGINT NOT NULL,
+    view_task_template_id BIGINT NOT NULL,
+    FOREIGN KEY(profile_id) REFERENCES profile(id),
+    FOREIGN KEY(view_task_template_id) REFERENCES view_task_template(id)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS profile_views__profile_id_view_task_template_id ON profile_views(profile_id, view_task_template_id);
+
+CREATE TABLE IF NOT EXISTS exposure_file_profile (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    exposure_file_id BIGINT NOT NULL,
+    profile_id BIGINT NOT NULL,
+    FOREIGN KEY(profile_id) REFERENCES profile(id),
+    FOREIGN KEY(exposure_file_id) REFERENCES exposure_file(id)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS exposure_file_profile__exposure_file_id ON exposure_file_profile(exposure_file_id);
+CREATE INDEX IF NOT EXISTS exposure_file_profile__exposure_file_id_profile_id ON exposure_file_profile(exposure_file_id, profile_id);
+
+CREATE TABLE IF NOT EXISTS exposure_file_profile_input (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    exposure_file_profile_id BIGINT NOT NULL,
+    -- This is the id for the arg from pmrtqs.
+    arg_id BIGINT NOT NULL,
+    input TEXT NOT NULL,
+    FOREIGN KEY(exposure_file_profile_id) REFERENCES exposure_file_profile(id)
+);
+CREATE INDEX IF NOT EXISTS exposure_file_profile_input__exposure_file_profile_id ON exposure_file_profile_input(exposure_file_profile_id);
+CREATE UNIQUE INDEX IF NOT EXISTS exposure_file_profile_input__exposure_file_profile_id_arg_id ON exposure_file_profile_input(exposure_file_profile_id, arg_id);
+
+ALTER TABLE exposure
+    ADD FOREIGN KEY(default_file_id) REFERENCES exposure_file(id);
+ALTER TABLE exposure_file
+    ADD FOREIGN KEY(default_view_id) REFERENCES exposure_file_view(id);
+ALTER TABLE exposure_file_view
+    ADD FOREIGN KEY(exposure_file_view_task_id) REFERENCES exposure_file_view_task(id);
@@ -0,0 +1,3 @@
+CREATE TABLE IF NOT EXISTS gen_alias_seq (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY
+);
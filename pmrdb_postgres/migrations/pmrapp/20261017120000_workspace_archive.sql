@@ -0,0 +1,2 @@
+ALTER TABLE workspace ADD COLUMN IF NOT EXISTS archived_ts BIGINT;  -- if archived, when
+CREATE INDEX IF NOT EXISTS workspace__archived_ts ON workspace(archived_ts);
@@ -0,0 +1 @@
+ALTER TABLE view_task_template ADD COLUMN IF NOT EXISTS depends_on TEXT;  -- view_key this view requires to be generated first
@@ -0,0 +1 @@
+ALTER TABLE exposure_file_profile_input ADD COLUMN IF NOT EXISTS multiple BOOLEAN NOT NULL DEFAULT FALSE;  -- input is a JSON list
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS citation (
+    id TEXT PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL,
+    journal TEXT,
+    volume TEXT,
+    first_page TEXT,
+    last_page TEXT,
+    issued TEXT
+);
+CREATE INDEX IF NOT EXISTS citation__title ON citation(title);
+
+CREATE TABLE IF NOT EXISTS citation_author (
+    citation_id TEXT NOT NULL,
+    family TEXT NOT NULL,
+    given TEXT,
+    other TEXT,
+    ordering BIGINT,
+    FOREIGN KEY(citation_id) REFERENCES citation(id)
+);
+
+CREATE INDEX IF NOT EXISTS citation_author__citation_id ON citation_author(citation_id);
+CREATE INDEX IF NOT EXISTS citation_author__family_given ON citation_author(family, given);
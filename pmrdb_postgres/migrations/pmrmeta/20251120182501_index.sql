@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS idx_kind (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    description TEXT NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_kind__description ON idx_kind(description);
+
+CREATE TABLE IF NOT EXISTS idx_entry (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    idx_kind_id BIGINT NOT NULL,
+    term TEXT NOT NULL,
+    FOREIGN KEY(idx_kind_id) REFERENCES idx_kind(id)
+);
+-- The equivalent of `COLLATE NOCASE` for the sqlite version; queries
+-- against term must use `lower(term)` to make use of this index.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_entry__idx_kind_id_term ON idx_entry(idx_kind_id, lower(term));
+
+CREATE TABLE IF NOT EXISTS idx_entry_link (
+    idx_entry_id BIGINT NOT NULL,
+    resource_path TEXT NOT NULL,
+    FOREIGN KEY(idx_entry_id) REFERENCES idx_entry(id)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_entry_link__idx_entry_id_resource_path ON idx_entry_link(idx_entry_id, resource_path);
+
+CREATE TABLE IF NOT EXISTS resource_indexed (
+    resource_path TEXT PRIMARY KEY NOT NULL,
+    data TEXT
+);
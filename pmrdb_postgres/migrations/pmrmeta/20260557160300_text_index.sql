@@ -0,0 +1,14 @@
+-- The equivalent of the fts5 virtual table for the sqlite backend, done
+-- as a GIN index on a generated tsvector column.
+-- <https://www.postgresql.org/docs/current/textsearch-tables.html>
+CREATE TABLE IF NOT EXISTS idx_text (
+    title TEXT,
+    content TEXT,
+    resource_path TEXT NOT NULL,
+    document TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(content, '')), 'B')
+    ) STORED
+);
+CREATE INDEX IF NOT EXISTS idx_text__resource_path ON idx_text(resource_path);
+CREATE INDEX IF NOT EXISTS idx_text__document ON idx_text USING GIN(document);
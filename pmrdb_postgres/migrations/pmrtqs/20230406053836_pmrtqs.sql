@@ -0,0 +1,62 @@
+-- Schema definition for pmrtqs
+--
+-- Refer to the sqlite migration for the commentary on the design of the
+-- following schema, this is largely the PostgreSQL translation of that.
+
+CREATE TABLE IF NOT EXISTS task_template (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    bin_path TEXT NOT NULL,
+    version_id TEXT NOT NULL,
+    created_ts BIGINT NOT NULL,
+    final_task_template_arg_id BIGINT,
+    superceded_by_id BIGINT
+);
+
+CREATE TABLE IF NOT EXISTS task (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    task_template_id BIGINT NOT NULL,
+    bin_path TEXT NOT NULL,
+    pid BIGINT,
+    created_ts BIGINT NOT NULL,
+    start_ts BIGINT,
+    stop_ts BIGINT,
+    exit_status BIGINT,
+    -- `basedir` will contain source, build and image directory
+    basedir TEXT NOT NULL,
+    FOREIGN KEY(task_template_id) REFERENCES task_template(id)
+);
+
+CREATE INDEX IF NOT EXISTS task__pid ON task(pid);
+
+CREATE TABLE IF NOT EXISTS task_arg (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    task_id BIGINT NOT NULL,
+    arg TEXT NOT NULL,
+    FOREIGN KEY(task_id) REFERENCES task(id)
+);
+
+CREATE INDEX IF NOT EXISTS task_arg__task_id ON task_arg(task_id);
+
+CREATE TABLE IF NOT EXISTS task_template_arg (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    task_template_id BIGINT NOT NULL,
+    flag TEXT,
+    flag_joined BOOLEAN NOT NULL,
+    flag_omit_when_null BOOLEAN NOT NULL,
+    prompt TEXT,
+    "default" TEXT,
+    choice_fixed BOOLEAN NOT NULL,
+    choice_source TEXT,
+    FOREIGN KEY(task_template_id) REFERENCES task_template(id)
+);
+
+CREATE TABLE IF NOT EXISTS task_template_arg_choice (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    task_template_arg_id BIGINT NOT NULL,
+    to_arg TEXT,
+    label TEXT NOT NULL,
+    FOREIGN KEY(task_template_arg_id) REFERENCES task_template_arg(id)
+);
+
+CREATE INDEX IF NOT EXISTS task_template_arg_choice__task_template_arg_id
+    ON task_template_arg_choice(task_template_arg_id);
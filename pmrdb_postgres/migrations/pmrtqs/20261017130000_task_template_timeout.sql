@@ -0,0 +1 @@
+ALTER TABLE task_template ADD COLUMN IF NOT EXISTS timeout BIGINT;  -- seconds, NULL for no limit
@@ -0,0 +1 @@
+ALTER TABLE task_template ADD COLUMN IF NOT EXISTS sandbox TEXT;  -- JSON encoded, NULL for no sandbox
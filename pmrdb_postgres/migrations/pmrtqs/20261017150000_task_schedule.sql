@@ -0,0 +1,17 @@
+ALTER TABLE task ADD COLUMN IF NOT EXISTS priority BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE task ADD COLUMN IF NOT EXISTS attempts BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE task ADD COLUMN IF NOT EXISTS max_retries BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE task ADD COLUMN IF NOT EXISTS retry_delay BIGINT NOT NULL DEFAULT 0;  -- seconds
+ALTER TABLE task ADD COLUMN IF NOT EXISTS retry_ts BIGINT;
+
+CREATE INDEX IF NOT EXISTS task__start_ts_priority ON task(start_ts, priority);
+
+CREATE TABLE IF NOT EXISTS task_dependency (
+    task_id BIGINT NOT NULL,
+    depends_on_id BIGINT NOT NULL,
+    PRIMARY KEY(task_id, depends_on_id),
+    FOREIGN KEY(task_id) REFERENCES task(id),
+    FOREIGN KEY(depends_on_id) REFERENCES task(id)
+);
+
+CREATE INDEX IF NOT EXISTS task_dependency__depends_on_id ON task_dependency(depends_on_id);
@@ -0,0 +1,2 @@
+ALTER TABLE task_template_arg ADD COLUMN IF NOT EXISTS multiple BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE task_template_arg ADD COLUMN IF NOT EXISTS multiple_separator TEXT;  -- NULL to repeat the flag
@@ -0,0 +1,20 @@
+#!/bin/sh
+
+set -e
+
+# The PostgreSQL counterpart to the script in pmrdb_sqlite; refer to that
+# for the rationale.  This requires a running PostgreSQL server that the
+# current user may create databases on, with the connection configured
+# via the standard libpq environment variables (e.g. PGHOST, PGPORT,
+# PGUSER), which is also used to construct the database url below.
+#
+# Run this script every time query! macros have their queries changed.
+
+cd "$(dirname "$0")"
+DB_NAME=pmrdb_postgres_prepare_$$
+createdb ${DB_NAME}
+trap "dropdb ${DB_NAME}" EXIT
+cat migrations/pmr*/*sql | psql -q -v ON_ERROR_STOP=1 -d ${DB_NAME}
+cargo sqlx prepare --database-url \
+    "postgres://${PGUSER:-$(id -un)}@${PGHOST:-localhost}:${PGPORT:-5432}/${DB_NAME}" \
+    -- --tests
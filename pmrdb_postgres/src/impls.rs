@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use pmrcore::platform::{ConnectorOption, PlatformConnector, PlatformCore, RawPlatform};
+use sqlx::{migrate::{MigrateDatabase, Migrator}, PgPool, Postgres};
+use std::sync::Arc;
+
+use crate::PostgresBackend;
+
+impl PlatformCore for PostgresBackend {
+    fn url(&self) -> &str {
+        self.url.as_ref()
+    }
+}
+
+impl RawPlatform for PostgresBackend {
+    type Backend = PgPool;
+
+    fn backend<'a>(&'a self) -> &'a Self::Backend {
+        &self.pool
+    }
+}
+
+impl PostgresBackend {
+    pub async fn connect(opts: ConnectorOption) -> Result<PostgresBackend, sqlx::Error> {
+        if opts.auto_create_db && !Postgres::database_exists(&opts.url).await.unwrap_or(false) {
+            log::warn!("postgres database {} does not exist; creating...", &opts.url);
+            Postgres::create_database(&opts.url).await?
+        }
+
+        let pool = PgPool::connect(&opts.url).await?;
+        Ok(PostgresBackend {
+            pool: Arc::new(pool),
+            url: opts.url,
+        })
+    }
+
+    // Unlike the sqlite backend, all platforms may reasonably share the
+    // same database, so the migrations that belong to the other platforms
+    // must be ignored by the migrator.
+    async fn migrate(self, mut migrator: Migrator) -> Result<Self, sqlx::Error> {
+        migrator.set_ignore_missing(true);
+        migrator.run(&*self.pool).await?;
+        Ok(self)
+    }
+
+    pub async fn migrate_ac(self) -> Result<Self, sqlx::Error> {
+        self.migrate(sqlx::migrate!("migrations/pmrac")).await
+    }
+
+    pub async fn migrate_mc(self) -> Result<Self, sqlx::Error> {
+        self.migrate(sqlx::migrate!("migrations/pmrapp")).await
+    }
+
+    pub async fn migrate_pc(self) -> Result<Self, sqlx::Error> {
+        self.migrate(sqlx::migrate!("migrations/pmrmeta")).await
+    }
+
+    pub async fn migrate_tm(self) -> Result<Self, sqlx::Error> {
+        self.migrate(sqlx::migrate!("migrations/pmrtqs")).await
+    }
+}
+
+#[async_trait]
+impl PlatformConnector for PostgresBackend {
+    async fn ac(opts: ConnectorOption) -> Result<PostgresBackend, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let backend = PostgresBackend::connect(opts).await
+            .map_err(Box::new)?
+            .migrate_ac()
+            .await
+            .map_err(Box::new)?;
+        Ok(backend)
+    }
+
+    async fn mc(opts: ConnectorOption) -> Result<PostgresBackend, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let backend = PostgresBackend::connect(opts).await
+            .map_err(Box::new)?
+            .migrate_mc()
+            .await
+            .map_err(Box::new)?;
+        Ok(backend)
+    }
+
+    async fn pc(opts: ConnectorOption) -> Result<PostgresBackend, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let backend = PostgresBackend::connect(opts).await
+            .map_err(Box::new)?
+            .migrate_pc()
+            .await
+            .map_err(Box::new)?;
+        Ok(backend)
+    }
+
+    async fn tm(opts: ConnectorOption) -> Result<PostgresBackend, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let backend = PostgresBackend::connect(opts).await
+            .map_err(Box::new)?
+            .migrate_tm()
+            .await
+            .map_err(Box::new)?;
+        Ok(backend)
+    }
+}
+
+mod ac;
+mod alias;
+
+mod citation;
+
+mod exposure;
+mod exposure_file;
+mod exposure_file_profile;
+mod exposure_file_view;
+mod exposure_file_view_task;
+mod exposure_file_view_task_template;
+
+mod idgen;
+mod index;
+mod listing;
+
+mod profile;
+
+mod workspace;
+mod workspace_sync;
+mod workspace_tag;
+
+mod task;
+mod task_template;
+
+mod default_impl {
+    use pmrcore::platform::{
+        DefaultACPlatform,
+        DefaultMCPlatform,
+        DefaultPCPlatform,
+        DefaultTMPlatform,
+    };
+    use crate::PostgresBackend;
+
+    impl DefaultACPlatform for PostgresBackend {}
+    impl DefaultMCPlatform for PostgresBackend {}
+    impl DefaultPCPlatform for PostgresBackend {}
+    impl DefaultTMPlatform for PostgresBackend {}
+}
+
+// For testing unified usage/traits
+#[cfg(test)]
+pub(crate) mod tests {
+    use pmrcore::{
+        platform::{
+            MCPlatform,
+            PlatformConnector,
+        },
+        workspace::Workspace,
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    #[async_std::test]
+    async fn create_aliased_workspace() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let entry = backend.create_aliased_workspace(
+            "https://models.example.com".into(),
+            "".into(),
+            "".into(),
+        ).await?;
+        assert_eq!(entry.alias, "1");
+        let answer = Workspace {
+            id: 1,
+            url: "https://models.example.com".into(),
+            superceded_by_id: None,
+            created_ts: 1234567890,
+            description: Some("".into()),
+            long_description: Some("".into()),
+            archived_ts: None,
+            exposures: None,
+        };
+        assert_eq!(entry.entity.into_inner(), answer);
+        Ok(())
+    }
+
+    mod conformance {
+        test_pmr::conformance_test_suite!(crate::PostgresBackend, test_pmr::postgres::create_database());
+    }
+}
@@ -0,0 +1,4 @@
+mod policy;
+mod resource;
+mod user;
+mod session;
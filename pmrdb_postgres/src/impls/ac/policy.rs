@@ -0,0 +1,731 @@
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use pmrcore::{
+    ac::{
+        agent::Agent,
+        group::Group,
+        role::Role,
+        traits::PolicyBackend,
+        user::User,
+        workflow::State,
+    },
+    error::BackendError,
+};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+};
+
+use crate::PostgresBackend;
+
+fn group_id(agent: &Agent) -> Option<i64> {
+    match agent {
+        Agent::Group(group) => Some(group.id),
+        _ => None,
+    }
+}
+
+async fn grant_role_to_user_postgres(
+    backend: &PostgresBackend,
+    user: &User,
+    role: Role,
+) -> Result<bool, BackendError> {
+    let role_str = <&'static str>::from(role);
+    match sqlx::query!(
+        r#"
+INSERT INTO user_role (
+    user_id,
+    role
+)
+VALUES ( $1, $2 )
+        "#,
+        user.id,
+        role_str,
+    )
+    .execute(&*backend.pool)
+    .await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            match e.as_database_error() {
+                Some(db_e) if db_e.is_unique_violation() => Ok(false),
+                _ => Err(e)?,
+            }
+        }
+    }
+}
+
+async fn revoke_role_from_user_postgres(
+    backend: &PostgresBackend,
+    user: &User,
+    role: Role,
+) -> Result<bool, BackendError> {
+    let role_str = <&'static str>::from(role);
+    Ok(sqlx::query!(
+        r#"
+DELETE FROM
+    user_role
+WHERE
+    user_id = $1 AND
+    role = $2
+        "#,
+        user.id,
+        role_str,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected() > 0)
+}
+
+async fn get_roles_for_user_postgres(
+    backend: &PostgresBackend,
+    user: &User,
+) -> Result<Vec<Role>, BackendError> {
+    Ok(sqlx::query!(
+        r#"
+SELECT
+    role
+FROM
+    user_role
+WHERE
+    user_id = $1
+        "#,
+        user.id,
+    )
+    .map(|row| Role::from_str(&row.role).unwrap_or(Role::default()))
+    .fetch_all(&*backend.pool)
+    .await?
+    .into())
+}
+
+async fn add_user_to_group_postgres(
+    backend: &PostgresBackend,
+    group: &Group,
+    user: &User,
+) -> Result<bool, BackendError> {
+    match sqlx::query!(
+        r#"
+INSERT INTO user_group_member (
+    user_group_id,
+    user_id
+)
+VALUES ( $1, $2 )
+        "#,
+        group.id,
+        user.id,
+    )
+    .execute(&*backend.pool)
+    .await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            match e.as_database_error() {
+                Some(db_e) if db_e.is_unique_violation() => Ok(false),
+                _ => Err(e)?,
+            }
+        }
+    }
+}
+
+async fn remove_user_from_group_postgres(
+    backend: &PostgresBackend,
+    group: &Group,
+    user: &User,
+) -> Result<bool, BackendError> {
+    Ok(sqlx::query!(
+        r#"
+DELETE FROM
+    user_group_member
+WHERE
+    user_group_id = $1 AND
+    user_id = $2
+        "#,
+        group.id,
+        user.id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected() > 0)
+}
+
+async fn get_groups_for_user_postgres(
+    backend: &PostgresBackend,
+    user: &User,
+) -> Result<Vec<Group>, BackendError> {
+    Ok(sqlx::query!(
+        r#"
+SELECT
+    user_group.id AS id,
+    user_group.name AS name,
+    user_group.created_ts AS created_ts
+FROM
+    user_group_member
+JOIN
+    user_group ON user_group_member.user_group_id = user_group.id
+WHERE
+    user_group_member.user_id = $1
+ORDER BY user_group.name
+        "#,
+        user.id,
+    )
+    .map(|row| Group {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_all(&*backend.pool)
+    .await?)
+}
+
+async fn get_group_members_postgres(
+    backend: &PostgresBackend,
+    group: &Group,
+) -> Result<Vec<User>, BackendError> {
+    Ok(sqlx::query!(
+        r#"
+SELECT
+    "user".id AS id,
+    "user".name AS name,
+    "user".created_ts AS created_ts
+FROM
+    user_group_member
+JOIN
+    "user" ON user_group_member.user_id = "user".id
+WHERE
+    user_group_member.user_group_id = $1
+ORDER BY "user".name
+        "#,
+        group.id,
+    )
+    .map(|row| User {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_all(&*backend.pool)
+    .await?)
+}
+
+async fn res_grant_role_to_agent_postgres(
+    backend: &PostgresBackend,
+    res: &str,
+    agent: &Agent,
+    role: Role,
+) -> Result<bool, BackendError> {
+    let user_id: Option<i64> = agent.into();
+    let user_group_id = group_id(agent);
+    let role_str = <&'static str>::from(role);
+    match sqlx::query!(
+        r#"
+INSERT INTO res_grant (
+    res,
+    user_id,
+    user_group_id,
+    role
+)
+VALUES ( $1, $2, $3, $4 )
+        "#,
+        res,
+        user_id,
+        user_group_id,
+        role_str,
+    )
+    .execute(&*backend.pool)
+    .await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            match e.as_database_error() {
+                Some(db_e) if db_e.is_unique_violation() => Ok(false),
+                _ => Err(e)?,
+            }
+        }
+    }
+}
+
+async fn res_revoke_role_from_agent_postgres(
+    backend: &PostgresBackend,
+    res: &str,
+    agent: &Agent,
+    role: Role,
+) -> Result<bool, BackendError> {
+    let role_str = <&'static str>::from(role);
+    let user_id: Option<i64> = agent.into();
+    let user_group_id = group_id(agent);
+    Ok(sqlx::query!(
+        r#"
+DELETE FROM
+    res_grant
+WHERE
+    res = $1 AND
+    (user_id = $2 OR user_group_id = $3) AND
+    role = $4
+        "#,
+        res,
+        user_id,
+        user_group_id,
+        role_str,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected() > 0)
+}
+
+async fn get_res_grants_for_res_postgres(
+    backend: &PostgresBackend,
+    res: &str,
+) -> Result<Vec<(Agent, Vec<Role>)>, BackendError> {
+    let mut result = HashMap::<(Option<i64>, Option<i64>), (Agent, Vec<Role>)>::new();
+    let mut rows = sqlx::query!(
+        r#"
+SELECT
+    res_grant.user_id AS user_id,
+    "user".name AS "user_name?",
+    "user".created_ts AS "user_created_ts?",
+    res_grant.user_group_id AS user_group_id,
+    user_group.name AS "group_name?",
+    user_group.created_ts AS "group_created_ts?",
+    res_grant.role AS role
+FROM
+    res_grant
+LEFT JOIN
+    "user" ON res_grant.user_id = "user".id
+LEFT JOIN
+    user_group ON res_grant.user_group_id = user_group.id
+WHERE
+    res_grant.res = $1
+        "#,
+        res,
+    )
+    .fetch(&*backend.pool);
+    while let Some(row) = rows.try_next().await? {
+        result
+            .entry((row.user_id, row.user_group_id))
+            .and_modify(|(_, roles)| roles.push(
+                Role::from_str(&row.role).unwrap_or(Role::default()),
+            ))
+            .or_insert((
+                // the columns from the user and group tables are null for
+                // the grants that have no user or group to join with.
+                match (
+                    (row.user_id, row.user_name, row.user_created_ts),
+                    (row.user_group_id, row.group_name, row.group_created_ts),
+                ) {
+                    ((Some(id), Some(name), Some(created_ts)), _) => {
+                        Agent::User(User {
+                            id,
+                            name,
+                            created_ts,
+                        })
+                    },
+                    (_, (Some(id), Some(name), Some(created_ts))) => {
+                        Agent::Group(Group {
+                            id,
+                            name,
+                            created_ts,
+                        })
+                    },
+                    _ => Agent::Anonymous,
+                },
+                vec![Role::from_str(&row.role).unwrap_or(Role::default())],
+            ));
+    }
+
+    Ok(result.into_values().collect())
+}
+
+async fn get_res_grants_for_agent_postgres(
+    backend: &PostgresBackend,
+    agent: &Agent,
+) -> Result<Vec<(String, Vec<Role>)>, BackendError> {
+    let mut result = HashMap::<String, Vec<Role>>::new();
+    let user_id: Option<i64> = agent.into();
+    let user_group_id = group_id(agent);
+    let mut rows = sqlx::query!(
+        r#"
+SELECT
+    res,
+    role
+FROM
+    res_grant
+WHERE
+    res_grant.user_id = $1 OR
+    res_grant.user_group_id = $2
+        "#,
+        user_id,
+        user_group_id,
+    )
+    .fetch(&*backend.pool);
+
+    while let Some(row) = rows.try_next().await? {
+        result
+            .entry(row.res)
+            .and_modify(|roles| roles.push(
+                Role::from_str(&row.role).unwrap_or(Role::default()),
+            ))
+            .or_insert(
+                vec![Role::from_str(&row.role).unwrap_or(Role::default())],
+            );
+    }
+    Ok(result
+        .into_iter()
+        .collect::<Vec<_>>())
+}
+
+async fn assign_policy_to_wf_state_postgres(
+    backend: &PostgresBackend,
+    wf_state: State,
+    role: Role,
+    action: &str,
+) -> Result<(), BackendError> {
+    let state = <&'static str>::from(wf_state);
+    let role = <&'static str>::from(role);
+    sqlx::query!(
+        r#"
+INSERT INTO wf_policy (
+    state,
+    role,
+    action
+)
+VALUES ( $1, $2, $3 )
+RETURNING id
+        "#,
+        state,
+        role,
+        action,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(())
+}
+
+async fn remove_policy_from_wf_state_postgres(
+    backend: &PostgresBackend,
+    state: State,
+    role: Role,
+    action: &str,
+) -> Result<(), BackendError> {
+    let state = <&'static str>::from(state);
+    let role = <&'static str>::from(role);
+    sqlx::query!(
+        r#"
+DELETE FROM
+    wf_policy
+WHERE
+    state = $1 AND
+    role = $2 AND
+    action = $3
+        "#,
+        state,
+        role,
+        action,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl PolicyBackend for PostgresBackend {
+    async fn grant_role_to_user(
+        &self,
+        user: &User,
+        role: Role,
+    ) -> Result<bool, BackendError> {
+        grant_role_to_user_postgres(
+            &self,
+            user,
+            role,
+        ).await
+    }
+
+    async fn revoke_role_from_user(
+        &self,
+        user: &User,
+        role: Role,
+    ) -> Result<bool, BackendError> {
+        revoke_role_from_user_postgres(
+            &self,
+            user,
+            role,
+        ).await
+    }
+
+    async fn get_roles_for_user(
+        &self,
+        user: &User,
+    ) -> Result<Vec<Role>, BackendError> {
+        get_roles_for_user_postgres(
+            &self,
+            user,
+        ).await
+    }
+
+    async fn add_user_to_group(
+        &self,
+        group: &Group,
+        user: &User,
+    ) -> Result<bool, BackendError> {
+        add_user_to_group_postgres(
+            &self,
+            group,
+            user,
+        ).await
+    }
+
+    async fn remove_user_from_group(
+        &self,
+        group: &Group,
+        user: &User,
+    ) -> Result<bool, BackendError> {
+        remove_user_from_group_postgres(
+            &self,
+            group,
+            user,
+        ).await
+    }
+
+    async fn get_groups_for_user(
+        &self,
+        user: &User,
+    ) -> Result<Vec<Group>, BackendError> {
+        get_groups_for_user_postgres(
+            &self,
+            user,
+        ).await
+    }
+
+    async fn get_group_members(
+        &self,
+        group: &Group,
+    ) -> Result<Vec<User>, BackendError> {
+        get_group_members_postgres(
+            &self,
+            group,
+        ).await
+    }
+
+    async fn res_grant_role_to_agent(
+        &self,
+        res: &str,
+        agent: &Agent,
+        role: Role,
+    ) -> Result<bool, BackendError> {
+        res_grant_role_to_agent_postgres(
+            &self,
+            res,
+            agent,
+            role,
+        ).await
+    }
+
+    async fn res_revoke_role_from_agent(
+        &self,
+        res: &str,
+        agent: &Agent,
+        role: Role,
+    ) -> Result<bool, BackendError> {
+        res_revoke_role_from_agent_postgres(
+            &self,
+            res,
+            agent,
+            role,
+        ).await
+    }
+
+    async fn get_res_grants_for_res(
+        &self,
+        res: &str,
+    ) -> Result<Vec<(Agent, Vec<Role>)>, BackendError> {
+        get_res_grants_for_res_postgres(
+            &self,
+            res,
+        ).await
+    }
+
+    async fn get_res_grants_for_agent(
+        &self,
+        agent: &Agent,
+    ) -> Result<Vec<(String, Vec<Role>)>, BackendError> {
+        get_res_grants_for_agent_postgres(
+            &self,
+            agent,
+        ).await
+    }
+
+    async fn assign_policy_to_wf_state(
+        &self,
+        wf_state: State,
+        role: Role,
+        action: &str,
+    ) -> Result<(), BackendError> {
+        assign_policy_to_wf_state_postgres(
+            &self,
+            wf_state,
+            role,
+            action,
+        ).await
+    }
+
+    async fn remove_policy_from_wf_state(
+        &self,
+        wf_state: State,
+        role: Role,
+        action: &str,
+    ) -> Result<(), BackendError> {
+        remove_policy_from_wf_state_postgres(
+            &self,
+            wf_state,
+            role,
+            action,
+        ).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        ac::{
+            agent::Agent,
+            role::Role,
+            traits::{
+                PolicyBackend,
+                UserBackend,
+            },
+            workflow::State,
+        },
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = PostgresBackend::ac(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        let user = UserBackend::get_user_by_id(&backend, user_id).await?
+            .expect("user is missing?");
+        let agent: Agent = user.clone().into();
+        let state = State::Published;
+        let role = Role::Reader;
+        PolicyBackend::res_grant_role_to_agent(&backend, "/", &agent, role).await?;
+        assert_eq!(
+            vec![(agent.clone(), vec![role])],
+            PolicyBackend::get_res_grants_for_res(&backend, "/").await?
+        );
+        assert_eq!(
+            vec![("/".to_string(), vec![role])],
+            PolicyBackend::get_res_grants_for_agent(&backend, &agent).await?,
+        );
+        PolicyBackend::res_revoke_role_from_agent(&backend, "/", &agent, role).await?;
+        assert!(PolicyBackend::get_res_grants_for_res(&backend, "/").await?.is_empty());
+        assert!(PolicyBackend::get_res_grants_for_agent(&backend, &agent).await?.is_empty());
+        PolicyBackend::assign_policy_to_wf_state(&backend, state, role, "").await?;
+        PolicyBackend::remove_policy_from_wf_state(&backend, state, role, "").await?;
+
+        PolicyBackend::grant_role_to_user(&backend, &user, Role::Manager).await?;
+        assert_eq!(
+            &[Role::Manager],
+            PolicyBackend::get_roles_for_user(&backend, &user).await?.as_slice()
+        );
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_double() -> anyhow::Result<()> {
+        let backend = PostgresBackend::ac(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        let user = UserBackend::get_user_by_id(&backend, user_id).await?
+            .expect("user is missing?");
+        let role = Role::Reader;
+        assert!(PolicyBackend::grant_role_to_user(&backend, &user, Role::Manager).await?);
+        assert!(!PolicyBackend::grant_role_to_user(&backend, &user, Role::Manager).await?);
+        assert_eq!(
+            &[Role::Manager],
+            PolicyBackend::get_roles_for_user(&backend, &user).await?.as_slice()
+        );
+        assert!(PolicyBackend::revoke_role_from_user(&backend, &user, Role::Manager).await?);
+        assert!(PolicyBackend::get_roles_for_user(&backend, &user).await?.is_empty());
+        assert!(!PolicyBackend::revoke_role_from_user(&backend, &user, Role::Manager).await?);
+        assert!(PolicyBackend::get_roles_for_user(&backend, &user).await?.is_empty());
+
+        let agent = user.into();
+        assert!(PolicyBackend::res_grant_role_to_agent(&backend, "/", &agent, role).await?);
+        assert!(!PolicyBackend::res_grant_role_to_agent(&backend, "/", &agent, role).await?);
+        assert_eq!(
+            vec![(agent.clone(), vec![role])],
+            PolicyBackend::get_res_grants_for_res(&backend, "/").await?
+        );
+        assert!(PolicyBackend::res_revoke_role_from_agent(&backend, "/", &agent, role).await?);
+        assert!(PolicyBackend::get_res_grants_for_res(&backend, "/").await?.is_empty());
+        assert!(!PolicyBackend::res_revoke_role_from_agent(&backend, "/", &agent, role).await?);
+        assert!(PolicyBackend::get_res_grants_for_res(&backend, "/").await?.is_empty());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_group() -> anyhow::Result<()> {
+        let backend = PostgresBackend::ac(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        let user = UserBackend::get_user_by_id(&backend, user_id).await?
+            .expect("user is missing?");
+        let other_id = UserBackend::add_user(&backend, "other_user").await?;
+        let other = UserBackend::get_user_by_id(&backend, other_id).await?
+            .expect("user is missing?");
+        let group_id = UserBackend::add_group(&backend, "lab").await?;
+        let group = UserBackend::get_group_by_id(&backend, group_id).await?
+            .expect("group is missing?");
+
+        assert!(PolicyBackend::add_user_to_group(&backend, &group, &user).await?);
+        assert!(!PolicyBackend::add_user_to_group(&backend, &group, &user).await?);
+        assert!(PolicyBackend::add_user_to_group(&backend, &group, &other).await?);
+        assert_eq!(
+            PolicyBackend::get_groups_for_user(&backend, &user).await?,
+            [group.clone()],
+        );
+        assert_eq!(
+            PolicyBackend::get_group_members(&backend, &group).await?,
+            [other.clone(), user.clone()],
+        );
+
+        // grants to the group are distinct from the grants to its members
+        // and to everyone.
+        let role = Role::Editor;
+        let agent: Agent = group.clone().into();
+        assert!(PolicyBackend::res_grant_role_to_agent(&backend, "/", &agent, role).await?);
+        assert!(!PolicyBackend::res_grant_role_to_agent(&backend, "/", &agent, role).await?);
+        assert!(PolicyBackend::res_grant_role_to_agent(
+            &backend, "/", &Agent::Anonymous, Role::Reader,
+        ).await?);
+        let mut grants = PolicyBackend::get_res_grants_for_res(&backend, "/").await?;
+        grants.sort_unstable();
+        assert_eq!(grants, [
+            (Agent::Anonymous, vec![Role::Reader]),
+            (agent.clone(), vec![role]),
+        ]);
+        assert_eq!(
+            PolicyBackend::get_res_grants_for_agent(&backend, &agent).await?,
+            [("/".to_string(), vec![role])],
+        );
+        assert!(PolicyBackend::get_res_grants_for_agent(&backend, &user.clone().into()).await?
+            .is_empty());
+        assert!(PolicyBackend::res_revoke_role_from_agent(&backend, "/", &agent, role).await?);
+        assert!(!PolicyBackend::res_revoke_role_from_agent(&backend, "/", &agent, role).await?);
+        assert!(PolicyBackend::get_res_grants_for_agent(&backend, &agent).await?.is_empty());
+
+        assert!(PolicyBackend::remove_user_from_group(&backend, &group, &user).await?);
+        assert!(!PolicyBackend::remove_user_from_group(&backend, &group, &user).await?);
+        assert!(PolicyBackend::get_groups_for_user(&backend, &user).await?.is_empty());
+        assert_eq!(
+            PolicyBackend::get_group_members(&backend, &group).await?,
+            [other],
+        );
+        Ok(())
+    }
+
+}
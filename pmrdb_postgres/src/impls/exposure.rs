@@ -0,0 +1,427 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    exposure::{
+        Exposure,
+        Exposures,
+        traits::ExposureBackend,
+    },
+    listing::{
+        Cursor,
+        ListingQuery,
+        Page,
+    },
+};
+use sqlx::{Postgres, QueryBuilder, Row};
+
+use crate::{
+    PostgresBackend,
+    chrono::Utc,
+    impls::listing::push_listing_query,
+};
+
+async fn insert_exposure_postgres(
+    backend: &PostgresBackend,
+    description: Option<&str>,
+    workspace_id: i64,
+    workspace_tag_id: Option<i64>,
+    commit_id: &str,
+    default_file_id: Option<i64>,
+) -> Result<i64, BackendError> {
+    let created_ts = Utc::now().timestamp();
+    let id = sqlx::query!(
+        r#"
+INSERT INTO exposure (
+    description,
+    workspace_id,
+    workspace_tag_id,
+    commit_id,
+    created_ts,
+    default_file_id
+)
+VALUES ( $1, $2, $3, $4, $5, $6 )
+RETURNING id
+        "#,
+        description,
+        workspace_id,
+        workspace_tag_id,
+        commit_id,
+        created_ts,
+        default_file_id,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(id)
+}
+
+async fn get_exposure_by_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<Exposure, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    description,
+    workspace_id,
+    workspace_tag_id,
+    commit_id,
+    created_ts,
+    default_file_id
+FROM exposure
+WHERE id = $1
+"#,
+        id,
+    )
+    .map(|row| Exposure {
+        id: row.id,
+        description: row.description,
+        workspace_id: row.workspace_id,
+        workspace_tag_id: row.workspace_tag_id,
+        commit_id: row.commit_id,
+        created_ts: row.created_ts,
+        default_file_id: row.default_file_id,
+        // TODO map to files.
+        files: None,
+    })
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+async fn list_exposures_postgres(
+    backend: &PostgresBackend,
+) -> Result<Exposures, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    description,
+    workspace_id,
+    workspace_tag_id,
+    commit_id,
+    created_ts,
+    default_file_id
+FROM exposure
+ORDER BY id
+"#,
+    )
+    .map(|row| Exposure {
+        id: row.id,
+        description: row.description,
+        workspace_id: row.workspace_id,
+        workspace_tag_id: row.workspace_tag_id,
+        commit_id: row.commit_id,
+        created_ts: row.created_ts,
+        default_file_id: row.default_file_id,
+        // won't have files.
+        files: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(rec.into())
+}
+
+async fn list_exposures_page_postgres(
+    backend: &PostgresBackend,
+    query: &ListingQuery,
+) -> Result<Page<Exposure>, BackendError> {
+    let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(r#"
+SELECT
+    id,
+    description,
+    workspace_id,
+    workspace_tag_id,
+    commit_id,
+    created_ts,
+    default_file_id
+FROM exposure"#);
+    let page_size = push_listing_query(&mut query_builder, query, &[])?;
+
+    let recs = query_builder
+        .build()
+        .try_map(|row| Ok(Exposure {
+            id: row.try_get("id")?,
+            description: row.try_get("description")?,
+            workspace_id: row.try_get("workspace_id")?,
+            workspace_tag_id: row.try_get("workspace_tag_id")?,
+            commit_id: row.try_get("commit_id")?,
+            created_ts: row.try_get("created_ts")?,
+            default_file_id: row.try_get("default_file_id")?,
+            // won't have files.
+            files: None,
+        }))
+        .fetch_all(&*backend.pool)
+        .await?;
+
+    Ok(Page::from_fetched(recs, page_size, |exposure| Cursor::new(
+        query.sort,
+        exposure.id,
+        exposure.created_ts,
+        exposure.description.as_deref(),
+    )))
+}
+
+async fn list_exposures_by_ids_postgres(
+    backend: &PostgresBackend,
+    ids: &[i64],
+) -> Result<Exposures, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    description,
+    workspace_id,
+    workspace_tag_id,
+    commit_id,
+    created_ts,
+    default_file_id
+FROM exposure
+WHERE id = ANY($1)
+ORDER BY id
+"#,
+        ids,
+    )
+    .map(|row| Exposure {
+        id: row.id,
+        description: row.description,
+        workspace_id: row.workspace_id,
+        workspace_tag_id: row.workspace_tag_id,
+        commit_id: row.commit_id,
+        created_ts: row.created_ts,
+        default_file_id: row.default_file_id,
+        // won't have files.
+        files: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+
+    Ok(recs.into())
+}
+
+async fn list_exposures_for_workspace_postgres(
+    backend: &PostgresBackend,
+    workspace_id: i64,
+) -> Result<Exposures, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    description,
+    workspace_id,
+    workspace_tag_id,
+    commit_id,
+    created_ts,
+    default_file_id
+FROM exposure
+WHERE workspace_id = $1
+ORDER BY id
+"#,
+        workspace_id,
+    )
+    .map(|row| Exposure {
+        id: row.id,
+        description: row.description,
+        workspace_id: row.workspace_id,
+        workspace_tag_id: row.workspace_tag_id,
+        commit_id: row.commit_id,
+        created_ts: row.created_ts,
+        default_file_id: row.default_file_id,
+        // won't have files.
+        files: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(rec.into())
+}
+
+async fn set_default_file_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    file_id: i64,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE exposure
+SET default_file_id = $2
+WHERE id = $1
+    AND $2 IN (
+        SELECT id
+        FROM exposure_file
+        WHERE exposure_id = $1
+    )
+"#,
+        id,
+        file_id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+#[async_trait]
+impl ExposureBackend for PostgresBackend {
+    async fn insert(
+        &self,
+        description: Option<&str>,
+        workspace_id: i64,
+        workspace_tag_id: Option<i64>,
+        commit_id: &str,
+        default_file_id: Option<i64>,
+    ) -> Result<i64, BackendError>{
+        insert_exposure_postgres(
+            &self,
+            description,
+            workspace_id,
+            workspace_tag_id,
+            commit_id,
+            default_file_id,
+        ).await
+    }
+
+    async fn list(
+        &self,
+    ) -> Result<Exposures, BackendError> {
+        list_exposures_postgres(
+            &self,
+        ).await
+    }
+
+    async fn list_page(
+        &self,
+        query: &ListingQuery,
+    ) -> Result<Page<Exposure>, BackendError> {
+        list_exposures_page_postgres(
+            &self,
+            query,
+        ).await
+    }
+
+    async fn list_by_ids(
+        &self,
+        ids: &[i64],
+    ) -> Result<Exposures, BackendError> {
+        list_exposures_by_ids_postgres(
+            &self,
+            ids,
+        ).await
+    }
+
+    async fn list_for_workspace(
+        &self,
+        workspace_id: i64,
+    ) -> Result<Exposures, BackendError> {
+        list_exposures_for_workspace_postgres(
+            &self,
+            workspace_id,
+        ).await
+    }
+
+    async fn get_id(
+        &self,
+        id: i64,
+    ) -> Result<Exposure, BackendError> {
+        get_exposure_by_id_postgres(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn set_default_file(
+        &self,
+        id: i64,
+        file_id: i64,
+    ) -> Result<bool, BackendError> {
+        set_default_file_postgres(
+            &self,
+            id,
+            file_id,
+        ).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use pmrcore::{
+        exposure::{
+            Exposure,
+            traits::{
+                Exposure as _,
+                ExposureBackend,
+            },
+        },
+        platform::{
+            MCPlatform,
+            PlatformConnector as _,
+        },
+        workspace::traits::Workspace,
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+    use super::super::workspace::testing::make_example_workspace;
+
+    pub(crate) async fn make_example_exposure(
+        backend: &dyn ExposureBackend,
+        workspace_id: i64,
+    ) -> anyhow::Result<i64> {
+        let description = format!("Exposure for Workspace {workspace_id}");
+        Ok(backend.insert(
+            Some(&description),
+            workspace_id,
+            None,
+            "abcdef".into(),
+            None,
+        ).await?)
+    }
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let workspace_id = make_example_workspace(&backend).await?;
+        let id = make_example_exposure(&backend, workspace_id).await?;
+        let exposure = ExposureBackend::get_id(
+            &backend, id
+        ).await?;
+        assert_eq!(exposure, Exposure {
+            id: 1,
+            description: Some("Exposure for Workspace 1".into()),
+            workspace_id: 1,
+            workspace_tag_id: None,
+            commit_id: "abcdef".into(),
+            created_ts: 1234567890,
+            default_file_id: None,
+            files: None,
+            // files: Some([].to_vec().into()),
+        });
+
+        let workspace = MCPlatform::get_workspace(&backend, id).await?;
+        assert_eq!(workspace.id(), workspace_id);
+        let exposures = workspace.exposures().await?;
+        assert_eq!(exposures.len(), 1);
+        assert_eq!(exposures[0].id(), exposure.id);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_get_exposure_workspace() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let w1 = make_example_workspace(&backend).await?;
+        let w2 = make_example_workspace(&backend).await?;
+        make_example_exposure(&backend, w1).await?;
+        make_example_exposure(&backend, w1).await?;
+        make_example_exposure(&backend, w2).await?;
+        make_example_exposure(&backend, w2).await?;
+        make_example_exposure(&backend, w2).await?;
+        make_example_exposure(&backend, w2).await?;
+
+        let eb: &dyn ExposureBackend = &backend;
+        assert_eq!(2, eb.list_for_workspace(w1).await?.len());
+        assert_eq!(4, eb.list_for_workspace(w2).await?.len());
+
+        Ok(())
+    }
+
+}
@@ -0,0 +1,297 @@
+use async_trait::async_trait;
+use futures::future;
+use pmrcore::{
+    error::BackendError,
+    exposure::profile::{
+        ExposureFileProfile,
+        traits::ExposureFileProfileBackend,
+    },
+    task_template::{
+        UserInput,
+        UserInputMap,
+    },
+};
+
+use crate::PostgresBackend;
+
+async fn set_ef_profile_postgres(
+    backend: &PostgresBackend,
+    exposure_file_id: i64,
+    profile_id: i64,
+) -> Result<(), BackendError> {
+    sqlx::query!(
+        r#"
+INSERT INTO exposure_file_profile (
+    exposure_file_id,
+    profile_id
+)
+VALUES ($1, $2)
+ON CONFLICT(exposure_file_id)
+DO UPDATE SET
+    profile_id = $2
+        "#,
+        exposure_file_id,
+        profile_id,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(())
+}
+
+async fn get_ef_profile_core_postgres(
+    backend: &PostgresBackend,
+    exposure_file_id: i64,
+) -> Result<Option<ExposureFileProfile>, BackendError> {
+    Ok(sqlx::query!(r#"
+SELECT
+    id,
+    exposure_file_id,
+    profile_id
+FROM exposure_file_profile
+WHERE exposure_file_id = $1
+"#,
+        exposure_file_id,
+    )
+        .map(|row| ExposureFileProfile::new(
+            row.id,
+            row.exposure_file_id,
+            row.profile_id,
+        ))
+        .fetch_optional(&*backend.pool)
+        .await?
+    )
+}
+
+async fn get_ef_profile_postgres(
+    backend: &PostgresBackend,
+    exposure_file_id: i64,
+) -> Result<Option<ExposureFileProfile>, BackendError> {
+    let rec = get_ef_profile_core_postgres(
+        backend,
+        exposure_file_id,
+    ).await?;
+
+    if let Some(mut rec) = rec {
+        rec.user_input = sqlx::query!(r#"
+SELECT
+    arg_id,
+    input,
+    multiple
+FROM exposure_file_profile_input
+WHERE
+    exposure_file_profile_id = $1
+    "#,
+            rec.id,
+        )
+            .try_map(|row| Ok((
+                row.arg_id,
+                if row.multiple {
+                    UserInput::Multiple(serde_json::from_str(&row.input)
+                        .map_err(|e| sqlx::Error::Decode(e.into()))?)
+                } else {
+                    UserInput::Single(row.input)
+                },
+            )))
+            .fetch_all(&*backend.pool)
+            .await?
+            .into_iter()
+            .collect();
+        Ok(Some(rec))
+    } else {
+        Ok(None)
+    }
+}
+
+async fn update_ef_user_input_postgres(
+    backend: &PostgresBackend,
+    exposure_file_id: i64,
+    user_input: &UserInputMap,
+) -> Result<(), BackendError> {
+    if let Some(rec) = get_ef_profile_core_postgres(backend, exposure_file_id).await? {
+        let exposure_file_profile_id = rec.id;
+        future::try_join_all(
+            user_input.iter()
+                .map(|(arg_id, input)| async move {
+                    // multiple values are stored as a JSON list
+                    let (input, multiple) = match input {
+                        UserInput::Single(value) => (value.clone(), false),
+                        UserInput::Multiple(values) => (
+                            serde_json::to_string(values)
+                                .expect("serialization shouldn't fail on this basic type"),
+                            true,
+                        ),
+                    };
+                    sqlx::query!(
+                        r#"
+INSERT INTO exposure_file_profile_input (
+    exposure_file_profile_id,
+    arg_id,
+    input,
+    multiple
+)
+VALUES ($1, $2, $3, $4)
+ON CONFLICT(exposure_file_profile_id, arg_id)
+DO UPDATE SET
+    arg_id = $2,
+    input = $3,
+    multiple = $4
+    "#,
+                        exposure_file_profile_id,
+                        arg_id,
+                        input,
+                        multiple,
+                    )
+                        .execute(&*backend.pool)
+                        .await?;
+                    Ok::<(), BackendError>(())
+                })
+        )
+            .await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl ExposureFileProfileBackend for PostgresBackend {
+    async fn set_ef_profile(
+        &self,
+        exposure_file_id: i64,
+        profile_id: i64,
+    ) -> Result<(), BackendError> {
+        set_ef_profile_postgres(
+            &self,
+            exposure_file_id,
+            profile_id,
+        ).await
+    }
+
+    async fn get_ef_profile(
+        &self,
+        exposure_file_id: i64,
+    ) -> Result<Option<ExposureFileProfile>, BackendError> {
+        get_ef_profile_postgres(
+            &self,
+            exposure_file_id,
+        ).await
+    }
+
+    async fn update_ef_user_input(
+        &self,
+        exposure_file_id: i64,
+        user_input: &UserInputMap,
+    ) -> Result<(), BackendError> {
+        update_ef_user_input_postgres(
+            &self,
+            exposure_file_id,
+            user_input,
+        ).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use pmrcore::{
+        exposure::profile::traits::ExposureFileProfileBackend,
+        platform::PlatformConnector as _,
+        profile::traits::{
+            ProfileBackend,
+            ProfileViewsBackend,
+            ViewTaskTemplateBackend,
+        },
+        task_template::UserInputMap,
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+    use super::super::{
+        workspace::testing::make_example_workspace,
+        exposure::testing::make_example_exposure,
+        exposure_file::testing::make_example_exposure_file,
+    };
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let exposure_file_id = make_example_exposure_file(
+            &backend,
+            make_example_exposure(
+                &backend,
+                make_example_workspace(&backend).await?,
+            ).await?,
+            "README.md"
+        ).await?;
+
+        let pb: &dyn ProfileBackend = &backend;
+        let profile_id = pb.insert_profile("Test Profile", "").await?;
+        let empty_profile_id = pb.insert_profile("Empty Profile", "").await?;
+
+        let vttb: &dyn ViewTaskTemplateBackend = &backend;
+        let v1 = vttb.insert_view_task_template("view1", "", 1).await?;
+        let v2 = vttb.insert_view_task_template("view2", "", 2).await?;
+
+        let pvb: &dyn ProfileViewsBackend = &backend;
+        pvb.insert_profile_views(profile_id, v1).await?;
+        pvb.insert_profile_views(profile_id, v2).await?;
+
+        let efpb: &dyn ExposureFileProfileBackend = &backend;
+        assert!(efpb.get_ef_profile(exposure_file_id)
+            .await?
+            .is_none());
+        efpb.set_ef_profile(
+            exposure_file_id,
+            profile_id,
+        ).await?;
+        let ef_profile = efpb.get_ef_profile(exposure_file_id)
+            .await?
+            .expect("profile should have been assigned here");
+
+        assert_eq!(ef_profile.exposure_file_id, exposure_file_id);
+        assert_eq!(ef_profile.profile_id, profile_id);
+        assert_eq!(ef_profile.user_input.len(), 0);
+
+        let user_input = UserInputMap::from([
+            (1, "First".into()),
+            (2, ["Alternate", "Other"].into()),
+        ]);
+        efpb.update_ef_user_input(exposure_file_id, &user_input).await?;
+
+        let ef_profile = efpb.get_ef_profile(exposure_file_id)
+            .await?
+            .expect("profile should have been assigned here");
+        assert_eq!(ef_profile.user_input, user_input);
+
+        efpb.set_ef_profile(
+            exposure_file_id,
+            empty_profile_id,
+        ).await?;
+        let empty_ef_profile = efpb.get_ef_profile(exposure_file_id)
+            .await?
+            .expect("profile should have been assigned here");
+
+        assert_eq!(empty_ef_profile.exposure_file_id, exposure_file_id);
+        assert_eq!(empty_ef_profile.profile_id, empty_profile_id);
+        // user input remains unchanged
+        assert_eq!(empty_ef_profile.user_input.len(), 2);
+
+        // Updating of user input will only replace existing values
+        efpb.update_ef_user_input(
+            exposure_file_id,
+            &UserInputMap::from([
+                (2, "Second".into()),
+                (3, ["Third", "Fourth"].into()),
+            ])
+        ).await?;
+        let final_ef_profile = efpb.get_ef_profile(exposure_file_id)
+            .await?
+            .expect("profile should have been assigned here");
+        assert_eq!(final_ef_profile.user_input, UserInputMap::from([
+            (1, "First".into()),
+            (2, "Second".into()),
+            (3, ["Third", "Fourth"].into()),
+        ]));
+
+        Ok(())
+    }
+}
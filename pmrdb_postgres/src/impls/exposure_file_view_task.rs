@@ -0,0 +1,580 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::{
+        BackendError,
+        Error,
+    },
+    exposure::task::{
+        ExposureFileViewBuild,
+        ExposureFileViewTask,
+        ExposureFileViewTaskStatus,
+        traits::ExposureTaskBackend,
+    },
+};
+
+use crate::{
+    PostgresBackend,
+    chrono::Utc,
+};
+
+async fn insert_exposure_file_view_task_postgres(
+    backend: &PostgresBackend,
+    exposure_file_view_id: i64,
+    view_task_template_id: i64,
+    task_id: Option<i64>,
+) -> Result<i64, BackendError> {
+    let mut tx = backend.pool.begin().await?;
+    let created_ts = task_id.map(|_| Utc::now().timestamp());
+    let id = sqlx::query!(
+        r#"
+INSERT INTO exposure_file_view_task (
+    exposure_file_view_id,
+    view_task_template_id,
+    task_id,
+    created_ts,
+    ready
+)
+VALUES ( $1, $2, $3, $4, false )
+RETURNING id
+        "#,
+        exposure_file_view_id,
+        view_task_template_id,
+        task_id,
+        created_ts,
+    )
+    .map(|row| row.id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let rows_affected = sqlx::query!(
+        r#"
+UPDATE exposure_file_view
+SET
+    exposure_file_view_task_id = $1
+WHERE
+    id = $2
+        "#,
+        id,
+        exposure_file_view_id,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if rows_affected == 0 {
+        tx.rollback().await?;
+        Err(BackendError::AppInvariantViolation(
+            format!(
+                "no such exposure_file_view with id: {}",
+                exposure_file_view_id,
+            )
+        ))
+    } else {
+        tx.commit().await?;
+        Ok(id)
+    }
+}
+
+async fn select_exposure_file_view_task_for_view_postgres(
+    backend: &PostgresBackend,
+    exposure_file_view_id: i64,
+) -> Result<Option<ExposureFileViewTask>, BackendError> {
+    let rec = sqlx::query_as!(
+        ExposureFileViewTask,
+        r#"
+SELECT
+    id,
+    exposure_file_view_id,
+    view_task_template_id,
+    task_id,
+    created_ts,
+    ready
+FROM
+    exposure_file_view_task
+WHERE
+    id = (
+        SELECT
+            exposure_file_view_task_id
+        FROM
+            exposure_file_view
+        WHERE
+            id = $1
+    )
+        "#,
+        exposure_file_view_id,
+    )
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+async fn finalize_exposure_file_view_task_with_task_id_postgres(
+    backend: &PostgresBackend,
+    task_id: i64,
+) -> Result<Option<(i64, Option<String>)>, Error> {
+    let some_task_id = Some(task_id);
+    let mut tx = backend.pool.begin().await
+        .map_err(BackendError::from)?;
+    let rows_affected = sqlx::query!(
+        r#"
+UPDATE
+    exposure_file_view_task
+SET
+    ready = true
+WHERE
+    task_id = $1
+        "#,
+        some_task_id,
+    )
+        .execute(&mut *tx)
+        .await
+        .map_err(BackendError::from)?
+        .rows_affected();
+
+    if rows_affected == 0 {
+        tx.rollback().await
+            .map_err(BackendError::from)?;
+        return Err(Error::Backend(BackendError::AppInvariantViolation(
+            format!(
+                "no exposure_file_view_task bound with task_id: {}",
+                task_id,
+            )
+        )))
+    }
+
+    let result = sqlx::query!(
+        r#"
+UPDATE
+    exposure_file_view
+SET
+    view_key = (
+        SELECT
+            view_key
+        FROM
+            view_task_template
+        WHERE id = (
+            SELECT
+                view_task_template_id
+            FROM
+                exposure_file_view
+            WHERE
+                exposure_file_view_task_id = (
+                    SELECT
+                        id
+                    FROM
+                        exposure_file_view_task
+                    WHERE
+                        task_id = $1
+                )
+        )
+    )
+WHERE id = (
+    SELECT
+        id
+    FROM
+        exposure_file_view
+    WHERE
+        exposure_file_view_task_id = (
+            SELECT
+                id
+            FROM
+                exposure_file_view_task
+            WHERE
+                task_id = $1
+        )
+)
+RETURNING id, view_key
+"#,
+        some_task_id,
+    )
+    // TODO if the view_key isn't updated it should be an error...
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(BackendError::from)?
+    .map(|result| (result.id, result.view_key));
+
+    tx.commit().await.map_err(BackendError::from)?;
+
+    // ... there are no distinctions between that or the view_key isn't
+    // defined, but a None result should indicate that something has
+    // gone wrong.
+    Ok(result)
+}
+
+
+async fn list_view_tasks_for_exposure_postgres(
+    backend: &PostgresBackend,
+    exposure_id: i64,
+) -> Result<Vec<ExposureFileViewTaskStatus>, BackendError> {
+    let recs = sqlx::query!(
+        r#"
+SELECT
+    exposure_file.id AS exposure_file_id,
+    exposure_file.workspace_file_path,
+    exposure_file_view.id AS exposure_file_view_id,
+    view_task_template.view_key,
+    exposure_file_view_task.task_id AS "task_id?"
+FROM
+    exposure_file
+JOIN
+    exposure_file_view ON exposure_file_view.exposure_file_id = exposure_file.id
+JOIN
+    view_task_template ON view_task_template.id = exposure_file_view.view_task_template_id
+LEFT JOIN
+    exposure_file_view_task ON exposure_file_view_task.id = exposure_file_view.exposure_file_view_task_id
+WHERE
+    exposure_file.exposure_id = $1
+ORDER BY
+    exposure_file.workspace_file_path,
+    view_task_template.view_key
+        "#,
+        exposure_id,
+    )
+    .map(|row| ExposureFileViewTaskStatus {
+        exposure_file_id: row.exposure_file_id,
+        workspace_file_path: row.workspace_file_path,
+        exposure_file_view_id: row.exposure_file_view_id,
+        view_key: row.view_key,
+        task_id: row.task_id,
+        task: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
+
+async fn list_view_builds_postgres(
+    backend: &PostgresBackend,
+    task_template_id: Option<i64>,
+) -> Result<Vec<ExposureFileViewBuild>, BackendError> {
+    let recs = sqlx::query!(
+        r#"
+SELECT
+    exposure_file.exposure_id,
+    exposure_file.id AS exposure_file_id,
+    exposure_file.workspace_file_path,
+    exposure_file_view.id AS exposure_file_view_id,
+    view_task_template.view_key,
+    view_task_template.task_template_id,
+    exposure_file_view_task.task_id AS "task_id?"
+FROM
+    exposure_file
+JOIN
+    exposure_file_view ON exposure_file_view.exposure_file_id = exposure_file.id
+JOIN
+    view_task_template ON view_task_template.id = exposure_file_view.view_task_template_id
+LEFT JOIN
+    exposure_file_view_task ON exposure_file_view_task.id = exposure_file_view.exposure_file_view_task_id
+WHERE
+    exposure_file_view.view_key IS NOT NULL
+    AND ($1::BIGINT IS NULL OR view_task_template.task_template_id = $1)
+ORDER BY
+    exposure_file.exposure_id,
+    exposure_file.workspace_file_path,
+    view_task_template.view_key
+        "#,
+        task_template_id,
+    )
+    .map(|row| ExposureFileViewBuild {
+        exposure_id: row.exposure_id,
+        exposure_file_id: row.exposure_file_id,
+        workspace_file_path: row.workspace_file_path,
+        exposure_file_view_id: row.exposure_file_view_id,
+        view_key: row.view_key,
+        task_template_id: row.task_template_id,
+        task_id: row.task_id,
+        built_task_template_id: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
+
+#[async_trait]
+impl ExposureTaskBackend for PostgresBackend {
+    async fn create_task_for_view(
+        &self,
+        exposure_file_view_id: i64,
+        view_task_template_id: i64,
+        task_id: Option<i64>,
+    ) -> Result<i64, BackendError> {
+        insert_exposure_file_view_task_postgres(
+            &self,
+            exposure_file_view_id,
+            view_task_template_id,
+            task_id,
+        ).await
+    }
+
+    async fn select_task_for_view(
+        &self,
+        exposure_file_id: i64,
+    ) -> Result<Option<ExposureFileViewTask>, BackendError> {
+        select_exposure_file_view_task_for_view_postgres(
+            &self,
+            exposure_file_id,
+        ).await
+    }
+
+    async fn finalize_task_id(
+        &self,
+        task_id: i64,
+    ) -> Result<Option<(i64, Option<String>)>, Error> {
+        finalize_exposure_file_view_task_with_task_id_postgres(
+            &self,
+            task_id,
+        ).await
+    }
+
+    async fn list_view_tasks_for_exposure(
+        &self,
+        exposure_id: i64,
+    ) -> Result<Vec<ExposureFileViewTaskStatus>, BackendError> {
+        list_view_tasks_for_exposure_postgres(&self, exposure_id).await
+    }
+
+    async fn list_view_builds(
+        &self,
+        task_template_id: Option<i64>,
+    ) -> Result<Vec<ExposureFileViewBuild>, BackendError> {
+        list_view_builds_postgres(&self, task_template_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmrcore::{
+        exposure::traits::ExposureFileViewBackend,
+        profile::traits::ViewTaskTemplateBackend,
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    use super::super::{
+        exposure::testing::make_example_exposure,
+        exposure_file::testing::make_example_exposure_file,
+        exposure_file_view::testing::make_example_exposure_file_view,
+        workspace::testing::make_example_workspace,
+    };
+
+    pub(crate) async fn make_example_exposure_file_view_task(
+        backend: &dyn ExposureTaskBackend,
+        exposure_file_view_id: i64,
+        view_task_template_id: i64,
+        task_id: Option<i64>,
+    ) -> anyhow::Result<i64> {
+        Ok(backend.create_task_for_view(
+            exposure_file_view_id,
+            view_task_template_id,
+            task_id,
+        ).await?)
+    }
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        // normally this is done, but we need the inner type not the opaque impl
+        // let backend = PostgresBackend::mc(create_database().into())
+        //     .await
+        //     .map_err(anyhow::Error::from_boxed)?;
+        let backend = PostgresBackend::connect(create_database().into())
+            .await?
+            .migrate_mc()
+            .await?;
+
+        // this is now done by `make_example_exposure_file_view`
+        // let vttb: &dyn ViewTaskTemplateBackend = &backend;
+        // let v1 = vttb.insert_view_task_template("view1", "", 1).await?;
+
+        let workspace_id = make_example_workspace(&backend).await?;
+        let exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let exposure_file_id = make_example_exposure_file(
+            &backend, exposure_id, "some_demo_file").await?;
+        let (exposure_file_view_id, v1) = make_example_exposure_file_view(
+            &backend, exposure_file_id, None, "view1").await?;
+        let _ = make_example_exposure_file_view_task(
+            &backend, exposure_file_view_id, v1, None).await?;
+
+        let etb: &dyn ExposureTaskBackend = &backend;
+        let et = etb.select_task_for_view(exposure_file_view_id).await?;
+        assert_eq!(et, Some(ExposureFileViewTask {
+            id: 1,
+            exposure_file_view_id: 1,
+            view_task_template_id: 1,
+            task_id: None,
+            created_ts: None,
+            ready: false,
+        }));
+
+        // add a few more tasks
+        let _ = make_example_exposure_file_view_task(
+            &backend, exposure_file_view_id, v1, None).await?;
+        let et = etb.select_task_for_view(exposure_file_view_id).await?;
+        assert_eq!(et.map(|et| et.id), Some(2));
+
+        let _ = make_example_exposure_file_view_task(
+            &backend, exposure_file_view_id, v1, None).await?;
+        let et = etb.select_task_for_view(exposure_file_view_id).await?;
+        assert_eq!(et.map(|et| et.id), Some(3));
+
+        let c = sqlx::query!(
+            r#"SELECT COUNT(id) AS "count!" FROM exposure_file_view_task"#
+        )
+            .fetch_one(&*backend.pool)
+            .await?;
+        assert_eq!(c.count, 3);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_failure() -> anyhow::Result<()> {
+        // normally this is done, but we need the inner type not the opaque impl
+        // let backend = PostgresBackend::mc(create_database().into())
+        //     .await
+        //     .map_err(anyhow::Error::from_boxed)?;
+        let backend = PostgresBackend::connect(create_database().into())
+            .await?
+            .migrate_mc()
+            .await?;
+
+        let vttb: &dyn ViewTaskTemplateBackend = &backend;
+        let v1 = vttb.insert_view_task_template("view1", "", 1).await?;
+        let exposure_file_view_task_id = make_example_exposure_file_view_task(
+            &backend, 1, v1, None).await;
+        assert!(exposure_file_view_task_id.is_err());
+
+        // ensure no actual insertion happened.
+        let c = sqlx::query!(
+            r#"SELECT COUNT(id) AS "count!" FROM exposure_file_view_task"#
+        )
+            .fetch_one(&*backend.pool)
+            .await?;
+        assert_eq!(c.count, 0);
+
+        Ok(())
+    }
+
+
+    #[async_std::test]
+    async fn test_list_view_tasks_for_exposure() -> anyhow::Result<()> {
+        let backend = PostgresBackend::connect(create_database().into())
+            .await?
+            .migrate_mc()
+            .await?;
+
+        let workspace_id = make_example_workspace(&backend).await?;
+        let exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let other_exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let file_b = make_example_exposure_file(
+            &backend, exposure_id, "file_b").await?;
+        let file_a = make_example_exposure_file(
+            &backend, exposure_id, "file_a").await?;
+        let other_file = make_example_exposure_file(
+            &backend, other_exposure_id, "file_a").await?;
+        let (view_b2, vb2) = make_example_exposure_file_view(
+            &backend, file_b, None, "view2").await?;
+        let (view_b1, _) = make_example_exposure_file_view(
+            &backend, file_b, None, "view1").await?;
+        let (view_a1, va1) = make_example_exposure_file_view(
+            &backend, file_a, None, "view1").await?;
+        let (other_view, other_v) = make_example_exposure_file_view(
+            &backend, other_file, None, "view1").await?;
+
+        make_example_exposure_file_view_task(
+            &backend, view_b2, vb2, Some(3)).await?;
+        make_example_exposure_file_view_task(
+            &backend, view_a1, va1, Some(4)).await?;
+        // only the most recent task for the view is listed
+        make_example_exposure_file_view_task(
+            &backend, view_a1, va1, Some(5)).await?;
+        make_example_exposure_file_view_task(
+            &backend, other_view, other_v, Some(6)).await?;
+
+        let etb: &dyn ExposureTaskBackend = &backend;
+        let results = etb.list_view_tasks_for_exposure(exposure_id).await?
+            .into_iter()
+            .map(|status| (
+                status.exposure_file_id,
+                status.workspace_file_path,
+                status.exposure_file_view_id,
+                status.view_key,
+                status.task_id,
+            ))
+            .collect::<Vec<_>>();
+        assert_eq!(results, [
+            (file_a, "file_a".to_string(), view_a1, "view1".to_string(), Some(5)),
+            (file_b, "file_b".to_string(), view_b1, "view1".to_string(), None),
+            (file_b, "file_b".to_string(), view_b2, "view2".to_string(), Some(3)),
+        ]);
+
+        assert_eq!(etb.list_view_tasks_for_exposure(0).await?.len(), 0);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_list_view_builds() -> anyhow::Result<()> {
+        let backend = PostgresBackend::connect(create_database().into())
+            .await?
+            .migrate_mc()
+            .await?;
+
+        let workspace_id = make_example_workspace(&backend).await?;
+        let exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let other_exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let file_b = make_example_exposure_file(
+            &backend, exposure_id, "file_b").await?;
+        let file_a = make_example_exposure_file(
+            &backend, exposure_id, "file_a").await?;
+        let other_file = make_example_exposure_file(
+            &backend, other_exposure_id, "file_a").await?;
+        let (view_b1, vb1) = make_example_exposure_file_view(
+            &backend, file_b, None, "view1").await?;
+        let (view_b2, _) = make_example_exposure_file_view(
+            &backend, file_b, None, "view2").await?;
+        let (view_a1, va1) = make_example_exposure_file_view(
+            &backend, file_a, None, "view1").await?;
+        let (other_view, other_v) = make_example_exposure_file_view(
+            &backend, other_file, None, "view1").await?;
+
+        make_example_exposure_file_view_task(
+            &backend, view_b1, vb1, Some(3)).await?;
+        make_example_exposure_file_view_task(
+            &backend, view_a1, va1, Some(4)).await?;
+        make_example_exposure_file_view_task(
+            &backend, other_view, other_v, Some(5)).await?;
+        // views yet to be built are not listed
+        ExposureFileViewBackend::update_view_key(&backend, view_b2, None).await?;
+        ViewTaskTemplateBackend::migrate_view_task_templates(&backend, 1, 2).await?;
+        ViewTaskTemplateBackend::update_view_task_template_by_fields(
+            &backend, va1, "view1", "", 1).await?;
+
+        let etb: &dyn ExposureTaskBackend = &backend;
+        let results = etb.list_view_builds(None).await?
+            .into_iter()
+            .map(|build| (
+                build.exposure_id,
+                build.workspace_file_path,
+                build.exposure_file_view_id,
+                build.task_template_id,
+                build.task_id,
+            ))
+            .collect::<Vec<_>>();
+        assert_eq!(results, [
+            (exposure_id, "file_a".to_string(), view_a1, 1, Some(4)),
+            (exposure_id, "file_b".to_string(), view_b1, 2, Some(3)),
+            (other_exposure_id, "file_a".to_string(), other_view, 2, Some(5)),
+        ]);
+
+        let results = etb.list_view_builds(Some(1)).await?
+            .into_iter()
+            .map(|build| build.exposure_file_view_id)
+            .collect::<Vec<_>>();
+        assert_eq!(results, [view_a1]);
+        assert_eq!(etb.list_view_builds(Some(3)).await?.len(), 0);
+
+        Ok(())
+    }
+
+}
@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    exposure::task::traits::ExposureTaskTemplateBackend,
+    profile::ViewTaskTemplate,
+};
+
+use crate::PostgresBackend;
+
+async fn set_exposure_file_view_task_template_postgres(
+    backend: &PostgresBackend,
+    exposure_file_id: i64,
+    view_task_template_ids: &[i64],
+) -> Result<(), BackendError> {
+    // TODO is there a way to insert/delete just the delta?
+    let mut tx = backend.pool.begin().await?;
+    sqlx::query!(
+        r#"
+DELETE FROM
+    exposure_file_view_task_template
+WHERE
+    exposure_file_id = $1
+        "#,
+        exposure_file_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let mut vttids = view_task_template_ids.iter();
+
+    while let Some(vtti) = vttids.next() {
+        sqlx::query!(
+            r#"
+INSERT INTO exposure_file_view_task_template (
+    exposure_file_id,
+    view_task_template_id
+)
+VALUES ( $1, $2 )
+            "#,
+            exposure_file_id,
+            vtti,
+        )
+        .execute(&mut *tx)
+        .await?;
+    };
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn select_exposure_file_view_task_template_postgres(
+    backend: &PostgresBackend,
+    exposure_file_id: i64,
+) -> Result<Vec<ViewTaskTemplate>, BackendError> {
+    let rec = sqlx::query!(
+        r#"
+SELECT
+    id,
+    view_key,
+    description,
+    task_template_id,
+    updated_ts,
+    depends_on
+FROM
+    view_task_template
+WHERE
+    id IN (
+        SELECT
+            view_task_template_id
+        FROM
+            exposure_file_view_task_template
+        WHERE
+            exposure_file_id = $1
+    )
+ORDER BY
+    id
+        "#,
+        exposure_file_id,
+    )
+    .map(|row| ViewTaskTemplate {
+        id: row.id,
+        view_key: row.view_key,
+        description: row.description,
+        task_template_id: row.task_template_id,
+        updated_ts: row.updated_ts,
+        depends_on: row.depends_on,
+        // task_template is from the other backend
+        task_template: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+#[async_trait]
+impl ExposureTaskTemplateBackend for PostgresBackend {
+    async fn set_file_templates(
+        &self,
+        exposure_file_id: i64,
+        view_task_template_ids: &[i64],
+    ) -> Result<(), BackendError> {
+        // TODO delete old templates
+        set_exposure_file_view_task_template_postgres(
+            &self,
+            exposure_file_id,
+            view_task_template_ids,
+        ).await?;
+        Ok(())
+    }
+
+    async fn get_file_templates(
+        &self,
+        exposure_file_id: i64,
+    ) -> Result<Vec<ViewTaskTemplate>, BackendError> {
+        select_exposure_file_view_task_template_postgres(
+            &self,
+            exposure_file_id,
+        ).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        profile::traits::ViewTaskTemplateBackend,
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    use super::super::{
+        exposure::testing::make_example_exposure,
+        exposure_file::testing::make_example_exposure_file,
+        workspace::testing::make_example_workspace,
+    };
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let vttb: &dyn ViewTaskTemplateBackend = &backend;
+        let v1 = vttb.insert_view_task_template("view1", "", 1).await?;
+        let v2 = vttb.insert_view_task_template("view2", "", 2).await?;
+        let v3 = vttb.insert_view_task_template("view3", "", 3).await?;
+        let v4 = vttb.insert_view_task_template("view4", "", 4).await?;
+
+        let workspace_id = make_example_workspace(&backend).await?;
+        let exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let exposure_file_1 = make_example_exposure_file(
+            &backend, exposure_id, "some_demo_file").await?;
+        let exposure_file_2 = make_example_exposure_file(
+            &backend, exposure_id, "some_other_demo_file").await?;
+
+        ExposureTaskTemplateBackend::set_file_templates(
+            &backend, exposure_file_1, &[v1, v2, v3],
+        ).await?;
+        ExposureTaskTemplateBackend::set_file_templates(
+            &backend, exposure_file_2, &[v2, v4],
+        ).await?;
+
+        let templates1 = ExposureTaskTemplateBackend::get_file_templates(
+            &backend, exposure_file_1).await?;
+        assert_eq!(templates1.len(), 3);
+
+        let templates2 = ExposureTaskTemplateBackend::get_file_templates(
+            &backend, exposure_file_2).await?;
+        assert_eq!(templates2.len(), 2);
+
+        // TODO include following test for delete
+
+        ExposureTaskTemplateBackend::set_file_templates(
+            &backend, exposure_file_1, &[v2, v4]
+        ).await?;
+        let templates1 = ExposureTaskTemplateBackend::get_file_templates(
+            &backend, exposure_file_1).await?;
+        assert_eq!(templates1.len(), 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    idgen::{
+        HexId,
+        traits::GenAliasBackend,
+    },
+};
+
+use crate::PostgresBackend;
+
+#[async_trait]
+impl GenAliasBackend for PostgresBackend {
+    async fn next(&self) -> Result<HexId, BackendError> {
+        Ok(sqlx::query!("INSERT INTO gen_alias_seq DEFAULT VALUES RETURNING id")
+            .map(|row| row.id)
+            .fetch_one(&*self.pool)
+            .await?
+            .into()
+        )
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use pmrcore::{
+        idgen::traits::GenAliasBackend,
+        platform::PlatformConnector as _,
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let id = GenAliasBackend::next(&backend).await?;
+        assert_eq!(id.to_string(), "1");
+
+        for _ in 1..11 {
+            GenAliasBackend::next(&backend).await?;
+        }
+
+        let id = GenAliasBackend::next(&backend).await?;
+        assert_eq!(id.to_string(), "c");
+        Ok(())
+    }
+}
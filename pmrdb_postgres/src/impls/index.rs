@@ -0,0 +1,888 @@
+use std::collections::BTreeMap;
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    index::{
+        IdxKind,
+        IndexResourceSet,
+        IndexTerms,
+        ResourceBrief,
+        ResourceKindedTerms,
+        traits::{
+            IndexCoreDBBackend,
+            IndexCoreDBCache,
+        },
+    }
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::PostgresBackend;
+
+const WORD_COUNT: i64 = 60;
+
+async fn resolve_kind_postgres(
+    backend: &PostgresBackend,
+    kind: &str,
+) -> Result<i64, BackendError> {
+    let id = sqlx::query!(
+        r#"
+WITH q AS (
+    INSERT INTO idx_kind (
+        description
+    )
+    VALUES ( $1 )
+    ON CONFLICT (description) DO NOTHING
+    RETURNING id
+)
+SELECT id AS "id!" FROM q
+UNION
+SELECT id FROM idx_kind WHERE description = $1
+        "#,
+        kind,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+
+    Ok(id)
+}
+
+async fn resolve_idx_entry_postgres(
+    backend: &PostgresBackend,
+    idx_kind_id: i64,
+    term: &str,
+) -> Result<i64, BackendError> {
+    let id = sqlx::query!(
+        r#"
+WITH q AS (
+    INSERT INTO idx_entry (
+        idx_kind_id,
+        term
+    )
+    VALUES ( $1, $2 )
+    ON CONFLICT (idx_kind_id, lower(term)) DO NOTHING
+    RETURNING id
+)
+SELECT id AS "id!" FROM q
+UNION
+SELECT id FROM idx_entry WHERE idx_kind_id = $1 AND lower(term) = lower($2)
+        "#,
+        idx_kind_id,
+        term,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+
+    Ok(id)
+}
+
+async fn add_idx_entry_link_postgres(
+    backend: &PostgresBackend,
+    idx_entry_id: i64,
+    resource_path: &str,
+) -> Result<(), BackendError> {
+    sqlx::query!(
+        r#"
+INSERT INTO idx_entry_link (
+    idx_entry_id,
+    resource_path
+)
+VALUES ( $1, $2 )
+ON CONFLICT(idx_entry_id, resource_path) DO NOTHING
+        "#,
+        idx_entry_id,
+        resource_path,
+    )
+    .execute(&*backend.pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn idx_text_forget_postgres(
+    backend: &PostgresBackend,
+    resource_path: &str,
+) -> Result<(), BackendError> {
+    sqlx::query!(
+        r#"
+DELETE FROM idx_text
+WHERE resource_path = $1
+        "#,
+        resource_path,
+    )
+    .execute(&*backend.pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn add_idx_text_postgres(
+    backend: &PostgresBackend,
+    title: Option<&str>,
+    content: Option<&str>,
+    resource_path: &str,
+) -> Result<(), BackendError> {
+    idx_text_forget_postgres(backend, resource_path).await?;
+
+    sqlx::query!(
+        r#"
+INSERT INTO idx_text (
+    title,
+    content,
+    resource_path
+)
+VALUES ( $1, $2, $3 )
+        "#,
+        title,
+        content,
+        resource_path,
+    )
+    .execute(&*backend.pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn forget_resource_path_postgres(
+    backend: &PostgresBackend,
+    kind: Option<&str>,
+    resource_path: &str,
+) -> Result<(), BackendError> {
+    match kind {
+        None => {
+            sqlx::query!(
+                r#"
+DELETE FROM idx_entry_link
+WHERE
+    resource_path = $1
+                "#,
+                resource_path,
+            )
+                .execute(&*backend.pool)
+                .await?;
+            // Also forgets the text index.
+            idx_text_forget_postgres(backend, resource_path).await?;
+        }
+        Some(kind) => {
+            let idx_kind = match get_idx_kind_postgres(backend, kind).await? {
+                Some(idx_kind) => idx_kind,
+                None => return Ok(()),
+            };
+            sqlx::query!(
+                r#"
+DELETE FROM idx_entry_link
+WHERE
+    resource_path = $1 AND idx_entry_id IN (
+        SELECT
+            id
+        FROM
+            idx_entry
+        WHERE
+            idx_kind_id = $2
+    )
+                "#,
+                resource_path,
+                idx_kind.id,
+            )
+                .execute(&*backend.pool)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_kinds_postgres(
+    backend: &PostgresBackend,
+) -> Result<Vec<String>, BackendError> {
+    let recs = sqlx::query!(
+        r#"
+SELECT
+    description
+FROM
+    idx_kind
+ORDER BY
+    id
+        "#,
+    )
+    .map(|row| row.description)
+    .fetch_all(&*backend.pool)
+    .await?;
+
+    Ok(recs)
+}
+
+async fn get_idx_kind_postgres(
+    backend: &PostgresBackend,
+    kind: &str,
+) -> Result<Option<IdxKind>, BackendError> {
+     Ok(sqlx::query_as!(
+        IdxKind,
+        r#"
+SELECT
+    id,
+    description
+FROM
+    idx_kind
+WHERE
+    description = $1
+        "#,
+        kind,
+    )
+    .fetch_optional(&*backend.pool)
+    .await?)
+}
+
+async fn list_terms_postgres(
+    backend: &PostgresBackend,
+    kind: &str,
+) -> Result<Option<IndexTerms>, BackendError> {
+    let idx_kind = match get_idx_kind_postgres(backend, kind).await? {
+        Some(idx_kind) => idx_kind,
+        None => return Ok(None),
+    };
+
+    let terms = sqlx::query!(
+        r#"
+SELECT
+    term
+FROM
+    idx_entry
+WHERE
+    idx_kind_id = $1
+ORDER BY
+    id
+        "#,
+        idx_kind.id,
+    )
+    .map(|row| row.term)
+    .fetch_all(&*backend.pool)
+    .await?;
+
+    Ok(Some(IndexTerms {
+        kind: idx_kind,
+        terms,
+    }))
+}
+
+async fn list_resources_postgres(
+    backend: &PostgresBackend,
+    kind: &str,
+    term: &str,
+) -> Result<Option<IndexResourceSet>, BackendError> {
+    let idx_kind = match get_idx_kind_postgres(backend, kind).await? {
+        Some(idx_kind) => idx_kind,
+        None => return Ok(None),
+    };
+
+    let idx_entry_id = match sqlx::query!(
+        r#"
+SELECT
+    id
+FROM
+    idx_entry
+WHERE
+    idx_kind_id = $1
+AND
+    lower(term) = lower($2)
+        "#,
+        idx_kind.id,
+        term,
+    )
+    .map(|row| row.id)
+    .fetch_optional(&*backend.pool)
+    .await? {
+        Some(idx_entry_id) => idx_entry_id,
+        None => return Ok(None),
+    };
+
+    let resource_paths = sqlx::query!(
+        r#"
+SELECT
+    resource_path
+FROM
+    idx_entry_link
+WHERE
+    idx_entry_id = $1
+        "#,
+        idx_entry_id,
+    )
+    .map(|row| row.resource_path)
+    .fetch_all(&*backend.pool)
+    .await?;
+
+    Ok(Some(IndexResourceSet {
+        kind: idx_kind,
+        term: term.to_string(),
+        resource_paths,
+    }))
+}
+
+async fn get_resource_kinded_terms_postgres(
+    backend: &PostgresBackend,
+    resource_path: &str,
+) -> Result<ResourceKindedTerms, BackendError> {
+    let data = sqlx::query!(
+        r#"
+SELECT
+    idx_kind.description AS kind,
+    idx_entry.term AS term
+FROM
+    idx_entry_link
+JOIN
+    idx_entry ON idx_entry_link.idx_entry_id = idx_entry.id
+JOIN
+    idx_kind ON idx_entry.idx_kind_id = idx_kind.id
+WHERE
+    resource_path = $1
+        "#,
+        resource_path,
+    )
+    .map(|row| (row.kind, row.term))
+    .fetch_all(&*backend.pool)
+    .await?
+    .into_iter()
+    .fold(BTreeMap::<String, Vec<String>>::new(), |mut data, (kind, term)| {
+        data.entry(kind)
+            .or_insert_with(|| Vec::new())
+            .push(term);
+        data
+    });
+
+    Ok(ResourceKindedTerms {
+        resource_path: resource_path.to_string(),
+        data,
+    })
+}
+
+async fn cache_resource_kinded_terms_postgres(
+    backend: &PostgresBackend,
+    resource_path: &str,
+) -> Result<ResourceKindedTerms, BackendError> {
+    let results = get_resource_kinded_terms_postgres(backend, resource_path).await?;
+    let data = serde_json::to_string(&results.data).expect("serialization shouldn't fail on this basic type");
+    sqlx::query!(
+        r#"
+INSERT INTO resource_indexed (
+    resource_path,
+    data
+)
+VALUES ( $1, $2 )
+ON CONFLICT(resource_path) DO UPDATE SET
+    data = $2
+        "#,
+        resource_path,
+        data,
+    )
+    .execute(&*backend.pool)
+    .await?;
+
+    Ok(results)
+}
+
+async fn get_cached_resource_kinded_terms_postgres(
+    backend: &PostgresBackend,
+    resource_path: &str,
+) -> Result<Option<ResourceKindedTerms>, BackendError> {
+    let result = sqlx::query!(
+        r#"
+SELECT
+    resource_path,
+    data
+FROM
+    resource_indexed
+WHERE
+    resource_path = $1
+        "#,
+        resource_path,
+    )
+    .map(|row| (row.resource_path, row.data))
+    .fetch_optional(&*backend.pool)
+    .await?;
+
+    if let Some((resource_path, data)) = result {
+        if let Some(data) = data {
+            return Ok(serde_json::from_str(&data)
+                .ok()
+                .map(|data| ResourceKindedTerms {
+                    resource_path,
+                    data,
+                })
+            )
+        }
+    }
+    Ok(None)
+}
+
+async fn uncache_resource_kinded_terms_postgres(
+    backend: &PostgresBackend,
+    resource_path: &str,
+) -> Result<(), BackendError> {
+    sqlx::query!(
+        r#"
+DELETE FROM
+    resource_indexed
+WHERE
+    resource_path = $1
+        "#,
+        resource_path,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(())
+}
+
+async fn uncache_all_resource_kinded_terms_postgres(
+    backend: &PostgresBackend,
+) -> Result<(), BackendError> {
+    sqlx::query!(
+        r#"
+DELETE FROM resource_indexed
+        "#,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(())
+}
+
+async fn get_resource_brief_postgres(
+    backend: &PostgresBackend,
+    resource_path: &str,
+) -> Result<Option<ResourceBrief>, BackendError> {
+    let result = sqlx::query!(
+        r#"
+SELECT
+    title,
+    content,
+    resource_path
+FROM
+    idx_text
+WHERE
+    resource_path = $1
+        "#,
+        resource_path,
+    )
+    .map(|row| {
+        let idx = row.content
+            .as_ref()
+            .map(|content| content.unicode_word_indices()
+                .nth(WORD_COUNT as usize)
+                .map(|(idx, word)| idx + word.len())
+                .unwrap_or(content.len())
+            );
+        ResourceBrief {
+            title: row.title,
+            brief: row.content.map(|content| {
+                let idx = idx.expect("this must have a length");
+                let mut brief = content[0..idx].to_owned();
+                if idx < content.len() {
+                    brief.push_str("…");
+                };
+                brief
+            }),
+            resource_path: row.resource_path,
+        }
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+
+    Ok(result)
+}
+
+async fn list_idx_text_postgres(
+    backend: &PostgresBackend,
+    text: &str,
+    bracket: Option<(&str, &str)>,
+) -> Result<Vec<ResourceBrief>, BackendError> {
+    let (start, end) = bracket.unwrap_or(("", ""));
+    // The options for `ts_headline` are only accepted as a single string,
+    // so the brackets are quoted for inclusion within it.
+    let options = format!(
+        r#"StartSel="{}", StopSel="{}", MaxWords={WORD_COUNT}, MinWords={}"#,
+        start.replace('"', r#""""#),
+        end.replace('"', r#""""#),
+        WORD_COUNT / 4,
+    );
+    let result = sqlx::query!(
+        r#"
+SELECT
+    title,
+    ts_headline('english', content, query, $2) AS content,
+    resource_path
+FROM
+    idx_text,
+    websearch_to_tsquery('english', $1) query
+WHERE
+    document @@ query
+ORDER BY
+    ts_rank(document, query) DESC
+        "#,
+        text,
+        options,
+    )
+    .map(|row| ResourceBrief {
+        title: row.title,
+        brief: row.content,
+        resource_path: row.resource_path,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+
+    Ok(result)
+}
+
+#[async_trait]
+impl IndexCoreDBBackend for PostgresBackend {
+    async fn resolve_kind(
+        &self,
+        kind: &str,
+    ) -> Result<i64, BackendError> {
+        resolve_kind_postgres(self, kind).await
+    }
+
+    async fn resolve_idx_entry(
+        &self,
+        idx_kind_id: i64,
+        term: &str,
+    ) -> Result<i64, BackendError> {
+        resolve_idx_entry_postgres(self, idx_kind_id, term).await
+    }
+
+    async fn add_idx_entry_link(
+        &self,
+        idx_entry_id: i64,
+        resource_path: &str,
+    ) -> Result<(), BackendError> {
+        add_idx_entry_link_postgres(self, idx_entry_id, resource_path).await
+    }
+
+    async fn add_idx_text_core(
+        &self,
+        title: Option<&str>,
+        content: Option<&str>,
+        resource_path: &str,
+    ) -> Result<(), BackendError> {
+        add_idx_text_postgres(self, title, content, resource_path).await
+    }
+
+    async fn forget_resource_path_core(
+        &self,
+        kind: Option<&str>,
+        resource_path: &str,
+    ) -> Result<(), BackendError> {
+        forget_resource_path_postgres(self, kind, resource_path).await
+    }
+
+    async fn forget_resource_text_core(
+        &self,
+        resource_path: &str,
+    ) -> Result<(), BackendError> {
+        idx_text_forget_postgres(self, resource_path).await
+    }
+
+    async fn list_kinds_core(&self) -> Result<Vec<String>, BackendError> {
+        list_kinds_postgres(self).await
+    }
+
+    async fn list_terms_core(
+        &self,
+        kind: &str,
+    ) -> Result<Option<IndexTerms>, BackendError> {
+        list_terms_postgres(self, kind).await
+    }
+
+    async fn list_resources_core(
+        &self,
+        kind: &str,
+        term: &str,
+    ) -> Result<Option<IndexResourceSet>, BackendError> {
+        list_resources_postgres(self, kind, term).await
+    }
+
+    async fn list_resources_text_core(
+        &self,
+        text: &str,
+        bracket: Option<(&str, &str)>,
+    ) -> Result<Vec<ResourceBrief>, BackendError> {
+        list_idx_text_postgres(self, text, bracket).await
+    }
+
+    async fn get_resource_kinded_terms_core(
+        &self,
+        resource_path: &str,
+    ) -> Result<ResourceKindedTerms, BackendError> {
+        get_resource_kinded_terms_postgres(self, resource_path).await
+    }
+
+    async fn get_resource_brief_core(
+        &self,
+        resource_path: &str,
+    ) -> Result<Option<ResourceBrief>, BackendError> {
+        get_resource_brief_postgres(self, resource_path).await
+    }
+}
+
+#[async_trait]
+impl IndexCoreDBCache for PostgresBackend {
+    async fn cache_resource_kinded_terms(
+        &self,
+        resource_path: &str,
+    ) -> Result<ResourceKindedTerms, BackendError> {
+        cache_resource_kinded_terms_postgres(self, resource_path).await
+    }
+
+    async fn get_cached_resource_kinded_terms(
+        &self,
+        resource_path: &str,
+    ) -> Result<Option<ResourceKindedTerms>, BackendError> {
+        get_cached_resource_kinded_terms_postgres(self, resource_path).await
+    }
+
+    async fn uncache_resource_kinded_terms(
+        &self,
+        resource_path: &str,
+    ) -> Result<(), BackendError> {
+        uncache_resource_kinded_terms_postgres(self, resource_path).await
+    }
+
+    async fn uncache_all_resource_kinded_terms(
+        &self,
+    ) -> Result<(), BackendError> {
+        uncache_all_resource_kinded_terms_postgres(self).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        index::{
+            traits::{
+                IndexBackend,
+                IndexCoreBackend,
+                IndexCoreDBBackend,
+                IndexCoreDBCache,
+            },
+            IndexBackendCache,
+            ResourceBrief,
+            ResourceKindedTermsCache,
+        },
+    };
+    use test_pmr::{postgres::create_database, postgres_pcb_cache_test_case, test_case};
+    use crate::PostgresBackend;
+
+    #[postgres_pcb_cache_test_case]
+    #[async_std::test]
+    async fn test_empty_index(backend: impl IndexBackend) -> anyhow::Result<()> {
+        assert!(backend.list_resources("no_such_kind", "no_such_term").await?.is_none());
+        assert!(backend.list_terms("no_such_kind").await?.is_none());
+        assert!(backend.list_kinds().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_foundation() -> anyhow::Result<()> {
+        let backend = PostgresBackend::pc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let id1 = backend.resolve_kind("test_kind").await?;
+        let id2 = backend.resolve_kind("test_kind").await?;
+        assert_eq!(id1, id2);
+
+        let id3 = backend.resolve_idx_entry(id1, "test_term").await?;
+        let id4 = backend.resolve_idx_entry(id1, "test_term").await?;
+        assert_eq!(id3, id4);
+
+        backend.add_idx_entry_link(id3, "resource1").await?;
+        backend.add_idx_entry_link(id3, "resource1").await?;
+
+        assert_eq!(
+            backend.list_kinds().await?,
+            vec!["test_kind".to_string()],
+        );
+        assert_eq!(
+            backend.list_terms("test_kind").await?.unwrap().terms,
+            vec!["test_term".to_string()],
+        );
+        assert_eq!(
+            backend.list_resources("test_kind", "test_term").await?.unwrap().resource_paths,
+            vec!["resource1".to_string()],
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_cache() -> anyhow::Result<()> {
+        let backend = PostgresBackend::pc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        backend.resource_link_kind_with_terms("/test/resource", "keyword", &mut [].into_iter()).await?;
+        backend.resource_link_kind_with_terms("/test/resource", "title", &mut [
+            "Test Resource",
+        ].into_iter()).await?;
+        backend.resource_link_kind_with_terms("/test/resource", "keyword", &mut [
+            "hello",
+            "world",
+        ].into_iter()).await?;
+
+        let kinded_terms = backend.get_resource_kinded_terms("/test/resource").await?;
+        assert_eq!(kinded_terms.resource_path, "/test/resource");
+        assert!(backend.get_cached_resource_kinded_terms("/test/resource").await?.is_none());
+
+        let caching_kinded_terms = backend.cache_resource_kinded_terms("/test/resource").await?;
+        let cached_kinded_terms = backend.get_cached_resource_kinded_terms("/test/resource").await?;
+        let (cached_resource_path, cached_data) = if let Some(cached_kinded_terms) = cached_kinded_terms {
+            (Some(cached_kinded_terms.resource_path), Some(cached_kinded_terms.data))
+        } else {
+            (None, None)
+        };
+        assert_eq!(kinded_terms.resource_path, caching_kinded_terms.resource_path);
+        assert_eq!(Some(kinded_terms.resource_path), cached_resource_path);
+
+        assert_eq!(kinded_terms.data, caching_kinded_terms.data);
+        assert_eq!(Some(kinded_terms.data), cached_data);
+
+        backend.forget_resource_path(None, "/test/resource").await?;
+        assert!(backend.list_resources("title", "Test Resource").await?.unwrap().resource_paths.is_empty());
+        // cache does NOT automatically be invalidated, use the version that manages this.
+        assert!(backend.get_cached_resource_kinded_terms("/test/resource").await?.is_some());
+
+        // non-existent references can be manually cached
+        let caching_empty = backend.cache_resource_kinded_terms("/test/alternate").await?;
+        let cached_empty = backend.get_cached_resource_kinded_terms("/test/alternate").await?
+            .expect("should have been correctly cached above");
+
+        assert!(caching_empty.data.is_empty());
+        assert!(cached_empty.data.is_empty());
+
+        // Cache may be cleared one at a time.
+        backend.uncache_resource_kinded_terms("/test/resource").await?;
+        assert!(backend.get_cached_resource_kinded_terms("/test/resource").await?.is_none());
+        assert!(backend.get_cached_resource_kinded_terms("/test/alternate").await?.is_some());
+
+        // Fully clear the cache.
+        backend.uncache_all_resource_kinded_terms().await?;
+        assert!(backend.get_cached_resource_kinded_terms("/test/alternate").await?.is_none());
+
+        Ok(())
+    }
+
+    #[postgres_pcb_cache_test_case]
+    #[async_std::test]
+    async fn test_resource_link_kind_with_terms(backend: impl IndexBackend) -> anyhow::Result<()> {
+        backend.resource_link_kind_with_terms("/test/resource", "keyword", &mut [].into_iter()).await?;
+        assert_eq!(
+            backend.list_kinds().await?,
+            vec!["keyword".to_string()],
+        );
+        assert!(backend.list_terms("keyword").await?.unwrap().terms.is_empty());
+
+        backend.resource_link_kind_with_terms("/test/resource", "title", &mut [
+            "Test Resource",
+        ].into_iter()).await?;
+        backend.resource_link_kind_with_terms("/test/resource", "keyword", &mut [
+            "hello",
+            "world",
+        ].into_iter()).await?;
+        assert_eq!(
+            backend.list_kinds().await?,
+            vec!["keyword".to_string(), "title".to_string()],
+        );
+        assert_eq!(
+            backend.list_terms("keyword").await?.unwrap().terms,
+            vec!["hello".to_string(), "world".to_string()],
+        );
+        // this isn't exactly highly ergonomic, but it does distinguish between a term
+        // that has been seen vs. unknown.
+        assert_eq!(
+            backend.list_resources("keyword", "hello").await?.unwrap().resource_paths,
+            vec!["/test/resource".to_string()],
+        );
+        assert!(backend.list_resources("keyword", "Test Resource").await?.is_none());
+
+        backend.forget_resource_path(Some("keyword"), "/test/resource").await?;
+        assert!(backend.list_resources("keyword", "hello").await?.unwrap().resource_paths.is_empty());
+        assert_eq!(
+            backend.list_resources("title", "Test Resource").await?.unwrap().resource_paths,
+            vec!["/test/resource".to_string()],
+        );
+        // query should be case insensitive, but data should still be returned as if they are.
+        assert_eq!(
+            backend.list_resources("title", "test resource").await?.unwrap().resource_paths,
+            vec!["/test/resource".to_string()],
+        );
+        let kinded_terms = backend.get_resource_kinded_terms("/test/resource").await?;
+        assert_eq!(kinded_terms.resource_path, "/test/resource");
+        assert_eq!(kinded_terms.data.get("title"), Some(vec!["Test Resource".to_string()]).as_ref());
+        backend.forget_resource_path(None, "/test/resource").await?;
+        assert!(backend.list_resources("title", "Test Resource").await?.unwrap().resource_paths.is_empty());
+        // TODO should clean up terms that have no records?
+        // TODO usage of the denormalized cache.
+
+        Ok(())
+    }
+
+    #[postgres_pcb_cache_test_case]
+    #[async_std::test]
+    async fn test_resource_text(backend: impl IndexBackend) -> anyhow::Result<()> {
+        backend.add_idx_text(
+            Some("Example Title"),
+            Some("Content is test+ corrected."),
+            "/test/resource",
+        ).await?;
+
+        assert_eq!(
+            backend.list_resources_text("title", Some(("**", "**"))).await?,
+            vec![
+                ResourceBrief {
+                    resource_path: "/test/resource".to_string(),
+                    title: Some("Example Title".to_string()),
+                    brief: Some("Content is test+ corrected.".to_string()),
+                },
+            ],
+        );
+
+        assert_eq!(
+            backend.list_resources_text("content", Some(("**", "**"))).await?,
+            vec![
+                ResourceBrief {
+                    resource_path: "/test/resource".to_string(),
+                    title: Some("Example Title".to_string()),
+                    brief: Some("**Content** is test+ corrected.".to_string()),
+                },
+            ],
+        );
+
+        // unlike fts5, the tsvector parser doesn't consider the symbol as
+        // part of the word so it is left outside of the highlight.
+        assert_eq!(
+            backend.list_resources_text("test+", Some(("**", "**"))).await?,
+            vec![
+                ResourceBrief {
+                    resource_path: "/test/resource".to_string(),
+                    title: Some("Example Title".to_string()),
+                    brief: Some("Content is **test**+ corrected.".to_string()),
+                },
+            ],
+        );
+
+        assert_eq!(
+            backend.list_resources_text("correct", Some(("**", "**"))).await?,
+            vec![
+                ResourceBrief {
+                    resource_path: "/test/resource".to_string(),
+                    title: Some("Example Title".to_string()),
+                    brief: Some("Content is test+ **corrected**.".to_string()),
+                },
+            ],
+        );
+
+        Ok(())
+    }
+
+}
@@ -0,0 +1,10 @@
+mod profile;
+mod profile_views;
+mod view_task_template;
+
+mod view_task_template_profile {
+    use pmrcore::profile::traits::ViewTaskTemplateProfileBackend;
+    use crate::PostgresBackend;
+
+    impl ViewTaskTemplateProfileBackend for PostgresBackend {}
+}
@@ -0,0 +1,306 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    profile::ViewTaskTemplate,
+    profile::traits::ViewTaskTemplateBackend,
+};
+
+use crate::{
+    PostgresBackend,
+    chrono::Utc,
+};
+
+async fn insert_view_task_template_postgres(
+    backend: &PostgresBackend,
+    view_key: &str,
+    description: &str,
+    task_template_id: i64,
+) -> Result<i64, BackendError> {
+    let updated_ts = Utc::now().timestamp();
+    let id = sqlx::query!(
+        r#"
+INSERT INTO view_task_template (
+    view_key,
+    description,
+    task_template_id,
+    updated_ts
+)
+VALUES ( $1, $2, $3, $4 )
+RETURNING id
+        "#,
+        view_key,
+        description,
+        task_template_id,
+        updated_ts,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(id)
+}
+
+async fn update_view_task_template_by_fields_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    view_key: &str,
+    description: &str,
+    task_template_id: i64,
+) -> Result<bool, BackendError> {
+    let updated_ts = Utc::now().timestamp();
+    let rows_affected = sqlx::query!(
+        r#"
+UPDATE
+    view_task_template
+SET
+    view_key = $2,
+    description = $3,
+    task_template_id = $4,
+    updated_ts = $5
+WHERE
+    id = $1
+        "#,
+        id,
+        view_key,
+        description,
+        task_template_id,
+        updated_ts,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn select_view_task_template_by_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<ViewTaskTemplate, BackendError> {
+    let result = sqlx::query!(
+        r#"
+SELECT
+    id,
+    view_key,
+    description,
+    task_template_id,
+    updated_ts,
+    depends_on
+FROM view_task_template
+WHERE id = $1
+        "#,
+        id,
+    )
+    .map(|row| ViewTaskTemplate {
+        id: row.id,
+        view_key: row.view_key,
+        description: row.description,
+        task_template_id: row.task_template_id,
+        updated_ts: row.updated_ts,
+        depends_on: row.depends_on,
+        task_template: None,
+    })
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(result)
+}
+
+async fn set_view_task_template_depends_on_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    depends_on: Option<&str>,
+) -> Result<bool, BackendError> {
+    let updated_ts = Utc::now().timestamp();
+    let rows_affected = sqlx::query!(
+        r#"
+UPDATE
+    view_task_template
+SET
+    depends_on = $2,
+    updated_ts = $3
+WHERE
+    id = $1
+        "#,
+        id,
+        depends_on,
+        updated_ts,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn migrate_view_task_templates_postgres(
+    backend: &PostgresBackend,
+    task_template_id: i64,
+    superceded_by_id: i64,
+) -> Result<Vec<i64>, BackendError> {
+    let updated_ts = Utc::now().timestamp();
+    let ids = sqlx::query!(
+        r#"
+UPDATE
+    view_task_template
+SET
+    task_template_id = $2,
+    updated_ts = $3
+WHERE
+    task_template_id = $1
+RETURNING
+    id
+        "#,
+        task_template_id,
+        superceded_by_id,
+        updated_ts,
+    )
+    .map(|row| row.id)
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(ids)
+}
+
+#[async_trait]
+impl ViewTaskTemplateBackend for PostgresBackend {
+    async fn insert_view_task_template(
+        &self,
+        view_key: &str,
+        description: &str,
+        task_template_id: i64,
+    ) -> Result<i64, BackendError> {
+        insert_view_task_template_postgres(
+            &self,
+            view_key,
+            description,
+            task_template_id,
+        ).await
+    }
+    async fn update_view_task_template_by_fields(
+        &self,
+        id: i64,
+        view_key: &str,
+        description: &str,
+        task_template_id: i64,
+    ) -> Result<bool, BackendError> {
+        update_view_task_template_by_fields_postgres(
+            &self,
+            id,
+            view_key,
+            description,
+            task_template_id,
+        ).await
+    }
+    async fn select_view_task_template_by_id(
+        &self,
+        id: i64,
+    ) -> Result<ViewTaskTemplate, BackendError> {
+        select_view_task_template_by_id_postgres(&self, id).await
+    }
+    async fn set_view_task_template_depends_on(
+        &self,
+        id: i64,
+        depends_on: Option<&str>,
+    ) -> Result<bool, BackendError> {
+        set_view_task_template_depends_on_postgres(&self, id, depends_on).await
+    }
+    async fn migrate_view_task_templates(
+        &self,
+        task_template_id: i64,
+        superceded_by_id: i64,
+    ) -> Result<Vec<i64>, BackendError> {
+        migrate_view_task_templates_postgres(&self, task_template_id, superceded_by_id).await
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        profile::{
+            ViewTaskTemplate,
+            traits::ViewTaskTemplateBackend,
+        },
+    };
+    use test_pmr::postgres::create_database;
+    use test_pmr::chrono::set_timestamp;
+
+    use crate::PostgresBackend;
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let b: &dyn ViewTaskTemplateBackend = &backend;
+        let view_task_template_id = b.insert_view_task_template(
+            "test_view",
+            "",
+            1,
+        ).await?;
+        let view_task_template = b.select_view_task_template_by_id(view_task_template_id).await?;
+        assert_eq!(view_task_template, ViewTaskTemplate {
+            id: view_task_template_id,
+            view_key: "test_view".to_string(),
+            description: "".to_string(),
+            task_template_id: 1,
+            updated_ts: 1234567890,
+            depends_on: None,
+            task_template: None,
+        });
+
+        set_timestamp(1357924680);
+        assert!(b.update_view_task_template_by_fields(
+            view_task_template_id,
+            "final_view",
+            "This is a finalized view.",
+            2,
+        ).await?);
+        let view_task_template = b.select_view_task_template_by_id(view_task_template_id).await?;
+        assert_eq!(view_task_template, ViewTaskTemplate {
+            id: view_task_template_id,
+            view_key: "final_view".to_string(),
+            description: "This is a finalized view.".to_string(),
+            task_template_id: 2,
+            updated_ts: 1357924680,
+            depends_on: None,
+            task_template: None,
+        });
+
+        set_timestamp(1357924681);
+        assert!(b.set_view_task_template_depends_on(
+            view_task_template_id,
+            Some("test_view"),
+        ).await?);
+        let view_task_template = b.select_view_task_template_by_id(view_task_template_id).await?;
+        assert_eq!(view_task_template.depends_on.as_deref(), Some("test_view"));
+        assert_eq!(view_task_template.updated_ts, 1357924681);
+        assert!(b.set_view_task_template_depends_on(view_task_template_id, None).await?);
+        let view_task_template = b.select_view_task_template_by_id(view_task_template_id).await?;
+        assert_eq!(view_task_template.depends_on, None);
+        assert!(!b.set_view_task_template_depends_on(view_task_template_id + 1, None).await?);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_migrate() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let b: &dyn ViewTaskTemplateBackend = &backend;
+        let v1 = b.insert_view_task_template("view1", "", 1).await?;
+        let v2 = b.insert_view_task_template("view2", "", 2).await?;
+        let v3 = b.insert_view_task_template("view3", "", 1).await?;
+
+        set_timestamp(1357924680);
+        let mut migrated = b.migrate_view_task_templates(1, 3).await?;
+        migrated.sort();
+        assert_eq!(migrated, [v1, v3]);
+        let view_task_template = b.select_view_task_template_by_id(v1).await?;
+        assert_eq!(view_task_template.task_template_id, 3);
+        assert_eq!(view_task_template.updated_ts, 1357924680);
+        let view_task_template = b.select_view_task_template_by_id(v2).await?;
+        assert_eq!(view_task_template.task_template_id, 2);
+        assert_eq!(view_task_template.updated_ts, 1234567890);
+
+        assert_eq!(b.migrate_view_task_templates(1, 3).await?.len(), 0);
+        Ok(())
+    }
+
+}
@@ -0,0 +1,1029 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::{
+        BackendError,
+        task::TaskError,
+    },
+    task::{
+        EXIT_DEPENDENCY_FAILED,
+        Task,
+        TaskArg,
+        TaskArgs,
+        TaskState,
+        traits::TaskBackend,
+    },
+};
+
+use crate::{
+    PostgresBackend,
+    chrono::Utc,
+};
+
+// TODO document that adds means _add_ing (insert) the _s_tructure.
+
+async fn adds_task_postgres(
+    backend: &PostgresBackend,
+    task: Task,
+) -> Result<Task, TaskError> {
+    if task.id > 0 {
+        return Err(TaskError::TaskAlreadyQueued(task.id));
+    }
+
+    let mut tx = backend.pool.begin().await
+        .map_err(BackendError::from)?;
+    let created_ts = Utc::now().timestamp();
+    let mut result = Task {
+        bin_path: task.bin_path.clone(),
+        created_ts: created_ts,
+        basedir: task.basedir.clone(),
+        priority: task.priority,
+        max_retries: task.max_retries,
+        retry_delay: task.retry_delay,
+        .. Default::default()
+    };
+
+    result.id = sqlx::query!(
+        "
+INSERT INTO task (
+    task_template_id,
+    bin_path,
+    created_ts,
+    basedir,
+    priority,
+    max_retries,
+    retry_delay
+)
+VALUES ( $1, $2, $3, $4, $5, $6, $7 )
+RETURNING id\
+        ",
+        task.task_template_id,
+        task.bin_path,
+        created_ts,
+        task.basedir,
+        task.priority,
+        task.max_retries,
+        task.retry_delay,
+    )
+        .map(|row| row.id)
+        .fetch_one(&mut *tx)
+        .await.map_err(BackendError::from)?;
+
+    result.args = match task.args {
+        Some(args) => {
+            let mut iter = args.iter().into_iter();
+            let mut results: Vec<TaskArg> = Vec::new();
+            while let Some(arg) = iter.next() {
+                // TaskArg on the other hand won't have a direct API for
+                // insertion, so if any of them containing an id will
+                // simply have a warning generated instead.
+                if arg.id > 0 {
+                    log::warn!("got an existing id for arg: {}", arg.id);
+                }
+                if arg.task_id > 0 {
+                    log::warn!("got an existing task_id for arg: {}", arg.task_id);
+                }
+                let task_arg_id = sqlx::query!(
+                        "
+INSERT INTO task_arg (
+    task_id,
+    arg
+)
+VALUES ( $1, $2 )
+RETURNING id\
+                        ",
+                        result.id,
+                        arg.arg,
+                    )
+                    .map(|row| row.id)
+                    .fetch_one(&mut *tx)
+                    .await.map_err(BackendError::from)?;
+                results.push(TaskArg {
+                    id: task_arg_id,
+                    task_id: result.id,
+                    arg: arg.arg.clone(),
+                })
+            }
+            Some(results.into())
+        }
+        None => None,
+    };
+    // the dependencies are locked against completion until the check
+    // below for any that have failed is committed along with this task.
+    sqlx::query!(
+        "
+SELECT id FROM task WHERE id = ANY($1) FOR SHARE\
+        ",
+        &task.depends_on[..],
+    ).fetch_all(&mut *tx)
+        .await.map_err(BackendError::from)?;
+    for depends_on_id in task.depends_on.iter() {
+        sqlx::query!(
+            "
+INSERT INTO task_dependency (
+    task_id,
+    depends_on_id
+)
+VALUES ( $1, $2 )\
+            ",
+            result.id,
+            depends_on_id,
+        ).execute(&mut *tx)
+            .await.map_err(BackendError::from)?;
+    }
+    // a dependency that had already failed means this task will never
+    // start, as it was not around for the failure to cascade to it.
+    let dependency_failed = i64::from(EXIT_DEPENDENCY_FAILED);
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    start_ts = $2,
+    stop_ts = $2,
+    exit_status = $3
+WHERE id = $1
+    AND EXISTS (
+        SELECT
+            1
+        FROM
+            task_dependency
+        JOIN
+            task AS dependency ON dependency.id = task_dependency.depends_on_id
+        WHERE task_dependency.task_id = $1
+            AND dependency.stop_ts IS NOT NULL
+            AND dependency.exit_status != 0
+    )\
+        ",
+        result.id,
+        created_ts,
+        dependency_failed,
+    ).execute(&mut *tx)
+        .await.map_err(BackendError::from)?
+        .rows_affected();
+    if rows_affected > 0 {
+        result.start_ts = Some(created_ts);
+        result.stop_ts = Some(created_ts);
+        result.exit_status = Some(dependency_failed);
+    }
+    result.depends_on = task.depends_on;
+    tx.commit().await.map_err(BackendError::from)?;
+    Ok(result)
+}
+
+async fn gets_task_args_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<TaskArgs, BackendError> {
+    Ok(sqlx::query_as!(
+        TaskArg,
+        "
+SELECT
+    id,
+    task_id,
+    arg
+FROM
+    task_arg
+WHERE
+    task_id = $1
+ORDER BY
+    id
+        ",
+        id,
+    )
+        .fetch_all(&*backend.pool)
+        .await?
+        .into()
+    )
+}
+
+async fn gets_task_dependencies_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<Vec<i64>, BackendError> {
+    Ok(sqlx::query!(
+        "
+SELECT
+    depends_on_id
+FROM
+    task_dependency
+WHERE
+    task_id = $1
+ORDER BY
+    depends_on_id
+        ",
+        id,
+    )
+        .map(|row| row.depends_on_id)
+        .fetch_all(&*backend.pool)
+        .await?
+    )
+}
+
+async fn gets_task_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<Task, BackendError> {
+    let mut result = sqlx::query!(
+        "
+SELECT
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+FROM
+    task
+WHERE
+    id = $1
+        ",
+        id,
+    )
+        .map(|row| Task {
+            id: row.id,
+            task_template_id: row.task_template_id,
+            bin_path: row.bin_path,
+            pid: row.pid,
+            host: row.host,
+            created_ts: row.created_ts,
+            start_ts: row.start_ts,
+            stop_ts: row.stop_ts,
+            exit_status: row.exit_status,
+            basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
+            args: None,
+        })
+        .fetch_one(&*backend.pool)
+        .await?;
+    result.args = Some(gets_task_args_postgres(backend, id).await?);
+    result.depends_on = gets_task_dependencies_postgres(backend, id).await?;
+
+    Ok(result)
+}
+
+async fn start_task_postgres(
+    backend: &PostgresBackend,
+) -> Result<Option<Task>, BackendError> {
+    let start_ts = Utc::now().timestamp();
+    // the query assumes the id is auto-incremented to have the earliest
+    // task has the lowest id; the row lock ensures concurrent runners
+    // will not start the same task.
+    let mut result = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    start_ts = $1,
+    attempts = attempts + 1,
+    lease_ts = NULL
+WHERE id = (
+    SELECT
+        id
+    FROM
+        task AS queued
+    WHERE
+        start_ts IS NULL
+        AND (retry_ts IS NULL OR retry_ts <= $1)
+        AND NOT EXISTS (
+            SELECT
+                1
+            FROM
+                task_dependency
+            JOIN
+                task AS dependency ON dependency.id = task_dependency.depends_on_id
+            WHERE
+                task_dependency.task_id = queued.id
+                AND (dependency.stop_ts IS NULL OR dependency.exit_status <> 0)
+        )
+    ORDER BY
+        priority DESC,
+        id
+    LIMIT 1
+    FOR UPDATE SKIP LOCKED
+)
+RETURNING
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+        ",
+        start_ts,
+    )
+        .map(|row| Task {
+            id: row.id,
+            task_template_id: row.task_template_id,
+            bin_path: row.bin_path,
+            pid: row.pid,
+            host: row.host,
+            created_ts: row.created_ts,
+            start_ts: row.start_ts,
+            stop_ts: row.stop_ts,
+            exit_status: row.exit_status,
+            basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
+            args: None,
+        })
+        .fetch_optional(&*backend.pool)
+        .await?;
+    match result.as_mut() {
+        Some(result) => {
+            result.args = Some(gets_task_args_postgres(backend, result.id).await?);
+            result.depends_on = gets_task_dependencies_postgres(backend, result.id).await?;
+        }
+        None => (),
+    }
+    Ok(result)
+}
+
+async fn run_task_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    pid: i64,
+    host: &str,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    pid = $2,
+    host = $3
+WHERE id = $1
+        ",
+        id,
+        pid,
+        host,
+    )
+        .execute(&*backend.pool)
+        .await?
+        .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn complete_task_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    exit_status: i64,
+) -> Result<bool, BackendError> {
+    let stop_ts = Utc::now().timestamp();
+    let mut tx = backend.pool.begin().await?;
+    // only the failures of the task itself are retried, as the negative
+    // statuses from the executor (e.g. aborted, timed out) are final.
+    if exit_status > 0 {
+        // a failed task with retries remaining goes back to the queue,
+        // with the delay doubled for each of the prior retries.
+        let rows_affected = sqlx::query!(
+            "
+UPDATE
+    task
+SET
+    start_ts = NULL,
+    pid = NULL,
+    host = NULL,
+    lease_ts = NULL,
+    exit_status = $3,
+    retry_ts = $2 + (retry_delay << LEAST(attempts - 1, 16)::INTEGER)
+WHERE id = $1
+    AND start_ts IS NOT NULL
+    AND stop_ts IS NULL
+    AND attempts <= max_retries
+            ",
+            id,
+            stop_ts,
+            exit_status,
+        )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if rows_affected > 0 {
+            tx.commit().await?;
+            return Ok(true);
+        }
+    }
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    stop_ts = $2,
+    exit_status = $3
+WHERE id = $1
+        ",
+        id,
+        stop_ts,
+        exit_status,
+    )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    if exit_status != 0 {
+        // the pending tasks depending on this one will never start.
+        let dependency_failed = i64::from(EXIT_DEPENDENCY_FAILED);
+        sqlx::query!(
+            "
+WITH RECURSIVE dependent(id) AS (
+    SELECT
+        task_id
+    FROM
+        task_dependency
+    WHERE
+        depends_on_id = $1
+    UNION
+    SELECT
+        task_dependency.task_id
+    FROM
+        task_dependency
+    JOIN
+        dependent ON task_dependency.depends_on_id = dependent.id
+)
+UPDATE
+    task
+SET
+    start_ts = $2,
+    stop_ts = $2,
+    exit_status = $3
+WHERE id IN (SELECT id FROM dependent)
+    AND start_ts IS NULL
+    AND stop_ts IS NULL
+            ",
+            id,
+            stop_ts,
+            dependency_failed,
+        )
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(rows_affected > 0)
+}
+
+async fn list_running_tasks_postgres(
+    backend: &PostgresBackend,
+) -> Result<Vec<Task>, BackendError> {
+    Ok(sqlx::query!(
+        "
+SELECT
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+FROM
+    task
+WHERE
+    pid IS NOT NULL
+    AND stop_ts IS NULL
+ORDER BY
+    id
+        ",
+    )
+        .map(|row| Task {
+            id: row.id,
+            task_template_id: row.task_template_id,
+            bin_path: row.bin_path,
+            pid: row.pid,
+            host: row.host,
+            created_ts: row.created_ts,
+            start_ts: row.start_ts,
+            stop_ts: row.stop_ts,
+            exit_status: row.exit_status,
+            basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
+            args: None,
+        })
+        .fetch_all(&*backend.pool)
+        .await?
+    )
+}
+
+async fn list_tasks_by_state_postgres(
+    backend: &PostgresBackend,
+    state: TaskState,
+) -> Result<Vec<Task>, BackendError> {
+    let state = state.as_str();
+    Ok(sqlx::query!(
+        "
+SELECT
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+FROM
+    task
+WHERE
+    CASE
+        WHEN stop_ts IS NOT NULL AND exit_status = 0 THEN 'succeeded'
+        WHEN stop_ts IS NOT NULL THEN 'failed'
+        WHEN start_ts IS NOT NULL THEN 'running'
+        ELSE 'queued'
+    END = $1
+ORDER BY
+    id
+        ",
+        state,
+    )
+        .map(|row| Task {
+            id: row.id,
+            task_template_id: row.task_template_id,
+            bin_path: row.bin_path,
+            pid: row.pid,
+            host: row.host,
+            created_ts: row.created_ts,
+            start_ts: row.start_ts,
+            stop_ts: row.stop_ts,
+            exit_status: row.exit_status,
+            basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
+            args: None,
+        })
+        .fetch_all(&*backend.pool)
+        .await?
+    )
+}
+
+async fn list_tasks_by_ids_postgres(
+    backend: &PostgresBackend,
+    ids: &[i64],
+) -> Result<Vec<Task>, BackendError> {
+    Ok(sqlx::query!(
+        "
+SELECT
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+FROM
+    task
+WHERE
+    id = ANY($1)
+ORDER BY
+    id
+        ",
+        ids,
+    )
+        .map(|row| Task {
+            id: row.id,
+            task_template_id: row.task_template_id,
+            bin_path: row.bin_path,
+            pid: row.pid,
+            host: row.host,
+            created_ts: row.created_ts,
+            start_ts: row.start_ts,
+            stop_ts: row.stop_ts,
+            exit_status: row.exit_status,
+            basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
+            args: None,
+        })
+        .fetch_all(&*backend.pool)
+        .await?
+    )
+}
+
+async fn requeue_task_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    start_ts = NULL,
+    pid = NULL,
+    host = NULL,
+    lease_ts = NULL
+WHERE id = $1
+    AND stop_ts IS NULL
+        ",
+        id,
+    )
+        .execute(&*backend.pool)
+        .await?
+        .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn lease_task_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    host: &str,
+    duration: i64,
+) -> Result<Option<i64>, BackendError> {
+    let lease_ts = Utc::now().timestamp() + duration;
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    host = $2,
+    lease_ts = $3
+WHERE id = $1
+    AND start_ts IS NOT NULL
+    AND stop_ts IS NULL
+    AND pid IS NULL
+    AND (host IS NULL OR host = $2)
+        ",
+        id,
+        host,
+        lease_ts,
+    )
+        .execute(&*backend.pool)
+        .await?
+        .rows_affected();
+    Ok((rows_affected > 0).then_some(lease_ts))
+}
+
+async fn requeue_expired_leases_postgres(
+    backend: &PostgresBackend,
+) -> Result<Vec<i64>, BackendError> {
+    let now = Utc::now().timestamp();
+    Ok(sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    start_ts = NULL,
+    host = NULL,
+    lease_ts = NULL
+WHERE lease_ts < $1
+    AND start_ts IS NOT NULL
+    AND stop_ts IS NULL
+RETURNING
+    id
+        ",
+        now,
+    )
+        .map(|row| row.id)
+        .fetch_all(&*backend.pool)
+        .await?
+    )
+}
+
+
+#[async_trait]
+impl TaskBackend for PostgresBackend {
+    async fn adds_task(
+        &self,
+        task: Task,
+    ) -> Result<Task, TaskError> {
+        adds_task_postgres(&self, task).await
+    }
+    async fn gets_task(
+        &self,
+        id: i64,
+    ) -> Result<Task, BackendError> {
+        gets_task_postgres(&self, id).await
+    }
+    async fn start(
+        &self,
+    ) -> Result<Option<Task>, BackendError> {
+        start_task_postgres(&self).await
+    }
+    async fn run(
+        &self,
+        id: i64,
+        pid: i64,
+        host: &str,
+    ) -> Result<bool, BackendError> {
+        run_task_postgres(&self, id, pid, host).await
+    }
+    async fn complete(
+        &self,
+        id: i64,
+        exit_status: i64,
+    ) -> Result<bool, BackendError> {
+        complete_task_postgres(&self, id, exit_status).await
+    }
+    async fn list_running(
+        &self,
+    ) -> Result<Vec<Task>, BackendError> {
+        list_running_tasks_postgres(&self).await
+    }
+    async fn list_by_state(
+        &self,
+        state: TaskState,
+    ) -> Result<Vec<Task>, BackendError> {
+        list_tasks_by_state_postgres(&self, state).await
+    }
+    async fn list_by_ids(
+        &self,
+        ids: &[i64],
+    ) -> Result<Vec<Task>, BackendError> {
+        list_tasks_by_ids_postgres(&self, ids).await
+    }
+    async fn lease(
+        &self,
+        id: i64,
+        host: &str,
+        duration: i64,
+    ) -> Result<Option<i64>, BackendError> {
+        lease_task_postgres(&self, id, host, duration).await
+    }
+    async fn requeue_expired_leases(
+        &self,
+    ) -> Result<Vec<i64>, BackendError> {
+        requeue_expired_leases_postgres(&self).await
+    }
+    async fn requeue(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        requeue_task_postgres(&self, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        task_template::traits::TaskTemplateBackend,
+        task::{
+            Task,
+            TaskArg,
+            traits::TaskBackend,
+        },
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    #[async_std::test]
+    async fn test_adds_task() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        // Need the task template to provide a valid reference for the
+        // Task that follow this.
+        let (id, _) = TaskTemplateBackend::add_task_template(
+            &backend, "/bin/true", "1.0.0",
+        ).await?;
+        TaskTemplateBackend::finalize_new_task_template(
+            &backend, id,
+        ).await?;
+
+        // Create a Task manually, bypassing the standard helpers that
+        // would have required setting up the task template which isn't
+        // the focus of the test.
+        //
+        // Normal usage should go through the relevant APIs for normal
+        // error checking and validation.
+        let task = Task {
+            task_template_id: id,
+            bin_path: "/bin/demo".into(),
+            basedir: "/tmp".into(),
+            args: Some(["--format=test", "-t", "standard" ].iter()
+                .map(|a| TaskArg {
+                    arg: a.to_string(),
+                    .. Default::default()
+                })
+                .collect::<Vec<_>>()
+                .into()),
+            .. Default::default()
+        };
+
+        let task = TaskBackend::adds_task(
+            &backend, task,
+        ).await?;
+
+        // additional assigned ids/values are all in place.
+        assert_eq!(task.id, 1);
+        assert_eq!(task.created_ts, 1234567890);
+        assert_eq!(&task.bin_path, "/bin/demo");
+        assert_eq!(&task.basedir, "/tmp");
+        assert_eq!(task.args, Some(serde_json::from_str(r#"[
+            {
+                "id": 1,
+                "task_id": 1,
+                "arg": "--format=test"
+            },
+            {
+                "id": 2,
+                "task_id": 1,
+                "arg": "-t"
+            },
+            {
+                "id": 3,
+                "task_id": 1,
+                "arg": "standard"
+            }
+        ]"#)?));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_start_task() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let (id, _) = TaskTemplateBackend::add_task_template(
+            &backend, "/bin/true", "1.0.0",
+        ).await?;
+        TaskTemplateBackend::finalize_new_task_template(
+            &backend, id,
+        ).await?;
+
+        // No tasks added.
+        assert!(TaskBackend::start(&backend).await?.is_none());
+
+        let task = TaskBackend::adds_task(
+            &backend,
+            Task {
+                task_template_id: id,
+                bin_path: "/bin/demo".into(),
+                basedir: "/tmp".into(),
+                args: Some(["--format=test", "-t", "standard" ].iter()
+                    .map(|a| TaskArg {
+                        arg: a.to_string(),
+                        .. Default::default()
+                    })
+                    .collect::<Vec<_>>()
+                    .into()),
+                .. Default::default()
+            }
+        ).await?;
+
+        // the only outstanding task should be returned.
+        let started_task = TaskBackend::start(&backend)
+            .await?
+            .expect("a task has started");
+        assert_eq!(started_task.id, task.id);
+        assert_eq!(started_task.start_ts, Some(1234567890));
+
+        // No outstanding task, so no task can be started.
+        assert!(TaskBackend::start(&backend).await?.is_none());
+
+        let task2 = TaskBackend::adds_task(
+            &backend,
+            Task {
+                task_template_id: id,
+                bin_path: "/bin/demo".into(),
+                basedir: "/tmp".into(),
+                .. Default::default()
+            }
+        ).await?;
+
+        let task3 = TaskBackend::adds_task(
+            &backend,
+            Task {
+                task_template_id: id,
+                bin_path: "/bin/demo".into(),
+                basedir: "/tmp".into(),
+                .. Default::default()
+            }
+        ).await?;
+
+        let start2 = TaskBackend::start(&backend)
+            .await?
+            .expect("second task has started");
+        assert_eq!(start2.id, task2.id);
+
+        let start3 = TaskBackend::start(&backend)
+            .await?
+            .expect("third task has started");
+        assert_eq!(start3.id, task3.id);
+        assert_eq!(start3.start_ts, Some(1234567890));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_task_complete_flow() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let (id, _) = TaskTemplateBackend::add_task_template(
+            &backend, "/bin/true", "1.0.0",
+        ).await?;
+        TaskTemplateBackend::finalize_new_task_template(
+            &backend, id,
+        ).await?;
+        let task = TaskBackend::adds_task(
+            &backend,
+            Task {
+                task_template_id: id,
+                bin_path: "/bin/demo".into(),
+                basedir: "/tmp".into(),
+                args: Some(["--format=test", "-t", "standard" ].iter()
+                    .map(|a| TaskArg {
+                        arg: a.to_string(),
+                        .. Default::default()
+                    })
+                    .collect::<Vec<_>>()
+                    .into()),
+                .. Default::default()
+            }
+        ).await?;
+
+        TaskBackend::start(&backend)
+            .await?
+            .expect("a task has started");
+
+        assert!(TaskBackend::run(&backend, task.id, 123, "localhost")
+            .await?
+        );
+        let running_task = TaskBackend::gets_task(
+            &backend, task.id
+        ).await?;
+        assert_eq!(running_task.pid, Some(123));
+
+        assert!(TaskBackend::complete(&backend, task.id, 0)
+            .await?
+        );
+        let completed_task = TaskBackend::gets_task(
+            &backend, task.id
+        ).await?;
+        assert_eq!(completed_task.pid, Some(123));
+        assert_eq!(completed_task.exit_status, Some(0));
+
+        Ok(())
+    }
+}
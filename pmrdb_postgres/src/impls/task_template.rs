@@ -0,0 +1,1365 @@
+use async_trait::async_trait;
+use futures::future;
+use pmrcore::{
+    error::BackendError,
+    task_template::{
+        TaskSandbox,
+        TaskTemplate,
+        TaskTemplateArg,
+        TaskTemplateArgs,
+        TaskTemplateArgChoice,
+        TaskTemplateArgChoices,
+        traits::TaskTemplateBackend,
+    },
+};
+
+use crate::{
+    PostgresBackend,
+    chrono::Utc,
+};
+
+async fn add_task_template_postgres(
+    backend: &PostgresBackend,
+    bin_path: &str,
+    version_id: &str,
+) -> Result<(i64, i64), BackendError> {
+    let created_ts = Utc::now().timestamp();
+
+    let id = sqlx::query!(
+        r#"
+INSERT INTO task_template (
+    bin_path,
+    version_id,
+    created_ts
+)
+VALUES ( $1, $2, $3 )
+RETURNING id
+        "#,
+        bin_path,
+        version_id,
+        created_ts,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+
+    Ok((id, created_ts))
+}
+
+async fn finalize_task_template_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<Option<i64>, BackendError> {
+    let mut tx = backend.pool.begin().await?;
+    let rec = sqlx::query!(
+        r#"
+UPDATE task_template
+SET
+    final_task_template_arg_id = COALESCE(
+        final_task_template_arg_id,
+        (
+            SELECT MAX(id)
+            FROM task_template_arg
+            WHERE task_template_id = $1
+        ),
+        0
+    )
+WHERE id = $1
+RETURNING final_task_template_arg_id AS "final_task_template_arg_id!"
+        "#,
+        id,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(Some(rec.final_task_template_arg_id))
+}
+
+async fn get_task_template_by_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<TaskTemplate, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    bin_path,
+    version_id,
+    created_ts,
+    final_task_template_arg_id,
+    superceded_by_id,
+    timeout,
+    sandbox
+FROM task_template
+WHERE id = $1
+"#,
+        id,
+    )
+    .try_map(|row| Ok(TaskTemplate {
+        id: row.id,
+        bin_path: row.bin_path,
+        version_id: row.version_id,
+        created_ts: row.created_ts,
+        final_task_template_arg_id: row.final_task_template_arg_id,
+        superceded_by_id: row.superceded_by_id,
+        timeout: row.timeout,
+        sandbox: row.sandbox
+            .map(|sandbox| serde_json::from_str(&sandbox))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        args: None,
+    }))
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+async fn get_task_template_by_arg_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<TaskTemplate, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    bin_path,
+    version_id,
+    created_ts,
+    final_task_template_arg_id,
+    superceded_by_id,
+    timeout,
+    sandbox
+FROM task_template
+WHERE id = (
+    SELECT task_template_id
+    FROM task_template_arg
+        WHERE id = $1
+)
+"#,
+        id,
+    )
+    .try_map(|row| Ok(TaskTemplate {
+        id: row.id,
+        bin_path: row.bin_path,
+        version_id: row.version_id,
+        created_ts: row.created_ts,
+        final_task_template_arg_id: row.final_task_template_arg_id,
+        superceded_by_id: row.superceded_by_id,
+        timeout: row.timeout,
+        sandbox: row.sandbox
+            .map(|sandbox| serde_json::from_str(&sandbox))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        args: None,
+    }))
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+async fn set_task_template_timeout_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    timeout: Option<i64>,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE task_template
+SET timeout = $2
+WHERE id = $1
+"#,
+        id,
+        timeout,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn set_task_template_sandbox_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    sandbox: Option<&TaskSandbox>,
+) -> Result<bool, BackendError> {
+    let sandbox = sandbox.map(|sandbox| serde_json::to_string(sandbox)
+        .expect("serialization shouldn't fail on this basic type"));
+    let rows_affected = sqlx::query!(r#"
+UPDATE task_template
+SET sandbox = $2
+WHERE id = $1
+"#,
+        id,
+        sandbox,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn supersede_task_template_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    superceded_by_id: i64,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE task_template
+SET superceded_by_id = $2
+WHERE id = $1
+    AND id != $2
+    AND superceded_by_id IS NULL
+    AND EXISTS (
+        SELECT 1
+        FROM task_template
+        WHERE id = $2
+            AND final_task_template_arg_id IS NOT NULL
+            AND superceded_by_id IS NULL
+    )
+"#,
+        id,
+        superceded_by_id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn add_task_template_arg_postgres(
+    backend: &PostgresBackend,
+    task_template_id: i64,
+    flag: Option<&str>,
+    flag_joined: bool,
+    flag_omit_when_null: bool,
+    prompt: Option<&str>,
+    default: Option<&str>,
+    choice_fixed: bool,
+    choice_source: Option<&str>,
+    multiple: bool,
+    multiple_separator: Option<&str>,
+) -> Result<i64, BackendError> {
+    let id = sqlx::query!(
+        r#"
+INSERT INTO task_template_arg (
+    task_template_id,
+    flag,
+    flag_joined,
+    flag_omit_when_null,
+    prompt,
+    "default",
+    choice_fixed,
+    choice_source,
+    multiple,
+    multiple_separator
+)
+VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10 )
+RETURNING id
+        "#,
+        task_template_id,
+        flag,
+        flag_joined,
+        flag_omit_when_null,
+        prompt,
+        default,
+        choice_fixed,
+        choice_source,
+        multiple,
+        multiple_separator,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+
+    Ok(id)
+}
+
+async fn add_task_template_arg_choice_postgres(
+    backend: &PostgresBackend,
+    task_template_arg_id: i64,
+    to_arg: Option<&str>,
+    label: &str,
+) -> Result<i64, BackendError> {
+    let id = sqlx::query!(
+        r#"
+INSERT INTO task_template_arg_choice (
+    task_template_arg_id,
+    to_arg,
+    label
+)
+VALUES ( $1, $2, $3 )
+RETURNING id
+        "#,
+        task_template_arg_id,
+        to_arg,
+        label,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+
+    Ok(id)
+}
+
+async fn delete_task_template_arg_choice_by_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<Option<TaskTemplateArgChoice>, BackendError> {
+    let mut tx = backend.pool.begin().await?;
+    let rec = sqlx::query_as!(TaskTemplateArgChoice, r#"
+DELETE FROM
+    task_template_arg_choice
+WHERE
+    id = $1
+    RETURNING *
+"#,
+        id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(rec)
+}
+
+async fn get_task_template_args_by_task_template_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<TaskTemplateArgs, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    task_template_id,
+    flag,
+    flag_joined,
+    flag_omit_when_null,
+    prompt,
+    "default",
+    choice_fixed,
+    choice_source,
+    multiple,
+    multiple_separator
+FROM task_template_arg,
+    (
+        SELECT final_task_template_arg_id
+        FROM task_template
+        WHERE id = $1
+    ) tt
+WHERE
+    task_template_id = $1 AND
+    (
+        tt.final_task_template_arg_id IS NULL OR
+        id <= tt.final_task_template_arg_id
+    )
+ORDER BY id
+"#,
+        id,
+    )
+    .map(|row| TaskTemplateArg {
+        id: row.id,
+        task_template_id: row.task_template_id,
+        flag: row.flag,
+        flag_joined: row.flag_joined,
+        flag_omit_when_null: row.flag_omit_when_null,
+        prompt: row.prompt,
+        default: row.default,
+        choice_fixed: row.choice_fixed,
+        choice_source: row.choice_source,
+        choices: None,
+        multiple: row.multiple,
+        multiple_separator: row.multiple_separator,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(rec.into())
+}
+
+async fn get_task_template_arg_by_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    complete: bool,
+) -> Result<Option<TaskTemplateArg>, BackendError> {
+    let mut rec = sqlx::query!(r#"
+SELECT
+    id,
+    task_template_id,
+    flag,
+    flag_joined,
+    flag_omit_when_null,
+    prompt,
+    "default",
+    choice_fixed,
+    choice_source,
+    multiple,
+    multiple_separator
+FROM
+    task_template_arg
+WHERE
+    id = $1
+"#,
+        id,
+    )
+    .map(|row| TaskTemplateArg {
+        id: row.id,
+        task_template_id: row.task_template_id,
+        flag: row.flag,
+        flag_joined: row.flag_joined,
+        flag_omit_when_null: row.flag_omit_when_null,
+        prompt: row.prompt,
+        default: row.default,
+        choice_fixed: row.choice_fixed,
+        choice_source: row.choice_source,
+        choices: None,
+        multiple: row.multiple,
+        multiple_separator: row.multiple_separator,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+
+    match &mut rec {
+        Some(ref mut rec) =>
+            rec.choices = if complete && rec.choice_source.is_some() {
+                Some(
+                    get_task_template_arg_choices_by_task_template_arg_id_postgres(
+                        backend,
+                        rec.id,
+                    ).await?
+                )
+            }
+            else {
+                None
+            },
+        None => {}
+    }
+
+    Ok(rec)
+}
+
+async fn delete_task_template_arg_by_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<Option<TaskTemplateArg>, BackendError> {
+    let mut tx = backend.pool.begin().await?;
+    let rec = sqlx::query!(r#"
+DELETE FROM
+    task_template_arg
+WHERE
+    (
+        SELECT final_task_template_arg_id
+        FROM task_template
+        WHERE id = (
+            SELECT task_template_id
+            FROM task_template_arg
+            WHERE id = $1
+        )
+    ) is NULL AND
+    id = $1
+    RETURNING *
+"#,
+        id,
+    )
+    .map(|row| TaskTemplateArg {
+        id: row.id,
+        task_template_id: row.task_template_id,
+        flag: row.flag,
+        flag_joined: row.flag_joined,
+        flag_omit_when_null: row.flag_omit_when_null,
+        prompt: row.prompt,
+        default: row.default,
+        choice_fixed: row.choice_fixed,
+        choice_source: row.choice_source,
+        choices: None,
+        multiple: row.multiple,
+        multiple_separator: row.multiple_separator,
+    })
+    .fetch_optional(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(rec)
+}
+
+async fn get_task_template_arg_choices_by_task_template_arg_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<TaskTemplateArgChoices, BackendError> {
+    let rec = sqlx::query_as!(TaskTemplateArgChoice, r#"
+SELECT
+    id,
+    task_template_arg_id,
+    to_arg,
+    label
+FROM task_template_arg_choice
+WHERE task_template_arg_id = $1
+ORDER BY id
+"#,
+        id,
+    )
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(rec.into())
+}
+
+#[async_trait]
+impl TaskTemplateBackend for PostgresBackend {
+    async fn add_task_template(
+        &self,
+        bin_path: &str,
+        version_id: &str,
+    ) -> Result<(i64, i64), BackendError> {
+        add_task_template_postgres(&self, bin_path, version_id).await
+    }
+
+    async fn finalize_new_task_template(
+        &self,
+        id: i64,
+    ) -> Result<Option<i64>, BackendError> {
+        finalize_task_template_postgres(&self, id).await
+    }
+
+    async fn add_task_template_arg(
+        &self,
+        task_template_id: i64,
+        flag: Option<&str>,
+        flag_joined: bool,
+        flag_omit_when_null: bool,
+        prompt: Option<&str>,
+        default: Option<&str>,
+        choice_fixed: bool,
+        choice_source: Option<&str>,
+        multiple: bool,
+        multiple_separator: Option<&str>,
+    ) -> Result<i64, BackendError> {
+        add_task_template_arg_postgres(
+            &self,
+            task_template_id,
+            flag,
+            flag_joined,
+            flag_omit_when_null,
+            prompt,
+            default,
+            choice_fixed,
+            choice_source,
+            multiple,
+            multiple_separator,
+        ).await
+    }
+
+    async fn delete_task_template_arg_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<TaskTemplateArg>, BackendError> {
+        delete_task_template_arg_by_id_postgres(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn add_task_template_arg_choice(
+        &self,
+        task_template_arg_id: i64,
+        to_arg: Option<&str>,
+        label: &str,
+    ) -> Result<i64, BackendError> {
+        add_task_template_arg_choice_postgres(
+            &self,
+            task_template_arg_id,
+            to_arg,
+            label,
+        ).await
+    }
+
+    async fn get_task_template_arg_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<TaskTemplateArg>, BackendError>{
+        get_task_template_arg_by_id_postgres(
+            &self,
+            id,
+            true,
+        ).await
+    }
+
+    async fn delete_task_template_arg_choice_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<TaskTemplateArgChoice>, BackendError> {
+        delete_task_template_arg_choice_by_id_postgres(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn get_task_template_by_id(
+        &self,
+        id: i64,
+    ) -> Result<TaskTemplate, BackendError> {
+        let mut result = get_task_template_by_id_postgres(&self, id).await?;
+        let mut args = get_task_template_args_by_task_template_id_postgres(
+            &self, result.id
+        ).await?;
+
+        future::try_join_all(args.iter_mut().map(|arg| async {
+            arg.choices = if arg.choice_source.is_some() {
+                Some(
+                    get_task_template_arg_choices_by_task_template_arg_id_postgres(
+                        &self,
+                        arg.id,
+                    ).await?
+                )
+            } else {
+                None
+            };
+            Ok::<(), BackendError>(())
+        })).await?;
+
+        result.args = Some(args);
+        Ok(result)
+    }
+
+    async fn get_task_template_by_arg_id(
+        &self,
+        id: i64,
+    ) -> Result<TaskTemplate, BackendError> {
+        let mut result = get_task_template_by_arg_id_postgres(&self, id).await?;
+        // TODO the following duplicates the above; will need to investigate
+        // how to better incorporate these additional selects into the function
+        // or provide additional functions/arguments/etc
+        let mut args = get_task_template_args_by_task_template_id_postgres(
+            &self, result.id
+        ).await?;
+
+        future::try_join_all(args.iter_mut().map(|arg| async {
+            arg.choices = if arg.choice_source.is_some() {
+                Some(
+                    get_task_template_arg_choices_by_task_template_arg_id_postgres(
+                        &self,
+                        arg.id,
+                    ).await?
+                )
+            } else {
+                None
+            };
+            Ok::<(), BackendError>(())
+        })).await?;
+
+        result.args = Some(args);
+        Ok(result)
+    }
+
+    async fn set_task_template_timeout(
+        &self,
+        id: i64,
+        timeout: Option<i64>,
+    ) -> Result<bool, BackendError> {
+        set_task_template_timeout_postgres(&self, id, timeout).await
+    }
+
+    async fn set_task_template_sandbox(
+        &self,
+        id: i64,
+        sandbox: Option<&TaskSandbox>,
+    ) -> Result<bool, BackendError> {
+        set_task_template_sandbox_postgres(&self, id, sandbox).await
+    }
+
+    async fn supersede_task_template(
+        &self,
+        id: i64,
+        superceded_by_id: i64,
+    ) -> Result<bool, BackendError> {
+        supersede_task_template_postgres(&self, id, superceded_by_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        task_template::{
+            TaskTemplate,
+            TaskTemplateArg,
+            TaskTemplateArgChoice,
+            traits::TaskTemplateBackend,
+        },
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    #[async_std::test]
+    async fn test_smoketest_no_args() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let ttb: &dyn TaskTemplateBackend = &backend;
+
+        let (id, _) = ttb.add_task_template("/bin/true", "1.0.0").await?;
+        let fin_arg_id = ttb.finalize_new_task_template(id).await?;
+        let template = ttb.get_task_template_by_id(id).await?;
+
+        assert_eq!(id, 1);
+        assert_eq!(fin_arg_id, Some(0));
+        assert_eq!(template, TaskTemplate {
+            id: 1,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 1234567890,
+            final_task_template_arg_id: Some(0),
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([].to_vec().into()),
+        });
+        Ok(())
+    }
+
+    // this tests for the situation where choices were added to argument
+    // where the choice source was None
+    #[async_std::test]
+    async fn test_smoketest_with_choice_source_none() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let ttb: &dyn TaskTemplateBackend = &backend;
+
+        let (id, _) = ttb.add_task_template("/bin/echo", "1.0.0").await?;
+        ttb.add_task_template_arg(
+            id,
+            None,
+            false,
+            false,
+            Some("Faulty Choice"),
+            None,
+            false,
+            None,
+            false,
+            None,
+        ).await?;
+        ttb.finalize_new_task_template(id).await?;
+
+        let template = ttb.get_task_template_by_id(id).await?;
+        let answer = TaskTemplate {
+            id: 1,
+            bin_path: "/bin/echo".into(),
+            version_id: "1.0.0".into(),
+            created_ts: template.created_ts,  // matching itself
+            final_task_template_arg_id: Some(1),
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([TaskTemplateArg {
+                id: 1,
+                task_template_id: 1,
+                flag: None,
+                flag_joined: false,
+                flag_omit_when_null: false,
+                prompt: Some("Faulty Choice".into()),
+                default: None,
+                choice_fixed: false,
+                choice_source: None,
+                choices: None,
+                multiple: false,
+                multiple_separator: None,
+            }].to_vec().into()),
+        };
+        assert_eq!(template, answer);
+        assert_eq!(template, serde_json::from_str(r#"
+        {
+            "id": 1,
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "created_ts": 1234567890,
+            "final_task_template_arg_id": 1,
+            "superceded_by_id": null,
+            "args": [
+                {
+                    "id": 1,
+                    "task_template_id": 1,
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "Faulty Choice",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": null
+                }
+            ]
+        }
+        "#)?);
+
+        // add a couple choices
+        ttb.add_task_template_arg_choice(1, None, "omit").await?;
+        ttb.add_task_template_arg_choice(1, Some(""), "empty string").await?;
+        let template = ttb.get_task_template_by_id(id).await?;
+
+        assert_eq!(template, serde_json::from_str(r#"
+        {
+            "id": 1,
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "created_ts": 1234567890,
+            "final_task_template_arg_id": 1,
+            "superceded_by_id": null,
+            "args": [
+                {
+                    "id": 1,
+                    "task_template_id": 1,
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "Faulty Choice",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": null
+                }
+            ]
+        }
+        "#)?);
+        assert!(template.args.unwrap()[0].choices.is_none());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_smoketest_with_args() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let ttb: &dyn TaskTemplateBackend = &backend;
+
+        let (id, _) = ttb.add_task_template("/bin/echo", "1.0.0").await?;
+        ttb.add_task_template_arg(
+            id,
+            None,
+            false,
+            false,
+            Some("First statement"),
+            None,
+            false,
+            None,
+            false,
+            None,
+        ).await?;
+        ttb.add_task_template_arg(
+            id,
+            None,
+            false,
+            false,
+            Some("Second statement"),
+            None,
+            false,
+            Some(""),
+            false,
+            None,
+        ).await?;
+        ttb.finalize_new_task_template(id).await?;
+
+        let template = ttb.get_task_template_by_id(id).await?;
+        let answer = TaskTemplate {
+            id: 1,
+            bin_path: "/bin/echo".into(),
+            version_id: "1.0.0".into(),
+            created_ts: template.created_ts,  // matching itself
+            final_task_template_arg_id: Some(2),
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([TaskTemplateArg {
+                id: 1,
+                task_template_id: 1,
+                flag: None,
+                flag_joined: false,
+                flag_omit_when_null: false,
+                prompt: Some("First statement".into()),
+                default: None,
+                choice_fixed: false,
+                choice_source: None,
+                choices: None,
+                multiple: false,
+                multiple_separator: None,
+            }, TaskTemplateArg {
+                id: 2,
+                task_template_id: 1,
+                flag: None,
+                flag_joined: false,
+                flag_omit_when_null: false,
+                prompt: Some("Second statement".into()),
+                default: None,
+                choice_fixed: false,
+                choice_source: Some("".into()),
+                choices: Some(vec![].into()),
+                multiple: false,
+                multiple_separator: None,
+            }].to_vec().into()),
+        };
+        assert_eq!(template, answer);
+        assert_eq!(template, serde_json::from_str(r#"
+        {
+            "id": 1,
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "created_ts": 1234567890,
+            "final_task_template_arg_id": 2,
+            "superceded_by_id": null,
+            "args": [
+                {
+                    "id": 1,
+                    "task_template_id": 1,
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "First statement",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": null
+                },
+                {
+                    "id": 2,
+                    "task_template_id": 1,
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "Second statement",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": "",
+                    "choices": []
+                }
+            ]
+        }
+        "#)?);
+
+        // add a couple choices
+        ttb.add_task_template_arg_choice(2, None, "omit").await?;
+        ttb.add_task_template_arg_choice(2, Some(""), "empty string").await?;
+        let template = ttb.get_task_template_by_id(id).await?;
+
+        assert_eq!(template, serde_json::from_str(r#"
+        {
+            "id": 1,
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "created_ts": 1234567890,
+            "final_task_template_arg_id": 2,
+            "superceded_by_id": null,
+            "args": [
+                {
+                    "id": 1,
+                    "task_template_id": 1,
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "First statement",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": null
+                },
+                {
+                    "id": 2,
+                    "task_template_id": 1,
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "Second statement",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": "",
+                    "choices": [
+                        {
+                            "id": 1,
+                            "task_template_arg_id": 2,
+                            "to_arg": null,
+                            "label": "omit"
+                        },
+                        {
+                            "id": 2,
+                            "task_template_arg_id": 2,
+                            "to_arg": "",
+                            "label": "empty string"
+                        }
+                    ]
+                }
+            ]
+        }
+        "#)?);
+        assert_eq!(template.args.unwrap()[1].choices, Some([
+            TaskTemplateArgChoice {
+                id: 1,
+                task_template_arg_id: 2,
+                to_arg: None,
+                label: "omit".into(),
+            },
+            TaskTemplateArgChoice {
+                id: 2,
+                task_template_arg_id: 2,
+                to_arg: Some("".into()),
+                label: "empty string".into(),
+            },
+        ].to_vec().into()));
+
+        let arg = ttb.get_task_template_arg_by_id(2).await?.unwrap();
+        assert_eq!(arg.prompt, Some("Second statement".into()));
+        assert_eq!(arg.choices, Some([
+            TaskTemplateArgChoice {
+                id: 1,
+                task_template_arg_id: 2,
+                to_arg: None,
+                label: "omit".into(),
+            },
+            TaskTemplateArgChoice {
+                id: 2,
+                task_template_arg_id: 2,
+                to_arg: Some("".into()),
+                label: "empty string".into(),
+            },
+        ].to_vec().into()));
+
+        ttb.delete_task_template_arg_choice_by_id(2).await?;
+        let template = ttb.get_task_template_by_id(id).await?;
+        assert_eq!(template.args.unwrap()[1].choices, Some([
+            TaskTemplateArgChoice {
+                id: 1,
+                task_template_arg_id: 2,
+                to_arg: None,
+                label: "omit".into(),
+            },
+        ].to_vec().into()));
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_add_manual_finalize() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let (id, _) = TaskTemplateBackend::add_task_template(
+            &backend, "/bin/true", "1.0.0",
+        ).await
+            .unwrap();
+        TaskTemplateBackend::add_task_template_arg(
+            &backend, 1, Some("-i"), false, false, None, None, false, None, false, None
+        ).await.unwrap();
+        let template = TaskTemplateBackend::get_task_template_by_id(
+            &backend, id
+        ).await.unwrap();
+        assert_eq!(template, TaskTemplate {
+            id: 1,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 1234567890,
+            final_task_template_arg_id: None,
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([TaskTemplateArg {
+                id: 1,
+                task_template_id: 1,
+                flag: Some("-i".into()),
+                flag_joined: false,
+                flag_omit_when_null: false,
+                prompt: None,
+                default: None,
+                choice_fixed: false,
+                choice_source: None,
+                choices: None,
+                multiple: false,
+                multiple_separator: None,
+            }].to_vec().into()),
+        });
+
+        TaskTemplateBackend::finalize_new_task_template(
+            &backend, id,
+        ).await.unwrap();
+
+        // deleting should fail
+        assert_eq!(None, TaskTemplateBackend::delete_task_template_arg_by_id(
+            &backend, 1).await.unwrap());
+
+        let template = TaskTemplateBackend::get_task_template_by_id(
+            &backend, id
+        ).await.unwrap();
+        assert_eq!(template, TaskTemplate {
+            id: 1,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 1234567890,
+            final_task_template_arg_id: Some(1),
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([TaskTemplateArg {
+                id: 1,
+                task_template_id: 1,
+                flag: Some("-i".into()),
+                flag_joined: false,
+                flag_omit_when_null: false,
+                prompt: None,
+                default: None,
+                choice_fixed: false,
+                choice_source: None,
+                choices: None,
+                multiple: false,
+                multiple_separator: None,
+            }].to_vec().into()),
+        });
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_add_rm() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let (id, _) = TaskTemplateBackend::add_task_template(
+            &backend, "/bin/true", "1.0.0",
+        ).await.unwrap();
+        TaskTemplateBackend::add_task_template_arg(
+            &backend, 1, Some("-i"), false, false, None, None, false, None, false, None
+        ).await.unwrap();
+        assert_eq!(TaskTemplateBackend::delete_task_template_arg_by_id(
+            &backend, 1).await.unwrap(), Some(TaskTemplateArg {
+                id: 1,
+                task_template_id: 1,
+                flag: Some("-i".into()),
+                flag_joined: false,
+                flag_omit_when_null: false,
+                prompt: None,
+                default: None,
+                choice_fixed: false,
+                choice_source: None,
+                choices: None,
+                multiple: false,
+                multiple_separator: None,
+            }
+        ));
+
+        let template = TaskTemplateBackend::get_task_template_by_id(
+            &backend, id
+        ).await.unwrap();
+        assert_eq!(template, TaskTemplate {
+            id: 1,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 1234567890,
+            final_task_template_arg_id: None,
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([].to_vec().into()),
+        });
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_add_manual_finalize_nospill() -> anyhow::Result<()> {
+        // normally this is done, but we need the inner type not the opaque impl
+        // let backend = PostgresBackend::tm(create_database().into())
+        //     .await
+        //     .map_err(anyhow::Error::from_boxed)?;
+        let backend = PostgresBackend::connect(create_database().into())
+            .await?
+            .migrate_tm()
+            .await?;
+
+        let (id, _) = TaskTemplateBackend::add_task_template(
+            &backend, "/bin/true", "1.0.0",
+        ).await
+            .unwrap();
+        // not yet finalized
+        let template = TaskTemplateBackend::get_task_template_by_id(
+            &backend, id
+        ).await.unwrap();
+        assert_eq!(template, TaskTemplate {
+            id: 1,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 1234567890,
+            final_task_template_arg_id: None,
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([].to_vec().into()),
+        });
+
+        // finalizing
+        TaskTemplateBackend::finalize_new_task_template(
+            &backend, id,
+        ).await.unwrap();
+
+        // should reflect finalized id of 0
+        let template = TaskTemplateBackend::get_task_template_by_id(
+            &backend, id
+        ).await.unwrap();
+        assert_eq!(template, TaskTemplate {
+            id: 1,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 1234567890,
+            final_task_template_arg_id: Some(0),
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([].to_vec().into()),
+        });
+
+        // doing a manual insert to avoid API changes that may prevent this
+        sqlx::query!(
+            r#"
+            INSERT INTO task_template_arg (
+                task_template_id,
+                flag,
+                flag_joined,
+                flag_omit_when_null,
+                prompt,
+                "default",
+                choice_fixed,
+                choice_source
+            )
+            VALUES ( 1, '-h', FALSE, FALSE, 'The prompt', NULL, FALSE, NULL)
+            "#,
+        )
+        .execute(&*backend.pool)
+        .await
+        .unwrap();
+
+        // attempt to finalize again despite choice manually added
+        TaskTemplateBackend::finalize_new_task_template(
+            &backend, id,
+        ).await.unwrap();
+        let template = TaskTemplateBackend::get_task_template_by_id(
+            &backend, id
+        ).await.unwrap();
+
+        // can't be added.
+        assert_eq!(template, TaskTemplate {
+            id: 1,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 1234567890,
+            final_task_template_arg_id: Some(0),
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([].to_vec().into()),
+        });
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_adds() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let ttb: &(dyn TaskTemplateBackend + Sync) = &backend;
+        let task_template: TaskTemplate = serde_json::from_str(r#"
+        {
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "args": [
+                {
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "First statement",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": [
+                        {
+                            "to_arg": null,
+                            "label": "omit"
+                        },
+                        {
+                            "to_arg": "",
+                            "label": "empty string"
+                        }
+                    ]
+                },
+                {
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "Second statement",
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": [],
+                    "multiple": true,
+                    "multiple_separator": ","
+                }
+            ]
+        }
+        "#)?;
+
+        let result = ttb.adds_task_template(task_template.clone()).await?;
+        assert_ne!(result, task_template);
+
+        assert_eq!(result.id, 1);
+        assert_eq!(result.final_task_template_arg_id, Some(2));
+
+        let args = ttb.get_task_template_by_id(result.id).await?
+            .args
+            .expect("args should be returned");
+        assert!(!args[0].multiple);
+        assert_eq!(args[0].multiple_separator, None);
+        assert!(args[1].multiple);
+        assert_eq!(args[1].multiple_separator.as_deref(), Some(","));
+
+        let tt2 = ttb.adds_task_template(serde_json::from_str(r#"
+        {
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "args": []
+        }"#)?).await?;
+        assert_eq!(tt2.final_task_template_arg_id, Some(0));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_supersede() -> anyhow::Result<()> {
+        let backend = PostgresBackend::tm(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let ttb: &(dyn TaskTemplateBackend + Sync) = &backend;
+        let task_template: TaskTemplate = serde_json::from_str(r#"
+        {
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "args": []
+        }"#)?;
+        let tt1 = ttb.adds_task_template(task_template.clone()).await?;
+        let tt2 = ttb.adds_task_template(task_template.clone()).await?;
+        let tt3 = ttb.adds_task_template(task_template).await?;
+        let (unfinalized, _) = ttb.add_task_template("/bin/echo", "2.0.0").await?;
+
+        // must be superceded by another finalized task template
+        assert!(!ttb.supersede_task_template(tt1.id, tt1.id).await?);
+        assert!(!ttb.supersede_task_template(tt1.id, unfinalized).await?);
+        assert!(!ttb.supersede_task_template(tt1.id, 0).await?);
+
+        assert!(ttb.supersede_task_template(tt1.id, tt2.id).await?);
+        assert_eq!(
+            ttb.get_task_template_by_id(tt1.id).await?.superceded_by_id,
+            Some(tt2.id),
+        );
+        // already superceded
+        assert!(!ttb.supersede_task_template(tt1.id, tt3.id).await?);
+        // cannot be superceded by a superceded task template
+        assert!(!ttb.supersede_task_template(tt3.id, tt1.id).await?);
+        assert!(ttb.supersede_task_template(tt2.id, tt3.id).await?);
+        assert_eq!(
+            ttb.get_task_template_by_id(tt2.id).await?.superceded_by_id,
+            Some(tt3.id),
+        );
+        assert_eq!(ttb.get_task_template_by_id(tt3.id).await?.superceded_by_id, None);
+
+        Ok(())
+    }
+
+}
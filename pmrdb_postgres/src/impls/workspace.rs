@@ -0,0 +1,622 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    listing::{
+        Cursor,
+        ListingQuery,
+        Page,
+    },
+    workspace::{
+        Workspace,
+        Workspaces,
+        traits::WorkspaceBackend,
+    }
+};
+use sqlx::{Postgres, QueryBuilder, Row};
+
+use crate::{
+    PostgresBackend,
+    chrono::Utc,
+    impls::listing::push_listing_query,
+};
+
+
+async fn add_workspace_postgres(
+    backend: &PostgresBackend,
+    url: &str,
+    description: Option<&str>,
+    long_description: Option<&str>,
+) -> Result<i64, BackendError> {
+    let ts = Utc::now().timestamp();
+    let id = sqlx::query!(
+        r#"
+INSERT INTO workspace (
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts
+)
+VALUES ( $1, $2, $3, $4, $5 )
+RETURNING id
+        "#,
+        url,
+        None::<i64>,
+        description,
+        long_description,
+        ts,
+    )
+    .map(|row| row.id)
+    .fetch_one(&*backend.pool)
+    .await?;
+
+    Ok(id)
+}
+
+async fn update_workspace_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    description: Option<&str>,
+    long_description: Option<&str>,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE
+    workspace
+SET
+    description = $1,
+    long_description = $2
+WHERE
+    id = $3"#,
+        description,
+        long_description,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn list_workspaces_postgres(
+    backend: &PostgresBackend,
+) -> Result<Workspaces, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace
+WHERE
+    archived_ts IS NULL
+ORDER BY
+    id
+        "#
+    )
+    .map(|row| Workspace {
+        id: row.id,
+        url: row.url,
+        superceded_by_id: row.superceded_by_id,
+        description: row.description,
+        long_description: row.long_description,
+        created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
+        exposures: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs.into())
+}
+
+async fn list_workspaces_page_postgres(
+    backend: &PostgresBackend,
+    query: &ListingQuery,
+) -> Result<Page<Workspace>, BackendError> {
+    let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace"#);
+
+    let mut conditions = vec!["archived_ts IS NULL"];
+    match query.has_exposure {
+        Some(true) => conditions.push(
+            "EXISTS (SELECT 1 FROM exposure WHERE exposure.workspace_id = workspace.id)"),
+        Some(false) => conditions.push(
+            "NOT EXISTS (SELECT 1 FROM exposure WHERE exposure.workspace_id = workspace.id)"),
+        None => (),
+    }
+    let page_size = push_listing_query(&mut query_builder, query, &conditions)?;
+
+    let recs = query_builder
+        .build()
+        .try_map(|row| Ok(Workspace {
+            id: row.try_get("id")?,
+            url: row.try_get("url")?,
+            superceded_by_id: row.try_get("superceded_by_id")?,
+            description: row.try_get("description")?,
+            long_description: row.try_get("long_description")?,
+            created_ts: row.try_get("created_ts")?,
+            archived_ts: row.try_get("archived_ts")?,
+            exposures: None,
+        }))
+        .fetch_all(&*backend.pool)
+        .await?;
+
+    Ok(Page::from_fetched(recs, page_size, |workspace| Cursor::new(
+        query.sort,
+        workspace.id,
+        workspace.created_ts,
+        workspace.description.as_deref(),
+    )))
+}
+
+async fn get_workspace_by_id_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<Workspace, BackendError> {
+    // ignoring superceded_by_id for now?
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace
+WHERE
+    id = $1
+        "#,
+        id,
+    )
+    .map(|row| Workspace {
+        id: row.id,
+        url: row.url,
+        superceded_by_id: row.superceded_by_id,
+        description: row.description,
+        long_description: row.long_description,
+        created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
+        exposures: None,
+    })
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+async fn list_workspaces_by_url_postgres(
+    backend: &PostgresBackend,
+    url: &str,
+) -> Result<Workspaces, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace
+WHERE
+    url = $1
+ORDER BY
+    id
+        "#,
+        url,
+    )
+    .map(|row| Workspace {
+        id: row.id,
+        url: row.url,
+        superceded_by_id: row.superceded_by_id,
+        description: row.description,
+        long_description: row.long_description,
+        created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
+        exposures: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs.into())
+}
+
+async fn list_workspaces_by_ids_postgres(
+    backend: &PostgresBackend,
+    ids: &[i64],
+) -> Result<Workspaces, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace
+WHERE
+    id = ANY($1)
+ORDER BY
+    id
+        "#,
+        ids,
+    )
+    .map(|row| Workspace {
+        id: row.id,
+        url: row.url,
+        superceded_by_id: row.superceded_by_id,
+        description: row.description,
+        long_description: row.long_description,
+        created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
+        exposures: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+
+    Ok(recs.into())
+}
+
+async fn set_workspace_superceded_by_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+    superceded_by_id: Option<i64>,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE
+    workspace
+SET
+    superceded_by_id = $1
+WHERE
+    id = $2"#,
+        superceded_by_id,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn archive_workspace_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let ts = Utc::now().timestamp();
+    let rows_affected = sqlx::query!(r#"
+UPDATE
+    workspace
+SET
+    archived_ts = $1
+WHERE
+    id = $2
+    AND archived_ts IS NULL"#,
+        ts,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn restore_workspace_postgres(
+    backend: &PostgresBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE
+    workspace
+SET
+    archived_ts = NULL
+WHERE
+    id = $1
+    AND archived_ts IS NOT NULL"#,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn list_archived_workspaces_postgres(
+    backend: &PostgresBackend,
+) -> Result<Workspaces, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace
+WHERE
+    archived_ts IS NOT NULL
+ORDER BY
+    id
+        "#
+    )
+    .map(|row| Workspace {
+        id: row.id,
+        url: row.url,
+        superceded_by_id: row.superceded_by_id,
+        description: row.description,
+        long_description: row.long_description,
+        created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
+        exposures: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs.into())
+}
+
+#[async_trait]
+impl WorkspaceBackend for PostgresBackend {
+    async fn add_workspace(
+        &self,
+        url: &str,
+        description: Option<&str>,
+        long_description: Option<&str>,
+    ) -> Result<i64, BackendError> {
+        add_workspace_postgres(
+            &self,
+            url,
+            description,
+            long_description,
+        ).await
+    }
+
+    async fn update_workspace(
+        &self,
+        id: i64,
+        description: Option<&str>,
+        long_description: Option<&str>,
+    ) -> Result<bool, BackendError> {
+        update_workspace_postgres(
+            &self,
+            id,
+            description,
+            long_description,
+        ).await
+    }
+
+    async fn list_workspaces(
+        &self,
+    ) -> Result<Workspaces, BackendError> {
+        list_workspaces_postgres(&self).await
+    }
+
+    async fn get_workspace_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Workspace, BackendError> {
+        get_workspace_by_id_postgres(&self, id).await
+    }
+
+    async fn list_workspaces_page(
+        &self,
+        query: &ListingQuery,
+    ) -> Result<Page<Workspace>, BackendError> {
+        list_workspaces_page_postgres(&self, query).await
+    }
+
+    async fn list_workspace_by_url(
+        &self,
+        url: &str,
+    ) -> Result<Workspaces, BackendError> {
+        list_workspaces_by_url_postgres(&self, url).await
+    }
+
+    async fn list_workspace_by_ids(
+        &self,
+        ids: &[i64],
+    ) -> Result<Workspaces, BackendError> {
+        list_workspaces_by_ids_postgres(&self, ids).await
+    }
+
+    async fn set_workspace_superceded_by(
+        &self,
+        id: i64,
+        superceded_by_id: Option<i64>,
+    ) -> Result<bool, BackendError> {
+        set_workspace_superceded_by_postgres(&self, id, superceded_by_id).await
+    }
+
+    async fn archive_workspace(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        archive_workspace_postgres(&self, id).await
+    }
+
+    async fn restore_workspace(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        restore_workspace_postgres(&self, id).await
+    }
+
+    async fn list_archived_workspaces(
+        &self,
+    ) -> Result<Workspaces, BackendError> {
+        list_archived_workspaces_postgres(&self).await
+    }
+}
+
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        workspace::{
+            Workspace,
+            traits::WorkspaceBackend,
+        },
+    };
+    use test_pmr::postgres::create_database;
+    use crate::PostgresBackend;
+
+    pub(crate) async fn make_example_workspace(
+        backend: &dyn WorkspaceBackend,
+    ) -> anyhow::Result<i64> {
+        Ok(backend.add_workspace(
+            "https://models.example.com".into(),
+            Some("".into()),
+            Some("".into()),
+        ).await?)
+    }
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let id = make_example_workspace(&backend)
+            .await?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        let workspace = wb.get_workspace_by_id(id).await?;
+        let answer = Workspace {
+            id: 1,
+            url: "https://models.example.com".into(),
+            superceded_by_id: None,
+            created_ts: 1234567890,
+            description: Some("".into()),
+            long_description: Some("".into()),
+            archived_ts: None,
+            exposures: None,
+        };
+        assert_eq!(workspace, answer);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_list_by_url() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        // note this makes _two_ workspaces with the same url
+        make_example_workspace(&backend).await?;
+        make_example_workspace(&backend).await?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        let workspaces = wb.list_workspace_by_url("https://models.example.com")
+            .await?;
+        assert_eq!(workspaces.len(), 2);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_listing() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        let id1 = make_example_workspace(wb).await?;
+        let id2 = make_example_workspace(wb).await?;
+        let id3 = make_example_workspace(wb).await?;
+        assert_eq!(wb.list_workspaces().await?.len(), 3);
+
+        let by_ids = wb.list_workspace_by_ids(&[id1, id3]).await?;
+        assert_eq!(by_ids.len(), 2);
+
+        let by_ids = wb.list_workspace_by_ids(&[id1]).await?;
+        assert_eq!(by_ids[0].id, id1);
+
+        let by_ids = wb.list_workspace_by_ids(&[id2]).await?;
+        assert_eq!(by_ids[0].id, id2);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_update() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let id = make_example_workspace(&backend)
+            .await?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        assert!(wb.update_workspace(id, Some("title"), Some("description")).await?);
+
+        let workspace = wb.get_workspace_by_id(id).await?;
+        assert_eq!(workspace, Workspace {
+            id: 1,
+            url: "https://models.example.com".into(),
+            superceded_by_id: None,
+            created_ts: 1234567890,
+            description: Some("title".into()),
+            long_description: Some("description".into()),
+            archived_ts: None,
+            exposures: None,
+        });
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_supersede() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        let id1 = make_example_workspace(wb).await?;
+        let id2 = make_example_workspace(wb).await?;
+        assert!(wb.set_workspace_superceded_by(id1, Some(id2)).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.superceded_by_id, Some(id2));
+        assert_eq!(wb.get_workspace_by_id(id2).await?.superceded_by_id, None);
+        assert!(wb.set_workspace_superceded_by(id1, None).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.superceded_by_id, None);
+        assert!(!wb.set_workspace_superceded_by(id2 + 1, Some(id1)).await?);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_archive() -> anyhow::Result<()> {
+        let backend = PostgresBackend::mc(create_database().into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        let id1 = make_example_workspace(wb).await?;
+        let id2 = make_example_workspace(wb).await?;
+        assert!(wb.list_archived_workspaces().await?.is_empty());
+
+        assert!(wb.archive_workspace(id1).await?);
+        assert!(!wb.archive_workspace(id1).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.archived_ts, Some(1234567890));
+        assert_eq!(
+            wb.list_workspaces().await?.iter().map(|w| w.id).collect::<Vec<_>>(),
+            [id2],
+        );
+        assert_eq!(
+            wb.list_archived_workspaces().await?.iter().map(|w| w.id).collect::<Vec<_>>(),
+            [id1],
+        );
+        // archived workspaces may still be looked up directly
+        assert_eq!(wb.list_workspace_by_ids(&[id1]).await?.len(), 1);
+
+        assert!(wb.restore_workspace(id1).await?);
+        assert!(!wb.restore_workspace(id1).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.archived_ts, None);
+        assert_eq!(wb.list_workspaces().await?.len(), 2);
+        Ok(())
+    }
+
+}
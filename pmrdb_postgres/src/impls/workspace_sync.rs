@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    workspace::{
+        WorkspaceSync,
+        WorkspaceSyncStatus,
+        traits::WorkspaceSyncBackend,
+    },
+};
+
+use crate::{
+    PostgresBackend,
+    chrono::Utc,
+};
+
+#[async_trait]
+impl WorkspaceSyncBackend for PostgresBackend {
+    async fn begin_sync(
+        &self,
+        workspace_id: i64,
+    ) -> Result<i64, BackendError> {
+        let ts = Utc::now().timestamp();
+
+        let id = sqlx::query!(
+            r#"
+    INSERT INTO workspace_sync ( workspace_id, start, status )
+    VALUES ( $1, $2, $3 )
+RETURNING id
+            "#,
+            workspace_id,
+            ts,
+            WorkspaceSyncStatus::Running as i64,
+        )
+        .map(|row| row.id)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    async fn complete_sync(
+        &self,
+        id: i64,
+        status: WorkspaceSyncStatus,
+    ) -> Result<bool, BackendError> {
+        let ts = Utc::now().timestamp();
+        let status_ = status as i64;
+
+        let rows_affected = sqlx::query!(
+            r#"
+    UPDATE workspace_sync
+    SET "end" = $1, status = $2
+    WHERE id = $3
+            "#,
+            ts,
+            status_,
+            id,
+        )
+        .execute(&*self.pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn get_workspaces_sync_records(
+        &self,
+        workspace_id: i64,
+    ) -> Result<Vec<WorkspaceSync>, BackendError> {
+        let recs = sqlx::query_as!(WorkspaceSync,
+            r#"
+SELECT id, workspace_id, start, "end", status
+FROM workspace_sync
+WHERE workspace_id = $1
+ORDER BY id
+            "#,
+            workspace_id,
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(recs)
+    }
+
+    async fn list_latest_sync_records(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<WorkspaceSync>, BackendError> {
+        let recs = sqlx::query_as!(WorkspaceSync,
+            r#"
+SELECT
+    id AS "id!",
+    workspace_id AS "workspace_id!",
+    start AS "start!",
+    "end",
+    status AS "status!"
+FROM (
+    SELECT id, workspace_id, start, "end", status,
+        ROW_NUMBER() OVER (PARTITION BY workspace_id ORDER BY id DESC) AS rank
+    FROM workspace_sync
+) AS latest
+WHERE rank <= $1
+ORDER BY workspace_id, id
+            "#,
+            limit,
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(recs)
+    }
+}
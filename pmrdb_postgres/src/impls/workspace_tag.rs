@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use pmrcore::{
+    error::BackendError,
+    workspace::{
+        WorkspaceTag,
+        traits::WorkspaceTagBackend,
+    },
+};
+
+use crate::PostgresBackend;
+
+#[async_trait]
+impl WorkspaceTagBackend for PostgresBackend {
+
+    async fn index_workspace_tag(
+        &self,
+        workspace_id: i64,
+        name: &str,
+        commit_id: &str,
+    ) -> Result<i64, BackendError> {
+        let id = sqlx::query!(
+            r#"
+    WITH q AS (
+        INSERT INTO workspace_tag ( workspace_id, name, commit_id )
+        VALUES ( $1, $2, $3 )
+        ON CONFLICT (workspace_id, name, commit_id) DO NOTHING
+        RETURNING id
+    )
+    SELECT id AS "id!" FROM q
+    UNION
+        SELECT id FROM workspace_tag
+        WHERE workspace_id = $1 AND name = $2 AND commit_id = $3
+            "#,
+            workspace_id,
+            name,
+            commit_id,
+        )
+        .map(|row| row.id)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(id)
+    }
+    // TODO create test so that the unique indexes are done correctly
+
+    async fn get_workspace_tags(
+        &self,
+        workspace_id: i64,
+    ) -> Result<Vec<WorkspaceTag>, BackendError> {
+        let recs = sqlx::query_as!(WorkspaceTag,
+            r#"
+    SELECT id, workspace_id, name, commit_id
+    FROM workspace_tag
+    WHERE workspace_id = $1
+    ORDER BY id
+            "#,
+            workspace_id,
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(recs)
+    }
+
+}
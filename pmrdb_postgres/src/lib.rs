@@ -0,0 +1,16 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub struct PostgresBackend {
+    pub(crate) pool: Arc<PgPool>,
+    pub(crate) url: String,
+}
+
+mod impls;
+
+pub(crate) mod chrono {
+    #[cfg(not(test))]
+    pub use ::chrono::Utc;
+    #[cfg(test)]
+    pub use test_pmr::chrono::Utc;
+}
@@ -0,0 +1,3 @@
+-- The bind requests are counted by origin to limit the rate at which
+-- they may be made.
+CREATE INDEX IF NOT EXISTS user_email_bindreq__origin_created_ts ON user_email_bindreq(origin, created_ts);
@@ -0,0 +1,25 @@
+-- A personal access token for a user, which may be exchanged for a
+-- session that is limited to the actions listed in its scopes until the
+-- token expires or is removed.
+CREATE TABLE IF NOT EXISTS user_token (
+    id INTEGER PRIMARY KEY NOT NULL,
+    user_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    token TEXT NOT NULL,
+    created_ts INTEGER NOT NULL,
+    expires_ts INTEGER NOT NULL,
+    FOREIGN KEY(user_id) REFERENCES 'user'(id)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS user_token__token ON user_token(token);
+CREATE INDEX IF NOT EXISTS user_token__user_id ON user_token(user_id);
+
+CREATE TABLE IF NOT EXISTS user_token_scope (
+    user_token_id INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    PRIMARY KEY(user_token_id, action),
+    FOREIGN KEY(user_token_id) REFERENCES user_token(id)
+);
+
+-- The token that the session was created from, if any.
+ALTER TABLE user_session ADD COLUMN user_token_id INTEGER REFERENCES user_token(id);
+CREATE INDEX IF NOT EXISTS user_session__user_token_id ON user_session(user_token_id);
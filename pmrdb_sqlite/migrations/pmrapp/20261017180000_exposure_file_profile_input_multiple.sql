@@ -0,0 +1,3 @@
+-- `multiple` denotes the `input` being the JSON encoded list of values
+-- provided for arguments that accept multiple values.
+ALTER TABLE exposure_file_profile_input ADD COLUMN multiple BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- `timeout` is the wall-clock limit in seconds for tasks created from
+-- the task template, NULL for no limit.
+ALTER TABLE task_template ADD COLUMN timeout INTEGER;
@@ -0,0 +1,3 @@
+-- `sandbox` is the JSON encoded restrictions for the processes of tasks
+-- created from the task template, NULL for none.
+ALTER TABLE task_template ADD COLUMN sandbox TEXT;
@@ -0,0 +1,25 @@
+-- `priority` orders the tasks to be started, highest first.
+ALTER TABLE task ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
+-- `attempts` counts the times the task was started, and while it is no
+-- more than `max_retries` a failed task is returned to the queue, to be
+-- started no earlier than `retry_ts`, which is `retry_delay` seconds
+-- after the failure, doubled for every retry.
+ALTER TABLE task ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE task ADD COLUMN max_retries INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE task ADD COLUMN retry_delay INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE task ADD COLUMN retry_ts INTEGER;
+
+CREATE INDEX task__start_ts_priority ON task(start_ts, priority);
+
+-- A task will not be started until all the tasks it depends on have
+-- completed successfully, and a failure of those will be recorded as
+-- the failure of the task.
+CREATE TABLE IF NOT EXISTS task_dependency (
+    task_id INTEGER NOT NULL,
+    depends_on_id INTEGER NOT NULL,
+    PRIMARY KEY(task_id, depends_on_id),
+    FOREIGN KEY(task_id) REFERENCES task(id),
+    FOREIGN KEY(depends_on_id) REFERENCES task(id)
+);
+
+CREATE INDEX task_dependency__depends_on_id ON task_dependency(depends_on_id);
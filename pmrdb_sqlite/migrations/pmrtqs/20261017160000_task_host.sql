@@ -0,0 +1,4 @@
+-- `host` identifies the host, along with its boot, where the process
+-- with the recorded `pid` was started, as the pid is only meaningful
+-- there.
+ALTER TABLE task ADD COLUMN host TEXT;
@@ -0,0 +1,4 @@
+-- `lease_ts` is when the lease of a task held by a remote runner (as
+-- identified by `host`) expires, upon which the task is returned to the
+-- queue unless the runner has renewed the lease.
+ALTER TABLE task ADD COLUMN lease_ts INTEGER;
@@ -0,0 +1,5 @@
+-- `multiple` specifies whether the argument accepts multiple values, which
+-- are repeated with the flag for each value unless `multiple_separator`
+-- is provided, in which case the values are joined with it instead.
+ALTER TABLE task_template_arg ADD COLUMN multiple BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE task_template_arg ADD COLUMN multiple_separator TEXT;
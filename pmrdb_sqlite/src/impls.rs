@@ -107,6 +107,7 @@ mod exposure_file_view_task_template;
 
 mod idgen;
 mod index;
+mod listing;
 
 mod profile;
 
@@ -187,9 +188,14 @@ pub(crate) mod tests {
             created_ts: 1234567890,
             description: Some("".into()),
             long_description: Some("".into()),
+            archived_ts: None,
             exposures: None,
         };
         assert_eq!(entry.entity.into_inner(), answer);
         Ok(())
     }
+
+    mod conformance {
+        test_pmr::conformance_test_suite!(crate::SqliteBackend, "sqlite::memory:");
+    }
 }
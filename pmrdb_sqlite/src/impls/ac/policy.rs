@@ -3,6 +3,7 @@ use futures::TryStreamExt;
 use pmrcore::{
     ac::{
         agent::Agent,
+        group::Group,
         role::Role,
         traits::PolicyBackend,
         user::User,
@@ -17,6 +18,13 @@ use std::{
 
 use crate::SqliteBackend;
 
+fn group_id(agent: &Agent) -> Option<i64> {
+    match agent {
+        Agent::Group(group) => Some(group.id),
+        _ => None,
+    }
+}
+
 async fn grant_role_to_user_sqlite(
     backend: &SqliteBackend,
     user: &User,
@@ -89,6 +97,113 @@ WHERE
     .into())
 }
 
+async fn add_user_to_group_sqlite(
+    backend: &SqliteBackend,
+    group: &Group,
+    user: &User,
+) -> Result<bool, BackendError> {
+    match sqlx::query!(
+        r#"
+INSERT INTO user_group_member (
+    user_group_id,
+    user_id
+)
+VALUES ( ?1, ?2 )
+        "#,
+        group.id,
+        user.id,
+    )
+    .execute(&*backend.pool)
+    .await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            match e.as_database_error() {
+                Some(db_e) if db_e.is_unique_violation() => Ok(false),
+                _ => Err(e)?,
+            }
+        }
+    }
+}
+
+async fn remove_user_from_group_sqlite(
+    backend: &SqliteBackend,
+    group: &Group,
+    user: &User,
+) -> Result<bool, BackendError> {
+    Ok(sqlx::query!(
+        r#"
+DELETE FROM
+    user_group_member
+WHERE
+    user_group_id = ?1 AND
+    user_id = ?2
+        "#,
+        group.id,
+        user.id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected() > 0)
+}
+
+async fn get_groups_for_user_sqlite(
+    backend: &SqliteBackend,
+    user: &User,
+) -> Result<Vec<Group>, BackendError> {
+    Ok(sqlx::query!(
+        r#"
+SELECT
+    user_group.id AS id,
+    user_group.name AS name,
+    user_group.created_ts AS created_ts
+FROM
+    user_group_member
+JOIN
+    user_group ON user_group_member.user_group_id == user_group.id
+WHERE
+    user_group_member.user_id = ?1
+ORDER BY user_group.name
+        "#,
+        user.id,
+    )
+    .map(|row| Group {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_all(&*backend.pool)
+    .await?)
+}
+
+async fn get_group_members_sqlite(
+    backend: &SqliteBackend,
+    group: &Group,
+) -> Result<Vec<User>, BackendError> {
+    Ok(sqlx::query!(
+        r#"
+SELECT
+    'user'.id AS id,
+    'user'.name AS name,
+    'user'.created_ts AS created_ts
+FROM
+    user_group_member
+JOIN
+    'user' ON user_group_member.user_id == 'user'.id
+WHERE
+    user_group_member.user_group_id = ?1
+ORDER BY 'user'.name
+        "#,
+        group.id,
+    )
+    .map(|row| User {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_all(&*backend.pool)
+    .await?)
+}
+
 async fn res_grant_role_to_agent_sqlite(
     backend: &SqliteBackend,
     res: &str,
@@ -96,18 +211,21 @@ async fn res_grant_role_to_agent_sqlite(
     role: Role,
 ) -> Result<bool, BackendError> {
     let user_id: Option<i64> = agent.into();
+    let user_group_id = group_id(agent);
     let role_str = <&'static str>::from(role);
     match sqlx::query!(
         r#"
 INSERT INTO res_grant (
     res,
     user_id,
+    user_group_id,
     role
 )
-VALUES ( ?1, ?2, ?3 )
+VALUES ( ?1, ?2, ?3, ?4 )
         "#,
         res,
         user_id,
+        user_group_id,
         role_str,
     )
     .execute(&*backend.pool)
@@ -130,17 +248,19 @@ async fn res_revoke_role_from_agent_sqlite(
 ) -> Result<bool, BackendError> {
     let role_str = <&'static str>::from(role);
     let user_id: Option<i64> = agent.into();
+    let user_group_id = group_id(agent);
     Ok(sqlx::query!(
         r#"
 DELETE FROM
     res_grant
 WHERE
     res = ?1 AND
-    user_id = ?2 AND
-    role = ?3
+    (user_id = ?2 OR user_group_id = ?3) AND
+    role = ?4
         "#,
         res,
         user_id,
+        user_group_id,
         role_str,
     )
     .execute(&*backend.pool)
@@ -152,18 +272,23 @@ async fn get_res_grants_for_res_sqlite(
     backend: &SqliteBackend,
     res: &str,
 ) -> Result<Vec<(Agent, Vec<Role>)>, BackendError> {
-    let mut result = HashMap::<Option<i64>, (Agent, Vec<Role>)>::new();
+    let mut result = HashMap::<(Option<i64>, Option<i64>), (Agent, Vec<Role>)>::new();
     let mut rows = sqlx::query!(
         r#"
 SELECT
     res_grant.user_id AS user_id,
     user.name AS user_name,
     'user'.created_ts as user_created_ts,
+    res_grant.user_group_id AS user_group_id,
+    user_group.name AS group_name,
+    user_group.created_ts AS group_created_ts,
     res_grant.role AS role
 FROM
     res_grant
 LEFT JOIN
     'user' ON res_grant.user_id == 'user'.id
+LEFT JOIN
+    user_group ON res_grant.user_group_id == user_group.id
 WHERE
     res_grant.res = ?1
         "#,
@@ -172,19 +297,26 @@ WHERE
     .fetch(&*backend.pool);
     while let Some(row) = rows.try_next().await? {
         result
-            .entry(row.user_id)
+            .entry((row.user_id, row.user_group_id))
             .and_modify(|(_, roles)| roles.push(
                 Role::from_str(&row.role).unwrap_or(Role::default()),
             ))
             .or_insert((
-                match row.user_id {
-                    Some(id) => {
+                match (row.user_id, row.user_group_id) {
+                    (Some(id), _) => {
                         Agent::User(User {
                             id,
                             name: row.user_name,
                             created_ts: row.user_created_ts,
                         })
                     },
+                    (_, Some(id)) => {
+                        Agent::Group(Group {
+                            id,
+                            name: row.group_name,
+                            created_ts: row.group_created_ts,
+                        })
+                    },
                     _ => Agent::Anonymous,
                 },
                 vec![Role::from_str(&row.role).unwrap_or(Role::default())],
@@ -200,6 +332,7 @@ async fn get_res_grants_for_agent_sqlite(
 ) -> Result<Vec<(String, Vec<Role>)>, BackendError> {
     let mut result = HashMap::<String, Vec<Role>>::new();
     let user_id: Option<i64> = agent.into();
+    let user_group_id = group_id(agent);
     let mut rows = sqlx::query!(
         r#"
 SELECT
@@ -208,9 +341,11 @@ SELECT
 FROM
     res_grant
 WHERE
-    res_grant.user_id = ?1
+    res_grant.user_id = ?1 OR
+    res_grant.user_group_id = ?2
         "#,
         user_id,
+        user_group_id,
     )
     .fetch(&*backend.pool);
 
@@ -318,6 +453,50 @@ impl PolicyBackend for SqliteBackend {
         ).await
     }
 
+    async fn add_user_to_group(
+        &self,
+        group: &Group,
+        user: &User,
+    ) -> Result<bool, BackendError> {
+        add_user_to_group_sqlite(
+            &self,
+            group,
+            user,
+        ).await
+    }
+
+    async fn remove_user_from_group(
+        &self,
+        group: &Group,
+        user: &User,
+    ) -> Result<bool, BackendError> {
+        remove_user_from_group_sqlite(
+            &self,
+            group,
+            user,
+        ).await
+    }
+
+    async fn get_groups_for_user(
+        &self,
+        user: &User,
+    ) -> Result<Vec<Group>, BackendError> {
+        get_groups_for_user_sqlite(
+            &self,
+            user,
+        ).await
+    }
+
+    async fn get_group_members(
+        &self,
+        group: &Group,
+    ) -> Result<Vec<User>, BackendError> {
+        get_group_members_sqlite(
+            &self,
+            group,
+        ).await
+    }
+
     async fn res_grant_role_to_agent(
         &self,
         res: &str,
@@ -480,4 +659,66 @@ pub(crate) mod testing {
         Ok(())
     }
 
+    #[async_std::test]
+    async fn test_group() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        let user = UserBackend::get_user_by_id(&backend, user_id).await?
+            .expect("user is missing?");
+        let other_id = UserBackend::add_user(&backend, "other_user").await?;
+        let other = UserBackend::get_user_by_id(&backend, other_id).await?
+            .expect("user is missing?");
+        let group_id = UserBackend::add_group(&backend, "lab").await?;
+        let group = UserBackend::get_group_by_id(&backend, group_id).await?
+            .expect("group is missing?");
+
+        assert!(PolicyBackend::add_user_to_group(&backend, &group, &user).await?);
+        assert!(!PolicyBackend::add_user_to_group(&backend, &group, &user).await?);
+        assert!(PolicyBackend::add_user_to_group(&backend, &group, &other).await?);
+        assert_eq!(
+            PolicyBackend::get_groups_for_user(&backend, &user).await?,
+            [group.clone()],
+        );
+        assert_eq!(
+            PolicyBackend::get_group_members(&backend, &group).await?,
+            [other.clone(), user.clone()],
+        );
+
+        // grants to the group are distinct from the grants to its members
+        // and to everyone.
+        let role = Role::Editor;
+        let agent: Agent = group.clone().into();
+        assert!(PolicyBackend::res_grant_role_to_agent(&backend, "/", &agent, role).await?);
+        assert!(!PolicyBackend::res_grant_role_to_agent(&backend, "/", &agent, role).await?);
+        assert!(PolicyBackend::res_grant_role_to_agent(
+            &backend, "/", &Agent::Anonymous, Role::Reader,
+        ).await?);
+        let mut grants = PolicyBackend::get_res_grants_for_res(&backend, "/").await?;
+        grants.sort_unstable();
+        assert_eq!(grants, [
+            (Agent::Anonymous, vec![Role::Reader]),
+            (agent.clone(), vec![role]),
+        ]);
+        assert_eq!(
+            PolicyBackend::get_res_grants_for_agent(&backend, &agent).await?,
+            [("/".to_string(), vec![role])],
+        );
+        assert!(PolicyBackend::get_res_grants_for_agent(&backend, &user.clone().into()).await?
+            .is_empty());
+        assert!(PolicyBackend::res_revoke_role_from_agent(&backend, "/", &agent, role).await?);
+        assert!(!PolicyBackend::res_revoke_role_from_agent(&backend, "/", &agent, role).await?);
+        assert!(PolicyBackend::get_res_grants_for_agent(&backend, &agent).await?.is_empty());
+
+        assert!(PolicyBackend::remove_user_from_group(&backend, &group, &user).await?);
+        assert!(!PolicyBackend::remove_user_from_group(&backend, &group, &user).await?);
+        assert!(PolicyBackend::get_groups_for_user(&backend, &user).await?.is_empty());
+        assert_eq!(
+            PolicyBackend::get_group_members(&backend, &group).await?,
+            [other],
+        );
+        Ok(())
+    }
+
 }
@@ -90,6 +90,33 @@ VALUES (?1, ?2, ?3)
     Ok(())
 }
 
+async fn list_res_with_wf_state_sqlite(
+    backend: &SqliteBackend,
+    wf_state: State,
+    prefix: &str,
+) -> Result<Vec<String>, BackendError> {
+    let state = <&'static str>::from(wf_state);
+    let recs = sqlx::query!(
+        r#"
+SELECT
+    res
+FROM
+    res_wf_state
+WHERE
+    state = ?1
+    AND res LIKE ?2 || '%'
+ORDER BY
+    res
+        "#,
+        state,
+        prefix,
+    )
+        .map(|row| row.res)
+        .fetch_all(&*backend.pool)
+        .await?;
+    Ok(recs)
+}
+
 async fn generate_policy_for_agent_res_sqlite(
     backend: &SqliteBackend,
     agent: &Agent,
@@ -104,8 +131,26 @@ async fn generate_policy_for_agent_res_sqlite(
     // note that this explicitly _ignores_ anonymous agents that may have been
     // assigned roles via `user_role` as the schema currently allows null for
     // user_id, but whether we should keep this remains an open question
-    let (agent_roles, res_grants) = match agent {
+    let (agent_groups, agent_roles, res_grants) = match agent {
         Agent::User(user) => {
+            let agent_groups = sqlx::query!(
+                r#"
+SELECT
+    user_group.name AS name
+FROM
+    user_group_member
+JOIN
+    user_group ON user_group_member.user_group_id == user_group.id
+WHERE
+    user_group_member.user_id = ?1
+ORDER BY user_group.name
+        "#,
+                user.id,
+            )
+            .map(|row| row.name)
+            .fetch_all(&*backend.pool)
+            .await?;
+
             let agent_roles = sqlx::query!(
                 "\
 SELECT
@@ -128,20 +173,33 @@ WHERE
             .fetch_all(&*backend.pool)
             .await?;
 
+            // the grants to the groups the user is a member of are
+            // included alongside the grants to the user and to everyone.
             let res_grants = sqlx::query!(
                 r#"
 SELECT
     res_grant.res as res,
     'user'.name as user_name,
+    user_group.name as group_name,
     res_grant.role AS role
 FROM
     res_grant
 LEFT JOIN
     'user' ON res_grant.user_id == 'user'.id
+LEFT JOIN
+    user_group ON res_grant.user_group_id == user_group.id
 WHERE
     (res_grant.res = ?1 OR res_grant.res = "/*")
     AND
-    (res_grant.user_id == ?2 OR res_grant.user_id is NULL)
+    (
+        res_grant.user_id == ?2
+        OR
+        res_grant.user_group_id IN (
+            SELECT user_group_id FROM user_group_member WHERE user_id = ?2
+        )
+        OR
+        (res_grant.user_id is NULL AND res_grant.user_group_id is NULL)
+    )
         "#,
                 res_str,
                 user.id,
@@ -149,12 +207,47 @@ WHERE
             .map(|row| ResGrant {
                 res: row.res,
                 agent: row.user_name,
+                group: row.group_name,
                 role: Role::from_str(&row.role).unwrap_or_default(),
             })
             .fetch_all(&*backend.pool)
             .await?;
 
-            (agent_roles, res_grants)
+            (agent_groups, agent_roles, res_grants)
+        }
+        Agent::Group(group) => {
+            let agent_roles = vec![];
+            let res_grants = sqlx::query!(
+                r#"
+SELECT
+    res_grant.res as res,
+    user_group.name as group_name,
+    res_grant.role AS role
+FROM
+    res_grant
+LEFT JOIN
+    user_group ON res_grant.user_group_id == user_group.id
+WHERE
+    (res_grant.res = ?1 OR res_grant.res = "/*")
+    AND
+    (
+        res_grant.user_group_id == ?2
+        OR
+        (res_grant.user_id is NULL AND res_grant.user_group_id is NULL)
+    )
+        "#,
+                res_str,
+                group.id,
+            )
+            .map(|row| ResGrant {
+                res: row.res,
+                agent: None,
+                group: row.group_name,
+                role: Role::from_str(&row.role).unwrap_or_default(),
+            })
+            .fetch_all(&*backend.pool)
+            .await?;
+            (vec![group.name.clone()], agent_roles, res_grants)
         }
         Agent::Anonymous => {
             let agent_roles = vec![];
@@ -172,17 +265,20 @@ WHERE
     (res = ?1 OR res = "/*")
     AND
     res_grant.user_id is NULL
+    AND
+    res_grant.user_group_id is NULL
         "#,
                 res_str,
             )
             .map(|row| ResGrant {
                 res: row.res,
                 agent: row.user_name,
+                group: None,
                 role: Role::from_str(&row.role).unwrap_or_default(),
             })
             .fetch_all(&*backend.pool)
             .await?;
-            (agent_roles, res_grants)
+            (vec![], agent_roles, res_grants)
         }
     };
 
@@ -211,6 +307,7 @@ WHERE
     Ok(Policy {
         agent,
         resource,
+        agent_groups,
         agent_roles,
         res_grants,
         role_permits,
@@ -255,6 +352,18 @@ impl ResourceBackend for SqliteBackend {
         ).await
     }
 
+    async fn list_res_with_wf_state(
+        &self,
+        wf_state: State,
+        prefix: &str,
+    ) -> Result<Vec<String>, BackendError> {
+        list_res_with_wf_state_sqlite(
+            &self,
+            wf_state,
+            prefix,
+        ).await
+    }
+
     async fn generate_policy_for_agent_res(
         &self,
         agent: &Agent,
@@ -299,6 +408,7 @@ pub(crate) mod testing {
         assert_eq!(policy, Policy {
             agent: Agent::Anonymous,
             resource: "/".to_string(),
+            agent_groups: vec![],
             agent_roles: vec![],
             res_grants: vec![],
             role_permits: vec![],
@@ -315,6 +425,7 @@ pub(crate) mod testing {
         assert_eq!(policy, Policy {
             agent: Agent::Anonymous,
             resource: "/".to_string(),
+            agent_groups: vec![],
             agent_roles: vec![],
             res_grants: vec![],
             role_permits: vec![],
@@ -408,6 +519,7 @@ pub(crate) mod testing {
         assert_eq!(policy, Policy {
             agent: agent.clone(),
             resource: "/".to_string(),
+            agent_groups: vec![],
             agent_roles: vec![],
             res_grants: serde_json::from_str(r#"[{"res": "/", "user": null, "role": "Reader"}]"#)?,
             role_permits: vec![],
@@ -536,4 +648,95 @@ pub(crate) mod testing {
         Ok(())
     }
 
+    #[async_std::test]
+    async fn groups() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+
+        let alice_id = UserBackend::add_user(&backend, "alice").await?;
+        let alice = UserBackend::get_user_by_id(&backend, alice_id).await?
+            .expect("user is missing?");
+        let bob_id = UserBackend::add_user(&backend, "bob").await?;
+        let bob = UserBackend::get_user_by_id(&backend, bob_id).await?
+            .expect("user is missing?");
+        let group_id = UserBackend::add_group(&backend, "lab").await?;
+        let group = UserBackend::get_group_by_id(&backend, group_id).await?
+            .expect("group is missing?");
+        PolicyBackend::add_user_to_group(&backend, &group, &alice).await?;
+        PolicyBackend::res_grant_role_to_agent(
+            &backend,
+            "/item/1",
+            &group.clone().into(),
+            Role::Editor,
+        ).await?;
+        PolicyBackend::res_grant_role_to_agent(
+            &backend,
+            "/item/1",
+            &Agent::Anonymous,
+            Role::Reader,
+        ).await?;
+
+        // the member of the group has the grant made to the group
+        let mut policy = ResourceBackend::generate_policy_for_agent_res(
+            &backend,
+            &alice.into(),
+            "/item/1".into(),
+        ).await?;
+        policy.res_grants.sort_unstable();
+        assert_eq!(policy, serde_json::from_str(r#"{
+            "agent": {
+                "User": {
+                    "id": 1,
+                    "name": "alice",
+                    "created_ts": 1234567890
+                }
+            },
+            "resource": "/item/1",
+            "agent_groups": ["lab"],
+            "agent_roles": [
+            ],
+            "res_grants": [
+                {"res": "/item/1", "agent": null, "role": "Reader"},
+                {"res": "/item/1", "agent": null, "group": "lab", "role": "Editor"}
+            ],
+            "role_permits": [
+            ]
+        }"#)?);
+
+        // whereas the grant to the group is not available to others, nor
+        // to the anonymous agent
+        let policy = ResourceBackend::generate_policy_for_agent_res(
+            &backend,
+            &bob.into(),
+            "/item/1".into(),
+        ).await?;
+        assert!(policy.agent_groups.is_empty());
+        assert_eq!(policy.res_grants, serde_json::from_str::<Vec<_>>(r#"[
+            {"res": "/item/1", "agent": null, "role": "Reader"}
+        ]"#)?);
+        let policy = ResourceBackend::generate_policy_for_agent_res(
+            &backend,
+            &Agent::Anonymous,
+            "/item/1".into(),
+        ).await?;
+        assert_eq!(policy.res_grants, serde_json::from_str::<Vec<_>>(r#"[
+            {"res": "/item/1", "agent": null, "role": "Reader"}
+        ]"#)?);
+
+        let mut policy = ResourceBackend::generate_policy_for_agent_res(
+            &backend,
+            &group.into(),
+            "/item/1".into(),
+        ).await?;
+        policy.res_grants.sort_unstable();
+        assert_eq!(policy.agent_groups, ["lab"]);
+        assert_eq!(policy.res_grants, serde_json::from_str::<Vec<_>>(r#"[
+            {"res": "/item/1", "agent": null, "role": "Reader"},
+            {"res": "/item/1", "agent": null, "group": "lab", "role": "Editor"}
+        ]"#)?);
+
+        Ok(())
+    }
+
 }
@@ -28,9 +28,10 @@ INSERT INTO user_session (
     user_id,
     origin,
     created_ts,
-    last_active_ts
+    last_active_ts,
+    user_token_id
 )
-VALUES ( ?1, ?2, ?3, ?4, ?5 )
+VALUES ( ?1, ?2, ?3, ?4, ?5, ?7 )
 ON CONFLICT(token)
 DO UPDATE SET
     origin = ?3,
@@ -42,6 +43,7 @@ DO UPDATE SET
         session.created_ts,
         session.last_active_ts,
         last_active_ts,
+        session.user_token_id,
     )
     .execute(&*backend.pool)
     .await?;
@@ -59,7 +61,8 @@ SELECT
     user_id,
     origin,
     created_ts,
-    last_active_ts
+    last_active_ts,
+    user_token_id
 FROM user_session
 WHERE token = ?1
 "#,
@@ -71,6 +74,7 @@ WHERE token = ?1
             origin: row.origin,
             created_ts: row.created_ts,
             last_active_ts: row.last_active_ts,
+            user_token_id: row.user_token_id,
         })
         .fetch_one(&*backend.pool)
         .await?
@@ -104,7 +108,8 @@ SELECT
     user_id,
     origin,
     created_ts,
-    last_active_ts
+    last_active_ts,
+    user_token_id
 FROM user_session
 WHERE user_id = ?1
 "#,
@@ -116,6 +121,7 @@ WHERE user_id = ?1
             origin: row.origin,
             created_ts: row.created_ts,
             last_active_ts: row.last_active_ts,
+            user_token_id: row.user_token_id,
         })
         .fetch_all(&*backend.pool)
         .await?
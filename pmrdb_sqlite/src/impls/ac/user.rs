@@ -1,8 +1,16 @@
 use async_trait::async_trait;
 use pmrcore::{
     ac::{
+        group::Group,
         traits::UserBackend,
-        user::User,
+        user::{
+            User,
+            UserEmail,
+            UserEmailBindReq,
+            UserIdentity,
+            UserPasswordResetReq,
+            UserToken,
+        },
     },
     error::BackendError,
 };
@@ -151,118 +159,1481 @@ WHERE
     Ok(())
 }
 
+async fn add_user_email_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+    email: &str,
+) -> Result<i64, BackendError> {
+    let id = sqlx::query!(
+        r#"
+INSERT INTO user_email (
+    user_id,
+    email
+)
+VALUES ( ?1, ?2 )
+        "#,
+        user_id,
+        email,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .last_insert_rowid();
+    Ok(id)
+}
 
-#[async_trait]
-impl UserBackend for SqliteBackend {
-    async fn add_user(
-        &self,
-        name: &str,
-    ) -> Result<i64, BackendError> {
-        add_user_sqlite(
-            &self,
-            name,
-        ).await
-    }
+async fn get_user_emails_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+) -> Result<Vec<UserEmail>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    user_id,
+    email
+FROM
+    user_email
+WHERE
+    user_id = ?1
+ORDER BY id
+        "#,
+        user_id,
+    )
+    .map(|row| UserEmail {
+        id: row.id,
+        user_id: row.user_id,
+        email: row.email,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
 
-    async fn get_user_by_id(
-        &self,
-        id: i64,
-    ) -> Result<Option<User>, BackendError> {
-        get_user_by_id_sqlite(
-            &self,
-            id,
-        ).await
-    }
+async fn get_user_by_email_sqlite(
+    backend: &SqliteBackend,
+    email: &str,
+) -> Result<Option<User>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    'user'.id,
+    'user'.name,
+    'user'.created_ts
+FROM
+    'user'
+JOIN
+    user_email ON 'user'.id = user_email.user_id
+WHERE
+    user_email.email = ?1
+        "#,
+        email,
+    )
+    .map(|row| User {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
 
-    async fn get_user_by_name(
-        &self,
-        name: &str,
-    ) -> Result<Option<User>, BackendError> {
-        get_user_by_name_sqlite(
-            &self,
-            name,
-        ).await
-    }
+async fn add_user_email_bindreq_sqlite(
+    backend: &SqliteBackend,
+    email: &str,
+    origin_user_id: Option<i64>,
+    origin: &str,
+    token: &str,
+) -> Result<i64, BackendError> {
+    let ts = Utc::now().timestamp();
+    let id = sqlx::query!(
+        r#"
+INSERT INTO user_email_bindreq (
+    email,
+    origin_user_id,
+    origin,
+    token,
+    created_ts,
+    rejected
+)
+VALUES ( ?1, ?2, ?3, ?4, ?5, FALSE )
+        "#,
+        email,
+        origin_user_id,
+        origin,
+        token,
+        ts,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .last_insert_rowid();
+    Ok(id)
+}
 
-    async fn store_user_password(
-        &self,
-        user_id: i64,
-        password: &str,
-    ) -> Result<i64, BackendError> {
-        store_user_password_sqlite(
-            &self,
-            user_id,
-            password,
-        ).await
-    }
+async fn get_user_email_bindreq_sqlite(
+    backend: &SqliteBackend,
+    token: &str,
+) -> Result<Option<UserEmailBindReq>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    email,
+    origin_user_id,
+    origin,
+    token,
+    created_ts,
+    rejected
+FROM
+    user_email_bindreq
+WHERE
+    token = ?1
+        "#,
+        token,
+    )
+    .map(|row| UserEmailBindReq {
+        id: row.id,
+        email: row.email,
+        origin_user_id: row.origin_user_id,
+        origin: row.origin,
+        token: row.token,
+        created_ts: row.created_ts,
+        rejected: row.rejected.unwrap_or(false),
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
 
-    async fn get_user_password(
-        &self,
-        user_id: i64,
-    ) -> Result<String, BackendError> {
-        get_user_password_sqlite(
-            &self,
-            user_id,
-        ).await
-    }
+async fn count_user_email_bindreqs_by_origin_sqlite(
+    backend: &SqliteBackend,
+    origin: &str,
+    since_ts: i64,
+) -> Result<i64, BackendError> {
+    let result = sqlx::query!(r#"
+SELECT
+    COUNT(id) AS count
+FROM
+    user_email_bindreq
+WHERE
+    origin = ?1 AND
+    created_ts >= ?2
+        "#,
+        origin,
+        since_ts,
+    )
+    .map(|row| row.count)
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(result)
+}
 
-    async fn purge_user_passwords(
-        &self,
-        user_id: i64,
-    ) -> Result<(), BackendError> {
-        purge_user_passwords_sqlite(
-            &self,
-            user_id,
-        ).await
-    }
+async fn consume_user_email_bindreq_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let result = sqlx::query!(r#"
+UPDATE
+    user_email_bindreq
+SET
+    token = NULL
+WHERE
+    id = ?1 AND
+    token IS NOT NULL
+        "#,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
 }
 
-#[cfg(test)]
-pub(crate) mod testing {
-    use pmrcore::{
-        platform::PlatformConnector as _,
-        ac::{
-            traits::UserBackend,
-            user::User,
-        },
-    };
-    use crate::SqliteBackend;
-    use test_pmr::chrono::set_timestamp;
+async fn reject_user_email_bindreq_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+) -> Result<(), BackendError> {
+    sqlx::query!(r#"
+UPDATE
+    user_email_bindreq
+SET
+    token = NULL,
+    rejected = TRUE
+WHERE
+    id = ?1
+        "#,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(())
+}
 
-    #[async_std::test]
-    async fn test_basic() -> anyhow::Result<()> {
-        let backend = SqliteBackend::ac("sqlite::memory:".into())
-            .await
-            .map_err(anyhow::Error::from_boxed)?;
-        let user_id = UserBackend::add_user(&backend, "test_user").await?;
-        let user = UserBackend::get_user_by_id(&backend, user_id).await?
-            .expect("user is missing?");
-        assert_eq!(
-            user,
-            User {
-                id: 1,
-                name: "test_user".to_string(),
-                created_ts: 1234567890,
-            },
-        );
 
-        // note that these tests **ONLY** test for the storage and retrieval of passwords,
-        // and has absolutely nothing to do with the security of the incoming passwords
-        // before getting to this API call.
-        set_timestamp(0);
-        UserBackend::store_user_password(&backend, user_id, "password1").await?;
-        set_timestamp(1234567899);
-        UserBackend::store_user_password(&backend, user_id, "password2").await?;
-        assert_eq!(UserBackend::get_user_password(&backend, user_id).await?, "password2");
+async fn add_user_password_resetreq_sqlite(
+    backend: &SqliteBackend,
+    user_email: &UserEmail,
+    origin: &str,
+    token: &str,
+) -> Result<i64, BackendError> {
+    let ts = Utc::now().timestamp();
+    let id = sqlx::query!(
+        r#"
+INSERT INTO user_password_resetreq (
+    user_id,
+    user_email_id,
+    origin,
+    token,
+    created_ts
+)
+VALUES ( ?1, ?2, ?3, ?4, ?5 )
+        "#,
+        user_email.user_id,
+        user_email.id,
+        origin,
+        token,
+        ts,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .last_insert_rowid();
+    Ok(id)
+}
 
-        UserBackend::purge_user_passwords(&backend, user_id).await?;
-        assert!(UserBackend::get_user_password(&backend, user_id).await.is_err());
+async fn get_user_password_resetreq_sqlite(
+    backend: &SqliteBackend,
+    token: &str,
+) -> Result<Option<UserPasswordResetReq>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    user_id,
+    user_email_id,
+    origin,
+    token,
+    created_ts
+FROM
+    user_password_resetreq
+WHERE
+    token = ?1
+        "#,
+        token,
+    )
+    .map(|row| UserPasswordResetReq {
+        id: row.id,
+        user_id: row.user_id,
+        user_email_id: row.user_email_id,
+        origin: row.origin,
+        token: row.token,
+        created_ts: row.created_ts,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
 
-        UserBackend::store_user_password(&backend, user_id, "password1").await?;
-        UserBackend::store_user_password(&backend, user_id, "password2").await?;
-        UserBackend::store_user_password(&backend, user_id, "password3").await?;
-        assert_eq!(UserBackend::get_user_password(&backend, user_id).await?, "password3");
+async fn count_user_password_resetreqs_by_origin_sqlite(
+    backend: &SqliteBackend,
+    origin: &str,
+    since_ts: i64,
+) -> Result<i64, BackendError> {
+    let result = sqlx::query!(r#"
+SELECT
+    COUNT(id) AS count
+FROM
+    user_password_resetreq
+WHERE
+    origin = ?1 AND
+    created_ts >= ?2
+        "#,
+        origin,
+        since_ts,
+    )
+    .map(|row| row.count)
+    .fetch_one(&*backend.pool)
+    .await?;
+    Ok(result)
+}
 
+async fn consume_user_password_resetreq_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let result = sqlx::query!(r#"
+UPDATE
+    user_password_resetreq
+SET
+    token = NULL
+WHERE
+    id = ?1 AND
+    token IS NOT NULL
+        "#,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+async fn consume_user_password_resetreqs_for_user_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+) -> Result<u64, BackendError> {
+    let result = sqlx::query!(r#"
+UPDATE
+    user_password_resetreq
+SET
+    token = NULL
+WHERE
+    user_id = ?1 AND
+    token IS NOT NULL
+        "#,
+        user_id,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+
+async fn add_user_identity_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+    provider: &str,
+    subject: &str,
+) -> Result<i64, BackendError> {
+    let ts = Utc::now().timestamp();
+    let id = sqlx::query!(
+        r#"
+INSERT INTO user_identity (
+    user_id,
+    provider,
+    subject,
+    created_ts
+)
+VALUES ( ?1, ?2, ?3, ?4 )
+        "#,
+        user_id,
+        provider,
+        subject,
+        ts,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .last_insert_rowid();
+    Ok(id)
+}
+
+async fn get_user_by_identity_sqlite(
+    backend: &SqliteBackend,
+    provider: &str,
+    subject: &str,
+) -> Result<Option<User>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    'user'.id,
+    'user'.name,
+    'user'.created_ts
+FROM
+    'user'
+JOIN
+    user_identity ON 'user'.id = user_identity.user_id
+WHERE
+    user_identity.provider = ?1 AND
+    user_identity.subject = ?2
+        "#,
+        provider,
+        subject,
+    )
+    .map(|row| User {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
+
+async fn get_user_identities_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+) -> Result<Vec<UserIdentity>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    user_id,
+    provider,
+    subject,
+    created_ts
+FROM
+    user_identity
+WHERE
+    user_id = ?1
+ORDER BY id
+        "#,
+        user_id,
+    )
+    .map(|row| UserIdentity {
+        id: row.id,
+        user_id: row.user_id,
+        provider: row.provider,
+        subject: row.subject,
+        created_ts: row.created_ts,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
+
+async fn remove_user_identity_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let result = sqlx::query!(r#"
+DELETE FROM
+    user_identity
+WHERE
+    id = ?1 AND
+    user_id = ?2
+        "#,
+        id,
+        user_id,
+    )
+    .execute(&*backend.pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+async fn add_user_token_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+    name: &str,
+    token: &str,
+    scopes: &[String],
+    expires_ts: i64,
+) -> Result<i64, BackendError> {
+    let ts = Utc::now().timestamp();
+    let mut tx = backend.pool.begin().await?;
+    let id = sqlx::query!(
+        r#"
+INSERT INTO user_token (
+    user_id,
+    name,
+    token,
+    created_ts,
+    expires_ts
+)
+VALUES ( ?1, ?2, ?3, ?4, ?5 )
+        "#,
+        user_id,
+        name,
+        token,
+        ts,
+        expires_ts,
+    )
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+    for action in scopes {
+        sqlx::query!(
+            r#"
+INSERT INTO user_token_scope (
+    user_token_id,
+    action
+)
+VALUES ( ?1, ?2 )
+ON CONFLICT DO NOTHING
+            "#,
+            id,
+            action,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(id)
+}
+
+async fn get_user_token_scopes_sqlite(
+    backend: &SqliteBackend,
+    user_token_id: i64,
+) -> Result<Vec<String>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    action
+FROM
+    user_token_scope
+WHERE
+    user_token_id = ?1
+ORDER BY action
+        "#,
+        user_token_id,
+    )
+    .map(|row| row.action)
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
+
+async fn get_user_token_sqlite(
+    backend: &SqliteBackend,
+    token: &str,
+) -> Result<Option<UserToken>, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    user_id,
+    name,
+    created_ts,
+    expires_ts
+FROM
+    user_token
+WHERE
+    token = ?1
+        "#,
+        token,
+    )
+    .map(|row| UserToken {
+        id: row.id,
+        user_id: row.user_id,
+        name: row.name,
+        scopes: Vec::new(),
+        created_ts: row.created_ts,
+        expires_ts: row.expires_ts,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(match rec {
+        Some(rec) => Some(UserToken {
+            scopes: get_user_token_scopes_sqlite(backend, rec.id).await?,
+            .. rec
+        }),
+        None => None,
+    })
+}
+
+async fn get_user_token_by_id_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+) -> Result<Option<UserToken>, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    user_id,
+    name,
+    created_ts,
+    expires_ts
+FROM
+    user_token
+WHERE
+    id = ?1
+        "#,
+        id,
+    )
+    .map(|row| UserToken {
+        id: row.id,
+        user_id: row.user_id,
+        name: row.name,
+        scopes: Vec::new(),
+        created_ts: row.created_ts,
+        expires_ts: row.expires_ts,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(match rec {
+        Some(rec) => Some(UserToken {
+            scopes: get_user_token_scopes_sqlite(backend, rec.id).await?,
+            .. rec
+        }),
+        None => None,
+    })
+}
+
+async fn get_user_tokens_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+) -> Result<Vec<UserToken>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    user_id,
+    name,
+    created_ts,
+    expires_ts
+FROM
+    user_token
+WHERE
+    user_id = ?1
+ORDER BY id
+        "#,
+        user_id,
+    )
+    .map(|row| UserToken {
+        id: row.id,
+        user_id: row.user_id,
+        name: row.name,
+        scopes: Vec::new(),
+        created_ts: row.created_ts,
+        expires_ts: row.expires_ts,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    let mut result = Vec::with_capacity(recs.len());
+    for rec in recs {
+        result.push(UserToken {
+            scopes: get_user_token_scopes_sqlite(backend, rec.id).await?,
+            .. rec
+        });
+    }
+    Ok(result)
+}
+
+async fn remove_user_token_sqlite(
+    backend: &SqliteBackend,
+    user_id: i64,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let mut tx = backend.pool.begin().await?;
+    let found = sqlx::query!(r#"
+SELECT
+    id
+FROM
+    user_token
+WHERE
+    id = ?1 AND
+    user_id = ?2
+        "#,
+        id,
+        user_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .is_some();
+    if !found {
+        return Ok(false);
+    }
+    sqlx::query!(r#"
+DELETE FROM
+    user_session
+WHERE
+    user_token_id = ?1
+        "#,
+        id,
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(r#"
+DELETE FROM
+    user_token_scope
+WHERE
+    user_token_id = ?1
+        "#,
+        id,
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(r#"
+DELETE FROM
+    user_token
+WHERE
+    id = ?1
+        "#,
+        id,
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+async fn add_group_sqlite(
+    backend: &SqliteBackend,
+    name: &str,
+) -> Result<i64, BackendError> {
+    let ts = Utc::now().timestamp();
+    let id = sqlx::query!(
+        r#"
+INSERT INTO user_group (
+    name,
+    created_ts
+)
+VALUES ( ?1, ?2 )
+        "#,
+        name,
+        ts,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .last_insert_rowid();
+    Ok(id)
+}
+
+async fn get_group_by_id_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+) -> Result<Option<Group>, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    name,
+    created_ts
+FROM
+    user_group
+WHERE
+    id = ?1
+        "#,
+        id,
+    )
+    .map(|row| Group {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+async fn get_group_by_name_sqlite(
+    backend: &SqliteBackend,
+    name: &str,
+) -> Result<Option<Group>, BackendError> {
+    let rec = sqlx::query!(r#"
+SELECT
+    id,
+    name,
+    created_ts
+FROM
+    user_group
+WHERE
+    name = ?1
+        "#,
+        name,
+    )
+    .map(|row| Group {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_optional(&*backend.pool)
+    .await?;
+    Ok(rec)
+}
+
+async fn get_groups_sqlite(
+    backend: &SqliteBackend,
+) -> Result<Vec<Group>, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    name,
+    created_ts
+FROM
+    user_group
+ORDER BY name
+        "#,
+    )
+    .map(|row| Group {
+        id: row.id,
+        name: row.name,
+        created_ts: row.created_ts,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs)
+}
+
+#[async_trait]
+impl UserBackend for SqliteBackend {
+    async fn add_user(
+        &self,
+        name: &str,
+    ) -> Result<i64, BackendError> {
+        add_user_sqlite(
+            &self,
+            name,
+        ).await
+    }
+
+    async fn get_user_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<User>, BackendError> {
+        get_user_by_id_sqlite(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn get_user_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<User>, BackendError> {
+        get_user_by_name_sqlite(
+            &self,
+            name,
+        ).await
+    }
+
+    async fn store_user_password(
+        &self,
+        user_id: i64,
+        password: &str,
+    ) -> Result<i64, BackendError> {
+        store_user_password_sqlite(
+            &self,
+            user_id,
+            password,
+        ).await
+    }
+
+    async fn get_user_password(
+        &self,
+        user_id: i64,
+    ) -> Result<String, BackendError> {
+        get_user_password_sqlite(
+            &self,
+            user_id,
+        ).await
+    }
+
+    async fn purge_user_passwords(
+        &self,
+        user_id: i64,
+    ) -> Result<(), BackendError> {
+        purge_user_passwords_sqlite(
+            &self,
+            user_id,
+        ).await
+    }
+
+    async fn add_user_email(
+        &self,
+        user_id: i64,
+        email: &str,
+    ) -> Result<i64, BackendError> {
+        add_user_email_sqlite(
+            &self,
+            user_id,
+            email,
+        ).await
+    }
+
+    async fn get_user_emails(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserEmail>, BackendError> {
+        get_user_emails_sqlite(
+            &self,
+            user_id,
+        ).await
+    }
+
+    async fn get_user_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Option<User>, BackendError> {
+        get_user_by_email_sqlite(
+            &self,
+            email,
+        ).await
+    }
+
+    async fn add_user_email_bindreq(
+        &self,
+        email: &str,
+        origin_user_id: Option<i64>,
+        origin: &str,
+        token: &str,
+    ) -> Result<i64, BackendError> {
+        add_user_email_bindreq_sqlite(
+            &self,
+            email,
+            origin_user_id,
+            origin,
+            token,
+        ).await
+    }
+
+    async fn get_user_email_bindreq(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserEmailBindReq>, BackendError> {
+        get_user_email_bindreq_sqlite(
+            &self,
+            token,
+        ).await
+    }
+
+    async fn count_user_email_bindreqs_by_origin(
+        &self,
+        origin: &str,
+        since_ts: i64,
+    ) -> Result<i64, BackendError> {
+        count_user_email_bindreqs_by_origin_sqlite(
+            &self,
+            origin,
+            since_ts,
+        ).await
+    }
+
+    async fn consume_user_email_bindreq(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        consume_user_email_bindreq_sqlite(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn reject_user_email_bindreq(
+        &self,
+        id: i64,
+    ) -> Result<(), BackendError> {
+        reject_user_email_bindreq_sqlite(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn add_user_password_resetreq(
+        &self,
+        user_email: &UserEmail,
+        origin: &str,
+        token: &str,
+    ) -> Result<i64, BackendError> {
+        add_user_password_resetreq_sqlite(
+            &self,
+            user_email,
+            origin,
+            token,
+        ).await
+    }
+
+    async fn get_user_password_resetreq(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserPasswordResetReq>, BackendError> {
+        get_user_password_resetreq_sqlite(
+            &self,
+            token,
+        ).await
+    }
+
+    async fn count_user_password_resetreqs_by_origin(
+        &self,
+        origin: &str,
+        since_ts: i64,
+    ) -> Result<i64, BackendError> {
+        count_user_password_resetreqs_by_origin_sqlite(
+            &self,
+            origin,
+            since_ts,
+        ).await
+    }
+
+    async fn consume_user_password_resetreq(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        consume_user_password_resetreq_sqlite(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn consume_user_password_resetreqs_for_user(
+        &self,
+        user_id: i64,
+    ) -> Result<u64, BackendError> {
+        consume_user_password_resetreqs_for_user_sqlite(
+            &self,
+            user_id,
+        ).await
+    }
+
+    async fn add_user_identity(
+        &self,
+        user_id: i64,
+        provider: &str,
+        subject: &str,
+    ) -> Result<i64, BackendError> {
+        add_user_identity_sqlite(
+            &self,
+            user_id,
+            provider,
+            subject,
+        ).await
+    }
+
+    async fn get_user_by_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<User>, BackendError> {
+        get_user_by_identity_sqlite(
+            &self,
+            provider,
+            subject,
+        ).await
+    }
+
+    async fn get_user_identities(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserIdentity>, BackendError> {
+        get_user_identities_sqlite(
+            &self,
+            user_id,
+        ).await
+    }
+
+    async fn remove_user_identity(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        remove_user_identity_sqlite(
+            &self,
+            user_id,
+            id,
+        ).await
+    }
+    async fn add_user_token(
+        &self,
+        user_id: i64,
+        name: &str,
+        token: &str,
+        scopes: &[String],
+        expires_ts: i64,
+    ) -> Result<i64, BackendError> {
+        add_user_token_sqlite(
+            &self,
+            user_id,
+            name,
+            token,
+            scopes,
+            expires_ts,
+        ).await
+    }
+
+    async fn get_user_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserToken>, BackendError> {
+        get_user_token_sqlite(
+            &self,
+            token,
+        ).await
+    }
+
+    async fn get_user_token_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<UserToken>, BackendError> {
+        get_user_token_by_id_sqlite(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn get_user_tokens(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserToken>, BackendError> {
+        get_user_tokens_sqlite(
+            &self,
+            user_id,
+        ).await
+    }
+
+    async fn remove_user_token(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        remove_user_token_sqlite(
+            &self,
+            user_id,
+            id,
+        ).await
+    }
+
+    async fn add_group(
+        &self,
+        name: &str,
+    ) -> Result<i64, BackendError> {
+        add_group_sqlite(
+            &self,
+            name,
+        ).await
+    }
+
+    async fn get_group_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<Group>, BackendError> {
+        get_group_by_id_sqlite(
+            &self,
+            id,
+        ).await
+    }
+
+    async fn get_group_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<Group>, BackendError> {
+        get_group_by_name_sqlite(
+            &self,
+            name,
+        ).await
+    }
+
+    async fn get_groups(
+        &self,
+    ) -> Result<Vec<Group>, BackendError> {
+        get_groups_sqlite(
+            &self,
+        ).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use pmrcore::{
+        platform::PlatformConnector as _,
+        ac::{
+            group::Group,
+            session::{
+                Session,
+                SessionToken,
+            },
+            traits::{
+                SessionBackend,
+                UserBackend,
+            },
+            user::{
+                User,
+                UserEmail,
+                UserIdentity,
+                UserToken,
+            },
+        },
+    };
+    use crate::SqliteBackend;
+    use std::str::FromStr;
+    use test_pmr::chrono::set_timestamp;
+
+    #[async_std::test]
+    async fn test_basic() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        let user = UserBackend::get_user_by_id(&backend, user_id).await?
+            .expect("user is missing?");
+        assert_eq!(
+            user,
+            User {
+                id: 1,
+                name: "test_user".to_string(),
+                created_ts: 1234567890,
+            },
+        );
+
+        // note that these tests **ONLY** test for the storage and retrieval of passwords,
+        // and has absolutely nothing to do with the security of the incoming passwords
+        // before getting to this API call.
+        set_timestamp(0);
+        UserBackend::store_user_password(&backend, user_id, "password1").await?;
+        set_timestamp(1234567899);
+        UserBackend::store_user_password(&backend, user_id, "password2").await?;
+        assert_eq!(UserBackend::get_user_password(&backend, user_id).await?, "password2");
+
+        UserBackend::purge_user_passwords(&backend, user_id).await?;
+        assert!(UserBackend::get_user_password(&backend, user_id).await.is_err());
+
+        UserBackend::store_user_password(&backend, user_id, "password1").await?;
+        UserBackend::store_user_password(&backend, user_id, "password2").await?;
+        UserBackend::store_user_password(&backend, user_id, "password3").await?;
+        assert_eq!(UserBackend::get_user_password(&backend, user_id).await?, "password3");
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_user_email() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        assert!(UserBackend::get_user_by_email(&backend, "test@example.com").await?
+            .is_none());
+        let id = UserBackend::add_user_email(&backend, user_id, "test@example.com").await?;
+        assert_eq!(
+            UserBackend::get_user_emails(&backend, user_id).await?,
+            [UserEmail {
+                id,
+                user_id,
+                email: "test@example.com".to_string(),
+            }],
+        );
+        assert_eq!(
+            UserBackend::get_user_by_email(&backend, "test@example.com").await?
+                .expect("user is missing?")
+                .id,
+            user_id,
+        );
+        // the email address may only be bound once.
+        let other_id = UserBackend::add_user(&backend, "other_user").await?;
+        assert!(UserBackend::add_user_email(&backend, other_id, "test@example.com").await
+            .is_err());
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_user_email_bindreq() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        set_timestamp(1000);
+        let id = UserBackend::add_user_email_bindreq(
+            &backend, "new@example.com", None, "127.0.0.1", "token1",
+        ).await?;
+        set_timestamp(2000);
+        UserBackend::add_user_email_bindreq(
+            &backend, "new@example.com", Some(1), "127.0.0.1", "token2",
+        ).await?;
+        UserBackend::add_user_email_bindreq(
+            &backend, "new@example.com", None, "127.0.0.2", "token3",
+        ).await?;
+
+        let bindreq = UserBackend::get_user_email_bindreq(&backend, "token1").await?
+            .expect("bindreq is missing?");
+        assert_eq!(bindreq.id, id);
+        assert_eq!(bindreq.email, "new@example.com");
+        assert_eq!(bindreq.origin_user_id, None);
+        assert_eq!(bindreq.origin.as_deref(), Some("127.0.0.1"));
+        assert_eq!(bindreq.created_ts, 1000);
+        assert!(!bindreq.rejected);
+        let bindreq = UserBackend::get_user_email_bindreq(&backend, "token2").await?
+            .expect("bindreq is missing?");
+        assert_eq!(bindreq.origin_user_id, Some(1));
+        assert!(UserBackend::get_user_email_bindreq(&backend, "token4").await?
+            .is_none());
+
+        assert_eq!(UserBackend::count_user_email_bindreqs_by_origin(
+            &backend, "127.0.0.1", 0).await?, 2);
+        assert_eq!(UserBackend::count_user_email_bindreqs_by_origin(
+            &backend, "127.0.0.1", 1500).await?, 1);
+        assert_eq!(UserBackend::count_user_email_bindreqs_by_origin(
+            &backend, "127.0.0.2", 0).await?, 1);
+
+        assert!(UserBackend::consume_user_email_bindreq(&backend, id).await?);
+        assert!(!UserBackend::consume_user_email_bindreq(&backend, id).await?);
+        assert!(UserBackend::get_user_email_bindreq(&backend, "token1").await?
+            .is_none());
+
+        UserBackend::reject_user_email_bindreq(&backend, bindreq.id).await?;
+        assert!(UserBackend::get_user_email_bindreq(&backend, "token2").await?
+            .is_none());
+        assert!(!UserBackend::consume_user_email_bindreq(&backend, bindreq.id).await?);
+
+        // consumed and rejected requests still count towards the origin.
+        assert_eq!(UserBackend::count_user_email_bindreqs_by_origin(
+            &backend, "127.0.0.1", 0).await?, 2);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_user_password_resetreq() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        UserBackend::add_user_email(&backend, user_id, "test@example.com").await?;
+        let user_email = UserBackend::get_user_emails(&backend, user_id).await?
+            .remove(0);
+        set_timestamp(1000);
+        let id = UserBackend::add_user_password_resetreq(
+            &backend, &user_email, "127.0.0.1", "token1",
+        ).await?;
+        set_timestamp(2000);
+        UserBackend::add_user_password_resetreq(
+            &backend, &user_email, "127.0.0.1", "token2",
+        ).await?;
+
+        let resetreq = UserBackend::get_user_password_resetreq(&backend, "token1").await?
+            .expect("resetreq is missing?");
+        assert_eq!(resetreq.id, id);
+        assert_eq!(resetreq.user_id, user_id);
+        assert_eq!(resetreq.user_email_id, user_email.id);
+        assert_eq!(resetreq.origin, "127.0.0.1");
+        assert_eq!(resetreq.created_ts, 1000);
+        assert!(UserBackend::get_user_password_resetreq(&backend, "token3").await?
+            .is_none());
+
+        assert_eq!(UserBackend::count_user_password_resetreqs_by_origin(
+            &backend, "127.0.0.1", 0).await?, 2);
+        assert_eq!(UserBackend::count_user_password_resetreqs_by_origin(
+            &backend, "127.0.0.1", 1500).await?, 1);
+        assert_eq!(UserBackend::count_user_password_resetreqs_by_origin(
+            &backend, "127.0.0.2", 0).await?, 0);
+
+        assert!(UserBackend::consume_user_password_resetreq(&backend, id).await?);
+        assert!(!UserBackend::consume_user_password_resetreq(&backend, id).await?);
+        assert!(UserBackend::get_user_password_resetreq(&backend, "token1").await?
+            .is_none());
+        // the remaining requests of the user are consumed all at once
+        assert_eq!(UserBackend::consume_user_password_resetreqs_for_user(
+            &backend, user_id).await?, 1);
+        assert_eq!(UserBackend::consume_user_password_resetreqs_for_user(
+            &backend, user_id).await?, 0);
+        assert!(UserBackend::get_user_password_resetreq(&backend, "token2").await?
+            .is_none());
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_user_identity() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        let other_id = UserBackend::add_user(&backend, "other_user").await?;
+        assert!(UserBackend::get_user_by_identity(&backend, "idp", "sub1").await?
+            .is_none());
+        set_timestamp(1000);
+        let id = UserBackend::add_user_identity(&backend, user_id, "idp", "sub1").await?;
+        UserBackend::add_user_identity(&backend, user_id, "other_idp", "sub1").await?;
+        assert_eq!(
+            UserBackend::get_user_identities(&backend, user_id).await?[0],
+            UserIdentity {
+                id,
+                user_id,
+                provider: "idp".to_string(),
+                subject: "sub1".to_string(),
+                created_ts: 1000,
+            },
+        );
+        assert_eq!(UserBackend::get_user_identities(&backend, user_id).await?.len(), 2);
+        assert_eq!(
+            UserBackend::get_user_by_identity(&backend, "idp", "sub1").await?
+                .expect("user is missing?")
+                .id,
+            user_id,
+        );
+        // the identity may only be linked once.
+        assert!(UserBackend::add_user_identity(&backend, other_id, "idp", "sub1").await
+            .is_err());
+        // only the user the identity is linked to may remove it.
+        assert!(!UserBackend::remove_user_identity(&backend, other_id, id).await?);
+        assert!(UserBackend::remove_user_identity(&backend, user_id, id).await?);
+        assert!(!UserBackend::remove_user_identity(&backend, user_id, id).await?);
+        assert!(UserBackend::get_user_by_identity(&backend, "idp", "sub1").await?
+            .is_none());
+        UserBackend::add_user_identity(&backend, other_id, "idp", "sub1").await?;
+        assert_eq!(UserBackend::get_user_identities(&backend, other_id).await?.len(), 1);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_user_token() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let user_id = UserBackend::add_user(&backend, "test_user").await?;
+        let other_id = UserBackend::add_user(&backend, "other_user").await?;
+        assert!(UserBackend::get_user_token(&backend, "token1").await?
+            .is_none());
+        set_timestamp(1000);
+        let scopes = ["protocol_view".to_string(), "".to_string()];
+        let id = UserBackend::add_user_token(
+            &backend, user_id, "script", "token1", &scopes, 2000,
+        ).await?;
+        let token = UserToken {
+            id,
+            user_id,
+            name: "script".to_string(),
+            scopes: vec!["".to_string(), "protocol_view".to_string()],
+            created_ts: 1000,
+            expires_ts: 2000,
+        };
+        assert_eq!(
+            UserBackend::get_user_token(&backend, "token1").await?,
+            Some(token.clone()),
+        );
+        assert_eq!(
+            UserBackend::get_user_token_by_id(&backend, id).await?,
+            Some(token.clone()),
+        );
+        UserBackend::add_user_token(&backend, user_id, "unscoped", "token2", &[], 2000).await?;
+        let tokens = UserBackend::get_user_tokens(&backend, user_id).await?;
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0], token);
+        assert!(tokens[1].scopes.is_empty());
+        // the token must be unique.
+        assert!(UserBackend::add_user_token(&backend, other_id, "dupe", "token1", &[], 2000).await
+            .is_err());
+
+        let session = Session {
+            token: SessionToken::from_str(&format!("{:0>32}", 1))?,
+            user_id,
+            origin: "localhost".to_string(),
+            created_ts: 1000,
+            last_active_ts: 1000,
+            user_token_id: Some(id),
+        };
+        SessionBackend::save_session(&backend, &session).await?;
+        assert_eq!(
+            SessionBackend::load_session(&backend, session.token).await?.user_token_id,
+            Some(id),
+        );
+
+        // only the user the token belongs to may remove it, which also
+        // removes the sessions created from it.
+        assert!(!UserBackend::remove_user_token(&backend, other_id, id).await?);
+        assert!(UserBackend::remove_user_token(&backend, user_id, id).await?);
+        assert!(!UserBackend::remove_user_token(&backend, user_id, id).await?);
+        assert!(UserBackend::get_user_token(&backend, "token1").await?
+            .is_none());
+        assert!(SessionBackend::load_session(&backend, session.token).await
+            .is_err());
+        assert_eq!(UserBackend::get_user_tokens(&backend, user_id).await?.len(), 1);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_group() -> anyhow::Result<()> {
+        let backend = SqliteBackend::ac("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        assert!(UserBackend::get_group_by_name(&backend, "lab").await?
+            .is_none());
+        set_timestamp(1000);
+        let id = UserBackend::add_group(&backend, "lab").await?;
+        let group = Group {
+            id,
+            name: "lab".to_string(),
+            created_ts: 1000,
+        };
+        assert_eq!(
+            UserBackend::get_group_by_id(&backend, id).await?,
+            Some(group.clone()),
+        );
+        assert_eq!(
+            UserBackend::get_group_by_name(&backend, "lab").await?,
+            Some(group.clone()),
+        );
+        // the name must be unique.
+        assert!(UserBackend::add_group(&backend, "lab").await.is_err());
+        UserBackend::add_group(&backend, "admin").await?;
+        assert_eq!(
+            UserBackend::get_groups(&backend).await?
+                .into_iter()
+                .map(|group| group.name)
+                .collect::<Vec<_>>(),
+            ["admin", "lab"],
+        );
         Ok(())
     }
 
@@ -143,6 +143,47 @@ WHERE kind = "#);
 
         Ok(recs)
     }
+
+    async fn reassign_aliases(
+        &self,
+        kind: &str,
+        kind_id: i64,
+        new_kind_id: i64,
+    ) -> Result<u64, BackendError> {
+        let rows_affected = sqlx::query!(
+            r#"
+UPDATE alias
+SET kind_id = ?3
+WHERE kind = ?1 AND kind_id = ?2
+            "#,
+            kind,
+            kind_id,
+            new_kind_id,
+        )
+        .execute(&*self.pool)
+        .await?
+        .rows_affected();
+        Ok(rows_affected)
+    }
+
+    async fn remove_aliases(
+        &self,
+        kind: &str,
+        kind_id: i64,
+    ) -> Result<u64, BackendError> {
+        let rows_affected = sqlx::query!(
+            r#"
+DELETE FROM alias
+WHERE kind = ?1 AND kind_id = ?2
+            "#,
+            kind,
+            kind_id,
+        )
+        .execute(&*self.pool)
+        .await?
+        .rows_affected();
+        Ok(rows_affected)
+    }
 }
 
 #[cfg(test)]
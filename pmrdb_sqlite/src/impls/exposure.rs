@@ -6,12 +6,18 @@ use pmrcore::{
         Exposures,
         traits::ExposureBackend,
     },
+    listing::{
+        Cursor,
+        ListingQuery,
+        Page,
+    },
 };
 use sqlx::{QueryBuilder, Row, Sqlite};
 
 use crate::{
     SqliteBackend,
     chrono::Utc,
+    impls::listing::push_listing_query,
 };
 
 async fn insert_exposure_sqlite(
@@ -113,6 +119,46 @@ FROM exposure
     Ok(rec.into())
 }
 
+async fn list_exposures_page_sqlite(
+    sqlite: &SqliteBackend,
+    query: &ListingQuery,
+) -> Result<Page<Exposure>, BackendError> {
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(r#"
+SELECT
+    id,
+    description,
+    workspace_id,
+    workspace_tag_id,
+    commit_id,
+    created_ts,
+    default_file_id
+FROM exposure"#);
+    let page_size = push_listing_query(&mut query_builder, query, &[])?;
+
+    let recs = query_builder
+        .build()
+        .try_map(|row| Ok(Exposure {
+            id: row.try_get("id")?,
+            description: row.try_get("description")?,
+            workspace_id: row.try_get("workspace_id")?,
+            workspace_tag_id: row.try_get("workspace_tag_id")?,
+            commit_id: row.try_get("commit_id")?,
+            created_ts: row.try_get("created_ts")?,
+            default_file_id: row.try_get("default_file_id")?,
+            // won't have files.
+            files: None,
+        }))
+        .fetch_all(&*sqlite.pool)
+        .await?;
+
+    Ok(Page::from_fetched(recs, page_size, |exposure| Cursor::new(
+        query.sort,
+        exposure.id,
+        exposure.created_ts,
+        exposure.description.as_deref(),
+    )))
+}
+
 async fn list_exposures_by_ids_sqlite(
     sqlite: &SqliteBackend,
     ids: &[i64],
@@ -241,6 +287,16 @@ impl ExposureBackend for SqliteBackend {
         ).await
     }
 
+    async fn list_page(
+        &self,
+        query: &ListingQuery,
+    ) -> Result<Page<Exposure>, BackendError> {
+        list_exposures_page_sqlite(
+            &self,
+            query,
+        ).await
+    }
+
     async fn list_by_ids(
         &self,
         ids: &[i64],
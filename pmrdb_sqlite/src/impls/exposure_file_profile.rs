@@ -6,7 +6,10 @@ use pmrcore::{
         ExposureFileProfile,
         traits::ExposureFileProfileBackend,
     },
-    task_template::UserInputMap,
+    task_template::{
+        UserInput,
+        UserInputMap,
+    },
 };
 
 use crate::SqliteBackend;
@@ -72,17 +75,23 @@ async fn get_ef_profile_sqlite(
         rec.user_input = sqlx::query!(r#"
 SELECT
     arg_id,
-    input
+    input,
+    multiple
 FROM exposure_file_profile_input
 WHERE
     exposure_file_profile_id = ?1
     "#,
             rec.id,
         )
-            .map(|row| (
+            .try_map(|row| Ok((
                 row.arg_id,
-                row.input,
-            ))
+                if row.multiple {
+                    UserInput::Multiple(serde_json::from_str(&row.input)
+                        .map_err(|e| sqlx::Error::Decode(e.into()))?)
+                } else {
+                    UserInput::Single(row.input)
+                },
+            )))
             .fetch_all(&*sqlite.pool)
             .await?
             .into_iter()
@@ -103,22 +112,34 @@ async fn update_ef_user_input_sqlite(
         future::try_join_all(
             user_input.iter()
                 .map(|(arg_id, input)| async move {
+                    // multiple values are stored as a JSON list
+                    let (input, multiple) = match input {
+                        UserInput::Single(value) => (value.clone(), false),
+                        UserInput::Multiple(values) => (
+                            serde_json::to_string(values)
+                                .expect("serialization shouldn't fail on this basic type"),
+                            true,
+                        ),
+                    };
                     sqlx::query!(
                         r#"
 INSERT INTO exposure_file_profile_input (
     exposure_file_profile_id,
     arg_id,
-    input
+    input,
+    multiple
 )
-VALUES (?1, ?2, ?3)
+VALUES (?1, ?2, ?3, ?4)
 ON CONFLICT(exposure_file_profile_id, arg_id)
 DO UPDATE SET
     arg_id = ?2,
-    input = ?3
+    input = ?3,
+    multiple = ?4
     "#,
                         exposure_file_profile_id,
                         arg_id,
                         input,
+                        multiple,
                     )
                         .execute(&*sqlite.pool)
                         .await?;
@@ -230,8 +251,8 @@ pub(crate) mod testing {
         assert_eq!(ef_profile.user_input.len(), 0);
 
         let user_input = UserInputMap::from([
-            (1, "First".to_string()),
-            (2, "Alternate".to_string()),
+            (1, "First".into()),
+            (2, ["Alternate", "Other"].into()),
         ]);
         efpb.update_ef_user_input(exposure_file_id, &user_input).await?;
 
@@ -257,17 +278,17 @@ pub(crate) mod testing {
         efpb.update_ef_user_input(
             exposure_file_id,
             &UserInputMap::from([
-                (2, "Second".to_string()),
-                (3, "Third".to_string()),
+                (2, "Second".into()),
+                (3, ["Third", "Fourth"].into()),
             ])
         ).await?;
         let final_ef_profile = efpb.get_ef_profile(exposure_file_id)
             .await?
             .expect("profile should have been assigned here");
         assert_eq!(final_ef_profile.user_input, UserInputMap::from([
-            (1, "First".to_string()),
-            (2, "Second".to_string()),
-            (3, "Third".to_string()),
+            (1, "First".into()),
+            (2, "Second".into()),
+            (3, ["Third", "Fourth"].into()),
         ]));
 
         Ok(())
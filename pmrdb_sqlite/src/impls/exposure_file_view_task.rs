@@ -5,7 +5,9 @@ use pmrcore::{
         Error,
     },
     exposure::task::{
+        ExposureFileViewBuild,
         ExposureFileViewTask,
+        ExposureFileViewTaskStatus,
         traits::ExposureTaskBackend,
     },
 };
@@ -200,6 +202,94 @@ RETURNING id, view_key
 }
 
 
+async fn list_view_tasks_for_exposure_sqlite(
+    sqlite: &SqliteBackend,
+    exposure_id: i64,
+) -> Result<Vec<ExposureFileViewTaskStatus>, BackendError> {
+    let recs = sqlx::query!(
+        r#"
+SELECT
+    exposure_file.id AS exposure_file_id,
+    exposure_file.workspace_file_path,
+    exposure_file_view.id AS exposure_file_view_id,
+    view_task_template.view_key,
+    exposure_file_view_task.task_id AS "task_id?"
+FROM
+    exposure_file
+JOIN
+    exposure_file_view ON exposure_file_view.exposure_file_id = exposure_file.id
+JOIN
+    view_task_template ON view_task_template.id = exposure_file_view.view_task_template_id
+LEFT JOIN
+    exposure_file_view_task ON exposure_file_view_task.id = exposure_file_view.exposure_file_view_task_id
+WHERE
+    exposure_file.exposure_id = ?1
+ORDER BY
+    exposure_file.workspace_file_path,
+    view_task_template.view_key
+        "#,
+        exposure_id,
+    )
+    .map(|row| ExposureFileViewTaskStatus {
+        exposure_file_id: row.exposure_file_id,
+        workspace_file_path: row.workspace_file_path,
+        exposure_file_view_id: row.exposure_file_view_id,
+        view_key: row.view_key,
+        task_id: row.task_id,
+        task: None,
+    })
+    .fetch_all(&*sqlite.pool)
+    .await?;
+    Ok(recs)
+}
+
+async fn list_view_builds_sqlite(
+    sqlite: &SqliteBackend,
+    task_template_id: Option<i64>,
+) -> Result<Vec<ExposureFileViewBuild>, BackendError> {
+    let recs = sqlx::query!(
+        r#"
+SELECT
+    exposure_file.exposure_id,
+    exposure_file.id AS exposure_file_id,
+    exposure_file.workspace_file_path,
+    exposure_file_view.id AS exposure_file_view_id,
+    view_task_template.view_key,
+    view_task_template.task_template_id,
+    exposure_file_view_task.task_id AS "task_id?"
+FROM
+    exposure_file
+JOIN
+    exposure_file_view ON exposure_file_view.exposure_file_id = exposure_file.id
+JOIN
+    view_task_template ON view_task_template.id = exposure_file_view.view_task_template_id
+LEFT JOIN
+    exposure_file_view_task ON exposure_file_view_task.id = exposure_file_view.exposure_file_view_task_id
+WHERE
+    exposure_file_view.view_key IS NOT NULL
+    AND (?1 IS NULL OR view_task_template.task_template_id = ?1)
+ORDER BY
+    exposure_file.exposure_id,
+    exposure_file.workspace_file_path,
+    view_task_template.view_key
+        "#,
+        task_template_id,
+    )
+    .map(|row| ExposureFileViewBuild {
+        exposure_id: row.exposure_id,
+        exposure_file_id: row.exposure_file_id,
+        workspace_file_path: row.workspace_file_path,
+        exposure_file_view_id: row.exposure_file_view_id,
+        view_key: row.view_key,
+        task_template_id: row.task_template_id,
+        task_id: row.task_id,
+        built_task_template_id: None,
+    })
+    .fetch_all(&*sqlite.pool)
+    .await?;
+    Ok(recs)
+}
+
 #[async_trait]
 impl ExposureTaskBackend for SqliteBackend {
     async fn create_task_for_view(
@@ -235,12 +325,29 @@ impl ExposureTaskBackend for SqliteBackend {
             task_id,
         ).await
     }
+
+    async fn list_view_tasks_for_exposure(
+        &self,
+        exposure_id: i64,
+    ) -> Result<Vec<ExposureFileViewTaskStatus>, BackendError> {
+        list_view_tasks_for_exposure_sqlite(&self, exposure_id).await
+    }
+
+    async fn list_view_builds(
+        &self,
+        task_template_id: Option<i64>,
+    ) -> Result<Vec<ExposureFileViewBuild>, BackendError> {
+        list_view_builds_sqlite(&self, task_template_id).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use pmrcore::profile::traits::ViewTaskTemplateBackend;
+    use pmrcore::{
+        exposure::traits::ExposureFileViewBackend,
+        profile::traits::ViewTaskTemplateBackend,
+    };
     use crate::SqliteBackend;
 
     use super::super::{
@@ -347,4 +454,126 @@ mod tests {
         Ok(())
     }
 
+
+    #[async_std::test]
+    async fn test_list_view_tasks_for_exposure() -> anyhow::Result<()> {
+        let backend = SqliteBackend::connect("sqlite::memory:".into())
+            .await?
+            .migrate_mc()
+            .await?;
+
+        let workspace_id = make_example_workspace(&backend).await?;
+        let exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let other_exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let file_b = make_example_exposure_file(
+            &backend, exposure_id, "file_b").await?;
+        let file_a = make_example_exposure_file(
+            &backend, exposure_id, "file_a").await?;
+        let other_file = make_example_exposure_file(
+            &backend, other_exposure_id, "file_a").await?;
+        let (view_b2, vb2) = make_example_exposure_file_view(
+            &backend, file_b, None, "view2").await?;
+        let (view_b1, _) = make_example_exposure_file_view(
+            &backend, file_b, None, "view1").await?;
+        let (view_a1, va1) = make_example_exposure_file_view(
+            &backend, file_a, None, "view1").await?;
+        let (other_view, other_v) = make_example_exposure_file_view(
+            &backend, other_file, None, "view1").await?;
+
+        make_example_exposure_file_view_task(
+            &backend, view_b2, vb2, Some(3)).await?;
+        make_example_exposure_file_view_task(
+            &backend, view_a1, va1, Some(4)).await?;
+        // only the most recent task for the view is listed
+        make_example_exposure_file_view_task(
+            &backend, view_a1, va1, Some(5)).await?;
+        make_example_exposure_file_view_task(
+            &backend, other_view, other_v, Some(6)).await?;
+
+        let etb: &dyn ExposureTaskBackend = &backend;
+        let results = etb.list_view_tasks_for_exposure(exposure_id).await?
+            .into_iter()
+            .map(|status| (
+                status.exposure_file_id,
+                status.workspace_file_path,
+                status.exposure_file_view_id,
+                status.view_key,
+                status.task_id,
+            ))
+            .collect::<Vec<_>>();
+        assert_eq!(results, [
+            (file_a, "file_a".to_string(), view_a1, "view1".to_string(), Some(5)),
+            (file_b, "file_b".to_string(), view_b1, "view1".to_string(), None),
+            (file_b, "file_b".to_string(), view_b2, "view2".to_string(), Some(3)),
+        ]);
+
+        assert_eq!(etb.list_view_tasks_for_exposure(0).await?.len(), 0);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_list_view_builds() -> anyhow::Result<()> {
+        let backend = SqliteBackend::connect("sqlite::memory:".into())
+            .await?
+            .migrate_mc()
+            .await?;
+
+        let workspace_id = make_example_workspace(&backend).await?;
+        let exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let other_exposure_id = make_example_exposure(&backend, workspace_id).await?;
+        let file_b = make_example_exposure_file(
+            &backend, exposure_id, "file_b").await?;
+        let file_a = make_example_exposure_file(
+            &backend, exposure_id, "file_a").await?;
+        let other_file = make_example_exposure_file(
+            &backend, other_exposure_id, "file_a").await?;
+        let (view_b1, vb1) = make_example_exposure_file_view(
+            &backend, file_b, None, "view1").await?;
+        let (view_b2, _) = make_example_exposure_file_view(
+            &backend, file_b, None, "view2").await?;
+        let (view_a1, va1) = make_example_exposure_file_view(
+            &backend, file_a, None, "view1").await?;
+        let (other_view, other_v) = make_example_exposure_file_view(
+            &backend, other_file, None, "view1").await?;
+
+        make_example_exposure_file_view_task(
+            &backend, view_b1, vb1, Some(3)).await?;
+        make_example_exposure_file_view_task(
+            &backend, view_a1, va1, Some(4)).await?;
+        make_example_exposure_file_view_task(
+            &backend, other_view, other_v, Some(5)).await?;
+        // views yet to be built are not listed
+        ExposureFileViewBackend::update_view_key(&backend, view_b2, None).await?;
+        ViewTaskTemplateBackend::migrate_view_task_templates(&backend, 1, 2).await?;
+        ViewTaskTemplateBackend::update_view_task_template_by_fields(
+            &backend, va1, "view1", "", 1).await?;
+
+        let etb: &dyn ExposureTaskBackend = &backend;
+        let results = etb.list_view_builds(None).await?
+            .into_iter()
+            .map(|build| (
+                build.exposure_id,
+                build.workspace_file_path,
+                build.exposure_file_view_id,
+                build.task_template_id,
+                build.task_id,
+            ))
+            .collect::<Vec<_>>();
+        assert_eq!(results, [
+            (exposure_id, "file_a".to_string(), view_a1, 1, Some(4)),
+            (exposure_id, "file_b".to_string(), view_b1, 2, Some(3)),
+            (other_exposure_id, "file_a".to_string(), other_view, 2, Some(5)),
+        ]);
+
+        let results = etb.list_view_builds(Some(1)).await?
+            .into_iter()
+            .map(|build| build.exposure_file_view_id)
+            .collect::<Vec<_>>();
+        assert_eq!(results, [view_a1]);
+        assert_eq!(etb.list_view_builds(Some(3)).await?.len(), 0);
+
+        Ok(())
+    }
+
 }
@@ -59,7 +59,8 @@ SELECT
     view_key,
     description,
     task_template_id,
-    updated_ts
+    updated_ts,
+    depends_on
 FROM
     view_task_template
 WHERE
@@ -80,6 +81,7 @@ WHERE
         description: row.description,
         task_template_id: row.task_template_id,
         updated_ts: row.updated_ts,
+        depends_on: row.depends_on,
         // task_template is from the other backend
         task_template: None,
     })
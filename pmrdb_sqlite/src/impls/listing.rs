@@ -0,0 +1,101 @@
+use pmrcore::{
+    error::BackendError,
+    listing::{
+        Cursor,
+        ListingQuery,
+        SortKey,
+        SortOrder,
+    },
+};
+use sqlx::{Encode, QueryBuilder, Sqlite, Type};
+
+fn push_condition<'q>(
+    builder: &mut QueryBuilder<'q, Sqlite>,
+    has_where: &mut bool,
+) {
+    builder.push(if *has_where { " AND " } else { " WHERE " });
+    *has_where = true;
+}
+
+// The entries after the cursor are those past the value of the sort key,
+// or those sharing the value that are past the id.
+fn push_keyset<'q, T>(
+    builder: &mut QueryBuilder<'q, Sqlite>,
+    key: &str,
+    cmp: &str,
+    value: T,
+    id: i64,
+)
+where
+    T: 'q + Clone + Encode<'q, Sqlite> + Type<Sqlite>,
+{
+    builder.push("(").push(key).push(cmp).push_bind(value.clone())
+        .push(" OR (").push(key).push(" = ").push_bind(value)
+        .push(" AND id").push(cmp).push_bind(id)
+        .push("))");
+}
+
+/// Completes the `SELECT` statement within the builder with the filters,
+/// ordering and limit for the listing query, along with the additional
+/// conditions.  The entity must have the `id`, `created_ts` and the
+/// `description` columns.
+///
+/// Returns the page size, as the limit will be one more than that to
+/// determine whether there is a following page.
+pub(crate) fn push_listing_query<'q>(
+    builder: &mut QueryBuilder<'q, Sqlite>,
+    query: &ListingQuery,
+    conditions: &[&str],
+) -> Result<u32, BackendError> {
+    let mut has_where = false;
+    for condition in conditions.iter() {
+        push_condition(builder, &mut has_where);
+        builder.push(condition);
+    }
+
+    if let Some(ids) = &query.ids {
+        push_condition(builder, &mut has_where);
+        // a single JSON array is bound rather than a parameter for each
+        // id, as the number of ids may exceed the limit on parameters.
+        let ids = ids.iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        builder.push("id IN (SELECT value FROM json_each(")
+            .push_bind(format!("[{ids}]"))
+            .push("))");
+    }
+
+    let (cmp, order) = match query.order {
+        SortOrder::Asc => (" > ", " ASC"),
+        SortOrder::Desc => (" < ", " DESC"),
+    };
+    let key = match query.sort {
+        SortKey::Id => "id",
+        SortKey::CreatedTs => "created_ts",
+        SortKey::Description => "COALESCE(description, '')",
+    };
+
+    if let Some(cursor) = query.cursor()? {
+        push_condition(builder, &mut has_where);
+        match cursor {
+            Cursor::Id(id) => {
+                builder.push("id").push(cmp).push_bind(*id);
+            }
+            Cursor::CreatedTs(ts, id) => {
+                push_keyset(builder, key, cmp, *ts, *id);
+            }
+            Cursor::Description(description, id) => {
+                push_keyset(builder, key, cmp, description.clone(), *id);
+            }
+        }
+    }
+
+    let page_size = query.page_size();
+    builder.push(" ORDER BY ")
+        .push(key).push(order)
+        .push(", id").push(order)
+        .push(" LIMIT ")
+        .push_bind(i64::from(page_size) + 1);
+    Ok(page_size)
+}
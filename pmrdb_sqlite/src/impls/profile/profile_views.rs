@@ -62,7 +62,8 @@ SELECT
     vtt.view_key,
     vtt.description,
     vtt.task_template_id,
-    vtt.updated_ts
+    vtt.updated_ts,
+    vtt.depends_on
 FROM view_task_template AS vtt
 JOIN profile_views ON vtt.id == profile_views.view_task_template_id
 WHERE profile_views.profile_id = ?1
@@ -75,6 +76,7 @@ WHERE profile_views.profile_id = ?1
         description: row.description,
         task_template_id: row.task_template_id,
         updated_ts: row.updated_ts,
+        depends_on: row.depends_on,
         task_template: None,
     })
     .fetch_all(&*sqlite.pool)
@@ -189,6 +191,7 @@ mod testing {
             description: "".into(),
             task_template_id: 6,
             updated_ts: 1234567890,
+            depends_on: None,
             task_template: None,
         }]);
 
@@ -81,7 +81,8 @@ SELECT
     view_key,
     description,
     task_template_id,
-    updated_ts
+    updated_ts,
+    depends_on
 FROM view_task_template
 WHERE id = ?1
         "#,
@@ -93,6 +94,7 @@ WHERE id = ?1
         description: row.description,
         task_template_id: row.task_template_id,
         updated_ts: row.updated_ts,
+        depends_on: row.depends_on,
         task_template: None,
     })
     .fetch_one(&*sqlite.pool)
@@ -100,6 +102,60 @@ WHERE id = ?1
     Ok(result)
 }
 
+async fn set_view_task_template_depends_on_sqlite(
+    sqlite: &SqliteBackend,
+    id: i64,
+    depends_on: Option<&str>,
+) -> Result<bool, BackendError> {
+    let updated_ts = Utc::now().timestamp();
+    let rows_affected = sqlx::query!(
+        r#"
+UPDATE
+    view_task_template
+SET
+    depends_on = ?2,
+    updated_ts = ?3
+WHERE
+    id = ?1
+        "#,
+        id,
+        depends_on,
+        updated_ts,
+    )
+    .execute(&*sqlite.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn migrate_view_task_templates_sqlite(
+    sqlite: &SqliteBackend,
+    task_template_id: i64,
+    superceded_by_id: i64,
+) -> Result<Vec<i64>, BackendError> {
+    let updated_ts = Utc::now().timestamp();
+    let ids = sqlx::query!(
+        r#"
+UPDATE
+    view_task_template
+SET
+    task_template_id = ?2,
+    updated_ts = ?3
+WHERE
+    task_template_id = ?1
+RETURNING
+    id
+        "#,
+        task_template_id,
+        superceded_by_id,
+        updated_ts,
+    )
+    .map(|row| row.id)
+    .fetch_all(&*sqlite.pool)
+    .await?;
+    Ok(ids)
+}
+
 #[async_trait]
 impl ViewTaskTemplateBackend for SqliteBackend {
     async fn insert_view_task_template(
@@ -136,6 +192,20 @@ impl ViewTaskTemplateBackend for SqliteBackend {
     ) -> Result<ViewTaskTemplate, BackendError> {
         select_view_task_template_by_id_sqlite(&self, id).await
     }
+    async fn set_view_task_template_depends_on(
+        &self,
+        id: i64,
+        depends_on: Option<&str>,
+    ) -> Result<bool, BackendError> {
+        set_view_task_template_depends_on_sqlite(&self, id, depends_on).await
+    }
+    async fn migrate_view_task_templates(
+        &self,
+        task_template_id: i64,
+        superceded_by_id: i64,
+    ) -> Result<Vec<i64>, BackendError> {
+        migrate_view_task_templates_sqlite(&self, task_template_id, superceded_by_id).await
+    }
 }
 
 #[cfg(test)]
@@ -169,6 +239,7 @@ mod testing {
             description: "".to_string(),
             task_template_id: 1,
             updated_ts: 1234567890,
+            depends_on: None,
             task_template: None,
         });
 
@@ -186,8 +257,47 @@ mod testing {
             description: "This is a finalized view.".to_string(),
             task_template_id: 2,
             updated_ts: 1357924680,
+            depends_on: None,
             task_template: None,
         });
+
+        set_timestamp(1357924681);
+        assert!(b.set_view_task_template_depends_on(
+            view_task_template_id,
+            Some("test_view"),
+        ).await?);
+        let view_task_template = b.select_view_task_template_by_id(view_task_template_id).await?;
+        assert_eq!(view_task_template.depends_on.as_deref(), Some("test_view"));
+        assert_eq!(view_task_template.updated_ts, 1357924681);
+        assert!(b.set_view_task_template_depends_on(view_task_template_id, None).await?);
+        let view_task_template = b.select_view_task_template_by_id(view_task_template_id).await?;
+        assert_eq!(view_task_template.depends_on, None);
+        assert!(!b.set_view_task_template_depends_on(view_task_template_id + 1, None).await?);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_migrate() -> anyhow::Result<()> {
+        let backend = SqliteBackend::mc("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let b: &dyn ViewTaskTemplateBackend = &backend;
+        let v1 = b.insert_view_task_template("view1", "", 1).await?;
+        let v2 = b.insert_view_task_template("view2", "", 2).await?;
+        let v3 = b.insert_view_task_template("view3", "", 1).await?;
+
+        set_timestamp(1357924680);
+        let mut migrated = b.migrate_view_task_templates(1, 3).await?;
+        migrated.sort();
+        assert_eq!(migrated, [v1, v3]);
+        let view_task_template = b.select_view_task_template_by_id(v1).await?;
+        assert_eq!(view_task_template.task_template_id, 3);
+        assert_eq!(view_task_template.updated_ts, 1357924680);
+        let view_task_template = b.select_view_task_template_by_id(v2).await?;
+        assert_eq!(view_task_template.task_template_id, 2);
+        assert_eq!(view_task_template.updated_ts, 1234567890);
+
+        assert_eq!(b.migrate_view_task_templates(1, 3).await?.len(), 0);
         Ok(())
     }
 
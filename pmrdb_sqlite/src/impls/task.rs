@@ -5,12 +5,15 @@ use pmrcore::{
         task::TaskError,
     },
     task::{
+        EXIT_DEPENDENCY_FAILED,
         Task,
         TaskArg,
         TaskArgs,
+        TaskState,
         traits::TaskBackend,
     },
 };
+use sqlx::{QueryBuilder, Row, Sqlite};
 
 use crate::{
     SqliteBackend,
@@ -34,6 +37,9 @@ async fn adds_task_sqlite(
         bin_path: task.bin_path.clone(),
         created_ts: created_ts,
         basedir: task.basedir.clone(),
+        priority: task.priority,
+        max_retries: task.max_retries,
+        retry_delay: task.retry_delay,
         .. Default::default()
     };
 
@@ -43,14 +49,20 @@ INSERT INTO task (
     task_template_id,
     bin_path,
     created_ts,
-    basedir
+    basedir,
+    priority,
+    max_retries,
+    retry_delay
 )
-VALUES ( ?1, ?2, ?3, ?4 )\
+VALUES ( ?1, ?2, ?3, ?4, ?5, ?6, ?7 )\
         ",
         task.task_template_id,
         task.bin_path,
         created_ts,
         task.basedir,
+        task.priority,
+        task.max_retries,
+        task.retry_delay,
     ).execute(&mut *tx)
         .await.map_err(BackendError::from)?
         .last_insert_rowid();
@@ -92,6 +104,56 @@ VALUES ( ?1, ?2 )\
         }
         None => None,
     };
+    for depends_on_id in task.depends_on.iter() {
+        sqlx::query!(
+            "
+INSERT INTO task_dependency (
+    task_id,
+    depends_on_id
+)
+VALUES ( ?1, ?2 )\
+            ",
+            result.id,
+            depends_on_id,
+        ).execute(&mut *tx)
+            .await.map_err(BackendError::from)?;
+    }
+    // a dependency that had already failed means this task will never
+    // start, as it was not around for the failure to cascade to it.
+    let dependency_failed = i64::from(EXIT_DEPENDENCY_FAILED);
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    start_ts = ?2,
+    stop_ts = ?2,
+    exit_status = ?3
+WHERE id = ?1
+    AND EXISTS (
+        SELECT
+            1
+        FROM
+            task_dependency
+        JOIN
+            task AS dependency ON dependency.id = task_dependency.depends_on_id
+        WHERE task_dependency.task_id = ?1
+            AND dependency.stop_ts IS NOT NULL
+            AND dependency.exit_status != 0
+    )\
+        ",
+        result.id,
+        created_ts,
+        dependency_failed,
+    ).execute(&mut *tx)
+        .await.map_err(BackendError::from)?
+        .rows_affected();
+    if rows_affected > 0 {
+        result.start_ts = Some(created_ts);
+        result.stop_ts = Some(created_ts);
+        result.exit_status = Some(dependency_failed);
+    }
+    result.depends_on = task.depends_on;
     tx.commit().await.map_err(BackendError::from)?;
     Ok(result)
 }
@@ -122,6 +184,29 @@ ORDER BY
     )
 }
 
+async fn gets_task_dependencies_sqlite(
+    sqlite: &SqliteBackend,
+    id: i64,
+) -> Result<Vec<i64>, BackendError> {
+    Ok(sqlx::query!(
+        "
+SELECT
+    depends_on_id
+FROM
+    task_dependency
+WHERE
+    task_id = ?1
+ORDER BY
+    depends_on_id
+        ",
+        id,
+    )
+        .map(|row| row.depends_on_id)
+        .fetch_all(&*sqlite.pool)
+        .await?
+    )
+}
+
 async fn gets_task_sqlite(
     sqlite: &SqliteBackend,
     id: i64,
@@ -133,11 +218,18 @@ SELECT
     task_template_id,
     bin_path,
     pid,
+    host,
     created_ts,
     start_ts,
     stop_ts,
     exit_status,
-    basedir
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
 FROM
     task
 WHERE
@@ -150,16 +242,25 @@ WHERE
             task_template_id: row.task_template_id,
             bin_path: row.bin_path,
             pid: row.pid,
+            host: row.host,
             created_ts: row.created_ts,
             start_ts: row.start_ts,
             stop_ts: row.stop_ts,
             exit_status: row.exit_status,
             basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
             args: None,
         })
         .fetch_one(&*sqlite.pool)
         .await?;
     result.args = Some(gets_task_args_sqlite(sqlite, id).await?);
+    result.depends_on = gets_task_dependencies_sqlite(sqlite, id).await?;
 
     Ok(result)
 }
@@ -175,15 +276,30 @@ async fn start_task_sqlite(
 UPDATE
     task
 SET
-    start_ts = ?1
+    start_ts = ?1,
+    attempts = attempts + 1,
+    lease_ts = NULL
 WHERE id = (
     SELECT
         id
     FROM
-        task
+        task AS queued
     WHERE
         start_ts IS NULL
+        AND (retry_ts IS NULL OR retry_ts <= ?1)
+        AND NOT EXISTS (
+            SELECT
+                1
+            FROM
+                task_dependency
+            JOIN
+                task AS dependency ON dependency.id = task_dependency.depends_on_id
+            WHERE
+                task_dependency.task_id = queued.id
+                AND (dependency.stop_ts IS NULL OR dependency.exit_status <> 0)
+        )
     ORDER BY
+        priority DESC,
         id
     LIMIT 1
 )
@@ -192,11 +308,18 @@ RETURNING
     task_template_id,
     bin_path,
     pid,
+    host,
     created_ts,
     start_ts,
     stop_ts,
     exit_status,
-    basedir
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
         ",
         start_ts,
     )
@@ -205,19 +328,28 @@ RETURNING
             task_template_id: row.task_template_id,
             bin_path: row.bin_path,
             pid: row.pid,
+            host: row.host,
             created_ts: row.created_ts,
             start_ts: row.start_ts,
             stop_ts: row.stop_ts,
             exit_status: row.exit_status,
             basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
             args: None,
         })
         .fetch_optional(&*sqlite.pool)
         .await?;
     match result.as_mut() {
-        Some(result) => result.args = Some(
-            gets_task_args_sqlite(sqlite, result.id).await?
-        ),
+        Some(result) => {
+            result.args = Some(gets_task_args_sqlite(sqlite, result.id).await?);
+            result.depends_on = gets_task_dependencies_sqlite(sqlite, result.id).await?;
+        }
         None => (),
     }
     Ok(result)
@@ -227,17 +359,20 @@ async fn run_task_sqlite(
     sqlite: &SqliteBackend,
     id: i64,
     pid: i64,
+    host: &str,
 ) -> Result<bool, BackendError> {
     let rows_affected = sqlx::query!(
         "
 UPDATE
     task
 SET
-    pid = ?2
+    pid = ?2,
+    host = ?3
 WHERE id = ?1
         ",
         id,
         pid,
+        host,
     )
         .execute(&*sqlite.pool)
         .await?
@@ -251,6 +386,40 @@ async fn complete_task_sqlite(
     exit_status: i64,
 ) -> Result<bool, BackendError> {
     let stop_ts = Utc::now().timestamp();
+    let mut tx = sqlite.pool.begin().await?;
+    // only the failures of the task itself are retried, as the negative
+    // statuses from the executor (e.g. aborted, timed out) are final.
+    if exit_status > 0 {
+        // a failed task with retries remaining goes back to the queue,
+        // with the delay doubled for each of the prior retries.
+        let rows_affected = sqlx::query!(
+            "
+UPDATE
+    task
+SET
+    start_ts = NULL,
+    pid = NULL,
+    host = NULL,
+    lease_ts = NULL,
+    exit_status = ?3,
+    retry_ts = ?2 + (retry_delay << MIN(attempts - 1, 16))
+WHERE id = ?1
+    AND start_ts IS NOT NULL
+    AND stop_ts IS NULL
+    AND attempts <= max_retries
+            ",
+            id,
+            stop_ts,
+            exit_status,
+        )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if rows_affected > 0 {
+            tx.commit().await?;
+            return Ok(true);
+        }
+    }
     let rows_affected = sqlx::query!(
         "
 UPDATE
@@ -263,6 +432,249 @@ WHERE id = ?1
         id,
         stop_ts,
         exit_status,
+    )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    if exit_status != 0 {
+        // the pending tasks depending on this one will never start.
+        let dependency_failed = i64::from(EXIT_DEPENDENCY_FAILED);
+        sqlx::query!(
+            "
+WITH RECURSIVE dependent(id) AS (
+    SELECT
+        task_id
+    FROM
+        task_dependency
+    WHERE
+        depends_on_id = ?1
+    UNION
+    SELECT
+        task_dependency.task_id
+    FROM
+        task_dependency
+    JOIN
+        dependent ON task_dependency.depends_on_id = dependent.id
+)
+UPDATE
+    task
+SET
+    start_ts = ?2,
+    stop_ts = ?2,
+    exit_status = ?3
+WHERE id IN (SELECT id FROM dependent)
+    AND start_ts IS NULL
+    AND stop_ts IS NULL
+            ",
+            id,
+            stop_ts,
+            dependency_failed,
+        )
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(rows_affected > 0)
+}
+
+async fn list_running_tasks_sqlite(
+    sqlite: &SqliteBackend,
+) -> Result<Vec<Task>, BackendError> {
+    Ok(sqlx::query!(
+        "
+SELECT
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+FROM
+    task
+WHERE
+    pid IS NOT NULL
+    AND stop_ts IS NULL
+ORDER BY
+    id
+        ",
+    )
+        .map(|row| Task {
+            id: row.id,
+            task_template_id: row.task_template_id,
+            bin_path: row.bin_path,
+            pid: row.pid,
+            host: row.host,
+            created_ts: row.created_ts,
+            start_ts: row.start_ts,
+            stop_ts: row.stop_ts,
+            exit_status: row.exit_status,
+            basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
+            args: None,
+        })
+        .fetch_all(&*sqlite.pool)
+        .await?
+    )
+}
+
+async fn list_tasks_by_state_sqlite(
+    sqlite: &SqliteBackend,
+    state: TaskState,
+) -> Result<Vec<Task>, BackendError> {
+    let state = state.as_str();
+    Ok(sqlx::query!(
+        "
+SELECT
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+FROM
+    task
+WHERE
+    CASE
+        WHEN stop_ts IS NOT NULL AND exit_status = 0 THEN 'succeeded'
+        WHEN stop_ts IS NOT NULL THEN 'failed'
+        WHEN start_ts IS NOT NULL THEN 'running'
+        ELSE 'queued'
+    END = ?1
+ORDER BY
+    id
+        ",
+        state,
+    )
+        .map(|row| Task {
+            id: row.id,
+            task_template_id: row.task_template_id,
+            bin_path: row.bin_path,
+            pid: row.pid,
+            host: row.host,
+            created_ts: row.created_ts,
+            start_ts: row.start_ts,
+            stop_ts: row.stop_ts,
+            exit_status: row.exit_status,
+            basedir: row.basedir,
+            priority: row.priority,
+            attempts: row.attempts,
+            max_retries: row.max_retries,
+            retry_delay: row.retry_delay,
+            retry_ts: row.retry_ts,
+            lease_ts: row.lease_ts,
+            depends_on: Vec::new(),
+            args: None,
+        })
+        .fetch_all(&*sqlite.pool)
+        .await?
+    )
+}
+
+async fn list_tasks_by_ids_sqlite(
+    sqlite: &SqliteBackend,
+    ids: &[i64],
+) -> Result<Vec<Task>, BackendError> {
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new("
+SELECT
+    id,
+    task_template_id,
+    bin_path,
+    pid,
+    host,
+    created_ts,
+    start_ts,
+    stop_ts,
+    exit_status,
+    basedir,
+    priority,
+    attempts,
+    max_retries,
+    retry_delay,
+    retry_ts,
+    lease_ts
+FROM
+    task
+WHERE
+    id IN (");
+
+    let mut separated = query_builder.separated(", ");
+    for id in ids.iter() {
+        separated.push_bind(id);
+    }
+    separated.push_unseparated(")
+ORDER BY
+    id");
+
+    Ok(query_builder
+        .build()
+        .try_map(|row| Ok(Task {
+            id: row.try_get("id")?,
+            task_template_id: row.try_get("task_template_id")?,
+            bin_path: row.try_get("bin_path")?,
+            pid: row.try_get("pid")?,
+            host: row.try_get("host")?,
+            created_ts: row.try_get("created_ts")?,
+            start_ts: row.try_get("start_ts")?,
+            stop_ts: row.try_get("stop_ts")?,
+            exit_status: row.try_get("exit_status")?,
+            basedir: row.try_get("basedir")?,
+            priority: row.try_get("priority")?,
+            attempts: row.try_get("attempts")?,
+            max_retries: row.try_get("max_retries")?,
+            retry_delay: row.try_get("retry_delay")?,
+            retry_ts: row.try_get("retry_ts")?,
+            lease_ts: row.try_get("lease_ts")?,
+            depends_on: Vec::new(),
+            args: None,
+        }))
+        .fetch_all(&*sqlite.pool)
+        .await?
+    )
+}
+
+async fn requeue_task_sqlite(
+    sqlite: &SqliteBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    start_ts = NULL,
+    pid = NULL,
+    host = NULL,
+    lease_ts = NULL
+WHERE id = ?1
+    AND stop_ts IS NULL
+        ",
+        id,
     )
         .execute(&*sqlite.pool)
         .await?
@@ -270,6 +682,62 @@ WHERE id = ?1
     Ok(rows_affected > 0)
 }
 
+async fn lease_task_sqlite(
+    sqlite: &SqliteBackend,
+    id: i64,
+    host: &str,
+    duration: i64,
+) -> Result<Option<i64>, BackendError> {
+    let lease_ts = Utc::now().timestamp() + duration;
+    let rows_affected = sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    host = ?2,
+    lease_ts = ?3
+WHERE id = ?1
+    AND start_ts IS NOT NULL
+    AND stop_ts IS NULL
+    AND pid IS NULL
+    AND (host IS NULL OR host = ?2)
+        ",
+        id,
+        host,
+        lease_ts,
+    )
+        .execute(&*sqlite.pool)
+        .await?
+        .rows_affected();
+    Ok((rows_affected > 0).then_some(lease_ts))
+}
+
+async fn requeue_expired_leases_sqlite(
+    sqlite: &SqliteBackend,
+) -> Result<Vec<i64>, BackendError> {
+    let now = Utc::now().timestamp();
+    Ok(sqlx::query!(
+        "
+UPDATE
+    task
+SET
+    start_ts = NULL,
+    host = NULL,
+    lease_ts = NULL
+WHERE lease_ts < ?1
+    AND start_ts IS NOT NULL
+    AND stop_ts IS NULL
+RETURNING
+    id
+        ",
+        now,
+    )
+        .map(|row| row.id)
+        .fetch_all(&*sqlite.pool)
+        .await?
+    )
+}
+
 
 #[async_trait]
 impl TaskBackend for SqliteBackend {
@@ -294,8 +762,9 @@ impl TaskBackend for SqliteBackend {
         &self,
         id: i64,
         pid: i64,
+        host: &str,
     ) -> Result<bool, BackendError> {
-        run_task_sqlite(&self, id, pid).await
+        run_task_sqlite(&self, id, pid, host).await
     }
     async fn complete(
         &self,
@@ -304,6 +773,42 @@ impl TaskBackend for SqliteBackend {
     ) -> Result<bool, BackendError> {
         complete_task_sqlite(&self, id, exit_status).await
     }
+    async fn list_running(
+        &self,
+    ) -> Result<Vec<Task>, BackendError> {
+        list_running_tasks_sqlite(&self).await
+    }
+    async fn list_by_state(
+        &self,
+        state: TaskState,
+    ) -> Result<Vec<Task>, BackendError> {
+        list_tasks_by_state_sqlite(&self, state).await
+    }
+    async fn list_by_ids(
+        &self,
+        ids: &[i64],
+    ) -> Result<Vec<Task>, BackendError> {
+        list_tasks_by_ids_sqlite(&self, ids).await
+    }
+    async fn lease(
+        &self,
+        id: i64,
+        host: &str,
+        duration: i64,
+    ) -> Result<Option<i64>, BackendError> {
+        lease_task_sqlite(&self, id, host, duration).await
+    }
+    async fn requeue_expired_leases(
+        &self,
+    ) -> Result<Vec<i64>, BackendError> {
+        requeue_expired_leases_sqlite(&self).await
+    }
+    async fn requeue(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        requeue_task_sqlite(&self, id).await
+    }
 }
 
 #[cfg(test)]
@@ -492,7 +997,7 @@ mod tests {
             .await?
             .expect("a task has started");
 
-        assert!(TaskBackend::run(&backend, task.id, 123)
+        assert!(TaskBackend::run(&backend, task.id, 123, "localhost")
             .await?
         );
         let running_task = TaskBackend::gets_task(
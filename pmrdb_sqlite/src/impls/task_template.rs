@@ -3,6 +3,7 @@ use futures::future;
 use pmrcore::{
     error::BackendError,
     task_template::{
+        TaskSandbox,
         TaskTemplate,
         TaskTemplateArg,
         TaskTemplateArgs,
@@ -89,21 +90,28 @@ SELECT
     version_id,
     created_ts,
     final_task_template_arg_id,
-    superceded_by_id
+    superceded_by_id,
+    timeout,
+    sandbox
 FROM task_template
 WHERE id = ?1
 "#,
         id,
     )
-    .map(|row| TaskTemplate {
+    .try_map(|row| Ok(TaskTemplate {
         id: row.id,
         bin_path: row.bin_path,
         version_id: row.version_id,
         created_ts: row.created_ts,
         final_task_template_arg_id: row.final_task_template_arg_id,
         superceded_by_id: row.superceded_by_id,
+        timeout: row.timeout,
+        sandbox: row.sandbox
+            .map(|sandbox| serde_json::from_str(&sandbox))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()))?,
         args: None,
-    })
+    }))
     .fetch_one(&*sqlite.pool)
     .await?;
     Ok(rec)
@@ -120,7 +128,9 @@ SELECT
     version_id,
     created_ts,
     final_task_template_arg_id,
-    superceded_by_id
+    superceded_by_id,
+    timeout,
+    sandbox
 FROM task_template
 WHERE id = (
     SELECT task_template_id
@@ -130,20 +140,93 @@ WHERE id = (
 "#,
         id,
     )
-    .map(|row| TaskTemplate {
+    .try_map(|row| Ok(TaskTemplate {
         id: row.id,
         bin_path: row.bin_path,
         version_id: row.version_id,
         created_ts: row.created_ts,
         final_task_template_arg_id: row.final_task_template_arg_id,
         superceded_by_id: row.superceded_by_id,
+        timeout: row.timeout,
+        sandbox: row.sandbox
+            .map(|sandbox| serde_json::from_str(&sandbox))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()))?,
         args: None,
-    })
+    }))
     .fetch_one(&*sqlite.pool)
     .await?;
     Ok(rec)
 }
 
+async fn set_task_template_timeout_sqlite(
+    sqlite: &SqliteBackend,
+    id: i64,
+    timeout: Option<i64>,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE task_template
+SET timeout = ?2
+WHERE id = ?1
+"#,
+        id,
+        timeout,
+    )
+    .execute(&*sqlite.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn set_task_template_sandbox_sqlite(
+    sqlite: &SqliteBackend,
+    id: i64,
+    sandbox: Option<&TaskSandbox>,
+) -> Result<bool, BackendError> {
+    let sandbox = sandbox.map(|sandbox| serde_json::to_string(sandbox)
+        .expect("serialization shouldn't fail on this basic type"));
+    let rows_affected = sqlx::query!(r#"
+UPDATE task_template
+SET sandbox = ?2
+WHERE id = ?1
+"#,
+        id,
+        sandbox,
+    )
+    .execute(&*sqlite.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn supersede_task_template_sqlite(
+    sqlite: &SqliteBackend,
+    id: i64,
+    superceded_by_id: i64,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE task_template
+SET superceded_by_id = ?2
+WHERE id = ?1
+    AND id != ?2
+    AND superceded_by_id IS NULL
+    AND EXISTS (
+        SELECT 1
+        FROM task_template
+        WHERE id = ?2
+            AND final_task_template_arg_id IS NOT NULL
+            AND superceded_by_id IS NULL
+    )
+"#,
+        id,
+        superceded_by_id,
+    )
+    .execute(&*sqlite.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
 async fn add_task_template_arg_sqlite(
     sqlite: &SqliteBackend,
     task_template_id: i64,
@@ -154,6 +237,8 @@ async fn add_task_template_arg_sqlite(
     default: Option<&str>,
     choice_fixed: bool,
     choice_source: Option<&str>,
+    multiple: bool,
+    multiple_separator: Option<&str>,
 ) -> Result<i64, BackendError> {
     let id = sqlx::query!(
         r#"
@@ -165,9 +250,11 @@ INSERT INTO task_template_arg (
     prompt,
     'default',
     choice_fixed,
-    choice_source
+    choice_source,
+    multiple,
+    multiple_separator
 )
-VALUES ( ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8 )
+VALUES ( ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10 )
         "#,
         task_template_id,
         flag,
@@ -177,6 +264,8 @@ VALUES ( ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8 )
         default,
         choice_fixed,
         choice_source,
+        multiple,
+        multiple_separator,
     )
     .execute(&*sqlite.pool)
     .await?
@@ -245,7 +334,9 @@ SELECT
     prompt,
     "default",
     choice_fixed,
-    choice_source
+    choice_source,
+    multiple,
+    multiple_separator
 FROM task_template_arg,
     (
         SELECT final_task_template_arg_id
@@ -272,6 +363,8 @@ WHERE
         choice_fixed: row.choice_fixed,
         choice_source: row.choice_source,
         choices: None,
+        multiple: row.multiple,
+        multiple_separator: row.multiple_separator,
     })
     .fetch_all(&*sqlite.pool)
     .await?;
@@ -293,7 +386,9 @@ SELECT
     prompt,
     "default",
     choice_fixed,
-    choice_source
+    choice_source,
+    multiple,
+    multiple_separator
 FROM
     task_template_arg
 WHERE
@@ -312,6 +407,8 @@ WHERE
         choice_fixed: row.choice_fixed,
         choice_source: row.choice_source,
         choices: None,
+        multiple: row.multiple,
+        multiple_separator: row.multiple_separator,
     })
     .fetch_optional(&*sqlite.pool)
     .await?;
@@ -369,6 +466,8 @@ WHERE
         choice_fixed: row.choice_fixed,
         choice_source: row.choice_source,
         choices: None,
+        multiple: row.multiple,
+        multiple_separator: row.multiple_separator,
     })
     .fetch_optional(&mut *tx)
     .await?;
@@ -423,6 +522,8 @@ impl TaskTemplateBackend for SqliteBackend {
         default: Option<&str>,
         choice_fixed: bool,
         choice_source: Option<&str>,
+        multiple: bool,
+        multiple_separator: Option<&str>,
     ) -> Result<i64, BackendError> {
         add_task_template_arg_sqlite(
             &self,
@@ -434,6 +535,8 @@ impl TaskTemplateBackend for SqliteBackend {
             default,
             choice_fixed,
             choice_source,
+            multiple,
+            multiple_separator,
         ).await
     }
 
@@ -538,6 +641,30 @@ impl TaskTemplateBackend for SqliteBackend {
         result.args = Some(args);
         Ok(result)
     }
+
+    async fn set_task_template_timeout(
+        &self,
+        id: i64,
+        timeout: Option<i64>,
+    ) -> Result<bool, BackendError> {
+        set_task_template_timeout_sqlite(&self, id, timeout).await
+    }
+
+    async fn set_task_template_sandbox(
+        &self,
+        id: i64,
+        sandbox: Option<&TaskSandbox>,
+    ) -> Result<bool, BackendError> {
+        set_task_template_sandbox_sqlite(&self, id, sandbox).await
+    }
+
+    async fn supersede_task_template(
+        &self,
+        id: i64,
+        superceded_by_id: i64,
+    ) -> Result<bool, BackendError> {
+        supersede_task_template_sqlite(&self, id, superceded_by_id).await
+    }
 }
 
 #[cfg(test)]
@@ -573,6 +700,8 @@ mod tests {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(0),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([].to_vec().into()),
         });
         Ok(())
@@ -598,6 +727,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            None,
         ).await?;
         ttb.finalize_new_task_template(id).await?;
 
@@ -609,6 +740,8 @@ mod tests {
             created_ts: template.created_ts,  // matching itself
             final_task_template_arg_id: Some(1),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([TaskTemplateArg {
                 id: 1,
                 task_template_id: 1,
@@ -620,6 +753,8 @@ mod tests {
                 choice_fixed: false,
                 choice_source: None,
                 choices: None,
+                multiple: false,
+                multiple_separator: None,
             }].to_vec().into()),
         };
         assert_eq!(template, answer);
@@ -700,6 +835,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            None,
         ).await?;
         ttb.add_task_template_arg(
             id,
@@ -710,6 +847,8 @@ mod tests {
             None,
             false,
             Some(""),
+            false,
+            None,
         ).await?;
         ttb.finalize_new_task_template(id).await?;
 
@@ -721,6 +860,8 @@ mod tests {
             created_ts: template.created_ts,  // matching itself
             final_task_template_arg_id: Some(2),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([TaskTemplateArg {
                 id: 1,
                 task_template_id: 1,
@@ -732,6 +873,8 @@ mod tests {
                 choice_fixed: false,
                 choice_source: None,
                 choices: None,
+                multiple: false,
+                multiple_separator: None,
             }, TaskTemplateArg {
                 id: 2,
                 task_template_id: 1,
@@ -743,6 +886,8 @@ mod tests {
                 choice_fixed: false,
                 choice_source: Some("".into()),
                 choices: Some(vec![].into()),
+                multiple: false,
+                multiple_separator: None,
             }].to_vec().into()),
         };
         assert_eq!(template, answer);
@@ -893,7 +1038,7 @@ mod tests {
         ).await
             .unwrap();
         TaskTemplateBackend::add_task_template_arg(
-            &backend, 1, Some("-i"), false, false, None, None, false, None
+            &backend, 1, Some("-i"), false, false, None, None, false, None, false, None
         ).await.unwrap();
         let template = TaskTemplateBackend::get_task_template_by_id(
             &backend, id
@@ -905,6 +1050,8 @@ mod tests {
             created_ts: 1234567890,
             final_task_template_arg_id: None,
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([TaskTemplateArg {
                 id: 1,
                 task_template_id: 1,
@@ -916,6 +1063,8 @@ mod tests {
                 choice_fixed: false,
                 choice_source: None,
                 choices: None,
+                multiple: false,
+                multiple_separator: None,
             }].to_vec().into()),
         });
 
@@ -937,6 +1086,8 @@ mod tests {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(1),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([TaskTemplateArg {
                 id: 1,
                 task_template_id: 1,
@@ -948,6 +1099,8 @@ mod tests {
                 choice_fixed: false,
                 choice_source: None,
                 choices: None,
+                multiple: false,
+                multiple_separator: None,
             }].to_vec().into()),
         });
         Ok(())
@@ -963,7 +1116,7 @@ mod tests {
             &backend, "/bin/true", "1.0.0",
         ).await.unwrap();
         TaskTemplateBackend::add_task_template_arg(
-            &backend, 1, Some("-i"), false, false, None, None, false, None
+            &backend, 1, Some("-i"), false, false, None, None, false, None, false, None
         ).await.unwrap();
         assert_eq!(TaskTemplateBackend::delete_task_template_arg_by_id(
             &backend, 1).await.unwrap(), Some(TaskTemplateArg {
@@ -977,6 +1130,8 @@ mod tests {
                 choice_fixed: false,
                 choice_source: None,
                 choices: None,
+                multiple: false,
+                multiple_separator: None,
             }
         ));
 
@@ -990,6 +1145,8 @@ mod tests {
             created_ts: 1234567890,
             final_task_template_arg_id: None,
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([].to_vec().into()),
         });
         Ok(())
@@ -1021,6 +1178,8 @@ mod tests {
             created_ts: 1234567890,
             final_task_template_arg_id: None,
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([].to_vec().into()),
         });
 
@@ -1040,6 +1199,8 @@ mod tests {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(0),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([].to_vec().into()),
         });
 
@@ -1079,6 +1240,8 @@ mod tests {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(0),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([].to_vec().into()),
         });
 
@@ -1123,7 +1286,9 @@ mod tests {
                     "default": null,
                     "choice_fixed": false,
                     "choice_source": null,
-                    "choices": []
+                    "choices": [],
+                    "multiple": true,
+                    "multiple_separator": ","
                 }
             ]
         }
@@ -1135,6 +1300,14 @@ mod tests {
         assert_eq!(result.id, 1);
         assert_eq!(result.final_task_template_arg_id, Some(2));
 
+        let args = ttb.get_task_template_by_id(result.id).await?
+            .args
+            .expect("args should be returned");
+        assert!(!args[0].multiple);
+        assert_eq!(args[0].multiple_separator, None);
+        assert!(args[1].multiple);
+        assert_eq!(args[1].multiple_separator.as_deref(), Some(","));
+
         let tt2 = ttb.adds_task_template(serde_json::from_str(r#"
         {
             "bin_path": "/bin/echo",
@@ -1146,4 +1319,45 @@ mod tests {
         Ok(())
     }
 
+    #[async_std::test]
+    async fn test_supersede() -> anyhow::Result<()> {
+        let backend = SqliteBackend::tm("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let ttb: &(dyn TaskTemplateBackend + Sync) = &backend;
+        let task_template: TaskTemplate = serde_json::from_str(r#"
+        {
+            "bin_path": "/bin/echo",
+            "version_id": "1.0.0",
+            "args": []
+        }"#)?;
+        let tt1 = ttb.adds_task_template(task_template.clone()).await?;
+        let tt2 = ttb.adds_task_template(task_template.clone()).await?;
+        let tt3 = ttb.adds_task_template(task_template).await?;
+        let (unfinalized, _) = ttb.add_task_template("/bin/echo", "2.0.0").await?;
+
+        // must be superceded by another finalized task template
+        assert!(!ttb.supersede_task_template(tt1.id, tt1.id).await?);
+        assert!(!ttb.supersede_task_template(tt1.id, unfinalized).await?);
+        assert!(!ttb.supersede_task_template(tt1.id, 0).await?);
+
+        assert!(ttb.supersede_task_template(tt1.id, tt2.id).await?);
+        assert_eq!(
+            ttb.get_task_template_by_id(tt1.id).await?.superceded_by_id,
+            Some(tt2.id),
+        );
+        // already superceded
+        assert!(!ttb.supersede_task_template(tt1.id, tt3.id).await?);
+        // cannot be superceded by a superceded task template
+        assert!(!ttb.supersede_task_template(tt3.id, tt1.id).await?);
+        assert!(ttb.supersede_task_template(tt2.id, tt3.id).await?);
+        assert_eq!(
+            ttb.get_task_template_by_id(tt2.id).await?.superceded_by_id,
+            Some(tt3.id),
+        );
+        assert_eq!(ttb.get_task_template_by_id(tt3.id).await?.superceded_by_id, None);
+
+        Ok(())
+    }
+
 }
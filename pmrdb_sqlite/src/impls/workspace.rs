@@ -1,6 +1,11 @@
 use async_trait::async_trait;
 use pmrcore::{
     error::BackendError,
+    listing::{
+        Cursor,
+        ListingQuery,
+        Page,
+    },
     workspace::{
         Workspace,
         Workspaces,
@@ -12,6 +17,7 @@ use sqlx::{QueryBuilder, Row, Sqlite};
 use crate::{
     SqliteBackend,
     chrono::Utc,
+    impls::listing::push_listing_query,
 };
 
 
@@ -80,9 +86,12 @@ SELECT
     superceded_by_id,
     description,
     long_description,
-    created_ts
+    created_ts,
+    archived_ts
 FROM
     workspace
+WHERE
+    archived_ts IS NULL
 ORDER BY
     id
         "#
@@ -94,6 +103,7 @@ ORDER BY
         description: row.description,
         long_description: row.long_description,
         created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
         exposures: None,
     })
     .fetch_all(&*backend.pool)
@@ -101,6 +111,55 @@ ORDER BY
     Ok(recs.into())
 }
 
+async fn list_workspaces_page_sqlite(
+    backend: &SqliteBackend,
+    query: &ListingQuery,
+) -> Result<Page<Workspace>, BackendError> {
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace"#);
+
+    let mut conditions = vec!["archived_ts IS NULL"];
+    match query.has_exposure {
+        Some(true) => conditions.push(
+            "EXISTS (SELECT 1 FROM exposure WHERE exposure.workspace_id = workspace.id)"),
+        Some(false) => conditions.push(
+            "NOT EXISTS (SELECT 1 FROM exposure WHERE exposure.workspace_id = workspace.id)"),
+        None => (),
+    }
+    let page_size = push_listing_query(&mut query_builder, query, &conditions)?;
+
+    let recs = query_builder
+        .build()
+        .try_map(|row| Ok(Workspace {
+            id: row.try_get("id")?,
+            url: row.try_get("url")?,
+            superceded_by_id: row.try_get("superceded_by_id")?,
+            description: row.try_get("description")?,
+            long_description: row.try_get("long_description")?,
+            created_ts: row.try_get("created_ts")?,
+            archived_ts: row.try_get("archived_ts")?,
+            exposures: None,
+        }))
+        .fetch_all(&*backend.pool)
+        .await?;
+
+    Ok(Page::from_fetched(recs, page_size, |workspace| Cursor::new(
+        query.sort,
+        workspace.id,
+        workspace.created_ts,
+        workspace.description.as_deref(),
+    )))
+}
+
 async fn get_workspace_by_id_sqlite(
     backend: &SqliteBackend,
     id: i64,
@@ -113,7 +172,8 @@ SELECT
     superceded_by_id,
     description,
     long_description,
-    created_ts
+    created_ts,
+    archived_ts
 FROM
     workspace
 WHERE
@@ -128,6 +188,7 @@ WHERE
         description: row.description,
         long_description: row.long_description,
         created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
         exposures: None,
     })
     .fetch_one(&*backend.pool)
@@ -146,7 +207,8 @@ SELECT
     superceded_by_id,
     description,
     long_description,
-    created_ts
+    created_ts,
+    archived_ts
 FROM
     workspace
 WHERE
@@ -161,6 +223,7 @@ WHERE
         description: row.description,
         long_description: row.long_description,
         created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
         exposures: None,
     })
     .fetch_all(&*backend.pool)
@@ -179,7 +242,8 @@ SELECT
     superceded_by_id,
     description,
     long_description,
-    created_ts
+    created_ts,
+    archived_ts
 FROM
     workspace
 WHERE
@@ -200,6 +264,7 @@ WHERE
             description: row.try_get("description")?,
             long_description: row.try_get("long_description")?,
             created_ts: row.try_get("created_ts")?,
+            archived_ts: row.try_get("archived_ts")?,
             exposures: None,
         }))
         .fetch_all(&*backend.pool)
@@ -208,6 +273,104 @@ WHERE
     Ok(recs.into())
 }
 
+async fn set_workspace_superceded_by_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+    superceded_by_id: Option<i64>,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE
+    workspace
+SET
+    superceded_by_id = ?1
+WHERE
+    id = ?2"#,
+        superceded_by_id,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn archive_workspace_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let ts = Utc::now().timestamp();
+    let rows_affected = sqlx::query!(r#"
+UPDATE
+    workspace
+SET
+    archived_ts = ?1
+WHERE
+    id = ?2
+    AND archived_ts IS NULL"#,
+        ts,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn restore_workspace_sqlite(
+    backend: &SqliteBackend,
+    id: i64,
+) -> Result<bool, BackendError> {
+    let rows_affected = sqlx::query!(r#"
+UPDATE
+    workspace
+SET
+    archived_ts = NULL
+WHERE
+    id = ?1
+    AND archived_ts IS NOT NULL"#,
+        id,
+    )
+    .execute(&*backend.pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
+async fn list_archived_workspaces_sqlite(
+    backend: &SqliteBackend,
+) -> Result<Workspaces, BackendError> {
+    let recs = sqlx::query!(r#"
+SELECT
+    id,
+    url,
+    superceded_by_id,
+    description,
+    long_description,
+    created_ts,
+    archived_ts
+FROM
+    workspace
+WHERE
+    archived_ts IS NOT NULL
+ORDER BY
+    id
+        "#
+    )
+    .map(|row| Workspace {
+        id: row.id,
+        url: row.url,
+        superceded_by_id: row.superceded_by_id,
+        description: row.description,
+        long_description: row.long_description,
+        created_ts: row.created_ts,
+        archived_ts: row.archived_ts,
+        exposures: None,
+    })
+    .fetch_all(&*backend.pool)
+    .await?;
+    Ok(recs.into())
+}
+
 #[async_trait]
 impl WorkspaceBackend for SqliteBackend {
     async fn add_workspace(
@@ -251,6 +414,13 @@ impl WorkspaceBackend for SqliteBackend {
         get_workspace_by_id_sqlite(&self, id).await
     }
 
+    async fn list_workspaces_page(
+        &self,
+        query: &ListingQuery,
+    ) -> Result<Page<Workspace>, BackendError> {
+        list_workspaces_page_sqlite(&self, query).await
+    }
+
     async fn list_workspace_by_url(
         &self,
         url: &str,
@@ -264,6 +434,34 @@ impl WorkspaceBackend for SqliteBackend {
     ) -> Result<Workspaces, BackendError> {
         list_workspaces_by_ids_sqlite(&self, ids).await
     }
+
+    async fn set_workspace_superceded_by(
+        &self,
+        id: i64,
+        superceded_by_id: Option<i64>,
+    ) -> Result<bool, BackendError> {
+        set_workspace_superceded_by_sqlite(&self, id, superceded_by_id).await
+    }
+
+    async fn archive_workspace(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        archive_workspace_sqlite(&self, id).await
+    }
+
+    async fn restore_workspace(
+        &self,
+        id: i64,
+    ) -> Result<bool, BackendError> {
+        restore_workspace_sqlite(&self, id).await
+    }
+
+    async fn list_archived_workspaces(
+        &self,
+    ) -> Result<Workspaces, BackendError> {
+        list_archived_workspaces_sqlite(&self).await
+    }
 }
 
 #[cfg(test)]
@@ -303,6 +501,7 @@ pub(crate) mod testing {
             created_ts: 1234567890,
             description: Some("".into()),
             long_description: Some("".into()),
+            archived_ts: None,
             exposures: None,
         };
         assert_eq!(workspace, answer);
@@ -365,9 +564,58 @@ pub(crate) mod testing {
             created_ts: 1234567890,
             description: Some("title".into()),
             long_description: Some("description".into()),
+            archived_ts: None,
             exposures: None,
         });
         Ok(())
     }
 
+    #[async_std::test]
+    async fn test_supersede() -> anyhow::Result<()> {
+        let backend = SqliteBackend::mc("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        let id1 = make_example_workspace(wb).await?;
+        let id2 = make_example_workspace(wb).await?;
+        assert!(wb.set_workspace_superceded_by(id1, Some(id2)).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.superceded_by_id, Some(id2));
+        assert_eq!(wb.get_workspace_by_id(id2).await?.superceded_by_id, None);
+        assert!(wb.set_workspace_superceded_by(id1, None).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.superceded_by_id, None);
+        assert!(!wb.set_workspace_superceded_by(id2 + 1, Some(id1)).await?);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_archive() -> anyhow::Result<()> {
+        let backend = SqliteBackend::mc("sqlite::memory:".into())
+            .await
+            .map_err(anyhow::Error::from_boxed)?;
+        let wb: &dyn WorkspaceBackend = &backend;
+        let id1 = make_example_workspace(wb).await?;
+        let id2 = make_example_workspace(wb).await?;
+        assert!(wb.list_archived_workspaces().await?.is_empty());
+
+        assert!(wb.archive_workspace(id1).await?);
+        assert!(!wb.archive_workspace(id1).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.archived_ts, Some(1234567890));
+        assert_eq!(
+            wb.list_workspaces().await?.iter().map(|w| w.id).collect::<Vec<_>>(),
+            [id2],
+        );
+        assert_eq!(
+            wb.list_archived_workspaces().await?.iter().map(|w| w.id).collect::<Vec<_>>(),
+            [id1],
+        );
+        // archived workspaces may still be looked up directly
+        assert_eq!(wb.list_workspace_by_ids(&[id1]).await?.len(), 1);
+
+        assert!(wb.restore_workspace(id1).await?);
+        assert!(!wb.restore_workspace(id1).await?);
+        assert_eq!(wb.get_workspace_by_id(id1).await?.archived_ts, None);
+        assert_eq!(wb.list_workspaces().await?.len(), 2);
+        Ok(())
+    }
+
 }
@@ -78,4 +78,31 @@ WHERE workspace_id = ?1
         .await?;
         Ok(recs)
     }
+
+    async fn list_latest_sync_records(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<WorkspaceSync>, BackendError> {
+        let recs = sqlx::query_as!(WorkspaceSync,
+            r#"
+SELECT
+    id AS "id!",
+    workspace_id AS "workspace_id!",
+    start AS "start!",
+    end,
+    status AS "status!"
+FROM (
+    SELECT id, workspace_id, start, end, status,
+        ROW_NUMBER() OVER (PARTITION BY workspace_id ORDER BY id DESC) AS rank
+    FROM workspace_sync
+)
+WHERE rank <= ?1
+ORDER BY workspace_id, id
+            "#,
+            limit,
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(recs)
+    }
 }
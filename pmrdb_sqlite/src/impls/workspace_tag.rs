@@ -18,7 +18,7 @@ impl WorkspaceTagBackend for SqliteBackend {
         name: &str,
         commit_id: &str,
     ) -> Result<i64, BackendError> {
-        let id = sqlx::query!(
+        let result = sqlx::query!(
             r#"
     INSERT INTO workspace_tag ( workspace_id, name, commit_id )
     VALUES ( ?1, ?2, ?3 )
@@ -29,12 +29,29 @@ impl WorkspaceTagBackend for SqliteBackend {
             commit_id,
         )
         .execute(&*self.pool)
-        .await?
-        .last_insert_rowid();
+        .await?;
+        if result.rows_affected() > 0 {
+            return Ok(result.last_insert_rowid());
+        }
+
+        // nothing was inserted as the tag was already indexed, so the
+        // existing id must be looked up instead.
+        let id = sqlx::query!(
+            r#"
+    SELECT id
+    FROM workspace_tag
+    WHERE workspace_id = ?1 AND name = ?2 AND commit_id = ?3
+            "#,
+            workspace_id,
+            name,
+            commit_id,
+        )
+        .map(|row| row.id)
+        .fetch_one(&*self.pool)
+        .await?;
 
         Ok(id)
     }
-    // TODO create test so that the unique indexes are done correctly
 
     async fn get_workspace_tags(
         &self,
@@ -3,7 +3,10 @@ use std::fmt::{
     Formatter,
     Result,
 };
-use super::BuildArgErrors;
+use super::{
+    BuildArgErrors,
+    TaskTemplateErrors,
+};
 
 impl Display for BuildArgErrors {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -17,3 +20,15 @@ impl Display for BuildArgErrors {
     }
 }
 
+
+impl Display for TaskTemplateErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "TaskTemplateErrors: [{}]",
+            self.0.iter()
+                .map(|e| e.to_string() + ", ")
+                .collect::<String>()
+        )
+    }
+}
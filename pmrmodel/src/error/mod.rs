@@ -14,6 +14,8 @@ pub enum ArgumentError {
     UnexpectedValue(i64),
     #[error("value expected but missing for argument id: {0}")]
     ValueExpected(i64),
+    #[error("multiple values provided for argument id: {0}")]
+    UnexpectedMultipleValues(i64),
 }
 
 #[derive(Debug, PartialEq, Error, Deserialize, Serialize)]
@@ -37,4 +39,35 @@ pub enum BuildArgError {
 #[derive(Debug, PartialEq, Error, Deserialize, Serialize)]
 pub struct BuildArgErrors(pub(crate) Vec<BuildArgError>);
 
+/// Violations of the invariants for a task template that would otherwise
+/// only surface as errors while building the arguments for its tasks;
+/// the arguments are identified by their position within the args.
+#[derive(Debug, PartialEq, Error, Deserialize, Serialize)]
+pub enum TaskTemplateError {
+    #[error("bin_path is empty")]
+    EmptyBinPath,
+    /// User input not limited to a fixed set of choices may otherwise
+    /// be used to pass arbitrary flags to the program.
+    #[error("args[{0}] accepts user input that is neither a fixed choice nor joined with a flag")]
+    UnconstrainedInput(usize),
+    #[error("args[{0}] has choice_source `{1}` that is not a known registry")]
+    UnknownChoiceSource(usize, String),
+    #[error("args[{0}] has fixed choices without a choice_source")]
+    FixedWithoutChoiceSource(usize),
+    #[error("args[{0}] has choices that are unused as choice_source is not the empty string")]
+    UnusedChoices(usize),
+    #[error("args[{0}] has no prompt but has a default that does not resolve to null")]
+    UnexpectedDefault(usize),
+    #[error("args[{0}] has an empty prompt and a choice_source but no default")]
+    DefaultExpected(usize),
+    /// Multiple values can only be provided by the user.
+    #[error("args[{0}] accepts multiple values but does not take user input")]
+    MultipleWithoutInput(usize),
+    #[error("args[{0}] has a multiple_separator but does not accept multiple values")]
+    SeparatorWithoutMultiple(usize),
+}
+
+#[derive(Debug, PartialEq, Error, Deserialize, Serialize)]
+pub struct TaskTemplateErrors(pub(crate) Vec<TaskTemplateError>);
+
 mod display;
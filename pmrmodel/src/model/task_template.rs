@@ -15,10 +15,12 @@ use pmrcore::{
         UserArg,
         UserArgs,
         UserChoiceRefs,
+        UserInput,
         UserInputMap,
     },
 };
 use std::{
+    borrow::Cow,
     iter::{
         FlatMap,
         Flatten,
@@ -36,11 +38,17 @@ use crate::error::{
     LookupError,
 };
 
+mod validate;
+
+pub use validate::validate_task_template;
+
 type ArgChunk<'a> = [Option<&'a str>; 2];
 
 #[derive(Debug, PartialEq)]
 pub struct TaskArgBuilder<'a> {
-    args: ArgChunk<'a>,
+    // owned values are only needed for the multiple values joined by
+    // the separator of the arg.
+    args: [Option<Cow<'a, str>>; 2],
     template: &'a TaskTemplateArg,
 }
 
@@ -60,6 +68,7 @@ pub struct UserArgRef<'a> {
     prompt: &'a str,
     default: Option<&'a str>,
     choice_fixed: bool,
+    multiple: bool,
     // ideal is to have a single reference to a slice, but for now just
     // have a vec of references, punt dealing with the lifetime of that
     // reference to later when we have a better idea on where the slice
@@ -101,10 +110,14 @@ impl<'a> TaskArgBuilder<'a> {
         template: &'a TaskTemplateArg,
     ) -> Self {
         Self {
-            args: args,
+            args: args.map(|arg| arg.map(Cow::Borrowed)),
             template: template,
         }
     }
+
+    fn take(&mut self, idx: usize) -> Cow<'a, str> {
+        self.args[idx].take().expect("arg must be checked to be present")
+    }
 }
 
 impl<'a> TaskBuilder<'a> {
@@ -213,17 +226,17 @@ impl<'a> Iterator for TaskArgBuilder<'a> {
                 self.args[0].is_some(),
                 self.args[1].is_some(),
             ) {
-                (true, true, true) => Some([
-                    self.args[0].take().unwrap().into(),
-                    self.args[1].take().unwrap().into(),
-                ].into()),
-                (false, true, true) => Some(self.args[0].take().unwrap().into()),
+                (true, true, true) => {
+                    let (flag, value) = (self.take(0), self.take(1));
+                    Some([flag.as_ref(), value.as_ref()].into())
+                }
+                (false, true, true) => Some(self.take(0).as_ref().into()),
                 (false, true, false) => {
                     // simply discard the arg as the final value is null.
                     self.args[0].take();
                     None
                 }
-                (_, _, true) => Some(self.args[1].take().unwrap().into()),
+                (_, _, true) => Some(self.take(1).as_ref().into()),
                 _ => None,
             }
         } else {
@@ -232,12 +245,12 @@ impl<'a> Iterator for TaskArgBuilder<'a> {
                 self.args[0].is_some(),
                 self.args[1].is_some(),
             ) {
-                (true, true, true) => Some([
-                    self.args[0].take().unwrap().into(),
-                    self.args[1].take().unwrap().into(),
-                ].into()),
-                (_, true, _) => Some(self.args[0].take().unwrap().into()),
-                (_, _, true) => Some(self.args[1].take().unwrap().into()),
+                (true, true, true) => {
+                    let (flag, value) = (self.take(0), self.take(1));
+                    Some([flag.as_ref(), value.as_ref()].into())
+                }
+                (_, true, _) => Some(self.take(0).as_ref().into()),
+                (_, _, true) => Some(self.take(1).as_ref().into()),
                 _ => None,
             }
         }
@@ -269,6 +282,7 @@ impl<'a, I: Iterator<Item=&'a TaskTemplateArg>, T> Iterator for UserArgBuilder<'
                         prompt: prompt,
                         default: arg.default.as_deref(),
                         choice_fixed: arg.choice_fixed,
+                        multiple: arg.multiple,
                         choices: self.choice_registry_cache.lookup(&arg)
                             .ok()
                             .as_deref()
@@ -322,6 +336,90 @@ impl<'a, T> TryFrom<InputArgLookup<'a, T>> for TaskArgBuilder<'a> {
     }
 }
 
+// Builds the chunks for the user input of an arg that may accept multiple
+// values; each value produces its own chunk (thus repeating the flag)
+// unless the arg has a separator, where the values resolved through the
+// choices are joined into a single value.
+fn arg_build_arg_chunks<'a, T>(
+    user_input: Option<&'a UserInput>,
+    task_template_arg: &'a TaskTemplateArg,
+    choice_registry_cache: ChoiceRegistryCache<'a, T>,
+) -> Result<Vec<TaskArgBuilder<'a>>, BuildArgError> {
+    let values = match (user_input, task_template_arg.multiple) {
+        (None, _) => Vec::new(),
+        (Some(UserInput::Single(value)), false) => return Ok(vec![
+            arg_build_arg_chunk(
+                Some(value),
+                task_template_arg,
+                choice_registry_cache,
+            )?
+        ]),
+        (Some(UserInput::Multiple(_)), false) => Err(
+            ArgumentError::UnexpectedMultipleValues(task_template_arg.id)
+        )?,
+        (Some(user_input), true) => user_input.values(),
+    };
+    match (values.is_empty(), task_template_arg.multiple_separator.as_deref()) {
+        (true, _) => Ok(vec![arg_build_arg_chunk(
+            None,
+            task_template_arg,
+            choice_registry_cache,
+        )?]),
+        (false, None) => values.into_iter()
+            .map(|value| arg_build_arg_chunk(
+                Some(value),
+                task_template_arg,
+                choice_registry_cache.clone(),
+            ))
+            .collect(),
+        (false, Some(separator)) => {
+            let mut resolved = Vec::new();
+            for value in values {
+                if let Some(value) = value_from_choices(
+                    value_from_arg_prompt(Some(value), task_template_arg)?,
+                    task_template_arg,
+                    choice_registry_cache.lookup(task_template_arg)?,
+                )? {
+                    resolved.push(value);
+                }
+            }
+            let joined = (!resolved.is_empty())
+                .then(|| resolved.join(separator));
+            let [flag, value] = value_to_argtuple(
+                joined.as_deref(),
+                task_template_arg,
+            )?;
+            Ok(vec![TaskArgBuilder {
+                args: [
+                    flag.map(|flag| Cow::Owned(flag.to_string())),
+                    value.map(|value| Cow::Owned(value.to_string())),
+                ],
+                template: task_template_arg,
+            }])
+        }
+    }
+}
+
+type InputArgsLookup<'a, T> = (
+    Option<&'a UserInput>,
+    &'a TaskTemplateArg,
+    ChoiceRegistryCache<'a, T>,
+);
+
+impl<'a, T> TryFrom<InputArgsLookup<'a, T>> for TaskArgBuilders<'a> {
+    type Error = BuildArgError;
+
+    fn try_from(
+        item: InputArgsLookup<'a, T>,
+    ) -> Result<TaskArgBuilders<'a>, BuildArgError> {
+        Ok(TaskArgBuilders(
+            arg_build_arg_chunks(item.0, item.1, item.2)?
+                .into_iter()
+                .flatten()
+        ))
+    }
+}
+
 impl From<&UserArgRef<'_>> for UserArg {
     fn from(item: &UserArgRef<'_>) -> Self {
         Self {
@@ -331,7 +429,8 @@ impl From<&UserArgRef<'_>> for UserArg {
             choice_fixed: item.choice_fixed,
             choices: item.choices
                 .as_ref()
-                .map(|choices| choices.into())
+                .map(|choices| choices.into()),
+            multiple: item.multiple,
         }
     }
 }
@@ -378,8 +477,8 @@ fn task_build_arg_chunk<'a, T>(
         Some(ref args) => {
             let (builders, errors): (Vec<_>, Vec<BuildArgError>) = args.iter()
                 .map(|arg| {
-                    arg_build_arg_chunk(
-                        user_input.get(&arg.id).map(|x| x.as_str()),
+                    arg_build_arg_chunks(
+                        user_input.get(&arg.id),
                         &arg,
                         choice_registry_cache.clone(),
                     )
@@ -393,7 +492,7 @@ fn task_build_arg_chunk<'a, T>(
             if errors.len() > 0 {
                 Err(BuildArgErrors(errors))
             } else {
-                Ok(builders)
+                Ok(builders.into_iter().flatten().collect::<Vec<_>>())
             }
         }
         None => Ok([].into())
@@ -1567,6 +1666,8 @@ mod test {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(999),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 999,
@@ -1596,6 +1697,8 @@ mod test {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(999),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 999,
@@ -1626,6 +1729,8 @@ mod test {
             created_ts: 1686715614,
             final_task_template_arg_id: Some(4242),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 12,
@@ -1644,6 +1749,8 @@ mod test {
                     choice_fixed: false,
                     choice_source: Some("".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 516,
@@ -1656,6 +1763,8 @@ mod test {
                     choice_fixed: false,
                     choice_source: Some("".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 777,
@@ -1674,6 +1783,8 @@ mod test {
                     choice_fixed: true,
                     choice_source: Some("git_repo".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 2424,
@@ -1695,6 +1806,8 @@ mod test {
                             "label": "no"
                         }
                     ]"#)?,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 4242,
@@ -1716,6 +1829,8 @@ mod test {
                             "label": "no"
                         }
                     ]"#)?,
+                    multiple: false,
+                    multiple_separator: None,
                 },
             ].into()),
         };
@@ -1751,6 +1866,7 @@ mod test {
                 "src/README.md".to_string(),
                 "src/main/example.model".to_string(),
             ].into()),
+            multiple: false,
         });
 
         let value: serde_json::Value = serde_json::from_str(&json_str)?;
@@ -1760,14 +1876,16 @@ mod test {
                 "prompt": "Heading for this publication",
                 "default": null,
                 "choice_fixed": false,
-                "choices": null
+                "choices": null,
+                "multiple": false
             },
             {
                 "id": 516,
                 "prompt": "Documentation file",
                 "default": null,
                 "choice_fixed": false,
-                "choices": null
+                "choices": null,
+                "multiple": false
             },
             {
                 "id": 894,
@@ -1777,7 +1895,8 @@ mod test {
                 "choices": [
                     ["src/README.md", false],
                     ["src/main/example.model", false]
-                ]
+                ],
+                "multiple": false
             },
             {
                 "id": 2424,
@@ -1787,7 +1906,8 @@ mod test {
                 "choices": [
                     ["no", false],
                     ["yes", false]
-                ]
+                ],
+                "multiple": false
             },
             {
                 "id": 4242,
@@ -1797,7 +1917,8 @@ mod test {
                 "choices": [
                     ["no", false],
                     ["yes", false]
-                ]
+                ],
+                "multiple": false
             }
         ]"#)?;
 
@@ -1815,6 +1936,8 @@ mod test {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(2),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 1,
@@ -1861,14 +1984,16 @@ mod test {
                 "prompt": "Raw text input from null choices",
                 "default": null,
                 "choice_fixed": false,
-                "choices": null
+                "choices": null,
+                "multiple": false
             },
             {
                 "id": 2,
                 "prompt": "Raw text input from empty choices",
                 "default": null,
                 "choice_fixed": false,
-                "choices": []
+                "choices": [],
+                "multiple": false
             }
         ]"#)?;
 
@@ -1888,6 +2013,8 @@ mod test {
             created_ts: 1234567890,
             final_task_template_arg_id: Some(2),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 1,
@@ -1934,14 +2061,16 @@ mod test {
                 "prompt": "Expect choices but none",
                 "default": null,
                 "choice_fixed": false,
-                "choices": null
+                "choices": null,
+                "multiple": false
             },
             {
                 "id": 2,
                 "prompt": "Unexpected choices from none source",
                 "default": null,
                 "choice_fixed": false,
-                "choices": null
+                "choices": null,
+                "multiple": false
             }
         ]"#)?;
 
@@ -1953,11 +2082,11 @@ mod test {
     #[test]
     fn test_process_user_inputs() {
         let user_input = UserInputMap::from([
-            (123, "The First Example Model".to_string()),
-            (516, "src/README.md".to_string()),
-            (894, "src/main/example.model".to_string()),
-            (2424, "no".to_string()),
-            (4242, "yes".to_string()),
+            (123, "The First Example Model".into()),
+            (516, "src/README.md".into()),
+            (894, "src/main/example.model".into()),
+            (2424, "no".into()),
+            (4242, "yes".into()),
         ]);
         let task_template = TaskTemplate {
             id: 3,
@@ -1966,6 +2095,8 @@ mod test {
             created_ts: 1686715614,
             final_task_template_arg_id: Some(4242),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 12,
@@ -1984,6 +2115,8 @@ mod test {
                     choice_fixed: false,
                     choice_source: Some("".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 516,
@@ -1996,6 +2129,8 @@ mod test {
                     choice_fixed: false,
                     choice_source: Some("".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 894,
@@ -2008,6 +2143,8 @@ mod test {
                     choice_fixed: true,
                     choice_source: Some("git_repo".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 2424,
@@ -2029,6 +2166,8 @@ mod test {
                             "label": "no"
                         }
                     ]"#).unwrap(),
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 4242,
@@ -2050,6 +2189,8 @@ mod test {
                             "label": "no"
                         }
                     ]"#).unwrap(),
+                    multiple: false,
+                    multiple_separator: None,
                 },
             ].into()),
         };
@@ -2094,9 +2235,9 @@ mod test {
     #[test]
     fn test_process_fail_validation_user_inputs() {
         let user_input = UserInputMap::from([
-            (1, "invalid_choice1".to_string()),
-            (2, "invalid_choice2".to_string()),
-            (3, "valid".to_string()),
+            (1, "invalid_choice1".into()),
+            (2, "invalid_choice2".into()),
+            (3, "valid".into()),
         ]);
         let task_template = TaskTemplate {
             id: 3,
@@ -2105,6 +2246,8 @@ mod test {
             created_ts: 1686715614,
             final_task_template_arg_id: Some(4242),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 1,
@@ -2117,6 +2260,8 @@ mod test {
                     choice_fixed: true,
                     choice_source: Some("git_repo".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 2,
@@ -2129,6 +2274,8 @@ mod test {
                     choice_fixed: true,
                     choice_source: Some("git_repo".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 3,
@@ -2146,6 +2293,8 @@ mod test {
                             "label": "valid"
                         }
                     ]"#).unwrap(),
+                    multiple: false,
+                    multiple_separator: None,
                 },
                 TaskTemplateArg {
                     id: 4,
@@ -2158,6 +2307,8 @@ mod test {
                     choice_fixed: false,
                     choice_source: None,
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
             ].into()),
         };
@@ -2189,6 +2340,128 @@ mod test {
         ]);
     }
 
+    #[test]
+    fn test_process_multiple_user_inputs() {
+        let task_template = TaskTemplate {
+            id: 4,
+            bin_path: "/usr/local/bin/model-processor".into(),
+            version_id: "1.4.0".into(),
+            created_ts: 1686715614,
+            final_task_template_arg_id: Some(4),
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some([
+                TaskTemplateArg {
+                    id: 1,
+                    task_template_id: 4,
+                    flag: Some("--input".into()),
+                    prompt: Some("The models to process".into()),
+                    choice_fixed: true,
+                    choice_source: Some("git_repo".into()),
+                    multiple: true,
+                    .. Default::default()
+                },
+                TaskTemplateArg {
+                    id: 2,
+                    task_template_id: 4,
+                    flag: Some("--tags=".into()),
+                    flag_joined: true,
+                    flag_omit_when_null: true,
+                    prompt: Some("Tags".into()),
+                    default: None,
+                    choice_fixed: true,
+                    choice_source: Some("".into()),
+                    choices: serde_json::from_str(r#"[
+                        {
+                            "to_arg": "alpha",
+                            "label": "a"
+                        },
+                        {
+                            "to_arg": "beta",
+                            "label": "b"
+                        },
+                        {
+                            "to_arg": null,
+                            "label": "none"
+                        }
+                    ]"#).unwrap(),
+                    multiple: true,
+                    multiple_separator: Some(",".into()),
+                },
+                TaskTemplateArg {
+                    id: 3,
+                    task_template_id: 4,
+                    flag: Some("--title".into()),
+                    prompt: Some("Title".into()),
+                    .. Default::default()
+                },
+            ].into()),
+        };
+
+        let files: Vec<String> = vec![
+            "src/README.md".into(),
+            "src/main/example.model".into(),
+        ];
+        let mut registry = PreparedChoiceRegistry::new();
+        registry.register("git_repo", files.into());
+        let build = |user_input: &UserInputMap| TaskArgBuilders::try_from((
+            user_input,
+            &task_template,
+            ChoiceRegistryCache::from(&registry as &dyn ChoiceRegistry<_>),
+        )).map(|builders| builders
+            .map(|a| a.arg.clone())
+            .collect::<Vec<_>>()
+        );
+
+        let user_input = UserInputMap::from([
+            (1, ["src/README.md", "src/main/example.model"].into()),
+            (2, ["a", "none", "b"].into()),
+            (3, "Example".into()),
+        ]);
+        assert_eq!(build(&user_input).unwrap(), &[
+            "--input",
+            "src/README.md",
+            "--input",
+            "src/main/example.model",
+            "--tags=alpha,beta",
+            "--title",
+            "Example",
+        ]);
+
+        // a single value remains acceptable for arguments that accept
+        // multiple values, and the flag is omitted when all the values
+        // resolved to null.
+        let user_input = UserInputMap::from([
+            (1, "src/README.md".into()),
+            (2, ["none"].into()),
+            (3, "Example".into()),
+        ]);
+        assert_eq!(build(&user_input).unwrap(), &[
+            "--input",
+            "src/README.md",
+            "--title",
+            "Example",
+        ]);
+
+        let user_input = UserInputMap::from([
+            (1, ["src/README.md", "no_such_file"].into()),
+            (2, ["a", "c"].into()),
+            (3, ["Example", "Another"].into()),
+        ]);
+        assert_eq!(build(&user_input).unwrap_err().0.as_slice(), &[
+            BuildArgError::LookupError(
+                LookupError::InvalidChoice(1, "no_such_file".to_string())
+            ),
+            BuildArgError::LookupError(
+                LookupError::InvalidChoice(2, "c".to_string())
+            ),
+            BuildArgError::ArgumentError(
+                ArgumentError::UnexpectedMultipleValues(3)
+            ),
+        ]);
+    }
+
     #[test]
     fn test_process_fail_validation_none_prompt_with_choice() -> anyhow::Result<()> {
         // Use case for having a choice source set up for an argument
@@ -2205,6 +2478,8 @@ mod test {
             created_ts: 1686715614,
             final_task_template_arg_id: Some(1),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 1,
@@ -2217,6 +2492,8 @@ mod test {
                     choice_fixed: true,
                     choice_source: Some("validation".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
             ].into()),
         };
@@ -2277,7 +2554,7 @@ mod test {
         // input - it should be ignored.
         {
             let user_input = UserInputMap::from([
-                (1, "invalid".to_string()),
+                (1, "invalid".into()),
             ]);
             let validation: TaskTemplateArgChoices = serde_json::from_str(r#"[
                 {
@@ -2328,6 +2605,8 @@ mod test {
             created_ts: 1686715614,
             final_task_template_arg_id: Some(1),
             superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
             args: Some([
                 TaskTemplateArg {
                     id: 1,
@@ -2340,6 +2619,8 @@ mod test {
                     choice_fixed: true,
                     choice_source: Some("validation".into()),
                     choices: None,
+                    multiple: false,
+                    multiple_separator: None,
                 },
             ].into()),
         };
@@ -2401,7 +2682,7 @@ mod test {
         // input - it should be ignored.
         {
             let user_input = UserInputMap::from([
-                (1, "invalid".to_string()),
+                (1, "invalid".into()),
             ]);
             let validation: TaskTemplateArgChoices = serde_json::from_str(r#"[
                 {
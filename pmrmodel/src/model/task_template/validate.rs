@@ -0,0 +1,383 @@
+use pmrcore::task_template::{
+    TaskTemplate,
+    TaskTemplateArg,
+};
+
+use crate::{
+    error::{
+        TaskTemplateError,
+        TaskTemplateErrors,
+    },
+    registry::split_choice_source,
+};
+
+/// Validate the task template against the invariants documented for the
+/// task template arguments, such that a template that would only fail
+/// while building the arguments for its tasks may be rejected upfront.
+///
+/// The names of the registries that may be referenced by `choice_source`
+/// are checked only if `choice_sources` is provided, as these are only
+/// known to the platform that builds the registries.  Only the name is
+/// checked for a `choice_source` that carries a parameter for its
+/// provider, i.e. the part before the `:`.
+pub fn validate_task_template(
+    task_template: &TaskTemplate,
+    choice_sources: Option<&[&str]>,
+) -> Result<(), TaskTemplateErrors> {
+    let mut errors = Vec::new();
+    if task_template.bin_path.is_empty() {
+        errors.push(TaskTemplateError::EmptyBinPath);
+    }
+    if let Some(args) = task_template.args.as_ref() {
+        for (idx, arg) in args.iter().enumerate() {
+            validate_arg(idx, arg, choice_sources, &mut errors);
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(TaskTemplateErrors(errors))
+    }
+}
+
+fn validate_arg(
+    idx: usize,
+    arg: &TaskTemplateArg,
+    choice_sources: Option<&[&str]>,
+    errors: &mut Vec<TaskTemplateError>,
+) {
+    let prompt = arg.prompt.as_deref();
+    let choice_source = arg.choice_source.as_deref();
+    let takes_input = matches!(prompt, Some(prompt) if !prompt.is_empty());
+
+    if takes_input
+        && !arg.choice_fixed
+        && !(arg.flag.is_some() && arg.flag_joined)
+    {
+        errors.push(TaskTemplateError::UnconstrainedInput(idx));
+    }
+
+    match (choice_source, choice_sources) {
+        (Some(source), Some(names)) if !source.is_empty()
+            && !names.contains(&split_choice_source(source).0) =>
+            errors.push(TaskTemplateError::UnknownChoiceSource(idx, source.to_string())),
+        _ => (),
+    }
+
+    if arg.choice_fixed
+        && choice_source.is_none()
+        && (takes_input || arg.default.is_some())
+    {
+        errors.push(TaskTemplateError::FixedWithoutChoiceSource(idx));
+    }
+
+    if arg.choices.as_ref().is_some_and(|choices| !choices.is_empty())
+        && choice_source != Some("")
+    {
+        errors.push(TaskTemplateError::UnusedChoices(idx));
+    }
+
+    // without a prompt the default is used as if it was provided by the
+    // user, which must resolve to null through the choices.
+    if let (None, Some(default)) = (prompt, arg.default.as_deref()) {
+        let resolves_null = match choice_source {
+            None => false,
+            Some("") => arg.choices.as_ref()
+                .and_then(|choices| choices.iter()
+                    .find(|choice| choice.label == default))
+                .is_some_and(|choice| choice.to_arg.is_none()),
+            // the choices from registries are only known at runtime.
+            Some(_) => true,
+        };
+        if !resolves_null {
+            errors.push(TaskTemplateError::UnexpectedDefault(idx));
+        }
+    }
+
+    if prompt == Some("")
+        && choice_source.is_some()
+        && arg.flag.is_some()
+        && arg.default.is_none()
+    {
+        errors.push(TaskTemplateError::DefaultExpected(idx));
+    }
+
+    if arg.multiple && !takes_input {
+        errors.push(TaskTemplateError::MultipleWithoutInput(idx));
+    }
+
+    if !arg.multiple && arg.multiple_separator.is_some() {
+        errors.push(TaskTemplateError::SeparatorWithoutMultiple(idx));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pmrcore::task_template::{
+        TaskTemplate,
+        TaskTemplateArg,
+    };
+    use crate::error::{
+        TaskTemplateError,
+        TaskTemplateErrors,
+    };
+    use super::validate_task_template;
+
+    fn task_template(args: Vec<TaskTemplateArg>) -> TaskTemplate {
+        TaskTemplate {
+            id: 0,
+            bin_path: "/bin/true".into(),
+            version_id: "1.0.0".into(),
+            created_ts: 0,
+            final_task_template_arg_id: None,
+            superceded_by_id: None,
+            timeout: None,
+            sandbox: None,
+            args: Some(args.into()),
+        }
+    }
+
+    #[test]
+    fn test_valid() -> anyhow::Result<()> {
+        let task_template: TaskTemplate = serde_json::from_str(r#"
+        {
+            "bin_path": "/bin/example",
+            "version_id": "1.0.0",
+            "final_task_template_arg_id": null,
+            "superceded_by_id": null,
+            "args": [
+                {
+                    "flag": "--output=",
+                    "flag_joined": true,
+                    "flag_omit_when_null": false,
+                    "prompt": "Output name",
+                    "default": "out",
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": null
+                },
+                {
+                    "flag": "--mode",
+                    "flag_joined": false,
+                    "flag_omit_when_null": true,
+                    "prompt": "Mode",
+                    "default": "fast",
+                    "choice_fixed": true,
+                    "choice_source": "",
+                    "choices": [
+                        {"to_arg": null, "label": "fast"},
+                        {"to_arg": "slow", "label": "slow"}
+                    ]
+                },
+                {
+                    "flag": null,
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": "",
+                    "default": "workspace_file_path",
+                    "choice_fixed": true,
+                    "choice_source": "workspace_file_path",
+                    "choices": null
+                },
+                {
+                    "flag": "--verbose",
+                    "flag_joined": false,
+                    "flag_omit_when_null": false,
+                    "prompt": null,
+                    "default": null,
+                    "choice_fixed": false,
+                    "choice_source": null,
+                    "choices": null
+                }
+            ]
+        }
+        "#)?;
+        assert_eq!(validate_task_template(&task_template, None), Ok(()));
+        assert_eq!(
+            validate_task_template(&task_template, Some(&["workspace_file_path"])),
+            Ok(()),
+        );
+        // only the name of the provider is checked for parameterized sources.
+        let mut task_template = task_template;
+        task_template.args.as_mut().unwrap()[2].choice_source =
+            Some("files_glob:*.cellml".into());
+        assert_eq!(
+            validate_task_template(&task_template, Some(&["files_glob"])),
+            Ok(()),
+        );
+        assert_eq!(
+            validate_task_template(&task_template, Some(&["files_glob:*.cellml"])),
+            Err(TaskTemplateErrors(vec![
+                TaskTemplateError::UnknownChoiceSource(2, "files_glob:*.cellml".into()),
+            ])),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unconstrained_input() {
+        let free = TaskTemplateArg {
+            prompt: Some("Value".into()),
+            .. Default::default()
+        };
+        let flagged = TaskTemplateArg {
+            flag: Some("--value".into()),
+            .. free.clone()
+        };
+        let joined = TaskTemplateArg {
+            flag_joined: true,
+            .. flagged.clone()
+        };
+        assert_eq!(
+            validate_task_template(&task_template(vec![free, flagged, joined]), None),
+            Err(TaskTemplateErrors(vec![
+                TaskTemplateError::UnconstrainedInput(0),
+                TaskTemplateError::UnconstrainedInput(1),
+            ])),
+        );
+    }
+
+    #[test]
+    fn test_choice_source() {
+        let args = vec![
+            TaskTemplateArg {
+                prompt: Some("".into()),
+                default: Some("files".into()),
+                choice_fixed: true,
+                choice_source: Some("files".into()),
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                prompt: Some("".into()),
+                default: Some("no_such_registry".into()),
+                choice_fixed: true,
+                choice_source: Some("no_such_registry".into()),
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                prompt: Some("Pick".into()),
+                default: Some("a".into()),
+                choice_fixed: true,
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                flag: Some("--flag".into()),
+                choice_source: Some("files".into()),
+                choices: Some(serde_json::from_str(r#"[
+                    {"to_arg": null, "label": "omit"}
+                ]"#).unwrap()),
+                .. Default::default()
+            },
+        ];
+        let task_template = task_template(args);
+        assert_eq!(
+            validate_task_template(&task_template, Some(&["files"])),
+            Err(TaskTemplateErrors(vec![
+                TaskTemplateError::UnknownChoiceSource(1, "no_such_registry".into()),
+                TaskTemplateError::FixedWithoutChoiceSource(2),
+                TaskTemplateError::UnusedChoices(3),
+            ])),
+        );
+        // the registry names are not checked without the list.
+        assert_eq!(
+            validate_task_template(&task_template, None),
+            Err(TaskTemplateErrors(vec![
+                TaskTemplateError::FixedWithoutChoiceSource(2),
+                TaskTemplateError::UnusedChoices(3),
+            ])),
+        );
+    }
+
+    #[test]
+    fn test_defaults() {
+        let choices = Some(serde_json::from_str(r#"[
+            {"to_arg": null, "label": "omit"},
+            {"to_arg": "--flag", "label": "keep"}
+        ]"#).unwrap());
+        let args = vec![
+            TaskTemplateArg {
+                default: Some("omit".into()),
+                choice_fixed: true,
+                choice_source: Some("".into()),
+                choices: choices.clone(),
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                default: Some("keep".into()),
+                choice_fixed: true,
+                choice_source: Some("".into()),
+                choices,
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                default: Some("value".into()),
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                flag: Some("--file".into()),
+                prompt: Some("".into()),
+                choice_fixed: true,
+                choice_source: Some("files".into()),
+                .. Default::default()
+            },
+        ];
+        assert_eq!(
+            validate_task_template(&task_template(args), None),
+            Err(TaskTemplateErrors(vec![
+                TaskTemplateError::UnexpectedDefault(1),
+                TaskTemplateError::UnexpectedDefault(2),
+                TaskTemplateError::DefaultExpected(3),
+            ])),
+        );
+
+        let empty = TaskTemplate {
+            bin_path: "".into(),
+            .. task_template(vec![])
+        };
+        assert_eq!(
+            validate_task_template(&empty, None),
+            Err(TaskTemplateErrors(vec![TaskTemplateError::EmptyBinPath])),
+        );
+    }
+
+    #[test]
+    fn test_multiple() {
+        let args = vec![
+            TaskTemplateArg {
+                flag: Some("--input".into()),
+                prompt: Some("Inputs".into()),
+                choice_fixed: true,
+                choice_source: Some("files".into()),
+                multiple: true,
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                flag: Some("--tags=".into()),
+                flag_joined: true,
+                prompt: Some("Tags".into()),
+                multiple: true,
+                multiple_separator: Some(",".into()),
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                flag: Some("--verbose".into()),
+                multiple: true,
+                .. Default::default()
+            },
+            TaskTemplateArg {
+                flag: Some("--name=".into()),
+                flag_joined: true,
+                prompt: Some("Name".into()),
+                multiple_separator: Some(",".into()),
+                .. Default::default()
+            },
+        ];
+        assert_eq!(
+            validate_task_template(&task_template(args), None),
+            Err(TaskTemplateErrors(vec![
+                TaskTemplateError::MultipleWithoutInput(2),
+                TaskTemplateError::SeparatorWithoutMultiple(3),
+            ])),
+        );
+    }
+}
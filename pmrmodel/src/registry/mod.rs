@@ -2,9 +2,17 @@ use pmrcore::task_template::MapToArgRef;
 
 mod prepared;
 mod cache;
+mod provider;
 
 pub use crate::registry::prepared::PreparedChoiceRegistry;
+pub use crate::registry::prepared::SizedMapToArgRef;
 pub use crate::registry::cache::ChoiceRegistryCache;
+pub use crate::registry::provider::{
+    split_choice_source,
+    ChoiceRegistryProvider,
+    ChoiceRegistryProviders,
+    ProvidedChoices,
+};
 
 pub trait ChoiceRegistry<T>: Send + Sync {
     fn register(&mut self, name: &str, registry: T);
@@ -0,0 +1,259 @@
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
+
+use crate::registry::{
+    ChoiceRegistry,
+    PreparedChoiceRegistry,
+    prepared::SizedMapToArgRef,
+};
+
+/// Split the `choice_source` of a task template argument into the name
+/// of the registry provider and its parameter, which follows the first
+/// `:` if present, e.g. `files_glob:*.cellml`.
+pub fn split_choice_source(choice_source: &str) -> (&str, Option<&str>) {
+    match choice_source.split_once(':') {
+        Some((name, parameter)) => (name, Some(parameter)),
+        None => (choice_source, None),
+    }
+}
+
+/// The choices provided for a registry, along with the keys that should
+/// be selected by default.
+pub struct ProvidedChoices {
+    choices: SizedMapToArgRef,
+    selected_keys: Option<Vec<String>>,
+}
+
+impl ProvidedChoices {
+    pub fn new(choices: impl Into<SizedMapToArgRef>) -> Self {
+        Self {
+            choices: choices.into(),
+            selected_keys: None,
+        }
+    }
+
+    pub fn select_keys(mut self, keys: Vec<String>) -> Self {
+        self.selected_keys = Some(keys);
+        self
+    }
+}
+
+/// Provides the registry of choices by name, prepared from a context `C`
+/// such as the exposure file the task template is being used with.
+pub trait ChoiceRegistryProvider<C, E>: Send + Sync {
+    /// The name of the registry, as referenced by the `choice_source`.
+    fn name(&self) -> &str;
+    /// A short description of the choices provided.
+    fn description(&self) -> &str;
+    /// A short description of the parameter required by the provider, for
+    /// those that require one.
+    fn parameter(&self) -> Option<&str> {
+        None
+    }
+    /// Provide the choices for the context; the parameter will only be
+    /// provided if the provider requires one.
+    fn provide(
+        &self,
+        context: &C,
+        parameter: Option<&str>,
+    ) -> Result<ProvidedChoices, E>;
+}
+
+/// The registry of the choice registry providers, keyed by their names.
+pub struct ChoiceRegistryProviders<C, E> {
+    providers: BTreeMap<String, Box<dyn ChoiceRegistryProvider<C, E>>>,
+}
+
+impl<C, E> Default for ChoiceRegistryProviders<C, E> {
+    fn default() -> Self {
+        Self {
+            providers: BTreeMap::new(),
+        }
+    }
+}
+
+impl<C, E> ChoiceRegistryProviders<C, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the provider under its name, returning the provider that
+    /// was previously registered under the same name, if any.
+    pub fn register(
+        &mut self,
+        provider: impl ChoiceRegistryProvider<C, E> + 'static,
+    ) -> Option<Box<dyn ChoiceRegistryProvider<C, E>>> {
+        self.providers.insert(provider.name().to_string(), Box::new(provider))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn ChoiceRegistryProvider<C, E>> {
+        self.providers.get(name).map(|provider| provider.as_ref())
+    }
+
+    /// The names of all the registered providers, in sorted order.
+    pub fn names(&self) -> Vec<&str> {
+        self.providers.keys().map(String::as_str).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn ChoiceRegistryProvider<C, E>> {
+        self.providers.values().map(|provider| provider.as_ref())
+    }
+
+    /// Whether the `choice_source` references a registered provider, with
+    /// the parameter provided if and only if the provider requires one.
+    pub fn provides(&self, choice_source: &str) -> bool {
+        let (name, parameter) = split_choice_source(choice_source);
+        self.get(name).is_some_and(|provider|
+            provider.parameter().is_some() == parameter.is_some()
+        )
+    }
+
+    /// Prepare the registry for the context, with the choices for each of
+    /// the `choice_sources` registered under the `choice_source` itself.
+    /// Sources not provided by any of the providers are skipped, such
+    /// that these are reported as missing when looked up.
+    pub fn prepare<'a>(
+        &self,
+        context: &C,
+        choice_sources: impl IntoIterator<Item = &'a str>,
+    ) -> Result<PreparedChoiceRegistry, E> {
+        let mut registry = PreparedChoiceRegistry::new();
+        let mut seen = HashSet::new();
+        for choice_source in choice_sources {
+            if !seen.insert(choice_source) || !self.provides(choice_source) {
+                continue;
+            }
+            let (name, parameter) = split_choice_source(choice_source);
+            let provided = self.providers[name].provide(context, parameter)?;
+            registry.register(choice_source, provided.choices);
+            if let Some(keys) = provided.selected_keys {
+                registry.select_keys(choice_source, keys);
+            }
+        }
+        Ok(registry)
+    }
+
+    /// The names of the providers that do not require a parameter, which
+    /// are the sources that may be prepared without being referenced.
+    pub fn default_sources(&self) -> impl Iterator<Item = &str> {
+        self.iter()
+            .filter(|provider| provider.parameter().is_none())
+            .map(|provider| provider.name())
+    }
+
+    /// Prepare the registry for the context with every provider that does
+    /// not require a parameter.
+    pub fn prepare_defaults(
+        &self,
+        context: &C,
+    ) -> Result<PreparedChoiceRegistry, E> {
+        self.prepare(context, self.default_sources())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use crate::registry::ChoiceRegistry;
+    use super::*;
+
+    struct Greeting;
+
+    impl ChoiceRegistryProvider<String, String> for Greeting {
+        fn name(&self) -> &str {
+            "greeting"
+        }
+
+        fn description(&self) -> &str {
+            "greets the context"
+        }
+
+        fn provide(
+            &self,
+            context: &String,
+            _: Option<&str>,
+        ) -> Result<ProvidedChoices, String> {
+            Ok(ProvidedChoices::new(HashMap::from([
+                ("hello".to_string(), format!("hello {context}")),
+                ("bye".to_string(), format!("bye {context}")),
+            ])).select_keys(vec!["hello".to_string()]))
+        }
+    }
+
+    struct Repeat;
+
+    impl ChoiceRegistryProvider<String, String> for Repeat {
+        fn name(&self) -> &str {
+            "repeat"
+        }
+
+        fn description(&self) -> &str {
+            "repeats the context"
+        }
+
+        fn parameter(&self) -> Option<&str> {
+            Some("the number of repeats")
+        }
+
+        fn provide(
+            &self,
+            context: &String,
+            parameter: Option<&str>,
+        ) -> Result<ProvidedChoices, String> {
+            let count = parameter
+                .unwrap_or_default()
+                .parse::<usize>()
+                .map_err(|e| e.to_string())?;
+            Ok(ProvidedChoices::new(vec![context.repeat(count)]))
+        }
+    }
+
+    #[test]
+    fn test_split_choice_source() {
+        assert_eq!(split_choice_source("files"), ("files", None));
+        assert_eq!(split_choice_source("files_glob:*.cellml"), ("files_glob", Some("*.cellml")));
+        assert_eq!(split_choice_source("files_glob:"), ("files_glob", Some("")));
+        assert_eq!(split_choice_source("a:b:c"), ("a", Some("b:c")));
+    }
+
+    #[test]
+    fn test_providers() {
+        let mut providers = ChoiceRegistryProviders::new();
+        assert!(providers.register(Repeat).is_none());
+        assert!(providers.register(Greeting).is_none());
+        assert!(providers.register(Greeting).is_some());
+        assert_eq!(providers.names(), ["greeting", "repeat"]);
+        assert_eq!(providers.default_sources().collect::<Vec<_>>(), ["greeting"]);
+
+        assert!(providers.provides("greeting"));
+        assert!(!providers.provides("greeting:1"));
+        assert!(providers.provides("repeat:2"));
+        assert!(!providers.provides("repeat"));
+        assert!(!providers.provides("no_such_provider"));
+
+        let context = "world".to_string();
+        let registry = providers.prepare_defaults(&context).unwrap();
+        assert!(registry.lookup("repeat").is_none());
+        let greeting = registry.lookup("greeting").unwrap();
+        assert_eq!(greeting.get("hello"), Some(&Some("hello world")));
+        assert_eq!(greeting.get("bye"), Some(&Some("bye world")));
+
+        let registry = providers.prepare(&context, [
+            "repeat:2",
+            "repeat",
+            "no_such_provider",
+        ]).unwrap();
+        assert!(registry.lookup("greeting").is_none());
+        assert!(registry.lookup("repeat").is_none());
+        assert!(registry.lookup("no_such_provider").is_none());
+        let repeat = registry.lookup("repeat:2").unwrap();
+        assert_eq!(repeat.get("worldworld"), Some(&Some("worldworld")));
+
+        assert_eq!(
+            providers.prepare(&context, ["repeat:two"]).err(),
+            Some("invalid digit found in string".to_string()),
+        );
+    }
+}
@@ -6,6 +6,7 @@ use casbin::{
 };
 use pmrcore::ac::{
     agent::Agent,
+    group::Group,
     role::Role,
     genpolicy::{
         AgentRole,
@@ -239,6 +240,17 @@ impl CasbinEnforcer {
             .unwrap_or("-".to_string())
     }
 
+    fn to_group(group: impl AsRef<str> + std::fmt::Display) -> String {
+        format!("g:{group}")
+    }
+
+    fn to_subject(agent: &Agent) -> String {
+        match agent {
+            Agent::Group(Group { name, .. }) => Self::to_group(name),
+            agent => Self::to_agent(<Agent as Into<Option<String>>>::into(agent.clone())),
+        }
+    }
+
     /// Grant agent the role, which will enable the agent the role for
     /// resources that have a policy attached for the role.
     pub async fn grant_agent_role(
@@ -294,6 +306,51 @@ impl CasbinEnforcer {
         ]).await
     }
 
+    /// Grant group specified role at resource.
+    /// Creates the relevant casbin grouping policy.
+    pub async fn grant_group_res(
+        &mut self,
+        group: impl AsRef<str> + std::fmt::Display,
+        role: Role,
+        resource: impl Into<String>,
+    ) -> Result<bool, casbin::Error> {
+        self.enforcer.add_named_grouping_policy("g", vec![
+            Self::to_group(group),
+            role.into(),
+            resource.into(),
+        ]).await
+    }
+
+    /// Revokes group specified role at resource.
+    /// Removes the relevant casbin grouping policy.
+    pub async fn revoke_group_res(
+        &mut self,
+        group: impl AsRef<str> + std::fmt::Display,
+        role: Role,
+        resource: impl Into<String>,
+    ) -> Result<bool, casbin::Error> {
+        self.enforcer.remove_named_grouping_policy("g", vec![
+            Self::to_group(group),
+            role.into(),
+            resource.into(),
+        ]).await
+    }
+
+    /// Grant agent the membership of the group at resource, such that
+    /// the agent will have the roles granted to the group at resource.
+    pub async fn grant_agent_group(
+        &mut self,
+        agent: &Agent,
+        group: impl AsRef<str> + std::fmt::Display,
+        resource: impl Into<String>,
+    ) -> Result<bool, casbin::Error> {
+        self.enforcer.add_named_grouping_policy("g", vec![
+            Self::to_subject(agent),
+            Self::to_group(group),
+            resource.into(),
+        ]).await
+    }
+
     /// Attach a policy.
     pub async fn attach_policy(
         &mut self,
@@ -329,8 +386,22 @@ impl CasbinEnforcer {
         for AgentRole { agent, role } in policy.agent_roles.into_iter() {
             self.grant_agent_role(agent, role).await?;
         }
-        for ResGrant { res, agent, role } in policy.res_grants.into_iter() {
-            self.grant_res(agent.as_ref(), role, res).await?;
+        for ResGrant { res, agent, group, role } in policy.res_grants.into_iter() {
+            match group {
+                Some(group) => {
+                    // the membership is only applicable at the resources
+                    // where the group has grants.
+                    if policy.agent_groups.contains(&group)
+                        && Self::to_subject(&policy.agent) != Self::to_group(&group)
+                    {
+                        self.grant_agent_group(&policy.agent, &group, res.clone()).await?;
+                    }
+                    self.grant_group_res(group, role, res).await?;
+                }
+                None => {
+                    self.grant_res(agent.as_ref(), role, res).await?;
+                }
+            }
         }
         for RolePermit { role, action } in policy.role_permits.into_iter() {
             self.attach_policy(role, policy.resource.clone(), action).await?;
@@ -341,12 +412,12 @@ impl CasbinEnforcer {
     /// Validates if the agent accessing the path has the required rights.
     fn casbin_enforce(
         &self,
-        agent: Option<impl AsRef<str> + std::fmt::Display>,
+        agent: &Agent,
         resource: impl AsRef<str>,
         action: impl AsRef<str>,
     ) -> Result<bool, casbin::Error> {
         self.enforcer.enforce((
-            Self::to_agent(agent).as_str(),
+            Self::to_subject(agent).as_str(),
             resource.as_ref(),
             action.as_ref(),
         ))
@@ -357,7 +428,7 @@ impl Enforcer for CasbinEnforcer {
     type Error = Error;
 
     fn enforce(&self, agent: &Agent, res: &str, action: &str) -> Result<bool, Self::Error> {
-        Ok(self.casbin_enforce(<Agent as Into<Option<String>>>::into(agent.clone()), res, action)?)
+        Ok(self.casbin_enforce(agent, res, action)?)
     }
 }
 
@@ -367,7 +438,7 @@ impl GenpolEnforcer for CasbinEnforcer {
     fn enforce(&self, action: &str) -> Result<bool, Self::Error> {
         if let Some(ref policy) = self.policy {
             Ok(self.casbin_enforce(
-                <Agent as Into<Option<String>>>::into(policy.agent.clone()),
+                &policy.agent,
                 policy.resource.clone(),
                 action,
             )?)
@@ -383,6 +454,7 @@ mod test {
     use pmrcore::ac::{
         agent::Agent,
         genpolicy::Policy,
+        group::Group,
         role::Role,
         user::User,
     };
@@ -535,6 +607,53 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn policy_usage_group() -> anyhow::Result<()> {
+        use pmrcore::ac::traits::Enforcer;
+        let mut security = CasbinBuilder::new().build().await?;
+        security.set_bulk_policy(serde_json::from_str(r#"{
+            "agent": {
+                "User": {
+                    "id": 0,
+                    "name": "alice",
+                    "created_ts": 1
+                }
+            },
+            "resource": "/item/1",
+            "agent_groups": ["lab"],
+            "agent_roles": [],
+            "res_grants": [
+                {"res": "/item/1", "agent": null, "group": "lab", "role": "Editor"},
+                {"res": "/item/1", "agent": null, "group": "admin", "role": "Owner"}
+            ],
+            "role_permits": [
+                {"role": "Editor", "action": "editor_view"},
+                {"role": "Editor", "action": "editor_edit"}
+            ]
+        }"#)?).await?;
+
+        // alice has the role granted to the group she is a member of
+        assert!(security.enforce(&mk_agent("alice"), "/item/1", "")?);
+        assert!(security.enforce(&mk_agent("alice"), "/item/1", "editor_view")?);
+        assert!(security.enforce(&mk_agent("alice"), "/item/1", "editor_edit")?);
+        // but not the one granted to the group she is not a member of
+        assert!(!security.enforce(&mk_agent("alice"), "/item/1", "grant_edit")?);
+        assert!(!security.enforce(&mk_agent("alice"), "/item/2", "")?);
+        // the membership only applies to alice
+        assert!(!security.enforce(&mk_agent("bob"), "/item/1", "")?);
+        assert!(!security.enforce(&Agent::Anonymous, "/item/1", "")?);
+        // the groups themselves have the role
+        let lab: Agent = Group { id: 1, name: "lab".to_string(), created_ts: 1 }.into();
+        let admin: Agent = Group { id: 2, name: "admin".to_string(), created_ts: 1 }.into();
+        assert!(security.enforce(&lab, "/item/1", "editor_edit")?);
+        assert!(!security.enforce(&lab, "/item/1", "grant_edit")?);
+        assert!(security.enforce(&admin, "/item/1", "grant_edit")?);
+        // a user with the same name as the group is not the group
+        assert!(!security.enforce(&mk_agent("lab"), "/item/1", "")?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn policy_usage_reviewer() -> anyhow::Result<()> {
         use pmrcore::ac::traits::Enforcer;
@@ -822,6 +941,43 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn comparison_policy_usage_group() -> anyhow::Result<()> {
+        let tester = EnforcerTester::new(
+            CasbinBuilder::new()
+                .anonymous_reader(true),
+            serde_json::from_str(r#"{
+                "agent": {
+                    "User": {
+                        "id": 0,
+                        "name": "alice",
+                        "created_ts": 1
+                    }
+                },
+                "resource": "/item/1",
+                "agent_groups": ["lab"],
+                "agent_roles": [],
+                "res_grants": [
+                    {"res": "/item/1", "agent": null, "group": "lab", "role": "Editor"},
+                    {"res": "/item/1", "agent": null, "group": "admin", "role": "Owner"}
+                ],
+                "role_permits": [
+                    {"role": "Editor", "action": "editor_view"},
+                    {"role": "Editor", "action": "editor_edit"},
+                    {"role": "Owner", "action": "grant_edit"}
+                ]
+            }"#)?
+        ).await?;
+
+        // only the grants to the groups the agent is a member of apply
+        assert!(tester.check_granted("editor_view").is_ok());
+        assert!(tester.check_granted("editor_edit").is_ok());
+        assert!(tester.check_denied("grant_edit").is_ok());
+        assert!(tester.check_denied("manage").is_ok());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn comparison_policy_usage_manager() -> anyhow::Result<()> {
         let tester = EnforcerTester::new(
@@ -155,6 +155,50 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn policy_enforcer_group() -> anyhow::Result<()> {
+        let agent: Agent = User {
+            id: 1,
+            name: "alice".to_owned(),
+            created_ts: 123456789,
+        }.into();
+        // the grant to the admin group is erroneously included.
+        let policy: Policy = serde_json::from_str(r#"{
+            "agent": {
+                "User": {
+                    "id": 1,
+                    "name": "alice",
+                    "created_ts": 123456789
+                }
+            },
+            "resource": "/item/1",
+            "agent_groups": ["lab"],
+            "agent_roles": [],
+            "res_grants": [
+                {"res": "/item/1", "agent": null, "group": "lab", "role": "Editor"},
+                {"res": "/item/1", "agent": null, "group": "admin", "role": "Owner"}
+            ],
+            "role_permits": [
+                {"role": "Editor", "action": "editor_view"},
+                {"role": "Owner", "action": "grant_edit"}
+            ]
+        }"#)?;
+        let enforcer: PolicyEnforcer = policy.into();
+        {
+            use pmrcore::ac::traits::Enforcer;
+            assert!(enforcer.enforce(&agent, "/item/1", "editor_view")?);
+            assert!(!enforcer.enforce(&agent, "/item/1", "grant_edit")?);
+        }
+        {
+            use pmrcore::ac::traits::GenpolEnforcer;
+            assert!(enforcer.enforce("editor_view")?);
+            // roles granted to groups the agent is not a member of are
+            // not applied.
+            assert!(!enforcer.enforce("grant_edit")?);
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn builder_anonymous_reader() -> anyhow::Result<()> {
         let policy: Policy = serde_json::from_str(r#"{
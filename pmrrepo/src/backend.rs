@@ -1,17 +1,24 @@
 use pmrcore::platform::MCPlatform;
 use std::{
+    fs,
     path::PathBuf,
     sync::Arc,
 };
 
 use crate::{
-    error::PmrRepoError,
+    error::{
+        ExecutionError,
+        PmrRepoError,
+    },
     handle::{
         Handle,
         GitHandle,
     },
 };
 
+/// The directory under the repo root where archived repositories are kept.
+const ARCHIVE_DIR: &str = ".archive";
+
 #[derive(Clone)]
 pub struct Backend {
     pub db_platform: Arc<dyn MCPlatform>,
@@ -33,11 +40,97 @@ impl Backend {
         Ok(())
     }
 
+    /// The directory of the repository for the workspace under the repo
+    /// root; this may not exist if the workspace was never synchronized.
+    pub fn repo_dir(&self, workspace_id: i64) -> PathBuf {
+        self.repo_root.join(workspace_id.to_string())
+    }
+
+    /// Initialize an empty bare repository for the workspace if it does
+    /// not have one, such that content may be pushed into it, returning
+    /// the directory of the repository.
+    ///
+    /// This will not apply to archived workspaces.
+    pub async fn init_workspace_repo(&self, workspace_id: i64) -> Result<PathBuf, PmrRepoError> {
+        let workspace = self.db_platform.get_workspace_by_id(workspace_id).await?;
+        if workspace.archived_ts.is_some() {
+            Err(ExecutionError::Archived { workspace_id })?
+        }
+        let repo_dir = self.repo_dir(workspace_id);
+        if !repo_dir.exists() {
+            git2::Repository::init_bare(&repo_dir)?;
+        }
+        Ok(repo_dir)
+    }
+
+    /// Apply the steps that follow a synchronization for the workspace
+    /// after its repository was updated directly, e.g. from a push.
+    pub async fn receive_workspace(&self, workspace_id: i64) -> Result<(), PmrRepoError> {
+        let workspace = self.db_platform.get_workspace(workspace_id).await?;
+        let handle = Handle::new(self, self.repo_root.clone(), workspace);
+        handle.record_update().await?;
+        Ok(())
+    }
+
     pub async fn git_handle<'a>(&'a self, workspace_id: i64) -> Result<GitHandle<'a>, PmrRepoError> {
         let workspace = self.db_platform.get_workspace(workspace_id).await?;
         Ok(GitHandle::new(&self, self.repo_root.clone(), workspace))
     }
 
+    /// Archive the workspace, moving its repository (if one had been
+    /// synchronized) out of the repo root and into the archive directory.
+    ///
+    /// Returns `false` if the workspace is already archived.
+    pub async fn archive_workspace(&self, workspace_id: i64) -> Result<bool, PmrRepoError> {
+        let workspace = self.db_platform.get_workspace_by_id(workspace_id).await?;
+        if workspace.archived_ts.is_some() {
+            return Ok(false);
+        }
+        let repo_dir = self.repo_dir(workspace_id);
+        let archive_dir = self.repo_root.join(ARCHIVE_DIR).join(workspace_id.to_string());
+        let moved = repo_dir.exists();
+        if moved {
+            fs::create_dir_all(self.repo_root.join(ARCHIVE_DIR))?;
+            fs::rename(&repo_dir, &archive_dir)?;
+        }
+        match self.db_platform.archive_workspace(workspace_id).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                // put the repository back as the workspace remains active.
+                if moved {
+                    fs::rename(&archive_dir, &repo_dir)?;
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Restore an archived workspace, moving its repository back under the
+    /// repo root.
+    ///
+    /// Returns `false` if the workspace was not archived.
+    pub async fn restore_workspace(&self, workspace_id: i64) -> Result<bool, PmrRepoError> {
+        let workspace = self.db_platform.get_workspace_by_id(workspace_id).await?;
+        if workspace.archived_ts.is_none() {
+            return Ok(false);
+        }
+        let repo_dir = self.repo_dir(workspace_id);
+        let archive_dir = self.repo_root.join(ARCHIVE_DIR).join(workspace_id.to_string());
+        let moved = archive_dir.exists();
+        if moved {
+            fs::rename(&archive_dir, &repo_dir)?;
+        }
+        match self.db_platform.restore_workspace(workspace_id).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                if moved {
+                    fs::rename(&repo_dir, &archive_dir)?;
+                }
+                Err(e.into())
+            }
+        }
+    }
+
     pub fn platform(&self) -> &(dyn MCPlatform + Send + Sync) {
         self.db_platform.as_ref()
     }
@@ -24,11 +24,17 @@ pub enum PmrRepoError {
 
 #[derive(Debug, Error)]
 pub enum GixError {
+    #[error(transparent)]
+    Blame(#[from] gix::blame::Error),
+    #[error(transparent)]
+    BlameFile(#[from] gix::repository::blame_file::Error),
     #[error(transparent)]
     ConfigFileInitFromPath(#[from] gix::config::file::init::from_paths::Error),
     #[error(transparent)]
     DateParse(#[from] gix::date::parse::Error),
     #[error(transparent)]
+    DiffTreeToTree(#[from] gix::repository::diff_tree_to_tree::Error),
+    #[error(transparent)]
     HashDecode(#[from] gix::hash::decode::Error),
     #[error(transparent)]
     IndexFileInit(#[from] gix::index::file::init::Error),
@@ -80,10 +86,20 @@ pub enum ContentError {
         workspace_id: i64,
         url: String,
     },
+    #[error("workspace `{workspace_id}` references a repository at `{url}` \n\
+             but access to its workspace was denied")]
+    SubmoduleDenied {
+        workspace_id: i64,
+        url: String,
+    },
 }
 
 #[derive(Debug, PartialEq, Error, Deserialize, Serialize)]
 pub enum ExecutionError {
+    #[error("workspace `{workspace_id}`: is archived")]
+    Archived {
+        workspace_id: i64,
+    },
     #[error("workspace `{workspace_id}`: failed to synchronize with \
              remote `{remote}`: {msg}")]
     Synchronize {
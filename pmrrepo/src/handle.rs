@@ -12,6 +12,10 @@ mod impls;
 mod git;
 
 pub use git::{
+    archive::{
+        Archive,
+        SubmoduleAccess,
+    },
     GitHandle,
     GitResultTarget,
     GitHandleResult,
@@ -34,6 +34,7 @@ pub struct GitHandleResult<'repo> {
     pub(super) workspace: &'repo WorkspaceRef<'repo>,
 }
 
+pub(super) mod archive;
 pub(super) mod error;
 mod impls;
 pub(super) mod util;
@@ -0,0 +1,348 @@
+use flate2::{
+    Compression,
+    write::GzEncoder,
+};
+use futures::future::BoxFuture;
+use gix::{
+    ObjectId,
+    ThreadSafeRepository,
+};
+use pmrcore::repo::ArchiveFormat;
+use std::io::{
+    self,
+    Write,
+};
+use zip::{
+    CompressionMethod,
+    DateTime,
+    ZipWriter,
+    write::SimpleFileOptions,
+};
+
+use crate::error::{
+    GixError,
+    PmrRepoError,
+};
+
+/// Decides whether the contents of a workspace, as resolved for one of
+/// the submodules, may be included in an `Archive`.
+pub trait SubmoduleAccess: Send + Sync {
+    fn permits(&self, workspace_id: i64) -> BoxFuture<'_, bool>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum EntryType {
+    Dir,
+    File,
+    Executable,
+    Link,
+}
+
+#[derive(Debug)]
+pub(crate) struct ArchiveEntry {
+    pub(crate) path: String,
+    pub(crate) kind: EntryType,
+    /// The index into `Archive::repos` and the id of the blob that
+    /// provides the data for this entry; `None` for directories.
+    pub(crate) source: Option<(usize, ObjectId)>,
+}
+
+/// The entries of a tree at some commit, gathered from one or more
+/// repositories (when submodules are resolved), ready to be written out
+/// in any of the supported `ArchiveFormat`s.
+///
+/// All lookups that may fail due to invalid paths or missing workspaces
+/// were done while gathering the entries, such that writing will only
+/// fail if the underlying writer fails.
+pub struct Archive {
+    pub(crate) commit_id: String,
+    pub(crate) timestamp: i64,
+    pub(crate) repos: Vec<ThreadSafeRepository>,
+    pub(crate) entries: Vec<ArchiveEntry>,
+}
+
+impl Archive {
+    pub(crate) fn new(commit_id: String, timestamp: i64) -> Self {
+        Self {
+            commit_id,
+            timestamp,
+            repos: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add_repo(&mut self, repo: ThreadSafeRepository) -> usize {
+        self.repos.push(repo);
+        self.repos.len() - 1
+    }
+
+    /// The id of the commit that the archive was produced from.
+    pub fn commit_id(&self) -> &str {
+        &self.commit_id
+    }
+
+    /// The paths of all entries, with directories suffixed with `/`.
+    pub fn paths(&self) -> Vec<String> {
+        self.entries.iter()
+            .map(|entry| match entry.kind {
+                EntryType::Dir => format!("{}/", entry.path),
+                _ => entry.path.clone(),
+            })
+            .collect()
+    }
+
+    /// Write out the archive in the specified format, with every entry
+    /// placed under the provided prefix (e.g. `"name/"`).
+    pub fn write(
+        &self,
+        format: ArchiveFormat,
+        prefix: &str,
+        writer: impl Write,
+    ) -> Result<(), PmrRepoError> {
+        match format {
+            ArchiveFormat::Zip => {
+                let (time, date) = dos_datetime(self.timestamp);
+                let options = SimpleFileOptions::default()
+                    .last_modified_time(DateTime::try_from_msdos(date, time)
+                        .unwrap_or_default());
+                let mut zip = ZipWriter::new_stream(writer);
+                self.each_entry(prefix, |path, kind, data| {
+                    match kind {
+                        EntryType::Dir => zip.add_directory(path, options
+                            .unix_permissions(0o755))?,
+                        EntryType::Link => zip.add_symlink(
+                            path,
+                            String::from_utf8_lossy(data),
+                            options,
+                        )?,
+                        EntryType::File | EntryType::Executable => {
+                            zip.start_file(path, options
+                                .compression_method(CompressionMethod::Deflated)
+                                .unix_permissions(mode(kind))
+                                // the size is known up front, so only the
+                                // entries that require ZIP64 will use it.
+                                .large_file(data.len() as u64 >= u32::MAX as u64))?;
+                            zip.write_all(data)?;
+                        }
+                    }
+                    Ok(())
+                })?;
+                zip.finish().map_err(io::Error::from)?;
+            }
+            ArchiveFormat::TarGz => {
+                let mtime = self.timestamp.max(0) as u64;
+                let mut tar = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+                self.each_entry(prefix, |path, kind, data| {
+                    let mut header = tar::Header::new_gnu();
+                    header.set_mtime(mtime);
+                    header.set_mode(mode(kind));
+                    match kind {
+                        EntryType::Link => {
+                            header.set_entry_type(tar::EntryType::Symlink);
+                            header.set_size(0);
+                            tar.append_link(&mut header, path, &*String::from_utf8_lossy(data))
+                        }
+                        EntryType::Dir => {
+                            header.set_entry_type(tar::EntryType::Directory);
+                            header.set_size(0);
+                            tar.append_data(&mut header, path, io::empty())
+                        }
+                        EntryType::File | EntryType::Executable => {
+                            header.set_entry_type(tar::EntryType::Regular);
+                            header.set_size(data.len() as u64);
+                            tar.append_data(&mut header, path, data)
+                        }
+                    }
+                })?;
+                tar.into_inner()?.finish()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn each_entry(
+        &self,
+        prefix: &str,
+        mut f: impl FnMut(&str, EntryType, &[u8]) -> io::Result<()>,
+    ) -> Result<(), PmrRepoError> {
+        let repos = self.repos.iter()
+            .map(ThreadSafeRepository::to_thread_local)
+            .collect::<Vec<_>>();
+        for entry in self.entries.iter() {
+            let path = format!("{prefix}{}", entry.path);
+            match entry.source {
+                Some((repo, id)) => {
+                    let object = repos[repo].find_object(id)
+                        .map_err(GixError::from)?;
+                    f(&path, entry.kind, &object.data)?;
+                }
+                None => f(&path, entry.kind, &[])?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn mode(kind: EntryType) -> u32 {
+    match kind {
+        EntryType::Dir | EntryType::Executable => 0o755,
+        EntryType::File => 0o644,
+        EntryType::Link => 0o777,
+    }
+}
+
+/// Convert the timestamp to the MS-DOS time and date used by zip, clamped
+/// to the range that may be represented.
+fn dos_datetime(timestamp: i64) -> (u16, u16) {
+    let days = timestamp.div_euclid(86400);
+    let secs = timestamp.rem_euclid(86400);
+    // civil date from days since the unix epoch
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    match year {
+        ..1980 => (0, (1 << 5) | 1),
+        2108.. => ((23 << 11) | (59 << 5) | 29, (127 << 9) | (12 << 5) | 31),
+        _ => (
+            ((secs / 3600) << 11 | (secs % 3600 / 60) << 5 | (secs % 60 / 2)) as u16,
+            ((year - 1980) << 9 | month << 5 | day) as u16,
+        ),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use flate2::read::GzDecoder;
+    use std::io::{
+        Cursor,
+        Read,
+    };
+    use tempfile::TempDir;
+    use test_pmr::repo::GitObj;
+    use super::*;
+
+    /// Read the entries from a tar.gz as `(path, typeflag, data)`, with
+    /// the link target as the data for symlinks.
+    pub(crate) fn read_tar_gz(bytes: &[u8]) -> Vec<(String, u8, Vec<u8>)> {
+        let mut tar = tar::Archive::new(GzDecoder::new(bytes));
+        tar.entries().unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let path = String::from_utf8(entry.path_bytes().to_vec()).unwrap();
+                let typeflag = entry.header().entry_type().as_byte();
+                let mut data = Vec::new();
+                match entry.link_name_bytes() {
+                    Some(link) => data.extend_from_slice(&link),
+                    None => {
+                        entry.read_to_end(&mut data).unwrap();
+                    }
+                }
+                (path, typeflag, data)
+            })
+            .collect()
+    }
+
+    /// Read the entries from a zip as `(name, unix mode, data)`.
+    pub(crate) fn read_zip(bytes: &[u8]) -> Vec<(String, u32, Vec<u8>)> {
+        let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        (0..zip.len())
+            .map(|i| {
+                let mut file = zip.by_index(i).unwrap();
+                let mut data = Vec::new();
+                file.read_to_end(&mut data).unwrap();
+                (file.name().to_string(), file.unix_mode().unwrap(), data)
+            })
+            .collect()
+    }
+
+    fn archive() -> anyhow::Result<(TempDir, Archive)> {
+        let tempdir = TempDir::new()?;
+        let (_, repo) = test_pmr::repo::repo_init(
+            None, Some(&tempdir.path().join("1")), Some(1666666700))?;
+        test_pmr::repo::append_commit_from_objects(
+            &repo, Some(1666666700), Some("files"), vec![
+            GitObj::Blob("README", "A readme.\n"),
+            GitObj::Blob("target", "target"),
+        ])?;
+        let long_name = "a_rather_long_name_".repeat(6);
+        let readme = repo.rev_parse_single("HEAD:README")?.detach();
+        let target = repo.rev_parse_single("HEAD:target")?.detach();
+
+        let mut archive = Archive::new("0".repeat(40), 1666666700);
+        let index = archive.add_repo(repo.into_sync());
+        archive.entries = vec![
+            ArchiveEntry {
+                path: "README".to_string(),
+                kind: EntryType::File,
+                source: Some((index, readme)),
+            },
+            ArchiveEntry {
+                path: "dir".to_string(),
+                kind: EntryType::Dir,
+                source: None,
+            },
+            ArchiveEntry {
+                path: format!("dir/{long_name}"),
+                kind: EntryType::Executable,
+                source: Some((index, readme)),
+            },
+            ArchiveEntry {
+                path: "link".to_string(),
+                kind: EntryType::Link,
+                source: Some((index, target)),
+            },
+        ];
+        Ok((tempdir, archive))
+    }
+
+    #[test]
+    fn test_write_tar_gz() -> anyhow::Result<()> {
+        let (_tempdir, archive) = archive()?;
+        let long_name = "a_rather_long_name_".repeat(6);
+        let mut buffer = Vec::new();
+        archive.write(ArchiveFormat::TarGz, "model/", &mut buffer)?;
+        assert_eq!(read_tar_gz(&buffer), [
+            ("model/README".to_string(), b'0', b"A readme.\n".to_vec()),
+            ("model/dir".to_string(), b'5', vec![]),
+            (format!("model/dir/{long_name}"), b'0', b"A readme.\n".to_vec()),
+            ("model/link".to_string(), b'2', b"target".to_vec()),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_zip() -> anyhow::Result<()> {
+        let (_tempdir, archive) = archive()?;
+        let long_name = "a_rather_long_name_".repeat(6);
+        let mut buffer = Vec::new();
+        archive.write(ArchiveFormat::Zip, "", &mut buffer)?;
+        assert_eq!(read_zip(&buffer), [
+            ("README".to_string(), 0o100644, b"A readme.\n".to_vec()),
+            ("dir/".to_string(), 0o040755, vec![]),
+            (format!("dir/{long_name}"), 0o100755, b"A readme.\n".to_vec()),
+            ("link".to_string(), 0o120777, b"target".to_vec()),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dos_datetime() {
+        // 2022-10-25 03:38:20 UTC
+        assert_eq!(
+            dos_datetime(1666669100),
+            ((3 << 11) | (38 << 5) | 10, (42 << 9) | (10 << 5) | 25),
+        );
+        // 2000-02-29 23:59:59 UTC
+        assert_eq!(
+            dos_datetime(951868799),
+            ((23 << 11) | (59 << 5) | 29, (20 << 9) | (2 << 5) | 29),
+        );
+        assert_eq!(dos_datetime(0), (0, (1 << 5) | 1));
+    }
+}
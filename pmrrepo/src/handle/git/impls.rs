@@ -4,19 +4,25 @@ use futures::stream::{
     futures_unordered::FuturesUnordered,
 };
 use gix::{
-    object::Kind,
-    objs::{
-        CommitRef,
-        tree::EntryKind,
+    object::{
+        Kind,
+        tree::diff::ChangeDetached,
     },
+    objs::tree::EntryKind,
     revision::walk::Sorting,
-    traverse::commit::simple::CommitTimeOrder,
+    traverse::{
+        commit::simple::CommitTimeOrder,
+        tree::Recorder,
+    },
     Commit,
     Repository,
 };
 use pmrcore::{
     git::PathObjectDetached,
     repo::{
+        BlameHunk,
+        BlameInfo,
+        DiffResult,
         LogEntryInfo,
         LogInfo,
         PathObjectInfo,
@@ -33,6 +39,7 @@ use pmrcore::{
     },
 };
 use std::{
+    collections::HashMap,
     io::Write,
     ops::Deref,
     path::{
@@ -53,6 +60,12 @@ use crate::{
 };
 
 use super::{
+    archive::{
+        Archive,
+        ArchiveEntry,
+        EntryType,
+        SubmoduleAccess,
+    },
     Handle,
     GitHandle,
     GitHandleResult,
@@ -124,7 +137,9 @@ impl<'repo> GitHandle<'repo> {
             })
     }
 
-    fn repo_tags(&self) -> Result<Vec<(String, String)>, PmrRepoError> {
+    /// Returns the tags of the repository by their full reference names,
+    /// along with the ids of the objects they point to.
+    pub fn repo_tags(&self) -> Result<Vec<(String, String)>, PmrRepoError> {
         Ok(self.repo()?
             .references()
             .map_err(GixError::from)?
@@ -212,74 +227,7 @@ impl<'repo> GitHandle<'repo> {
                 workspace: &self.workspace,
             }),
             Some(commit) => {
-                let tree = commit
-                    .tree_id().map_err(GixError::from)?
-                    .object().map_err(GixError::from)?;
-
-                let target = match path.as_ref().map(|s| s.as_ref()) {
-                    Some("") | Some("/") | None => {
-                        info!("No path provided; using root tree entry");
-                        GitResultTarget::Object(
-                            PathObjectDetached::new("".to_string(), tree.into()),
-                        )
-                    },
-                    Some(s) => {
-                        let path = s.strip_prefix('/').unwrap_or(&s);
-                        let mut comps = Path::new(path).components();
-                        let mut curr_path = PathBuf::new();
-                        let mut object = Some(tree);
-                        let mut target: Option<GitResultTarget> = None;
-
-                        while let Some(component) = comps.next() {
-                            let entry = object
-                                .expect("iteration has this set or look breaked")
-                                .try_into_tree().map_err(GixError::from)?
-                                .peel_to_entry_by_path(
-                                    Path::new(&component)
-                                ).map_err(GixError::from)?
-                                .ok_or_else(
-                                    || PmrRepoError::from(PathError::NoSuchPath {
-                                        workspace_id: workspace_id,
-                                        oid: commit.id.to_string(),
-                                        path: path.to_string(),
-                                    })
-                                )?;
-                            curr_path.push(component);
-                            match entry.mode() {
-                                k if (k == EntryKind::Commit.into()) => {
-                                    info!("entry {:?} is a commit", entry.id());
-                                    let location = get_submodule_target(
-                                        &commit,
-                                        workspace_id,
-                                        curr_path.to_str().unwrap(),
-                                    )?;
-                                    target = Some(GitResultTarget::RemoteInfo(RemoteInfo {
-                                        location: location,
-                                        commit: entry.id().to_string(),
-                                        subpath: comps.as_path().to_str().unwrap().to_string(),
-                                        path: path.to_string(),
-                                    }));
-                                    object = None;
-                                    break;
-                                }
-                                _ => ()
-                            }
-                            let next_object = entry
-                                .object().map_err(GixError::from)?;
-                            info!("got {} {:?}", next_object.kind, &next_object);
-                            object = Some(next_object);
-                        };
-                        match object {
-                            Some(object) =>
-                                GitResultTarget::Object(
-                                    PathObjectDetached::new(path.to_string(), object.into())
-                                ),
-                            None =>
-                                // Only way object is None is have target set.
-                                target.expect("to be a RemoteInfo"),
-                        }
-                    },
-                };
+                let target = path_target(&commit, workspace_id, path.as_deref())?;
                 Ok(GitHandleResult {
                     backend: &self.backend,
                     repo: &self.repo.get()
@@ -316,6 +264,35 @@ impl<'repo> GitHandle<'repo> {
         // TODO need to provide a way to skip
         count: Option<usize>,
         full_details: bool,
+    ) -> Result<LogInfo, PmrRepoError> {
+        self.log_entries(commit_id, path, false, None, count, full_details)
+    }
+
+    /// The page of the log starting from the commit identified by
+    /// `commit_id` (default `HEAD`), with up to `count` entries that
+    /// follow the commit identified by `after`.  If `path` is provided,
+    /// only the commits that changed the path will be included, and if
+    /// `follow` is set, the path will be followed through the commits
+    /// that renamed the file at the path.
+    pub fn history(
+        &self,
+        commit_id: Option<&str>,
+        path: Option<&str>,
+        follow: bool,
+        after: Option<&str>,
+        count: Option<usize>,
+    ) -> Result<LogInfo, PmrRepoError> {
+        self.log_entries(commit_id, path, follow, after, count, false)
+    }
+
+    fn log_entries(
+        &self,
+        commit_id: Option<&str>,
+        path: Option<&str>,
+        follow: bool,
+        after: Option<&str>,
+        count: Option<usize>,
+        full_details: bool,
     ) -> Result<LogInfo, PmrRepoError> {
         let signature_ref = if full_details {
             format_signature_ref
@@ -326,39 +303,146 @@ impl<'repo> GitHandle<'repo> {
         let repo = self.repo()?;
         let commit = get_commit(&repo, workspace_id, commit_id)?;
         if commit.is_none() {
-            return Ok(LogInfo { entries: Vec::new() });
+            return Ok(LogInfo { entries: Vec::new(), next: None });
         }
         let commit = commit.expect("None case should have been handled");
-        let mut filter = PathFilter::new(&repo, path);
-        let log_entry_iter = repo.rev_walk([commit.id])
+        let after = after
+            .map(|after| get_commit(&repo, workspace_id, Some(after)))
+            .transpose()?
+            .flatten()
+            .map(|commit| commit.id);
+        let path = path
+            .map(|path| path.trim_matches('/'))
+            .filter(|path| !path.is_empty());
+        let mut filter = PathFilter::new(&repo, path, follow);
+        // the walk is restarted from the top for every page such that the
+        // entries and the followed path remain consistent across pages.
+        let mut skipping = after.is_some();
+        let mut entries = Vec::new();
+        let mut next = None;
+        for info in repo.rev_walk([commit.id])
             .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
             .all().map_err(GixError::from)?
-            .filter(|info| info.as_ref()
-                .map(|info| filter.check(info))
-                .unwrap_or(true)
-            )
-            .map(|info| {
-                let commit = info?.object()?;
-                let commit_ref = CommitRef::from_bytes(&commit.data)?;
-                let committer = commit_ref.committer()?;
-                Ok(LogEntryInfo {
-                    commit_id: format!("{}", commit.id()),
-                    author: signature_ref(&commit_ref.author()?),
-                    committer: signature_ref(&committer),
-                    commit_timestamp: committer.time()?.seconds,
-                    message: commit_ref.message.to_string(),
-                })
-            });
-
-        let log_entries = match count {
-            Some(count) => log_entry_iter
-                .take(count)
-                .collect::<Result<Vec<_>, GixError>>()?,
-            None => log_entry_iter
-                .collect::<Result<Vec<_>, GixError>>()?,
-        };
+        {
+            let info = info.map_err(GixError::from)?;
+            if !filter.check(&info) {
+                continue;
+            }
+            if skipping {
+                skipping = after != Some(info.id);
+                continue;
+            }
+            if count.is_some_and(|count| entries.len() >= count) {
+                next = entries.last().map(|entry: &LogEntryInfo| entry.commit_id.clone());
+                break;
+            }
+            let commit = info.object().map_err(GixError::from)?;
+            entries.push(log_entry_info(
+                &commit,
+                signature_ref,
+                filter.path().map(str::to_string),
+            )?);
+        }
 
-        Ok(LogInfo { entries: log_entries })
+        Ok(LogInfo { entries, next })
+    }
+
+    /// Blame the file at `path` as of the commit identified by
+    /// `commit_id` (default `HEAD`), for up to `count` lines starting at
+    /// the line `start` (default 1).  The commits that renamed the file
+    /// will be followed.
+    pub fn blame(
+        &self,
+        commit_id: Option<&str>,
+        path: &str,
+        start: Option<u32>,
+        count: Option<u32>,
+    ) -> Result<BlameInfo, PmrRepoError> {
+        let workspace_id = self.workspace.id();
+        let repo = self.repo()?;
+        let commit = get_commit(&repo, workspace_id, commit_id)?
+            .ok_or_else(|| PathError::NoSuchCommit {
+                workspace_id,
+                oid: "HEAD".into(),
+            })?;
+        let path = path.trim_matches('/');
+        let id = repo.rev_parse_single(format!("{}:{path}", commit.id).as_str())
+            .map_err(|_| PathError::NoSuchPath {
+                workspace_id,
+                oid: commit.id.to_string(),
+                path: path.to_string(),
+            })?;
+        let (data, binary) = blob_content(&repo, workspace_id, &id, path)?;
+        if binary {
+            return Err(ContentError::Invalid {
+                workspace_id,
+                oid: commit.id.to_string(),
+                path: path.to_string(),
+                msg: "binary files cannot be blamed".to_string(),
+            }.into());
+        }
+        let line_count: u32 = data.split_inclusive(|c| *c == b'\n')
+            .count()
+            .try_into()
+            .unwrap_or(u32::MAX);
+        let start = start.unwrap_or(1).max(1);
+        let end = count
+            .map_or(line_count, |count| start.saturating_add(count.max(1) - 1))
+            .min(line_count);
+
+        let mut hunks = Vec::new();
+        if start <= end {
+            let outcome = repo.blame_file(
+                path.into(),
+                commit.id,
+                gix::repository::blame_file::Options {
+                    ranges: gix::blame::BlameRanges::from_one_based_inclusive_range(start..=end)
+                        .map_err(GixError::from)?,
+                    rewrites: Some(Default::default()),
+                    ..Default::default()
+                },
+            ).map_err(GixError::from)?;
+            let mut commits = HashMap::new();
+            for (entry, lines) in outcome.entries_with_lines() {
+                let commit = match commits.get(&entry.commit_id) {
+                    Some(commit) => LogEntryInfo::clone(commit),
+                    None => {
+                        let commit = log_entry_info(
+                            &repo.find_object(entry.commit_id)
+                                .map_err(GixError::from)?
+                                .try_into_commit()
+                                .map_err(GixError::from)?,
+                            name_from_signature_ref,
+                            None,
+                        )?;
+                        commits.insert(entry.commit_id, commit.clone());
+                        commit
+                    }
+                };
+                hunks.push(BlameHunk {
+                    start_line: entry.start_in_blamed_file + 1,
+                    orig_start_line: entry.start_in_source_file + 1,
+                    orig_path: entry.source_file_name.map(|name| name.to_string()),
+                    commit,
+                    lines: lines.into_iter()
+                        .map(|line| line.to_string()
+                            .trim_end_matches(['\r', '\n'])
+                            .to_string()
+                        )
+                        .collect(),
+                });
+            }
+        }
+
+        Ok(BlameInfo {
+            workspace: self.workspace.clone_inner(),
+            commit: (&commit).try_into()
+                .map_err(GixError::from)?,
+            path: path.to_string(),
+            line_count,
+            hunks,
+            next: (end < line_count).then_some(end + 1),
+        })
     }
 
     pub fn files(
@@ -373,6 +457,75 @@ impl<'repo> GitHandle<'repo> {
         result
     }
 
+    /// Compare the commit identified by `new_commit_id` (default `HEAD`)
+    /// against the commit identified by `old_commit_id`, which defaults
+    /// to the first parent of the new commit.  The changes may be
+    /// restricted to the files under `path`.
+    pub fn diff(
+        &self,
+        old_commit_id: Option<&str>,
+        new_commit_id: Option<&str>,
+        path: Option<&str>,
+    ) -> Result<DiffResult, PmrRepoError> {
+        let workspace_id = self.workspace.id();
+        let repo = self.repo()?;
+        let new_commit = get_commit(
+            &repo,
+            workspace_id,
+            Some(new_commit_id.unwrap_or("HEAD")),
+        )?.expect("a commit was expected with Some(commit_id) provided");
+        let parent_id = new_commit.parent_ids()
+            .next()
+            .map(|id| id.to_string());
+        let old_commit = match old_commit_id.or(parent_id.as_deref()) {
+            Some(commit_id) => get_commit(&repo, workspace_id, Some(commit_id))?,
+            None => None,
+        };
+        let old_tree = old_commit.as_ref()
+            .map(|commit| commit.tree())
+            .transpose()
+            .map_err(GixError::from)?;
+        let new_tree = new_commit.tree().map_err(GixError::from)?;
+        let changes = repo.diff_tree_to_tree(
+            old_tree.as_ref(),
+            &new_tree,
+            gix::diff::Options::default()
+                .with_rewrites(Some(Default::default())),
+        ).map_err(GixError::from)?;
+
+        let path = path
+            .map(|path| path.trim_matches('/'))
+            .filter(|path| !path.is_empty());
+        let in_path = |name: &str| path.is_none_or(|path| name == path
+            || name.strip_prefix(path).is_some_and(|rest| rest.starts_with('/')));
+        let mut files = Vec::new();
+        for change in changes.into_iter() {
+            let source = match &change {
+                ChangeDetached::Rewrite { source_location, .. } => Some(source_location.to_string()),
+                _ => None,
+            };
+            if !in_path(&change.location().to_string()) && !source.as_deref().is_some_and(in_path) {
+                continue;
+            }
+            if let Some(file) = file_diff(&repo, workspace_id, change)? {
+                files.push(file);
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(DiffResult {
+            workspace: self.workspace.clone_inner(),
+            old_commit: old_commit.as_ref()
+                .map(|commit| commit.try_into())
+                .transpose()
+                .map_err(GixError::from)?,
+            new_commit: (&new_commit).try_into()
+                .map_err(GixError::from)?,
+            path: path.map(str::to_string),
+            files,
+        })
+    }
+
     pub fn checkout(
         &self,
         commit_id: Option<&str>,
@@ -389,6 +542,220 @@ impl<'repo> GitHandle<'repo> {
         checkout(&repo, &commit, dest_dir)
     }
 
+    /// Gather the contents of the tree at `path` (default the root) of
+    /// the commit (default `HEAD`) as an `Archive`.  Submodules are
+    /// included as empty directories, unless `submodules` is provided
+    /// which will include the contents from the workspaces registered
+    /// for their urls, failing should it not permit any of them.
+    pub async fn archive(
+        &self,
+        commit_id: Option<&str>,
+        path: Option<&str>,
+        submodules: Option<&dyn SubmoduleAccess>,
+    ) -> Result<Archive, PmrRepoError> {
+        let mut archive = {
+            let repo = self.repo()?;
+            let commit = get_commit(
+                &repo,
+                self.workspace.id(),
+                Some(commit_id.unwrap_or("HEAD")),
+            )?.expect("a commit was expected with Some(commit_id) provided");
+            Archive::new(
+                commit.id().to_string(),
+                commit.time().map_err(GixError::from)?.seconds,
+            )
+        };
+        let commit_id = archive.commit_id.clone();
+        self.archive_into(
+            &mut archive,
+            commit_id,
+            path.unwrap_or("").trim_matches('/').to_string(),
+            String::new(),
+            submodules,
+        ).await?;
+        archive.entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(archive)
+    }
+
+    #[async_recursion]
+    async fn archive_into(
+        &self,
+        archive: &mut Archive,
+        commit_id: String,
+        path: String,
+        dest: String,
+        submodules: Option<&dyn SubmoduleAccess>,
+    ) -> Result<(), PmrRepoError> {
+        let workspace_id = self.workspace.id();
+        // the submodules to be resolved, as (location, commit, subpath,
+        // dest); gathered first as the repository can't be held across
+        // the awaits.
+        let mut pending = Vec::new();
+        {
+            let repo = self.repo()?;
+            let commit = get_commit(&repo, workspace_id, Some(&commit_id))?
+                .expect("a commit was expected with Some(commit_id) provided");
+            let index = archive.add_repo(self.repo.get()
+                .expect("OnceLock should have been set with the self.repo()")
+                .as_ref()
+                .expect("Valid repo was resolved by here")
+                .clone());
+            match path_target(&commit, workspace_id, Some(&path))? {
+                GitResultTarget::Object(object) if object.object.kind == Kind::Tree => {
+                    let tree = object.object
+                        .attach(&repo)
+                        .into_tree();
+                    let mut recorder = Recorder::default();
+                    tree.traverse()
+                        .breadthfirst(&mut recorder).map_err(GixError::from)?;
+                    for entry in recorder.records.into_iter() {
+                        let filepath = entry.filepath.to_string();
+                        let dest_path = format!("{dest}{filepath}");
+                        let (kind, source) = match entry.mode.kind() {
+                            EntryKind::Tree => (EntryType::Dir, None),
+                            EntryKind::Blob => (EntryType::File, Some((index, entry.oid))),
+                            EntryKind::BlobExecutable => (EntryType::Executable, Some((index, entry.oid))),
+                            EntryKind::Link => (EntryType::Link, Some((index, entry.oid))),
+                            EntryKind::Commit if submodules.is_some() => {
+                                let repo_path = match path.as_str() {
+                                    "" => filepath,
+                                    path => format!("{path}/{filepath}"),
+                                };
+                                let location = get_submodule_target(&commit, workspace_id, &repo_path)?;
+                                pending.push((location, entry.oid.to_string(), String::new(), format!("{dest_path}/")));
+                                continue;
+                            }
+                            EntryKind::Commit => (EntryType::Dir, None),
+                        };
+                        archive.entries.push(ArchiveEntry { path: dest_path, kind, source });
+                    }
+                }
+                GitResultTarget::Object(object) => {
+                    let name = object.path.rsplit('/').next().unwrap_or(&object.path);
+                    archive.entries.push(ArchiveEntry {
+                        path: format!("{dest}{name}"),
+                        kind: EntryType::File,
+                        source: Some((index, object.object.id)),
+                    });
+                }
+                GitResultTarget::RemoteInfo(RemoteInfo { location, commit, subpath, .. }) => {
+                    if submodules.is_none() {
+                        return Err(ContentError::Invalid {
+                            workspace_id,
+                            oid: commit_id.clone(),
+                            path,
+                            msg: "path is within a submodule".to_string(),
+                        }.into())
+                    }
+                    pending.push((location, commit, subpath, dest));
+                }
+            }
+        }
+
+        let Some(access) = submodules else {
+            return Ok(());
+        };
+        for (location, commit, subpath, dest) in pending.into_iter() {
+            let workspaces = WorkspaceBackend::list_workspace_by_url(
+                self.backend.db_platform.as_ref(), &location,
+            ).await?;
+            // as with `GitHandleResult::stream_blob`, use the first result.
+            let Some(workspace) = workspaces.iter().next() else {
+                return Err(ContentError::NoWorkspaceForUrl {
+                    workspace_id,
+                    url: location,
+                }.into())
+            };
+            if !access.permits(workspace.id).await {
+                return Err(ContentError::SubmoduleDenied {
+                    workspace_id,
+                    url: location,
+                }.into())
+            }
+            let handle = self.backend.git_handle(workspace.id).await?;
+            handle.archive_into(archive, commit, subpath, dest, submodules).await?;
+        }
+        Ok(())
+    }
+
+}
+
+/// Resolve the target at `path` within the tree of the commit, stopping
+/// at the first submodule encountered along the way.
+fn path_target(
+    commit: &Commit<'_>,
+    workspace_id: i64,
+    path: Option<&str>,
+) -> Result<GitResultTarget, PmrRepoError> {
+    let tree = commit
+        .tree_id().map_err(GixError::from)?
+        .object().map_err(GixError::from)?;
+
+    let target = match path {
+        Some("") | Some("/") | None => {
+            info!("No path provided; using root tree entry");
+            GitResultTarget::Object(
+                PathObjectDetached::new("".to_string(), tree.into()),
+            )
+        },
+        Some(s) => {
+            let path = s.strip_prefix('/').unwrap_or(s);
+            let mut comps = Path::new(path).components();
+            let mut curr_path = PathBuf::new();
+            let mut object = Some(tree);
+            let mut target: Option<GitResultTarget> = None;
+
+            while let Some(component) = comps.next() {
+                let entry = object
+                    .expect("iteration has this set or look breaked")
+                    .try_into_tree().map_err(GixError::from)?
+                    .peel_to_entry_by_path(
+                        Path::new(&component)
+                    ).map_err(GixError::from)?
+                    .ok_or_else(
+                        || PmrRepoError::from(PathError::NoSuchPath {
+                            workspace_id,
+                            oid: commit.id.to_string(),
+                            path: path.to_string(),
+                        })
+                    )?;
+                curr_path.push(component);
+                match entry.mode() {
+                    k if (k == EntryKind::Commit.into()) => {
+                        info!("entry {:?} is a commit", entry.id());
+                        let location = get_submodule_target(
+                            commit,
+                            workspace_id,
+                            curr_path.to_str().unwrap(),
+                        )?;
+                        target = Some(GitResultTarget::RemoteInfo(RemoteInfo {
+                            location,
+                            commit: entry.id().to_string(),
+                            subpath: comps.as_path().to_str().unwrap().to_string(),
+                            path: path.to_string(),
+                        }));
+                        object = None;
+                        break;
+                    }
+                    _ => ()
+                }
+                let next_object = entry
+                    .object().map_err(GixError::from)?;
+                info!("got {} {:?}", next_object.kind, &next_object);
+                object = Some(next_object);
+            };
+            match object {
+                Some(object) =>
+                    GitResultTarget::Object(
+                        PathObjectDetached::new(path.to_string(), object.into())
+                    ),
+                None =>
+                    // Only way object is None is have target set.
+                    target.expect("to be a RemoteInfo"),
+            }
+        },
+    };
+    Ok(target)
 }
 
 impl<'repo> GitHandleResult<'repo> {
@@ -404,6 +771,20 @@ impl<'repo> GitHandleResult<'repo> {
                 .into_commit())
     }
 
+    /// The id of the tree of the commit for this result.
+    pub fn commit_tree_id(&self) -> Option<String> {
+        let repo = self.repo.to_thread_local();
+        self.commit
+            .as_ref()
+            .and_then(|commit| commit.clone()
+                .attach(&repo)
+                .into_commit()
+                .tree_id()
+                .ok()
+            )
+            .map(|id| id.to_string())
+    }
+
     pub fn path(&self) -> Option<&str> {
         self.target
             .as_ref()
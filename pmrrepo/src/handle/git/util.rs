@@ -7,11 +7,35 @@ use gix::{
         File,
         Source,
     },
-    object::Kind,
-    objs::tree::EntryKind,
+    diff::blob::{
+        Algorithm,
+        UnifiedDiff,
+        intern::InternedInput,
+        unified_diff::{
+            ConsumeBinaryHunk,
+            ContextSize,
+        },
+    },
+    object::{
+        Kind,
+        tree::diff::ChangeDetached,
+    },
+    objs::{
+        CommitRef,
+        tree::EntryKind,
+    },
     remote::find::existing::Error::NotFound,
     traverse::tree::Recorder,
 };
+use pmrcore::{
+    git::PathObject,
+    repo::{
+        ChangeStatus,
+        FileDiff,
+        LogEntryInfo,
+        PathObjectInfo,
+    },
+};
 use std::path::Path;
 use crate::{
     error::{
@@ -43,44 +67,100 @@ pub(super) fn name_from_signature_ref(
     value.name.to_string()
 }
 
+pub(super) fn log_entry_info(
+    commit: &Commit,
+    signature_ref: fn(&SignatureRef) -> String,
+    path: Option<String>,
+) -> Result<LogEntryInfo, GixError> {
+    let commit_ref = CommitRef::from_bytes(&commit.data)?;
+    let committer = commit_ref.committer()?;
+    Ok(LogEntryInfo {
+        commit_id: format!("{}", commit.id()),
+        author: signature_ref(&commit_ref.author()?),
+        committer: signature_ref(&committer),
+        commit_timestamp: committer.time()?.seconds,
+        message: commit_ref.message.to_string(),
+        path,
+    })
+}
+
 pub(super) struct PathFilter<'a> {
     repo: &'a Repository,
-    path: Option<&'a str>,
+    path: Option<String>,
+    follow: bool,
+    current: Option<String>,
 }
 
 impl<'a> PathFilter<'a> {
     pub(super) fn new(
         repo: &'a Repository,
-        path: Option<&'a str>,
+        path: Option<&str>,
+        follow: bool,
     ) -> Self {
         PathFilter {
-            repo: repo,
-            path: path,
+            repo,
+            path: path.map(str::to_string),
+            follow,
+            current: None,
         }
     }
 
+    /// The path as of the commit that was last checked.
+    pub(super) fn path(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    fn entry_id(&self, commit_id: &gix::oid, path: &str) -> Option<gix::ObjectId> {
+        self.repo
+            .rev_parse_single(format!("{commit_id}:{path}").as_str())
+            .ok()
+            .map(|id| id.detach())
+    }
+
+    /// The path the file at `path` was renamed from by the commit.
+    fn rename_source(&self, commit_id: &gix::oid, parent_id: &gix::oid, path: &str) -> Option<String> {
+        let tree = |id| self.repo.find_commit(id).ok()?.tree().ok();
+        let changes = self.repo.diff_tree_to_tree(
+            tree(parent_id).as_ref(),
+            tree(commit_id).as_ref()?,
+            gix::diff::Options::default()
+                .with_rewrites(Some(Default::default())),
+        ).ok()?;
+        changes.into_iter().find_map(|change| match change {
+            ChangeDetached::Rewrite { source_location, location, copy: false, .. }
+                if location == path => Some(source_location.to_string()),
+            _ => None,
+        })
+    }
+
     pub(super) fn check(
         &mut self,
         info: &gix::revision::walk::Info,
     ) -> bool {
-        self.path
-            .map(|path| {
-                let oid = self.repo
-                    .rev_parse_single(
-                        format!("{}:{}", info.id, path).as_str()
-                    )
-                    .ok();
-                // any mismatches will be safe to skip (e.g. when the
-                // path does not exist in the commit).
-                !info.parent_ids
-                    .iter()
-                    .all(|id| self.repo
-                        .rev_parse_single(
-                            format!("{}:{}", id, path).as_str()
-                        ).ok() == oid
-                    )
-            })
-            .unwrap_or(true)
+        let Some(path) = self.path.clone() else {
+            return true;
+        };
+        let oid = self.entry_id(&info.id, &path);
+        // any mismatches will be safe to skip (e.g. when the path does
+        // not exist in the commit).
+        let parent_oids = info.parent_ids
+            .iter()
+            .map(|id| self.entry_id(id, &path))
+            .collect::<Vec<_>>();
+        let changed = !parent_oids.iter().all(|parent_oid| *parent_oid == oid);
+        // when following, a path that was added by a commit may have been
+        // renamed from a path that the older commits will be checked for.
+        if changed && self.follow && oid.is_some() {
+            if let Some(parent_id) = info.parent_ids.first() {
+                if parent_oids.iter().all(Option::is_none) {
+                    if let Some(source) = self.rename_source(&info.id, parent_id, &path) {
+                        self.path = Some(source);
+                    }
+                }
+            }
+        }
+        self.current = Some(path);
+        changed
     }
 }
 
@@ -118,6 +198,134 @@ pub(crate) fn get_commit<'a>(
     }).transpose()
 }
 
+/// Returns the content of the blob along with whether it is binary, as
+/// determined for its `FileInfo`.
+pub(super) fn blob_content(
+    repo: &Repository,
+    workspace_id: i64,
+    id: &gix::oid,
+    path: &str,
+) -> Result<(Vec<u8>, bool), PmrRepoError> {
+    let object = repo.find_object(id).map_err(GixError::from)?;
+    let data = object.data.clone();
+    match PathObjectInfo::from(PathObject::new(path.to_string(), object)) {
+        PathObjectInfo::FileInfo(info) => Ok((data, info.binary)),
+        _ => Err(ContentError::Invalid {
+            workspace_id,
+            oid: id.to_string(),
+            path: path.to_string(),
+            msg: "expected to be a blob".to_string(),
+        }.into()),
+    }
+}
+
+/// Produce the hunks of the unified diff between the two versions of
+/// the text, with 3 lines of context.
+pub(super) fn unified_diff(
+    old: &[u8],
+    new: &[u8],
+) -> Result<String, PmrRepoError> {
+    let input = InternedInput::new(old, new);
+    let hunks = gix::diff::blob::diff(
+        Algorithm::Histogram,
+        &input,
+        UnifiedDiff::new(
+            &input,
+            ConsumeBinaryHunk::new(Vec::new(), "\n"),
+            ContextSize::symmetrical(3),
+        ),
+    )?;
+    Ok(String::from_utf8_lossy(&hunks).into_owned())
+}
+
+/// Convert a change between two trees into the `FileDiff`, which will
+/// be `None` for changes to the trees themselves as these are covered by
+/// the changes to the files within.
+pub(super) fn file_diff(
+    repo: &Repository,
+    workspace_id: i64,
+    change: ChangeDetached,
+) -> Result<Option<FileDiff>, PmrRepoError> {
+    let (status, path, old_path, old, new) = match change {
+        ChangeDetached::Addition { location, entry_mode, id, .. } =>
+            (ChangeStatus::Added, location, None, None, Some((entry_mode, id))),
+        ChangeDetached::Deletion { location, entry_mode, id, .. } =>
+            (ChangeStatus::Deleted, location, None, Some((entry_mode, id)), None),
+        ChangeDetached::Modification {
+            location,
+            previous_entry_mode,
+            previous_id,
+            entry_mode,
+            id,
+        } => (
+            ChangeStatus::Modified,
+            location,
+            None,
+            Some((previous_entry_mode, previous_id)),
+            Some((entry_mode, id)),
+        ),
+        ChangeDetached::Rewrite {
+            source_location,
+            source_entry_mode,
+            source_id,
+            entry_mode,
+            id,
+            location,
+            copy,
+            ..
+        } => (
+            if copy { ChangeStatus::Copied } else { ChangeStatus::Renamed },
+            location,
+            Some(source_location),
+            Some((source_entry_mode, source_id)),
+            Some((entry_mode, id)),
+        ),
+    };
+    if [&old, &new].into_iter().flatten().any(|(mode, _)| mode.is_tree()) {
+        return Ok(None);
+    }
+    let path = path.to_string();
+    let old_path = old_path.map(|path| path.to_string());
+    let content = |side: &Option<(gix::objs::tree::EntryMode, gix::ObjectId)>, path: &str| side
+        .as_ref()
+        .filter(|(mode, _)| !mode.is_commit())
+        .map(|(_, id)| blob_content(repo, workspace_id, id, path))
+        .transpose();
+    // submodules are only identified by their commit.
+    let is_submodule = [&old, &new].into_iter().flatten().any(|(mode, _)| mode.is_commit());
+    let old_content = content(&old, old_path.as_deref().unwrap_or(&path))?;
+    let new_content = content(&new, &path)?;
+    let binary = [&old_content, &new_content].into_iter()
+        .flatten()
+        .any(|(_, binary)| *binary);
+    let diff = if binary || is_submodule {
+        None
+    } else {
+        let empty = Vec::new();
+        let old_data = old_content.as_ref().map_or(&empty, |(data, _)| data);
+        let new_data = new_content.as_ref().map_or(&empty, |(data, _)| data);
+        let hunks = unified_diff(old_data, new_data)?;
+        Some(if hunks.is_empty() {
+            hunks
+        } else {
+            let old_name = old.as_ref()
+                .map_or("/dev/null".to_string(), |_| format!("a/{}", old_path.as_ref().unwrap_or(&path)));
+            let new_name = new.as_ref()
+                .map_or("/dev/null".to_string(), |_| format!("b/{path}"));
+            format!("--- {old_name}\n+++ {new_name}\n{hunks}")
+        })
+    };
+    Ok(Some(FileDiff {
+        status,
+        path,
+        old_path,
+        old_id: old.map(|(_, id)| id.to_string()),
+        new_id: new.map(|(_, id)| id.to_string()),
+        binary,
+        diff,
+    }))
+}
+
 pub(crate) fn get_submodule_target(
     commit: &Commit,
     workspace_id: i64,
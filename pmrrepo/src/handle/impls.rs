@@ -61,13 +61,34 @@ impl<'handle> Handle<'handle> {
             }
         }
     }
+
+    /// Record an update to the repository that was made directly rather
+    /// than through synchronization with the remote (e.g. from a push),
+    /// followed by the same steps taken after a successful sync.
+    pub(crate) async fn record_update(
+        self,
+    ) -> Result<GitHandle<'handle>, PmrRepoError> {
+        let ticket = self.workspace.begin_sync().await?;
+        ticket.complete_sync().await?;
+        let handle: GitHandle<'handle> = self.into();
+        handle.index_tags().await?;
+        Ok(handle)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures::future::{
+        self,
+        BoxFuture,
+    };
     use mockall::predicate::*;
     use pmrcore::{
         repo::{
+            ArchiveFormat,
+            BlameInfo,
+            ChangeStatus,
+            LogInfo,
             PathObjectInfo,
             RemoteInfo,
         },
@@ -79,14 +100,34 @@ mod tests {
     use std::sync::Arc;
     use tempfile::TempDir;
 
-    use test_pmr::core::MockPlatform;
+    use test_pmr::{
+        core::MockPlatform,
+        repo::GitObj,
+    };
 
     use crate::{
         backend::Backend,
-        handle::GitResultTarget,
+        error::{
+            ContentError,
+            PathError,
+        },
+        handle::{
+            GitResultTarget,
+            SubmoduleAccess,
+            git::archive::tests::read_tar_gz,
+        },
     };
     use super::*;
 
+    /// Permits the workspaces with the ids held.
+    struct Permits(Vec<i64>);
+
+    impl SubmoduleAccess for Permits {
+        fn permits(&self, workspace_id: i64) -> BoxFuture<'_, bool> {
+            Box::pin(future::ready(self.0.contains(&workspace_id)))
+        }
+    }
+
     fn expect_workspace(
         platform: &mut MockPlatform,
         id: i64,
@@ -103,6 +144,7 @@ mod tests {
                 description: Some(format!("Workspace {id}")),
                 long_description: None,
                 created_ts: 1234567890,
+                archived_ts: None,
                 exposures: None,
             }));
     }
@@ -164,6 +206,52 @@ mod tests {
         Ok(())
     }
 
+    #[async_std::test]
+    async fn test_receive_workspace_with_index_tag() -> anyhow::Result<()> {
+        let (td, _) = test_pmr::repo::repo_init(None, None, None)?;
+        let td = td.as_ref().expect("tempdir created");
+        let repo_root = TempDir::new()?;
+        let wid = 5;
+
+        let mut platform = MockPlatform::new();
+        expect_workspace(&mut platform, wid, "https://example.com/unused");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        // a fresh repository is only created where one is absent.
+        let repo_dir = backend.init_workspace_repo(wid).await?;
+        assert_eq!(repo_dir, repo_root.path().join("5"));
+        assert!(git2::Repository::open_bare(&repo_dir)?.is_empty()?);
+
+        // simulate the push of a tagged commit into the repository.
+        let origin = git2::Repository::open_bare(td)?;
+        let head = origin.head()?;
+        let oid = head.target().expect("HEAD has a target");
+        let branch = head.name().expect("valid name").to_string();
+        origin.tag_lightweight("pushed", &origin.find_object(oid, None)?, false)?;
+        origin.remote_anonymous(repo_dir.to_str().expect("valid path"))?
+            .push(&[branch.as_str(), "refs/tags/pushed"], None)?;
+
+        let mut platform = MockPlatform::new();
+        let sid = 7;
+        platform.expect_begin_sync()
+            .times(1)
+            .with(eq(wid))
+            .returning(move |_| Ok(sid));
+        platform.expect_complete_sync()
+            .times(1)
+            .with(eq(sid), eq(WorkspaceSyncStatus::Completed))
+            .returning(|_, _| Ok(true));
+        platform.expect_index_workspace_tag()
+            .times(1)
+            .withf(move |workspace_id: &i64, name: &str, commit_id: &str| {
+                *workspace_id == wid && name == "refs/tags/pushed" && commit_id == oid.to_string()
+            })
+            .returning(|_, _, _| Ok(1));
+        expect_workspace(&mut platform, wid, "https://example.com/unused");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        backend.receive_workspace(wid).await?;
+        Ok(())
+    }
+
     #[async_std::test]
     async fn test_sync_failure_invalid_remote() -> anyhow::Result<()> {
         // where remote couldn't be found or invalid.
@@ -335,6 +423,7 @@ mod tests {
                 superceded_by_id: None,
                 long_description: None,
                 created_ts: 1234567890,
+                archived_ts: None,
                 exposures: None,
             }].into()));
         expect_workspace(&mut platform, 3, "http://models.example.com/w/repodata");
@@ -733,4 +822,390 @@ mod tests {
         Ok(())
     }
 
+    #[async_std::test]
+    async fn test_diff() -> anyhow::Result<()> {
+        let repo_root = TempDir::new()?;
+        let (_, repo) = test_pmr::repo::repo_init(
+            None, Some(&repo_root.path().join("5")), Some(1111010101))?;
+        let initial = repo.head_id()?.detach();
+        let first = test_pmr::repo::commit(&repo, vec![
+            ("model.cellml", "a\nb\nc\n"),
+            ("image.png", "\u{0}\u{1}binary"),
+        ])?;
+        let second = test_pmr::repo::append_commit_from_objects(&repo, None, None, vec![
+            GitObj::Blob("model.cellml", "a\nB\nc\n"),
+            GitObj::Blob("image.png", "\u{0}\u{2}binary"),
+            GitObj::Tree("docs", vec![GitObj::Blob("README", "readme\n")]),
+        ])?;
+
+        let mut platform = MockPlatform::new();
+        expect_workspace(&mut platform, 5, "https://example.com/unused");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        let handle = backend.git_handle(5).await?;
+
+        // defaults to HEAD against its parent.
+        let result = handle.diff(None, None, None)?;
+        assert_eq!(result.old_commit.map(|c| c.commit_id), Some(first.to_string()));
+        assert_eq!(result.new_commit.commit_id, second.to_string());
+        assert_eq!(
+            result.files.iter()
+                .map(|file| (file.path.as_str(), file.status, file.binary))
+                .collect::<Vec<_>>(),
+            [
+                ("docs/README", ChangeStatus::Added, false),
+                ("image.png", ChangeStatus::Modified, true),
+                ("model.cellml", ChangeStatus::Modified, false),
+            ],
+        );
+        assert_eq!(result.files[0].diff.as_deref(), Some(
+            "--- /dev/null\n+++ b/docs/README\n@@ -1,0 +1,1 @@\n+readme\n"
+        ));
+        assert_eq!(result.files[1].diff, None);
+        assert_eq!(result.files[2].diff.as_deref(), Some(
+            "--- a/model.cellml\n+++ b/model.cellml\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n"
+        ));
+
+        // restricted to a path, against an explicit commit.
+        let initial = initial.to_string();
+        let result = handle.diff(Some(&initial), Some(&first.to_string()), Some("model.cellml"))?;
+        assert_eq!(result.old_commit.map(|c| c.commit_id), Some(initial.clone()));
+        assert_eq!(result.path.as_deref(), Some("model.cellml"));
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].status, ChangeStatus::Added);
+        assert_eq!(result.files[0].old_id, None);
+        let result = handle.diff(Some(&initial), None, Some("/docs/"))?;
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].path, "docs/README");
+        assert!(handle.diff(None, None, Some("doc"))?.files.is_empty());
+
+        // the initial commit has nothing to compare against.
+        let result = handle.diff(None, Some(&initial), None)?;
+        assert_eq!(result.old_commit, None);
+        assert!(result.files.is_empty());
+
+        assert!(handle.diff(Some("nosuchcommit"), None, None).is_err());
+        Ok(())
+    }
+
+    // rename a file at the top level of the tree of HEAD as a new commit.
+    fn rename_commit(
+        repo: &gix::Repository,
+        timestamp: i64,
+        from: &str,
+        to: &str,
+    ) -> anyhow::Result<gix::ObjectId> {
+        let head = repo.head_commit()?;
+        let mut tree: gix::objs::Tree = head.tree()?.decode()?.into();
+        for entry in tree.entries.iter_mut() {
+            if entry.filename == from {
+                entry.filename = to.into();
+            }
+        }
+        tree.entries.sort();
+        let tree_id = repo.write_object(tree)?.detach();
+        let signature = gix::actor::Signature {
+            name: "user".into(),
+            email: "user@example.com".into(),
+            time: gix::date::Time::new(timestamp, 0),
+        };
+        Ok(repo.commit_as(
+            signature.to_ref(&mut Default::default()),
+            signature.to_ref(&mut Default::default()),
+            "HEAD",
+            "rename",
+            tree_id,
+            [head.id().detach()],
+        )?.detach())
+    }
+
+    fn history_repo(
+        repo_root: &TempDir,
+    ) -> anyhow::Result<Vec<gix::ObjectId>> {
+        let (_, repo) = test_pmr::repo::repo_init(
+            None, Some(&repo_root.path().join("5")), Some(1111010101))?;
+        Ok(vec![
+            test_pmr::repo::append_commit_from_objects(&repo, Some(1111010110), Some("add"), vec![
+                GitObj::Blob("old.cellml", "a\nb\nc\n"),
+                GitObj::Blob("other", "other\n"),
+            ])?,
+            test_pmr::repo::append_commit_from_objects(&repo, Some(1111010120), Some("edit"), vec![
+                GitObj::Blob("old.cellml", "a\nB\nc\n"),
+            ])?,
+            test_pmr::repo::append_commit_from_objects(&repo, Some(1111010130), Some("other"), vec![
+                GitObj::Blob("other", "another\n"),
+            ])?,
+            rename_commit(&repo, 1111010140, "old.cellml", "new.cellml")?,
+            test_pmr::repo::append_commit_from_objects(&repo, Some(1111010150), Some("append"), vec![
+                GitObj::Blob("new.cellml", "a\nB\nc\nd\n"),
+            ])?,
+        ])
+    }
+
+    #[async_std::test]
+    async fn test_history() -> anyhow::Result<()> {
+        let repo_root = TempDir::new()?;
+        let oids = history_repo(&repo_root)?;
+        let mut platform = MockPlatform::new();
+        expect_workspace(&mut platform, 5, "https://example.com/unused");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        let handle = backend.git_handle(5).await?;
+
+        let entries = |log: &LogInfo| log.entries.iter()
+            .map(|entry| (entry.commit_id.clone(), entry.path.clone()))
+            .collect::<Vec<_>>();
+        let entry = |i: usize, path: &str| (oids[i].to_string(), Some(path.to_string()));
+
+        // without following, the history stops at the rename.
+        let log = handle.history(None, Some("new.cellml"), false, None, None)?;
+        assert_eq!(entries(&log), [
+            entry(4, "new.cellml"),
+            entry(3, "new.cellml"),
+        ]);
+        assert_eq!(log.next, None);
+
+        let log = handle.history(None, Some("new.cellml"), true, None, None)?;
+        assert_eq!(entries(&log), [
+            entry(4, "new.cellml"),
+            entry(3, "new.cellml"),
+            entry(1, "old.cellml"),
+            entry(0, "old.cellml"),
+        ]);
+
+        // paginated, where the followed path carries across the pages.
+        let log = handle.history(None, Some("new.cellml"), true, None, Some(2))?;
+        assert_eq!(entries(&log), [
+            entry(4, "new.cellml"),
+            entry(3, "new.cellml"),
+        ]);
+        assert_eq!(log.next, Some(oids[3].to_string()));
+        let log = handle.history(None, Some("new.cellml"), true, log.next.as_deref(), Some(2))?;
+        assert_eq!(entries(&log), [
+            entry(1, "old.cellml"),
+            entry(0, "old.cellml"),
+        ]);
+        assert_eq!(log.next, None);
+
+        // pages without a path.
+        let log = handle.history(None, None, false, Some(&oids[2].to_string()), Some(2))?;
+        assert_eq!(entries(&log), [
+            (oids[1].to_string(), None),
+            (oids[0].to_string(), None),
+        ]);
+        assert!(log.next.is_some());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_blame() -> anyhow::Result<()> {
+        let repo_root = TempDir::new()?;
+        let oids = history_repo(&repo_root)?;
+        let mut platform = MockPlatform::new();
+        expect_workspace(&mut platform, 5, "https://example.com/unused");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        let handle = backend.git_handle(5).await?;
+
+        let hunks = |blame: &BlameInfo| blame.hunks.iter()
+            .map(|hunk| (
+                hunk.start_line,
+                hunk.commit.commit_id.clone(),
+                hunk.orig_path.clone(),
+                hunk.lines.clone(),
+            ))
+            .collect::<Vec<_>>();
+
+        let blame = handle.blame(None, "new.cellml", None, None)?;
+        assert_eq!(blame.line_count, 4);
+        assert_eq!(blame.next, None);
+        assert_eq!(hunks(&blame), [
+            (1, oids[0].to_string(), Some("old.cellml".to_string()), vec!["a".to_string()]),
+            (2, oids[1].to_string(), Some("old.cellml".to_string()), vec!["B".to_string()]),
+            (3, oids[0].to_string(), Some("old.cellml".to_string()), vec!["c".to_string()]),
+            (4, oids[4].to_string(), None, vec!["d".to_string()]),
+        ]);
+        assert_eq!(blame.hunks[1].commit.message, "edit");
+
+        let blame = handle.blame(Some(&oids[3].to_string()), "new.cellml", Some(2), Some(1))?;
+        assert_eq!(blame.line_count, 3);
+        assert_eq!(blame.next, Some(3));
+        assert_eq!(hunks(&blame), [
+            (2, oids[1].to_string(), Some("old.cellml".to_string()), vec!["B".to_string()]),
+        ]);
+
+        let blame = handle.blame(None, "new.cellml", Some(9), None)?;
+        assert!(blame.hunks.is_empty());
+        assert_eq!(blame.next, None);
+
+        assert!(matches!(
+            handle.blame(None, "old.cellml", None, None),
+            Err(PmrRepoError::PathError(PathError::NoSuchPath { .. })),
+        ));
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_archive() -> anyhow::Result<()> {
+        let (
+            repo_root,
+            _, // (import1, import1_oids),
+            _, // (import2, import2_oids),
+            (_, repodata_oids),
+        ) = test_pmr::repo::create_repodata();
+        let commit_id = repodata_oids[4].to_string();
+
+        let mut platform = MockPlatform::new();
+        expect_workspace(&mut platform, 3, "http://models.example.com/w/repodata");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        let handle = backend.git_handle(3).await?;
+
+        let archive = handle.archive(None, Some("dir1"), None).await?;
+        assert_eq!(archive.commit_id(), repodata_oids[8].to_string());
+        assert_eq!(archive.paths(), [
+            "file1",
+            "file2",
+            "nested/",
+            "nested/file_a",
+            "nested/file_b",
+            "nested/file_c",
+        ]);
+
+        let archive = handle.archive(Some(&commit_id), None, None).await?;
+        assert_eq!(archive.paths(), [
+            ".gitmodules",
+            "README",
+            "dir1/",
+            "dir1/file1",
+            "dir1/file2",
+            "dir1/nested/",
+            "dir1/nested/file_a",
+            "dir1/nested/file_b",
+            "ext/",
+            "ext/import1/",
+            "ext/import2/",
+            "file1",
+            "file2",
+        ]);
+
+        let archive = handle.archive(Some(&commit_id), Some("README"), None).await?;
+        assert_eq!(archive.paths(), ["README"]);
+
+        assert!(matches!(
+            handle.archive(Some(&commit_id), Some("ext/import1/README"), None).await,
+            Err(PmrRepoError::ContentError(ContentError::Invalid { .. })),
+        ));
+        assert!(matches!(
+            handle.archive(Some(&commit_id), Some("no_such_file"), None).await,
+            Err(PmrRepoError::PathError(PathError::NoSuchPath { .. })),
+        ));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_archive_submodules() -> anyhow::Result<()> {
+        let (
+            repo_root,
+            _, // (import1, import1_oids),
+            _, // (import2, import2_oids),
+            (_, repodata_oids),
+        ) = test_pmr::repo::create_repodata();
+        let commit_id = repodata_oids[4].to_string();
+
+        let mut platform = MockPlatform::new();
+        for (id, url) in [
+            (1, "http://models.example.com/w/import1"),
+            (2, "http://models.example.com/w/import2"),
+        ] {
+            platform.expect_workspace_list_workspace_by_url()
+                .times(2)
+                .with(eq(url))
+                .returning(move |url| Ok([Workspace {
+                    id,
+                    url: url.to_string(),
+                    description: Some(format!("Workspace {id}")),
+                    superceded_by_id: None,
+                    long_description: None,
+                    created_ts: 1234567890,
+                    archived_ts: None,
+                    exposures: None,
+                }].into()));
+        }
+        expect_workspace(&mut platform, 3, "http://models.example.com/w/repodata");
+        for (id, url) in [
+            (1, "http://models.example.com/w/import1"),
+            (2, "http://models.example.com/w/import2"),
+            (1, "http://models.example.com/w/import1"),
+            (2, "http://models.example.com/w/import2"),
+        ] {
+            expect_workspace(&mut platform, id, url);
+        }
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        let handle = backend.git_handle(3).await?;
+        let permits = Permits(vec![1, 2]);
+
+        let archive = handle.archive(Some(&commit_id), Some("ext"), Some(&permits)).await?;
+        assert_eq!(archive.paths(), [
+            "import1/README",
+            "import1/if1",
+            "import2/README",
+        ]);
+        let mut buffer = Vec::new();
+        archive.write(ArchiveFormat::TarGz, "ext/", &mut buffer)?;
+        assert_eq!(read_tar_gz(&buffer), [
+            ("ext/import1/README".to_string(), b'0', b"The readme for import1.\n".to_vec()),
+            ("ext/import1/if1".to_string(), b'0', b"if1\n".to_vec()),
+            ("ext/import2/README".to_string(), b'0', b"this is import2\n".to_vec()),
+        ]);
+
+        let archive = handle.archive(Some(&commit_id), Some("ext/import1/README"), Some(&permits)).await?;
+        assert_eq!(archive.paths(), ["README"]);
+        let archive = handle.archive(Some(&commit_id), Some("ext/import2"), Some(&permits)).await?;
+        assert_eq!(archive.paths(), ["README"]);
+
+        let mut platform = MockPlatform::new();
+        platform.expect_workspace_list_workspace_by_url()
+            .times(1)
+            .with(eq("http://models.example.com/w/import1"))
+            .returning(|_| Ok([].into()));
+        expect_workspace(&mut platform, 3, "http://models.example.com/w/repodata");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        let handle = backend.git_handle(3).await?;
+        assert!(matches!(
+            handle.archive(Some(&commit_id), Some("ext"), Some(&permits)).await,
+            Err(PmrRepoError::ContentError(ContentError::NoWorkspaceForUrl { .. })),
+        ));
+
+        // the contents of any submodule that is not permitted will fail
+        // the archive.
+        let mut platform = MockPlatform::new();
+        for (id, url) in [
+            (1, "http://models.example.com/w/import1"),
+            (2, "http://models.example.com/w/import2"),
+        ] {
+            platform.expect_workspace_list_workspace_by_url()
+                .times(1)
+                .with(eq(url))
+                .returning(move |url| Ok([Workspace {
+                    id,
+                    url: url.to_string(),
+                    description: Some(format!("Workspace {id}")),
+                    superceded_by_id: None,
+                    long_description: None,
+                    created_ts: 1234567890,
+                    archived_ts: None,
+                    exposures: None,
+                }].into()));
+        }
+        expect_workspace(&mut platform, 3, "http://models.example.com/w/repodata");
+        expect_workspace(&mut platform, 1, "http://models.example.com/w/import1");
+        let backend = Backend::new(Arc::new(platform), repo_root.path().to_path_buf());
+        let handle = backend.git_handle(3).await?;
+        assert!(matches!(
+            handle.archive(Some(&commit_id), Some("ext"), Some(&Permits(vec![1]))).await,
+            Err(PmrRepoError::ContentError(ContentError::SubmoduleDenied { .. })),
+        ));
+
+        Ok(())
+    }
+
 }
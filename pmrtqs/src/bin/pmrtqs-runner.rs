@@ -5,8 +5,11 @@ use pmrdb::{
     ConnectorOption,
 };
 use pmrtqs::{
-    executor::TMPlatformExecutor,
-    runtime::Runtime,
+    executor::{
+        OrphanPolicy,
+        TMPlatformExecutor,
+    },
+    runtime::Builder as RuntimeBuilder,
 };
 use std::sync::Arc;
 use tokio;
@@ -23,6 +26,9 @@ use tokio;
 struct Cli {
     #[clap(short = 'r', long = "runners", default_value = "8")]
     runners: usize,
+    /// How to recover the tasks orphaned by a previous runner.
+    #[clap(long, value_enum, default_value_t = OrphanPolicy::Fail)]
+    orphan_policy: OrphanPolicy,
     #[clap(long, value_name = "PMRTQS_DB_URL", env = "PMRTQS_DB_URL")]
     pmrtqs_db_url: String,
     #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
@@ -50,7 +56,10 @@ fn main() -> anyhow::Result<()> {
         )
     })?;
     let executor = TMPlatformExecutor::new(<Arc<dyn TMPlatform>>::from(backend));
-    let mut runtime = Runtime::new(executor.clone(), args.runners);
+    let mut runtime = RuntimeBuilder::from(executor.clone())
+        .permits(args.runners)
+        .orphan_policy(args.orphan_policy)
+        .build();
     runtime.start();
     log::info!("runner runtime starting");
     runtime.wait();
@@ -8,12 +8,17 @@ use pmrdb::{
     ConnectorOption,
 };
 use pmrcore::{
-    task::traits::TaskBackend,
+    task::{
+        TaskState,
+        traits::TaskBackend,
+    },
     task_template::{
+        TaskSandbox,
         TaskTemplate,
         traits::TaskTemplateBackend,
     },
 };
+use pmrmodel::model::task_template::validate_task_template;
 use std::{
     fs::File,
     io::{
@@ -54,6 +59,37 @@ enum Commands {
         #[clap(short = 'j', long = "json", action)]
         json: bool,
     },
+    /// Set the wall-clock limit for tasks created from the task template
+    #[command(arg_required_else_help = true)]
+    Timeout {
+        id: i64,
+        /// The limit in seconds; omit to remove the limit.
+        #[arg(value_parser = clap::value_parser!(i64).range(1..))]
+        seconds: Option<i64>,
+    },
+    /// Set the sandbox for tasks created from the task template
+    #[command(arg_required_else_help = true)]
+    Sandbox {
+        id: i64,
+        /// CPU time limit in seconds.
+        #[arg(long)]
+        cpu_time: Option<u64>,
+        /// Address space limit in bytes.
+        #[arg(long)]
+        memory: Option<u64>,
+        /// Size limit in bytes for any file written, including output.
+        #[arg(long)]
+        output_size: Option<u64>,
+        /// Deny network access and all writes outside of the basedir.
+        #[arg(long)]
+        isolate: bool,
+        /// Terminate the process on system calls that tools do not need.
+        #[arg(long)]
+        seccomp: bool,
+        /// Remove the sandbox.
+        #[arg(long, conflicts_with_all = ["cpu_time", "memory", "output_size", "isolate", "seccomp"])]
+        clear: bool,
+    },
     #[command(arg_required_else_help = true)]
     Arg {
         #[command(subcommand)]
@@ -68,6 +104,22 @@ enum Commands {
     Import {
         /// Path to the JSON containing the complete task template; omit to read from stdin.
         input: Option<std::path::PathBuf>,
+        /// Publish the imported task template as the new version of this task template.
+        #[arg(long, value_name = "ID")]
+        supersedes: Option<i64>,
+    },
+    /// Validate a complete task template without importing it; the
+    /// registries referenced by choice_source are checked by pmrctrl.
+    Validate {
+        /// Path to the JSON containing the complete task template; omit to read from stdin.
+        input: Option<std::path::PathBuf>,
+    },
+    /// Mark the task template as superseded by a newer finalized version;
+    /// use pmrctrl to also migrate the view task templates.
+    #[command(arg_required_else_help = true)]
+    Supersede {
+        id: i64,
+        superceded_by_id: i64,
     },
     ExecOneShot,
     Query {
@@ -95,6 +147,10 @@ enum Arg {
         choice_fixed: bool,
         #[arg(long, value_name = "CHOICE_SOURCE")]
         choice_source: Option<String>,
+        #[arg(long, value_name = "MULTIPLE")]
+        multiple: bool,
+        #[arg(long, value_name = "MULTIPLE_SEPARATOR")]
+        multiple_separator: Option<String>,
     },
     #[command(arg_required_else_help = true)]
     Rm {
@@ -135,10 +191,15 @@ enum QueryCmd {
     Task {
         task_id: i64,
     },
+    #[command(arg_required_else_help = true)]
+    /// List the tasks in the state (queued, running, succeeded, failed)
+    State {
+        state: TaskState,
+    },
 }
 
 
-#[async_std::main]
+#[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
     let args = Cli::parse();
@@ -182,21 +243,71 @@ async fn main() -> anyhow::Result<()> {
                 println!("{}", task_template);
             }
         }
+        Commands::Timeout { id, seconds } => {
+            if TaskTemplateBackend::set_task_template_timeout(
+                backend.as_ref(), id, seconds,
+            ).await? {
+                match seconds {
+                    Some(seconds) => println!("timeout for id '{id}' set to {seconds} seconds"),
+                    None => println!("timeout for id '{id}' removed"),
+                }
+            } else {
+                bail!("Task Template with id {} not found", id);
+            }
+        }
+        Commands::Sandbox { id, cpu_time, memory, output_size, isolate, seccomp, clear } => {
+            let sandbox = (!clear).then_some(TaskSandbox {
+                cpu_time,
+                memory,
+                output_size,
+                isolate,
+                seccomp,
+            });
+            if TaskTemplateBackend::set_task_template_sandbox(
+                backend.as_ref(), id, sandbox.as_ref(),
+            ).await? {
+                match sandbox {
+                    Some(sandbox) => println!("sandbox for id '{id}' set to {sandbox:?}"),
+                    None => println!("sandbox for id '{id}' removed"),
+                }
+            } else {
+                bail!("Task Template with id {} not found", id);
+            }
+        }
         Commands::Arg { arg } => {
             parse_arg(arg, backend.as_ref()).await?;
         }
         Commands::Choice { choice } => {
             parse_choice(choice, backend.as_ref()).await?
         }
-        Commands::Import { input } => {
+        Commands::Import { input, supersedes } => {
+            if let Some(id) = supersedes {
+                get_task_template_by_id(backend.as_ref(), id).await?;
+            }
+            let task_template: TaskTemplate = match input {
+                Some(path) => serde_json::from_reader(BufReader::new(File::open(path)?))?,
+                None => serde_json::from_reader(BufReader::new(stdin()))?,
+            };
+            validate_task_template(&task_template, None)?;
             let result = TaskTemplateBackend::adds_task_template(
                 backend.as_ref(),
-                match input {
-                    Some(path) => serde_json::from_reader(BufReader::new(File::open(path)?))?,
-                    None => serde_json::from_reader(BufReader::new(stdin()))?,
-                }
+                task_template,
             ).await?;
             println!("program '{}' imported as id: {}", &result.bin_path, result.id);
+            if let Some(id) = supersedes {
+                supersede_task_template(backend.as_ref(), id, result.id).await?;
+            }
+        }
+        Commands::Validate { input } => {
+            let task_template: TaskTemplate = match input {
+                Some(path) => serde_json::from_reader(BufReader::new(File::open(path)?))?,
+                None => serde_json::from_reader(BufReader::new(stdin()))?,
+            };
+            validate_task_template(&task_template, None)?;
+            println!("program '{}' is valid", &task_template.bin_path);
+        }
+        Commands::Supersede { id, superceded_by_id } => {
+            supersede_task_template(backend.as_ref(), id, superceded_by_id).await?;
         }
         Commands::ExecOneShot => {
             match backend
@@ -234,6 +345,28 @@ async fn get_task_template_by_id(
     }
 }
 
+async fn supersede_task_template(
+    backend: &dyn TaskTemplateBackend,
+    id: i64,
+    superceded_by_id: i64,
+) -> anyhow::Result<()> {
+    if TaskTemplateBackend::supersede_task_template(
+        backend, id, superceded_by_id,
+    ).await? {
+        println!("task template id '{id}' superceded by id '{superceded_by_id}'");
+    } else {
+        let task_template = get_task_template_by_id(backend, id).await?;
+        match task_template.superceded_by_id {
+            Some(other) => bail!("Task Template with id {id} already superceded by id {other}"),
+            None => bail!(
+                "Task Template with id {id} cannot be superceded by id {superceded_by_id}, \
+                which must be a different finalized task template that is not superceded"
+            ),
+        }
+    }
+    Ok(())
+}
+
 async fn parse_arg(arg: Arg, backend: &dyn TaskTemplateBackend) -> anyhow::Result<()> {
     match arg {
         Arg::Add {
@@ -245,6 +378,8 @@ async fn parse_arg(arg: Arg, backend: &dyn TaskTemplateBackend) -> anyhow::Resul
             default_value,
             choice_fixed,
             choice_source,
+            multiple,
+            multiple_separator,
         } => {
             println!("Setting argument for task template id {id}");
             let argid = TaskTemplateBackend::add_task_template_arg(
@@ -257,6 +392,8 @@ async fn parse_arg(arg: Arg, backend: &dyn TaskTemplateBackend) -> anyhow::Resul
                 default_value.as_deref(),
                 choice_fixed,
                 choice_source.as_deref(),
+                multiple,
+                multiple_separator.as_deref(),
             ).await?;
             println!("Created task template arg id {argid}");
             let task_template = get_task_template_by_id(
@@ -362,15 +499,44 @@ async fn parse_query(cmd: QueryCmd, backend: &dyn TaskBackend) -> anyhow::Result
     match cmd {
         QueryCmd::Task { task_id } => {
             let task = backend.gets_task(task_id).await?;
+            println!("task id {} is {}", task.id, task.state());
             match task.exit_status {
                 Some(status) => println!("task id {} has exit status {status}", task.id),
                 None => println!("task id {} has no exit status", task.id),
             }
+            if let Some(failure) = task.failure() {
+                println!("task id {} failed: {failure}", task.id);
+            }
+            println!(
+                "task id {} has priority {}, attempted {} time(s) of {} retries allowed",
+                task.id,
+                task.priority,
+                task.attempts,
+                task.max_retries,
+            );
+            if let Some(retry_ts) = task.retry_ts {
+                println!("task id {} will not be retried before {retry_ts}", task.id);
+            }
+            if !task.depends_on.is_empty() {
+                println!("task id {} depends on tasks {:?}", task.id, task.depends_on);
+            }
             match Command::try_from(&task) {
                 Ok(command) => println!("it will run the following:\n{command:?}"),
                 Err(_) => println!("could not convert the task into an executable command"),
             }
         }
+        QueryCmd::State { state } => {
+            let tasks = backend.list_by_state(state).await?;
+            println!("{} task(s) {state}", tasks.len());
+            for task in tasks.iter() {
+                println!(
+                    "task id {} (template {}) created at {}",
+                    task.id,
+                    task.task_template_id,
+                    task.created_ts,
+                );
+            }
+        }
     }
     Ok(())
 }
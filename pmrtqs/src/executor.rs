@@ -2,7 +2,14 @@ mod impls;
 pub mod traits;
 mod types;
 
+pub use impls::{
+    aborted,
+    host_id,
+    recover_orphans,
+};
 pub use types::{
+    OrphanPolicy,
     TMPlatformExecutor,
     TMPlatformExecutorInstance,
+    TaskProcess,
 };
@@ -1,30 +1,57 @@
 use async_trait::async_trait;
+use futures::future;
 use pmrcore::{
     error::ValueError,
     platform::TMPlatform,
     task::{
+       EXIT_ABORTED,
+       EXIT_ORPHANED,
+       EXIT_SANDBOX_FAILED,
+       EXIT_TIMED_OUT,
+       Task,
        TaskDetached,
+       TaskLog,
        TaskRef,
+       traits::TaskBackend,
     },
+    task_template::TaskTemplate,
 };
 use std::{
     fs::File,
-    path::PathBuf,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::Stdio,
+    sync::{
+        Arc,
+        OnceLock,
+    },
+    time::Duration,
+};
+use tokio::{
     process::{
+        Child,
         Command,
-        Stdio,
     },
-    sync::Arc,
+    sync::broadcast::{
+        self,
+        error::RecvError,
+    },
+    time,
 };
-use tokio::sync::broadcast;
 
-use crate::error::RunnerError;
+use crate::{
+    error::RunnerError,
+    sandbox,
+};
 use super::*;
 
 impl<'a> TMPlatformExecutorInstance<'a> {
     fn new(task: TaskRef<'a>) -> Self {
         Self {
             task,
+            read_only: Vec::new(),
         }
     }
 
@@ -32,41 +59,280 @@ impl<'a> TMPlatformExecutorInstance<'a> {
         &self.task
     }
 
+    /// Make the path available to the task as read-only should its
+    /// sandbox isolate it, such as the files the task is to process.
+    pub fn add_read_only(&mut self, path: impl Into<PathBuf>) {
+        self.read_only.push(path.into());
+    }
+
     pub async fn execute(&mut self) -> Result<(i32, bool), RunnerError> {
+        // the sender is dropped immediately, so this will never abort.
+        let (_, abort_receiver) = broadcast::channel(1);
+        self.execute_abortable(abort_receiver).await
+    }
+
+    /// Execute the task, killing its process should an abort signal be
+    /// received or if it runs past the timeout of its task template.
+    pub async fn execute_abortable(
+        &mut self,
+        abort_receiver: broadcast::Receiver<()>,
+    ) -> Result<(i32, bool), RunnerError> {
+        let task_template = self.task.task_template().await?;
+        let Some(mut process) = TaskProcess::spawn(
+            self.task.inner(),
+            &task_template,
+            &self.read_only,
+        )? else {
+            self.task.complete(EXIT_SANDBOX_FAILED.into()).await?;
+            return Ok((EXIT_SANDBOX_FAILED, false));
+        };
+        let pid = process.pid();
+        if let Err(e) = self.task.run(pid.into(), host_id()).await {
+            // a task without its pid recorded cannot be recovered, so
+            // don't leave it running.
+            process.kill().await?;
+            return Err(e.into());
+        }
+        let code = process.wait(abort_receiver).await?;
+        self.task.complete(code.into()).await?;
+        Ok((code, code == 0))
+    }
+}
+
+impl TaskProcess {
+    /// Spawn the process for the task under the sandbox of its task
+    /// template, with the output streams captured into the logs within
+    /// the basedir of the task.  Should the sandbox isolate the task, the
+    /// `read_only` paths are made available to it.
+    ///
+    /// Returns none if the task could not be started within its sandbox,
+    /// as such a task must not be run without it.
+    pub fn spawn(
+        task: &Task,
+        task_template: &TaskTemplate,
+        read_only: &[PathBuf],
+    ) -> Result<Option<Self>, RunnerError> {
         // the base conversion to command does not handle the creation of directories, but will
         // also join work to the base dir.
         // so, create the temp_path
-        let temp_path = PathBuf::from(self.task.basedir()).join("temp");
+        let temp_path = PathBuf::from(&task.basedir).join("temp");
 
         // convert the command
-        let mut command = Command::try_from(&self.task)?;
-        log::trace!("task id {} will run: {command:?}", self.task.id());
+        let mut command = Command::try_from(task)?;
+        log::trace!("task id {} will run: {command:?}", task.id);
 
         // also create the work path
-        let work_path = command.get_current_dir()
+        let work_path = command.as_std().get_current_dir()
+            .map(Path::to_path_buf)
             .ok_or(ValueError::UninitializedAttribute("task missing basedir"))?;
         std::fs::create_dir_all(&work_path)?;
         std::fs::create_dir_all(&temp_path)?;
 
         // and redirect the stdout and stderr to files in temp_path
-        let stdout_file = File::create(temp_path.join("stdout"))?;
-        let stderr_file = File::create(temp_path.join("stderr"))?;
+        let stdout_file = File::create(TaskLog::Stdout.path(&task.basedir))?;
+        let stderr_file = File::create(TaskLog::Stderr.path(&task.basedir))?;
 
         command
             .stdout(Stdio::from(stdout_file))
             .stderr(Stdio::from(stderr_file));
+        // the task and any processes it spawns are killed as a group.
+        #[cfg(unix)]
+        command.process_group(0);
+
+        let spawned = match &task_template.sandbox {
+            Some(task_sandbox) => {
+                if task_sandbox.isolate {
+                    // as the usual temporary directory will be read-only.
+                    command.env("TMPDIR", &temp_path);
+                }
+                sandbox::apply(
+                    &mut command,
+                    task_sandbox,
+                    Path::new(&task.basedir),
+                    read_only,
+                ).and_then(|_| command.spawn())
+            }
+            None => command.spawn(),
+        };
+        let child = match spawned {
+            Ok(child) => child,
+            Err(e) if task_template.sandbox.is_some() => {
+                log::error!("task id {} failed to start in sandbox: {e}", task.id);
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let pid = child.id()
+            .ok_or(ValueError::UninitializedAttribute("child missing pid"))?;
+        Ok(Some(Self {
+            id: task.id,
+            child,
+            pid,
+            timeout: task_template.timeout_duration(),
+        }))
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Kill the process along with the processes it spawned.
+    pub async fn kill(&mut self) -> std::io::Result<()> {
+        kill_group(&mut self.child, self.pid).await
+    }
 
-        let mut child = command.spawn()?;
-        let pid = child.id();
-        // TODO if the DB died here, kill the task?
-        self.task.run(pid.into()).await?;
+    /// Wait for the process to exit, killing it should an abort signal be
+    /// received or if it runs past the timeout, returning the exit status
+    /// to be recorded for the task.
+    pub async fn wait(
+        mut self,
+        mut abort_receiver: broadcast::Receiver<()>,
+    ) -> Result<i32, RunnerError> {
+        let pid = self.pid;
         log::trace!("waiting for child {pid}");
-        let exit_status = child.wait()?;
-        let code = exit_status.code().unwrap_or(-1);
+        let code = tokio::select! {
+            exit_status = self.child.wait() => sandbox::exit_code(exit_status?),
+            _ = aborted(&mut abort_receiver) => {
+                log::info!("task id {} aborted, killing child {pid}", self.id);
+                self.kill().await?;
+                EXIT_ABORTED
+            }
+            _ = expired(self.timeout) => {
+                log::info!("task id {} timed out, killing child {pid}", self.id);
+                self.kill().await?;
+                EXIT_TIMED_OUT
+            }
+        };
         log::trace!("child {pid} exit with code {code}");
-        self.task.complete(code.into()).await?;
-        Ok((code, code == 0))
+        Ok(code)
+    }
+}
+
+/// Resolves once the abort signal is received; a closed channel means
+/// no abort signal can ever be sent, so that will never resolve.
+pub async fn aborted(abort_receiver: &mut broadcast::Receiver<()>) {
+    match abort_receiver.recv().await {
+        Ok(()) | Err(RecvError::Lagged(_)) => (),
+        Err(RecvError::Closed) => future::pending().await,
+    }
+}
+
+async fn expired(timeout: Option<Duration>) {
+    match timeout {
+        Some(timeout) => time::sleep(timeout).await,
+        None => future::pending().await,
+    }
+}
+
+// Kill the process group led by the child, so that the processes spawned
+// by the task go along with it, then wait for the child.
+async fn kill_group(child: &mut Child, pid: u32) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if let Ok(pid) = libc::pid_t::try_from(pid) {
+        unsafe { libc::kill(-pid, libc::SIGKILL) };
+    }
+    #[cfg(not(unix))]
+    let _ = pid;
+    child.kill().await
+}
+
+/// The identity of the current host that is recorded with the pid of a
+/// running task, in the form of `hostname/boot_id`, such that the pid is
+/// only checked on the host and the boot of it where it was started.
+pub fn host_id() -> &'static str {
+    static HOST_ID: OnceLock<String> = OnceLock::new();
+    HOST_ID.get_or_init(|| {
+        let boot_id = std::fs::read_to_string("/proc/sys/kernel/random/boot_id")
+            .unwrap_or_default();
+        format!("{}/{}", hostname(), boot_id.trim())
+    })
+}
+
+fn split_host_id(host: &str) -> (&str, &str) {
+    host.rsplit_once('/').unwrap_or((host, ""))
+}
+
+#[cfg(unix)]
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    let result = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if result != 0 {
+        return String::new();
+    }
+    let len = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[cfg(not(unix))]
+fn hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_default()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: i64) -> bool {
+    match libc::pid_t::try_from(pid) {
+        // signal 0 only checks whether the process may be signaled.
+        Ok(pid) if pid > 0 => {
+            let result = unsafe { libc::kill(pid, 0) };
+            result == 0
+                || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: i64) -> bool {
+    // without a way to check, assume the process is alive so that no
+    // running tasks are recovered.
+    true
+}
+
+/// Recover the running tasks with a pid that no longer refers to a live
+/// process, as those have been orphaned by a runner that did not get to
+/// complete them.  As pids are local to the host, only the tasks started
+/// on the current host are considered, where the ones started before the
+/// host was last booted are orphaned regardless of their pid.  Tasks
+/// without a recorded host are assumed to be from the current boot.
+///
+/// Returns the ids of the recovered tasks.
+pub async fn recover_orphans(
+    platform: &dyn TMPlatform,
+    policy: OrphanPolicy,
+) -> Result<Vec<i64>, RunnerError> {
+    let mut result = Vec::new();
+    if policy == OrphanPolicy::Ignore {
+        return Ok(result);
+    }
+    let (hostname, boot_id) = split_host_id(host_id());
+    for task in TaskBackend::list_running(platform).await? {
+        let orphaned = match task.host.as_deref().map(split_host_id) {
+            // the tasks on other hosts are left to the runners there.
+            Some((host, _)) if host != hostname => continue,
+            Some((_, boot)) if boot != boot_id => true,
+            _ => !task.pid.is_some_and(process_alive),
+        };
+        if !orphaned {
+            continue;
+        }
+        let recovered = match policy {
+            OrphanPolicy::Fail => TaskBackend::complete(
+                platform,
+                task.id,
+                EXIT_ORPHANED.into(),
+            ).await?,
+            OrphanPolicy::Requeue => TaskBackend::requeue(
+                platform,
+                task.id,
+            ).await?,
+            OrphanPolicy::Ignore => false,
+        };
+        if recovered {
+            log::warn!("recovered orphaned task id {} ({policy:?})", task.id);
+            result.push(task.id);
+        }
     }
+    Ok(result)
 }
 
 impl<'a> From<TaskRef<'a>> for TMPlatformExecutorInstance<'a> {
@@ -90,6 +356,7 @@ impl TMPlatformExecutor {
 #[async_trait]
 impl traits::Executor for TMPlatformExecutor {
     type Error = RunnerError;
+    type Task = TaskDetached;
 
     async fn start_task(
         &self,
@@ -102,11 +369,16 @@ impl traits::Executor for TMPlatformExecutor {
     async fn execute(
         &self,
         task: TaskDetached,
-        _abort_receiver: broadcast::Receiver<()>,
+        abort_receiver: broadcast::Receiver<()>,
     ) -> Result<(i32, bool), Self::Error> {
         let mut executor: TMPlatformExecutorInstance = task.bind(self.platform.as_ref())?.into();
-        // the abort token needs to be passed/run with the
-        // executor so it knows if the abort is set.
-        executor.execute().await
+        executor.execute_abortable(abort_receiver).await
+    }
+
+    async fn recover(
+        &self,
+        policy: OrphanPolicy,
+    ) -> Result<usize, Self::Error> {
+        Ok(recover_orphans(self.platform.as_ref(), policy).await?.len())
     }
 }
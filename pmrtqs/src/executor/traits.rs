@@ -1,17 +1,28 @@
 use async_trait::async_trait;
-use pmrcore::task::TaskDetached;
+use std::fmt::Display;
 use tokio::sync::broadcast;
 
+use crate::executor::OrphanPolicy;
+
 #[async_trait]
 pub trait Executor {
     type Error;
+    /// The task as started by this executor, which carries whatever is
+    /// needed to execute it, e.g. a `TaskDetached` for a local platform.
+    type Task: Display + Send + 'static;
 
     async fn start_task(
         &self,
-    ) -> Result<Option<TaskDetached>, Self::Error>;
+    ) -> Result<Option<Self::Task>, Self::Error>;
     async fn execute(
         &self,
-        task: TaskDetached,
+        task: Self::Task,
         abort_receiver: broadcast::Receiver<()>,
     ) -> Result<(i32, bool), Self::Error>;
+    /// Recover the tasks orphaned by a previous runner according to the
+    /// policy, returning the number of tasks recovered.
+    async fn recover(
+        &self,
+        policy: OrphanPolicy,
+    ) -> Result<usize, Self::Error>;
 }
@@ -2,7 +2,12 @@ use pmrcore::{
     platform::TMPlatform,
     task::TaskRef,
 };
-use std::sync::Arc;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::process::Child;
 
 #[derive(Clone)]
 pub struct TMPlatformExecutor {
@@ -11,4 +16,26 @@ pub struct TMPlatformExecutor {
 
 pub struct TMPlatformExecutorInstance<'a> {
     pub(crate) task: TaskRef<'a>,
+    pub(crate) read_only: Vec<PathBuf>,
+}
+
+/// The process of a task spawned under the limits of its task template,
+/// independent of where the task is recorded.
+pub struct TaskProcess {
+    pub(crate) id: i64,
+    pub(crate) child: Child,
+    pub(crate) pid: u32,
+    pub(crate) timeout: Option<Duration>,
+}
+
+/// How tasks orphaned by a previous runner are to be recovered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OrphanPolicy {
+    /// Complete the task with `EXIT_ORPHANED` as its exit status.
+    #[default]
+    Fail,
+    /// Return the task to the queue so it will be started again.
+    Requeue,
+    /// Leave the task as is.
+    Ignore,
 }
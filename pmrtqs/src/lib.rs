@@ -2,3 +2,4 @@ pub mod error;
 pub mod executor;
 pub mod runner;
 pub mod runtime;
+pub mod sandbox;
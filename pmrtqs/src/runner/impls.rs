@@ -1,4 +1,3 @@
-use pmrcore::task::TaskDetached;
 use std::{
     sync::{
         Arc,
@@ -22,7 +21,10 @@ use tokio_stream::{
 };
 use tokio_util::task::TaskTracker;
 
-use crate::executor::traits;
+use crate::executor::{
+    OrphanPolicy,
+    traits,
+};
 
 use super::*;
 
@@ -118,6 +120,24 @@ where
     }
 }
 
+// not derived, as that would require the task of the executor to be
+// Clone, when only the executor itself is cloned.
+impl<EX> Clone for RunnerHandle<EX>
+where
+    EX: traits::Executor + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            executor: self.executor.clone(),
+            abort_sender: self.abort_sender.clone(),
+            sender: self.sender.clone(),
+            task_tracker: self.task_tracker.clone(),
+            termination_token: self.termination_token.clone(),
+            rt_handle: self.rt_handle.clone(),
+        }
+    }
+}
+
 impl<EX> RunnerHandle<EX>
 where
     for<'a> EX: traits::Executor + Sync + Send + Clone + 'a,
@@ -125,7 +145,7 @@ where
 {
     // queue_task sends a message through the sender which hopefully the
     // underlying runner will receive and do something with it.
-    pub async fn queue_task(&self, task: TaskDetached) {
+    pub async fn queue_task(&self, task: EX::Task) {
         match self.sender.send(RunnerMessage::Task(task)).await {
             Ok(()) => (),
             Err(_) => log::debug!("failed to queue new task to runner as it is no longer listening"),
@@ -142,6 +162,16 @@ where
         log::debug!("finished waiting for task_tracker");
     }
 
+    // recover the tasks orphaned by a previous runner; this should be
+    // done before polling so the tasks started here are not affected.
+    pub async fn recover(&self, policy: OrphanPolicy) {
+        match self.executor.recover(policy).await {
+            Ok(0) => log::debug!("no orphaned tasks recovered"),
+            Ok(n) => log::info!("recovered {n} orphaned task(s)"),
+            Err(e) => log::error!("failed to recover orphaned tasks: {e}"),
+        }
+    }
+
     pub fn abort(&self) {
         match self.abort_sender.send(()) {
             Ok(n) => log::debug!("abort signal sent to {n} receiver(s)."),
@@ -162,16 +192,21 @@ where
         let mut ticker = IntervalStream::new(time::interval(Duration::from_millis(100)));
         log::debug!("task queue starting");
         while let Some(_) = (!self.is_closed()).then_some(ticker.next().await).flatten() {
-            while let Some(task) = self.executor.start_task()
-                .await
-                // FIXME need to figure out a more robust way to deal
-                // with the following error, as database crash will
-                // probably crash the task polling completely.
-                .expect("database error when trying to poll for a new task")
-            {
-                log::debug!("sending task {task}");
-                self.queue_task(task).await;
-            };
+            loop {
+                match self.executor.start_task().await {
+                    Ok(Some(task)) => {
+                        log::debug!("sending task {task}");
+                        self.queue_task(task).await;
+                    }
+                    Ok(None) => break,
+                    // try again on the next tick, as the backend may
+                    // only be temporarily unavailable.
+                    Err(e) => {
+                        log::error!("failed to poll for a new task: {e}");
+                        break;
+                    }
+                }
+            }
         };
         log::debug!("task queue stopping");
     }
@@ -1,4 +1,3 @@
-use pmrcore::task::TaskDetached;
 use std::{
     sync::{
         Arc,
@@ -18,27 +17,26 @@ use tokio_util::task::TaskTracker;
 use crate::executor::traits;
 
 
-pub enum RunnerMessage {
-    Task(TaskDetached),
+pub enum RunnerMessage<T> {
+    Task(T),
     Shutdown,
 }
 
 pub struct Runner<EX: traits::Executor> {
     pub(super) executor: EX,
     pub(super) rt_handle: runtime::Handle,
-    pub(super) sender: mpsc::Sender<RunnerMessage>,
-    pub(super) receiver: mpsc::Receiver<RunnerMessage>,
+    pub(super) sender: mpsc::Sender<RunnerMessage<EX::Task>>,
+    pub(super) receiver: mpsc::Receiver<RunnerMessage<EX::Task>>,
     pub(super) semaphore: Arc<Semaphore>,
     pub(super) task_tracker: TaskTracker,
     pub(super) termination_token: Arc<AtomicBool>,
     pub(super) abort_sender: broadcast::Sender<()>,
 }
 
-#[derive(Clone)]
 pub struct RunnerHandle<EX: traits::Executor> {
     pub(super) executor: EX,
     pub(super) abort_sender: broadcast::Sender<()>,
-    pub(super) sender: mpsc::Sender<RunnerMessage>,
+    pub(super) sender: mpsc::Sender<RunnerMessage<EX::Task>>,
     pub(super) task_tracker: TaskTracker,
     pub(super) termination_token: Arc<AtomicBool>,
     pub(super) rt_handle: tokio::runtime::Handle,
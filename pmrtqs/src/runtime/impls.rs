@@ -1,7 +1,10 @@
 use tokio::runtime;
 
 use crate::{
-    executor::traits,
+    executor::{
+        OrphanPolicy,
+        traits,
+    },
     runner::Runner,
 };
 
@@ -27,6 +30,7 @@ where
         Self {
             executor: None,
             permits: 0,
+            orphan_policy: OrphanPolicy::default(),
         }
     }
 
@@ -40,24 +44,33 @@ where
         self
     }
 
+    pub fn orphan_policy(mut self, value: OrphanPolicy) -> Self {
+        self.orphan_policy = value;
+        self
+    }
+
     pub fn build(self) -> Runtime<EX> {
-        Runtime::new(
+        let mut runtime = Runtime::new(
             self.executor
                 .expect("Executor was not provided with Builder"),
             self.permits,
-        )
+        );
+        runtime.orphan_policy = self.orphan_policy;
+        runtime
     }
 
     pub fn build_with_handle(
         self,
         handle: runtime::Handle,
     ) -> Runtime<EX> {
-        Runtime::with_handle(
+        let mut runtime = Runtime::with_handle(
             handle,
             self.executor
                 .expect("Executor was not provided with Builder"),
             self.permits,
-        )
+        );
+        runtime.orphan_policy = self.orphan_policy;
+        runtime
     }
 }
 
@@ -81,6 +94,7 @@ where
             handle,
             executor,
             permits,
+            orphan_policy: OrphanPolicy::default(),
             driver: None,
         }
     }
@@ -95,6 +109,7 @@ where
             handle,
             executor,
             permits,
+            orphan_policy: OrphanPolicy::default(),
             driver: None,
         }
     }
@@ -110,8 +125,10 @@ where
             self.permits,
         );
         let runner_handle = runner.handle();
+        let orphan_policy = self.orphan_policy;
         self.driver = Some(runner_handle.clone());
         self.handle.spawn({async move {
+            runner_handle.recover(orphan_policy).await;
             runner_handle.poll().await
         }});
         self.handle.spawn({async move {
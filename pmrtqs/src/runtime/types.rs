@@ -1,7 +1,10 @@
 use tokio::runtime::Handle;
 
 use crate::{
-    executor::traits,
+    executor::{
+        OrphanPolicy,
+        traits,
+    },
     runner::RunnerHandle,
 };
 
@@ -9,6 +12,7 @@ use crate::{
 pub struct Builder<EX: traits::Executor> {
     pub(super) executor: Option<EX>,
     pub(super) permits: usize,
+    pub(super) orphan_policy: OrphanPolicy,
 }
 
 pub struct Runtime<EX: traits::Executor> {
@@ -16,5 +20,6 @@ pub struct Runtime<EX: traits::Executor> {
     pub(super) handle: Handle,
     pub(super) executor: EX,
     pub(super) permits: usize,
+    pub(super) orphan_policy: OrphanPolicy,
     pub(super) driver: Option<RunnerHandle<EX>>,
 }
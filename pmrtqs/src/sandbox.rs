@@ -0,0 +1,727 @@
+//! Restrictions on the processes of tasks, as specified by the
+//! `TaskSandbox` of their task templates.
+//!
+//! The restrictions are applied in the child process between fork and
+//! exec, so only system calls are made there; everything that requires
+//! allocation is prepared beforehand.
+
+use pmrcore::{
+    task::{
+        EXIT_CPU_LIMIT,
+        EXIT_OUTPUT_LIMIT,
+        EXIT_SIGNALED,
+        EXIT_SYSCALL_DENIED,
+    },
+    task_template::TaskSandbox,
+};
+use std::{
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::ExitStatus,
+};
+use tokio::process::Command;
+
+/// Map the exit status of a task process to the exit status recorded for
+/// the task, such that the termination by the signals raised for sandbox
+/// violations are recorded as such.
+pub fn exit_code(status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        match status.signal() {
+            Some(libc::SIGXCPU) => return EXIT_CPU_LIMIT,
+            Some(libc::SIGXFSZ) => return EXIT_OUTPUT_LIMIT,
+            Some(libc::SIGSYS) => return EXIT_SYSCALL_DENIED,
+            _ => (),
+        }
+    }
+    status.code().unwrap_or(EXIT_SIGNALED)
+}
+
+/// Set up the command to be run within the sandbox.  Should the sandbox
+/// isolate, the task will only see the system directories, the directory
+/// of its program and the `read_only` paths, with `basedir` being the
+/// only location that remains writable.
+#[cfg(target_os = "linux")]
+pub fn apply(
+    command: &mut Command,
+    sandbox: &TaskSandbox,
+    basedir: &Path,
+    read_only: &[PathBuf],
+) -> io::Result<()> {
+    // the new root only has the resolved paths.
+    let work_dir = command.as_std().get_current_dir()
+        .map(|dir| dir.canonicalize().and_then(|dir| linux::path_to_cstring(&dir)))
+        .transpose()?;
+    let isolation = if sandbox.isolate {
+        let mut read_only = read_only.to_vec();
+        read_only.extend(program_dir(Path::new(command.as_std().get_program())));
+        Some(linux::Isolation::new(basedir, &read_only, work_dir)?)
+    } else {
+        None
+    };
+    let filter = sandbox.seccomp
+        .then(linux::seccomp_filter)
+        .transpose()?;
+    let sandbox = sandbox.clone();
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(isolation) = &isolation {
+                isolation.enter()?;
+            }
+            linux::set_limits(&sandbox)?;
+            if let Some(filter) = &filter {
+                linux::install_filter(filter)?;
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply(
+    _command: &mut Command,
+    _sandbox: &TaskSandbox,
+    _basedir: &Path,
+    _read_only: &[PathBuf],
+) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "task sandbox is only supported on Linux",
+    ))
+}
+
+// The directory that the program is installed into, which is the prefix
+// should the program be in a `bin` directory so its libraries go along.
+#[cfg(target_os = "linux")]
+fn program_dir(program: &Path) -> Option<PathBuf> {
+    let dir = program.canonicalize().ok()?.parent()?.to_path_buf();
+    match dir.file_name() {
+        Some(name) if name == "bin" => dir.parent().map(Path::to_path_buf),
+        _ => Some(dir),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use pmrcore::task_template::TaskSandbox;
+    use std::{
+        collections::HashSet,
+        ffi::{
+            CStr,
+            CString,
+        },
+        io,
+        os::unix::ffi::OsStrExt,
+        path::{
+            Path,
+            PathBuf,
+        },
+        ptr,
+    };
+
+    // system calls that are not needed by the tools run as tasks, but
+    // may be used to escape or interfere with the host.
+    const DENIED_SYSCALLS: &[libc::c_long] = &[
+        libc::SYS_add_key,
+        libc::SYS_bpf,
+        libc::SYS_chroot,
+        libc::SYS_delete_module,
+        libc::SYS_finit_module,
+        libc::SYS_fsconfig,
+        libc::SYS_fsmount,
+        libc::SYS_fsopen,
+        libc::SYS_init_module,
+        libc::SYS_io_uring_enter,
+        libc::SYS_io_uring_register,
+        libc::SYS_io_uring_setup,
+        libc::SYS_kexec_load,
+        libc::SYS_keyctl,
+        libc::SYS_mount,
+        libc::SYS_mount_setattr,
+        libc::SYS_move_mount,
+        libc::SYS_open_tree,
+        libc::SYS_perf_event_open,
+        libc::SYS_pivot_root,
+        libc::SYS_process_vm_readv,
+        libc::SYS_process_vm_writev,
+        libc::SYS_ptrace,
+        libc::SYS_reboot,
+        libc::SYS_request_key,
+        libc::SYS_setns,
+        libc::SYS_swapoff,
+        libc::SYS_swapon,
+        libc::SYS_umount2,
+        libc::SYS_unshare,
+        libc::SYS_userfaultfd,
+    ];
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xc000_003e);
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xc000_00b7);
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    const AUDIT_ARCH: Option<u32> = None;
+
+    // the flags for clone that create new namespaces.
+    const CLONE_NAMESPACES: libc::c_int = libc::CLONE_NEWNS
+        | libc::CLONE_NEWUSER
+        | libc::CLONE_NEWPID
+        | libc::CLONE_NEWNET
+        | libc::CLONE_NEWIPC
+        | libc::CLONE_NEWUTS
+        | libc::CLONE_NEWCGROUP
+        | libc::CLONE_NEWTIME;
+
+    // offsets into `struct seccomp_data`.
+    const SECCOMP_DATA_NR: u32 = 0;
+    const SECCOMP_DATA_ARCH: u32 = 4;
+    // the lower half of the first argument on little-endian.
+    const SECCOMP_DATA_ARG0: u32 = 16;
+    // the x32 ABI shares the x86_64 audit arch with syscall numbers
+    // offset by this bit.
+    const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+    pub(super) fn path_to_cstring(path: &Path) -> io::Result<CString> {
+        CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    fn check(result: libc::c_int) -> io::Result<()> {
+        if result == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    // system directories that are made available to the task as is.
+    const SYSTEM_PATHS: &[&str] = &[
+        "/bin",
+        "/etc",
+        "/lib",
+        "/lib32",
+        "/lib64",
+        "/libx32",
+        "/opt",
+        "/sbin",
+        "/usr",
+    ];
+
+    // devices that are bound into the root of the task.
+    const DEVICES: &[&str] = &[
+        "/dev/full",
+        "/dev/null",
+        "/dev/random",
+        "/dev/urandom",
+        "/dev/zero",
+    ];
+
+    /// A step in building the root filesystem of the task, with all paths
+    /// being the targets under the new root.
+    enum Step {
+        Dir(CString),
+        File(CString),
+        Symlink {
+            target: CString,
+            path: CString,
+        },
+        Bind {
+            source: CString,
+            path: CString,
+            writable: bool,
+        },
+    }
+
+    /// The separate user, mount, pid, network, IPC and UTS namespaces that
+    /// the task process will be moved into, with a new root filesystem
+    /// that is read-only apart from the basedir.
+    pub(super) struct Isolation {
+        root: CString,
+        steps: Vec<Step>,
+        proc_dir: CString,
+        work_dir: Option<CString>,
+        uid_map: Vec<u8>,
+        gid_map: Vec<u8>,
+    }
+
+    impl Isolation {
+        pub(super) fn new(
+            basedir: &Path,
+            read_only: &[PathBuf],
+            work_dir: Option<CString>,
+        ) -> io::Result<Self> {
+            // the bind mounts require the paths to be resolved.
+            let basedir = basedir.canonicalize()?;
+            // the tmpfs for the new root is mounted here, as the basedir
+            // is bound without its submounts this will be empty within.
+            let root = basedir.join("temp").join("root");
+            std::fs::create_dir_all(&root)?;
+
+            let mut steps = Vec::new();
+            let mut binds = Vec::new();
+            for path in SYSTEM_PATHS.iter().map(Path::new) {
+                let Ok(metadata) = path.symlink_metadata() else {
+                    continue;
+                };
+                if metadata.is_symlink() {
+                    steps.push(Step::Symlink {
+                        target: path_to_cstring(&std::fs::read_link(path)?)?,
+                        path: path_to_cstring(&rooted(&root, path))?,
+                    });
+                } else if metadata.is_dir() {
+                    binds.push((path.to_path_buf(), false));
+                }
+            }
+            binds.extend(read_only.iter()
+                .filter_map(|path| path.canonicalize().ok())
+                .map(|path| (path, false)));
+            binds.extend(DEVICES.iter()
+                .map(PathBuf::from)
+                .filter(|path| path.exists())
+                .map(|path| (path, true)));
+            binds.push((basedir, true));
+            binds.sort();
+
+            let mut dirs = HashSet::new();
+            let mut bound: Vec<PathBuf> = Vec::new();
+            for (source, writable) in binds {
+                // nothing more to do for what is already bound read-only.
+                if !writable && bound.iter().any(|path| source.starts_with(path)) {
+                    continue;
+                }
+                let path = rooted(&root, &source);
+                let mut ancestors = path.ancestors()
+                    .skip(1)
+                    .take_while(|ancestor| *ancestor != root)
+                    .collect::<Vec<_>>();
+                ancestors.reverse();
+                for ancestor in ancestors {
+                    if dirs.insert(ancestor.to_path_buf()) {
+                        steps.push(Step::Dir(path_to_cstring(ancestor)?));
+                    }
+                }
+                steps.push(if source.is_dir() {
+                    dirs.insert(path.clone());
+                    Step::Dir(path_to_cstring(&path)?)
+                } else {
+                    Step::File(path_to_cstring(&path)?)
+                });
+                steps.push(Step::Bind {
+                    source: path_to_cstring(&source)?,
+                    path: path_to_cstring(&path)?,
+                    writable,
+                });
+                if !writable {
+                    bound.push(source);
+                }
+            }
+            let proc_dir = root.join("proc");
+            steps.push(Step::Dir(path_to_cstring(&proc_dir)?));
+
+            // map the current ids into the namespace as is, such that
+            // the ownership of the files remain unchanged.
+            let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+            Ok(Self {
+                root: path_to_cstring(&root)?,
+                steps,
+                proc_dir: path_to_cstring(&proc_dir)?,
+                work_dir,
+                uid_map: format!("{uid} {uid} 1").into_bytes(),
+                gid_map: format!("{gid} {gid} 1").into_bytes(),
+            })
+        }
+
+        /// Enter the namespaces.  As the process that enters a new pid
+        /// namespace is not moved into it, this forks twice: the first
+        /// child becomes the init of the namespace that builds the new
+        /// root, and its child returns to become the task.  The original
+        /// process remains outside to wait for the task, and exits the
+        /// same way as the task did, such that the task remains the child
+        /// of the runner as far as the runner can tell.
+        pub(super) fn enter(&self) -> io::Result<()> {
+            unsafe {
+                check(libc::unshare(
+                    libc::CLONE_NEWUSER
+                        | libc::CLONE_NEWNS
+                        | libc::CLONE_NEWPID
+                        | libc::CLONE_NEWNET
+                        | libc::CLONE_NEWIPC
+                        | libc::CLONE_NEWUTS
+                ))?;
+                write_file(c"/proc/self/setgroups", b"deny")?;
+                write_file(c"/proc/self/uid_map", &self.uid_map)?;
+                write_file(c"/proc/self/gid_map", &self.gid_map)?;
+
+                // the init reports the wait status of the task through
+                // this, as the init itself cannot be killed by the task.
+                let mut status_pipe = [0; 2];
+                check(libc::pipe2(status_pipe.as_mut_ptr(), libc::O_CLOEXEC))?;
+                let init = libc::fork();
+                check(init)?;
+                if init > 0 {
+                    libc::close(status_pipe[1]);
+                    relay(init, status_pipe[0]);
+                }
+                libc::close(status_pipe[0]);
+                // the namespace goes away along with its init, which in
+                // turn goes along with the process outside.
+                check(libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL))?;
+                self.pivot_root()?;
+
+                let task = libc::fork();
+                check(task)?;
+                if task > 0 {
+                    reap(task, status_pipe[1]);
+                }
+            }
+            Ok(())
+        }
+
+        unsafe fn pivot_root(&self) -> io::Result<()> {
+            // keep the changes to the mounts from propagating back.
+            check(libc::mount(
+                ptr::null(),
+                c"/".as_ptr(),
+                ptr::null(),
+                libc::MS_REC | libc::MS_PRIVATE,
+                ptr::null(),
+            ))?;
+            check(libc::mount(
+                c"tmpfs".as_ptr(),
+                self.root.as_ptr(),
+                c"tmpfs".as_ptr(),
+                libc::MS_NOSUID | libc::MS_NODEV,
+                c"mode=0755".as_ptr().cast(),
+            ))?;
+            for step in &self.steps {
+                match step {
+                    Step::Dir(path) => {
+                        if libc::mkdir(path.as_ptr(), 0o755) == -1 {
+                            exists()?;
+                        }
+                    }
+                    Step::File(path) => {
+                        let fd = libc::open(
+                            path.as_ptr(),
+                            libc::O_RDONLY | libc::O_CREAT | libc::O_CLOEXEC,
+                            0o644,
+                        );
+                        check(fd)?;
+                        libc::close(fd);
+                    }
+                    Step::Symlink { target, path } => {
+                        check(libc::symlink(target.as_ptr(), path.as_ptr()))?;
+                    }
+                    Step::Bind { source, path, writable } => {
+                        // the writable binds leave out their submounts,
+                        // the read-only binds take theirs along as
+                        // read-only.
+                        let flags = if *writable {
+                            libc::MS_BIND
+                        } else {
+                            libc::MS_BIND | libc::MS_REC
+                        };
+                        check(libc::mount(
+                            source.as_ptr(),
+                            path.as_ptr(),
+                            ptr::null(),
+                            flags,
+                            ptr::null(),
+                        ))?;
+                        if !writable {
+                            mount_setattr(
+                                path,
+                                libc::AT_RECURSIVE as libc::c_uint,
+                                libc::MOUNT_ATTR_RDONLY | libc::MOUNT_ATTR_NOSUID,
+                                0,
+                            )?;
+                        }
+                    }
+                }
+            }
+            // not every environment permits a new proc to be mounted, and
+            // the task may do without.
+            libc::mount(
+                c"proc".as_ptr(),
+                self.proc_dir.as_ptr(),
+                c"proc".as_ptr(),
+                libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                ptr::null(),
+            );
+            mount_setattr(&self.root, 0, libc::MOUNT_ATTR_RDONLY, 0)?;
+
+            // stack the old root under the new one, then detach it.
+            check(libc::chdir(self.root.as_ptr()))?;
+            check(libc::syscall(
+                libc::SYS_pivot_root,
+                c".".as_ptr(),
+                c".".as_ptr(),
+            ) as libc::c_int)?;
+            check(libc::umount2(c".".as_ptr(), libc::MNT_DETACH))?;
+            check(libc::chdir(c"/".as_ptr()))?;
+            if let Some(work_dir) = &self.work_dir {
+                check(libc::chdir(work_dir.as_ptr()))?;
+            }
+            Ok(())
+        }
+    }
+
+    fn rooted(root: &Path, path: &Path) -> PathBuf {
+        root.join(path.strip_prefix("/").unwrap_or(path))
+    }
+
+    fn exists() -> io::Result<()> {
+        let error = io::Error::last_os_error();
+        if error.kind() == io::ErrorKind::AlreadyExists {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    // Close every file descriptor other than the standard ones and `fd`,
+    // which is moved to the first one after them.  This is done by the
+    // processes that remain around the task so that the runner is not
+    // kept from seeing the task through to its exec.
+    unsafe fn close_other_fds(fd: libc::c_int) -> libc::c_int {
+        let keep = 3;
+        if fd != keep {
+            libc::dup2(fd, keep);
+        }
+        if libc::syscall(libc::SYS_close_range, keep + 1, libc::c_uint::MAX, 0) == -1 {
+            for fd in keep + 1..1024 {
+                libc::close(fd);
+            }
+        }
+        keep
+    }
+
+    // Wait on the init of the namespace, then exit the same way as the
+    // task, or as the init should it fail to report the task.
+    unsafe fn relay(init: libc::pid_t, status_fd: libc::c_int) -> ! {
+        let status_fd = close_other_fds(status_fd);
+        let mut status = 0;
+        while libc::waitpid(init, &mut status, 0) == -1 {
+            if io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+                libc::_exit(1);
+            }
+        }
+        let mut reported = [0u8; size_of::<libc::c_int>()];
+        if libc::read(status_fd, reported.as_mut_ptr().cast(), reported.len())
+            == reported.len() as isize
+        {
+            status = libc::c_int::from_ne_bytes(reported);
+        }
+        if libc::WIFSIGNALED(status) {
+            let signal = libc::WTERMSIG(status);
+            // raise the same signal without leaving a core behind.
+            let no_core = libc::rlimit {
+                rlim_cur: 0,
+                rlim_max: 0,
+            };
+            libc::setrlimit(libc::RLIMIT_CORE, &no_core);
+            libc::signal(signal, libc::SIG_DFL);
+            libc::kill(libc::getpid(), signal);
+            libc::_exit(128 + signal);
+        }
+        libc::_exit(if libc::WIFEXITED(status) { libc::WEXITSTATUS(status) } else { 1 })
+    }
+
+    // Reap every process in the namespace as its init until the task
+    // exits, then report the status of the task.
+    unsafe fn reap(task: libc::pid_t, status_fd: libc::c_int) -> ! {
+        let status_fd = close_other_fds(status_fd);
+        loop {
+            let mut status = 0;
+            let pid = libc::waitpid(-1, &mut status, 0);
+            if pid == task {
+                let status = status.to_ne_bytes();
+                libc::write(status_fd, status.as_ptr().cast(), status.len());
+                libc::_exit(0);
+            }
+            if pid == -1
+                && io::Error::last_os_error().kind() != io::ErrorKind::Interrupted
+            {
+                libc::_exit(1);
+            }
+        }
+    }
+
+    unsafe fn write_file(path: &CStr, data: &[u8]) -> io::Result<()> {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        check(fd)?;
+        let written = libc::write(fd, data.as_ptr().cast(), data.len());
+        let result = if written == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        };
+        libc::close(fd);
+        result
+    }
+
+    unsafe fn mount_setattr(
+        path: &CStr,
+        flags: libc::c_uint,
+        attr_set: u64,
+        attr_clr: u64,
+    ) -> io::Result<()> {
+        let attr = libc::mount_attr {
+            attr_set,
+            attr_clr,
+            propagation: 0,
+            userns_fd: 0,
+        };
+        let result = libc::syscall(
+            libc::SYS_mount_setattr,
+            libc::AT_FDCWD,
+            path.as_ptr(),
+            flags,
+            &attr as *const libc::mount_attr,
+            size_of::<libc::mount_attr>(),
+        );
+        if result == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    unsafe fn set_limit(
+        resource: libc::__rlimit_resource_t,
+        soft: u64,
+        hard: u64,
+    ) -> io::Result<()> {
+        let mut current = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        check(libc::getrlimit(resource, &mut current))?;
+        // limits may only be lowered.
+        let hard = hard.min(current.rlim_max);
+        let limit = libc::rlimit {
+            rlim_cur: soft.min(hard),
+            rlim_max: hard,
+        };
+        check(libc::setrlimit(resource, &limit))
+    }
+
+    pub(super) fn set_limits(sandbox: &TaskSandbox) -> io::Result<()> {
+        unsafe {
+            if let Some(cpu_time) = sandbox.cpu_time {
+                // the hard limit is set past the soft limit so that the
+                // process receives SIGXCPU rather than SIGKILL.
+                set_limit(libc::RLIMIT_CPU, cpu_time, cpu_time.saturating_add(1))?;
+            }
+            if let Some(memory) = sandbox.memory {
+                set_limit(libc::RLIMIT_AS, memory, memory)?;
+            }
+            if let Some(output_size) = sandbox.output_size {
+                set_limit(libc::RLIMIT_FSIZE, output_size, output_size)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn statement(code: u32, k: u32) -> libc::sock_filter {
+        jump(code, k, 0, 0)
+    }
+
+    fn jump(code: u32, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+        libc::sock_filter {
+            code: code as u16,
+            jt,
+            jf,
+            k,
+        }
+    }
+
+    // Where a matched system call in the filter goes to.
+    enum Target {
+        Kill,
+        NoSys,
+        CheckClone,
+    }
+
+    /// Build the filter that kills the process on any of the denied
+    /// system calls, on clone with any of the flags for new namespaces,
+    /// or on any system call made through a different architecture.  As
+    /// the flags of clone3 are out of reach of the filter, clone3 fails
+    /// as unsupported so that callers fall back to clone.
+    pub(super) fn seccomp_filter() -> io::Result<Vec<libc::sock_filter>> {
+        let arch = AUDIT_ARCH.ok_or_else(|| io::Error::new(
+            io::ErrorKind::Unsupported,
+            "seccomp filter is not supported on this architecture",
+        ))?;
+        let mut filter = vec![
+            statement(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, SECCOMP_DATA_ARCH),
+            jump(libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K, arch, 1, 0),
+            statement(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_KILL_PROCESS),
+            statement(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, SECCOMP_DATA_NR),
+        ];
+        let mut matches = Vec::new();
+        if cfg!(target_arch = "x86_64") {
+            matches.push((libc::BPF_JGE, X32_SYSCALL_BIT, Target::Kill));
+        }
+        matches.push((libc::BPF_JEQ, libc::SYS_clone3 as u32, Target::NoSys));
+        matches.push((libc::BPF_JEQ, libc::SYS_clone as u32, Target::CheckClone));
+        matches.extend(DENIED_SYSCALLS.iter()
+            .map(|nr| (libc::BPF_JEQ, *nr as u32, Target::Kill)));
+
+        // the matches are followed by the allow, then the check on the
+        // flags of clone, then the returns for the targets.
+        let start = filter.len();
+        let allow = start + matches.len();
+        let check_clone = allow + 1;
+        let no_sys = check_clone + 3;
+        let kill = no_sys + 1;
+        for (i, (op, k, target)) in matches.into_iter().enumerate() {
+            let target = match target {
+                Target::Kill => kill,
+                Target::NoSys => no_sys,
+                Target::CheckClone => check_clone,
+            };
+            let offset = u8::try_from(target - (start + i) - 1)
+                .map_err(|_| io::Error::other("seccomp filter is too long"))?;
+            filter.push(jump(libc::BPF_JMP | op | libc::BPF_K, k, offset, 0));
+        }
+        filter.extend([
+            statement(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_ALLOW),
+            statement(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, SECCOMP_DATA_ARG0),
+            jump(
+                libc::BPF_JMP | libc::BPF_JSET | libc::BPF_K,
+                CLONE_NAMESPACES as u32,
+                2,
+                0,
+            ),
+            statement(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_ALLOW),
+            statement(
+                libc::BPF_RET | libc::BPF_K,
+                libc::SECCOMP_RET_ERRNO | libc::ENOSYS as u32,
+            ),
+            statement(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_KILL_PROCESS),
+        ]);
+        Ok(filter)
+    }
+
+    pub(super) fn install_filter(filter: &[libc::sock_filter]) -> io::Result<()> {
+        let program = libc::sock_fprog {
+            len: filter.len() as u16,
+            filter: filter.as_ptr() as *mut libc::sock_filter,
+        };
+        unsafe {
+            check(libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0))?;
+            check(libc::prctl(
+                libc::PR_SET_SECCOMP,
+                libc::SECCOMP_MODE_FILTER,
+                &program as *const libc::sock_fprog,
+            ))?;
+        }
+        Ok(())
+    }
+}
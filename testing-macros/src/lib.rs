@@ -2,53 +2,70 @@ extern crate proc_macro;
 use std::str::FromStr;
 use proc_macro::TokenStream;
 
-#[proc_macro_attribute]
-pub fn sqlite_pcb_cache_test_case(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let mut tokens = TokenStream::from_str(
+fn pcb_cache_test_case(backend: &str, name: &str, item: TokenStream) -> TokenStream {
+    let mut tokens = TokenStream::from_str(&format!(
         r#"
         #[test_case(
-            SqliteBackend::pc("sqlite::memory:".into())
+            {backend}
                 .await
                 .unwrap()
-            ; "base sqlite backend"
+            ; "base {name} backend"
         )]
         #[test_case(
             IndexBackendCache::new(
                 ::std::sync::Arc::new(
-                    SqliteBackend::pc("sqlite::memory:".into())
+                    {backend}
                         .await
                         .unwrap(),
                 ),
             )
-            ; "disk cached sqlite backend"
+            ; "disk cached {name} backend"
         )]
         #[test_case(
             ResourceKindedTermsCache::new(
                 ::std::sync::Arc::new(
-                    SqliteBackend::pc("sqlite::memory:".into())
+                    {backend}
                         .await
                         .unwrap()
                 ),
             )
-            ; "memory cached sqlite backend"
+            ; "memory cached {name} backend"
         )]
         #[test_case(
             ResourceKindedTermsCache::new(
                   ::std::sync::Arc::new(
                       IndexBackendCache::new(
                           ::std::sync::Arc::new(
-                              SqliteBackend::pc("sqlite::memory:".into())
+                              {backend}
                                   .await
                                   .unwrap()
                           )
                       )
                   )
               )
-              ; "memory plus disk cached sqlite backend"
+              ; "memory plus disk cached {name} backend"
           )]
           "#
-      )
+      ))
       .unwrap();
       tokens.extend(item);
       tokens
-  }
+}
+
+#[proc_macro_attribute]
+pub fn sqlite_pcb_cache_test_case(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    pcb_cache_test_case(
+        r#"SqliteBackend::pc("sqlite::memory:".into())"#,
+        "sqlite",
+        item,
+    )
+}
+
+#[proc_macro_attribute]
+pub fn postgres_pcb_cache_test_case(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    pcb_cache_test_case(
+        r#"PostgresBackend::pc(::test_pmr::postgres::create_database().into())"#,
+        "postgres",
+        item,
+    )
+}
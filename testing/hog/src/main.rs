@@ -0,0 +1,55 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process::exit;
+
+// Consumes the resource named by the first argument.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["cpu"] => {
+            let mut i: u64 = 0;
+            loop {
+                i = std::hint::black_box(i.wrapping_add(1));
+            }
+        }
+        ["output", size] => {
+            let size: usize = size.parse().expect("size must be a number");
+            let mut stdout = io::stdout();
+            for _ in 0..size / 1024 {
+                stdout.write_all(&[b'.'; 1024]).expect("failed to write");
+            }
+            stdout.flush().expect("failed to flush");
+        }
+        ["write", path] => {
+            if let Err(e) = fs::write(path, "written") {
+                eprintln!("{e}");
+                exit(1);
+            }
+        }
+        ["pid"] => {
+            println!("{}", std::process::id());
+        }
+        ["spawn"] => {
+            let status = env::current_exe()
+                .and_then(|hog| std::process::Command::new(hog).arg("pid").status());
+            match status {
+                Ok(status) => exit(status.code().unwrap_or(1)),
+                Err(e) => {
+                    eprintln!("{e}");
+                    exit(1);
+                }
+            }
+        }
+        ["chroot"] => {
+            if let Err(e) = std::os::unix::fs::chroot("/") {
+                eprintln!("{e}");
+                exit(1);
+            }
+        }
+        _ => {
+            eprintln!("usage: hog cpu | output <size> | write <path> | pid | spawn | chroot");
+            exit(2);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use std::env;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    thread::sleep(Duration::from_secs(
+        env::args()
+            .skip(1)
+            .next()
+            .as_deref()
+            .unwrap_or("60")
+            .parse::<u64>()
+            .unwrap_or(60)
+    ))
+}
@@ -0,0 +1,97 @@
+//! A backend-agnostic conformance test suite for the platform traits.
+//!
+//! Every check within the submodules is a generic function that accepts
+//! some [`PlatformConnector`] along with the [`ConnectorOption`] for it,
+//! connects to a fresh instance of the backend through the connector and
+//! then exercises the contract of the traits that compose the relevant
+//! platform, i.e. ordering of results, `None` vs error semantics, the
+//! uniqueness constraints and the state transitions.  This is meant to
+//! ensure all backends behave the same way when accessed through the
+//! platforms.
+//!
+//! Backends should make use of [`conformance_test_suite!`] to generate
+//! the individual test cases, e.g.
+//!
+//! ```ignore
+//! #[cfg(test)]
+//! mod conformance {
+//!     test_pmr::conformance_test_suite!(crate::SqliteBackend, "sqlite::memory:");
+//! }
+//! ```
+//!
+//! Note that the expression providing the connection string is evaluated
+//! once for every check, as each check expects a clean backend.
+//!
+//! [`PlatformConnector`]: pmrcore::platform::PlatformConnector
+//! [`ConnectorOption`]: pmrcore::platform::ConnectorOption
+//! [`conformance_test_suite!`]: crate::conformance_test_suite
+
+pub mod ac;
+pub mod mc;
+pub mod pc;
+pub mod tm;
+
+/// Generate the complete set of conformance test cases for the provided
+/// connector type, using the expression for the connection string.  The
+/// crate invoking this will require `async-std` with `attributes`.
+#[macro_export]
+macro_rules! conformance_test_suite {
+    ($connector:ty, $opts:expr $(,)?) => {
+        $crate::conformance_test_suite!(@checks $connector, $opts;
+            ac {
+                user,
+                policy,
+                resource,
+                group,
+                session,
+            },
+            mc {
+                workspace,
+                workspace_archive,
+                workspace_page,
+                workspace_sync,
+                workspace_tag,
+                alias,
+                gen_alias,
+                exposure,
+                exposure_page,
+                exposure_file,
+                exposure_file_view,
+            },
+            pc {
+                citation,
+                index,
+                index_text,
+                index_cache,
+            },
+            tm {
+                task_template,
+                task,
+                task_running,
+                task_schedule,
+                task_status,
+            },
+        );
+    };
+    (@checks $connector:ty, $opts:expr; $($module:ident {
+        $($check:ident),* $(,)?
+    }),* $(,)?) => {
+        $(
+            mod $module {
+                #[allow(unused_imports)]
+                use super::*;
+
+                $(
+                    #[async_std::test]
+                    async fn $check() {
+                        if let Err(e) = $crate::conformance::$module::$check::<$connector>(
+                            ($opts).into(),
+                        ).await {
+                            panic!("{e:?}");
+                        }
+                    }
+                )*
+            }
+        )*
+    };
+}
@@ -0,0 +1,372 @@
+//! Conformance checks for the backends composing the `ACPlatform`.
+
+use pmrcore::{
+    ac::{
+        agent::Agent,
+        genpolicy::{
+            ResGrant,
+            RolePermit,
+        },
+        group::Group,
+        role::Role,
+        session::{
+            Session,
+            SessionToken,
+        },
+        traits::{
+            PolicyBackend,
+            ResourceBackend,
+            SessionBackend,
+            UserBackend,
+        },
+        user::User,
+        workflow::State,
+    },
+    platform::{
+        ACPlatform,
+        ConnectorOption,
+        PlatformConnector,
+    },
+};
+use std::str::FromStr;
+
+async fn connect<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<impl ACPlatform> {
+    C::ac(opts)
+        .await
+        .map_err(anyhow::Error::from_boxed)
+}
+
+async fn make_user(
+    platform: &impl ACPlatform,
+    name: &str,
+) -> anyhow::Result<User> {
+    let id = platform.add_user(name).await?;
+    Ok(platform.get_user_by_id(id).await?
+        .expect("the user was added"))
+}
+
+pub async fn user<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    assert_eq!(platform.get_user_by_id(1).await?, None);
+    assert_eq!(platform.get_user_by_name("admin").await?, None);
+
+    let id1 = platform.add_user("admin").await?;
+    let id2 = platform.add_user("user").await?;
+    assert!(id1 < id2);
+    // user names are unique
+    assert!(platform.add_user("admin").await.is_err());
+
+    let user = platform.get_user_by_id(id1).await?
+        .expect("the user was added");
+    assert_eq!(user.id, id1);
+    assert_eq!(user.name, "admin");
+    assert_eq!(platform.get_user_by_name("user").await?.map(|user| user.id), Some(id2));
+    assert_eq!(platform.get_user_by_id(id2 + 1).await?, None);
+
+    // a user without a password is an error
+    assert!(platform.get_user_password(id1).await.is_err());
+    platform.store_user_password(id1, "first").await?;
+    platform.store_user_password(id1, "second").await?;
+    platform.store_user_password(id2, "other").await?;
+    // the most recently stored password is returned
+    assert_eq!(platform.get_user_password(id1).await?, "second");
+    platform.purge_user_passwords(id1).await?;
+    assert!(platform.get_user_password(id1).await.is_err());
+    assert_eq!(platform.get_user_password(id2).await?, "other");
+
+    Ok(())
+}
+
+pub async fn policy<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let user = make_user(&platform, "user").await?;
+    let other = make_user(&platform, "other").await?;
+    assert!(platform.get_roles_for_user(&user).await?.is_empty());
+
+    // granting returns whether the grant was newly added
+    assert!(platform.grant_role_to_user(&user, Role::Manager).await?);
+    assert!(!platform.grant_role_to_user(&user, Role::Manager).await?);
+    assert!(platform.grant_role_to_user(&user, Role::Reader).await?);
+    let mut roles = platform.get_roles_for_user(&user).await?;
+    roles.sort();
+    assert_eq!(roles, [Role::Manager, Role::Reader]);
+    assert!(platform.get_roles_for_user(&other).await?.is_empty());
+
+    // revoking returns whether the grant was removed
+    assert!(platform.revoke_role_from_user(&user, Role::Manager).await?);
+    assert!(!platform.revoke_role_from_user(&user, Role::Manager).await?);
+    assert_eq!(platform.get_roles_for_user(&user).await?, [Role::Reader]);
+
+    let agent = Agent::User(user.clone());
+    assert!(platform.get_res_grants_for_res("/item").await?.is_empty());
+    assert!(platform.get_res_grants_for_agent(&agent).await?.is_empty());
+    assert!(platform.res_grant_role_to_agent("/item", &agent, Role::Owner).await?);
+    assert!(!platform.res_grant_role_to_agent("/item", &agent, Role::Owner).await?);
+    assert!(platform.res_grant_role_to_agent("/item", &agent, Role::Editor).await?);
+    assert!(platform.res_grant_role_to_agent("/other", &agent, Role::Reader).await?);
+    assert!(platform.res_grant_role_to_agent("/item", &Agent::Anonymous, Role::Reader).await?);
+
+    let mut grants = platform.get_res_grants_for_res("/item").await?
+        .into_iter()
+        .map(|(agent, mut roles)| {
+            roles.sort();
+            (agent, roles)
+        })
+        .collect::<Vec<_>>();
+    grants.sort_by_key(|(agent, _)| Option::<i64>::from(agent));
+    assert_eq!(grants, [
+        (Agent::Anonymous, vec![Role::Reader]),
+        (agent.clone(), vec![Role::Owner, Role::Editor]),
+    ]);
+
+    let mut grants = platform.get_res_grants_for_agent(&agent).await?
+        .into_iter()
+        .map(|(res, mut roles)| {
+            roles.sort();
+            (res, roles)
+        })
+        .collect::<Vec<_>>();
+    grants.sort();
+    assert_eq!(grants, [
+        ("/item".to_string(), vec![Role::Owner, Role::Editor]),
+        ("/other".to_string(), vec![Role::Reader]),
+    ]);
+    assert!(platform.get_res_grants_for_agent(&Agent::User(other)).await?.is_empty());
+
+    assert!(platform.res_revoke_role_from_agent("/item", &agent, Role::Owner).await?);
+    assert!(!platform.res_revoke_role_from_agent("/item", &agent, Role::Owner).await?);
+    assert_eq!(
+        platform.get_res_grants_for_agent(&agent).await?
+            .into_iter()
+            .find(|(res, _)| res == "/item"),
+        Some(("/item".to_string(), vec![Role::Editor])),
+    );
+
+    Ok(())
+}
+
+pub async fn resource<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let user = make_user(&platform, "user").await?;
+    let agent = Agent::User(user.clone());
+
+    // unknown resources have the unknown state
+    assert_eq!(platform.get_wf_state_for_res("/item").await?, State::Unknown);
+    platform.set_wf_state_for_res("/item", State::Private).await?;
+    assert_eq!(platform.get_wf_state_for_res("/item").await?, State::Private);
+    platform.set_wf_state_for_res("/item", State::Published).await?;
+    assert_eq!(platform.get_wf_state_for_res("/item").await?, State::Published);
+    assert_eq!(platform.get_wf_state_for_res("/other").await?, State::Unknown);
+
+    platform.set_wf_state_for_res("/item/2", State::Published).await?;
+    platform.set_wf_state_for_res("/other/1", State::Published).await?;
+    platform.set_wf_state_for_res("/item/3", State::Private).await?;
+    assert_eq!(
+        platform.list_res_with_wf_state(State::Published, "/item").await?,
+        ["/item", "/item/2"],
+    );
+    assert_eq!(
+        platform.list_res_with_wf_state(State::Published, "").await?,
+        ["/item", "/item/2", "/other/1"],
+    );
+    assert!(platform.list_res_with_wf_state(State::Expired, "/").await?.is_empty());
+
+    let policy = platform.generate_policy_for_agent_res(&agent, "/item".into()).await?;
+    assert_eq!(policy.agent, agent);
+    assert_eq!(policy.resource, "/item");
+    assert!(policy.agent_roles.is_empty());
+    assert!(policy.res_grants.is_empty());
+    assert!(policy.role_permits.is_empty());
+
+    platform.grant_role_to_user(&user, Role::Reader).await?;
+    platform.res_grant_role_to_agent("/item", &agent, Role::Editor).await?;
+    platform.res_grant_role_to_agent("/other", &agent, Role::Owner).await?;
+    platform.res_grant_role_to_agent("/item", &Agent::Anonymous, Role::Reader).await?;
+    platform.assign_policy_to_wf_state(State::Published, Role::Reader, "view").await?;
+    platform.assign_policy_to_wf_state(State::Published, Role::Editor, "edit").await?;
+    platform.assign_policy_to_wf_state(State::Private, Role::Owner, "edit").await?;
+
+    let policy = platform.generate_policy_for_agent_res(&agent, "/item".into()).await?;
+    assert_eq!(
+        policy.agent_roles.iter()
+            .map(|agent_role| (agent_role.agent.as_deref(), agent_role.role))
+            .collect::<Vec<_>>(),
+        [(Some("user"), Role::Reader)],
+    );
+    let mut res_grants = policy.res_grants;
+    res_grants.sort();
+    assert_eq!(res_grants, [
+        ResGrant { res: "/item".into(), agent: None, group: None, role: Role::Reader },
+        ResGrant { res: "/item".into(), agent: Some("user".into()), group: None, role: Role::Editor },
+    ]);
+    let mut role_permits = policy.role_permits;
+    role_permits.sort();
+    assert_eq!(role_permits, [
+        RolePermit { role: Role::Editor, action: "edit".into() },
+        RolePermit { role: Role::Reader, action: "view".into() },
+    ]);
+
+    // anonymous agents only get the anonymous grants
+    let policy = platform.generate_policy_for_agent_res(&Agent::Anonymous, "/item".into()).await?;
+    assert!(policy.agent_roles.is_empty());
+    assert_eq!(policy.res_grants, [
+        ResGrant { res: "/item".into(), agent: None, group: None, role: Role::Reader },
+    ]);
+
+    platform.remove_policy_from_wf_state(State::Published, Role::Editor, "edit").await?;
+    let policy = platform.generate_policy_for_agent_res(&agent, "/item".into()).await?;
+    assert_eq!(policy.role_permits, [
+        RolePermit { role: Role::Reader, action: "view".into() },
+    ]);
+
+    Ok(())
+}
+
+pub async fn group<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let user = make_user(&platform, "user").await?;
+    let other = make_user(&platform, "other").await?;
+    assert_eq!(platform.get_group_by_name("lab").await?, None);
+    assert!(platform.get_groups().await?.is_empty());
+
+    let id = platform.add_group("lab").await?;
+    // group names are unique
+    assert!(platform.add_group("lab").await.is_err());
+    let group: Group = platform.get_group_by_id(id).await?
+        .expect("the group was added");
+    assert_eq!(group.name, "lab");
+    assert_eq!(platform.get_group_by_name("lab").await?, Some(group.clone()));
+    assert_eq!(platform.get_groups().await?, std::slice::from_ref(&group));
+
+    // adding and removing members return whether the membership changed
+    assert!(platform.add_user_to_group(&group, &user).await?);
+    assert!(!platform.add_user_to_group(&group, &user).await?);
+    assert_eq!(platform.get_groups_for_user(&user).await?, std::slice::from_ref(&group));
+    assert!(platform.get_groups_for_user(&other).await?.is_empty());
+    assert_eq!(platform.get_group_members(&group).await?, std::slice::from_ref(&user));
+
+    let agent = Agent::Group(group.clone());
+    assert!(platform.res_grant_role_to_agent("/item", &agent, Role::Editor).await?);
+    assert!(!platform.res_grant_role_to_agent("/item", &agent, Role::Editor).await?);
+    assert_eq!(platform.get_res_grants_for_res("/item").await?, [
+        (agent.clone(), vec![Role::Editor]),
+    ]);
+    assert_eq!(platform.get_res_grants_for_agent(&agent).await?, [
+        ("/item".to_string(), vec![Role::Editor]),
+    ]);
+    // the grant to the group is not a grant to its members directly
+    assert!(platform.get_res_grants_for_agent(&Agent::User(user.clone())).await?.is_empty());
+
+    // but the generated policy for members includes the group grants
+    let policy = platform.generate_policy_for_agent_res(
+        &Agent::User(user.clone()),
+        "/item".into(),
+    ).await?;
+    assert_eq!(policy.agent_groups, ["lab"]);
+    assert_eq!(policy.res_grants, [
+        ResGrant {
+            res: "/item".into(),
+            agent: None,
+            group: Some("lab".into()),
+            role: Role::Editor,
+        },
+    ]);
+    let policy = platform.generate_policy_for_agent_res(
+        &Agent::User(other.clone()),
+        "/item".into(),
+    ).await?;
+    assert!(policy.agent_groups.is_empty());
+    assert!(policy.res_grants.is_empty());
+    let policy = platform.generate_policy_for_agent_res(&Agent::Anonymous, "/item".into()).await?;
+    assert!(policy.res_grants.is_empty());
+
+    assert!(platform.remove_user_from_group(&group, &user).await?);
+    assert!(!platform.remove_user_from_group(&group, &user).await?);
+    assert!(platform.get_group_members(&group).await?.is_empty());
+    let policy = platform.generate_policy_for_agent_res(
+        &Agent::User(user.clone()),
+        "/item".into(),
+    ).await?;
+    assert!(policy.agent_groups.is_empty());
+    assert!(policy.res_grants.is_empty());
+
+    assert!(platform.res_revoke_role_from_agent("/item", &agent, Role::Editor).await?);
+    assert!(!platform.res_revoke_role_from_agent("/item", &agent, Role::Editor).await?);
+    assert!(platform.get_res_grants_for_res("/item").await?.is_empty());
+
+    Ok(())
+}
+
+pub async fn session<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let user = make_user(&platform, "user").await?;
+    let other = make_user(&platform, "other").await?;
+    let token = |s: &str| SessionToken::from_str(&format!("{s:0>32}"));
+    let new_session = |token: SessionToken, user_id: i64| Session {
+        token,
+        user_id,
+        origin: "localhost".into(),
+        created_ts: 1234567890,
+        last_active_ts: 1234567890,
+        user_token_id: None,
+    };
+
+    let token1 = token("1")?;
+    let token2 = token("2")?;
+    let token3 = token("3")?;
+    assert!(platform.load_session(token1).await.is_err());
+    assert!(platform.get_user_sessions(user.id).await?.is_empty());
+
+    let session1 = new_session(token1, user.id);
+    platform.save_session(&session1).await?;
+    let loaded = platform.load_session(token1).await?;
+    assert_eq!(loaded.token, token1);
+    assert_eq!(loaded.user_id, user.id);
+    assert_eq!(loaded.origin, "localhost");
+    assert_eq!(loaded.created_ts, 1234567890);
+
+    // saving an existing session updates it instead
+    let ts = platform.save_session(&Session {
+        origin: "elsewhere".into(),
+        .. session1.clone()
+    }).await?;
+    let loaded = platform.load_session(token1).await?;
+    assert_eq!(loaded.origin, "elsewhere");
+    assert_eq!(loaded.last_active_ts, ts);
+
+    platform.save_session(&new_session(token2, user.id)).await?;
+    platform.save_session(&new_session(token3, other.id)).await?;
+
+    // tokens are not provided with the listing
+    let sessions = platform.get_user_sessions(user.id).await?;
+    assert_eq!(sessions.len(), 2);
+    assert!(sessions.iter().all(|session| session.token == SessionToken::default()));
+    assert!(sessions.iter().all(|session| session.user_id == user.id));
+
+    platform.purge_user_sessions(user.id, Some(token2)).await?;
+    assert!(platform.load_session(token1).await.is_err());
+    assert!(platform.load_session(token2).await.is_ok());
+    assert!(platform.load_session(token3).await.is_ok());
+
+    platform.purge_session(token3).await?;
+    assert!(platform.load_session(token3).await.is_err());
+    // purging a missing session is not an error
+    platform.purge_session(token3).await?;
+
+    platform.purge_user_sessions(user.id, None).await?;
+    assert!(platform.get_user_sessions(user.id).await?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,695 @@
+//! Conformance checks for the backends composing the `MCPlatform`.
+
+use pmrcore::{
+    alias::traits::AliasBackend,
+    exposure::traits::{
+        ExposureBackend,
+        ExposureFileBackend,
+        ExposureFileViewBackend,
+    },
+    idgen::traits::GenAliasBackend,
+    listing::{
+        Cursor,
+        ListingQuery,
+        SortKey,
+        SortOrder,
+    },
+    platform::{
+        ConnectorOption,
+        MCPlatform,
+        PlatformConnector,
+    },
+    profile::traits::ViewTaskTemplateBackend,
+    workspace::{
+        WorkspaceSyncStatus,
+        traits::{
+            WorkspaceBackend,
+            WorkspaceSyncBackend,
+            WorkspaceTagBackend,
+        },
+    },
+};
+
+async fn connect<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<impl MCPlatform> {
+    C::mc(opts)
+        .await
+        .map_err(anyhow::Error::from_boxed)
+}
+
+async fn make_workspace(
+    platform: &impl MCPlatform,
+) -> anyhow::Result<i64> {
+    Ok(WorkspaceBackend::add_workspace(
+        platform,
+        "https://models.example.com/workspace",
+        None,
+        None,
+    ).await?)
+}
+
+// Collects the ids from every page of the listing, starting from the
+// provided query.
+async fn walk_workspace_pages(
+    platform: &impl MCPlatform,
+    mut query: ListingQuery,
+) -> anyhow::Result<Vec<i64>> {
+    let mut result = Vec::new();
+    loop {
+        let page = platform.list_workspaces_page(&query).await?;
+        result.extend(page.entries.iter().map(|workspace| workspace.id));
+        match page.next {
+            Some(next) => query.after = Some(next),
+            None => break Ok(result),
+        }
+    }
+}
+
+pub async fn workspace<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    assert!(WorkspaceBackend::list_workspaces(&platform).await?.is_empty());
+
+    let id1 = WorkspaceBackend::add_workspace(
+        &platform,
+        "https://models.example.com/w1",
+        Some("first"),
+        None,
+    ).await?;
+    let id2 = WorkspaceBackend::add_workspace(
+        &platform,
+        "https://models.example.com/w2",
+        None,
+        Some("second workspace"),
+    ).await?;
+    // urls are not unique.
+    let id3 = WorkspaceBackend::add_workspace(
+        &platform,
+        "https://models.example.com/w1",
+        Some("third"),
+        None,
+    ).await?;
+    assert!(id1 < id2);
+    assert!(id2 < id3);
+
+    let workspace = WorkspaceBackend::get_workspace_by_id(&platform, id1).await?;
+    assert_eq!(workspace.id, id1);
+    assert_eq!(workspace.url, "https://models.example.com/w1");
+    assert_eq!(workspace.description.as_deref(), Some("first"));
+    assert_eq!(workspace.long_description, None);
+    assert_eq!(workspace.superceded_by_id, None);
+    // missing entities are errors
+    assert!(WorkspaceBackend::get_workspace_by_id(&platform, id3 + 1).await.is_err());
+
+    assert!(WorkspaceBackend::update_workspace(
+        &platform,
+        id2,
+        Some("second"),
+        None,
+    ).await?);
+    let workspace = WorkspaceBackend::get_workspace_by_id(&platform, id2).await?;
+    assert_eq!(workspace.description.as_deref(), Some("second"));
+    assert_eq!(workspace.long_description, None);
+    assert!(!WorkspaceBackend::update_workspace(
+        &platform,
+        id3 + 1,
+        Some("missing"),
+        None,
+    ).await?);
+
+    let ids = |workspaces: pmrcore::workspace::Workspaces| workspaces
+        .iter()
+        .map(|workspace| workspace.id)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids(WorkspaceBackend::list_workspaces(&platform).await?),
+        [id1, id2, id3],
+    );
+    assert_eq!(
+        ids(WorkspaceBackend::list_workspace_by_url(
+            &platform,
+            "https://models.example.com/w1",
+        ).await?),
+        [id1, id3],
+    );
+    assert!(WorkspaceBackend::list_workspace_by_url(
+        &platform,
+        "https://models.example.com/none",
+    ).await?.is_empty());
+    // ordered by id, missing ids ignored
+    assert_eq!(
+        ids(WorkspaceBackend::list_workspace_by_ids(
+            &platform,
+            &[id3, id1, id3 + 1],
+        ).await?),
+        [id1, id3],
+    );
+    assert!(WorkspaceBackend::list_workspace_by_ids(&platform, &[]).await?.is_empty());
+
+    Ok(())
+}
+
+pub async fn workspace_archive<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let id1 = make_workspace(&platform).await?;
+    let id2 = make_workspace(&platform).await?;
+    let ids = |workspaces: pmrcore::workspace::Workspaces| workspaces
+        .iter()
+        .map(|workspace| workspace.id)
+        .collect::<Vec<_>>();
+
+    assert!(platform.set_workspace_superceded_by(id1, Some(id2)).await?);
+    assert_eq!(
+        WorkspaceBackend::get_workspace_by_id(&platform, id1).await?.superceded_by_id,
+        Some(id2),
+    );
+    assert!(platform.set_workspace_superceded_by(id1, None).await?);
+    assert_eq!(
+        WorkspaceBackend::get_workspace_by_id(&platform, id1).await?.superceded_by_id,
+        None,
+    );
+    assert!(!platform.set_workspace_superceded_by(id2 + 1, Some(id1)).await?);
+
+    assert!(WorkspaceBackend::list_archived_workspaces(&platform).await?.is_empty());
+    assert!(platform.archive_workspace(id1).await?);
+    // archiving is not repeated
+    assert!(!platform.archive_workspace(id1).await?);
+    assert!(!platform.archive_workspace(id2 + 1).await?);
+    assert!(WorkspaceBackend::get_workspace_by_id(&platform, id1).await?
+        .archived_ts
+        .is_some());
+    assert_eq!(ids(WorkspaceBackend::list_workspaces(&platform).await?), [id2]);
+    assert_eq!(ids(WorkspaceBackend::list_archived_workspaces(&platform).await?), [id1]);
+    // archived workspaces remain accessible by their ids
+    assert_eq!(
+        ids(WorkspaceBackend::list_workspace_by_ids(&platform, &[id1, id2]).await?),
+        [id1, id2],
+    );
+
+    assert!(platform.restore_workspace(id1).await?);
+    assert!(!platform.restore_workspace(id1).await?);
+    assert_eq!(
+        WorkspaceBackend::get_workspace_by_id(&platform, id1).await?.archived_ts,
+        None,
+    );
+    assert_eq!(ids(WorkspaceBackend::list_workspaces(&platform).await?), [id1, id2]);
+
+    Ok(())
+}
+
+pub async fn workspace_page<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let page = platform.list_workspaces_page(&ListingQuery::default()).await?;
+    assert!(page.entries.is_empty());
+    assert_eq!(page.next, None);
+
+    let mut ids = Vec::new();
+    for description in [Some("b"), None, Some("a"), Some("c")] {
+        ids.push(WorkspaceBackend::add_workspace(
+            &platform,
+            "https://models.example.com/workspace",
+            description,
+            None,
+        ).await?);
+    }
+    let [id1, id2, id3, id4] = ids[..] else { unreachable!() };
+    platform.archive_workspace(id4).await?;
+    ExposureBackend::insert(&platform, None, id1, None, "0123", None).await?;
+
+    let query = ListingQuery {
+        limit: Some(2),
+        .. Default::default()
+    };
+    let page = platform.list_workspaces_page(&query).await?;
+    assert_eq!(page.entries.iter().map(|w| w.id).collect::<Vec<_>>(), [id1, id2]);
+    assert_eq!(page.next, Some(Cursor::Id(id2)));
+    let page = platform.list_workspaces_page(&ListingQuery {
+        after: page.next,
+        .. query.clone()
+    }).await?;
+    // archived workspaces are excluded
+    assert_eq!(page.entries.iter().map(|w| w.id).collect::<Vec<_>>(), [id3]);
+    assert_eq!(page.next, None);
+
+    let limit = Some(1);
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        limit,
+        order: SortOrder::Desc,
+        .. Default::default()
+    }).await?, [id3, id2, id1]);
+    // missing descriptions are sorted as empty
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        limit,
+        sort: SortKey::Description,
+        .. Default::default()
+    }).await?, [id2, id3, id1]);
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        limit,
+        sort: SortKey::Description,
+        order: SortOrder::Desc,
+        .. Default::default()
+    }).await?, [id1, id3, id2]);
+    // ties are broken by the id
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        limit,
+        sort: SortKey::CreatedTs,
+        .. Default::default()
+    }).await?, [id1, id2, id3]);
+
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        has_exposure: Some(true),
+        .. Default::default()
+    }).await?, [id1]);
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        has_exposure: Some(false),
+        .. Default::default()
+    }).await?, [id2, id3]);
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        ids: Some(vec![id4, id3, id1]),
+        .. Default::default()
+    }).await?, [id1, id3]);
+    assert!(walk_workspace_pages(&platform, ListingQuery {
+        ids: Some(vec![]),
+        .. Default::default()
+    }).await?.is_empty());
+    // the number of ids must not be limited by the number of parameters
+    // permitted by the backend
+    assert_eq!(walk_workspace_pages(&platform, ListingQuery {
+        ids: Some((id1..id1 + 40000).collect()),
+        .. Default::default()
+    }).await?, [id1, id2, id3]);
+
+    // cursors must match the sort key
+    assert!(platform.list_workspaces_page(&ListingQuery {
+        sort: SortKey::Description,
+        after: Some(Cursor::Id(id1)),
+        .. Default::default()
+    }).await.is_err());
+
+    Ok(())
+}
+
+pub async fn workspace_sync<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let workspace_id = make_workspace(&platform).await?;
+    let other_id = make_workspace(&platform).await?;
+    assert!(platform.get_workspaces_sync_records(workspace_id).await?.is_empty());
+
+    let sync1 = platform.begin_sync(workspace_id).await?;
+    let records = platform.get_workspaces_sync_records(workspace_id).await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].id, sync1);
+    assert_eq!(records[0].workspace_id, workspace_id);
+    assert_eq!(records[0].status, WorkspaceSyncStatus::Running as i64);
+    assert_eq!(records[0].end, None);
+
+    assert!(platform.complete_sync(sync1, WorkspaceSyncStatus::Completed).await?);
+    let sync2 = platform.begin_sync(workspace_id).await?;
+    assert!(platform.complete_sync(sync2, WorkspaceSyncStatus::Error).await?);
+    assert!(!platform.complete_sync(sync2 + 1, WorkspaceSyncStatus::Completed).await?);
+
+    let records = platform.get_workspaces_sync_records(workspace_id).await?;
+    assert_eq!(
+        records.iter()
+            .map(|record| (record.id, record.status, record.end.is_some()))
+            .collect::<Vec<_>>(),
+        [
+            (sync1, WorkspaceSyncStatus::Completed as i64, true),
+            (sync2, WorkspaceSyncStatus::Error as i64, true),
+        ],
+    );
+    assert!(platform.get_workspaces_sync_records(other_id).await?.is_empty());
+
+    let sync3 = platform.begin_sync(workspace_id).await?;
+    let sync4 = platform.begin_sync(other_id).await?;
+    let records = platform.list_latest_sync_records(2).await?;
+    assert_eq!(
+        records.iter()
+            .map(|record| (record.workspace_id, record.id))
+            .collect::<Vec<_>>(),
+        [
+            (workspace_id, sync2),
+            (workspace_id, sync3),
+            (other_id, sync4),
+        ],
+    );
+    assert_eq!(platform.list_latest_sync_records(3).await?.len(), 4);
+
+    Ok(())
+}
+
+pub async fn workspace_tag<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let workspace_id = make_workspace(&platform).await?;
+    let other_id = make_workspace(&platform).await?;
+    assert!(platform.get_workspace_tags(workspace_id).await?.is_empty());
+
+    let tag1 = platform.index_workspace_tag(workspace_id, "v1", "0123").await?;
+    let tag2 = platform.index_workspace_tag(workspace_id, "v2", "4567").await?;
+    assert_ne!(tag1, tag2);
+    // indexing the identical tag again returns the existing id
+    assert_eq!(
+        platform.index_workspace_tag(workspace_id, "v1", "0123").await?,
+        tag1,
+    );
+    // the same tag may exist on another workspace
+    platform.index_workspace_tag(other_id, "v1", "0123").await?;
+    // but a name can only point to a single commit per workspace
+    assert!(platform.index_workspace_tag(workspace_id, "v1", "89ab").await.is_err());
+
+    let tags = platform.get_workspace_tags(workspace_id).await?;
+    assert_eq!(
+        tags.iter()
+            .map(|tag| (tag.id, tag.workspace_id, tag.name.as_str(), tag.commit_id.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (tag1, workspace_id, "v1", "0123"),
+            (tag2, workspace_id, "v2", "4567"),
+        ],
+    );
+
+    Ok(())
+}
+
+pub async fn alias<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    assert!(platform.aliases_by_kind("workspace").await?.is_empty());
+    assert_eq!(platform.get_alias("workspace", 1).await?, None);
+    assert_eq!(platform.resolve_alias("workspace", "missing").await?, None);
+
+    platform.add_alias("workspace", 1, "first").await?;
+    platform.add_alias("workspace", 2, "second").await?;
+    platform.add_alias("workspace", 2, "other").await?;
+    // aliases are unique per kind
+    assert!(platform.add_alias("workspace", 3, "first").await.is_err());
+    // but the same alias may be used for a different kind
+    platform.add_alias("exposure", 3, "first").await?;
+
+    assert_eq!(platform.resolve_alias("workspace", "first").await?, Some(1));
+    assert_eq!(platform.resolve_alias("workspace", "other").await?, Some(2));
+    assert_eq!(platform.resolve_alias("exposure", "first").await?, Some(3));
+    assert_eq!(platform.resolve_alias("exposure", "second").await?, None);
+
+    assert_eq!(platform.get_alias("workspace", 1).await?.as_deref(), Some("first"));
+    assert_eq!(platform.get_alias("workspace", 3).await?, None);
+
+    let aliases = platform.get_aliases("workspace", 2).await?;
+    let mut names = aliases.iter()
+        .map(|alias| {
+            assert_eq!(alias.kind, "workspace");
+            assert_eq!(alias.kind_id, 2);
+            alias.alias.as_str()
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["other", "second"]);
+    assert!(platform.get_aliases("workspace", 3).await?.is_empty());
+
+    let mut aliases = platform.aliases_by_kind("workspace").await?;
+    aliases.sort();
+    assert_eq!(aliases, [
+        ("first".to_string(), 1),
+        ("other".to_string(), 2),
+        ("second".to_string(), 2),
+    ]);
+
+    let mut aliases = platform.aliases_by_kind_ids("workspace", &[1, 3]).await?;
+    aliases.sort();
+    assert_eq!(aliases, [("first".to_string(), 1)]);
+    assert!(platform.aliases_by_kind_ids("workspace", &[]).await?.is_empty());
+
+    // the aliases may be moved to another id of the same kind
+    assert_eq!(platform.reassign_aliases("workspace", 2, 3).await?, 2);
+    assert_eq!(platform.resolve_alias("workspace", "second").await?, Some(3));
+    assert_eq!(platform.resolve_alias("workspace", "other").await?, Some(3));
+    assert!(platform.get_aliases("workspace", 2).await?.is_empty());
+    assert_eq!(platform.resolve_alias("exposure", "first").await?, Some(3));
+    assert_eq!(platform.reassign_aliases("workspace", 2, 1).await?, 0);
+
+    // and removed, such that they may be used again
+    assert_eq!(platform.remove_aliases("workspace", 3).await?, 2);
+    assert_eq!(platform.resolve_alias("workspace", "second").await?, None);
+    assert_eq!(platform.remove_aliases("workspace", 3).await?, 0);
+    platform.add_alias("workspace", 1, "second").await?;
+    assert_eq!(platform.resolve_alias("workspace", "second").await?, Some(1));
+    assert_eq!(platform.resolve_alias("exposure", "first").await?, Some(3));
+
+    Ok(())
+}
+
+pub async fn gen_alias<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let id1 = platform.next().await?;
+    let id2 = platform.next().await?;
+    let id3 = platform.next().await?;
+    assert_ne!(id1, id2);
+    assert_ne!(id2, id3);
+    assert_ne!(id1, id3);
+
+    Ok(())
+}
+
+pub async fn exposure<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let workspace_id = make_workspace(&platform).await?;
+    let other_id = make_workspace(&platform).await?;
+    let tag_id = platform.index_workspace_tag(workspace_id, "v1", "0123").await?;
+    assert!(ExposureBackend::list(&platform).await?.is_empty());
+
+    let id1 = ExposureBackend::insert(
+        &platform,
+        Some("first"),
+        workspace_id,
+        Some(tag_id),
+        "0123",
+        None,
+    ).await?;
+    let id2 = ExposureBackend::insert(
+        &platform,
+        None,
+        other_id,
+        None,
+        "4567",
+        None,
+    ).await?;
+    let id3 = ExposureBackend::insert(
+        &platform,
+        None,
+        workspace_id,
+        None,
+        "89ab",
+        None,
+    ).await?;
+    assert!(id1 < id2);
+    assert!(id2 < id3);
+
+    let exposure = ExposureBackend::get_id(&platform, id1).await?;
+    assert_eq!(exposure.id, id1);
+    assert_eq!(exposure.description.as_deref(), Some("first"));
+    assert_eq!(exposure.workspace_id, workspace_id);
+    assert_eq!(exposure.workspace_tag_id, Some(tag_id));
+    assert_eq!(exposure.commit_id, "0123");
+    assert_eq!(exposure.default_file_id, None);
+    assert!(ExposureBackend::get_id(&platform, id3 + 1).await.is_err());
+
+    let ids = |exposures: pmrcore::exposure::Exposures| exposures
+        .iter()
+        .map(|exposure| exposure.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids(ExposureBackend::list(&platform).await?), [id1, id2, id3]);
+    assert_eq!(
+        ids(ExposureBackend::list_for_workspace(&platform, workspace_id).await?),
+        [id1, id3],
+    );
+    assert!(ExposureBackend::list_for_workspace(&platform, other_id + 1).await?.is_empty());
+    assert_eq!(
+        ids(ExposureBackend::list_by_ids(&platform, &[id3, id2, id3 + 1]).await?),
+        [id2, id3],
+    );
+    assert!(ExposureBackend::list_by_ids(&platform, &[]).await?.is_empty());
+
+    let file_id = ExposureFileBackend::insert(&platform, id1, "README", None).await?;
+    assert!(ExposureBackend::set_default_file(&platform, id1, file_id).await?);
+    assert_eq!(
+        ExposureBackend::get_id(&platform, id1).await?.default_file_id,
+        Some(file_id),
+    );
+    assert!(!ExposureBackend::set_default_file(&platform, id3 + 1, file_id).await?);
+
+    Ok(())
+}
+
+pub async fn exposure_page<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let workspace_id = make_workspace(&platform).await?;
+    let mut ids = Vec::new();
+    for description in [Some("b"), None, Some("a")] {
+        ids.push(ExposureBackend::insert(
+            &platform,
+            description,
+            workspace_id,
+            None,
+            "0123",
+            None,
+        ).await?);
+    }
+    let [id1, id2, id3] = ids[..] else { unreachable!() };
+
+    let mut query = ListingQuery {
+        sort: SortKey::Description,
+        limit: Some(2),
+        .. Default::default()
+    };
+    let page = platform.list_page(&query).await?;
+    assert_eq!(page.entries.iter().map(|e| e.id).collect::<Vec<_>>(), [id2, id3]);
+    assert_eq!(page.next, Some(Cursor::Description("a".to_string(), id3)));
+    query.after = page.next;
+    let page = platform.list_page(&query).await?;
+    assert_eq!(page.entries.iter().map(|e| e.id).collect::<Vec<_>>(), [id1]);
+    assert_eq!(page.next, None);
+
+    let page = platform.list_page(&ListingQuery {
+        order: SortOrder::Desc,
+        ids: Some(vec![id1, id3]),
+        // not applicable to exposures
+        has_exposure: Some(false),
+        .. Default::default()
+    }).await?;
+    assert_eq!(page.entries.iter().map(|e| e.id).collect::<Vec<_>>(), [id3, id1]);
+
+    Ok(())
+}
+
+pub async fn exposure_file<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let workspace_id = make_workspace(&platform).await?;
+    let exposure_id = ExposureBackend::insert(
+        &platform, None, workspace_id, None, "0123", None,
+    ).await?;
+    let other_id = ExposureBackend::insert(
+        &platform, None, workspace_id, None, "4567", None,
+    ).await?;
+    assert!(ExposureFileBackend::list_for_exposure(&platform, exposure_id).await?.is_empty());
+
+    let id1 = ExposureFileBackend::insert(&platform, exposure_id, "model.cellml", None).await?;
+    let id2 = ExposureFileBackend::insert(&platform, exposure_id, "README", None).await?;
+    // path is unique per exposure
+    assert!(ExposureFileBackend::insert(&platform, exposure_id, "README", None).await.is_err());
+    let id3 = ExposureFileBackend::insert(&platform, other_id, "README", None).await?;
+
+    let file = ExposureFileBackend::get_id(&platform, id1).await?;
+    assert_eq!(file.id, id1);
+    assert_eq!(file.exposure_id, exposure_id);
+    assert_eq!(file.workspace_file_path, "model.cellml");
+    assert_eq!(file.default_view_id, None);
+    assert!(ExposureFileBackend::get_id(&platform, id3 + 1).await.is_err());
+
+    assert_eq!(
+        ExposureFileBackend::get_by_exposure_filepath(&platform, other_id, "README").await?.id,
+        id3,
+    );
+    assert!(ExposureFileBackend::get_by_exposure_filepath(
+        &platform,
+        other_id,
+        "model.cellml",
+    ).await.is_err());
+
+    assert_eq!(
+        ExposureFileBackend::list_for_exposure(&platform, exposure_id).await?
+            .iter()
+            .map(|file| file.id)
+            .collect::<Vec<_>>(),
+        [id1, id2],
+    );
+
+    let vtt_id = platform.insert_view_task_template("view", "A view", 1).await?;
+    let view_id = ExposureFileViewBackend::insert(&platform, id1, vtt_id, None).await?;
+    assert!(ExposureFileBackend::set_default_view(&platform, id1, view_id).await?);
+    assert_eq!(
+        ExposureFileBackend::get_id(&platform, id1).await?.default_view_id,
+        Some(view_id),
+    );
+    assert!(!ExposureFileBackend::set_default_view(&platform, id3 + 1, view_id).await?);
+
+    Ok(())
+}
+
+pub async fn exposure_file_view<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let workspace_id = make_workspace(&platform).await?;
+    let exposure_id = ExposureBackend::insert(
+        &platform, None, workspace_id, None, "0123", None,
+    ).await?;
+    let file_id = ExposureFileBackend::insert(&platform, exposure_id, "model.cellml", None).await?;
+    let other_id = ExposureFileBackend::insert(&platform, exposure_id, "README", None).await?;
+    let vtt1 = platform.insert_view_task_template("view1", "View 1", 1).await?;
+    let vtt2 = platform.insert_view_task_template("view2", "View 2", 1).await?;
+    assert!(ExposureFileViewBackend::list_for_exposure_file(&platform, file_id).await?.is_empty());
+
+    let id1 = ExposureFileViewBackend::insert(&platform, file_id, vtt1, None).await?;
+    let id2 = ExposureFileViewBackend::insert(&platform, file_id, vtt2, None).await?;
+    // a view task template may only be used once per file
+    assert!(ExposureFileViewBackend::insert(&platform, file_id, vtt1, None).await.is_err());
+    let id3 = ExposureFileViewBackend::insert(&platform, other_id, vtt1, None).await?;
+
+    let view = ExposureFileViewBackend::get_id(&platform, id1).await?;
+    assert_eq!(view.id, id1);
+    assert_eq!(view.exposure_file_id, file_id);
+    assert_eq!(view.view_task_template_id, vtt1);
+    assert_eq!(view.exposure_file_view_task_id, None);
+    assert_eq!(view.view_key, None);
+    assert!(ExposureFileViewBackend::get_id(&platform, id3 + 1).await.is_err());
+
+    assert_eq!(
+        ExposureFileViewBackend::get_by_file_view_template(&platform, other_id, vtt1).await?.id,
+        id3,
+    );
+    assert!(ExposureFileViewBackend::get_by_file_view_template(
+        &platform,
+        other_id,
+        vtt2,
+    ).await.is_err());
+
+    assert!(ExposureFileViewBackend::get_by_file_view_key(&platform, file_id, "view").await.is_err());
+    assert!(ExposureFileViewBackend::update_view_key(&platform, id2, Some("view")).await?);
+    assert_eq!(
+        ExposureFileViewBackend::get_by_file_view_key(&platform, file_id, "view").await?.id,
+        id2,
+    );
+    assert!(ExposureFileViewBackend::get_by_file_view_key(&platform, other_id, "view").await.is_err());
+    assert!(ExposureFileViewBackend::update_view_key(&platform, id2, None).await?);
+    assert!(ExposureFileViewBackend::get_by_file_view_key(&platform, file_id, "view").await.is_err());
+    assert!(!ExposureFileViewBackend::update_view_key(&platform, id3 + 1, Some("view")).await?);
+
+    assert_eq!(
+        ExposureFileViewBackend::list_for_exposure_file(&platform, file_id).await?
+            .iter()
+            .map(|view| view.id)
+            .collect::<Vec<_>>(),
+        [id1, id2],
+    );
+
+    Ok(())
+}
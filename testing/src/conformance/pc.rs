@@ -0,0 +1,240 @@
+//! Conformance checks for the backends composing the `PCPlatform`.
+
+use pmrcore::{
+    citation::{
+        Citation,
+        CitationAuthor,
+        traits::CitationBackend,
+    },
+    index::traits::{
+        IndexBackend,
+        IndexCoreBackend,
+        IndexCoreDBBackend,
+        IndexCoreDBCache,
+    },
+    platform::{
+        ConnectorOption,
+        PlatformConnector,
+        RawPCPlatform,
+    },
+};
+
+async fn connect<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<impl RawPCPlatform> {
+    C::pc(opts)
+        .await
+        .map_err(anyhow::Error::from_boxed)
+}
+
+fn make_citation(id: &str, title: &str, families: &[&str]) -> Citation {
+    Citation {
+        id: id.to_string(),
+        title: title.to_string(),
+        authors: families.iter()
+            .map(|family| CitationAuthor {
+                family: family.to_string(),
+                .. Default::default()
+            })
+            .collect(),
+        .. Default::default()
+    }
+}
+
+pub async fn citation<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    assert!(platform.list_citations().await?.is_empty());
+    assert_eq!(platform.get_citation_by_identifier("urn:example:1").await?, None);
+
+    let citation1 = make_citation("urn:example:1", "Second", &["Family2", "Family1"]);
+    let citation2 = make_citation("urn:example:2", "First", &["Family3"]);
+    platform.add_citation(&citation1).await?;
+    platform.add_citation(&citation2).await?;
+    // adding a citation with an existing identifier is silently ignored
+    platform.add_citation(&make_citation("urn:example:1", "Ignored", &["Ignored"])).await?;
+
+    // authors are returned in the order they were provided
+    assert_eq!(
+        platform.get_citation_by_identifier("urn:example:1").await?,
+        Some(citation1.clone()),
+    );
+    // citations are listed by title
+    assert_eq!(platform.list_citations().await?, [citation2, citation1]);
+
+    Ok(())
+}
+
+pub async fn index<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    assert!(platform.list_kinds().await?.is_empty());
+    assert!(platform.list_terms("keyword").await?.is_none());
+    assert!(platform.list_resources("keyword", "hello").await?.is_none());
+
+    // resolving is idempotent
+    let kind_id = platform.resolve_kind("keyword").await?;
+    assert_eq!(platform.resolve_kind("keyword").await?, kind_id);
+    let entry_id = platform.resolve_idx_entry(kind_id, "hello").await?;
+    assert_eq!(platform.resolve_idx_entry(kind_id, "hello").await?, entry_id);
+    platform.add_idx_entry_link(entry_id, "/resource/1").await?;
+    platform.add_idx_entry_link(entry_id, "/resource/1").await?;
+    assert_eq!(
+        platform.list_resources("keyword", "hello").await?
+            .expect("the term is known")
+            .resource_paths,
+        ["/resource/1"],
+    );
+
+    // kinds without terms are still listed
+    platform.resource_link_kind_with_terms("/resource/1", "title", &mut [].into_iter()).await?;
+    platform.resource_link_kind_with_terms("/resource/2", "keyword", &mut [
+        "world",
+        "hello",
+    ].into_iter()).await?;
+    assert_eq!(platform.list_kinds().await?, ["keyword", "title"]);
+    assert!(platform.list_terms("title").await?
+        .expect("the kind is known")
+        .terms
+        .is_empty());
+    assert_eq!(
+        platform.list_terms("keyword").await?
+            .expect("the kind is known")
+            .terms,
+        ["hello", "world"],
+    );
+    // the order of the resources is not defined
+    let mut resource_paths = platform.list_resources("keyword", "hello").await?
+        .expect("the term is known")
+        .resource_paths;
+    resource_paths.sort();
+    assert_eq!(resource_paths, ["/resource/1", "/resource/2"]);
+
+    // terms are matched case-insensitively but returned as stored
+    platform.resource_link_kind_with_terms("/resource/1", "title", &mut [
+        "Some Title",
+    ].into_iter()).await?;
+    assert_eq!(
+        platform.list_resources("title", "some title").await?
+            .expect("the term is known")
+            .resource_paths,
+        ["/resource/1"],
+    );
+    assert!(platform.list_resources("keyword", "Some Title").await?.is_none());
+    let kinded_terms = platform.get_resource_kinded_terms("/resource/1").await?;
+    assert_eq!(kinded_terms.resource_path, "/resource/1");
+    assert_eq!(
+        kinded_terms.data.get("title").map(Vec::as_slice),
+        Some(["Some Title".to_string()].as_slice()),
+    );
+
+    platform.forget_resource_path(Some("keyword"), "/resource/1").await?;
+    assert_eq!(
+        platform.list_resources("keyword", "hello").await?
+            .expect("the term is known")
+            .resource_paths,
+        ["/resource/2"],
+    );
+    assert_eq!(
+        platform.list_resources("title", "Some Title").await?
+            .expect("the term is known")
+            .resource_paths,
+        ["/resource/1"],
+    );
+    platform.forget_resource_path(None, "/resource/1").await?;
+    assert!(platform.list_resources("title", "Some Title").await?
+        .expect("the term remains known")
+        .resource_paths
+        .is_empty());
+    assert!(platform.get_resource_kinded_terms("/resource/1").await?.data.is_empty());
+
+    Ok(())
+}
+
+pub async fn index_text<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    assert!(platform.list_resources_text("content", None).await?.is_empty());
+    assert_eq!(platform.get_resource_brief("/resource/1").await?, None);
+
+    platform.add_idx_text(
+        Some("Example Title"),
+        Some("Content is correct."),
+        "/resource/1",
+    ).await?;
+    platform.add_idx_text(
+        Some("Other Title"),
+        Some("Unrelated."),
+        "/resource/2",
+    ).await?;
+
+    let brief = platform.get_resource_brief("/resource/1").await?
+        .expect("the text was added");
+    assert_eq!(brief.resource_path, "/resource/1");
+    assert_eq!(brief.title.as_deref(), Some("Example Title"));
+    assert_eq!(brief.brief.as_deref(), Some("Content is correct."));
+
+    // only the exact highlighting is left to the backend
+    let results = platform.list_resources_text("content", Some(("**", "**"))).await?;
+    assert_eq!(
+        results.iter()
+            .map(|brief| brief.resource_path.as_str())
+            .collect::<Vec<_>>(),
+        ["/resource/1"],
+    );
+    assert!(results[0].brief.as_deref()
+        .is_some_and(|brief| brief.contains("**")));
+    let results = platform.list_resources_text("title", None).await?;
+    let mut paths = results.iter()
+        .map(|brief| brief.resource_path.as_str())
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert_eq!(paths, ["/resource/1", "/resource/2"]);
+    assert!(platform.list_resources_text("missing", None).await?.is_empty());
+
+    platform.forget_resource_text("/resource/1").await?;
+    assert!(platform.list_resources_text("content", None).await?.is_empty());
+    assert_eq!(platform.get_resource_brief("/resource/1").await?, None);
+
+    Ok(())
+}
+
+pub async fn index_cache<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    platform.resource_link_kind_with_terms("/resource/1", "keyword", &mut [
+        "hello",
+    ].into_iter()).await?;
+    assert!(platform.get_cached_resource_kinded_terms("/resource/1").await?.is_none());
+
+    let caching = platform.cache_resource_kinded_terms("/resource/1").await?;
+    let cached = platform.get_cached_resource_kinded_terms("/resource/1").await?
+        .expect("the kinded terms were cached");
+    assert_eq!(caching.resource_path, cached.resource_path);
+    assert_eq!(caching.data, cached.data);
+    assert_eq!(cached.data, platform.get_resource_kinded_terms("/resource/1").await?.data);
+
+    // the cache is not invalidated by the underlying index
+    platform.forget_resource_path(None, "/resource/1").await?;
+    assert_eq!(
+        platform.get_cached_resource_kinded_terms("/resource/1").await?
+            .map(|kinded_terms| kinded_terms.data),
+        Some(cached.data),
+    );
+
+    // unknown resources may also be cached
+    assert!(platform.cache_resource_kinded_terms("/resource/2").await?.data.is_empty());
+    assert!(platform.get_cached_resource_kinded_terms("/resource/2").await?.is_some());
+
+    platform.uncache_resource_kinded_terms("/resource/1").await?;
+    assert!(platform.get_cached_resource_kinded_terms("/resource/1").await?.is_none());
+    assert!(platform.get_cached_resource_kinded_terms("/resource/2").await?.is_some());
+    platform.uncache_all_resource_kinded_terms().await?;
+    assert!(platform.get_cached_resource_kinded_terms("/resource/2").await?.is_none());
+
+    Ok(())
+}
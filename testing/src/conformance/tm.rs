@@ -0,0 +1,515 @@
+//! Conformance checks for the backends composing the `TMPlatform`.
+
+use pmrcore::{
+    error::task::TaskError,
+    platform::{
+        ConnectorOption,
+        PlatformConnector,
+        TMPlatform,
+    },
+    task::{
+        EXIT_ABORTED,
+        EXIT_DEPENDENCY_FAILED,
+        Task,
+        TaskArg,
+        TaskFailure,
+        TaskState,
+        TaskStatus,
+        traits::TaskBackend,
+    },
+    task_template::{
+        TaskSandbox,
+        TaskTemplate,
+        traits::TaskTemplateBackend,
+    },
+};
+
+use crate::chrono::set_timestamp;
+
+async fn connect<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<impl TMPlatform> {
+    C::tm(opts)
+        .await
+        .map_err(anyhow::Error::from_boxed)
+}
+
+async fn add_arg(
+    platform: &impl TMPlatform,
+    task_template_id: i64,
+    flag: &str,
+    choice_source: Option<&str>,
+) -> anyhow::Result<i64> {
+    Ok(platform.add_task_template_arg(
+        task_template_id,
+        Some(flag),
+        false,
+        false,
+        None,
+        None,
+        choice_source.is_some(),
+        choice_source,
+        false,
+        None,
+    ).await?)
+}
+
+pub async fn task_template<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+
+    let (id, _) = platform.add_task_template("/bin/demo", "1.0.0").await?;
+    let template = platform.get_task_template_by_id(id).await?;
+    assert_eq!(template.id, id);
+    assert_eq!(template.bin_path, "/bin/demo");
+    assert_eq!(template.version_id, "1.0.0");
+    assert_eq!(template.final_task_template_arg_id, None);
+    assert_eq!(template.superceded_by_id, None);
+    assert!(platform.get_task_template_by_id(id + 1).await.is_err());
+
+    let arg1 = add_arg(&platform, id, "-a", Some("")).await?;
+    let choice1 = platform.add_task_template_arg_choice(arg1, Some("x"), "X").await?;
+    let choice2 = platform.add_task_template_arg_choice(arg1, None, "none").await?;
+    let arg2 = add_arg(&platform, id, "-b", None).await?;
+    assert!(arg1 < arg2);
+
+    let arg = platform.get_task_template_arg_by_id(arg1).await?
+        .expect("the argument was added");
+    assert_eq!(arg.id, arg1);
+    assert_eq!(arg.task_template_id, id);
+    assert_eq!(arg.flag.as_deref(), Some("-a"));
+    assert_eq!(
+        arg.choices
+            .expect("choices are provided with a choice_source")
+            .iter()
+            .map(|choice| (choice.id, choice.to_arg.as_deref(), choice.label.as_str()))
+            .collect::<Vec<_>>(),
+        [(choice1, Some("x"), "X"), (choice2, None, "none")],
+    );
+    assert_eq!(platform.get_task_template_arg_by_id(arg2 + 1).await?, None);
+    assert_eq!(platform.get_task_template_by_arg_id(arg2).await?.id, id);
+    assert!(platform.get_task_template_by_arg_id(arg2 + 1).await.is_err());
+
+    let deleted = platform.delete_task_template_arg_choice_by_id(choice2).await?
+        .expect("the choice was deleted");
+    assert_eq!(deleted.id, choice2);
+    assert_eq!(platform.delete_task_template_arg_choice_by_id(choice2).await?, None);
+
+    // arguments may be removed while the template remains open
+    let deleted = platform.delete_task_template_arg_by_id(arg2).await?
+        .expect("the argument was deleted");
+    assert_eq!(deleted.id, arg2);
+    assert_eq!(platform.delete_task_template_arg_by_id(arg2).await?, None);
+    assert_eq!(platform.get_task_template_arg_by_id(arg2).await?, None);
+
+    let arg3 = add_arg(&platform, id, "-c", None).await?;
+    assert_eq!(platform.finalize_new_task_template(id).await?, Some(arg3));
+    // finalizing again does not change the final argument
+    assert_eq!(platform.finalize_new_task_template(id).await?, Some(arg3));
+
+    // arguments for finalized templates cannot be deleted
+    assert_eq!(platform.delete_task_template_arg_by_id(arg1).await?, None);
+    assert!(platform.get_task_template_arg_by_id(arg1).await?.is_some());
+
+    // arguments added after finalization are not part of the template
+    add_arg(&platform, id, "-d", None).await?;
+    let template = platform.get_task_template_by_id(id).await?;
+    assert_eq!(template.final_task_template_arg_id, Some(arg3));
+    assert_eq!(
+        template.args
+            .expect("args are provided")
+            .iter()
+            .map(|arg| (arg.id, arg.flag.as_deref()))
+            .collect::<Vec<_>>(),
+        [(arg1, Some("-a")), (arg3, Some("-c"))],
+    );
+
+    // a template without arguments may also be finalized
+    let (empty_id, _) = platform.add_task_template("/bin/true", "1.0.0").await?;
+    assert!(platform.finalize_new_task_template(empty_id).await?.is_some());
+    let template = platform.get_task_template_by_id(empty_id).await?;
+    assert!(template.final_task_template_arg_id.is_some());
+    assert!(template.args.expect("args are provided").is_empty());
+    assert_eq!(template.timeout, None);
+
+    // the timeout may be changed after finalization
+    assert!(platform.set_task_template_timeout(empty_id, Some(30)).await?);
+    assert_eq!(platform.get_task_template_by_id(empty_id).await?.timeout, Some(30));
+    assert!(platform.set_task_template_timeout(empty_id, None).await?);
+    assert_eq!(platform.get_task_template_by_id(empty_id).await?.timeout, None);
+    assert!(!platform.set_task_template_timeout(empty_id + 1, Some(30)).await?);
+
+    // the timeout is also provided with the complete structure
+    let added = platform.adds_task_template(TaskTemplate {
+        id: 0,
+        bin_path: "/bin/sleep".into(),
+        version_id: "1.0.0".into(),
+        created_ts: 0,
+        final_task_template_arg_id: None,
+        superceded_by_id: None,
+        timeout: Some(5),
+        sandbox: None,
+        args: None,
+    }).await?;
+    assert_eq!(added.timeout, Some(5));
+    assert_eq!(platform.get_task_template_by_id(added.id).await?.timeout, Some(5));
+
+    // likewise for the sandbox
+    let sandbox = TaskSandbox {
+        cpu_time: Some(10),
+        memory: Some(1 << 30),
+        output_size: None,
+        isolate: true,
+        seccomp: false,
+    };
+    assert_eq!(platform.get_task_template_by_id(empty_id).await?.sandbox, None);
+    assert!(platform.set_task_template_sandbox(empty_id, Some(&sandbox)).await?);
+    assert_eq!(
+        platform.get_task_template_by_id(empty_id).await?.sandbox.as_ref(),
+        Some(&sandbox),
+    );
+    assert!(platform.set_task_template_sandbox(empty_id, None).await?);
+    assert_eq!(platform.get_task_template_by_id(empty_id).await?.sandbox, None);
+    assert!(!platform.set_task_template_sandbox(added.id + 1, Some(&sandbox)).await?);
+
+    let added = platform.adds_task_template(TaskTemplate {
+        id: 0,
+        bin_path: "/bin/sleep".into(),
+        version_id: "1.0.0".into(),
+        created_ts: 0,
+        final_task_template_arg_id: None,
+        superceded_by_id: None,
+        timeout: None,
+        sandbox: Some(sandbox.clone()),
+        args: None,
+    }).await?;
+    assert_eq!(added.sandbox.as_ref(), Some(&sandbox));
+    assert_eq!(
+        platform.get_task_template_by_id(added.id).await?.sandbox,
+        Some(sandbox),
+    );
+
+    Ok(())
+}
+
+pub async fn task<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let (task_template_id, _) = platform.add_task_template("/bin/demo", "1.0.0").await?;
+    platform.finalize_new_task_template(task_template_id).await?;
+    assert_eq!(TaskBackend::start(&platform).await?, None);
+
+    let new_task = |args: &[&str]| Task {
+        task_template_id,
+        bin_path: "/bin/demo".into(),
+        basedir: "/tmp".into(),
+        args: Some(args.iter()
+            .map(|arg| TaskArg {
+                arg: arg.to_string(),
+                .. Default::default()
+            })
+            .collect::<Vec<_>>()
+            .into()),
+        .. Default::default()
+    };
+
+    let task1 = platform.adds_task(new_task(&["-a", "first"])).await?;
+    let task2 = platform.adds_task(new_task(&["-a", "second"])).await?;
+    assert!(task1.id < task2.id);
+    assert_eq!(
+        task1.args
+            .as_ref()
+            .expect("args were provided")
+            .iter()
+            .map(|arg| (arg.task_id, arg.arg.as_str()))
+            .collect::<Vec<_>>(),
+        [(task1.id, "-a"), (task1.id, "first")],
+    );
+    // a task that has been added cannot be added again
+    assert!(matches!(
+        platform.adds_task(task1.clone()).await,
+        Err(TaskError::TaskAlreadyQueued(id)) if id == task1.id,
+    ));
+
+    let task = platform.gets_task(task1.id).await?;
+    assert_eq!(task.task_template_id, task_template_id);
+    assert_eq!(task.start_ts, None);
+    assert_eq!(task.args, task1.args);
+    assert!(platform.gets_task(task2.id + 1).await.is_err());
+
+    // tasks are started in the order they were added
+    let started = TaskBackend::start(&platform).await?
+        .expect("a task was queued");
+    assert_eq!(started.id, task1.id);
+    assert!(started.start_ts.is_some());
+    assert_eq!(started.args, task1.args);
+    let started = TaskBackend::start(&platform).await?
+        .expect("a task was queued");
+    assert_eq!(started.id, task2.id);
+    assert_eq!(TaskBackend::start(&platform).await?, None);
+
+    assert!(platform.run(task1.id, 123, "localhost").await?);
+    assert!(!platform.run(task2.id + 1, 123, "localhost").await?);
+    let task = platform.gets_task(task1.id).await?;
+    assert_eq!(task.pid, Some(123));
+    assert_eq!(task.host.as_deref(), Some("localhost"));
+    assert_eq!(task.stop_ts, None);
+    assert_eq!(task.exit_status, None);
+
+    assert!(platform.complete(task1.id, 0).await?);
+    assert!(!platform.complete(task2.id + 1, 0).await?);
+    let task = platform.gets_task(task1.id).await?;
+    assert!(task.stop_ts.is_some());
+    assert_eq!(task.exit_status, Some(0));
+
+    Ok(())
+}
+
+pub async fn task_running<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let (task_template_id, _) = platform.add_task_template("/bin/demo", "1.0.0").await?;
+    platform.finalize_new_task_template(task_template_id).await?;
+    let new_task = || Task {
+        task_template_id,
+        bin_path: "/bin/demo".into(),
+        basedir: "/tmp".into(),
+        args: Some(vec![].into()),
+        .. Default::default()
+    };
+
+    let task1 = platform.adds_task(new_task()).await?;
+    let task2 = platform.adds_task(new_task()).await?;
+    assert_eq!(platform.list_running().await?, []);
+
+    // only tasks with a pid are running
+    TaskBackend::start(&platform).await?
+        .expect("a task was queued");
+    TaskBackend::start(&platform).await?
+        .expect("a task was queued");
+    assert!(platform.run(task1.id, 123, "localhost").await?);
+    let running = platform.list_running().await?;
+    assert_eq!(
+        running.iter()
+            .map(|task| (task.id, task.pid))
+            .collect::<Vec<_>>(),
+        [(task1.id, Some(123))],
+    );
+    assert_eq!(running[0].args, None);
+
+    // a requeued task is no longer running and will be started again
+    assert!(platform.requeue(task1.id).await?);
+    assert_eq!(platform.list_running().await?, []);
+    let task = platform.gets_task(task1.id).await?;
+    assert_eq!(task.start_ts, None);
+    assert_eq!(task.pid, None);
+    assert_eq!(task.host, None);
+    let started = TaskBackend::start(&platform).await?
+        .expect("the task was requeued");
+    assert_eq!(started.id, task1.id);
+    assert_eq!(TaskBackend::start(&platform).await?, None);
+
+    // completed tasks are neither running nor may be requeued
+    assert!(platform.run(task1.id, 456, "localhost").await?);
+    assert!(platform.run(task2.id, 789, "localhost").await?);
+    assert!(platform.complete(task1.id, 0).await?);
+    assert_eq!(
+        platform.list_running().await?
+            .iter()
+            .map(|task| task.id)
+            .collect::<Vec<_>>(),
+        [task2.id],
+    );
+    assert!(!platform.requeue(task1.id).await?);
+    assert!(!platform.requeue(task2.id + 1).await?);
+    assert_eq!(platform.gets_task(task1.id).await?.exit_status, Some(0));
+
+    Ok(())
+}
+
+pub async fn task_schedule<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let (task_template_id, _) = platform.add_task_template("/bin/demo", "1.0.0").await?;
+    platform.finalize_new_task_template(task_template_id).await?;
+    let new_task = |priority, max_retries, retry_delay, depends_on: &[i64]| Task {
+        task_template_id,
+        bin_path: "/bin/demo".into(),
+        basedir: "/tmp".into(),
+        priority,
+        max_retries,
+        retry_delay,
+        depends_on: depends_on.to_vec(),
+        args: Some(vec![].into()),
+        .. Default::default()
+    };
+    let start = async || -> anyhow::Result<Option<i64>> {
+        Ok(TaskBackend::start(&platform).await?.map(|task| task.id))
+    };
+    set_timestamp(1000000000);
+
+    // tasks with a higher priority are started first, otherwise in the
+    // order they were added
+    let bulk = platform.adds_task(new_task(-10, 0, 0, &[])).await?;
+    let task1 = platform.adds_task(new_task(0, 0, 0, &[])).await?;
+    let task2 = platform.adds_task(new_task(0, 0, 0, &[])).await?;
+    let urgent = platform.adds_task(new_task(5, 0, 0, &[])).await?;
+    assert_eq!(urgent.priority, 5);
+    assert_eq!(platform.gets_task(bulk.id).await?.priority, -10);
+    assert_eq!(start().await?, Some(urgent.id));
+    assert_eq!(start().await?, Some(task1.id));
+    assert_eq!(start().await?, Some(task2.id));
+    assert_eq!(start().await?, Some(bulk.id));
+    assert_eq!(start().await?, None);
+    for task in [&bulk, &task1, &task2, &urgent] {
+        assert!(platform.complete(task.id, 0).await?);
+    }
+
+    // a failed task is retried after the delay, doubling every retry
+    let retried = platform.adds_task(new_task(0, 2, 60, &[])).await?;
+    assert_eq!(start().await?, Some(retried.id));
+    assert!(platform.complete(retried.id, 1).await?);
+    let task = platform.gets_task(retried.id).await?;
+    assert_eq!(task.attempts, 1);
+    assert_eq!(task.start_ts, None);
+    assert_eq!(task.stop_ts, None);
+    assert_eq!(task.exit_status, Some(1));
+    assert_eq!(task.retry_ts, Some(1000000060));
+    assert_eq!(start().await?, None);
+    set_timestamp(1000000060);
+    assert_eq!(start().await?, Some(retried.id));
+    assert!(platform.complete(retried.id, 1).await?);
+    assert_eq!(platform.gets_task(retried.id).await?.retry_ts, Some(1000000180));
+    set_timestamp(1000000180);
+    assert_eq!(start().await?, Some(retried.id));
+    // no more retries remaining, so the failure is final
+    assert!(platform.complete(retried.id, 2).await?);
+    let task = platform.gets_task(retried.id).await?;
+    assert_eq!(task.attempts, 3);
+    assert!(task.stop_ts.is_some());
+    assert_eq!(task.failure(), Some(TaskFailure::Exit(2)));
+    assert_eq!(start().await?, None);
+
+    // a task is only started after its dependencies have succeeded,
+    // even where it has the higher priority
+    let first = platform.adds_task(new_task(0, 0, 0, &[])).await?;
+    let second = platform.adds_task(new_task(1, 0, 0, &[first.id])).await?;
+    assert_eq!(second.depends_on, [first.id]);
+    assert_eq!(platform.gets_task(second.id).await?.depends_on, [first.id]);
+    assert_eq!(start().await?, Some(first.id));
+    assert_eq!(start().await?, None);
+    assert!(platform.complete(first.id, 0).await?);
+    assert_eq!(start().await?, Some(second.id));
+    assert!(platform.complete(second.id, 0).await?);
+
+    // the failure of a dependency fails all its pending dependents
+    let first = platform.adds_task(new_task(0, 0, 0, &[])).await?;
+    let second = platform.adds_task(new_task(0, 0, 0, &[first.id])).await?;
+    let third = platform.adds_task(new_task(0, 0, 0, &[second.id])).await?;
+    let other = platform.adds_task(new_task(-1, 0, 0, &[])).await?;
+    assert_eq!(start().await?, Some(first.id));
+    assert!(platform.complete(first.id, 1).await?);
+    for task in [&second, &third] {
+        let task = platform.gets_task(task.id).await?;
+        assert!(task.stop_ts.is_some());
+        assert_eq!(task.exit_status, Some(EXIT_DEPENDENCY_FAILED.into()));
+        assert_eq!(task.failure(), Some(TaskFailure::DependencyFailed));
+    }
+    assert_eq!(start().await?, Some(other.id));
+    assert_eq!(start().await?, None);
+
+    // while a dependency is pending a retry its dependents must wait
+    let first = platform.adds_task(new_task(0, 1, 0, &[])).await?;
+    let second = platform.adds_task(new_task(0, 0, 0, &[first.id])).await?;
+    assert_eq!(start().await?, Some(first.id));
+    assert!(platform.complete(first.id, 1).await?);
+    assert_eq!(platform.gets_task(second.id).await?.stop_ts, None);
+    assert_eq!(start().await?, Some(first.id));
+    assert!(platform.complete(first.id, 0).await?);
+    assert_eq!(start().await?, Some(second.id));
+    assert!(platform.complete(second.id, 0).await?);
+
+    // the failures reported by the executor are final despite retries
+    let aborted = platform.adds_task(new_task(0, 2, 0, &[])).await?;
+    assert_eq!(start().await?, Some(aborted.id));
+    assert!(platform.complete(aborted.id, EXIT_ABORTED.into()).await?);
+    let task = platform.gets_task(aborted.id).await?;
+    assert_eq!(task.attempts, 1);
+    assert!(task.stop_ts.is_some());
+    assert_eq!(task.failure(), Some(TaskFailure::Aborted));
+    assert_eq!(start().await?, None);
+
+    // a task added after its dependency had failed will never start
+    let dependent = platform.adds_task(new_task(0, 0, 0, &[aborted.id])).await?;
+    assert_eq!(dependent.exit_status, Some(EXIT_DEPENDENCY_FAILED.into()));
+    let task = platform.gets_task(dependent.id).await?;
+    assert!(task.stop_ts.is_some());
+    assert_eq!(task.failure(), Some(TaskFailure::DependencyFailed));
+    assert_eq!(start().await?, None);
+
+    Ok(())
+}
+
+pub async fn task_status<C: PlatformConnector>(
+    opts: ConnectorOption,
+) -> anyhow::Result<()> {
+    let platform = connect::<C>(opts).await?;
+    let (task_template_id, _) = platform.add_task_template("/bin/demo", "1.0.0").await?;
+    platform.finalize_new_task_template(task_template_id).await?;
+    let new_task = |max_retries| Task {
+        task_template_id,
+        bin_path: "/bin/demo".into(),
+        basedir: "/tmp".into(),
+        max_retries,
+        args: Some(vec![].into()),
+        .. Default::default()
+    };
+    let list_by_state = async |state| -> anyhow::Result<Vec<i64>> {
+        Ok(platform.list_by_state(state).await?
+            .into_iter()
+            .map(|task| task.id)
+            .collect())
+    };
+    set_timestamp(1000000000);
+
+    let succeeded = platform.adds_task(new_task(0)).await?;
+    let failed = platform.adds_task(new_task(0)).await?;
+    let retried = platform.adds_task(new_task(1)).await?;
+    let running = platform.adds_task(new_task(0)).await?;
+    let queued = platform.adds_task(new_task(0)).await?;
+    for _ in 0..4 {
+        TaskBackend::start(&platform).await?;
+    }
+    assert!(platform.complete(succeeded.id, 0).await?);
+    assert!(platform.complete(failed.id, 1).await?);
+    assert!(platform.complete(retried.id, 1).await?);
+
+    // a task pending a retry is queued again
+    assert_eq!(list_by_state(TaskState::Queued).await?, [retried.id, queued.id]);
+    assert_eq!(list_by_state(TaskState::Running).await?, [running.id]);
+    assert_eq!(list_by_state(TaskState::Succeeded).await?, [succeeded.id]);
+    assert_eq!(list_by_state(TaskState::Failed).await?, [failed.id]);
+
+    let tasks = platform.list_by_ids(&[queued.id, failed.id, queued.id + 1]).await?;
+    assert_eq!(
+        tasks.iter()
+            .map(|task| (task.id, task.state()))
+            .collect::<Vec<_>>(),
+        [
+            (failed.id, TaskState::Failed),
+            (queued.id, TaskState::Queued),
+        ],
+    );
+    let status = TaskStatus::from(&tasks[0]);
+    assert_eq!(status.state, TaskState::Failed);
+    assert_eq!(status.failure, Some(TaskFailure::Exit(1)));
+    assert_eq!(status.start_ts, Some(1000000000));
+    assert_eq!(TaskStatus::from(&tasks[1]).failure, None);
+    assert_eq!(platform.list_by_ids(&[]).await?, []);
+
+    Ok(())
+}
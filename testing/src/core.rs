@@ -31,7 +31,9 @@ use pmrcore::{
             ExposureFileViewBackend,
         },
         task::{
+            ExposureFileViewBuild,
             ExposureFileViewTask,
+            ExposureFileViewTaskStatus,
             traits::{
                 ExposureTaskBackend,
                 ExposureTaskTemplateBackend,
@@ -52,6 +54,10 @@ use pmrcore::{
         ResourceBrief,
         ResourceKindedTerms,
     },
+    listing::{
+        ListingQuery,
+        Page,
+    },
     platform::{
         DefaultMCPlatform,
         DefaultPCPlatform,
@@ -61,9 +67,11 @@ use pmrcore::{
     },
     task::{
         Task,
+        TaskState,
         traits::TaskBackend,
     },
     task_template::{
+        TaskSandbox,
         TaskTemplate,
         TaskTemplateArg,
         TaskTemplateArgChoice,
@@ -111,9 +119,18 @@ mock! {
             long_description: Option<&'a str>,
         ) -> Result<bool, BackendError>;
         pub async fn workspace_list_workspaces(&self) -> Result<Workspaces, BackendError>;
+        pub async fn workspace_list_workspaces_page(&self, query: &ListingQuery) -> Result<Page<Workspace>, BackendError>;
         pub async fn workspace_get_workspace_by_id(&self, id: i64) -> Result<Workspace, BackendError>;
         pub async fn workspace_list_workspace_by_url(&self, url: &str) -> Result<Workspaces, BackendError>;
         pub async fn workspace_list_workspace_by_ids(&self, ids: &[i64]) -> Result<Workspaces, BackendError>;
+        pub async fn workspace_set_workspace_superceded_by(
+            &self,
+            id: i64,
+            superceded_by_id: Option<i64>,
+        ) -> Result<bool, BackendError>;
+        pub async fn workspace_archive_workspace(&self, id: i64) -> Result<bool, BackendError>;
+        pub async fn workspace_restore_workspace(&self, id: i64) -> Result<bool, BackendError>;
+        pub async fn workspace_list_archived_workspaces(&self) -> Result<Workspaces, BackendError>;
 
         pub async fn exposure_insert<'a>(
             &self,
@@ -126,6 +143,10 @@ mock! {
         pub async fn exposure_list(
             &self,
         ) -> Result<Exposures, BackendError>;
+        pub async fn exposure_list_page(
+            &self,
+            query: &ListingQuery,
+        ) -> Result<Page<Exposure>, BackendError>;
         pub async fn exposure_list_by_ids(
             &self,
             ids: &[i64],
@@ -193,6 +214,33 @@ mock! {
             exposure_file_id: i64,
             view_key: &str,
         ) -> Result<ExposureFileView, BackendError>;
+        pub async fn view_task_template_insert_view_task_template(
+            &self,
+            view_key: &str,
+            description: &str,
+            task_template_id: i64,
+        ) -> Result<i64, BackendError>;
+        pub async fn view_task_template_update_view_task_template_by_fields(
+            &self,
+            id: i64,
+            view_key: &str,
+            description: &str,
+            task_template_id: i64,
+        ) -> Result<bool, BackendError>;
+        pub async fn view_task_template_select_view_task_template_by_id(
+            &self,
+            id: i64,
+        ) -> Result<ViewTaskTemplate, BackendError>;
+        pub async fn view_task_template_set_view_task_template_depends_on<'a>(
+            &'a self,
+            id: i64,
+            depends_on: Option<&'a str>,
+        ) -> Result<bool, BackendError>;
+        pub async fn view_task_template_migrate_view_task_templates(
+            &self,
+            task_template_id: i64,
+            superceded_by_id: i64,
+        ) -> Result<Vec<i64>, BackendError>;
         pub async fn exposure_file_view_update_view_key<'a>(
             &'a self,
             id: i64,
@@ -247,6 +295,7 @@ mock! {
         async fn begin_sync(&self, workspace_id: i64) -> Result<i64, BackendError>;
         async fn complete_sync(&self, id: i64, status: WorkspaceSyncStatus) -> Result<bool, BackendError>;
         async fn get_workspaces_sync_records(&self, workspace_id: i64) -> Result<Vec<WorkspaceSync>, BackendError>;
+        async fn list_latest_sync_records(&self, limit: i64) -> Result<Vec<WorkspaceSync>, BackendError>;
     }
 
     #[async_trait]
@@ -257,6 +306,8 @@ mock! {
         async fn resolve_alias(&self, kind: &str, alias: &str) -> Result<Option<i64>, BackendError>;
         async fn aliases_by_kind(&self, kind: &str) -> Result<Vec<(String, i64)>, BackendError>;
         async fn aliases_by_kind_ids(&self, kind: &str, ids: &[i64]) -> Result<Vec<(String, i64)>, BackendError>;
+        async fn reassign_aliases(&self, kind: &str, kind_id: i64, new_kind_id: i64) -> Result<u64, BackendError>;
+        async fn remove_aliases(&self, kind: &str, kind_id: i64) -> Result<u64, BackendError>;
     }
 
     #[async_trait]
@@ -288,27 +339,6 @@ mock! {
         // This may be implemented at the backends for the linked types.
     }
 
-    #[async_trait]
-    impl ViewTaskTemplateBackend for Platform {
-        async fn insert_view_task_template(
-            &self,
-            view_key: &str,
-            description: &str,
-            task_template_id: i64,
-        ) -> Result<i64, BackendError>;
-        async fn update_view_task_template_by_fields(
-            &self,
-            id: i64,
-            view_key: &str,
-            description: &str,
-            task_template_id: i64,
-        ) -> Result<bool, BackendError>;
-        async fn select_view_task_template_by_id(
-            &self,
-            id: i64,
-        ) -> Result<ViewTaskTemplate, BackendError>;
-    }
-
     #[async_trait]
     impl ProfileViewsBackend for Platform {
         // TODO determine if exposing these low level records are necessary.
@@ -356,6 +386,14 @@ mock! {
             &self,
             task_id: i64,
         ) -> Result<Option<(i64, Option<String>)>, Error>;
+        async fn list_view_tasks_for_exposure(
+            &self,
+            exposure_id: i64,
+        ) -> Result<Vec<ExposureFileViewTaskStatus>, BackendError>;
+        async fn list_view_builds(
+            &self,
+            task_template_id: Option<i64>,
+        ) -> Result<Vec<ExposureFileViewBuild>, BackendError>;
     }
 
     #[async_trait]
@@ -375,12 +413,37 @@ mock! {
             &self,
             id: i64,
             pid: i64,
+            host: &str,
         ) -> Result<bool, BackendError>;
         async fn complete(
             &self,
             id: i64,
             exit_status: i64,
         ) -> Result<bool, BackendError>;
+        async fn list_running(
+            &self,
+        ) -> Result<Vec<Task>, BackendError>;
+        async fn list_by_state(
+            &self,
+            state: TaskState,
+        ) -> Result<Vec<Task>, BackendError>;
+        async fn list_by_ids(
+            &self,
+            ids: &[i64],
+        ) -> Result<Vec<Task>, BackendError>;
+        async fn lease(
+            &self,
+            id: i64,
+            host: &str,
+            duration: i64,
+        ) -> Result<Option<i64>, BackendError>;
+        async fn requeue_expired_leases(
+            &self,
+        ) -> Result<Vec<i64>, BackendError>;
+        async fn requeue(
+            &self,
+            id: i64,
+        ) -> Result<bool, BackendError>;
     }
 
     #[async_trait]
@@ -441,6 +504,9 @@ impl WorkspaceBackend for MockPlatform {
     async fn list_workspaces(&self) -> Result<Workspaces, BackendError> {
         self.workspace_list_workspaces().await
     }
+    async fn list_workspaces_page(&self, query: &ListingQuery) -> Result<Page<Workspace>, BackendError> {
+        self.workspace_list_workspaces_page(query).await
+    }
     async fn get_workspace_by_id(&self, id: i64) -> Result<Workspace, BackendError> {
         self.workspace_get_workspace_by_id(id).await
     }
@@ -450,6 +516,20 @@ impl WorkspaceBackend for MockPlatform {
     async fn list_workspace_by_ids(&self, ids: &[i64]) -> Result<Workspaces, BackendError> {
         self.workspace_list_workspace_by_ids(ids).await
     }
+    async fn set_workspace_superceded_by(
+        &self, id: i64, superceded_by_id: Option<i64>,
+    ) -> Result<bool, BackendError> {
+        self.workspace_set_workspace_superceded_by(id, superceded_by_id).await
+    }
+    async fn archive_workspace(&self, id: i64) -> Result<bool, BackendError> {
+        self.workspace_archive_workspace(id).await
+    }
+    async fn restore_workspace(&self, id: i64) -> Result<bool, BackendError> {
+        self.workspace_restore_workspace(id).await
+    }
+    async fn list_archived_workspaces(&self) -> Result<Workspaces, BackendError> {
+        self.workspace_list_archived_workspaces().await
+    }
 }
 
 #[async_trait]
@@ -469,6 +549,12 @@ impl ExposureBackend for MockPlatform {
     ) -> Result<Exposures, BackendError> {
         self.exposure_list().await
     }
+    async fn list_page(
+        &self,
+        query: &ListingQuery,
+    ) -> Result<Page<Exposure>, BackendError> {
+        self.exposure_list_page(query).await
+    }
     async fn list_by_ids(
         &self,
         ids: &[i64],
@@ -627,6 +713,59 @@ impl ExposureTaskTemplateBackend for MockPlatform {
     }
 }
 
+#[async_trait]
+impl ViewTaskTemplateBackend for MockPlatform {
+    async fn insert_view_task_template(
+        &self,
+        view_key: &str,
+        description: &str,
+        task_template_id: i64,
+    ) -> Result<i64, BackendError> {
+        self.view_task_template_insert_view_task_template(
+            view_key,
+            description,
+            task_template_id,
+        ).await
+    }
+    async fn update_view_task_template_by_fields(
+        &self,
+        id: i64,
+        view_key: &str,
+        description: &str,
+        task_template_id: i64,
+    ) -> Result<bool, BackendError> {
+        self.view_task_template_update_view_task_template_by_fields(
+            id,
+            view_key,
+            description,
+            task_template_id,
+        ).await
+    }
+    async fn select_view_task_template_by_id(
+        &self,
+        id: i64,
+    ) -> Result<ViewTaskTemplate, BackendError> {
+        self.view_task_template_select_view_task_template_by_id(id).await
+    }
+    async fn set_view_task_template_depends_on(
+        &self,
+        id: i64,
+        depends_on: Option<&str>,
+    ) -> Result<bool, BackendError> {
+        self.view_task_template_set_view_task_template_depends_on(id, depends_on).await
+    }
+    async fn migrate_view_task_templates(
+        &self,
+        task_template_id: i64,
+        superceded_by_id: i64,
+    ) -> Result<Vec<i64>, BackendError> {
+        self.view_task_template_migrate_view_task_templates(
+            task_template_id,
+            superceded_by_id,
+        ).await
+    }
+}
+
 #[async_trait]
 impl TaskTemplateBackend for MockPlatform {
     async fn add_task_template(
@@ -652,6 +791,8 @@ impl TaskTemplateBackend for MockPlatform {
         _default: Option<&str>,
         _choice_fixed: bool,
         _choice_source: Option<&str>,
+        _multiple: bool,
+        _multiple_separator: Option<&str>,
     ) -> Result<i64, BackendError> {
         unimplemented!()
     }
@@ -693,6 +834,27 @@ impl TaskTemplateBackend for MockPlatform {
     ) -> Result<TaskTemplate, BackendError> {
         unimplemented!()
     }
+    async fn set_task_template_timeout(
+        &self,
+        _id: i64,
+        _timeout: Option<i64>,
+    ) -> Result<bool, BackendError> {
+        unimplemented!()
+    }
+    async fn set_task_template_sandbox(
+        &self,
+        _id: i64,
+        _sandbox: Option<&TaskSandbox>,
+    ) -> Result<bool, BackendError> {
+        unimplemented!()
+    }
+    async fn supersede_task_template(
+        &self,
+        _id: i64,
+        _superceded_by_id: i64,
+    ) -> Result<bool, BackendError> {
+        unimplemented!()
+    }
 }
 
 // Can't easily be mocked due to multiple implied &'# str in signature.
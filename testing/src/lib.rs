@@ -2,9 +2,13 @@
 pub mod ac;
 #[cfg(feature = "chrono")]
 pub mod chrono;
+#[cfg(feature = "conformance")]
+pub mod conformance;
 pub mod core;
 #[cfg(feature = "platform")]
 pub mod ctrl;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 #[cfg(feature = "rand")]
 pub mod rand;
 #[cfg(feature = "repo")]
@@ -13,4 +17,7 @@ pub mod repo;
 mod utils;
 pub use utils::*;
 pub use test_case::test_case;
-pub use test_pmr_macros::sqlite_pcb_cache_test_case;
+pub use test_pmr_macros::{
+    postgres_pcb_cache_test_case,
+    sqlite_pcb_cache_test_case,
+};
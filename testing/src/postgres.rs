@@ -0,0 +1,189 @@
+//! A throwaway PostgreSQL cluster for tests.
+//!
+//! The cluster is created using `initdb` under a temporary directory on
+//! first use, listening only on a unix socket inside that directory, and
+//! is shared by all tests within the same test binary.  Each call to
+//! [`create_database`] will create a new database within the cluster so
+//! that every test gets a clean slate.  The cluster is stopped and its
+//! directory removed when the test process exits.
+//!
+//! The binaries are located using the `PG_BINDIR` environment variable,
+//! followed by `pg_config --bindir`, falling back to `PATH`.  Should the
+//! tests be executed as root, the cluster will be run as the `postgres`
+//! user as the server refuses to run as root.
+
+use std::{
+    os::unix::{
+        fs::MetadataExt,
+        process::CommandExt,
+    },
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+static CLUSTER: OnceLock<Cluster> = OnceLock::new();
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+const PORT: u16 = 5432;
+
+struct Cluster {
+    root: PathBuf,
+    bindir: Option<PathBuf>,
+    owner: Option<(u32, u32)>,
+}
+
+extern "C" {
+    fn atexit(cb: extern "C" fn()) -> std::os::raw::c_int;
+}
+
+extern "C" fn stop_cluster() {
+    if let Some(cluster) = CLUSTER.get() {
+        cluster.stop();
+    }
+}
+
+fn bindir() -> Option<PathBuf> {
+    if let Some(bindir) = std::env::var_os("PG_BINDIR") {
+        return Some(bindir.into());
+    }
+    Command::new("pg_config")
+        .arg("--bindir")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().into())
+}
+
+fn lookup_id(flag: &str) -> u32 {
+    let output = Command::new("id")
+        .args([flag, "postgres"])
+        .output()
+        .expect("failed to execute `id`");
+    assert!(
+        output.status.success(),
+        "running tests as root requires the `postgres` user to be available",
+    );
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .expect("`id` should have returned a number")
+}
+
+impl Cluster {
+    fn command(&self, program: &str) -> Command {
+        let mut cmd = Command::new(match &self.bindir {
+            Some(bindir) => bindir.join(program),
+            None => program.into(),
+        });
+        if let Some((uid, gid)) = self.owner {
+            cmd.uid(uid).gid(gid);
+        }
+        cmd
+    }
+
+    fn datadir(&self) -> PathBuf {
+        self.root.join("data")
+    }
+
+    fn run(&self, mut cmd: Command) {
+        let output = cmd.output()
+            .unwrap_or_else(|e| panic!("failed to execute {cmd:?}: {e}"));
+        assert!(
+            output.status.success(),
+            "{cmd:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr),
+        );
+    }
+
+    fn start() -> Self {
+        let root = tempfile::Builder::new()
+            .prefix("pmrpg")
+            .tempdir()
+            .expect("failed to create temporary directory")
+            .keep();
+        let owner = (std::fs::metadata(&root)
+            .expect("failed to stat temporary directory")
+            .uid() == 0
+        ).then(|| {
+            let owner = (lookup_id("-u"), lookup_id("-g"));
+            std::os::unix::fs::chown(&root, Some(owner.0), Some(owner.1))
+                .expect("failed to chown temporary directory");
+            owner
+        });
+        let cluster = Self { root, bindir: bindir(), owner };
+
+        let mut cmd = cluster.command("initdb");
+        cmd.arg("-D")
+            .arg(cluster.datadir())
+            .args(["-U", "postgres", "--auth=trust", "--no-sync", "-E", "UTF8"]);
+        cluster.run(cmd);
+
+        let mut cmd = cluster.command("pg_ctl");
+        cmd.arg("-D")
+            .arg(cluster.datadir())
+            .arg("-l")
+            .arg(cluster.root.join("postgres.log"))
+            .arg("-o")
+            .arg(format!(
+                "-c listen_addresses='' -k {} -p {PORT} -F",
+                cluster.root.display(),
+            ))
+            .args(["-w", "start"]);
+        cluster.run(cmd);
+
+        unsafe {
+            atexit(stop_cluster);
+        }
+        cluster
+    }
+
+    fn stop(&self) {
+        let _ = self.command("pg_ctl")
+            .arg("-D")
+            .arg(self.datadir())
+            .args(["-m", "immediate", "-w", "stop"])
+            .output();
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+
+    fn socket_dir(&self) -> &Path {
+        &self.root
+    }
+}
+
+/// Create a new database in the throwaway cluster, returning the
+/// connection string to that database.
+pub fn create_database() -> String {
+    let cluster = CLUSTER.get_or_init(Cluster::start);
+    let name = format!(
+        "pmrtest_{}_{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst),
+    );
+    let mut cmd = cluster.command("createdb");
+    cmd.arg("-h")
+        .arg(cluster.socket_dir())
+        .args(["-p", &PORT.to_string(), "-U", "postgres", &name]);
+    cluster.run(cmd);
+    format!(
+        "postgres://postgres@localhost:{PORT}/{name}?host={}",
+        cluster.socket_dir().display(),
+    )
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn smoke_test_create_database() {
+        let db1 = create_database();
+        let db2 = create_database();
+        assert_ne!(db1, db2);
+    }
+}